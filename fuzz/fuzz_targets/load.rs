@@ -0,0 +1,39 @@
+//! Loads arbitrary CBOR input as a level file.
+//!
+//! The loader must reject malformed files with an error instead of panicking,
+//! and a successfully loaded level must survive a few simulated frames.
+//! The input is plain CBOR without the zstd wrapper of `.osav` files,
+//! so that the fuzzer can mutate the structure directly.
+
+#![no_main]
+
+use std::time::Duration;
+
+use bevy::app::{App, TaskPoolPlugin};
+use bevy::ecs::system::Command;
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use libfuzzer_sys::fuzz_target;
+use omniatc::{level, load, util};
+
+/// Number of frames simulated after a successful load.
+const FRAMES: usize = 10;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(file) = ciborium::from_reader::<store::File, _>(data) else { return };
+
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TimePlugin,
+        level::Plug::<()>::default(),
+        load::Plug,
+        util::Plug,
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+
+    load::Command { source: load::Source::Parsed(Box::new(file)), on_error: Box::new(|_, _| {}) }
+        .apply(app.world_mut());
+    for _ in 0..FRAMES {
+        app.update();
+    }
+});
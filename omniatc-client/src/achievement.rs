@@ -0,0 +1,278 @@
+//! Global achievements tracked across all sessions.
+//!
+//! Unlike quests, achievements are not defined by the scenario.
+//! Each achievement in [`REGISTRY`] is unlocked when its [`Metric`] reaches the target.
+//! Metrics are accumulated from the level statistics of every session
+//! and persisted by the storage module, so progress carries over between scenarios.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Res, ResMut};
+use bevy::time::{self, Time};
+use jiff::Timestamp;
+use omniatc::level::quest::outcome::Outcome;
+use omniatc::level::score::Stats;
+use omniatc::level::{self, clock};
+use omniatc::load;
+use serde::{Deserialize, Serialize};
+
+use crate::UpdateSystemSets;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Achievements>();
+        app.add_systems(
+            app::Update,
+            track_system.in_set(UpdateSystemSets::Simulate).after(level::AllSystemSets),
+        );
+    }
+}
+
+/// A quantity accumulated across sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Total number of arrivals completed.
+    Landings,
+    /// Total number of departures completed.
+    Departures,
+    /// Total number of arrivals completed at night.
+    NightLandings,
+    /// Total number of scenarios completed successfully.
+    ScenariosCompleted,
+    /// Longest game time in minutes controlled without a new conflict in a single session.
+    CleanMinutes,
+}
+
+impl Metric {
+    /// Key of the metric in the persisted progress.
+    fn key(self) -> &'static str {
+        match self {
+            Metric::Landings => "landings",
+            Metric::Departures => "departures",
+            Metric::NightLandings => "night-landings",
+            Metric::ScenariosCompleted => "scenarios-completed",
+            Metric::CleanMinutes => "clean-minutes",
+        }
+    }
+}
+
+/// Definition of an achievement.
+pub struct Definition {
+    /// Persisted identifier of the achievement.
+    pub id:          &'static str,
+    pub title:       &'static str,
+    pub description: &'static str,
+    pub metric:      Metric,
+    /// Value of the metric at which the achievement is unlocked.
+    pub target:      u64,
+}
+
+/// All achievements in display order.
+pub const REGISTRY: &[Definition] = &[
+    Definition {
+        id:          "first-landing",
+        title:       "First touchdown",
+        description: "Land your first aircraft.",
+        metric:      Metric::Landings,
+        target:      1,
+    },
+    Definition {
+        id:          "landings-100",
+        title:       "Centurion",
+        description: "Land 100 aircraft.",
+        metric:      Metric::Landings,
+        target:      100,
+    },
+    Definition {
+        id:          "landings-1000",
+        title:       "Busy tower",
+        description: "Land 1000 aircraft.",
+        metric:      Metric::Landings,
+        target:      1000,
+    },
+    Definition {
+        id:          "departures-100",
+        title:       "Wheels up",
+        description: "Complete 100 departures.",
+        metric:      Metric::Departures,
+        target:      100,
+    },
+    Definition {
+        id:          "night-landings-25",
+        title:       "Night owl",
+        description: "Land 25 aircraft at night.",
+        metric:      Metric::NightLandings,
+        target:      25,
+    },
+    Definition {
+        id:          "clean-hour",
+        title:       "Clean sheet",
+        description: "Control traffic for an hour of game time without a conflict.",
+        metric:      Metric::CleanMinutes,
+        target:      60,
+    },
+    Definition {
+        id:          "scenario-complete",
+        title:       "Mission accomplished",
+        description: "Complete a scenario.",
+        metric:      Metric::ScenariosCompleted,
+        target:      1,
+    },
+    Definition {
+        id:          "scenarios-10",
+        title:       "Veteran",
+        description: "Complete 10 scenarios.",
+        metric:      Metric::ScenariosCompleted,
+        target:      10,
+    },
+];
+
+/// Persisted achievement progress.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Progress {
+    /// Metric values keyed by [`Metric::key`].
+    #[serde(default)]
+    pub metrics:  BTreeMap<String, u64>,
+    /// Unlock time of each unlocked achievement, keyed by [`Definition::id`].
+    #[serde(default)]
+    pub unlocked: BTreeMap<String, Timestamp>,
+}
+
+impl Progress {
+    #[must_use]
+    pub fn get(&self, metric: Metric) -> u64 {
+        self.metrics.get(metric.key()).copied().unwrap_or(0)
+    }
+
+    /// Adds `delta` to a cumulative metric.
+    ///
+    /// Returns whether the metric has changed.
+    pub fn add(&mut self, metric: Metric, delta: u64) -> bool {
+        if delta == 0 {
+            return false;
+        }
+        *self.metrics.entry(metric.key().into()).or_default() += delta;
+        true
+    }
+
+    /// Records a new value for a best-of metric, keeping the maximum.
+    ///
+    /// Returns whether the metric has changed.
+    pub fn record(&mut self, metric: Metric, value: u64) -> bool {
+        if value <= self.get(metric) {
+            return false;
+        }
+        self.metrics.insert(metric.key().into(), value);
+        true
+    }
+
+    /// Unlocks all achievements whose targets have been reached,
+    /// returning the newly unlocked ones.
+    pub fn evaluate(&mut self, now: Timestamp) -> Vec<&'static Definition> {
+        let mut newly_unlocked = Vec::new();
+        for def in REGISTRY {
+            if !self.unlocked.contains_key(def.id) && self.get(def.metric) >= def.target {
+                self.unlocked.insert(def.id.into(), now);
+                newly_unlocked.push(def);
+            }
+        }
+        newly_unlocked
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Achievements {
+    pub progress:       Progress,
+    /// Whether the persisted progress has been loaded.
+    ///
+    /// Session statistics are only accumulated after loading
+    /// so that they are not overwritten by the persisted progress.
+    pub loaded:         bool,
+    /// Whether `progress` has changed since it was last persisted.
+    pub unsaved:        bool,
+    /// Achievements unlocked since the last notification.
+    pub newly_unlocked: Vec<&'static Definition>,
+}
+
+/// Statistics already accumulated from the current session.
+#[derive(Default)]
+struct Session {
+    landings:    u32,
+    departures:  u32,
+    conflicts:   u32,
+    /// Elapsed level time since which no new conflict has occurred.
+    clean_since: Duration,
+    /// Whether a successful outcome has been counted.
+    completed:   bool,
+}
+
+fn track_system(
+    mut achievements: ResMut<Achievements>,
+    mut session: Local<Session>,
+    meta: Res<load::LoadedMeta>,
+    stats: Res<Stats>,
+    outcome: Res<Outcome>,
+    clock: Res<clock::Clock>,
+    time: Res<Time<time::Virtual>>,
+) {
+    let now = time.elapsed();
+    if meta.is_changed() {
+        // Statistics restored from a savefile do not count towards achievements.
+        *session = Session {
+            landings:    stats.num_runway_arrivals + stats.num_apron_arrivals,
+            departures:  stats.num_departures,
+            conflicts:   stats.num_conflicts,
+            clean_since: now,
+            completed:   outcome.is_resolved(),
+        };
+    }
+
+    if !achievements.loaded {
+        return;
+    }
+
+    let achievements = &mut *achievements;
+    let progress = &mut achievements.progress;
+    let mut changed = false;
+
+    let landings = stats.num_runway_arrivals + stats.num_apron_arrivals;
+    let new_landings = u64::from(landings.saturating_sub(session.landings));
+    changed |= progress.add(Metric::Landings, new_landings);
+    if clock.daylight(now) <= 0.0 {
+        changed |= progress.add(Metric::NightLandings, new_landings);
+    }
+    let new_departures = u64::from(stats.num_departures.saturating_sub(session.departures));
+    changed |= progress.add(Metric::Departures, new_departures);
+    session.landings = landings;
+    session.departures = stats.num_departures;
+
+    if stats.num_conflicts > session.conflicts {
+        session.conflicts = stats.num_conflicts;
+        session.clean_since = now;
+    }
+    let clean_minutes = now.saturating_sub(session.clean_since).as_secs() / 60;
+    changed |= progress.record(Metric::CleanMinutes, clean_minutes);
+
+    if let Outcome::Succeeded { .. } = *outcome
+        && !session.completed
+    {
+        session.completed = true;
+        changed |= progress.add(Metric::ScenariosCompleted, 1);
+    }
+
+    if changed {
+        let newly_unlocked = progress.evaluate(Timestamp::now());
+        achievements.newly_unlocked.extend(newly_unlocked);
+        achievements.unsaved = true;
+    }
+}
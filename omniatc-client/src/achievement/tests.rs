@@ -0,0 +1,51 @@
+use jiff::Timestamp;
+
+use super::{Metric, Progress, REGISTRY};
+
+#[test]
+fn test_registry_ids_unique() {
+    for (index, def) in REGISTRY.iter().enumerate() {
+        assert!(
+            REGISTRY[..index].iter().all(|other| other.id != def.id),
+            "duplicate achievement id {:?}",
+            def.id
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_unlocks_once() {
+    let mut progress = Progress::default();
+    assert!(progress.evaluate(Timestamp::UNIX_EPOCH).is_empty());
+
+    assert!(progress.add(Metric::Landings, 1));
+    let unlocked: Vec<_> =
+        progress.evaluate(Timestamp::UNIX_EPOCH).into_iter().map(|def| def.id).collect();
+    assert_eq!(unlocked, ["first-landing"]);
+
+    assert!(progress.add(Metric::Landings, 99));
+    let unlocked: Vec<_> =
+        progress.evaluate(Timestamp::UNIX_EPOCH).into_iter().map(|def| def.id).collect();
+    assert_eq!(unlocked, ["landings-100"], "already unlocked achievements are not repeated");
+}
+
+#[test]
+fn test_record_keeps_maximum() {
+    let mut progress = Progress::default();
+    assert!(progress.record(Metric::CleanMinutes, 30));
+    assert!(!progress.record(Metric::CleanMinutes, 20));
+    assert_eq!(progress.get(Metric::CleanMinutes), 30);
+    assert!(!progress.add(Metric::Departures, 0));
+}
+
+#[test]
+fn test_progress_roundtrip() {
+    let mut progress = Progress::default();
+    progress.add(Metric::ScenariosCompleted, 1);
+    progress.evaluate(Timestamp::UNIX_EPOCH);
+
+    let json = serde_json::to_string(&progress).expect("serialize progress");
+    let restored: Progress = serde_json::from_str(&json).expect("deserialize progress");
+    assert_eq!(restored.get(Metric::ScenariosCompleted), 1);
+    assert!(restored.unlocked.contains_key("scenario-complete"));
+}
@@ -28,6 +28,7 @@ impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.init_config::<ConfigManager, Conf>("input");
         app.init_resource::<CursorState>();
+        app.init_resource::<PairSelection>();
         app.add_systems(
             app::Update,
             CursorState::update_system
@@ -128,15 +129,25 @@ impl CursorState {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
 pub struct ReadCurrentCursorCameraSystemSet;
 
+/// A pair of objects selected to compare their separation.
+///
+/// The pair is selected by right-clicking another object while an object is selected,
+/// and cleared by right-clicking elsewhere.
+#[derive(Resource, Default)]
+pub struct PairSelection(pub Option<[Entity; 2]>);
+
 #[derive(Resource, Default)]
 #[expect(clippy::struct_excessive_bools, reason = "multiple independent flags")]
 pub struct Hotkeys {
     pub search:          bool,
+    pub focus_command:   bool,
     pub deselect:        bool,
     pub fast_forward:    bool,
     pub toggle_pause:    bool,
     pub reset_speed:     bool,
     pub north:           bool,
+    pub screenshot:      bool,
+    pub timelapse:       bool,
     pub pick_route:      bool,
     pub append_route:    bool,
     pub send:            bool,
@@ -166,11 +177,14 @@ impl Hotkeys {
 
         ctx.input(|state| {
             this.search = conf.level_control.search.clicked(state);
+            this.focus_command = conf.level_control.focus_command.clicked(state);
             this.deselect = conf.level_control.deselect.clicked(state);
             this.fast_forward = conf.level_control.fast_forward.down(state);
             this.toggle_pause = conf.level_control.toggle_pause.clicked(state);
             this.reset_speed = conf.level_control.reset_speed.clicked(state);
             this.north = conf.level_control.north.clicked(state);
+            this.screenshot = conf.level_control.screenshot.clicked(state);
+            this.timelapse = conf.level_control.timelapse.clicked(state);
             this.pick_route = conf.picking.pick_route.down(state);
             this.append_route = conf.picking.append_route.down(state);
             this.send = conf.object_control.send.clicked(state);
@@ -199,17 +213,26 @@ struct Conf {
 #[derive(Config)]
 struct LevelControlConf {
     #[config(default = KeySet::from(egui::Key::Slash))]
-    search:       KeySet,
+    search:        KeySet,
+    /// Opens the accessible view and focuses its command line.
+    #[config(default = KeySet::from(egui::Key::Semicolon))]
+    focus_command: KeySet,
     #[config(default = KeySet::from(egui::Key::Escape))]
-    deselect:     KeySet,
+    deselect:      KeySet,
     #[config(default = KeySet::from(egui::Key::Space).shift(true))]
-    fast_forward: KeySet,
+    fast_forward:  KeySet,
     #[config(default = KeySet::from(egui::Key::Space).shift(false))]
-    toggle_pause: KeySet,
+    toggle_pause:  KeySet,
     #[config(default = KeySet::from(egui::Key::Num1))]
-    reset_speed:  KeySet,
+    reset_speed:   KeySet,
     #[config(default = KeySet::from(egui::Key::N))]
-    north:        KeySet,
+    north:         KeySet,
+    /// Captures a screenshot.
+    #[config(default = KeySet::from(egui::Key::F12).shift(false))]
+    screenshot:    KeySet,
+    /// Starts or stops timelapse capture.
+    #[config(default = KeySet::from(egui::Key::F12).shift(true))]
+    timelapse:     KeySet,
 }
 
 #[derive(Config)]
@@ -17,7 +17,9 @@ use itertools::Itertools;
 use omniatc::level;
 use strum::IntoEnumIterator;
 
+mod achievement;
 pub mod input;
+mod presence;
 pub mod render;
 mod storage;
 pub mod util;
@@ -40,9 +42,30 @@ pub struct Options {
     pub open_level_id:    Option<String>,
     #[clap(long, default_value = storage::scenario_loader::DEFAULT_SCENARIO)]
     pub default_scenario: String,
+    /// Name of the color theme in `assets/themes` to load on startup.
+    #[clap(long, default_value = storage::theme::DEFAULT_THEME)]
+    pub theme:            String,
 }
 
 pub fn main_app(options: Options) -> App {
+    build_app(
+        options,
+        WgpuSettings {
+            limits: WgpuLimits { max_texture_dimension_2d: 8192, ..Default::default() },
+            ..Default::default()
+        },
+    )
+}
+
+/// Builds the client app with the given renderer settings.
+///
+/// Tests without a GPU set `backends` to `None` to disable the renderer.
+fn build_app(options: Options, wgpu_settings: WgpuSettings) -> App {
+    #[cfg(not(target_family = "wasm"))]
+    if !options.headless_test {
+        storage::install_panic_hook();
+    }
+
     let mut app = App::new();
 
     app.add_plugins({
@@ -53,10 +76,7 @@ pub fn main_app(options: Options) -> App {
                 ..Default::default()
             })
             .set(RenderPlugin {
-                render_creation: RenderCreation::Automatic(WgpuSettings {
-                    limits: WgpuLimits { max_texture_dimension_2d: 8192, ..Default::default() },
-                    ..Default::default()
-                }),
+                render_creation: RenderCreation::Automatic(wgpu_settings),
                 ..Default::default()
             });
         if options.headless_test {
@@ -70,6 +90,7 @@ pub fn main_app(options: Options) -> App {
     if options.headless_test {
         app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
     }
+    app.insert_resource(storage::theme::Active::new(options.theme));
 
     app.add_plugins((
         EntityCountDiagnosticsPlugin::default(),
@@ -83,13 +104,16 @@ pub fn main_app(options: Options) -> App {
         level::Plug::<ConfigManager>::default(),
         omniatc::load::Plug,
         omniatc::util::Plug,
+        achievement::Plug,
         input::Plug,
+        presence::Plug,
         render::Plug,
         storage::plugin(storage::StartupLevelOptions {
             open_level_id:    options.open_level_id,
             default_scenario: options.default_scenario,
         }),
         util::billboard::Plug,
+        util::palette::Plug,
         util::shapes::Plug,
     ));
 
@@ -144,6 +168,10 @@ pub enum EguiSystemSets {
     ManageTabs,
     Dock,
     TutorialPopup,
+    GoalsPanel,
+    Debrief,
+    Review,
+    Toasts,
 }
 
 #[derive(Debug, Resource, Default)]
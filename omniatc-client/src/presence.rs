@@ -0,0 +1,147 @@
+//! Rich presence reporting to external platforms.
+//!
+//! The [`Presence`] resource summarizes the current session
+//! and is updated after the simulation every frame.
+//! Registered [`Backend`]s publish it to their platforms at a throttled rate.
+//!
+//! Platform backends are compiled behind cargo features:
+//! - `discord`: Discord rich presence through the local IPC socket.
+//!   The application ID is read from `OMNIATC_DISCORD_CLIENT_ID` at compile time.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::change_detection::{DetectChanges, DetectChangesMut};
+use bevy::ecs::query::With;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Query, Res, ResMut};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use omniatc::level::{self, object};
+use omniatc::load;
+
+use crate::{ConfigManager, UpdateSystemSets};
+
+#[cfg(all(feature = "discord", not(target_family = "wasm")))]
+mod discord;
+
+/// Minimum real time between two publications to the backends.
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(15);
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("presence");
+        app.init_resource::<Presence>();
+        app.init_resource::<Backends>();
+
+        #[cfg(all(feature = "discord", not(target_family = "wasm")))]
+        discord::register(app);
+
+        app.add_systems(
+            app::Update,
+            (update_system, publish_system)
+                .chain()
+                .in_set(UpdateSystemSets::Simulate)
+                .after(level::AllSystemSets),
+        );
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Publish the current session to rich presence platforms.
+    #[config(default = true)]
+    enabled: bool,
+}
+
+/// Summary of the current session for rich presence.
+#[derive(Resource, Default, PartialEq)]
+pub struct Presence {
+    /// Title of the loaded map, if any.
+    pub map:          Option<String>,
+    /// Number of objects in the level.
+    pub traffic:      usize,
+    /// Real time elapsed since the map was loaded.
+    pub session_time: Duration,
+}
+
+/// Publishes [`Presence`] to a platform.
+pub trait Backend: Send + Sync + 'static {
+    /// Name of the platform, for logging.
+    fn name(&self) -> &'static str;
+
+    /// Reports the latest presence.
+    ///
+    /// Called at most once per [`PUBLISH_INTERVAL`].
+    fn publish(&mut self, presence: &Presence);
+
+    /// Clears the reported presence, e.g. when presence is disabled.
+    fn clear(&mut self);
+}
+
+/// Backends receiving presence updates.
+#[derive(Resource, Default)]
+pub struct Backends(Vec<Box<dyn Backend>>);
+
+impl Backends {
+    pub fn add(&mut self, backend: impl Backend) {
+        bevy::log::debug!("Registered rich presence backend {}", backend.name());
+        self.0.push(Box::new(backend));
+    }
+}
+
+fn update_system(
+    mut presence: ResMut<Presence>,
+    loaded_meta: Res<load::LoadedMeta>,
+    object_query: Query<(), With<object::Object>>,
+    time: Res<Time<time::Real>>,
+    mut session_start: Local<Duration>,
+) {
+    if loaded_meta.is_changed() {
+        *session_start = time.elapsed();
+    }
+
+    presence.set_if_neq(Presence {
+        map:          loaded_meta.0.as_ref().map(|meta| meta.title.clone()),
+        traffic:      object_query.iter().count(),
+        session_time: time.elapsed().saturating_sub(*session_start),
+    });
+}
+
+#[derive(Default)]
+struct PublishState {
+    last_publish: Option<Duration>,
+    published:    bool,
+}
+
+fn publish_system(
+    presence: Res<Presence>,
+    mut backends: ResMut<Backends>,
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Real>>,
+    mut state: Local<PublishState>,
+) {
+    let conf = conf.read();
+
+    if !conf.enabled {
+        if state.published {
+            backends.0.iter_mut().for_each(|backend| backend.clear());
+            state.published = false;
+            state.last_publish = None;
+        }
+        return;
+    }
+
+    if state.last_publish.is_some_and(|last| time.elapsed() < last + PUBLISH_INTERVAL) {
+        return;
+    }
+
+    for backend in &mut backends.0 {
+        backend.publish(&presence);
+    }
+    state.last_publish = Some(time.elapsed());
+    state.published = true;
+}
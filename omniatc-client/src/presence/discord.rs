@@ -0,0 +1,184 @@
+//! Discord rich presence over the local IPC socket.
+//!
+//! The IPC protocol exchanges frames consisting of
+//! a little-endian `u32` opcode, a little-endian `u32` payload length and a JSON payload.
+//! Blocking I/O is performed on a dedicated thread
+//! so that an unresponsive Discord client does not stall the game.
+
+use std::io::{self, Read, Write};
+use std::sync::mpsc;
+use std::time::SystemTime;
+use std::{process, thread};
+
+use anyhow::Context;
+use bevy::app::App;
+use serde_json::{Value, json};
+
+use super::{Backends, Presence};
+
+const OPCODE_HANDSHAKE: u32 = 0;
+const OPCODE_FRAME: u32 = 1;
+
+/// Number of IPC socket indices probed when connecting.
+const MAX_SOCKET_INDEX: u32 = 10;
+
+pub(super) fn register(app: &mut App) {
+    let Some(client_id) = option_env!("OMNIATC_DISCORD_CLIENT_ID") else {
+        bevy::log::warn!(
+            "Discord presence is disabled because OMNIATC_DISCORD_CLIENT_ID was not set at \
+             compile time"
+        );
+        return;
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    if let Err(err) = thread::Builder::new()
+        .name("discord-presence".into())
+        .spawn(move || run(client_id, &receiver))
+    {
+        bevy::log::error!("Cannot spawn Discord presence thread: {err}");
+        return;
+    }
+
+    app.world_mut().resource_mut::<Backends>().add(Backend { sender });
+}
+
+/// An activity update sent to the IPC thread. `None` clears the activity.
+type Update = Option<Value>;
+
+struct Backend {
+    sender: mpsc::Sender<Update>,
+}
+
+impl super::Backend for Backend {
+    fn name(&self) -> &'static str { "discord" }
+
+    fn publish(&mut self, presence: &Presence) {
+        let start = SystemTime::now()
+            .checked_sub(presence.session_time)
+            .and_then(|start| start.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .unwrap_or_default();
+
+        let activity = json!({
+            "details": presence.map.as_deref().unwrap_or("In menu"),
+            "state": format!("{} aircraft", presence.traffic),
+            "timestamps": { "start": start.as_secs() },
+        });
+        // The thread only exits if the channel is disconnected, so sending cannot fail.
+        _ = self.sender.send(Some(activity));
+    }
+
+    fn clear(&mut self) { _ = self.sender.send(None); }
+}
+
+fn run(client_id: &str, receiver: &mpsc::Receiver<Update>) {
+    let mut connection = None;
+
+    for update in receiver {
+        if connection.is_none() {
+            match Connection::open(client_id) {
+                Ok(conn) => connection = Some(conn),
+                Err(err) => {
+                    bevy::log::debug!("Cannot connect to Discord: {err:?}");
+                    continue;
+                }
+            }
+        }
+
+        let Some(conn) = &mut connection else { continue };
+        if let Err(err) = conn.set_activity(update) {
+            bevy::log::debug!("Discord connection lost: {err:?}");
+            connection = None;
+        }
+    }
+}
+
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+struct Connection {
+    stream: Box<dyn Stream>,
+    nonce:  u64,
+}
+
+impl Connection {
+    fn open(client_id: &str) -> anyhow::Result<Self> {
+        let stream = (0..MAX_SOCKET_INDEX)
+            .find_map(|index| open_socket(index).ok())
+            .context("no Discord IPC socket available")?;
+        let mut conn = Self { stream, nonce: 0 };
+
+        conn.write_frame(OPCODE_HANDSHAKE, &json!({ "v": 1, "client_id": client_id }))?;
+        conn.read_frame().context("read handshake response")?;
+        Ok(conn)
+    }
+
+    fn set_activity(&mut self, activity: Update) -> anyhow::Result<()> {
+        self.nonce += 1;
+        let payload = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": { "pid": process::id(), "activity": activity },
+            "nonce": self.nonce.to_string(),
+        });
+        self.write_frame(OPCODE_FRAME, &payload)?;
+
+        // Drain the response so that the socket buffer does not fill up.
+        let (_, response) = self.read_frame().context("read SET_ACTIVITY response")?;
+        if response.get("evt").and_then(Value::as_str) == Some("ERROR") {
+            bevy::log::warn!("Discord rejected presence update: {response}");
+        }
+        Ok(())
+    }
+
+    fn write_frame(&mut self, opcode: u32, payload: &Value) -> io::Result<()> {
+        let payload = serde_json::to_vec(payload)?;
+        let len = u32::try_from(payload.len()).map_err(io::Error::other)?;
+
+        let mut frame = Vec::with_capacity(8 + payload.len());
+        frame.extend_from_slice(&opcode.to_le_bytes());
+        frame.extend_from_slice(&len.to_le_bytes());
+        frame.extend_from_slice(&payload);
+        self.stream.write_all(&frame)?;
+        self.stream.flush()
+    }
+
+    fn read_frame(&mut self) -> anyhow::Result<(u32, Value)> {
+        let mut header = [0u8; 8];
+        self.stream.read_exact(&mut header)?;
+        let [o0, o1, o2, o3, l0, l1, l2, l3] = header;
+        let opcode = u32::from_le_bytes([o0, o1, o2, o3]);
+        let len = u32::from_le_bytes([l0, l1, l2, l3]);
+
+        let mut payload = vec![0; usize::try_from(len).context("frame too large")?];
+        self.stream.read_exact(&mut payload)?;
+        Ok((opcode, serde_json::from_slice(&payload).context("parse frame payload")?))
+    }
+}
+
+#[cfg(unix)]
+fn open_socket(index: u32) -> io::Result<Box<dyn Stream>> {
+    use std::env;
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .into_iter()
+        .find_map(env::var_os)
+        .unwrap_or_else(|| "/tmp".into());
+    let mut path = std::path::PathBuf::from(dir);
+    path.push(format!("discord-ipc-{index}"));
+
+    let stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    Ok(Box::new(stream))
+}
+
+#[cfg(windows)]
+fn open_socket(index: u32) -> io::Result<Box<dyn Stream>> {
+    let pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(format!(r"\\?\pipe\discord-ipc-{index}"))?;
+    Ok(Box::new(pipe))
+}
@@ -11,11 +11,23 @@ use strum::IntoEnumIterator;
 use crate::EguiSystemSets;
 use crate::util::new_type_id;
 
+mod accessible;
+mod achievements;
+mod callsign;
+mod capture;
 mod config_editor;
+mod debrief;
 mod dock;
+mod file_manager;
+mod goals_panel;
 mod level_info;
-mod messages;
+mod loading_screen;
+mod macros;
+pub mod messages;
 mod object_info;
+mod perf_overlay;
+mod profile;
+mod spawn_editor;
 pub mod threedim;
 mod tutorial_popup;
 pub mod twodim;
@@ -26,13 +38,27 @@ impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             dock::Plug,
+            accessible::Plug,
+            achievements::Plug,
+            capture::Plug,
             messages::Plug,
             config_editor::Plug,
+            debrief::Plug,
+            file_manager::Plug,
+            goals_panel::Plug,
             level_info::Plug,
+            macros::Plug,
             object_info::Plug,
+            profile::Plug,
             tutorial_popup::Plug,
             twodim::Plug,
         ));
+        app.add_plugins((
+            callsign::Plug,
+            loading_screen::Plug,
+            perf_overlay::Plug,
+            spawn_editor::Plug,
+        ));
 
         for set in SystemSets::iter() {
             app.configure_sets(app::Update, set.in_set(crate::UpdateSystemSets::Render));
@@ -55,6 +81,8 @@ impl Plugin for Plug {
 pub enum SystemSets {
     /// Update existing entities for config changes.
     Reload,
+    /// Compute the visible region for culling off-screen geometry.
+    Cull,
     /// Spawn new entities.
     Spawn,
     /// Update existing entities regularly.
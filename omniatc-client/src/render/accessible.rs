@@ -0,0 +1,611 @@
+//! Accessible text view of the traffic situation.
+//!
+//! The view summarizes the selected object, nearby traffic and recent messages
+//! as plain text with enlarged fonts,
+//! and accepts typed commands so that every instruction
+//! can be issued without a pointing device.
+//! See [`command`] for the command syntax.
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{QueryData, With};
+use bevy::ecs::relationship::RelationshipTarget;
+use bevy::ecs::schedule::{self, IntoScheduleConfigs, Schedulable, ScheduleConfigs};
+use bevy::ecs::system::{Commands, Query, Res, ResMut, Single, SystemParam};
+use bevy::time::{self, Time};
+use bevy_egui::{EguiPrimaryContextPass, egui};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use egui_material_icons::icons;
+use math::{Heading, Length, TurnDirection};
+use omniatc::QueryTryLog;
+use omniatc::level::aerodrome::Aerodrome;
+use omniatc::level::dest::Destination;
+use omniatc::level::instr::{self, CommandsExt, Instruction};
+use omniatc::level::message::{self, Message};
+use omniatc::level::object::{self, Object};
+use omniatc::level::route::{self, Route, TaxiStopMode};
+use omniatc::level::waypoint::Waypoint;
+use omniatc::level::{conflict, ground, nav, note, pilot_request, quest, transition};
+use ordered_float::OrderedFloat;
+use store::YawTarget;
+
+use crate::render::dock::{self, TabPlacement};
+use crate::render::object_info::{self, CurrentObjectSelectorSystemSet};
+use crate::render::{MenuButton, MenuButtonClicked};
+use crate::{ConfigManager, EguiSystemSets, input, render};
+
+mod command;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("accessible");
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_ACCESSIBILITY,
+                title:    "Accessible view".into(),
+                group:    render::MenuButtonGroup::Game,
+                priority: 90,
+            },
+            MenuButtonMarker,
+        ));
+
+        app.add_systems(EguiPrimaryContextPass, open_tab_system.in_set(EguiSystemSets::ManageTabs));
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Scale factor for all text in the accessible view.
+    #[config(default = 1.5, min = 1.0, max = 4.0)]
+    font_scale:    f32,
+    /// Number of nearest objects listed for the selected object.
+    #[config(default = 5)]
+    nearby_count:  usize,
+    /// Number of most recent messages listed.
+    #[config(default = 8)]
+    message_count: usize,
+    /// Objects farther than this distance are not listed as nearby traffic.
+    #[config(default = Length::from_nm(20.0), min = Length::ZERO, max = Length::from_nm(100.0))]
+    nearby_range:  Length<f32>,
+}
+
+#[derive(Component)]
+struct MenuButtonMarker;
+
+fn open_tab_system(
+    mut dock_state: ResMut<dock::State>,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<MenuButtonMarker>>,
+    hotkeys: Res<input::Hotkeys>,
+) {
+    if (menu_button_clicked.consume() || hotkeys.focus_command)
+        && let Some(state) = &mut dock_state.state
+    {
+        dock::focus_or_create_tab(
+            state,
+            || dock::Tab::Accessible(TabType::default()),
+            dock::ReplaceTab(|tab| matches!(tab, dock::Tab::Accessible(_)))
+                .or_always(dock::NewSurface),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct TabType {
+    /// The command line being typed.
+    input:    String,
+    /// Result of the last submitted command.
+    feedback: Option<Result<String, String>>,
+}
+
+#[derive(QueryData)]
+struct ObjectData {
+    entity:          Entity,
+    display:         &'static object::Display,
+    object:          &'static Object,
+    rotation:        &'static object::Rotation,
+    dest:            &'static Destination,
+    telephony:       Option<&'static message::Telephony>,
+    airborne:        Option<&'static object::Airborne>,
+    nav_vel:         Option<&'static nav::VelocityTarget>,
+    target_alt:      Option<&'static nav::TargetAltitude>,
+    target_waypoint: Option<&'static nav::TargetWaypoint>,
+    route:           Option<&'static Route>,
+    route_id:        Option<&'static route::Id>,
+    conflicts:       Option<&'static conflict::Record>,
+    requests:        Option<&'static pilot_request::RequestList>,
+    notes:           Option<&'static note::Notes>,
+}
+
+#[derive(SystemParam)]
+pub struct UiParams<'w, 's> {
+    conf:          ReadConfig<'w, 's, Conf>,
+    hotkeys:       Res<'w, input::Hotkeys>,
+    time:          Res<'w, Time<time::Virtual>>,
+    pair_query:    Query<'w, 's, &'static conflict::PairState>,
+    message_query: Query<'w, 's, &'static Message>,
+    executor:      Executor<'w, 's>,
+}
+
+/// Executes command lines against the selected object.
+#[derive(SystemParam)]
+pub struct Executor<'w, 's> {
+    selected_object: ResMut<'w, object_info::CurrentObject>,
+    object_query:    Query<'w, 's, ObjectData>,
+    request_query:   Query<'w, 's, (), With<pilot_request::Request>>,
+    resolve:         ResolveParams<'w, 's>,
+    commands:        Commands<'w, 's>,
+}
+
+#[derive(SystemParam)]
+struct ResolveParams<'w, 's> {
+    waypoints:    Query<'w, 's, (Entity, &'static Waypoint)>,
+    aerodromes:   Query<'w, 's, (Entity, &'static Aerodrome)>,
+    segments:     Query<'w, 's, &'static ground::SegmentLabel>,
+    preset_lists: Query<'w, 's, &'static route::WaypointPresetList>,
+    presets:      Query<'w, 's, (&'static route::Preset, &'static route::DestinationMatcher)>,
+    transition:   Res<'w, transition::Transition>,
+}
+
+impl dock::TabType for TabType {
+    type TitleSystemParam<'w, 's> = ();
+    fn title(&self, (): ()) -> String { "Accessible view".into() }
+
+    type UiSystemParam<'w, 's> = UiParams<'w, 's>;
+    fn ui(&mut self, mut params: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
+        let font_scale = params.conf.read().font_scale;
+        for font in ui.style_mut().text_styles.values_mut() {
+            font.size *= font_scale;
+        }
+
+        self.show_command_line(ui, &mut params);
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading("Selected");
+            let selected = params
+                .executor
+                .selected_object
+                .0
+                .and_then(|entity| params.executor.object_query.get(entity).ok());
+            if let Some(data) = &selected {
+                show_summary(ui, data, &params);
+            } else {
+                ui.label("No aircraft selected. Type a callsign to select it.");
+            }
+
+            ui.heading("Traffic");
+            show_traffic(ui, selected.as_ref(), &params);
+
+            ui.heading("Messages");
+            show_messages(ui, &params);
+        });
+    }
+
+    fn schedule_configs<T>(configs: ScheduleConfigs<T>) -> ScheduleConfigs<T>
+    where
+        T: Schedulable<Metadata = schedule::GraphInfo, GroupMetadata = schedule::Chain>,
+    {
+        configs.in_set(quest::UiEventWriterSystemSet).in_set(CurrentObjectSelectorSystemSet)
+    }
+
+    type OnCloseSystemParam<'w, 's> = ();
+
+    type PrepareRenderSystemParam<'w, 's> = ();
+}
+
+impl TabType {
+    fn show_command_line(&mut self, ui: &mut egui::Ui, params: &mut UiParams) {
+        let resp = ui.add(
+            egui::TextEdit::singleline(&mut self.input)
+                .hint_text("Callsign and instructions, e.g. CPA123 H270 A5000 S210")
+                .desired_width(f32::INFINITY),
+        );
+        if params.hotkeys.focus_command {
+            resp.request_focus();
+        }
+
+        if resp.lost_focus()
+            && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter))
+        {
+            let input = std::mem::take(&mut self.input);
+            self.feedback = Some(params.executor.execute(&input));
+            resp.request_focus();
+        }
+
+        match &self.feedback {
+            Some(Ok(feedback)) => {
+                ui.label(feedback);
+            }
+            Some(Err(err)) => {
+                ui.colored_label(egui::Color32::ORANGE, err);
+            }
+            None => {}
+        }
+    }
+}
+
+fn show_summary(ui: &mut egui::Ui, data: &ObjectDataItem, params: &UiParams) {
+    let mut name = data.display.name.clone();
+    if let Some(telephony) = data.telephony {
+        name = format!("{name} ({})", telephony.0);
+    }
+    ui.label(name);
+
+    let position = data.object.position;
+    let transition = &params.executor.resolve.transition;
+    ui.label(format!(
+        "Altitude {:#}, vertical rate {:+.0} feet per minute",
+        transition.reading(position.altitude()),
+        data.object.ground_speed.vertical().into_fpm(),
+    ));
+    ui.label(format!(
+        "Heading {:03.0}, ground speed {:.0} knots",
+        Heading::from_quat(data.rotation.0).degrees(),
+        data.object.ground_speed.horizontal().magnitude_exact().into_knots(),
+    ));
+    if let Some(airborne) = data.airborne {
+        ui.label(format!(
+            "Indicated airspeed {:.0} knots",
+            airborne.airspeed.horizontal().magnitude_exact().into_knots()
+        ));
+    }
+
+    let mut targets = Vec::new();
+    if let Some(nav_vel) = data.nav_vel {
+        if let YawTarget::Heading(heading) | YawTarget::TurnHeading { heading, .. } = nav_vel.yaw
+            && data.target_waypoint.is_none()
+        {
+            targets.push(format!("heading {:03.0}", heading.degrees()));
+        }
+        targets.push(format!("speed {:.0} knots", nav_vel.horiz_speed.into_knots()));
+    }
+    if let Some(target_alt) = data.target_alt {
+        targets.push(format!("altitude {:#}", transition.reading(target_alt.altitude)));
+    }
+    if let Some(target) = data.target_waypoint
+        && let Some((_, waypoint)) =
+            params.executor.resolve.waypoints.log_get(target.waypoint_entity)
+    {
+        targets.push(format!("direct {}", waypoint.name));
+    }
+    if !targets.is_empty() {
+        ui.label(format!("Cleared {}", targets.join(", ")));
+    }
+
+    if let Some(id) = data.route_id.and_then(|id| id.0.as_deref()) {
+        ui.label(format!("Route {id}"));
+    }
+    if data.route.is_some_and(|route| matches!(route.current(), Some(route::Node::Standby(_)))) {
+        ui.label("Awaiting clearance for the next step (C)");
+    }
+
+    let destination = match *data.dest {
+        Destination::Landing { aerodrome } | Destination::Parking { aerodrome } => params
+            .executor
+            .resolve
+            .aerodromes
+            .log_get(aerodrome)
+            .map_or_else(|| "unknown aerodrome".into(), |(_, data)| data.name.clone()),
+        Destination::VacateAnyRunway => "any runway".into(),
+        Destination::Departure { .. } => "departure".into(),
+    };
+    ui.label(format!("Destination {destination}"));
+
+    if let Some(request) = pending_request(data, &params.executor) {
+        let content = params.message_query.log_get(request).map_or("", |m| m.content.as_str());
+        ui.label(format!("Pending request: {content} (APP or DENY)"));
+    }
+
+    if let Some(notes) = data.notes {
+        if !notes.scratchpad.trim().is_empty() {
+            ui.label(format!("Notes: {}", notes.scratchpad.trim()));
+        }
+        if let Some(reminder) = notes.next_reminder() {
+            let remaining = reminder.due.saturating_sub(params.time.elapsed()).as_secs();
+            ui.label(format!(
+                "Next reminder: {} in {}:{:02}",
+                reminder.text,
+                remaining / 60,
+                remaining % 60
+            ));
+        }
+    }
+}
+
+fn show_traffic(ui: &mut egui::Ui, selected: Option<&ObjectDataItem>, params: &UiParams) {
+    let conf = params.conf.read();
+
+    let Some(selected) = selected else {
+        let active: Vec<_> = params.pair_query.iter().filter(|pair| pair.is_active).collect();
+        if active.is_empty() {
+            ui.label("No active conflicts");
+        }
+        for pair in active {
+            let name = |entity| {
+                params
+                    .executor
+                    .object_query
+                    .get(entity)
+                    .map_or("unknown", |data| data.display.name.as_str())
+            };
+            ui.label(format!("Conflict: {} and {}", name(pair.entity_a), name(pair.entity_b)));
+        }
+        return;
+    };
+
+    let own = selected.object.position;
+    let mut nearby: Vec<_> = params
+        .executor
+        .object_query
+        .iter()
+        .filter(|data| data.entity != selected.entity)
+        .map(|data| (own.horizontal_distance_exact(data.object.position), data))
+        .filter(|&(distance, _)| distance <= conf.nearby_range)
+        .collect();
+    nearby.sort_by_key(|&(distance, _)| OrderedFloat(distance.0));
+
+    if nearby.is_empty() {
+        ui.label("No traffic nearby");
+    }
+    for (distance, data) in nearby.into_iter().take(conf.nearby_count) {
+        let other = data.object.position;
+        let bearing = (other.horizontal() - own.horizontal()).heading();
+        let vertical = (other.altitude() - own.altitude()).into_feet();
+        let relative = if vertical >= 0.0 { "above" } else { "below" };
+        let in_conflict = selected
+            .conflicts
+            .and_then(|record| record.pair_for(data.entity))
+            .and_then(|pair| params.pair_query.get(pair).ok())
+            .is_some_and(|pair| pair.is_active);
+
+        let text = format!(
+            "{}{}: {:.1} miles, bearing {:03.0}, {:.0} feet {relative}",
+            if in_conflict { "CONFLICT " } else { "" },
+            data.display.name,
+            distance.into_nm(),
+            bearing.degrees(),
+            vertical.abs(),
+        );
+        if in_conflict {
+            ui.colored_label(egui::Color32::RED, text);
+        } else {
+            ui.label(text);
+        }
+    }
+}
+
+fn show_messages(ui: &mut egui::Ui, params: &UiParams) {
+    let mut messages: Vec<_> = params.message_query.iter().collect();
+    messages.sort_by_key(|message| std::cmp::Reverse(message.created));
+
+    if messages.is_empty() {
+        ui.label("No messages");
+    }
+    for message in messages.into_iter().take(params.conf.read().message_count) {
+        let sender = params
+            .executor
+            .object_query
+            .get(message.source)
+            .map_or("", |data| data.display.name.as_str());
+        #[expect(clippy::unchecked_time_subtraction, reason = "time.elapsed() is monotonic")]
+        let age = (params.time.elapsed() - message.created).as_secs();
+        ui.label(format!("{sender}: {} ({age} seconds ago)", message.content));
+    }
+}
+
+fn pending_request(data: &ObjectDataItem, executor: &Executor) -> Option<Entity> {
+    data.requests?.iter().find(|&request| executor.request_query.contains(request))
+}
+
+impl Executor<'_, '_> {
+    /// Parses and executes a command line,
+    /// returning a description of the result.
+    pub fn execute(&mut self, input: &str) -> Result<String, String> {
+        let params = self;
+        let line = command::parse(input, |word| find_object(params, word).is_some())?;
+
+        if let Some(callsign) = &line.callsign {
+            params.selected_object.0 = find_object(params, callsign);
+        }
+        let object = params.selected_object.0.ok_or("No aircraft selected")?;
+        let data =
+            params.object_query.get(object).map_err(|_| "Selected aircraft is unavailable")?;
+        let name = data.display.name.clone();
+
+        let mut vector = instr::AirborneVector::default();
+        let mut instructions: Vec<Instruction> = Vec::new();
+        let mut response = None;
+        for clause in line.clauses {
+            match clause {
+                command::Clause::Heading { degrees, direction } => {
+                    vector.directional =
+                        Some(instr::AirborneVectorDirectional::SetHeading(instr::SetHeading {
+                            target: yaw_target(degrees, direction),
+                        }));
+                }
+                command::Clause::Altitude { altitude, expedite } => {
+                    vector.altitude = Some(instr::SetAltitude {
+                        target: nav::TargetAltitude {
+                            altitude: params.resolve.transition.altitude(altitude),
+                            expedite,
+                        },
+                    });
+                }
+                command::Clause::Speed(target) => vector.speed = Some(instr::SetSpeed { target }),
+                command::Clause::Direct(name) => {
+                    let waypoint = find_waypoint(&params.resolve, &name)?;
+                    vector.directional =
+                        Some(instr::AirborneVectorDirectional::SetWaypoint(instr::SetWaypoint {
+                            waypoint,
+                        }));
+                }
+                command::Clause::Respond { approve } => {
+                    let request = pending_request(&data, params).ok_or("No pending request")?;
+                    response = Some(pilot_request::Respond { request, approve });
+                }
+                clause => instructions.push(resolve_clause(clause, &data, &params.resolve)?),
+            }
+        }
+
+        let mut count = instructions.len();
+        if vector.directional.is_some() || vector.speed.is_some() || vector.altitude.is_some() {
+            params.commands.send_instruction(object, vector);
+            count += 1;
+        }
+        for instruction in instructions {
+            params.commands.send_instruction(object, instruction);
+        }
+        if let Some(response) = response {
+            params.commands.queue(response);
+            count += 1;
+        }
+
+        Ok(match count {
+            0 => format!("Selected {name}"),
+            1 => format!("Sent 1 instruction to {name}"),
+            _ => format!("Sent {count} instructions to {name}"),
+        })
+    }
+}
+
+fn resolve_clause(
+    clause: command::Clause,
+    data: &ObjectDataItem,
+    params: &ResolveParams,
+) -> Result<Instruction, String> {
+    Ok(match clause {
+        command::Clause::Route(id) => {
+            let presets = data
+                .target_waypoint
+                .and_then(|target| params.preset_lists.get(target.waypoint_entity).ok())
+                .ok_or("No routes available from the current target")?;
+            let preset = presets
+                .iter()
+                .filter_map(|entity| params.presets.get(entity).ok())
+                .filter(|(_, matcher)| matcher.matches(data.dest))
+                .map(|(preset, _)| preset)
+                .find(|preset| preset.id.eq_ignore_ascii_case(&id))
+                .ok_or_else(|| format!("Route {id} is not available"))?;
+            instr::SelectRoute { preset: preset.clone() }.into()
+        }
+        command::Clause::ClearRoute => instr::ClearRoute.into(),
+        command::Clause::Continue => {
+            let skip_id = data
+                .route
+                .and_then(|route| {
+                    route.iter().find_map(|node| match node {
+                        route::Node::Standby(node) => Some(node.skip_id),
+                        _ => None,
+                    })
+                })
+                .ok_or("No clearance is awaited")?;
+            instr::RemoveStandby { skip_id }.into()
+        }
+        command::Clause::Taxi { segment, append } => {
+            let label = find_segment(params, &segment)?;
+            let stop_mode = match label {
+                ground::SegmentLabel::Taxiway { .. } => TaxiStopMode::LineUp,
+                ground::SegmentLabel::RunwayPair(_) => TaxiStopMode::HoldShort,
+                ground::SegmentLabel::Apron { .. } => TaxiStopMode::Exhaust,
+            };
+            instr::AppendSegment { clear_existing: !append, segment: label, stop_mode }.into()
+        }
+        command::Clause::Divert(code) => {
+            let aerodrome = params
+                .aerodromes
+                .iter()
+                .find(|(_, aerodrome)| aerodrome.code.eq_ignore_ascii_case(&code))
+                .map(|(entity, _)| entity)
+                .ok_or_else(|| format!("Unknown aerodrome {code}"))?;
+            instr::Divert { aerodrome }.into()
+        }
+        command::Clause::FlightFollowing => instr::GrantFlightFollowing.into(),
+        command::Clause::Breakup => instr::BreakupFormation.into(),
+        command::Clause::BirdCaution => instr::BirdCaution.into(),
+        command::Clause::Recycle => instr::CycleTransponder.into(),
+        command::Clause::Contact(frequency) => instr::ContactFrequency { frequency }.into(),
+        command::Clause::ClearApproach => instr::ClearApproach.into(),
+        command::Clause::SpeedUntil { initial, waypoint, distance, then } => {
+            let waypoint = find_waypoint(params, &waypoint)?;
+            let condition = match distance {
+                Some(distance) => route::SpeedCondition::Distance { waypoint, distance },
+                None => route::SpeedCondition::Waypoint(waypoint),
+            };
+            instr::SetSpeedUntil { initial: instr::SetSpeed { target: initial }, condition, then }
+                .into()
+        }
+        command::Clause::Cross { waypoint, altitude } => instr::CrossAltitude {
+            waypoint: find_waypoint(params, &waypoint)?,
+            altitude: params.transition.altitude(altitude),
+        }
+        .into(),
+        command::Clause::VectorThenResume { degrees, direction, waypoint, condition } => {
+            instr::VectorThenResume {
+                heading:   instr::SetHeading { target: yaw_target(degrees, direction) },
+                condition: match condition {
+                    command::ResumeClause::Distance(distance) => {
+                        nav::ResumeCondition::Distance(distance)
+                    }
+                    command::ResumeClause::Abeam(fix) => {
+                        nav::ResumeCondition::Abeam(find_waypoint(params, &fix)?)
+                    }
+                    command::ResumeClause::Time(duration) => nav::ResumeCondition::Time(duration),
+                },
+                waypoint:  waypoint.map(|name| find_waypoint(params, &name)).transpose()?,
+            }
+            .into()
+        }
+        command::Clause::Heading { .. }
+        | command::Clause::Altitude { .. }
+        | command::Clause::Speed(_)
+        | command::Clause::Direct(_)
+        | command::Clause::Respond { .. } => unreachable!("handled by the caller"),
+    })
+}
+
+fn yaw_target(degrees: f32, direction: Option<TurnDirection>) -> YawTarget {
+    let heading = Heading::from_degrees(degrees);
+    match direction {
+        None => YawTarget::Heading(heading),
+        Some(direction) => YawTarget::TurnHeading { heading, direction, remaining_crosses: 0 },
+    }
+}
+
+fn find_object(params: &Executor, callsign: &str) -> Option<Entity> {
+    params
+        .object_query
+        .iter()
+        .find(|data| data.display.name.eq_ignore_ascii_case(callsign))
+        .map(|data| data.entity)
+}
+
+fn find_waypoint(params: &ResolveParams, name: &str) -> Result<Entity, String> {
+    params
+        .waypoints
+        .iter()
+        .find(|(_, waypoint)| waypoint.name.eq_ignore_ascii_case(name))
+        .map(|(entity, _)| entity)
+        .ok_or_else(|| format!("Unknown waypoint {name}"))
+}
+
+/// Finds a segment label by taxiway name, apron name or runway designator.
+fn find_segment(params: &ResolveParams, name: &str) -> Result<ground::SegmentLabel, String> {
+    params
+        .segments
+        .iter()
+        .find(|label| match label {
+            ground::SegmentLabel::Taxiway { name: label }
+            | ground::SegmentLabel::Apron { name: label } => label.eq_ignore_ascii_case(name),
+            &&ground::SegmentLabel::RunwayPair(runways) => runways.iter().any(|&runway| {
+                params
+                    .waypoints
+                    .get(runway)
+                    .is_ok_and(|(_, waypoint)| waypoint.name.eq_ignore_ascii_case(name))
+            }),
+        })
+        .cloned()
+        .ok_or_else(|| format!("Unknown taxiway, apron or runway {name}"))
+}
@@ -0,0 +1,262 @@
+//! Typed instruction syntax for keyboard-only control.
+//!
+//! A command line consists of whitespace-separated clauses, optionally preceded by a callsign:
+//!
+//! - `H270`: fly heading 270
+//! - `L270`, `R270`: turn left/right to heading 270
+//! - `A5000`, `FL120`: climb or descend, append `X` to expedite
+//! - `S250`: set indicated airspeed to 250 knots
+//! - `S250 U MAIN07/10 S180`: 250 knots until 10 nm from MAIN07, then 180 knots;
+//!   omit `/10` to change speed when passing the waypoint
+//! - `H240 E APPNE/10`: fly heading 240, then proceed direct to APPNE after 10 nm;
+//!   use `/2M` to resume after 2 minutes or `/<fix>` when abeam a fix,
+//!   and omit the waypoint to resume the route after the vector
+//! - `D <waypoint>`: proceed direct to a waypoint
+//! - `X <waypoint> A4000`: cross a waypoint of the route at an altitude
+//! - `RT <route>`: follow a route preset available at the target waypoint
+//! - `CLR`: cancel the current route
+//! - `C`: clearance for the next step of the route
+//! - `T <segment>`: taxi via a taxiway, runway or apron
+//! - `T+ <segment>`: append a segment after the current taxi path
+//! - `DIV <code>`: divert to an aerodrome
+//! - `FF`: grant flight following
+//! - `BRK`: break up formation
+//! - `BIRD`: caution about bird activity
+//! - `RCY`: recycle a failed transponder
+//! - `GND`, `TWR`: contact ground or tower
+//! - `APR`: clearance for the approach in the route
+//! - `APP`, `DENY`: respond to the pending pilot request
+//!
+//! Clauses are case-insensitive.
+
+use std::time::Duration;
+
+use math::{Length, Speed, TurnDirection};
+use omniatc::level::frequency::Frequency;
+use omniatc::level::transition;
+
+#[cfg(test)]
+mod tests;
+
+/// A parsed command line.
+#[derive(Debug, PartialEq)]
+pub struct Line {
+    /// Callsign of the object to select before executing the clauses.
+    pub callsign: Option<String>,
+    /// Clauses in the order they were typed.
+    pub clauses:  Vec<Clause>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Clause {
+    Heading {
+        degrees:   f32,
+        direction: Option<TurnDirection>,
+    },
+    VectorThenResume {
+        degrees:   f32,
+        direction: Option<TurnDirection>,
+        waypoint:  Option<String>,
+        condition: ResumeClause,
+    },
+    Altitude {
+        /// Feet for `A` clauses, or a flight level for `FL` clauses.
+        altitude: transition::Reading,
+        expedite: bool,
+    },
+    Speed(Speed<f32>),
+    SpeedUntil {
+        initial:  Speed<f32>,
+        waypoint: String,
+        distance: Option<Length<f32>>,
+        then:     Speed<f32>,
+    },
+    Direct(String),
+    Cross {
+        waypoint: String,
+        altitude: transition::Reading,
+    },
+    Route(String),
+    ClearRoute,
+    Continue,
+    Taxi {
+        segment: String,
+        append:  bool,
+    },
+    Divert(String),
+    FlightFollowing,
+    Breakup,
+    BirdCaution,
+    Recycle,
+    Contact(Frequency),
+    ClearApproach,
+    Respond {
+        approve: bool,
+    },
+}
+
+/// Condition of [`Clause::VectorThenResume`].
+#[derive(Debug, PartialEq)]
+pub enum ResumeClause {
+    Distance(Length<f32>),
+    Abeam(String),
+    Time(Duration),
+}
+
+/// Parses a command line.
+///
+/// The first word is treated as a callsign if `is_callsign` returns true for it.
+///
+/// # Errors
+/// Returns a human-readable description of the first invalid clause.
+pub fn parse(input: &str, is_callsign: impl FnOnce(&str) -> bool) -> Result<Line, String> {
+    let mut words = input.split_whitespace().peekable();
+
+    let callsign = words.next_if(|&word| is_callsign(word)).map(str::to_uppercase);
+
+    let mut clauses = Vec::new();
+    while let Some(word) = words.next() {
+        let upper = word.to_uppercase();
+        let mut argument = |name: &str| {
+            words.next().map(str::to_string).ok_or_else(|| format!("{name} requires an argument"))
+        };
+
+        let clause = match upper.as_str() {
+            "D" => Clause::Direct(argument("D")?),
+            "X" => {
+                let waypoint = argument("X")?;
+                let altitude = argument("X")?.to_uppercase();
+                match parse_numeric(&altitude) {
+                    Some(Ok(Clause::Altitude { altitude, .. })) => {
+                        Clause::Cross { waypoint, altitude }
+                    }
+                    Some(Err(err)) => return Err(err),
+                    _ => return Err(format!("X requires an altitude, got {altitude:?}")),
+                }
+            }
+            "U" => {
+                let Some(Clause::Speed(initial)) = clauses.pop() else {
+                    return Err("U must follow a speed".into());
+                };
+                let fix = argument("U")?;
+                let (waypoint, distance) = match fix.split_once('/') {
+                    Some((waypoint, distance)) => {
+                        (waypoint.to_string(), Some(Length::from_nm(parse_number(distance)?)))
+                    }
+                    None => (fix, None),
+                };
+                let then = argument("U")?.to_uppercase();
+                let Some(Ok(Clause::Speed(then))) = parse_numeric(&then) else {
+                    return Err(format!("U requires a speed after the waypoint, got {then:?}"));
+                };
+                Clause::SpeedUntil { initial, waypoint, distance, then }
+            }
+            "E" => {
+                let Some(Clause::Heading { degrees, direction }) = clauses.pop() else {
+                    return Err("E must follow a heading".into());
+                };
+                let resume = argument("E")?;
+                let Some((waypoint, condition)) = resume.split_once('/') else {
+                    return Err(format!("E requires a resume condition, got {resume:?}"));
+                };
+                Clause::VectorThenResume {
+                    degrees,
+                    direction,
+                    waypoint: Some(waypoint.to_string()).filter(|waypoint| !waypoint.is_empty()),
+                    condition: parse_resume(condition)?,
+                }
+            }
+            "RT" => Clause::Route(argument("RT")?),
+            "CLR" => Clause::ClearRoute,
+            "C" => Clause::Continue,
+            "T" => Clause::Taxi { segment: argument("T")?, append: false },
+            "T+" => Clause::Taxi { segment: argument("T+")?, append: true },
+            "DIV" => Clause::Divert(argument("DIV")?),
+            "FF" => Clause::FlightFollowing,
+            "BRK" => Clause::Breakup,
+            "BIRD" => Clause::BirdCaution,
+            "RCY" => Clause::Recycle,
+            "GND" => Clause::Contact(Frequency::Ground),
+            "TWR" => Clause::Contact(Frequency::Tower),
+            "APR" => Clause::ClearApproach,
+            "APP" => Clause::Respond { approve: true },
+            "DENY" => Clause::Respond { approve: false },
+            _ => parse_numeric(&upper).ok_or_else(|| format!("Unknown instruction {word:?}"))??,
+        };
+        clauses.push(clause);
+    }
+
+    if callsign.is_none() && clauses.is_empty() {
+        return Err("Empty command".into());
+    }
+
+    Ok(Line { callsign, clauses })
+}
+
+/// Parses clauses of the form `<prefix><number>`.
+///
+/// Returns `None` if the word is not a numeric clause,
+/// or `Some(Err)` if the prefix is recognized but the value is invalid.
+fn parse_numeric(word: &str) -> Option<Result<Clause, String>> {
+    if let Some(value) = word.strip_prefix("FL") {
+        return Some(parse_altitude(value, transition::Reading::FlightLevel));
+    }
+
+    let (prefix, value) = word.split_at_checked(1)?;
+    if !value.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let clause = match prefix {
+        "H" => parse_heading(value, None),
+        "L" => parse_heading(value, Some(TurnDirection::CounterClockwise)),
+        "R" => parse_heading(value, Some(TurnDirection::Clockwise)),
+        "A" => parse_altitude(value, transition::Reading::Altitude),
+        "S" => parse_number(value).map(|knots| Clause::Speed(Speed::from_knots(knots))),
+        _ => return None,
+    };
+    Some(clause)
+}
+
+fn parse_heading(value: &str, direction: Option<TurnDirection>) -> Result<Clause, String> {
+    let degrees = parse_number(value)?;
+    if !(0.0..=360.0).contains(&degrees) {
+        return Err(format!("Heading {degrees} is out of range"));
+    }
+    Ok(Clause::Heading { degrees, direction })
+}
+
+fn parse_altitude(
+    value: &str,
+    reading: impl FnOnce(f32) -> transition::Reading,
+) -> Result<Clause, String> {
+    let (value, expedite) = match value.strip_suffix('X') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    Ok(Clause::Altitude { altitude: reading(parse_number(value)?), expedite })
+}
+
+/// Parses the condition of an `E` clause:
+/// a distance in nm, a duration in minutes suffixed with `M`, or the name of a fix.
+fn parse_resume(value: &str) -> Result<ResumeClause, String> {
+    if !value.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(ResumeClause::Abeam(value.to_string()));
+    }
+    let upper = value.to_uppercase();
+    Ok(match upper.strip_suffix('M') {
+        Some(minutes) => ResumeClause::Time(
+            Duration::try_from_secs_f32(parse_number(minutes)? * 60.0)
+                .map_err(|_| format!("Invalid number {minutes:?}"))?,
+        ),
+        None => ResumeClause::Distance(Length::from_nm(parse_number(value)?)),
+    })
+}
+
+fn parse_number(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+        .ok_or_else(|| format!("Invalid number {value:?}"))
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use math::{Length, Speed, TurnDirection};
+use omniatc::level::frequency::Frequency;
+use omniatc::level::transition::Reading;
+
+use super::{Clause, Line, ResumeClause, parse};
+
+#[test]
+fn test_parse_vector_with_callsign() {
+    let line = parse("cpa123 L090 a5000x s210", |word| word.eq_ignore_ascii_case("CPA123"));
+    assert_eq!(
+        line,
+        Ok(Line {
+            callsign: Some("CPA123".into()),
+            clauses:  vec![
+                Clause::Heading {
+                    degrees:   90.0,
+                    direction: Some(TurnDirection::CounterClockwise),
+                },
+                Clause::Altitude { altitude: Reading::Altitude(5000.0), expedite: true },
+                Clause::Speed(Speed::from_knots(210.0)),
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_parse_flight_level_and_arguments() {
+    let line = parse("FL120 D POLAR T+ B twr x dwind a4000", |_| false);
+    assert_eq!(
+        line,
+        Ok(Line {
+            callsign: None,
+            clauses:  vec![
+                Clause::Altitude { altitude: Reading::FlightLevel(120.0), expedite: false },
+                Clause::Direct("POLAR".into()),
+                Clause::Taxi { segment: "B".into(), append: true },
+                Clause::Contact(Frequency::Tower),
+                Clause::Cross { waypoint: "dwind".into(), altitude: Reading::Altitude(4000.0) },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(parse("H400", |_| false).is_err());
+    assert!(parse("D", |_| false).is_err());
+    assert!(parse("X DWIND", |_| false).is_err());
+    assert!(parse("U MAIN07 S180", |_| false).is_err());
+    assert!(parse("S250 U MAIN07/10", |_| false).is_err());
+    assert!(parse("X DWIND S250", |_| false).is_err());
+    assert!(parse("XYZ", |_| false).is_err());
+    assert!(parse("", |_| false).is_err());
+}
+
+#[test]
+fn test_parse_speed_until() {
+    let line = parse("S250 U MAIN07/10 S180 S210 U DWIND S190", |_| false);
+    assert_eq!(
+        line,
+        Ok(Line {
+            callsign: None,
+            clauses:  vec![
+                Clause::SpeedUntil {
+                    initial:  Speed::from_knots(250.0),
+                    waypoint: "MAIN07".into(),
+                    distance: Some(Length::from_nm(10.0)),
+                    then:     Speed::from_knots(180.0),
+                },
+                Clause::SpeedUntil {
+                    initial:  Speed::from_knots(210.0),
+                    waypoint: "DWIND".into(),
+                    distance: None,
+                    then:     Speed::from_knots(190.0),
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_parse_vector_then_resume() {
+    let line = parse("H240 E APPNE/10 L180 E /2m R090 E APPNE/main07", |_| false);
+    assert_eq!(
+        line,
+        Ok(Line {
+            callsign: None,
+            clauses:  vec![
+                Clause::VectorThenResume {
+                    degrees:   240.0,
+                    direction: None,
+                    waypoint:  Some("APPNE".into()),
+                    condition: ResumeClause::Distance(Length::from_nm(10.0)),
+                },
+                Clause::VectorThenResume {
+                    degrees:   180.0,
+                    direction: Some(TurnDirection::CounterClockwise),
+                    waypoint:  None,
+                    condition: ResumeClause::Time(Duration::from_mins(2)),
+                },
+                Clause::VectorThenResume {
+                    degrees:   90.0,
+                    direction: Some(TurnDirection::Clockwise),
+                    waypoint:  Some("APPNE".into()),
+                    condition: ResumeClause::Abeam("main07".into()),
+                },
+            ],
+        })
+    );
+    assert!(parse("S250 E APPNE/10", |_| false).is_err());
+    assert!(parse("H240 E /1e20m", |_| false).is_err());
+    assert!(parse("H240 E APPNE", |_| false).is_err());
+}
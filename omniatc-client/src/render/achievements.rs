@@ -0,0 +1,163 @@
+//! Achievement browser and unlock notifications.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Res, ResMut, Single, SystemParam};
+use bevy::time::{self, Time};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use egui_material_icons::icons;
+
+use crate::achievement::{self, Achievements};
+use crate::render::dock::{self, TabPlacement};
+use crate::render::{MenuButton, MenuButtonClicked};
+use crate::{EguiSystemSets, render};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_EMOJI_EVENTS,
+                title:    "Achievements".into(),
+                group:    render::MenuButtonGroup::Game,
+                priority: 80,
+            },
+            MenuButtonMarker,
+        ));
+
+        app.add_systems(EguiPrimaryContextPass, open_tab_system.in_set(EguiSystemSets::ManageTabs));
+        app.add_systems(EguiPrimaryContextPass, show_toasts_system.in_set(EguiSystemSets::Toasts));
+    }
+}
+
+/// Duration for which an unlock notification is displayed.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Component)]
+struct MenuButtonMarker;
+
+fn open_tab_system(
+    mut dock_state: ResMut<dock::State>,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<MenuButtonMarker>>,
+) {
+    if menu_button_clicked.consume()
+        && let Some(state) = &mut dock_state.state
+    {
+        dock::focus_or_create_tab(
+            state,
+            || dock::Tab::Achievements(TabType),
+            dock::ReplaceTab(|tab| matches!(tab, dock::Tab::Achievements(_)))
+                .or_always(dock::NewSurface),
+        );
+    }
+}
+
+#[derive(Default)]
+struct Toasts {
+    /// Achievements waiting to be notified.
+    queue:   VecDeque<&'static achievement::Definition>,
+    /// The currently displayed achievement and the real time it was first displayed.
+    current: Option<(&'static achievement::Definition, Duration)>,
+}
+
+fn show_toasts_system(
+    mut contexts: EguiContexts,
+    mut achievements: ResMut<Achievements>,
+    real_time: Res<Time<time::Real>>,
+    mut toasts: Local<Toasts>,
+) {
+    if !achievements.newly_unlocked.is_empty() {
+        toasts.queue.extend(achievements.newly_unlocked.drain(..));
+    }
+
+    let now = real_time.elapsed();
+    if toasts.current.is_some_and(|(_, shown_at)| now.saturating_sub(shown_at) >= TOAST_DURATION) {
+        toasts.current = None;
+    }
+    if toasts.current.is_none() {
+        toasts.current = toasts.queue.pop_front().map(|def| (def, now));
+    }
+    let Some((def, _)) = toasts.current else { return };
+
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    egui::Area::new(egui::Id::new("achievement-toast"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-20., -20.))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(icons::ICON_EMOJI_EVENTS).size(24.));
+                    ui.vertical(|ui| {
+                        ui.small("Achievement unlocked");
+                        ui.strong(def.title);
+                        ui.label(def.description);
+                    });
+                });
+            });
+        });
+    ctx.request_repaint_after(Duration::from_millis(200));
+}
+
+pub struct TabType;
+
+#[derive(SystemParam)]
+pub struct UiParams<'w> {
+    achievements: Res<'w, Achievements>,
+}
+
+impl dock::TabType for TabType {
+    type TitleSystemParam<'w, 's> = ();
+    fn title(&self, (): ()) -> String { "Achievements".into() }
+
+    type UiSystemParam<'w, 's> = UiParams<'w>;
+    fn ui(&mut self, params: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
+        let progress = &params.achievements.progress;
+        let unlocked_count = achievement::REGISTRY
+            .iter()
+            .filter(|def| progress.unlocked.contains_key(def.id))
+            .count();
+        ui.label(format!("{unlocked_count}/{} unlocked", achievement::REGISTRY.len()));
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for def in achievement::REGISTRY {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        let unlocked_at = progress.unlocked.get(def.id);
+                        let icon = if unlocked_at.is_some() {
+                            icons::ICON_EMOJI_EVENTS
+                        } else {
+                            icons::ICON_LOCK
+                        };
+                        ui.label(egui::RichText::new(icon).size(20.));
+                        ui.vertical(|ui| {
+                            ui.strong(def.title);
+                            ui.label(def.description);
+                            if let Some(unlocked_at) = unlocked_at {
+                                ui.small(format!("Unlocked {}", unlocked_at.strftime("%Y-%m-%d")));
+                            } else {
+                                let current = progress.get(def.metric).min(def.target);
+                                #[expect(clippy::cast_precision_loss, reason = "display only")]
+                                let fraction = current as f32 / def.target as f32;
+                                ui.add(
+                                    egui::ProgressBar::new(fraction)
+                                        .text(format!("{current}/{}", def.target)),
+                                );
+                            }
+                        });
+                    });
+                });
+            }
+        });
+    }
+
+    type OnCloseSystemParam<'w, 's> = ();
+
+    type PrepareRenderSystemParam<'w, 's> = ();
+}
@@ -0,0 +1,159 @@
+//! Clickable callsigns in UI text.
+//!
+//! The [`Index`] maps the display name of each object to its entity.
+//! [`Links`] renders text with every indexed callsign as a link,
+//! which selects the object and centers the radar view on it when clicked.
+
+use std::collections::HashMap;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::lifecycle::RemovedComponents;
+use bevy::ecs::message::{Message, MessageReader, MessageWriter};
+use bevy::ecs::query::Changed;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, Res, ResMut, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::object;
+
+use crate::render::{self, object_info};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Index>();
+        app.add_message::<FocusMessage>();
+        app.add_systems(
+            app::Update,
+            (index_system, focus_system).in_set(render::SystemSets::Update),
+        );
+    }
+}
+
+/// Maps the display name of each object to its entity.
+#[derive(Resource, Default)]
+pub struct Index(HashMap<String, Entity>);
+
+impl Index {
+    /// Returns the object displayed as `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Entity> { self.0.get(name).copied() }
+}
+
+/// Requests to select an object and center the radar view on it.
+#[derive(Message)]
+pub struct FocusMessage(pub Entity);
+
+fn index_system(
+    mut index: ResMut<Index>,
+    changed_query: Query<(), Changed<object::Display>>,
+    mut removed: RemovedComponents<object::Display>,
+    display_query: Query<(Entity, &object::Display)>,
+) {
+    if changed_query.is_empty() && removed.read().count() == 0 {
+        return;
+    }
+    index.0 =
+        display_query.iter().map(|(entity, display)| (display.name.clone(), entity)).collect();
+}
+
+fn focus_system(
+    mut messages: MessageReader<FocusMessage>,
+    mut current_object: ResMut<object_info::CurrentObject>,
+) {
+    if let Some(&FocusMessage(entity)) = messages.read().last() {
+        current_object.0 = Some(entity);
+    }
+}
+
+/// A run of text, either plain or a callsign of an indexed object.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Plain(&'a str),
+    Callsign(&'a str, Entity),
+}
+
+/// Splits `text` into plain runs and callsigns found in `index`.
+///
+/// Punctuation around a word is not part of the callsign.
+#[must_use]
+pub fn segments<'a>(text: &'a str, index: &Index) -> Vec<Segment<'a>> {
+    let mut segments = Vec::new();
+    let mut plain_start = 0;
+
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let word_start = offset;
+        offset += word.len();
+
+        let trimmed = word.trim_matches(|ch: char| !ch.is_alphanumeric());
+        let Some(entity) = index.get(trimmed) else { continue };
+
+        let callsign_start = word_start + word.find(trimmed).unwrap_or(0);
+        if plain_start < callsign_start {
+            segments.push(Segment::Plain(&text[plain_start..callsign_start]));
+        }
+        segments.push(Segment::Callsign(trimmed, entity));
+        plain_start = callsign_start + trimmed.len();
+    }
+    if plain_start < text.len() {
+        segments.push(Segment::Plain(&text[plain_start..]));
+    }
+    segments
+}
+
+/// Renders callsigns as links that focus the object.
+#[derive(SystemParam)]
+pub struct Links<'w> {
+    index: Res<'w, Index>,
+    focus: MessageWriter<'w, FocusMessage>,
+}
+
+impl Links<'_> {
+    /// Shows `name` as a link if it is the callsign of an object, or as a plain label otherwise.
+    pub fn link(&mut self, ui: &mut egui::Ui, name: &str) -> egui::Response {
+        let Some(entity) = self.index.get(name) else { return ui.label(name) };
+        let resp = ui.link(name).on_hover_text("Select and center on the radar view");
+        if resp.clicked() {
+            self.focus.write(FocusMessage(entity));
+        }
+        resp
+    }
+
+    /// Shows `text` with every callsign as a link.
+    ///
+    /// The segments are added as separate widgets,
+    /// so this should be called within a horizontal layout without item spacing,
+    /// e.g. [`Links::paragraph`].
+    pub fn text(&mut self, ui: &mut egui::Ui, text: &str, color: egui::Color32) {
+        for segment in segments(text, &self.index) {
+            match segment {
+                Segment::Plain(plain) => {
+                    ui.label(egui::RichText::new(plain).color(color));
+                }
+                Segment::Callsign(name, entity) => {
+                    let resp = ui
+                        .link(egui::RichText::new(name).color(color).strong())
+                        .on_hover_text("Select and center on the radar view");
+                    if resp.clicked() {
+                        self.focus.write(FocusMessage(entity));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Shows `text` in a wrapped paragraph with every callsign as a link.
+    pub fn paragraph(&mut self, ui: &mut egui::Ui, text: &str) {
+        let color = ui.visuals().text_color();
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.;
+            self.text(ui, text, color);
+        });
+    }
+}
@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use bevy::ecs::world::World;
+
+use super::{Index, Segment, segments};
+
+#[test]
+fn test_segments_link_indexed_callsigns() {
+    let mut world = World::new();
+    let abc = world.spawn_empty().id();
+    let def = world.spawn_empty().id();
+    let index = Index(HashMap::from([("ABC123".into(), abc), ("DEF456".into(), def)]));
+
+    assert_eq!(
+        segments("ABC123, traffic DEF456 (A320) ahead", &index),
+        [
+            Segment::Callsign("ABC123", abc),
+            Segment::Plain(", traffic "),
+            Segment::Callsign("DEF456", def),
+            Segment::Plain(" (A320) ahead"),
+        ],
+    );
+    assert_eq!(
+        segments("Land (ABC123)", &index),
+        [Segment::Plain("Land ("), Segment::Callsign("ABC123", abc), Segment::Plain(")")],
+    );
+    assert_eq!(segments("No callsigns here", &index), [Segment::Plain("No callsigns here")]);
+}
@@ -0,0 +1,151 @@
+//! Screenshot and timelapse capture.
+//!
+//! Screenshots are taken with the screenshot hotkey.
+//! Timelapse mode, toggled with its own hotkey,
+//! captures an image every configured interval of simulated time
+//! into a new directory for each timelapse session.
+//! File names encode the simulated time elapsed at the moment of capture.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::camera::RenderTarget;
+use bevy::ecs::query::With;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use jiff::Timestamp;
+
+use super::{SystemSets, twodim};
+use crate::{ConfigManager, input};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("capture");
+        app.init_resource::<Timelapse>();
+        app.add_systems(app::Update, capture_system.in_set(SystemSets::Update));
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Capture only the radar view instead of the whole window including the UI.
+    #[config(default = false)]
+    radar_only:         bool,
+    /// Simulated time between two timelapse frames.
+    #[config(default = Duration::from_mins(5), min = Duration::from_secs(10), max = Duration::from_hours(2))]
+    timelapse_interval: Duration,
+}
+
+/// State of the current timelapse session.
+#[derive(Resource, Default)]
+pub struct Timelapse {
+    /// The active session, if timelapse capture is running.
+    pub session: Option<TimelapseSession>,
+}
+
+pub struct TimelapseSession {
+    /// Directory containing the image sequence.
+    pub dir:      PathBuf,
+    /// Number of frames captured so far.
+    pub frames:   u32,
+    /// Simulated time at which the next frame is captured.
+    next_capture: Duration,
+}
+
+fn capture_system(
+    mut commands: Commands,
+    hotkeys: Res<input::Hotkeys>,
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    mut timelapse: ResMut<Timelapse>,
+    radar_query: Query<&RenderTarget, With<twodim::camera::UiState>>,
+) {
+    let conf = conf.read();
+    let elapsed = time.elapsed();
+
+    let target = || {
+        if conf.radar_only
+            && let Some(RenderTarget::Image(image)) = radar_query.iter().next()
+        {
+            Screenshot::image(image.handle.clone())
+        } else {
+            Screenshot::primary_window()
+        }
+    };
+
+    if hotkeys.screenshot {
+        let mut path = output_dir();
+        path.push(format!("omniatc-{}-{}.png", session_name(), format_sim_time(elapsed)));
+        capture(&mut commands, target(), path);
+    }
+
+    if hotkeys.timelapse {
+        if let Some(session) = timelapse.session.take() {
+            bevy::log::info!(
+                "Timelapse stopped after {} frames in {}",
+                session.frames,
+                session.dir.display()
+            );
+        } else {
+            let mut dir = output_dir();
+            dir.push(format!("omniatc-timelapse-{}", session_name()));
+            bevy::log::info!("Timelapse started in {}", dir.display());
+            timelapse.session = Some(TimelapseSession { dir, frames: 0, next_capture: elapsed });
+        }
+    }
+
+    if let Some(session) = &mut timelapse.session
+        && elapsed >= session.next_capture
+    {
+        let mut path = session.dir.clone();
+        path.push(format!("{:05}-{}.png", session.frames, format_sim_time(elapsed)));
+        capture(&mut commands, target(), path);
+
+        session.frames += 1;
+        session.next_capture = elapsed + conf.timelapse_interval;
+    }
+}
+
+fn capture(commands: &mut Commands, screenshot: Screenshot, path: PathBuf) {
+    #[cfg(not(target_family = "wasm"))]
+    if let Some(dir) = path.parent()
+        && let Err(err) = std::fs::create_dir_all(dir)
+    {
+        bevy::log::error!("Cannot create screenshot directory {}: {err}", dir.display());
+        return;
+    }
+
+    commands.spawn(screenshot).observe(save_to_disk(path));
+}
+
+/// Directory to write captured images to.
+///
+/// On the web, only the file name is used for the download.
+fn output_dir() -> PathBuf {
+    if let Some(mut path) = dirs::picture_dir() {
+        path.push("omniatc");
+        path
+    } else if let Some(mut path) = dirs::data_dir() {
+        path.push("omniatc");
+        path.push("screenshots");
+        path
+    } else {
+        PathBuf::new()
+    }
+}
+
+/// Real-world time of the capture, distinguishing files from different sessions.
+fn session_name() -> String { Timestamp::now().strftime("%Y%m%d-%H%M%S").to_string() }
+
+/// Formats the elapsed simulated time as `T+HHhMMmSSs`.
+fn format_sim_time(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("T+{:02}h{:02}m{:02}s", secs / 3600, (secs / 60) % 60, secs % 60)
+}
@@ -3,11 +3,13 @@ use bevy::ecs::component::Component;
 use bevy::ecs::query::With;
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{ResMut, Single};
+use bevy_egui::egui::Widget;
 use bevy_egui::{EguiPrimaryContextPass, egui};
 use egui_material_icons::icons;
 
 use crate::render::dock::TabPlacement;
 use crate::render::{MenuButton, MenuButtonClicked, dock};
+use crate::storage::config_profile;
 use crate::{EguiSystemSets, render};
 
 pub struct Plug;
@@ -40,7 +42,7 @@ fn open_editor_system(
     {
         dock::focus_or_create_tab(
             state,
-            || dock::Tab::ConfigEditor(TabType),
+            || dock::Tab::ConfigEditor(TabType::default()),
             dock::ReplaceTab(|tab| matches!(tab, dock::Tab::ConfigEditor(_)))
                 .or_always(dock::NewSurface),
         );
@@ -50,18 +52,69 @@ fn open_editor_system(
 #[derive(Component)]
 struct ConfigMenuButtonMarker;
 
-pub struct TabType;
+#[derive(Default)]
+pub struct TabType {
+    /// Name of the profile to save or load, as typed by the user.
+    profile_name: String,
+}
 
 impl dock::TabType for TabType {
     type TitleSystemParam<'w, 's> = ();
     fn title(&self, (): ()) -> String { "Settings".into() }
 
-    type UiSystemParam<'w, 's> = bevy_mod_config::manager::egui::Display<'w, 's>;
-    fn ui(&mut self, mut param: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
-        param.show(ui);
+    type UiSystemParam<'w, 's> =
+        (bevy_mod_config::manager::egui::Display<'w, 's>, ResMut<'w, config_profile::Profiles>);
+    fn ui(
+        &mut self,
+        (mut display, mut profiles): Self::UiSystemParam<'_, '_>,
+        ui: &mut egui::Ui,
+        _order: usize,
+    ) {
+        self.show_profiles(&mut profiles, ui);
+        ui.separator();
+        display.show(ui);
     }
 
     type OnCloseSystemParam<'w, 's> = ();
 
     type PrepareRenderSystemParam<'w, 's> = ();
 }
+
+impl TabType {
+    fn show_profiles(&mut self, profiles: &mut config_profile::Profiles, ui: &mut egui::Ui) {
+        ui.heading("Profiles");
+        ui.label(format!("Active profile: {}", profiles.active));
+
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.profile_name).hint_text(&profiles.active).ui(ui);
+            let name = if self.profile_name.is_empty() {
+                profiles.active.clone()
+            } else {
+                self.profile_name.clone()
+            };
+            if ui.button("Save").clicked() {
+                profiles.save(name.clone());
+            }
+            if ui.add_enabled(profiles.names.contains(&name), egui::Button::new("Load")).clicked() {
+                profiles.load(name);
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            let mut selected = None;
+            for name in &profiles.names {
+                if ui.selectable_label(*name == profiles.active, name).clicked() {
+                    selected = Some(name.clone());
+                }
+            }
+            if let Some(name) = selected {
+                profiles.load(name);
+            }
+        });
+
+        ui.checkbox(
+            &mut profiles.apply_map_settings,
+            "Apply display settings recommended by the map",
+        );
+    }
+}
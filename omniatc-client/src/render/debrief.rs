@@ -0,0 +1,290 @@
+//! Debrief window shown when the scenario outcome is resolved.
+
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Query, Res, ResMut, SystemParam};
+use bevy::time::{self, Time};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use math::{Heading, Length};
+use omniatc::level::aerodrome::Aerodrome;
+use omniatc::level::quest::outcome::Outcome;
+use omniatc::level::quest::{self, Quest};
+use omniatc::level::score::Stats;
+use omniatc::level::session::{self, bookmark};
+use omniatc::level::track;
+use omniatc::load;
+use strum::IntoEnumIterator;
+
+use crate::EguiSystemSets;
+use crate::render::callsign;
+use crate::storage::library::{Kind, Library};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            setup_window_system.in_set(EguiSystemSets::Debrief),
+        );
+    }
+}
+
+/// Span of the radar view when jumping to an incident.
+const JUMP_VIEW_LENGTH: Length<f32> = Length::from_nm(15.0);
+
+#[derive(Default)]
+struct State {
+    /// The outcome that the window was last shown for.
+    shown:     Option<Outcome>,
+    /// Whether the user has dismissed the window for the current outcome.
+    dismissed: bool,
+    /// Whether the game was paused by the debrief.
+    paused:    bool,
+}
+
+#[derive(SystemParam)]
+struct DebriefParams<'w, 's> {
+    outcome:         Res<'w, Outcome>,
+    stats:           Res<'w, Stats>,
+    meta:            Res<'w, load::LoadedMeta>,
+    time:            ResMut<'w, Time<time::Virtual>>,
+    camera_advice:   ResMut<'w, load::CameraAdvice>,
+    library:         ResMut<'w, Library>,
+    tracks:          Res<'w, track::Log>,
+    session:         Res<'w, session::Log>,
+    quest_query:     Query<'w, 's, &'static Quest, With<quest::Failed>>,
+    aerodrome_query: Query<'w, 's, (Entity, &'static Aerodrome)>,
+    links:           callsign::Links<'w>,
+}
+
+fn setup_window_system(
+    mut contexts: EguiContexts,
+    mut params: DebriefParams,
+    mut state: Local<State>,
+) {
+    let outcome = *params.outcome;
+    if state.shown != Some(outcome) {
+        *state = State { shown: Some(outcome), ..Default::default() };
+        if outcome.is_resolved() && !params.time.is_paused() {
+            params.time.pause();
+            state.paused = true;
+        }
+    }
+
+    let (title, time) = match outcome {
+        Outcome::Pending => return,
+        Outcome::Succeeded { time } => ("Scenario complete", time),
+        Outcome::Failed { time } => ("Scenario failed", time),
+    };
+    if state.dismissed {
+        return;
+    }
+
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+
+    let mut action = None;
+    egui::Window::new(title)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.)
+        .show(ctx, |ui| {
+            if let Some(meta) = &params.meta.0 {
+                ui.heading(&meta.title);
+            }
+            ui.label(format!("Duration: {}", format_duration(time)));
+
+            ui.separator();
+            show_score(ui, &params);
+
+            ui.separator();
+            show_events(ui, &mut params);
+
+            if let Outcome::Failed { .. } = outcome {
+                for quest in &params.quest_query {
+                    ui.label(format!("Failed: {}", quest.title));
+                }
+            }
+
+            let next_scenario = params.meta.0.as_ref().and_then(|meta| meta.next_scenario.clone());
+            if let (Outcome::Succeeded { .. }, Some(next)) = (outcome, &next_scenario) {
+                ui.separator();
+                ui.strong("Unlocked");
+                let title = params
+                    .library
+                    .scenarios
+                    .iter()
+                    .find(|scenario| &scenario.id == next)
+                    .map_or(next.as_str(), |scenario| scenario.title.as_str());
+                ui.label(title);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if let Some(meta) = &params.meta.0
+                    && ui.button("Retry").clicked()
+                {
+                    action = Some(Action::Load(meta.id.clone()));
+                }
+                if let (Outcome::Succeeded { .. }, Some(next)) = (outcome, next_scenario)
+                    && ui.button("Continue").clicked()
+                {
+                    action = Some(Action::Load(next));
+                }
+                if ui.button("Keep playing").clicked() {
+                    action = Some(Action::Dismiss);
+                }
+            });
+            show_export(ui, &mut params);
+            if let Some(status) = &params.library.status {
+                ui.small(status);
+            }
+        });
+
+    match action {
+        Some(Action::Load(id)) => {
+            params.library.load(Kind::Scenario, id);
+            resume(&mut state, &mut params.time);
+        }
+        Some(Action::Dismiss) => {
+            state.dismissed = true;
+            resume(&mut state, &mut params.time);
+        }
+        None => {}
+    }
+}
+
+enum Action {
+    Load(String),
+    Dismiss,
+}
+
+fn resume(state: &mut State, time: &mut Time<time::Virtual>) {
+    if state.paused {
+        time.unpause();
+        state.paused = false;
+    }
+}
+
+fn show_export(ui: &mut egui::Ui, params: &mut DebriefParams) {
+    ui.horizontal(|ui| {
+        if ui
+            .button("Export result")
+            .on_hover_text("Save the session result for leaderboard submission")
+            .clicked()
+        {
+            params.library.export_session_result();
+        }
+
+        let has_origin = params.tracks.origin.is_some();
+        for format in track::export::Format::iter() {
+            if ui
+                .add_enabled(has_origin, egui::Button::new(format!("Export tracks ({format})")))
+                .on_hover_text("Save the flown tracks for viewing in a map viewer")
+                .on_disabled_hover_text("The map has no geographic origin")
+                .clicked()
+            {
+                params.library.export_tracks(format);
+            }
+        }
+    });
+}
+
+fn show_score(ui: &mut egui::Ui, params: &DebriefParams) {
+    let stats = &*params.stats;
+    egui::Grid::new("debrief-score").num_columns(2).show(ui, |ui| {
+        let mut row = |label: &str, value: String| {
+            ui.label(label);
+            ui.label(value);
+            ui.end_row();
+        };
+        row("Score", stats.total.0.to_string());
+        row("Runway arrivals", stats.num_runway_arrivals.to_string());
+        row("Apron arrivals", stats.num_apron_arrivals.to_string());
+        row("Departures", stats.num_departures.to_string());
+        row("Conflicts", stats.num_conflicts.to_string());
+        row("Time in conflict", format_duration(stats.total_conflict_time));
+        row(
+            "Go-arounds",
+            format!(
+                "{} (player-issued {}, unstable {}, runway occupied {}, weather {}, bird strike \
+                 {})",
+                stats.go_arounds.total(),
+                stats.go_arounds.player_issued,
+                stats.go_arounds.unstable,
+                stats.go_arounds.runway_occupied,
+                stats.go_arounds.weather,
+                stats.go_arounds.bird_strike,
+            ),
+        );
+        row(
+            "Pilot requests approved",
+            format!(
+                "{}/{}",
+                stats.num_pilot_requests_approved,
+                stats.num_pilot_requests_approved + stats.num_pilot_requests_denied,
+            ),
+        );
+    });
+
+    if params.aerodrome_query.iter().len() > 1 {
+        for (entity, aerodrome) in &params.aerodrome_query {
+            let stats = stats.aerodromes.get(&entity).copied().unwrap_or_default();
+            ui.label(format!(
+                "{}: {} arrivals, {} departures",
+                &aerodrome.code,
+                stats.num_runway_arrivals + stats.num_apron_arrivals,
+                stats.num_departures,
+            ));
+        }
+    }
+}
+
+fn show_events(ui: &mut egui::Ui, params: &mut DebriefParams) {
+    let bookmarks = &params.session.bookmarks;
+    if bookmarks.is_empty() {
+        ui.label("No incidents.");
+        return;
+    }
+
+    ui.strong("Incidents");
+    egui::ScrollArea::vertical().max_height(160.).show(ui, |ui| {
+        for bookmark in bookmarks {
+            ui.horizontal(|ui| {
+                let window = bookmark::window(bookmark);
+                ui.label(format_duration(bookmark.time)).on_hover_text(format!(
+                    "Review from {} to {}",
+                    format_duration(window.start),
+                    format_duration(window.end),
+                ));
+                ui.label(match bookmark.kind {
+                    store::BookmarkKind::Conflict => "Conflict",
+                    store::BookmarkKind::GoAround => "Go-around",
+                    store::BookmarkKind::Incursion => "Incursion",
+                    store::BookmarkKind::Emergency => "Emergency",
+                });
+                for name in &bookmark.objects {
+                    params.links.link(ui, name);
+                }
+                if ui.small_button("Jump").on_hover_text("Center the radar view here").clicked() {
+                    params.camera_advice.0 = Some(store::Camera::TwoDimension(store::Camera2d {
+                        center:       bookmark.position,
+                        up:           Heading::NORTH,
+                        scale_axis:   store::AxisDirection::X,
+                        scale_length: JUMP_VIEW_LENGTH,
+                    }));
+                }
+            });
+        }
+    });
+}
+
+pub(super) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+}
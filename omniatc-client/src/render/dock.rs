@@ -14,7 +14,10 @@ use egui_dock::tab_viewer::OnCloseResponse;
 use egui_dock::{DockArea, DockState, NodeIndex, SurfaceIndex, TabIndex};
 
 use crate::EguiSystemSets;
-use crate::render::{config_editor, level_info, messages, object_info, twodim};
+use crate::render::{
+    accessible, achievements, config_editor, file_manager, level_info, macros, messages,
+    object_info, profile, spawn_editor, twodim,
+};
 
 pub struct Plug;
 
@@ -171,13 +174,25 @@ define_tabs! {
     (p0 p0 p0) Quests(level_info::quests::TabType)
     /// Configuration editor.
     (p0 p0 p0 p0) ConfigEditor(config_editor::TabType)
+    /// Accessible text view and command line.
+    (p0 p0 p0 p0 p0) Accessible(accessible::TabType)
+    /// Stored scenarios and levels.
+    (p0 p0 p0 p0 p0 p0) FileManager(file_manager::TabType)
+    /// Instruction macro buttons.
+    (p0 p0 p0 p0 p0 p0 p0) Macros(macros::TabType)
+    /// Global achievement browser.
+    (p0 p0 p0 p0 p0 p0 p0 p0) Achievements(achievements::TabType)
+    /// Vertical profile of the selected object.
+    (p0 p0 p0 p0 p0 p0 p0 p0 p0) Profile(profile::TabType)
+    /// Spawn set editor for the loaded level.
+    (p0 p0 p0 p0 p0 p0 p0 p0 p0 p0) SpawnEditor(spawn_editor::TabType)
 
     // Repeatable tabs.
 
     /// Show information about an object.
-    (p0 p0 p0 p0 p0) ObjectInfo(object_info::TabType)
+    (p0 p0 p0 p0 p0 p0 p0 p0 p0 p0 p0) ObjectInfo(object_info::TabType)
     /// Render 2D world camera.
-    (p0 p0 p0 p0 p0 p0) TwoDimCamera(twodim::camera::TabType)
+    (p0 p0 p0 p0 p0 p0 p0 p0 p0 p0 p0 p0) TwoDimCamera(twodim::camera::TabType)
 }
 
 #[derive(Resource, Default)]
@@ -0,0 +1,261 @@
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{ResMut, Single};
+use bevy_egui::{EguiPrimaryContextPass, egui};
+use egui_material_icons::icons;
+#[cfg(feature = "debug")]
+use omniatc::level::ground;
+#[cfg(feature = "debug")]
+use strum::IntoEnumIterator;
+
+use crate::render::debrief::format_duration;
+use crate::render::dock::TabPlacement;
+use crate::render::{MenuButton, MenuButtonClicked, dock};
+use crate::storage::library::{Kind, Library};
+use crate::storage::{SaveSlotMeta, THUMBNAIL_SIZE};
+use crate::{EguiSystemSets, render};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_FOLDER_OPEN,
+                title:    "Files".into(),
+                group:    render::MenuButtonGroup::Game,
+                priority: 95,
+            },
+            FilesMenuButtonMarker,
+        ));
+
+        app.add_systems(EguiPrimaryContextPass, open_tab_system.in_set(EguiSystemSets::ManageTabs));
+    }
+}
+
+fn open_tab_system(
+    mut dock_state: ResMut<dock::State>,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<FilesMenuButtonMarker>>,
+    mut library: ResMut<Library>,
+) {
+    if menu_button_clicked.consume()
+        && let Some(state) = &mut dock_state.state
+    {
+        library.refresh();
+        dock::focus_or_create_tab(
+            state,
+            || dock::Tab::FileManager(TabType::default()),
+            dock::ReplaceTab(|tab| matches!(tab, dock::Tab::FileManager(_)))
+                .or_always(dock::NewSurface),
+        );
+    }
+}
+
+#[derive(Component)]
+struct FilesMenuButtonMarker;
+
+#[derive(Default)]
+pub struct TabType {
+    /// Path of the file to import, as typed by the user.
+    #[cfg_attr(target_family = "wasm", expect(dead_code, reason = "browsers use a file picker"))]
+    import_path: String,
+    /// Name of the save slot to write, as typed by the user.
+    save_name:   String,
+    /// Whether callsigns are anonymized in exported bug reports.
+    anonymize:   bool,
+}
+
+impl dock::TabType for TabType {
+    type TitleSystemParam<'w, 's> = ();
+    fn title(&self, (): ()) -> String { "Files".into() }
+
+    type UiSystemParam<'w, 's> = ResMut<'w, Library>;
+    fn ui(&mut self, mut library: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
+        self.show_import(&mut library, ui);
+        ui.horizontal(|ui| {
+            if ui.button("Report issue").clicked() {
+                library.export_bug_report(self.anonymize);
+            }
+            ui.checkbox(&mut self.anonymize, "Anonymize callsigns");
+        });
+        #[cfg(feature = "debug")]
+        ui.horizontal(|ui| {
+            for format in ground::export::Format::iter() {
+                if ui
+                    .button(format!("Export ground graph ({format})"))
+                    .on_hover_text("Save the resolved taxiway network for inspection")
+                    .clicked()
+                {
+                    library.export_ground_graph(format);
+                }
+            }
+        });
+        if let Some(status) = &library.status {
+            ui.label(status);
+        }
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let library = &mut *library;
+
+            ui.heading("Save slots");
+            self.show_save(library, ui);
+            if let Some((action, id)) = show_save_slots(ui, &library.save_slots) {
+                match action {
+                    SlotAction::Entry(action) => action.request(library, Kind::SaveSlot, id),
+                    SlotAction::Delete => library.delete_save_slot(id),
+                }
+            }
+
+            ui.heading("Saved levels");
+            let levels = library.levels.iter().map(|level| (level.id.as_str(), &level.title));
+            let action = show_entries(ui, "levels", levels);
+            if let Some((action, id)) = action {
+                action.request(library, Kind::Level, id);
+            }
+
+            ui.heading("Scenarios");
+            let scenarios =
+                library.scenarios.iter().map(|scenario| (scenario.id.as_str(), &scenario.title));
+            let action = show_entries(ui, "scenarios", scenarios);
+            if let Some((action, id)) = action {
+                action.request(library, Kind::Scenario, id);
+            }
+        });
+    }
+
+    type OnCloseSystemParam<'w, 's> = ();
+
+    type PrepareRenderSystemParam<'w, 's> = ();
+}
+
+impl TabType {
+    fn show_save(&mut self, library: &mut Library, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.save_name).hint_text("Slot name"));
+            let enabled = !self.save_name.trim().is_empty();
+            if ui.add_enabled(enabled, egui::Button::new("Save")).clicked() {
+                library.save(self.save_name.clone());
+            }
+            #[cfg(feature = "cloud-sync")]
+            if ui.button("Sync").clicked() {
+                library.sync();
+            }
+        });
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn show_import(&mut self, library: &mut Library, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Import").clicked() {
+                library.import(String::new());
+            }
+            if ui.button("Refresh").clicked() {
+                library.refresh();
+            }
+        });
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn show_import(&mut self, library: &mut Library, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.import_path).hint_text("Path to .osav"));
+            if ui.add_enabled(!self.import_path.is_empty(), egui::Button::new("Import")).clicked() {
+                library.import(self.import_path.clone());
+            }
+            if ui.button("Refresh").clicked() {
+                library.refresh();
+            }
+        });
+    }
+}
+
+enum Action {
+    Load,
+    Export,
+}
+
+impl Action {
+    fn request(self, library: &mut Library, kind: Kind, id: String) {
+        match self {
+            Self::Load => library.load(kind, id),
+            Self::Export => library.export(kind, id),
+        }
+    }
+}
+
+fn show_entries<'a>(
+    ui: &mut egui::Ui,
+    grid_id: &str,
+    entries: impl Iterator<Item = (&'a str, &'a String)>,
+) -> Option<(Action, String)> {
+    let mut action = None;
+    egui::Grid::new(grid_id).striped(true).show(ui, |ui| {
+        for (id, title) in entries {
+            ui.label(title).on_hover_text(id);
+            if ui.button("Load").clicked() {
+                action = Some((Action::Load, id.to_string()));
+            }
+            if ui.button("Export").clicked() {
+                action = Some((Action::Export, id.to_string()));
+            }
+            ui.end_row();
+        }
+    });
+    action
+}
+
+enum SlotAction {
+    Entry(Action),
+    Delete,
+}
+
+/// Side length of the thumbnail drawn for each save slot, in points.
+const THUMBNAIL_POINTS: f32 = 48.;
+
+fn show_save_slots(ui: &mut egui::Ui, slots: &[SaveSlotMeta]) -> Option<(SlotAction, String)> {
+    let mut action = None;
+    egui::Grid::new("save_slots").striped(true).show(ui, |ui| {
+        for slot in slots {
+            show_thumbnail(ui, &slot.thumbnail);
+            ui.vertical(|ui| {
+                ui.strong(&slot.name).on_hover_text(&slot.id);
+                ui.label(&slot.map_title);
+                ui.small(format!(
+                    "Saved {}, sim time {}, score {}",
+                    slot.saved.strftime("%Y-%m-%d %H:%M"),
+                    format_duration(slot.sim_time),
+                    slot.score.0,
+                ));
+            });
+            if ui.button("Load").clicked() {
+                action = Some((SlotAction::Entry(Action::Load), slot.id.clone()));
+            }
+            if ui.button("Export").clicked() {
+                action = Some((SlotAction::Entry(Action::Export), slot.id.clone()));
+            }
+            if ui.button("Delete").clicked() {
+                action = Some((SlotAction::Delete, slot.id.clone()));
+            }
+            ui.end_row();
+        }
+    });
+    action
+}
+
+/// Draws the object positions of a save slot as dots in a small square.
+fn show_thumbnail(ui: &mut egui::Ui, thumbnail: &[[u8; 2]]) {
+    let (rect, _) =
+        ui.allocate_exact_size(egui::Vec2::splat(THUMBNAIL_POINTS), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 2., visuals.extreme_bg_color);
+
+    let scale = THUMBNAIL_POINTS / f32::from(THUMBNAIL_SIZE);
+    for &[x, y] in thumbnail {
+        let pos = rect.min + egui::vec2(f32::from(x), f32::from(y)) * scale;
+        painter.circle_filled(pos, 1.5, visuals.strong_text_color());
+    }
+}
@@ -0,0 +1,164 @@
+//! Overlay panel listing the active scenario goals with their live progress.
+
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::entity::{Entity, EntityHashMap, EntityHashSet};
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Query, Res};
+use bevy::time::{self, Time};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use omniatc::level::quest::{self, Quest};
+use omniatc::level::score::Stats;
+
+use crate::{ConfigManager, EguiSystemSets};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("goals-panel");
+        app.add_systems(
+            EguiPrimaryContextPass,
+            setup_window_system.in_set(EguiSystemSets::GoalsPanel),
+        );
+    }
+}
+
+#[derive(QueryData)]
+struct GoalQuery {
+    entity:   Entity,
+    quest:    &'static Quest,
+    active:   Has<quest::Active>,
+    counter:  quest::condition::Counter,
+    progress: quest::progress::ConditionQuery,
+}
+
+#[derive(Default)]
+struct Completions {
+    /// Goals that were displayed in the previous frame.
+    displayed: EntityHashSet,
+    /// Real time at which recently completed goals were completed.
+    completed: EntityHashMap<Duration>,
+}
+
+fn setup_window_system(
+    mut contexts: EguiContexts,
+    conf: ReadConfig<Conf>,
+    quest_query: Query<GoalQuery>,
+    stats: Res<Stats>,
+    virtual_time: Res<Time<time::Virtual>>,
+    real_time: Res<Time<time::Real>>,
+    mut completions: Local<Completions>,
+) {
+    let conf = conf.read();
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    let now = real_time.elapsed();
+
+    let mut goals: Vec<_> =
+        quest_query.iter().filter(|goal| goal.quest.class.display_in_list()).collect();
+    goals.sort_by_key(|goal| goal.quest.index);
+
+    for goal in &goals {
+        if completions.displayed.contains(&goal.entity) && goal.counter.count() == 0 {
+            completions.completed.insert(goal.entity, now);
+        }
+    }
+    completions
+        .completed
+        .retain(|_, &mut completed_at| now.saturating_sub(completed_at) < conf.completion_display);
+    completions.displayed =
+        goals.iter().filter(|goal| goal.active).map(|goal| goal.entity).collect();
+
+    goals.retain(|goal| goal.active || completions.completed.contains_key(&goal.entity));
+    if !conf.enabled || goals.is_empty() {
+        return;
+    }
+
+    let default_pos = ctx.content_rect().right_top() + egui::vec2(-20., 40.);
+    egui::Window::new("Goals")
+        .pivot(egui::Align2::RIGHT_TOP)
+        .default_pos(default_pos)
+        .default_width(260.)
+        .resizable(false)
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_unmultiplied(0, 0, 0, 200),
+            inner_margin: egui::Margin::same(8),
+            ..Default::default()
+        })
+        .show(ctx, |ui| {
+            for goal in goals {
+                if let Some(&completed_at) = completions.completed.get(&goal.entity) {
+                    let fract = now.saturating_sub(completed_at).as_secs_f32()
+                        / conf.completion_display.as_secs_f32();
+                    show_completed(ui, goal.quest, fract);
+                    ui.ctx().request_repaint();
+                } else {
+                    show_active(ui, &goal, &stats, virtual_time.elapsed());
+                }
+            }
+        });
+}
+
+fn show_active(ui: &mut egui::Ui, goal: &GoalQueryItem, stats: &Stats, now: Duration) {
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.strong(&goal.quest.title);
+
+        let total = goal.quest.conditions.len();
+        let remaining = usize::try_from(goal.counter.count()).unwrap_or(usize::MAX);
+        if total > 1 {
+            ui.small(format!(
+                "{done}/{total} conditions met",
+                done = total.saturating_sub(remaining)
+            ));
+        }
+
+        for progress in goal.progress.list(stats, now) {
+            let text = match progress {
+                quest::progress::Progress::Count { label, current, target } => {
+                    format!("{label}: {current}/{target}")
+                }
+                quest::progress::Progress::Score { current, target } => {
+                    format!("Score: {}/{}", current.0, target.0)
+                }
+                quest::progress::Progress::Countdown { label, remaining, .. } => {
+                    let secs = remaining.as_secs();
+                    format!(
+                        "{label}: {minutes}:{seconds:02} remaining",
+                        minutes = secs / 60,
+                        seconds = secs % 60
+                    )
+                }
+            };
+            let mut bar = egui::ProgressBar::new(progress.fraction()).text(text);
+            if let quest::progress::Progress::Countdown { failing: true, .. } = progress {
+                bar = bar.fill(egui::Color32::DARK_RED);
+            }
+            ui.add(bar);
+        }
+    });
+}
+
+fn show_completed(ui: &mut egui::Ui, quest: &Quest, fract: f32) {
+    let alpha = 1.0 - fract.clamp(0.0, 1.0);
+    let fill = egui::Color32::from_rgb(40, 160, 60).gamma_multiply(alpha);
+    egui::Frame::group(ui.style()).fill(fill).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(egui_material_icons::icons::ICON_CHECK_CIRCLE).strong());
+            ui.strong(&quest.title);
+        });
+        ui.label("Completed");
+    });
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Whether to display the goals panel.
+    #[config(default = true)]
+    enabled:            bool,
+    /// Duration to keep completed goals visible in the panel.
+    #[config(default = Duration::from_secs(3), min = Duration::from_millis(100), max = Duration::from_secs(30))]
+    completion_display: Duration,
+}
@@ -11,10 +11,12 @@ use crate::render::dock::{self, Tab};
 use crate::render::object_info::CurrentObjectSelectorSystemSet;
 
 mod camera;
+mod departure_queues;
 mod diagnostics;
 pub(super) mod objects;
 pub(super) mod quests;
 mod score;
+mod sectors;
 mod time;
 
 pub struct Plug;
@@ -69,9 +71,11 @@ pub struct LevelWriteParams<'w, 's> {
         'w,
         's,
         (
-            score::WriteScoreParams<'w>,
+            score::WriteScoreParams<'w, 's>,
             time::WriteTimeParams<'w, 's>,
             camera::WriteCameraParams<'w, 's>,
+            sectors::WriteSectorsParams<'w, 's>,
+            departure_queues::WriteDepartureQueuesParams<'w, 's>,
             diagnostics::WriteDiagnosticsParams<'w>,
             // NOTE: remember to update each_write_params upon adding an entry here
         ),
@@ -85,6 +89,8 @@ macro_rules! each_write_params {
         $mac!(set.ps.p1(), $state);
         $mac!(set.ps.p2(), $state);
         $mac!(set.ps.p3(), $state);
+        $mac!(set.ps.p4(), $state);
+        $mac!(set.ps.p5(), $state);
     };
 }
 
@@ -125,7 +131,7 @@ pub(super) fn create_splits(dock: &mut DockState<Tab>) {
             [
                 Tab::LevelInfo(ScalarTabType),
                 Tab::Quests(quests::TabType),
-                Tab::ConfigEditor(config_editor::TabType),
+                Tab::ConfigEditor(config_editor::TabType::default()),
             ]
             .into(),
         ),
@@ -0,0 +1,88 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::{Commands, Query, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::departure_queue::{self, ResequenceCommand};
+use omniatc::level::instr::{self, CommandsExt};
+use omniatc::level::object;
+use omniatc::level::route::Route;
+use omniatc::level::waypoint::Waypoint;
+
+use super::WriteParams;
+use crate::render::twodim::departure_queue::entry_text;
+
+/// Payload of a dragged queue entry.
+struct DragEntry {
+    runway: Entity,
+    index:  usize,
+}
+
+#[derive(SystemParam)]
+pub struct WriteDepartureQueuesParams<'w, 's> {
+    queue_query:  Query<'w, 's, (Entity, &'static departure_queue::Queue, &'static Waypoint)>,
+    object_query: Query<'w, 's, (&'static object::Display, &'static Route)>,
+    commands:     Commands<'w, 's>,
+}
+
+impl WriteParams for WriteDepartureQueuesParams<'_, '_> {
+    fn title(&self) -> String { "Departure queues".into() }
+
+    fn default_open() -> bool { false }
+
+    fn write(&mut self, ui: &mut egui::Ui) {
+        let mut queues: Vec<_> =
+            self.queue_query.iter().filter(|(_, queue, _)| !queue.entries.is_empty()).collect();
+        if queues.is_empty() {
+            ui.label("No departures queued");
+            return;
+        }
+        queues.sort_by(|(_, _, a), (_, _, b)| a.name.cmp(&b.name));
+
+        let mut moved = None;
+        for (runway, queue, waypoint) in queues {
+            ui.strong(&waypoint.name);
+            for (index, entry) in queue.entries.iter().enumerate() {
+                let name = self
+                    .object_query
+                    .get(entry.object)
+                    .map_or("?", |(display, _)| display.name.as_str());
+                let response = ui
+                    .dnd_drag_source(
+                        egui::Id::new(("departure_queue", entry.object)),
+                        DragEntry { runway, index },
+                        |ui| ui.label(format!("{name}  {}", entry_text(index, entry))),
+                    )
+                    .response;
+                if let Some(dragged) = response.dnd_release_payload::<DragEntry>()
+                    && dragged.runway == runway
+                    && dragged.index != index
+                {
+                    let mut order: Vec<Entity> =
+                        queue.entries.iter().map(|entry| entry.object).collect();
+                    let object = order.remove(dragged.index);
+                    order.insert(index, object);
+                    moved = Some(order);
+                }
+            }
+        }
+        ui.small("Drag departures to resequence. The first departure is cleared to line up.");
+
+        if let Some(order) = moved {
+            self.resequence(order);
+        }
+    }
+}
+
+impl WriteDepartureQueuesParams<'_, '_> {
+    /// Applies the new order of a queue,
+    /// clearing the first departure to line up if it is holding short.
+    fn resequence(&mut self, order: Vec<Entity>) {
+        if let Some(&first) = order.first()
+            && let Ok((_, route)) = self.object_query.get(first)
+            && let Some(standby) = departure_queue::lineup_standby(route)
+        {
+            self.commands
+                .send_instruction(first, instr::RemoveStandby { skip_id: standby.skip_id });
+        }
+        self.commands.queue(ResequenceCommand { order });
+    }
+}
@@ -1,14 +1,14 @@
 use std::cmp;
 
 use bevy::ecs::entity::Entity;
-use bevy::ecs::query::{QueryData, With};
+use bevy::ecs::query::{Has, QueryData, With};
 use bevy::ecs::schedule::{self, IntoScheduleConfigs, Schedulable, ScheduleConfigs};
 use bevy::ecs::system::{Local, Query, Res, ResMut, SystemParam};
 use bevy_egui::egui;
 use egui_extras::{Column, TableBuilder};
 use math::Heading;
 use omniatc::level::object::{self, Object};
-use omniatc::level::quest;
+use omniatc::level::{fuel, quest};
 use ordered_float::OrderedFloat;
 use strum::IntoEnumIterator;
 
@@ -22,6 +22,7 @@ pub struct ObjectTableData {
     display:  &'static object::Display,
     rotation: &'static object::Rotation,
     object:   &'static Object,
+    min_fuel: Has<fuel::MinimumFuel>,
 }
 
 #[derive(SystemParam)]
@@ -69,6 +70,11 @@ impl ObjectTableColumn {
 
     fn cell_value(&self, ui: &mut egui::Ui, data: &ObjectTableDataItem) -> egui::Response {
         let text: egui::WidgetText = match self {
+            Self::Name if data.min_fuel => {
+                egui::WidgetText::from(format!("{} (MINFUEL)", data.display.name))
+                    .strong()
+                    .color(egui::Color32::ORANGE)
+            }
             Self::Name => egui::WidgetText::from(data.display.name.as_str()).strong(),
             Self::Altitude => {
                 format!("{:.0}", &data.object.position.altitude().amsl().into_feet()).into()
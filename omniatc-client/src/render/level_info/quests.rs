@@ -4,12 +4,13 @@ use bevy::ecs::system::{Commands, Query, SystemParam};
 use bevy_egui::egui;
 use omniatc::level::quest::{self, Quest};
 
-use crate::render::dock;
+use crate::render::{callsign, dock};
 
 #[derive(SystemParam)]
 pub struct WriteQuestsParams<'w, 's> {
     quest_query: Query<'w, 's, QuestData>,
     commands:    Commands<'w, 's>,
+    links:       callsign::Links<'w>,
 }
 
 #[derive(QueryData)]
@@ -20,7 +21,7 @@ pub struct QuestData {
 }
 
 impl QuestDataItem<'_, '_> {
-    pub fn show(&self, ui: &mut egui::Ui, commands: &mut Commands) {
+    pub fn show(&self, ui: &mut egui::Ui, commands: &mut Commands, links: &mut callsign::Links) {
         ui.horizontal(|ui| {
             ui.heading(self.quest.title.clone());
 
@@ -31,7 +32,7 @@ impl QuestDataItem<'_, '_> {
                 }
             }
         });
-        ui.label(self.quest.description.clone());
+        links.paragraph(ui, &self.quest.description);
     }
 }
 
@@ -51,7 +52,7 @@ impl dock::TabType for TabType {
         quests.sort_by_key(|d| d.quest.index);
         for d in quests {
             egui::Frame::group(&egui::Style::default()).show(ui, |ui| {
-                d.show(ui, &mut params.commands);
+                d.show(ui, &mut params.commands, &mut params.links);
             });
         }
     }
@@ -1,15 +1,20 @@
-use bevy::ecs::system::{Res, SystemParam};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::{Query, Res, SystemParam};
 use bevy_egui::egui;
+use omniatc::level::aerodrome::Aerodrome;
 use omniatc::level::score;
+use omniatc::level::spawn::timetable;
 
 use super::WriteParams;
 
 #[derive(SystemParam)]
-pub struct WriteScoreParams<'w> {
-    score: Res<'w, score::Stats>,
+pub struct WriteScoreParams<'w, 's> {
+    score:           Res<'w, score::Stats>,
+    aerodrome_query: Query<'w, 's, (Entity, &'static Aerodrome)>,
+    timetable:       Res<'w, timetable::Stats>,
 }
 
-impl WriteParams for WriteScoreParams<'_> {
+impl WriteParams for WriteScoreParams<'_, '_> {
     fn title(&self) -> String { format!("Score: {}", self.score.total.0) }
 
     fn default_open() -> bool { true }
@@ -20,5 +25,49 @@ impl WriteParams for WriteScoreParams<'_> {
             self.score.num_runway_arrivals + self.score.num_apron_arrivals
         ));
         ui.label(format!("Departures completed: {}", self.score.num_departures));
+        ui.label(format!(
+            "Pilot requests approved: {}/{}",
+            self.score.num_pilot_requests_approved,
+            self.score.num_pilot_requests_approved + self.score.num_pilot_requests_denied,
+        ));
+
+        let go_arounds = &self.score.go_arounds;
+        if go_arounds.total() > 0 {
+            let causes: Vec<_> = [
+                ("player-issued", go_arounds.player_issued),
+                ("unstable", go_arounds.unstable),
+                ("runway occupied", go_arounds.runway_occupied),
+                ("weather", go_arounds.weather),
+                ("bird strike", go_arounds.bird_strike),
+            ]
+            .into_iter()
+            .filter(|&(_, count)| count > 0)
+            .map(|(label, count)| format!("{label} {count}"))
+            .collect();
+            ui.label(format!("Go-arounds: {} ({})", go_arounds.total(), causes.join(", ")));
+        }
+
+        if let Some(ratio) = self.timetable.on_time_ratio() {
+            let completed = self.timetable.on_time + self.timetable.delayed;
+            ui.label(format!(
+                "On-time performance: {:.0}% ({}/{}), average delay {}s",
+                ratio * 100.0,
+                self.timetable.on_time,
+                completed,
+                self.timetable.total_delay.as_secs() / u64::from(completed),
+            ));
+        }
+
+        if self.aerodrome_query.iter().len() > 1 {
+            for (entity, aerodrome) in &self.aerodrome_query {
+                let stats = self.score.aerodromes.get(&entity).copied().unwrap_or_default();
+                ui.label(format!(
+                    "{}: {} arrivals, {} departures",
+                    &aerodrome.code,
+                    stats.num_runway_arrivals + stats.num_apron_arrivals,
+                    stats.num_departures,
+                ));
+            }
+        }
     }
 }
@@ -0,0 +1,33 @@
+use bevy::camera::visibility::Visibility;
+use bevy::ecs::system::{Query, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::sector::Sector;
+
+use super::WriteParams;
+
+#[derive(SystemParam)]
+pub struct WriteSectorsParams<'w, 's> {
+    sector_query: Query<'w, 's, (&'static Sector, &'static mut Visibility)>,
+}
+
+impl WriteParams for WriteSectorsParams<'_, '_> {
+    fn title(&self) -> String { "Sectors".into() }
+
+    fn default_open() -> bool { false }
+
+    fn write(&mut self, ui: &mut egui::Ui) {
+        if self.sector_query.is_empty() {
+            ui.label("No sectors in this map");
+            return;
+        }
+
+        let mut sectors: Vec<_> = self.sector_query.iter_mut().collect();
+        sectors.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        for (sector, mut vis) in sectors {
+            let mut shown = *vis != Visibility::Hidden;
+            if ui.checkbox(&mut shown, &sector.name).changed() {
+                *vis = if shown { Visibility::Inherited } else { Visibility::Hidden };
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::ecs::system::{Local, Res, ResMut, SystemParam};
 use bevy::time::{self, Time};
 use bevy_egui::egui;
+use omniatc::level::clock;
 
 use super::WriteParams;
 use crate::input;
@@ -11,6 +12,7 @@ pub struct WriteTimeParams<'w, 's> {
     regular_speed: Local<'s, Option<f32>>,
     hotkeys:       Res<'w, input::Hotkeys>,
     paused:        Local<'s, bool>,
+    clock:         Res<'w, clock::Clock>,
 }
 
 impl WriteParams for WriteTimeParams<'_, '_> {
@@ -33,6 +35,21 @@ impl WriteParams for WriteTimeParams<'_, '_> {
             millis = elapsed.subsec_millis(),
         ));
 
+        let time_of_day = self.clock.time_of_day(elapsed).as_secs();
+        let daylight = self.clock.daylight(elapsed);
+        ui.label(format!(
+            "Clock: {hours:02}:{minutes:02} ({phase})",
+            hours = time_of_day / 3600,
+            minutes = (time_of_day / 60) % 60,
+            phase = if daylight >= 1.0 {
+                "Day"
+            } else if daylight <= 0.0 {
+                "Night"
+            } else {
+                "Twilight"
+            },
+        ));
+
         if self.hotkeys.toggle_pause {
             *self.paused = !*self.paused;
         }
@@ -0,0 +1,74 @@
+//! Progress screen shown while a level is loading,
+//! and the list of elements skipped by the last load.
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Res};
+use bevy::time::{self, Time};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use omniatc::load;
+
+use crate::EguiSystemSets;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            (show_system, issues_system).in_set(EguiSystemSets::Toasts),
+        );
+    }
+}
+
+fn show_system(
+    mut contexts: EguiContexts,
+    loading: Option<Res<load::Loading>>,
+    time: Res<Time<time::Real>>,
+) {
+    let Some(loading) = loading else { return };
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+
+    let progress = loading.progress();
+    egui::Modal::new(egui::Id::new("loading-screen")).show(ctx, |ui| {
+        ui.set_width(320.);
+        ui.heading("Loading level");
+        ui.add(egui::ProgressBar::new(progress.fraction).show_percentage().animate(true));
+        let dots = ".".repeat(time.elapsed().as_millis() as usize / 500 % 4);
+        ui.label(format!("{}{dots}", progress.stage.label()));
+    });
+    ctx.request_repaint();
+}
+
+/// Lists the [issues](load::Issues) of the last load until dismissed.
+fn issues_system(
+    mut contexts: EguiContexts,
+    loading: Option<Res<load::Loading>>,
+    issues: Res<load::Issues>,
+    mut open: Local<bool>,
+) {
+    if issues.is_changed() {
+        *open = !issues.0.is_empty();
+    }
+    if loading.is_some() || !*open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+
+    egui::Window::new(format!("Load issues ({})", issues.0.len()))
+        .id(egui::Id::new("load-issues"))
+        .open(&mut open)
+        .collapsible(true)
+        .show(ctx, |ui| {
+            ui.label("The following elements could not be loaded and were skipped:");
+            egui::ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                for issue in &issues.0 {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.strong(&issue.element);
+                        ui.label(issue.error.to_string());
+                    });
+                }
+            });
+        });
+}
@@ -0,0 +1,284 @@
+//! Instruction macros for quick clearances.
+//!
+//! A macro is a command line in the syntax of the accessible view,
+//! e.g. `D APPNE A4000 RT ILS18L`,
+//! executed against the selected object when its hotkey or button is pressed.
+//!
+//! Macro definitions are stored in the `macros` config group,
+//! so they persist with config profiles.
+//! The JSON representation of that group can be copied and pasted to share macros.
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::query::With;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{ResMut, Single, SystemParam};
+use bevy::ecs::world::World;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use egui_material_icons::icons;
+use omniatc::level::quest;
+use serde_json::{Map, Value};
+
+use crate::input::KeySet;
+use crate::render::dock::{self, TabPlacement};
+use crate::render::object_info::CurrentObjectSelectorSystemSet;
+use crate::render::{MenuButton, MenuButtonClicked, accessible};
+use crate::storage::config_profile;
+use crate::{ConfigManager, EguiSystemSets, UpdateSystemSets, render};
+
+/// Config group storing the macro definitions.
+const CONFIG_KEY: &str = "macros";
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>(CONFIG_KEY);
+        app.init_resource::<State>();
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_BOLT,
+                title:    "Macros".into(),
+                group:    render::MenuButtonGroup::Game,
+                priority: 85,
+            },
+            MenuButtonMarker,
+        ));
+
+        app.add_systems(
+            EguiPrimaryContextPass,
+            (read_hotkeys_system, open_tab_system).chain().in_set(EguiSystemSets::ManageTabs),
+        );
+        app.add_systems(
+            app::Update,
+            execute_system
+                .in_set(UpdateSystemSets::Input)
+                .in_set(CurrentObjectSelectorSystemSet)
+                .in_set(quest::UiEventWriterSystemSet),
+        );
+        app.add_systems(app::Update, transfer_system.in_set(UpdateSystemSets::Input));
+    }
+}
+
+#[derive(Config)]
+#[config(expose(read))]
+struct Conf {
+    slot1: SlotConf,
+    slot2: SlotConf,
+    slot3: SlotConf,
+    slot4: SlotConf,
+    slot5: SlotConf,
+    slot6: SlotConf,
+    slot7: SlotConf,
+    slot8: SlotConf,
+    slot9: SlotConf,
+}
+
+#[derive(Config)]
+struct SlotConf {
+    /// Label of the macro button.
+    name:     String,
+    /// Command line executed against the selected aircraft.
+    /// The slot is unused if this is empty.
+    commands: String,
+    /// Hotkey executing the macro.
+    hotkey:   KeySet,
+}
+
+/// Returns the `(name, commands, hotkey)` of all defined macros, with their slot index.
+fn defined_macros<'a>(
+    conf: &ConfRead<'a>,
+) -> impl Iterator<Item = (usize, &'a str, &'a str, KeySet)> {
+    [
+        &conf.slot1,
+        &conf.slot2,
+        &conf.slot3,
+        &conf.slot4,
+        &conf.slot5,
+        &conf.slot6,
+        &conf.slot7,
+        &conf.slot8,
+        &conf.slot9,
+    ]
+    .into_iter()
+    .enumerate()
+    .filter(|(_, slot)| !slot.commands.trim().is_empty())
+    .map(|(index, slot)| {
+        let name = if slot.name.is_empty() { slot.commands } else { slot.name };
+        (index, name, slot.commands, *slot.hotkey)
+    })
+}
+
+#[derive(Resource, Default)]
+struct State {
+    /// Slots of macros triggered since the last execution.
+    triggered:     Vec<usize>,
+    /// Result of the last executed macro.
+    feedback:      Option<Result<String, String>>,
+    /// JSON to be imported into the macro config.
+    import:        Option<String>,
+    /// Whether the JSON of the macro config is requested.
+    export:        bool,
+    /// The exported or pasted JSON.
+    transfer_text: String,
+}
+
+#[derive(Component)]
+struct MenuButtonMarker;
+
+fn read_hotkeys_system(
+    mut contexts: EguiContexts,
+    conf: ReadConfig<Conf>,
+    mut state: ResMut<State>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+
+    let conf = conf.read();
+    ctx.input(|input| {
+        for (index, _, _, hotkey) in defined_macros(&conf) {
+            if hotkey.clicked(input) {
+                state.triggered.push(index);
+            }
+        }
+    });
+}
+
+fn open_tab_system(
+    mut dock_state: ResMut<dock::State>,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<MenuButtonMarker>>,
+) {
+    if menu_button_clicked.consume()
+        && let Some(state) = &mut dock_state.state
+    {
+        dock::focus_or_create_tab(
+            state,
+            || dock::Tab::Macros(TabType),
+            dock::ReplaceTab(|tab| matches!(tab, dock::Tab::Macros(_))).or_always(dock::NewSurface),
+        );
+    }
+}
+
+fn execute_system(
+    conf: ReadConfig<Conf>,
+    mut state: ResMut<State>,
+    mut executor: accessible::Executor,
+) {
+    if state.triggered.is_empty() {
+        return;
+    }
+
+    let conf = conf.read();
+    for index in std::mem::take(&mut state.triggered) {
+        if let Some((_, name, commands, _)) = defined_macros(&conf).find(|&(i, ..)| i == index) {
+            state.feedback = Some(
+                executor.execute(commands).map_err(|err| format!("Macro {name} failed: {err}")),
+            );
+        }
+    }
+}
+
+fn transfer_system(world: &mut World) {
+    let mut state = world.resource_mut::<State>();
+    let import = state.import.take();
+    let export = std::mem::take(&mut state.export);
+
+    if let Some(json) = import {
+        let result = serde_json::from_str::<Map<String, Value>>(&json);
+        let feedback = match result {
+            Ok(values) => {
+                let values: Map<_, _> = values
+                    .into_iter()
+                    .filter(|(key, _)| key.starts_with(&format!("{CONFIG_KEY}.")))
+                    .collect();
+                let count = values.len();
+                config_profile::write(world, &values);
+                Ok(format!("Imported {count} macro settings"))
+            }
+            Err(err) => Err(format!("Invalid macro JSON: {err}")),
+        };
+        world.resource_mut::<State>().feedback = Some(feedback);
+    }
+
+    if export {
+        let values: Map<_, _> = config_profile::snapshot(world)
+            .into_iter()
+            .filter(|(key, _)| key.starts_with(&format!("{CONFIG_KEY}.")))
+            .collect();
+        match serde_json::to_string_pretty(&values) {
+            Ok(json) => world.resource_mut::<State>().transfer_text = json,
+            Err(err) => bevy::log::error!("Cannot serialize macros: {err}"),
+        }
+    }
+}
+
+pub struct TabType;
+
+#[derive(SystemParam)]
+pub struct UiParams<'w, 's> {
+    conf:  ReadConfig<'w, 's, Conf>,
+    state: ResMut<'w, State>,
+}
+
+impl dock::TabType for TabType {
+    type TitleSystemParam<'w, 's> = ();
+    fn title(&self, (): ()) -> String { "Macros".into() }
+
+    type UiSystemParam<'w, 's> = UiParams<'w, 's>;
+    fn ui(&mut self, params: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
+        let UiParams { conf, mut state } = params;
+        let conf = conf.read();
+
+        ui.horizontal_wrapped(|ui| {
+            let mut any = false;
+            for (index, name, commands, _) in defined_macros(&conf) {
+                any = true;
+                if ui.button(name).on_hover_text(commands).clicked() {
+                    state.triggered.push(index);
+                }
+            }
+            if !any {
+                ui.label("No macros defined. Define them in the \"macros\" settings group.");
+            }
+        });
+
+        match &state.feedback {
+            Some(Ok(feedback)) => {
+                ui.label(feedback);
+            }
+            Some(Err(err)) => {
+                ui.colored_label(egui::Color32::ORANGE, err);
+            }
+            None => {}
+        }
+
+        ui.separator();
+        ui.label("Share macros as JSON:");
+        ui.horizontal(|ui| {
+            if ui.button("Export").clicked() {
+                state.export = true;
+            }
+            if ui.button("Import").clicked() {
+                state.import = Some(state.transfer_text.clone());
+            }
+            if ui.button("Copy").clicked() {
+                ui.ctx().copy_text(state.transfer_text.clone());
+            }
+        });
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut state.transfer_text)
+                    .code_editor()
+                    .desired_width(f32::INFINITY),
+            );
+        });
+    }
+
+    type OnCloseSystemParam<'w, 's> = ();
+
+    type PrepareRenderSystemParam<'w, 's> = ();
+}
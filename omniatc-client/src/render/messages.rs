@@ -1,19 +1,54 @@
-use bevy::app::{App, Plugin};
+//! Message pane listing transmissions in the level.
+//!
+//! Every [`Message`] is recorded into the [`History`] when it is spawned,
+//! so that the pane can show the scrollback of expired messages,
+//! keep [pinned](Entry::pinned) messages after they expire,
+//! and filter messages by search text or by the selected object.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
 use bevy::color::Color;
+use bevy::ecs::change_detection::DetectChanges;
 use bevy::ecs::entity::Entity;
-use bevy::ecs::system::{Commands, Query, Res, SystemParam};
+use bevy::ecs::query::{Added, With};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Local, Query, Res, ResMut, SystemParam};
 use bevy::time::{self, Time};
 use bevy_egui::egui;
-use bevy_egui::egui::text::LayoutJob;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
 use egui_dock::DockState;
+use egui_material_icons::icons;
+use omniatc::level::clock::Clock;
+use omniatc::level::frequency::{self, Frequency};
 use omniatc::level::message::{self, Message};
+use omniatc::level::{instr, pilot_request};
+use omniatc::load;
+use strum::IntoEnumIterator;
 
-use crate::render::dock;
+use crate::render::{callsign, dock, object_info};
+use crate::{ConfigManager, render};
+
+#[cfg(test)]
+mod tests;
 
 pub struct Plug;
 
 impl Plugin for Plug {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("messages");
+        app.init_resource::<History>();
+        app.add_systems(app::Update, record_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Maximum number of unpinned messages kept in the scrollback.
+    #[config(default = 500)]
+    history_limit: usize,
 }
 
 fn color_from_class(class: message::Class) -> Color {
@@ -26,14 +61,131 @@ fn color_from_class(class: message::Class) -> Color {
     }
 }
 
+/// Scrollback of all messages in the current level, oldest first.
+#[derive(Resource, Default)]
+pub struct History {
+    entries: VecDeque<Entry>,
+}
+
+/// A recorded message.
+pub struct Entry {
+    /// The message entity, which may have expired.
+    message: Entity,
+    /// The object the message is about,
+    /// i.e. the recipient of an instruction or the sender of other messages.
+    subject: Entity,
+    /// Display name of the sender when the message was sent.
+    sender:  String,
+    content: String,
+    class:   message::Class,
+    created: Duration,
+    /// Pinned messages are listed on top and are never dropped from the scrollback.
+    pinned:  bool,
+}
+
+impl History {
+    /// Iterates over the recorded entries, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> { self.entries.iter() }
+
+    /// Appends an entry, dropping the oldest unpinned entries beyond `limit`.
+    fn push(&mut self, entry: Entry, limit: usize) {
+        self.entries.push_back(entry);
+
+        let mut excess =
+            self.entries.iter().filter(|entry| !entry.pinned).count().saturating_sub(limit);
+        self.entries.retain(|entry| {
+            if excess > 0 && !entry.pinned {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+impl Entry {
+    /// Display name of the sender when the message was sent.
+    #[must_use]
+    pub fn sender(&self) -> &str { &self.sender }
+
+    /// Text of the message.
+    #[must_use]
+    pub fn content(&self) -> &str { &self.content }
+
+    /// Simulation time at which the message was sent.
+    #[must_use]
+    pub fn created(&self) -> Duration { self.created }
+
+    /// Whether the entry is about `subject` (if specified)
+    /// and contains `search` in its sender or content, ignoring case.
+    fn matches(&self, search: &str, subject: Option<Entity>) -> bool {
+        if subject.is_some_and(|subject| subject != self.subject) {
+            return false;
+        }
+        if search.is_empty() {
+            return true;
+        }
+        let search = search.to_lowercase();
+        self.sender.to_lowercase().contains(&search)
+            || self.content.to_lowercase().contains(&search)
+    }
+}
+
+fn record_system(
+    conf: ReadConfig<Conf>,
+    meta: Res<load::LoadedMeta>,
+    mut history: ResMut<History>,
+    message_query: Query<(Entity, &Message, Option<&instr::Recipient>), Added<Message>>,
+    sender_query: Query<(&message::Sender, Option<&message::Telephony>)>,
+) {
+    let conf = conf.read();
+
+    if meta.is_changed() {
+        history.entries.clear();
+    }
+
+    let mut messages: Vec<_> = message_query.iter().collect();
+    messages.sort_by_key(|&(_, message, _)| message.created);
+
+    for (entity, message, recipient) in messages {
+        let sender = match sender_query.get(message.source) {
+            Ok((sender, Some(telephony))) => format!("{} ({})", sender.display, telephony.0),
+            Ok((sender, None)) => sender.display.clone(),
+            Err(_) => continue,
+        };
+        history.push(
+            Entry {
+                message: entity,
+                subject: recipient.map_or(message.source, |&instr::Recipient(object)| object),
+                sender,
+                content: message.content.clone(),
+                class: message.class,
+                created: message.created,
+                pinned: false,
+            },
+            conf.history_limit,
+        );
+    }
+}
+
 pub struct TabType;
 
 #[derive(SystemParam)]
 pub struct UiParams<'w, 's> {
-    messages: Query<'w, 's, (Entity, &'static Message)>,
-    senders:  Query<'w, 's, &'static message::Sender>,
-    time:     Res<'w, Time<time::Virtual>>,
-    commands: Commands<'w, 's>,
+    messages:        Query<'w, 's, (), With<Message>>,
+    history:         ResMut<'w, History>,
+    requests:        Query<'w, 's, (), With<pilot_request::Request>>,
+    time:            Res<'w, Time<time::Virtual>>,
+    clock:           Res<'w, Clock>,
+    frequency_conf:  ReadConfig<'w, 's, frequency::Conf>,
+    transmitting:    ResMut<'w, frequency::Transmitting>,
+    selected_object: Res<'w, object_info::CurrentObject>,
+    links:           callsign::Links<'w>,
+    search_str:      Local<'s, String>,
+    selected_only:   Local<'s, bool>,
+    show_expired:    Local<'s, bool>,
+    commands:        Commands<'w, 's>,
 }
 
 impl dock::TabType for TabType {
@@ -44,56 +196,89 @@ impl dock::TabType for TabType {
 
     type UiSystemParam<'w, 's> = UiParams<'w, 's>;
     fn ui(&mut self, mut params: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
-        let mut messages: Vec<_> = params.messages.into_iter().collect();
-        messages.sort_by_key(|(_, message)| message.created);
+        if params.frequency_conf.read().split {
+            ui.horizontal(|ui| {
+                ui.label("Transmitting on:");
+                for frequency in Frequency::iter() {
+                    ui.selectable_value(
+                        &mut params.transmitting.0,
+                        frequency,
+                        frequency.to_string(),
+                    );
+                }
+            });
+            ui.separator();
+        }
 
-        for (entity, message) in messages {
-            let mut job = LayoutJob::default();
+        ui.horizontal(|ui| {
+            ui.label(icons::ICON_SEARCH);
+            ui.add(
+                egui::TextEdit::singleline(&mut *params.search_str).hint_text("Search messages"),
+            );
+            ui.checkbox(&mut params.selected_only, "Selected object only");
+            ui.checkbox(&mut params.show_expired, "Show expired")
+                .on_hover_text("Include messages that have expired in the scrollback");
+        });
+
+        let subject = if *params.selected_only {
+            Some(params.selected_object.0.unwrap_or(Entity::PLACEHOLDER))
+        } else {
+            None
+        };
 
-            let sender = match params.senders.get(message.source) {
-                Ok(sender) => format!("{}: ", &sender.display),
-                _ => continue, // format!("<sender {:?}>: ", message.source),
+        let mut actions = Vec::new();
+        let mut show = |ui: &mut egui::Ui, index: usize, entry: &Entry| {
+            let state = EntryState {
+                live:    params.messages.contains(entry.message),
+                request: params.requests.contains(entry.message),
             };
-            job.append(
-                &sender,
-                0.,
-                egui::TextFormat {
-                    color: egui::Color32::from_rgba_unmultiplied(180, 180, 180, 255),
-                    ..Default::default()
-                },
-            );
+            if let Some(action) = show_entry(
+                ui,
+                &mut params.links,
+                entry,
+                state,
+                &params.clock,
+                params.time.elapsed(),
+            ) {
+                actions.push((index, action));
+            }
+        };
 
-            let color = color_from_class(message.class).to_srgba();
-            #[expect(
-                clippy::cast_sign_loss,
-                clippy::cast_possible_truncation,
-                reason = "rgba should be within [0., 1.]"
-            )]
-            let color = egui::Color32::from_rgba_unmultiplied(
-                (color.red * 255.) as u8,
-                (color.green * 255.) as u8,
-                (color.blue * 255.) as u8,
-                (color.alpha * 255.) as u8,
-            );
+        let mut pinned = params
+            .history
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.pinned && entry.matches(&params.search_str, subject))
+            .peekable();
+        if pinned.peek().is_some() {
+            ui.label(egui::RichText::new("Pinned").small().strong());
+            for (index, entry) in pinned {
+                show(ui, index, entry);
+            }
+            ui.separator();
+        }
 
-            job.append(&message.content, 0., egui::TextFormat { color, ..Default::default() });
-
-            #[expect(
-                clippy::unchecked_time_subtraction,
-                reason = "time.elapsed() is monotonic increasing"
-            )]
-            job.append(
-                &format!(" [{:.1}s]", (params.time.elapsed() - message.created).as_secs_f32()),
-                0.,
-                egui::TextFormat {
-                    color: egui::Color32::from_rgba_unmultiplied(150, 150, 150, 255),
-                    ..Default::default()
-                },
-            );
+        egui::ScrollArea::vertical().auto_shrink([false; 2]).stick_to_bottom(true).show(ui, |ui| {
+            for (index, entry) in params.history.entries.iter().enumerate() {
+                if entry.pinned
+                    || !(*params.show_expired || params.messages.contains(entry.message))
+                    || !entry.matches(&params.search_str, subject)
+                {
+                    continue;
+                }
+                show(ui, index, entry);
+            }
+        });
 
-            let resp = ui.add(egui::Label::new(job).wrap());
-            if resp.clicked() {
-                params.commands.entity(entity).despawn();
+        for (index, action) in actions {
+            let Some(entry) = params.history.entries.get_mut(index) else { continue };
+            match action {
+                Action::TogglePin => entry.pinned = !entry.pinned,
+                Action::Dismiss => params.commands.entity(entry.message).despawn(),
+                Action::Respond { approve } => params
+                    .commands
+                    .queue(pilot_request::Respond { request: entry.message, approve }),
             }
         }
     }
@@ -103,6 +288,102 @@ impl dock::TabType for TabType {
     type PrepareRenderSystemParam<'w, 's> = ();
 }
 
+#[derive(Clone, Copy)]
+struct EntryState {
+    /// Whether the message entity still exists.
+    live:    bool,
+    /// Whether the message is a pending pilot request.
+    request: bool,
+}
+
+enum Action {
+    TogglePin,
+    Dismiss,
+    Respond { approve: bool },
+}
+
+/// Shows a message entry, returning the action requested by the user.
+fn show_entry(
+    ui: &mut egui::Ui,
+    links: &mut callsign::Links,
+    entry: &Entry,
+    state: EntryState,
+    clock: &Clock,
+    now: Duration,
+) -> Option<Action> {
+    let gray = egui::Color32::from_rgba_unmultiplied(150, 150, 150, 255);
+
+    let color = if state.live { color_from_class(entry.class) } else { Color::srgb(0.5, 0.5, 0.5) };
+    let color = color.to_srgba();
+    #[expect(
+        clippy::cast_sign_loss,
+        clippy::cast_possible_truncation,
+        reason = "rgba should be within [0., 1.]"
+    )]
+    let color = egui::Color32::from_rgba_unmultiplied(
+        (color.red * 255.) as u8,
+        (color.green * 255.) as u8,
+        (color.blue * 255.) as u8,
+        (color.alpha * 255.) as u8,
+    );
+
+    let mut action = None;
+    ui.horizontal_wrapped(|ui| {
+        let pin_resp = ui
+            .selectable_label(entry.pinned, icons::ICON_PUSH_PIN)
+            .on_hover_text(if entry.pinned { "Unpin" } else { "Pin" });
+        if pin_resp.clicked() {
+            action = Some(Action::TogglePin);
+        }
+        if state.live
+            && !state.request
+            && ui.small_button(icons::ICON_CLOSE).on_hover_text("Dismiss").clicked()
+        {
+            action = Some(Action::Dismiss);
+        }
+
+        ui.spacing_mut().item_spacing.x = 0.;
+
+        let time_of_day = clock.time_of_day(entry.created).as_secs();
+        ui.label(
+            egui::RichText::new(format!(
+                "[{:02}:{:02}:{:02}] ",
+                time_of_day / 3600,
+                (time_of_day / 60) % 60,
+                time_of_day % 60
+            ))
+            .color(gray),
+        );
+
+        links.text(ui, &entry.sender, egui::Color32::from_rgba_unmultiplied(180, 180, 180, 255));
+        ui.label(": ");
+        links.text(ui, &entry.content, color);
+
+        if state.live {
+            ui.label(
+                egui::RichText::new(format!(
+                    " [{:.1}s]",
+                    now.saturating_sub(entry.created).as_secs_f32()
+                ))
+                .color(gray),
+            );
+        }
+    });
+
+    if state.live && state.request {
+        ui.horizontal(|ui| {
+            if ui.small_button("Approve").clicked() {
+                action = Some(Action::Respond { approve: true });
+            }
+            if ui.small_button("Deny").clicked() {
+                action = Some(Action::Respond { approve: false });
+            }
+        });
+    }
+
+    action
+}
+
 pub(super) fn create_splits(dock: &mut DockState<dock::Tab>) {
     dock.split(
         (egui_dock::SurfaceIndex::main(), egui_dock::NodeIndex::root()),
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::world::World;
+use omniatc::level::message;
+
+use super::{Entry, History};
+
+fn entry(world: &mut World, subject: Entity, content: &str) -> Entry {
+    Entry {
+        message: world.spawn_empty().id(),
+        subject,
+        sender: "ABC123 (Alpha 123)".into(),
+        content: content.into(),
+        class: message::Class::VerboseInfo,
+        created: Duration::ZERO,
+        pinned: false,
+    }
+}
+
+fn contents(history: &History) -> Vec<&str> {
+    history.entries.iter().map(|entry| entry.content.as_str()).collect()
+}
+
+#[test]
+fn test_history_limit_keeps_pinned() {
+    let mut world = World::new();
+    let object = world.spawn_empty().id();
+
+    let mut history = History::default();
+    history.push(entry(&mut world, object, "first"), 2);
+    history.entries[0].pinned = true;
+    history.push(entry(&mut world, object, "second"), 2);
+    history.push(entry(&mut world, object, "third"), 2);
+    history.push(entry(&mut world, object, "fourth"), 2);
+
+    assert_eq!(contents(&history), ["first", "third", "fourth"]);
+}
+
+#[test]
+fn test_entry_matches_search_and_subject() {
+    let mut world = World::new();
+    let object = world.spawn_empty().id();
+    let other = world.spawn_empty().id();
+    let entry = entry(&mut world, object, "Requesting climb to 12000 feet");
+
+    assert!(entry.matches("", None));
+    assert!(entry.matches("CLIMB", None));
+    assert!(entry.matches("alpha", Some(object)));
+    assert!(!entry.matches("descent", None));
+    assert!(!entry.matches("", Some(other)));
+}
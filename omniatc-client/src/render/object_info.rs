@@ -14,7 +14,7 @@ use egui_dock::DockState;
 use egui_material_icons::icons;
 use omniatc::QueryTryLog;
 use omniatc::level::instr::CommandsExt;
-use omniatc::level::{instr, object, quest};
+use omniatc::level::{facility, instr, object, quest};
 
 use crate::render::dock::{self, State, Tab, TabPlacement};
 use crate::render::tutorial_popup;
@@ -103,59 +103,102 @@ trait Writer: QueryData {
 
     fn should_show(this: &Self::Item<'_, '_>) -> bool;
 
+    /// Whether the writer issues airborne vectors,
+    /// which are unavailable in tower-only and ground-only modes.
+    fn issues_vectors() -> bool { false }
+
     fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, param: &mut Self::SystemParams<'_, '_>);
 }
 
 macro_rules! writer_def {
-    ($($index:ident: $writer:ty,)*) => {
+    ($($group:ident => { $($index:ident $field:ident: $writer:ty,)* },)*) => {
         #[derive(QueryData)]
         struct WriteQueryData {
-            $(
-                $index: $writer,
-            )*
+            $($(
+                $field: $writer,
+            )*)*
         }
 
+        // `ParamSet` supports at most 8 params, so writers are nested in groups.
         #[derive(SystemParam)]
         pub struct WriteParams<'w, 's> {
-            sets: ParamSet<'w, 's, ($(<$writer as Writer>::SystemParams<'w, 's>,)*)>,
+            sets: ParamSet<'w, 's, ($(
+                ParamSet<'w, 's, ($(<$writer as Writer>::SystemParams<'w, 's>,)*)>,
+            )*)>,
         }
 
-        fn show_writers(ui: &mut egui::Ui, qd: &WriteQueryDataItem, params: &mut WriteParams) {
-            $(
+        fn show_writers(
+            ui: &mut egui::Ui,
+            qd: &WriteQueryDataItem,
+            params: &mut WriteParams,
+            facility_mode: &facility::Mode,
+        ) {
+            $($(
                 {
-                    let qd = &qd.$index;
+                    let qd = &qd.$field;
 
                     if <$writer as Writer>::should_show(qd) {
-                        let mut params = params.sets.$index();
+                        let mut group = params.sets.$group();
+                        let mut params = group.$index();
                         egui::CollapsingHeader::new(<$writer as Writer>::title())
                             .default_open(<$writer as Writer>::default_open())
                             .show(ui, |ui| {
-                                <$writer as Writer>::show(qd, ui, &mut params);
+                                let enabled = !<$writer as Writer>::issues_vectors()
+                                    || facility_mode.allows_vectors();
+                                ui.add_enabled_ui(enabled, |ui| {
+                                    <$writer as Writer>::show(qd, ui, &mut params);
+                                });
                             });
                     }
                 }
-            )*
+            )*)*
         }
     }
 }
 
 writer_def! {
-    p0: dest::ObjectQuery,
-    p1: dir::ObjectQuery,
-    p2: alt::ObjectQuery,
-    p3: speed::ObjectQuery,
-    p4: env::ObjectQuery,
-    p5: signal::ObjectQuery,
-    p6: route::ObjectQuery,
+    p0 => {
+        p0 dest: dest::ObjectQuery,
+        p1 dir: dir::ObjectQuery,
+        p2 alt: alt::ObjectQuery,
+        p3 speed: speed::ObjectQuery,
+        p4 env: env::ObjectQuery,
+        p5 signal: signal::ObjectQuery,
+        p6 route: route::ObjectQuery,
+        p7 vfr: vfr::ObjectQuery,
+    },
+    p1 => {
+        p0 formation: formation::ObjectQuery,
+        p1 bird: bird::ObjectQuery,
+        p2 note: note::ObjectQuery,
+        p3 deviation: deviation::ObjectQuery,
+        p4 advisory: advisory::ObjectQuery,
+        p5 frequency: frequency::ObjectQuery,
+        p6 hold: hold::ObjectQuery,
+        p7 transponder: transponder::ObjectQuery,
+    },
+    p2 => {
+        p0 radio: radio::ObjectQuery,
+    },
 }
 
+mod advisory;
 mod alt;
+mod bird;
 mod dest;
+mod deviation;
 mod dir;
 mod env;
+mod formation;
+mod frequency;
+mod hold;
+mod note;
+mod radio;
 mod route;
 mod signal;
 mod speed;
+mod transponder;
+mod vfr;
 
 fn highlight_selected_system(
     conf: ReadConfig<super::twodim::pick::Conf>,
@@ -164,6 +207,9 @@ fn highlight_selected_system(
     request_highlight: Option<
         Single<(), (With<tutorial_popup::Focused>, With<quest::highlight::ObjectSelect>)>,
     >,
+    request_object_highlight: Option<
+        Single<&quest::highlight::Object, With<tutorial_popup::Focused>>,
+    >,
     mut color_theme_query: Query<&mut super::twodim::object::ColorTheme>,
     time: Res<Time>,
 ) {
@@ -179,11 +225,21 @@ fn highlight_selected_system(
         theme.body = conf.selected_color;
     }
 
-    if request_highlight.is_some() && time.elapsed().as_secs().is_multiple_of(2) {
-        for mut theme in color_theme_query {
+    if !time.elapsed().as_secs().is_multiple_of(2) {
+        return;
+    }
+
+    if request_highlight.is_some() {
+        for mut theme in &mut color_theme_query {
             theme.body = conf.tutorial_highlight_color;
             theme.ring = conf.tutorial_highlight_color;
         }
+    } else if let Some(highlight) = request_object_highlight
+        && let Some(entity) = highlight.entity
+        && let Ok(mut theme) = color_theme_query.get_mut(entity)
+    {
+        theme.body = conf.tutorial_highlight_color;
+        theme.ring = conf.tutorial_highlight_color;
     }
 }
 
@@ -210,8 +266,9 @@ pub enum TabType {
 
 #[derive(SystemParam)]
 pub struct UiParams<'w, 's> {
-    object_query: Query<'w, 's, (WriteQueryData, &'static object::Display)>,
-    param_set:    ParamSet<'w, 's, (SendParams<'w, 's>, WriteParams<'w, 's>)>,
+    object_query:  Query<'w, 's, (WriteQueryData, &'static object::Display)>,
+    param_set:     ParamSet<'w, 's, (SendParams<'w, 's>, WriteParams<'w, 's>)>,
+    facility_mode: Res<'w, facility::Mode>,
 }
 
 impl dock::TabType for TabType {
@@ -251,7 +308,11 @@ impl dock::TabType for TabType {
 
         let mut send_params = params.param_set.p0();
         let send_clicked = ui
-            .add_enabled(send_params.draft.airborne_vector.is_some(), egui::Button::new("Send"))
+            .add_enabled(
+                send_params.draft.airborne_vector.is_some()
+                    && params.facility_mode.allows_vectors(),
+                egui::Button::new("Send"),
+            )
             .clicked();
         if (send_clicked || send_params.hotkeys.send)
             && let Some(instr) = send_params.draft.airborne_vector.take()
@@ -260,7 +321,7 @@ impl dock::TabType for TabType {
         }
 
         egui::ScrollArea::vertical().show(ui, |ui| {
-            show_writers(ui, &object.0, &mut params.param_set.p1());
+            show_writers(ui, &object.0, &mut params.param_set.p1(), &params.facility_mode);
         });
     }
 
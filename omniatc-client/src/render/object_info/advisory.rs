@@ -0,0 +1,65 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::system::{Commands, Query, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::conflict::{self, advisory};
+use omniatc::level::instr::CommandsExt;
+use omniatc::level::object;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    record: Option<&'static conflict::Record>,
+    active: Has<conflict::ActiveObject>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    options_query: Query<'w, 's, &'static advisory::Options>,
+    display_query: Query<'w, 's, &'static object::Display>,
+    commands:      Commands<'w, 's>,
+}
+
+impl WriteParams<'_, '_> {
+    fn name(&self, entity: Entity) -> &str {
+        self.display_query.get(entity).map_or("?", |display| display.name.as_str())
+    }
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Conflict advisory" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.active && this.record.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let Some(record) = this.record else { return };
+
+        ui.colored_label(egui::Color32::YELLOW, "Advisory only, for training use")
+            .on_hover_text("Suggestions assume straight-line tracks and ignore other traffic");
+
+        let mut shown = false;
+        for (peer, pair) in record.pairs() {
+            let Ok(options) = params.options_query.get(pair) else { continue };
+            shown = true;
+
+            ui.label(format!("Conflict with {}", params.name(peer)));
+            if options.0.is_empty() {
+                ui.weak("No simple resolution found");
+            }
+            for resolution in &options.0 {
+                let label =
+                    format!("{}: {}", params.name(resolution.object), resolution.describe());
+                if ui.button(label).on_hover_text("Issue this instruction").clicked() {
+                    params.commands.send_instruction(resolution.object, resolution.instruction());
+                }
+            }
+        }
+
+        if !shown {
+            ui.weak("Enable advisories in the conflict settings to see suggestions");
+        }
+    }
+}
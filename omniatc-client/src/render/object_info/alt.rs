@@ -5,7 +5,7 @@ use bevy_egui::egui;
 use math::{Position, TROPOPAUSE_ALTITUDE};
 use omniatc::QueryTryLog;
 use omniatc::level::waypoint::Waypoint;
-use omniatc::level::{instr, nav, object, quest};
+use omniatc::level::{instr, nav, object, pilot, quest, transition};
 
 use super::Writer;
 use crate::input;
@@ -20,12 +20,14 @@ pub struct ObjectQuery {
     airborne:     Option<&'static object::Airborne>,
     target_alt:   Option<&'static nav::TargetAltitude>,
     target_glide: Option<(&'static nav::TargetGlide, &'static nav::TargetGlideStatus)>,
+    clearance:    Option<&'static pilot::Clearance>,
 }
 
 #[derive(SystemParam)]
 pub struct WriteParams<'w, 's> {
     waypoint_query: Query<'w, 's, &'static Waypoint>,
     hotkeys:        Res<'w, input::Hotkeys>,
+    transition:     Res<'w, transition::Transition>,
     draft:          ResMut<'w, DraftInstructions>,
     req_highlight: Option<
         Single<'w, 's, (), (With<tutorial_popup::Focused>, With<quest::highlight::SetAltitude>)>,
@@ -39,15 +41,21 @@ impl Writer for ObjectQuery {
 
     fn should_show(this: &Self::Item<'_, '_>) -> bool { this.airborne.is_some() }
 
+    fn issues_vectors() -> bool { true }
+
     fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
-        ui.label(format!("Current: {:.0} ft", this.object.position.altitude().amsl().into_feet()));
+        let transition = &*params.transition;
+        ui.label(format!("Current: {}", transition.reading(this.object.position.altitude())));
         if let Some(airborne) = this.airborne {
             ui.label(format!("Vert rate: {:+.0} fpm", airborne.airspeed.vertical().into_fpm()));
         }
 
         if let Some(target_alt) = this.target_alt {
             let expedite = if target_alt.expedite { " (expedite)" } else { "" };
-            ui.label(format!("Target: {:.0} ft{expedite}", target_alt.altitude.amsl().into_feet()));
+            ui.label(format!("Target: {}{expedite}", transition.reading(target_alt.altitude)));
+        }
+        if let Some(altitude) = this.clearance.and_then(|clearance| clearance.altitude) {
+            ui.label(format!("Cleared: {}", transition.reading(altitude)));
         }
 
         let mut frame = egui::Frame::NONE;
@@ -67,18 +75,22 @@ impl Writer for ObjectQuery {
                 };
                 let initial_alt = initial_alt.amsl().into_feet();
                 let mut slider_alt = initial_alt;
-                let slider_resp = ui.add(egui::Slider::new(
-                    &mut slider_alt,
-                    0.0..=TROPOPAUSE_ALTITUDE.amsl().into_feet(),
-                ));
+                let slider_resp = ui.add(
+                    egui::Slider::new(
+                        &mut slider_alt,
+                        0.0..=TROPOPAUSE_ALTITUDE.amsl().into_feet(),
+                    )
+                    .custom_formatter(|feet, _| format_slider_altitude(transition, feet))
+                    .custom_parser(|text| parse_slider_altitude(transition, text)),
+                );
                 if params.hotkeys.set_altitude {
                     slider_resp.request_focus();
                 }
                 if params.hotkeys.inc_altitude {
-                    slider_alt = (slider_alt / 1000.).floor() * 1000. + 1000.;
+                    slider_alt = step_altitude(transition, slider_alt, 1.0);
                 }
                 if params.hotkeys.dec_altitude {
-                    slider_alt = (slider_alt / 1000.).ceil() * 1000. - 1000.;
+                    slider_alt = step_altitude(transition, slider_alt, -1.0);
                 }
 
                 let mut checkbox_expedite = expedite;
@@ -114,6 +126,42 @@ impl Writer for ObjectQuery {
     }
 }
 
+/// Formats a slider value in feet as an altitude or a flight level.
+fn format_slider_altitude(transition: &transition::Transition, feet: f64) -> String {
+    #[expect(clippy::cast_possible_truncation, reason = "slider values are within f32 range")]
+    let altitude = Position::from_amsl_feet(feet as f32);
+    transition.reading(altitude).to_string()
+}
+
+/// Parses `FL350`, `F350` or a plain number of feet into a slider value in feet.
+fn parse_slider_altitude(transition: &transition::Transition, text: &str) -> Option<f64> {
+    let text = text.trim().to_uppercase();
+    let altitude = match text.strip_prefix("FL").or_else(|| text.strip_prefix('F')) {
+        Some(level) => transition.flight_level_altitude(level.trim().parse().ok()?),
+        None => Position::from_amsl_feet(text.trim_end_matches("FT").trim().parse().ok()?),
+    };
+    Some(f64::from(altitude.amsl().into_feet()))
+}
+
+/// Steps an altitude in feet to the next multiple of 1000 feet in the direction of `sign`,
+/// or of 10 flight levels at or above the transition level.
+fn step_altitude(transition: &transition::Transition, feet: f32, sign: f32) -> f32 {
+    let step = |value: f32, unit: f32| {
+        if sign > 0.0 {
+            (value / unit).floor() * unit + unit
+        } else {
+            (value / unit).ceil() * unit - unit
+        }
+    };
+
+    match transition.reading(Position::from_amsl_feet(feet)) {
+        transition::Reading::FlightLevel(level) => {
+            transition.flight_level_altitude(step(level, 10.0)).amsl().into_feet()
+        }
+        transition::Reading::Altitude(feet) => step(feet, 1000.0),
+    }
+}
+
 fn display_glide(
     ui: &mut egui::Ui,
     params: &mut WriteParams,
@@ -0,0 +1,45 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::system::{Commands, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::bird;
+use omniatc::level::instr::{self, CommandsExt};
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:    Entity,
+    cautioned: Has<bird::Cautioned>,
+    struck:    Has<bird::Struck>,
+    emergency: Has<bird::Emergency>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Bird activity" }
+
+    fn default_open() -> bool { false }
+
+    fn should_show(_this: &Self::Item<'_, '_>) -> bool { true }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        if this.emergency {
+            ui.label("Emergency declared after bird strike");
+        } else if this.struck {
+            ui.label("Bird strike reported");
+        }
+
+        if this.cautioned {
+            ui.label("Cautioned about bird activity");
+        } else if ui.button("Caution bird activity").clicked() {
+            params.commands.send_instruction(this.entity, instr::BirdCaution);
+        }
+    }
+}
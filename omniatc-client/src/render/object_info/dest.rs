@@ -1,22 +1,26 @@
+use bevy::ecs::entity::Entity;
 use bevy::ecs::query::QueryData;
-use bevy::ecs::system::{Query, SystemParam};
+use bevy::ecs::system::{Commands, Query, SystemParam};
 use bevy_egui::egui;
 use omniatc::QueryTryLog;
 use omniatc::level::aerodrome::Aerodrome;
 use omniatc::level::dest::Destination;
+use omniatc::level::instr::{self, CommandsExt};
 use omniatc::level::waypoint::Waypoint;
 
 use super::Writer;
 
 #[derive(QueryData)]
 pub struct ObjectQuery {
-    dest: &'static Destination,
+    entity: Entity,
+    dest:   &'static Destination,
 }
 
 #[derive(SystemParam)]
 pub struct WriteParams<'w, 's> {
-    aerodrome: Query<'w, 's, &'static Aerodrome>,
+    aerodrome: Query<'w, 's, (Entity, &'static Aerodrome)>,
     waypoint:  Query<'w, 's, &'static Waypoint>,
+    commands:  Commands<'w, 's>,
 }
 
 impl Writer for ObjectQuery {
@@ -29,11 +33,11 @@ impl Writer for ObjectQuery {
     fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
         ui.label(match *this.dest {
             Destination::Landing { aerodrome } => {
-                let Some(data) = params.aerodrome.log_get(aerodrome) else { return };
+                let Some((_, data)) = params.aerodrome.log_get(aerodrome) else { return };
                 format!("Runway arrival at {}", &data.name)
             }
             Destination::Parking { aerodrome } => {
-                let Some(data) = params.aerodrome.log_get(aerodrome) else { return };
+                let Some((_, data)) = params.aerodrome.log_get(aerodrome) else { return };
                 format!("Apron arrival at {}", &data.name)
             }
             Destination::VacateAnyRunway => String::from("Land at any runway and vacate"),
@@ -60,5 +64,42 @@ impl Writer for ObjectQuery {
                 }
             }
         });
+
+        if let Destination::Landing { aerodrome } | Destination::Parking { aerodrome } = *this.dest
+        {
+            write_divert(ui, this.entity, aerodrome, params);
+        }
+    }
+}
+
+fn write_divert(
+    ui: &mut egui::Ui,
+    object: Entity,
+    current_aerodrome: Entity,
+    params: &mut WriteParams,
+) {
+    let mut selection = None;
+
+    ui.horizontal(|ui| {
+        ui.label("Divert");
+
+        egui::ComboBox::from_id_salt("divert").selected_text("Select aerodrome").show_ui(
+            ui,
+            |ui| {
+                for (aerodrome_entity, aerodrome) in &params.aerodrome {
+                    if aerodrome_entity != current_aerodrome {
+                        ui.selectable_value(
+                            &mut selection,
+                            Some(aerodrome_entity),
+                            &aerodrome.name,
+                        );
+                    }
+                }
+            },
+        );
+    });
+
+    if let Some(aerodrome) = selection {
+        params.commands.send_instruction(object, instr::Divert { aerodrome });
     }
 }
@@ -0,0 +1,45 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::{Commands, Res, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::instr::CommandsExt;
+use omniatc::level::{deviation, transition};
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity: Entity,
+    alert:  Option<&'static deviation::Alert>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    commands:   Commands<'w, 's>,
+    transition: Res<'w, transition::Transition>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Deviation" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.alert.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let Some(alert) = this.alert else { return };
+        ui.colored_label(egui::Color32::RED, alert.kind.describe());
+
+        let label = match alert.kind {
+            deviation::AlertKind::Altitude { cleared, .. } => {
+                format!("Reissue {}", params.transition.reading(cleared))
+            }
+            deviation::AlertKind::Heading { cleared, .. } => {
+                format!("Reissue heading {:03.0}", cleared.degrees())
+            }
+        };
+        if ui.button(label).on_hover_text("Repeat the acknowledged clearance").clicked() {
+            params.commands.send_instruction(this.entity, alert.kind.corrective_instruction());
+        }
+    }
+}
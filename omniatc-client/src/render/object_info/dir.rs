@@ -5,7 +5,7 @@ use bevy_egui::egui;
 use math::{Heading, TurnDirection};
 use omniatc::level::object::Object;
 use omniatc::level::waypoint::Waypoint;
-use omniatc::level::{ground, instr, nav, object, plane};
+use omniatc::level::{ground, instr, nav, object, pilot, plane};
 use omniatc::{QueryTryLog, try_log_return};
 use store::YawTarget;
 
@@ -24,6 +24,7 @@ pub struct ObjectQuery {
     target_waypoint:  Option<&'static nav::TargetWaypoint>,
     target_alignment: Option<(&'static nav::TargetAlignment, &'static nav::TargetAlignmentStatus)>,
     ground:           Option<&'static object::OnGround>,
+    clearance:        Option<&'static pilot::Clearance>,
 }
 
 #[derive(SystemParam)]
@@ -42,6 +43,8 @@ impl Writer for ObjectQuery {
 
     fn should_show(_this: &Self::Item<'_, '_>) -> bool { true }
 
+    fn issues_vectors() -> bool { true }
+
     fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut WriteParams) {
         ui.label(format!(
             "Ground track: {:.0}\u{b0}",
@@ -51,6 +54,9 @@ impl Writer for ObjectQuery {
             if let Some(control) = this.plane_control {
                 ui.label(format!("Current yaw: {:.0}\u{b0}", control.heading.degrees()));
             }
+            if let Some(heading) = this.clearance.and_then(|clearance| clearance.heading) {
+                ui.label(format!("Cleared heading: {:03.0}\u{b0}", heading.degrees()));
+            }
             if let Some(nav_vel) = this.nav_vel {
                 show_yaw_target(
                     ui,
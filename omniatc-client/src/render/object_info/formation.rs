@@ -0,0 +1,36 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::{Commands, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::formation;
+use omniatc::level::instr::{self, CommandsExt};
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:    Entity,
+    formation: Option<&'static formation::Formation>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Formation" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.formation.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let Some(formation) = this.formation else { return };
+
+        ui.label(format!("Flight of {}", formation.size));
+        if ui.button("Break up formation").clicked() {
+            params.commands.send_instruction(this.entity, instr::BreakupFormation);
+        }
+    }
+}
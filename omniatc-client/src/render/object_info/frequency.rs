@@ -0,0 +1,48 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::{Commands, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::frequency::{self, Frequency};
+use omniatc::level::instr::{self, CommandsExt};
+use strum::IntoEnumIterator;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:      Entity,
+    frequency:   Option<&'static Frequency>,
+    handoff_due: Option<&'static frequency::HandoffDue>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Frequency" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.frequency.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let Some(&tuned) = this.frequency else { return };
+
+        ui.label(format!("Tuned to {tuned}"));
+        if let Some(&frequency::HandoffDue(expected)) = this.handoff_due {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("Requesting contact {expected}"));
+        }
+
+        ui.horizontal(|ui| {
+            for frequency in Frequency::iter().filter(|&frequency| frequency != tuned) {
+                if ui.button(format!("Contact {frequency}")).clicked() {
+                    params
+                        .commands
+                        .send_instruction(this.entity, instr::ContactFrequency { frequency });
+                }
+            }
+        });
+    }
+}
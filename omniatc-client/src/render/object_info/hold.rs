@@ -0,0 +1,53 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::{Commands, Query, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::hold;
+use omniatc::level::instr::{self, CommandsExt};
+use omniatc::level::waypoint::Waypoint;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:  Entity,
+    holding: Option<&'static hold::Holding>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    pattern_query:  Query<'w, 's, &'static hold::Pattern>,
+    waypoint_query: Query<'w, 's, &'static Waypoint>,
+    commands:       Commands<'w, 's>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Holding" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.holding.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let Some(holding) = this.holding else { return };
+        let Ok(pattern) = params.pattern_query.get(holding.pattern) else { return };
+
+        let fix_name =
+            params.waypoint_query.get(pattern.fix).map_or("unknown", |w| w.name.as_str());
+        let phase = match holding.phase {
+            hold::Phase::Inbound => "inbound",
+            hold::Phase::Outbound { .. } => "outbound",
+        };
+        ui.label(format!("Holding at {fix_name}, {phase}"));
+        ui.label(format!(
+            "Level {} of {} at {:.0} ft",
+            holding.level + 1,
+            pattern.levels,
+            pattern.level_altitude(holding.level).amsl().into_feet()
+        ));
+
+        if ui.button("Leave hold and continue route").clicked() {
+            params.commands.send_instruction(this.entity, instr::RemoveStandby { skip_id: None });
+        }
+    }
+}
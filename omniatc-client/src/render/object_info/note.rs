@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::{Commands, Local, Query, Res, SystemParam};
+use bevy::time::{self, Time};
+use bevy_egui::egui;
+use omniatc::level::note;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity: Entity,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    notes_query: Query<'w, 's, &'static mut note::Notes>,
+    time:        Res<'w, Time<time::Virtual>>,
+    draft:       Local<'s, DraftReminder>,
+    commands:    Commands<'w, 's>,
+}
+
+/// Reminder being composed in the object info panel.
+pub struct DraftReminder {
+    text:    String,
+    minutes: u64,
+}
+
+impl Default for DraftReminder {
+    fn default() -> Self { Self { text: String::new(), minutes: 2 } }
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Notes" }
+
+    fn default_open() -> bool { false }
+
+    fn should_show(_this: &Self::Item<'_, '_>) -> bool { true }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let now = params.time.elapsed();
+        let mut new_notes = None;
+        let notes = match params.notes_query.get_mut(this.entity) {
+            Ok(notes) => notes.into_inner(),
+            Err(_) => new_notes.insert(note::Notes::default()),
+        };
+
+        ui.add(
+            egui::TextEdit::multiline(&mut notes.scratchpad)
+                .hint_text("Scratchpad")
+                .desired_rows(2),
+        );
+
+        let mut remove = None;
+        for (index, reminder) in notes.reminders.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let remaining = reminder.due.saturating_sub(now).as_secs();
+                ui.label(format!("{} in {}:{:02}", reminder.text, remaining / 60, remaining % 60));
+                if ui.small_button("Dismiss").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            notes.reminders.swap_remove(index);
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut params.draft.text)
+                    .hint_text("Reminder")
+                    .desired_width(120.),
+            );
+            ui.add(egui::DragValue::new(&mut params.draft.minutes).range(1..=60).suffix(" min"));
+            if ui.button("Remind").clicked() && !params.draft.text.trim().is_empty() {
+                notes.reminders.push(note::Reminder {
+                    text: params.draft.text.trim().to_owned(),
+                    due:  now + Duration::from_mins(params.draft.minutes),
+                });
+                params.draft.text.clear();
+            }
+        });
+
+        if let Some(notes) = new_notes
+            && !notes.is_empty()
+        {
+            params.commands.entity(this.entity).insert(notes);
+        }
+    }
+}
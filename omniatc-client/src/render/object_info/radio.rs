@@ -0,0 +1,29 @@
+use bevy::ecs::query::QueryData;
+use bevy_egui::egui;
+use omniatc::level::radio;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    failed: Option<&'static radio::Failed>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = ();
+
+    fn title() -> &'static str { "Radio" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.failed.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, (): &mut ()) {
+        let Some(failed) = this.failed else { return };
+
+        ui.label(format!("Radio failure, squawking {}", radio::SQUAWK));
+        if failed.resumed {
+            ui.label("Resumed route under lost-communication rules");
+        } else {
+            ui.label("Maintaining last clearance");
+        }
+    }
+}
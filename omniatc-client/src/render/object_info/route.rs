@@ -1,6 +1,6 @@
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::QueryData;
-use bevy::ecs::system::{Commands, Query, Res, SystemParam};
+use bevy::ecs::system::{Commands, Query, Res, ResMut, SystemParam};
 use bevy_egui::egui;
 use itertools::Itertools;
 use omniatc::QueryTryLog;
@@ -9,11 +9,12 @@ use omniatc::level::instr::{self, CommandsExt};
 use omniatc::level::route::{self, Route};
 use omniatc::level::runway::RunwayOf;
 use omniatc::level::waypoint::Waypoint;
-use omniatc::level::{dest, ground, nav, object, taxi};
+use omniatc::level::{deadlock, dest, ground, nav, object, taxi, transition};
 use store::WaypointProximity;
 
 use super::Writer;
 use crate::input;
+use crate::render::twodim::object::preview;
 use crate::util::new_type_id;
 
 #[derive(QueryData)]
@@ -23,6 +24,7 @@ pub struct ObjectQuery {
     target_waypoint: Option<&'static nav::TargetWaypoint>,
     taxi_target:     Option<&'static taxi::Target>,
     on_ground:       Option<&'static object::OnGround>,
+    deadlocked:      Option<&'static deadlock::Deadlocked>,
     dest:            &'static dest::Destination,
     entity:          Entity,
 }
@@ -35,8 +37,12 @@ pub struct WriteRouteParams<'w, 's> {
     runway_query:           Query<'w, 's, (&'static Waypoint, &'static RunwayOf)>,
     aerodrome_query:        Query<'w, 's, &'static Aerodrome>,
     segment_query:          Query<'w, 's, &'static ground::SegmentLabel>,
+    ground_query:           Query<'w, 's, (Entity, &'static object::OnGround)>,
+    display_query:          Query<'w, 's, &'static object::Display>,
     commands:               Commands<'w, 's>,
     hotkeys:                Res<'w, input::Hotkeys>,
+    transient_preview:      ResMut<'w, preview::TransientPreview>,
+    transition:             Res<'w, transition::Transition>,
 }
 
 impl Writer for ObjectQuery {
@@ -59,6 +65,7 @@ impl Writer for ObjectQuery {
                 presets,
                 this.route_id.and_then(|id| id.0.as_deref()),
                 &params.hotkeys,
+                &mut params.transient_preview,
             );
         }
 
@@ -66,6 +73,14 @@ impl Writer for ObjectQuery {
             write_taxi_target(ui, target, params, this.on_ground);
         }
 
+        if let Some(runway) = this.on_ground.and_then(|ground| ground.crossing.holding_runway()) {
+            write_runway_crossing(ui, this.entity, runway, params);
+        }
+
+        if let Some(deadlocked) = this.deadlocked {
+            write_deadlock(ui, this.entity, deadlocked, params);
+        }
+
         if let Some(route) = this.route {
             for node in route.iter() {
                 write_route_node(ui, node, this.entity, params);
@@ -83,6 +98,7 @@ fn write_route_options(
     presets: &route::WaypointPresetList,
     current_route_id: Option<&str>,
     hotkeys: &input::Hotkeys,
+    transient_preview: &mut preview::TransientPreview,
 ) {
     #[derive(Clone, Copy, PartialEq)]
     enum Selection {
@@ -95,7 +111,7 @@ fn write_route_options(
         .iter()
         .filter_map(|entity| {
             let (preset, matcher) = preset_query.log_get(entity)?;
-            matcher.matches(dest).then_some(preset)
+            matcher.matches(dest).then_some((entity, preset))
         })
         .collect();
 
@@ -104,7 +120,7 @@ fn write_route_options(
     }
 
     let current_index =
-        current_route_id.and_then(|curr| presets.iter().position(|preset| preset.id == curr));
+        current_route_id.and_then(|curr| presets.iter().position(|(_, preset)| preset.id == curr));
     let current_selection = match (current_index, current_route_id) {
         (None, Some(_)) => Selection::Retain,
         (None, None) => Selection::None,
@@ -139,8 +155,12 @@ fn write_route_options(
                         current_route_id.unwrap(),
                     );
                 }
-                for (i, preset) in presets.iter().enumerate() {
-                    ui.selectable_value(&mut selection, Selection::Available(i), &preset.title);
+                for (i, &(entity, preset)) in presets.iter().enumerate() {
+                    let response =
+                        ui.selectable_value(&mut selection, Selection::Available(i), &preset.title);
+                    if response.hovered() {
+                        transient_preview.0 = Some(preview::TransientSource::Preset(entity));
+                    }
                 }
             },
         );
@@ -150,7 +170,7 @@ fn write_route_options(
                     commands.send_instruction(object, instr::ClearRoute);
                 }
                 Selection::Available(index) => {
-                    let new_preset = presets[index];
+                    let (_, new_preset) = presets[index];
                     commands.send_instruction(
                         object,
                         instr::SelectRoute { preset: new_preset.clone() },
@@ -200,6 +220,56 @@ fn write_taxi_target(
     }
 }
 
+/// Offers crossing clearances to the object
+/// and to all other objects holding short of the same runway.
+fn write_runway_crossing(
+    ui: &mut egui::Ui,
+    object: Entity,
+    runway: Entity,
+    params: &mut WriteRouteParams,
+) {
+    let runway_name = params.waypoint_query.log_get(runway).map_or("?", |waypoint| &waypoint.name);
+    let holding: Vec<Entity> = params
+        .ground_query
+        .iter()
+        .filter(|(_, ground)| ground.crossing.holding_runway() == Some(runway))
+        .map(|(entity, _)| entity)
+        .collect();
+
+    ui.label(format!("Holding short of runway {runway_name}"));
+    ui.horizontal(|ui| {
+        if ui.button("Cross runway").clicked() {
+            params.commands.send_instruction(object, instr::CrossRunway { runway });
+        }
+        if holding.len() > 1 && ui.button(format!("Cross all {} holding", holding.len())).clicked()
+        {
+            for entity in holding {
+                params.commands.send_instruction(entity, instr::CrossRunway { runway });
+            }
+        }
+    });
+}
+
+fn write_deadlock(
+    ui: &mut egui::Ui,
+    object: Entity,
+    deadlocked: &deadlock::Deadlocked,
+    params: &WriteRouteParams,
+) {
+    let name = |entity| params.display_query.get(entity).map_or("?", |display| &display.name);
+    let others =
+        deadlocked.cycle.iter().filter(|&&entity| entity != object).map(|&entity| name(entity));
+    ui.colored_label(
+        egui::Color32::ORANGE,
+        format!("Ground deadlock with {}", others.format(", ")),
+    );
+    if deadlocked.reroute == object {
+        ui.label("Suggested to reroute this object");
+    } else {
+        ui.label(format!("Suggested to reroute {}", name(deadlocked.reroute)));
+    }
+}
+
 fn write_route_node(
     ui: &mut egui::Ui,
     node: &route::Node,
@@ -223,7 +293,7 @@ fn write_route_node(
 
             if let Some(altitude) = node.altitude {
                 ui.indent(new_type_id!(), |ui| {
-                    ui.label(format!("Pass at altitude {:.0} ft", altitude.amsl().into_feet()));
+                    ui.label(format!("Pass at {}", params.transition.reading(altitude)));
                 });
             }
         }
@@ -238,8 +308,8 @@ fn write_route_node(
         route::Node::StartSetAltitude(node) => {
             let expedite = if node.expedite { " (expedite)" } else { "" };
             ui.label(format!(
-                "Start approaching altitude {:.0} ft{expedite}",
-                node.altitude.amsl().into_feet()
+                "Start approaching {}{expedite}",
+                params.transition.reading(node.altitude)
             ));
             if let Some(error) = node.error {
                 ui.indent(new_type_id!(), |ui| {
@@ -254,7 +324,12 @@ fn write_route_node(
                 return;
             };
             let Some(aerodrome) = params.aerodrome_query.log_get(aerodrome_id) else { return };
-            ui.label(format!("Align with ILS {} of {}", &waypoint.name, &aerodrome.name));
+            ui.horizontal(|ui| {
+                ui.label(format!("Align with ILS {} of {}", &waypoint.name, &aerodrome.name));
+                if ui.button("Clear approach").clicked() {
+                    params.commands.send_instruction(entity, instr::ClearApproach);
+                }
+            });
         }
         route::Node::ShortFinal(node) => {
             let Some((waypoint, &RunwayOf(aerodrome_id))) =
@@ -283,5 +358,8 @@ fn write_route_node(
         route::Node::Takeoff(_) => {
             ui.label("Take off");
         }
+        route::Node::Deice(_) => {
+            ui.label("De-ice");
+        }
     }
 }
@@ -1,9 +1,10 @@
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::{QueryData, With};
-use bevy::ecs::system::{Res, ResMut, Single, SystemParam};
+use bevy::ecs::system::{Query, Res, ResMut, Single, SystemParam};
 use bevy_egui::egui;
 use math::Speed;
-use omniatc::level::{instr, nav, object, quest};
+use omniatc::level::waypoint::Waypoint;
+use omniatc::level::{instr, nav, object, pilot, quest, route};
 
 use super::Writer;
 use crate::input;
@@ -12,17 +13,20 @@ use crate::render::tutorial_popup;
 
 #[derive(QueryData)]
 pub struct ObjectQuery {
-    entity:   Entity,
-    object:   &'static object::Object,
-    airborne: Option<&'static object::Airborne>,
-    nav_vel:  Option<&'static nav::VelocityTarget>,
-    ground:   Option<&'static object::OnGround>,
+    entity:    Entity,
+    object:    &'static object::Object,
+    airborne:  Option<&'static object::Airborne>,
+    nav_vel:   Option<&'static nav::VelocityTarget>,
+    ground:    Option<&'static object::OnGround>,
+    clearance: Option<&'static pilot::Clearance>,
+    next:      Option<&'static route::ConditionalSpeed>,
 }
 
 #[derive(SystemParam)]
 pub struct WriteParams<'w, 's> {
-    hotkeys:       Res<'w, input::Hotkeys>,
-    draft:         ResMut<'w, DraftInstructions>,
+    hotkeys:        Res<'w, input::Hotkeys>,
+    waypoint_query: Query<'w, 's, &'static Waypoint>,
+    draft:          ResMut<'w, DraftInstructions>,
     req_highlight: Option<
         Single<'w, 's, (), (With<tutorial_popup::Focused>, With<quest::highlight::SetSpeed>)>,
     >,
@@ -35,6 +39,8 @@ impl Writer for ObjectQuery {
 
     fn should_show(_this: &Self::Item<'_, '_>) -> bool { true }
 
+    fn issues_vectors() -> bool { true }
+
     fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
         ui.label(format!(
             "Current ground: {:.0} kt",
@@ -49,6 +55,22 @@ impl Writer for ObjectQuery {
                 "Current indicated airspeed: {:.0} kt",
                 airborne.airspeed.horizontal().magnitude_exact().into_knots()
             ));
+            if let Some(speed) = this.clearance.and_then(|clearance| clearance.speed) {
+                ui.label(format!("Cleared IAS: {:.0} kt", speed.into_knots()));
+            }
+            if let Some(next) = this.next {
+                let waypoint_name = params
+                    .waypoint_query
+                    .get(next.condition.waypoint())
+                    .map_or("unknown", |waypoint| waypoint.name.as_str());
+                let condition = match next.condition {
+                    route::SpeedCondition::Distance { distance, .. } => {
+                        format!("{:.0} nm from {waypoint_name}", distance.into_nm())
+                    }
+                    route::SpeedCondition::Waypoint(_) => format!("passing {waypoint_name}"),
+                };
+                ui.label(format!("Then {:.0} kt {condition}", next.speed.into_knots()));
+            }
 
             if let Some(nav_vel) = this.nav_vel {
                 let target_knots = nav_vel.horiz_speed.into_knots();
@@ -0,0 +1,38 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::system::{Commands, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::instr::{self, CommandsExt};
+use omniatc::level::transponder;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:    Entity,
+    failed:    Has<transponder::Failed>,
+    recycling: Has<transponder::Recycling>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Transponder" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.failed }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        ui.label("Transponder failed, primary target only");
+
+        if this.recycling {
+            ui.label("Recycling transponder");
+        } else if ui.button("Recycle transponder").clicked() {
+            params.commands.send_instruction(this.entity, instr::CycleTransponder);
+        }
+    }
+}
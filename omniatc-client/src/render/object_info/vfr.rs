@@ -0,0 +1,45 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::system::{Commands, SystemParam};
+use bevy_egui::egui;
+use omniatc::level::instr::{self, CommandsExt};
+use omniatc::level::vfr;
+
+use super::Writer;
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:    Entity,
+    vfr:       Option<&'static vfr::Vfr>,
+    following: Has<vfr::FlightFollowing>,
+}
+
+#[derive(SystemParam)]
+pub struct WriteParams<'w, 's> {
+    commands: Commands<'w, 's>,
+}
+
+impl Writer for ObjectQuery {
+    type SystemParams<'w, 's> = WriteParams<'w, 's>;
+
+    fn title() -> &'static str { "Visual flight rules" }
+
+    fn should_show(this: &Self::Item<'_, '_>) -> bool { this.vfr.is_some() }
+
+    fn show(this: &Self::Item<'_, '_>, ui: &mut egui::Ui, params: &mut Self::SystemParams<'_, '_>) {
+        let Some(vfr) = this.vfr else { return };
+
+        if vfr.remaining_legs > 0 {
+            ui.label(format!("Wandering, {} legs remaining", vfr.remaining_legs));
+        }
+
+        if this.following {
+            ui.label("Flight following granted");
+        } else {
+            ui.label("Not identified, may not comply with instructions");
+            if ui.button("Grant flight following").clicked() {
+                params.commands.send_instruction(this.entity, instr::GrantFlightFollowing);
+            }
+        }
+    }
+}
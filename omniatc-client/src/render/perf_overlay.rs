@@ -0,0 +1,280 @@
+//! Overlay showing the frame time budget for performance reports.
+//!
+//! The wall time between the [update system sets](UpdateSystemSets)
+//! and of the whole egui pass is recorded into bevy [diagnostics](Diagnostics),
+//! along with the number of entities of interest.
+//! Frames taking much longer than the recent average are recorded as [spikes](Spikes).
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use bevy::app::{self, App, Plugin};
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, EntityCountDiagnosticsPlugin,
+    FrameTimeDiagnosticsPlugin, RegisterDiagnostic,
+};
+use bevy::ecs::component::Component;
+use bevy::ecs::query::With;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Query, Res, ResMut, Single};
+use bevy::time::{self, Time};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use egui_material_icons::icons;
+use omniatc::level::ground;
+use omniatc::level::message::Message;
+use omniatc::level::object::Object;
+use strum::IntoEnumIterator;
+
+use crate::render::{MenuButton, MenuButtonClicked};
+use crate::{ConfigManager, EguiSystemSets, UpdateSystemSets, render};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("perf_overlay");
+        app.init_resource::<Spikes>();
+        app.init_resource::<LastBoundary>();
+        app.init_resource::<EguiStart>();
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_SPEED,
+                title:    "Performance overlay".into(),
+                group:    render::MenuButtonGroup::Game,
+                priority: 95,
+            },
+            MenuButtonMarker,
+        ));
+
+        for path in UpdateSystemSets::iter().map(set_path).chain([
+            EGUI_PATH,
+            OBJECT_COUNT,
+            SEGMENT_COUNT,
+            MESSAGE_COUNT,
+        ]) {
+            app.register_diagnostic(Diagnostic::new(path));
+        }
+
+        let sets: Vec<_> = UpdateSystemSets::iter().collect();
+        for index in 0..=sets.len() {
+            let mut configs = (move |last: ResMut<LastBoundary>, diagnostics: Diagnostics| {
+                set_boundary_system(index, last, diagnostics);
+            })
+            .into_configs();
+            if let Some(&previous) = index.checked_sub(1).and_then(|prev| sets.get(prev)) {
+                configs = configs.after(previous);
+            }
+            if let Some(&next) = sets.get(index) {
+                configs = configs.before(next);
+            }
+            app.add_systems(app::Update, configs);
+        }
+
+        app.add_systems(app::Last, (count_system, detect_spike_system));
+        app.add_systems(
+            EguiPrimaryContextPass,
+            (
+                egui_begin_system.before(EguiSystemSets::Init),
+                egui_end_system.after(EguiSystemSets::Toasts),
+                overlay_system.in_set(EguiSystemSets::Toasts),
+            ),
+        );
+    }
+}
+
+const EGUI_PATH: DiagnosticPath = DiagnosticPath::const_new("omniatc/time/egui");
+const OBJECT_COUNT: DiagnosticPath = DiagnosticPath::const_new("omniatc/count/objects");
+const SEGMENT_COUNT: DiagnosticPath = DiagnosticPath::const_new("omniatc/count/segments");
+const MESSAGE_COUNT: DiagnosticPath = DiagnosticPath::const_new("omniatc/count/messages");
+
+fn set_path(set: UpdateSystemSets) -> DiagnosticPath {
+    DiagnosticPath::const_new(match set {
+        UpdateSystemSets::Input => "omniatc/time/input",
+        UpdateSystemSets::Simulate => "omniatc/time/simulate",
+        UpdateSystemSets::Render => "omniatc/time/render",
+    })
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Target frame time, against which the timings are shown.
+    #[config(default = Duration::from_micros(16_667), min = Duration::from_millis(1), max = Duration::from_millis(100))]
+    frame_budget:  Duration,
+    /// A frame is a spike if its frame time exceeds the average by this ratio.
+    #[config(default = 2.0, min = 1.0, max = 10.0)]
+    spike_ratio:   f32,
+    /// Frames faster than this are never spikes.
+    #[config(default = Duration::from_millis(20), min = Duration::ZERO, max = Duration::from_secs(1))]
+    spike_minimum: Duration,
+    /// Number of recent spikes listed in the overlay.
+    #[config(default = 5, min = 0, max = 50)]
+    spike_history: usize,
+}
+
+#[derive(Component)]
+struct MenuButtonMarker;
+
+/// Frames whose frame time greatly exceeded the recent average.
+#[derive(Resource, Default)]
+pub struct Spikes {
+    /// Total number of spikes since startup.
+    count:  u64,
+    /// The most recent spikes, oldest first.
+    recent: VecDeque<Spike>,
+}
+
+struct Spike {
+    /// Real time elapsed since startup when the spike occurred.
+    at:         Duration,
+    frame_time: Duration,
+    average:    Duration,
+}
+
+/// Whether a frame taking `frame_time` is a spike compared to the `average` frame time.
+fn is_spike(frame_time: Duration, average: Duration, ratio: f32, minimum: Duration) -> bool {
+    frame_time >= minimum && frame_time.as_secs_f32() > average.as_secs_f32() * ratio
+}
+
+/// Records the time elapsed since the previous boundary as the timing of the set before `index`.
+///
+/// One boundary system runs between each pair of consecutive update system sets,
+/// plus one before the first set and one after the last set.
+fn set_boundary_system(index: usize, mut last: ResMut<LastBoundary>, mut diagnostics: Diagnostics) {
+    let now = Instant::now();
+    if let Some(set) = index.checked_sub(1).and_then(|prev| UpdateSystemSets::iter().nth(prev))
+        && let Some(last) = last.0
+    {
+        diagnostics.add_measurement(&set_path(set), || millis(now - last));
+    }
+    last.0 = Some(now);
+}
+
+/// The time at which the last update set boundary system ran.
+#[derive(Resource, Default)]
+struct LastBoundary(Option<Instant>);
+
+/// The time at which the current egui pass started.
+#[derive(Resource, Default)]
+struct EguiStart(Option<Instant>);
+
+fn egui_begin_system(mut start: ResMut<EguiStart>) { start.0 = Some(Instant::now()); }
+
+fn egui_end_system(start: Res<EguiStart>, mut diagnostics: Diagnostics) {
+    if let Some(start) = start.0 {
+        diagnostics.add_measurement(&EGUI_PATH, || millis(start.elapsed()));
+    }
+}
+
+fn millis(duration: Duration) -> f64 { duration.as_secs_f64() * 1000. }
+
+fn count_system(
+    object_query: Query<(), With<Object>>,
+    segment_query: Query<(), With<ground::Segment>>,
+    message_query: Query<(), With<Message>>,
+    mut diagnostics: Diagnostics,
+) {
+    #[expect(clippy::cast_precision_loss, reason = "entity counts are small")]
+    let count = |count: usize| count as f64;
+    diagnostics.add_measurement(&OBJECT_COUNT, || count(object_query.iter().len()));
+    diagnostics.add_measurement(&SEGMENT_COUNT, || count(segment_query.iter().len()));
+    diagnostics.add_measurement(&MESSAGE_COUNT, || count(message_query.iter().len()));
+}
+
+fn detect_spike_system(
+    conf: ReadConfig<Conf>,
+    store: Res<DiagnosticsStore>,
+    time: Res<Time<time::Real>>,
+    mut spikes: ResMut<Spikes>,
+) {
+    let conf = conf.read();
+
+    let Some(frame_time) = store.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else { return };
+    let (Some(value), Some(average)) = (frame_time.value(), frame_time.average()) else { return };
+    let (value, average) =
+        (Duration::from_secs_f64(value / 1000.), Duration::from_secs_f64(average / 1000.));
+
+    if is_spike(value, average, conf.spike_ratio, conf.spike_minimum) {
+        spikes.count += 1;
+        spikes.recent.push_back(Spike { at: time.elapsed(), frame_time: value, average });
+        while spikes.recent.len() > conf.spike_history {
+            spikes.recent.pop_front();
+        }
+    }
+}
+
+fn overlay_system(
+    mut contexts: EguiContexts,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<MenuButtonMarker>>,
+    mut open: Local<bool>,
+    conf: ReadConfig<Conf>,
+    store: Res<DiagnosticsStore>,
+    spikes: Res<Spikes>,
+    time: Res<Time<time::Real>>,
+) {
+    if menu_button_clicked.consume() {
+        *open = !*open;
+    }
+    if !*open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    let conf = conf.read();
+
+    let smoothed = |path: &DiagnosticPath| store.get(path).and_then(Diagnostic::smoothed);
+    let budget = millis(conf.frame_budget);
+
+    egui::Window::new("Performance")
+        .id(egui::Id::new("perf-overlay"))
+        .open(&mut open)
+        .anchor(egui::Align2::RIGHT_TOP, [-10., 40.])
+        .resizable(false)
+        .collapsible(true)
+        .show(ctx, |ui| {
+            if let Some(fps) = smoothed(&FrameTimeDiagnosticsPlugin::FPS) {
+                ui.label(format!("FPS: {fps:.0}"));
+            }
+
+            let mut timings: Vec<_> = UpdateSystemSets::iter()
+                .map(|set| (format!("{set:?}"), smoothed(&set_path(set))))
+                .collect();
+            timings.push(("Egui".into(), smoothed(&EGUI_PATH)));
+            timings.push(("Frame".into(), smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME)));
+            for (name, ms) in timings {
+                let Some(ms) = ms else { continue };
+                #[expect(clippy::cast_possible_truncation, reason = "ratio is small")]
+                let ratio = (ms / budget) as f32;
+                ui.add(
+                    egui::ProgressBar::new(ratio.min(1.))
+                        .text(format!("{name}: {ms:.2} ms ({:.0}%)", ratio * 100.)),
+                );
+            }
+
+            ui.separator();
+            for (name, path) in [
+                ("Entities", &EntityCountDiagnosticsPlugin::ENTITY_COUNT),
+                ("Objects", &OBJECT_COUNT),
+                ("Segments", &SEGMENT_COUNT),
+                ("Messages", &MESSAGE_COUNT),
+            ] {
+                if let Some(count) = store.get(path).and_then(Diagnostic::value) {
+                    ui.label(format!("{name}: {count:.0}"));
+                }
+            }
+
+            ui.separator();
+            ui.label(format!("Spikes: {}", spikes.count));
+            for spike in spikes.recent.iter().rev() {
+                ui.label(format!(
+                    "{:.0}s ago: {:.1} ms (average {:.1} ms)",
+                    time.elapsed().saturating_sub(spike.at).as_secs_f32(),
+                    millis(spike.frame_time),
+                    millis(spike.average),
+                ));
+            }
+        });
+}
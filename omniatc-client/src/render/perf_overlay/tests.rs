@@ -0,0 +1,12 @@
+use std::time::Duration;
+
+use super::is_spike;
+
+#[test]
+fn test_is_spike() {
+    let ms = Duration::from_millis;
+
+    assert!(is_spike(ms(50), ms(16), 2., ms(20)));
+    assert!(!is_spike(ms(30), ms(16), 2., ms(20)));
+    assert!(!is_spike(ms(15), ms(5), 2., ms(20)), "frames under the minimum are not spikes");
+}
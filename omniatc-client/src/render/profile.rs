@@ -0,0 +1,533 @@
+//! Vertical profile of the selected object along its route.
+//!
+//! The [`Profile`] is recomputed in the render update set for the current object,
+//! and drawn in a dock tab with the distance along the route on the horizontal axis
+//! and the altitude on the vertical axis.
+//! The profile shows the terrain cross-section under the route,
+//! the altitude restrictions at each waypoint,
+//! the top of descent towards the first restriction below the current altitude,
+//! the glidepath of the runway the route lands on and the transition level.
+//! Restrictions at or above the transition level are labelled as flight levels.
+
+use bevy::app::{self, App, Plugin};
+use bevy::color::Color;
+use bevy::ecs::component::Component;
+use bevy::ecs::query::{QueryData, With};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, Res, ResMut, Single, SystemParam};
+use bevy::math::Vec2;
+use bevy_egui::{EguiPrimaryContextPass, egui};
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use egui_material_icons::icons;
+use math::{Angle, Length, Position, Speed};
+use omniatc::QueryTryLog;
+use omniatc::level::object::{self, Object};
+use omniatc::level::route::{self, Route};
+use omniatc::level::runway::Runway;
+use omniatc::level::terrain::Heightmap;
+use omniatc::level::waypoint::Waypoint;
+use omniatc::level::{nav, plane, transition};
+
+use crate::render::dock::{self, TabPlacement};
+use crate::render::{MenuButton, MenuButtonClicked, object_info};
+use crate::{ConfigManager, EguiSystemSets, render};
+
+/// Maximum number of terrain samples along the route.
+const MAX_TERRAIN_SAMPLES: usize = 500;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("profile");
+        app.init_resource::<Profile>();
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_LANDSCAPE,
+                title:    "Vertical profile".into(),
+                group:    render::MenuButtonGroup::Object,
+                priority: 10,
+            },
+            MenuButtonMarker,
+        ));
+
+        app.add_systems(EguiPrimaryContextPass, open_tab_system.in_set(EguiSystemSets::ManageTabs));
+        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Config)]
+#[config(expose(read))]
+struct Conf {
+    /// Horizontal interval between terrain samples.
+    #[config(default = Length::from_nm(0.5), min = Length::from_nm(0.1), max = Length::from_nm(5.0))]
+    terrain_interval:  Length<f32>,
+    /// Color of the terrain cross-section.
+    #[config(default = Color::srgb(0.35, 0.3, 0.2))]
+    terrain_color:     Color,
+    /// Color of the planned vertical path.
+    #[config(default = Color::srgb(0.9, 0.7, 0.8))]
+    path_color:        Color,
+    /// Color of altitude restriction markers.
+    #[config(default = Color::srgb(0.5, 0.8, 1.0))]
+    restriction_color: Color,
+    /// Color of the top of descent marker.
+    #[config(default = Color::srgb(1.0, 0.8, 0.3))]
+    tod_color:         Color,
+    /// Color of the glidepath.
+    #[config(default = Color::srgb(0.5, 0.9, 0.5))]
+    glidepath_color:   Color,
+    /// Color of the transition level line.
+    #[config(default = Color::srgb(0.7, 0.7, 0.7))]
+    transition_color:  Color,
+}
+
+#[derive(Component)]
+struct MenuButtonMarker;
+
+fn open_tab_system(
+    mut dock_state: ResMut<dock::State>,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<MenuButtonMarker>>,
+) {
+    if menu_button_clicked.consume()
+        && let Some(state) = &mut dock_state.state
+    {
+        dock::focus_or_create_tab(
+            state,
+            || dock::Tab::Profile(TabType),
+            dock::ReplaceTab(|tab| matches!(tab, dock::Tab::Profile(_)))
+                .or_always(dock::NewSurface),
+        );
+    }
+}
+
+/// Vertical profile of the current object.
+///
+/// Empty if the current object is not airborne.
+#[derive(Resource)]
+pub struct Profile {
+    /// Display name of the object.
+    object_name:    Option<String>,
+    /// Current altitude of the object.
+    altitude:       Position<f32>,
+    /// Waypoints along the route, in order.
+    fixes:          Vec<Fix>,
+    /// Terrain altitude samples along the route.
+    terrain:        Vec<(Length<f32>, Position<f32>)>,
+    /// Distance along the route at which the object should start descending.
+    top_of_descent: Option<Length<f32>>,
+    /// Glidepath of the runway that the route lands on.
+    glidepath:      Option<Glidepath>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            object_name:    None,
+            altitude:       Position::SEA_LEVEL,
+            fixes:          Vec::new(),
+            terrain:        Vec::new(),
+            top_of_descent: None,
+            glidepath:      None,
+        }
+    }
+}
+
+struct Fix {
+    /// Distance along the route from the object.
+    distance:    Length<f32>,
+    name:        String,
+    /// Altitude at which the object is planned to pass the waypoint.
+    restriction: Option<Position<f32>>,
+}
+
+struct Glidepath {
+    /// Distance along the route from the object to the runway threshold.
+    distance: Length<f32>,
+    /// Altitude of the runway threshold.
+    altitude: Position<f32>,
+    /// Angle of descent of the glidepath.
+    angle:    Angle,
+}
+
+impl Glidepath {
+    /// Altitude of the glidepath at a distance along the route.
+    fn altitude_at(&self, distance: Length<f32>) -> Position<f32> {
+        self.altitude + (self.distance - distance) * self.angle.acute_signed_tan()
+    }
+}
+
+#[derive(QueryData)]
+struct ObjectQuery {
+    object:  &'static Object,
+    display: &'static object::Display,
+    route:   Option<&'static Route>,
+    limits:  Option<&'static nav::Limits>,
+}
+
+#[derive(SystemParam)]
+struct UpdateParams<'w, 's> {
+    conf:           ReadConfig<'w, 's, Conf>,
+    current_object: Res<'w, object_info::CurrentObject>,
+    object_query:   Query<'w, 's, ObjectQuery, (With<object::Airborne>, With<plane::Control>)>,
+    waypoint_query: Query<'w, 's, &'static Waypoint>,
+    runway_query:   Query<'w, 's, &'static Runway>,
+    heightmap:      Res<'w, Heightmap>,
+}
+
+fn update_system(params: UpdateParams, mut profile: ResMut<Profile>) {
+    let Some(object) =
+        params.current_object.0.and_then(|entity| params.object_query.get(entity).ok())
+    else {
+        if profile.object_name.is_some() {
+            *profile = Profile::default();
+        }
+        return;
+    };
+    let conf = params.conf.read();
+    let profile = &mut *profile;
+
+    profile.object_name = Some(object.display.name.clone());
+    profile.altitude = object.object.position.altitude();
+    profile.fixes.clear();
+    profile.glidepath = None;
+
+    let mut positions = vec![object.object.position.horizontal()];
+    let mut distance = Length::ZERO;
+    let mut started_altitude = None;
+    for node in object.route.into_iter().flat_map(Route::iter) {
+        let (waypoint_entity, restriction) = match node {
+            route::Node::StartSetAltitude(node) => {
+                started_altitude = Some(node.altitude);
+                continue;
+            }
+            route::Node::DirectWaypoint(node) => (node.waypoint, node.altitude),
+            route::Node::AlignRunway(node) => (node.runway, None),
+            _ => continue,
+        };
+        let Some(waypoint) = params.waypoint_query.log_get(waypoint_entity) else { continue };
+
+        let position = waypoint.position.horizontal();
+        if let Some(&last) = positions.last() {
+            distance += last.distance_exact(position);
+        }
+        positions.push(position);
+        profile.fixes.push(Fix {
+            distance,
+            name: waypoint.name.clone(),
+            restriction: restriction.or(started_altitude.take()),
+        });
+
+        if let route::Node::AlignRunway(_) = node {
+            if let Ok(runway) = params.runway_query.get(waypoint_entity) {
+                profile.glidepath = Some(Glidepath {
+                    distance,
+                    altitude: waypoint.position.altitude(),
+                    angle: runway.glide_descent,
+                });
+            }
+            break;
+        }
+    }
+
+    sample_terrain(&mut profile.terrain, &positions, conf.terrain_interval, &params.heightmap);
+
+    let ground_speed = object.object.ground_speed.horizontal().magnitude_exact();
+    profile.top_of_descent = object.limits.and_then(|limits| {
+        let descent_rate = -limits.std_descent.vert_rate;
+        if descent_rate <= Speed::ZERO {
+            return None;
+        }
+        let gradient = descent_rate / ground_speed;
+        if !gradient.is_finite() || gradient <= 0.0 {
+            return None;
+        }
+
+        let fix = profile.fixes.iter().find(|fix| {
+            fix.restriction.is_some_and(|restriction| restriction < profile.altitude)
+        })?;
+        let descent = (profile.altitude - fix.restriction?) / gradient;
+        Some((fix.distance - descent).max(Length::ZERO))
+    });
+}
+
+/// Samples the terrain altitude along the polyline `positions` at every `interval`.
+fn sample_terrain(
+    samples: &mut Vec<(Length<f32>, Position<f32>)>,
+    positions: &[Position<Vec2>],
+    interval: Length<f32>,
+    heightmap: &Heightmap,
+) {
+    samples.clear();
+
+    let mut leg_start_distance = Length::ZERO;
+    for (index, window) in positions.windows(2).enumerate() {
+        let &[start, end] = window else { continue };
+        let leg_length = start.distance_exact(end);
+
+        let mut offset = if index == 0 { Length::ZERO } else { interval };
+        while offset < leg_length && samples.len() < MAX_TERRAIN_SAMPLES {
+            let position = start.lerp(end, offset / leg_length);
+            samples.push((leg_start_distance + offset, heightmap.altitude_at(position)));
+            offset += interval;
+        }
+
+        leg_start_distance += leg_length;
+        samples.push((leg_start_distance, heightmap.altitude_at(end)));
+    }
+
+    if samples.is_empty()
+        && let Some(&position) = positions.first()
+    {
+        samples.push((Length::ZERO, heightmap.altitude_at(position)));
+    }
+}
+
+pub struct TabType;
+
+#[derive(SystemParam)]
+pub struct UiParams<'w, 's> {
+    conf:       ReadConfig<'w, 's, Conf>,
+    profile:    Res<'w, Profile>,
+    transition: Res<'w, transition::Transition>,
+}
+
+impl dock::TabType for TabType {
+    type TitleSystemParam<'w, 's> = Res<'w, Profile>;
+    fn title(&self, profile: Res<Profile>) -> String {
+        match &profile.object_name {
+            Some(name) => format!("Profile: {name}"),
+            None => "Profile".into(),
+        }
+    }
+
+    type UiSystemParam<'w, 's> = UiParams<'w, 's>;
+    fn ui(&mut self, params: Self::UiSystemParam<'_, '_>, ui: &mut egui::Ui, _order: usize) {
+        if params.profile.object_name.is_none() {
+            ui.label("Select an airborne object to view its vertical profile.");
+            return;
+        }
+
+        let conf = params.conf.read();
+        let size = ui.available_size().max(egui::vec2(200.0, 120.0));
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        draw_profile(&painter, rect, &params.profile, &params.transition, &conf);
+    }
+
+    type OnCloseSystemParam<'w, 's> = ();
+
+    type PrepareRenderSystemParam<'w, 's> = ();
+}
+
+/// Transforms profile coordinates into screen coordinates.
+struct Axes {
+    plot:         egui::Rect,
+    max_distance: Length<f32>,
+    max_altitude: Position<f32>,
+}
+
+impl Axes {
+    const MARGIN_LEFT: f32 = 48.0;
+    const MARGIN_BOTTOM: f32 = 20.0;
+    const MARGIN: f32 = 8.0;
+
+    fn new(rect: egui::Rect, profile: &Profile) -> Self {
+        let max_distance = profile
+            .fixes
+            .last()
+            .map_or(Length::ZERO, |fix| fix.distance)
+            .max(profile.terrain.last().map_or(Length::ZERO, |&(distance, _)| distance))
+            .max(Length::from_nm(1.0));
+        let max_altitude = profile
+            .fixes
+            .iter()
+            .filter_map(|fix| fix.restriction)
+            .chain(profile.terrain.iter().map(|&(_, altitude)| altitude))
+            .fold(profile.altitude, Position::max)
+            .max(Position::from_amsl_feet(1000.0));
+        Self {
+            plot: egui::Rect::from_min_max(
+                rect.min + egui::vec2(Self::MARGIN_LEFT, Self::MARGIN),
+                rect.max - egui::vec2(Self::MARGIN, Self::MARGIN_BOTTOM),
+            ),
+            max_distance,
+            max_altitude: Position::SEA_LEVEL + max_altitude.amsl() * 1.1,
+        }
+    }
+
+    fn x(&self, distance: Length<f32>) -> f32 {
+        self.plot.left() + self.plot.width() * (distance / self.max_distance)
+    }
+
+    fn y(&self, altitude: Position<f32>) -> f32 {
+        self.plot.bottom()
+            - self.plot.height() * altitude.ratio_between(Position::SEA_LEVEL, self.max_altitude)
+    }
+
+    fn point(&self, distance: Length<f32>, altitude: Position<f32>) -> egui::Pos2 {
+        egui::pos2(self.x(distance), self.y(altitude))
+    }
+}
+
+fn draw_profile(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    profile: &Profile,
+    transition: &transition::Transition,
+    conf: &ConfRead,
+) {
+    let axes = Axes::new(rect, profile);
+    let text_color = painter.ctx().style().visuals.text_color();
+    let grid_color = painter.ctx().style().visuals.weak_text_color().gamma_multiply(0.3);
+    let font = egui::FontId::proportional(11.0);
+
+    let max_feet = axes.max_altitude.amsl().into_feet();
+    let step = [1000.0, 2000.0, 5000.0, 10000.0]
+        .into_iter()
+        .find(|&step| max_feet / step <= 8.0)
+        .unwrap_or(20000.0);
+    let mut feet = 0.0;
+    while feet <= max_feet {
+        let y = axes.y(Position::from_amsl_feet(feet));
+        painter.hline(axes.plot.x_range(), y, egui::Stroke::new(1.0, grid_color));
+        painter.text(
+            egui::pos2(axes.plot.left() - 4.0, y),
+            egui::Align2::RIGHT_CENTER,
+            format!("{feet:.0}"),
+            font.clone(),
+            text_color,
+        );
+        feet += step;
+    }
+
+    draw_transition_level(painter, &axes, transition, conf, &font);
+
+    let terrain_color = to_color32(conf.terrain_color);
+    for window in profile.terrain.windows(2) {
+        let &[(start_distance, start_altitude), (end_distance, end_altitude)] = window else {
+            continue;
+        };
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                axes.point(start_distance, Position::SEA_LEVEL),
+                axes.point(start_distance, start_altitude),
+                axes.point(end_distance, end_altitude),
+                axes.point(end_distance, Position::SEA_LEVEL),
+            ],
+            terrain_color,
+            egui::Stroke::NONE,
+        ));
+    }
+
+    if let Some(glidepath) = &profile.glidepath {
+        let start = (glidepath.distance - Length::from_nm(15.0)).max(Length::ZERO);
+        painter.line_segment(
+            [
+                axes.point(start, glidepath.altitude_at(start)),
+                axes.point(glidepath.distance, glidepath.altitude),
+            ],
+            egui::Stroke::new(1.5, to_color32(conf.glidepath_color)),
+        );
+    }
+
+    let restriction_color = to_color32(conf.restriction_color);
+    for fix in &profile.fixes {
+        let x = axes.x(fix.distance);
+        painter.vline(x, axes.plot.y_range(), egui::Stroke::new(1.0, grid_color));
+        painter.text(
+            egui::pos2(x, axes.plot.bottom() + 2.0),
+            egui::Align2::CENTER_TOP,
+            &fix.name,
+            font.clone(),
+            text_color,
+        );
+        if let Some(restriction) = fix.restriction {
+            let point = axes.point(fix.distance, restriction);
+            painter.circle_filled(point, 3.0, restriction_color);
+            painter.text(
+                point + egui::vec2(4.0, -4.0),
+                egui::Align2::LEFT_BOTTOM,
+                transition.reading(restriction).to_string(),
+                font.clone(),
+                restriction_color,
+            );
+        }
+    }
+
+    let mut path = vec![axes.point(Length::ZERO, profile.altitude)];
+    if let Some(tod) = profile.top_of_descent {
+        path.push(axes.point(tod, profile.altitude));
+    }
+    for fix in &profile.fixes {
+        if let Some(restriction) = fix.restriction {
+            path.push(axes.point(fix.distance, restriction));
+        }
+    }
+    let path_color = to_color32(conf.path_color);
+    painter.add(egui::Shape::line(path, egui::Stroke::new(1.5, path_color)));
+    painter.circle_filled(axes.point(Length::ZERO, profile.altitude), 4.0, path_color);
+
+    if let Some(tod) = profile.top_of_descent {
+        let tod_color = to_color32(conf.tod_color);
+        let point = axes.point(tod, profile.altitude);
+        painter.add(egui::Shape::convex_polygon(
+            vec![point + egui::vec2(-4.0, -8.0), point + egui::vec2(4.0, -8.0), point],
+            tod_color,
+            egui::Stroke::NONE,
+        ));
+        painter.text(
+            point + egui::vec2(0.0, -10.0),
+            egui::Align2::CENTER_BOTTOM,
+            "ToD",
+            font,
+            tod_color,
+        );
+    }
+}
+
+/// Draws a dashed line at the transition level, labelled with its flight level.
+fn draw_transition_level(
+    painter: &egui::Painter,
+    axes: &Axes,
+    transition: &transition::Transition,
+    conf: &ConfRead,
+    font: &egui::FontId,
+) {
+    let transition_altitude = transition.true_altitude(transition.level);
+    if transition_altitude > axes.max_altitude {
+        return;
+    }
+
+    let y = axes.y(transition_altitude);
+    let transition_color = to_color32(conf.transition_color);
+    painter.add(egui::Shape::dashed_line(
+        &[egui::pos2(axes.plot.left(), y), egui::pos2(axes.plot.right(), y)],
+        egui::Stroke::new(1.0, transition_color),
+        6.0,
+        4.0,
+    ));
+    painter.text(
+        egui::pos2(axes.plot.right(), y - 2.0),
+        egui::Align2::RIGHT_BOTTOM,
+        format!("TL {}", transition.reading(transition_altitude)),
+        font.clone(),
+        transition_color,
+    );
+}
+
+fn to_color32(color: Color) -> egui::Color32 {
+    let color = color.to_srgba();
+    #[expect(
+        clippy::cast_sign_loss,
+        clippy::cast_possible_truncation,
+        reason = "rgba should be within [0., 1.]"
+    )]
+    egui::Color32::from_rgba_unmultiplied(
+        (color.red * 255.) as u8,
+        (color.green * 255.) as u8,
+        (color.blue * 255.) as u8,
+        (color.alpha * 255.) as u8,
+    )
+}
@@ -0,0 +1,470 @@
+//! Editor for the spawn sets of the loaded level.
+//!
+//! Edits are made on a draft of [`store::Level::spawn_sets`]
+//! and only take effect when the level is restarted with the draft applied.
+//! The traffic mix resulting from the draft is previewed
+//! both analytically and by simulated draws.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Res, ResMut, Single};
+use bevy_egui::{EguiPrimaryContextPass, egui};
+use egui_material_icons::icons;
+use omniatc::load;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use store::{SpawnSet, SpawnTrigger, WeightedList};
+
+use crate::render::dock::{self, TabPlacement};
+use crate::render::{MenuButton, MenuButtonClicked};
+use crate::{EguiSystemSets, render};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.world_mut().spawn((
+            MenuButton {
+                icon:     icons::ICON_TUNE,
+                title:    "Spawn editor".into(),
+                group:    render::MenuButtonGroup::Level,
+                priority: 40,
+            },
+            MenuButtonMarker,
+        ));
+
+        app.add_systems(EguiPrimaryContextPass, open_tab_system.in_set(EguiSystemSets::ManageTabs));
+    }
+}
+
+/// Number of simulated draws in the traffic mix preview.
+const PREVIEW_DRAWS: u32 = 1000;
+
+#[derive(Component)]
+struct MenuButtonMarker;
+
+fn open_tab_system(
+    mut dock_state: ResMut<dock::State>,
+    mut menu_button_clicked: Single<&mut MenuButtonClicked, With<MenuButtonMarker>>,
+) {
+    if menu_button_clicked.consume()
+        && let Some(state) = &mut dock_state.state
+    {
+        dock::focus_or_create_tab(
+            state,
+            || dock::Tab::SpawnEditor(TabType::default()),
+            dock::ReplaceTab(|tab| matches!(tab, dock::Tab::SpawnEditor(_)))
+                .or_always(dock::NewSurface),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct TabType {
+    draft: Option<Draft>,
+}
+
+struct Draft {
+    /// The loaded file that the draft was created from.
+    base:       Arc<store::File>,
+    /// The edited spawn sets.
+    spawn_sets: WeightedList<SpawnSet>,
+    /// Seed for the simulated draws.
+    seed:       u64,
+}
+
+impl Draft {
+    fn new(base: Arc<store::File>) -> Self {
+        let spawn_sets = base.level.spawn_sets.clone();
+        Self { base, spawn_sets, seed: 0 }
+    }
+}
+
+impl dock::TabType for TabType {
+    type TitleSystemParam<'w, 's> = ();
+    fn title(&self, (): ()) -> String { "Spawn editor".into() }
+
+    type UiSystemParam<'w, 's> = (Res<'w, load::LoadedFile>, Commands<'w, 's>);
+    fn ui(
+        &mut self,
+        (loaded, mut commands): Self::UiSystemParam<'_, '_>,
+        ui: &mut egui::Ui,
+        _order: usize,
+    ) {
+        let Some(file) = &loaded.0 else {
+            ui.label("No level is loaded.");
+            return;
+        };
+        let draft = match &mut self.draft {
+            Some(draft) if Arc::ptr_eq(&draft.base, file) => draft,
+            draft => draft.insert(Draft::new(Arc::clone(file))),
+        };
+
+        ui.horizontal(|ui| {
+            if ui.button("Apply and restart").clicked() {
+                let mut file = store::File::clone(&draft.base);
+                file.level.spawn_sets = draft.spawn_sets.clone();
+                commands.queue(load::StagedCommand {
+                    source:   load::Source::Parsed(Box::new(file)),
+                    on_error: Box::new(|_world, err| bevy::log::error!("Load error: {err}")),
+                });
+            }
+            if ui.button("Discard changes").clicked() {
+                draft.spawn_sets = draft.base.level.spawn_sets.clone();
+            }
+        });
+
+        let issues = validate(
+            &KnownRefs::new(&draft.base.level),
+            &draft.base.level.spawn_trigger,
+            &draft.spawn_sets,
+        );
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::CollapsingHeader::new("Traffic mix").default_open(true).show(ui, |ui| {
+                show_preview(ui, draft);
+            });
+
+            for issue in issues.iter().filter(|issue| issue.set.is_none()) {
+                ui.colored_label(egui::Color32::LIGHT_RED, &issue.message);
+            }
+
+            let mut removed = None;
+            for (index, entry) in draft.spawn_sets.items.iter_mut().enumerate() {
+                ui.push_id(index, |ui| {
+                    egui::CollapsingHeader::new(set_title(index, &entry.item)).show(ui, |ui| {
+                        for issue in issues.iter().filter(|issue| issue.set == Some(index)) {
+                            ui.colored_label(egui::Color32::LIGHT_RED, &issue.message);
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label("Weight");
+                            weight_ui(ui, &mut entry.weight);
+                            ui.checkbox(&mut entry.item.locked, "Locked");
+                            if ui.button(icons::ICON_DELETE).on_hover_text("Remove set").clicked() {
+                                removed = Some(index);
+                            }
+                        });
+                        show_set(ui, &draft.base.level, &mut entry.item);
+                    });
+                });
+            }
+            if let Some(index) = removed {
+                draft.spawn_sets.items.remove(index);
+            }
+        });
+    }
+
+    type OnCloseSystemParam<'w, 's> = ();
+
+    type PrepareRenderSystemParam<'w, 's> = ();
+}
+
+fn set_title(index: usize, set: &SpawnSet) -> String {
+    let mut title = match &set.id {
+        Some(id) => id.0.clone(),
+        None => format!("Set {}", index + 1),
+    };
+    if set.locked {
+        title.push_str(" (locked)");
+    }
+    title
+}
+
+fn show_preview(ui: &mut egui::Ui, draft: &mut Draft) {
+    let mut mix = expected_mix(&draft.spawn_sets);
+    simulate(&mut mix, &draft.spawn_sets, PREVIEW_DRAWS, &mut SmallRng::seed_from_u64(draft.seed));
+    let rate = hourly_rate(&draft.base.level.spawn_trigger);
+
+    ui.horizontal(|ui| {
+        match &draft.base.level.spawn_trigger {
+            SpawnTrigger::Disabled => ui.label("Spawning is disabled."),
+            SpawnTrigger::Periodic { .. } => {
+                ui.label(format!("{:.1} spawns per hour", rate.unwrap_or_default()))
+            }
+            SpawnTrigger::ObjectCount { count } => {
+                ui.label(format!("Maintains {count} objects; hourly rates are not known."))
+            }
+            SpawnTrigger::Timetable(_) => {
+                ui.label("Spawns follow the timetable; spawn sets are not used.")
+            }
+        };
+        if ui.button("Redraw").clicked() {
+            draft.seed = draft.seed.wrapping_add(1);
+        }
+    });
+
+    for (heading, shares) in [("Route", &mix.routes), ("Type", &mix.types)] {
+        egui::Grid::new(heading).striped(true).show(ui, |ui| {
+            ui.strong(heading);
+            ui.strong("Expected");
+            ui.strong("Simulated");
+            ui.strong("Per hour");
+            ui.end_row();
+
+            for (name, share) in shares {
+                ui.label(name);
+                ui.label(format!("{:.1}%", share.expected * 100.));
+                #[expect(clippy::cast_precision_loss, reason = "draw counts are small")]
+                ui.label(format!("{:.1}%", share.simulated as f32 / PREVIEW_DRAWS as f32 * 100.));
+                match rate {
+                    Some(rate) => ui.label(format!("{:.1}", share.expected * rate)),
+                    None => ui.label("-"),
+                };
+                ui.end_row();
+            }
+        });
+        ui.add_space(4.);
+    }
+}
+
+fn show_set(ui: &mut egui::Ui, level: &store::Level, set: &mut SpawnSet) {
+    ui.label("Routes");
+    weighted_list_ui(ui, "routes", &mut set.route, |route| route.preset.0.clone());
+    let used: HashSet<&str> =
+        set.route.items.iter().map(|entry| entry.item.preset.0.as_str()).collect();
+    let unused_preset = level
+        .route_presets
+        .iter()
+        .filter_map(|preset| preset.ref_id.as_ref())
+        .find(|ref_id| !used.contains(ref_id.0.as_str()))
+        .cloned();
+    if let (Some(preset), Some(template)) = (unused_preset, set.route.items.first()) {
+        let template = template.item.clone();
+        if ui.button(format!("Add route {}", preset.0)).clicked() {
+            set.route.items.push(store::WeightedEntry {
+                item:   store::SpawnRoute { preset, ..template },
+                weight: 1.,
+            });
+        }
+    }
+
+    ui.label("Types");
+    weighted_list_ui(ui, "types", &mut set.types, |ty| ty.0.clone());
+    let mut object_types: Vec<_> = level
+        .object_types
+        .keys()
+        .filter(|ty| set.types.items.iter().all(|entry| entry.item != **ty))
+        .collect();
+    object_types.sort_by(|a, b| a.0.cmp(&b.0));
+    if !object_types.is_empty() {
+        egui::ComboBox::from_id_salt("add-type").selected_text("Add type").show_ui(ui, |ui| {
+            for ty in object_types {
+                if ui.selectable_label(false, &ty.0).clicked() {
+                    set.types.items.push(store::WeightedEntry { item: ty.clone(), weight: 1. });
+                }
+            }
+        });
+    }
+}
+
+/// Shows the weights of a list, with buttons to remove entries.
+fn weighted_list_ui<T>(
+    ui: &mut egui::Ui,
+    id: &str,
+    list: &mut WeightedList<T>,
+    label: impl Fn(&T) -> String,
+) {
+    let mut removed = None;
+    egui::Grid::new(id).show(ui, |ui| {
+        for (index, entry) in list.items.iter_mut().enumerate() {
+            ui.label(label(&entry.item));
+            weight_ui(ui, &mut entry.weight);
+            if ui.button(icons::ICON_DELETE).clicked() {
+                removed = Some(index);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(index) = removed {
+        list.items.remove(index);
+    }
+}
+
+fn weight_ui(ui: &mut egui::Ui, weight: &mut f32) {
+    ui.add(egui::DragValue::new(weight).range(0.0..=f32::MAX).speed(0.1));
+}
+
+/// Share of spawns taken by a route or an object type.
+#[derive(Default)]
+struct Share {
+    /// Probability that a spawn uses this item.
+    expected:  f32,
+    /// Number of simulated draws that used this item.
+    simulated: u32,
+}
+
+/// Traffic mix resulting from a list of spawn sets.
+#[derive(Default)]
+struct Mix {
+    /// Shares by route preset reference.
+    routes: BTreeMap<String, Share>,
+    /// Shares by object type.
+    types:  BTreeMap<String, Share>,
+}
+
+/// Probability of each entry in `list` being sampled among those matching `filter`,
+/// consistent with [`WeightedList::sample_where`].
+fn probabilities<T>(
+    list: &WeightedList<T>,
+    filter: impl Fn(&T) -> bool,
+) -> impl Iterator<Item = (&T, f32)> {
+    let matching: Vec<_> = list.items.iter().filter(|entry| filter(&entry.item)).collect();
+    let total: f32 = matching.iter().map(|entry| entry.weight).sum();
+    let singleton = matching.len() == 1;
+    matching.into_iter().map(move |entry| {
+        let probability = if singleton {
+            1.
+        } else if total > 0. {
+            entry.weight / total
+        } else {
+            0.
+        };
+        (&entry.item, probability)
+    })
+}
+
+/// Computes the expected share of each route and object type among spawns.
+///
+/// Locked sets are excluded, as they do not spawn objects until unlocked.
+fn expected_mix(sets: &WeightedList<SpawnSet>) -> Mix {
+    let mut mix = Mix::default();
+    for (set, set_probability) in probabilities(sets, |set| !set.locked) {
+        for (route, probability) in probabilities(&set.route, |_| true) {
+            mix.routes.entry(route.preset.0.clone()).or_default().expected +=
+                set_probability * probability;
+        }
+        for (ty, probability) in probabilities(&set.types, |_| true) {
+            mix.types.entry(ty.0.clone()).or_default().expected += set_probability * probability;
+        }
+    }
+    mix
+}
+
+/// Records `draws` simulated spawns from `sets` into `mix`.
+fn simulate(mix: &mut Mix, sets: &WeightedList<SpawnSet>, draws: u32, rng: &mut impl rand::Rng) {
+    for _ in 0..draws {
+        let Some(set) = sets.sample_where(rng, |set| !set.locked) else { return };
+        if let Some(route) = set.route.sample(rng) {
+            mix.routes.entry(route.preset.0.clone()).or_default().simulated += 1;
+        }
+        if let Some(ty) = set.types.sample(rng) {
+            mix.types.entry(ty.0.clone()).or_default().simulated += 1;
+        }
+    }
+}
+
+/// Expected number of spawns per hour under `trigger`,
+/// if it does not depend on the gameplay.
+fn hourly_rate(trigger: &SpawnTrigger) -> Option<f32> {
+    match *trigger {
+        SpawnTrigger::Disabled => Some(0.),
+        SpawnTrigger::Periodic { duration } if !duration.is_zero() => {
+            Some(Duration::from_hours(1).as_secs_f32() / duration.as_secs_f32())
+        }
+        SpawnTrigger::Periodic { .. }
+        | SpawnTrigger::ObjectCount { .. }
+        | SpawnTrigger::Timetable(_) => None,
+    }
+}
+
+/// A problem with the spawn sets that would prevent them from loading or spawning as intended.
+#[derive(Debug, PartialEq)]
+struct Issue {
+    /// Index of the spawn set with the issue, or `None` if it affects all sets.
+    set:     Option<usize>,
+    message: String,
+}
+
+/// Names that spawn sets may reference.
+struct KnownRefs<'a> {
+    /// `ref_id`s of route presets.
+    presets: HashSet<&'a str>,
+    /// Names of object types.
+    types:   HashSet<&'a str>,
+}
+
+impl<'a> KnownRefs<'a> {
+    fn new(level: &'a store::Level) -> Self {
+        Self {
+            presets: level
+                .route_presets
+                .iter()
+                .filter_map(|preset| preset.ref_id.as_ref())
+                .map(|ref_id| ref_id.0.as_str())
+                .collect(),
+            types:   level.object_types.keys().map(|ty| ty.0.as_str()).collect(),
+        }
+    }
+}
+
+/// Checks that the references in `sets` exist and that the weights are usable.
+fn validate(
+    known: &KnownRefs,
+    trigger: &SpawnTrigger,
+    sets: &WeightedList<SpawnSet>,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if sets.items.is_empty() {
+        if matches!(trigger, SpawnTrigger::Periodic { .. } | SpawnTrigger::ObjectCount { .. }) {
+            issues.push(Issue { set: None, message: "No spawn sets".into() });
+        }
+    } else if sets.items.iter().all(|entry| entry.item.locked) {
+        issues.push(Issue { set: None, message: "All spawn sets are locked".into() });
+    } else {
+        check_weights(&mut issues, None, "spawn sets", sets);
+    }
+
+    for (index, entry) in sets.items.iter().enumerate() {
+        let set = &entry.item;
+
+        for route in &set.route.items {
+            if !known.presets.contains(route.item.preset.0.as_str()) {
+                issues.push(Issue {
+                    set:     Some(index),
+                    message: format!("Route preset {} does not exist", route.item.preset.0),
+                });
+            }
+        }
+        for ty in &set.types.items {
+            if !known.types.contains(ty.item.0.as_str()) {
+                issues.push(Issue {
+                    set:     Some(index),
+                    message: format!("Object type {} does not exist", ty.item.0),
+                });
+            }
+        }
+
+        check_weights(&mut issues, Some(index), "routes", &set.route);
+        check_weights(&mut issues, Some(index), "types", &set.types);
+        check_weights(&mut issues, Some(index), "name generators", &set.gen_name);
+        check_weights(&mut issues, Some(index), "positions", &set.position);
+    }
+
+    issues
+}
+
+fn check_weights<T>(
+    issues: &mut Vec<Issue>,
+    set: Option<usize>,
+    name: &str,
+    list: &WeightedList<T>,
+) {
+    let mut push = |message: String| issues.push(Issue { set, message });
+    if list.items.is_empty() {
+        push(format!("No {name}"));
+    } else if list.items.iter().any(|entry| entry.weight < 0. || entry.weight.is_nan()) {
+        push(format!("Invalid weight in {name}"));
+    } else if list.items.len() > 1 && list.items.iter().all(|entry| entry.weight <= 0.) {
+        push(format!("All {name} have zero weight"));
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use store::{SpawnSet, SpawnTrigger, WeightedList};
+
+use super::{Issue, KnownRefs, expected_mix, hourly_rate, simulate, validate};
+
+fn spawn_set(routes: &[(&str, f32)], types: &[(&str, f32)], locked: bool) -> SpawnSet {
+    SpawnSet {
+        route: routes
+            .iter()
+            .map(|&(preset, weight)| {
+                (
+                    store::SpawnRoute {
+                        preset:      preset.into(),
+                        destination: store::Destination::VacateAnyRunway,
+                        score:       store::Score(1),
+                    },
+                    weight,
+                )
+            })
+            .into(),
+        gen_name: WeightedList::singleton(store::NameGenerator::Registration {
+            region: store::RegistrationRegion::UnitedKingdom,
+        }),
+        types: types.iter().map(|&(ty, weight)| (store::ObjectTypeRef::from(ty), weight)).into(),
+        position: WeightedList::singleton(store::SpawnPosition::Aprons {
+            aerodrome: "MAIN".into(),
+            aprons:    None,
+        }),
+        endurance: None,
+        vfr: None,
+        formation: None,
+        id: None,
+        locked,
+    }
+}
+
+#[test]
+fn test_expected_mix_excludes_locked_sets() {
+    let sets = WeightedList::from([
+        (spawn_set(&[("ARR", 3.), ("DEP", 1.)], &[("A320", 1.)], false), 1.),
+        (spawn_set(&[("DEP", 1.)], &[("B738", 1.), ("A320", 1.)], false), 1.),
+        (spawn_set(&[("VFR", 1.)], &[("C172", 1.)], true), 10.),
+    ]);
+    let mix = expected_mix(&sets);
+
+    assert!((mix.routes["ARR"].expected - 0.375).abs() < 1e-6);
+    assert!((mix.routes["DEP"].expected - 0.625).abs() < 1e-6);
+    assert!(!mix.routes.contains_key("VFR"));
+    assert!((mix.types["A320"].expected - 0.75).abs() < 1e-6);
+    assert!((mix.types["B738"].expected - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_simulated_draws_approach_expected_mix() {
+    let sets = WeightedList::from([
+        (spawn_set(&[("ARR", 3.), ("DEP", 1.)], &[("A320", 1.)], false), 1.),
+        (spawn_set(&[("DEP", 1.)], &[("B738", 1.)], false), 1.),
+    ]);
+    let mut mix = expected_mix(&sets);
+    simulate(&mut mix, &sets, 10000, &mut SmallRng::seed_from_u64(1));
+
+    for share in mix.routes.values().chain(mix.types.values()) {
+        #[expect(clippy::cast_precision_loss, reason = "draw counts are small")]
+        let simulated = share.simulated as f32 / 10000.;
+        assert!((simulated - share.expected).abs() < 0.03, "{simulated} vs {}", share.expected);
+    }
+}
+
+#[test]
+fn test_hourly_rate() {
+    let rate = hourly_rate(&SpawnTrigger::Periodic { duration: Duration::from_mins(2) });
+    assert_eq!(rate, Some(30.));
+    assert_eq!(hourly_rate(&SpawnTrigger::ObjectCount { count: 5 }), None);
+}
+
+#[test]
+fn test_validate_references() {
+    let known = KnownRefs { presets: HashSet::from(["ARR"]), types: HashSet::from(["A320"]) };
+    let sets = WeightedList::from([
+        (spawn_set(&[("ARR", 1.)], &[("A320", 1.)], false), 1.),
+        (spawn_set(&[("ARR", 1.), ("DEP", 0.)], &[("B738", 1.)], false), 1.),
+    ]);
+    let issues = validate(&known, &SpawnTrigger::ObjectCount { count: 5 }, &sets);
+
+    assert_eq!(
+        issues,
+        [
+            Issue { set: Some(1), message: "Route preset DEP does not exist".into() },
+            Issue { set: Some(1), message: "Object type B738 does not exist".into() },
+        ]
+    );
+}
+
+#[test]
+fn test_validate_weights() {
+    let known = KnownRefs { presets: HashSet::from(["ARR"]), types: HashSet::from(["A320"]) };
+    let sets = WeightedList::from([(spawn_set(&[("ARR", 0.), ("ARR", 0.)], &[], true), 1.)]);
+    let issues = validate(&known, &SpawnTrigger::ObjectCount { count: 5 }, &sets);
+
+    assert_eq!(
+        issues,
+        [
+            Issue { set: None, message: "All spawn sets are locked".into() },
+            Issue { set: Some(0), message: "All routes have zero weight".into() },
+            Issue { set: Some(0), message: "No types".into() },
+        ]
+    );
+}
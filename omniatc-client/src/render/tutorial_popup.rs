@@ -7,7 +7,7 @@ use bevy::ecs::system::{Commands, Query};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 
 use crate::EguiSystemSets;
-use crate::render::level_info;
+use crate::render::{callsign, level_info};
 
 pub struct Plug;
 
@@ -28,6 +28,7 @@ fn setup_window_system(
     quest_query: Query<(level_info::quests::QuestData, Has<Focused>)>,
     focused_query: Query<Entity, With<Focused>>,
     mut commands: Commands,
+    mut links: callsign::Links,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else { return };
 
@@ -52,7 +53,7 @@ fn setup_window_system(
                     ..Default::default()
                 })
                 .show(ctx, |ui| {
-                    quest.show(ui, &mut commands);
+                    quest.show(ui, &mut commands, &mut links);
                 });
 
             Some(quest.entity)
@@ -5,10 +5,18 @@ use bevy::transform::components::Transform;
 use math::{Length, Position};
 
 mod aerodrome;
+mod airway;
 pub mod camera;
+pub mod cull;
+pub mod departure_queue;
 pub mod object;
+mod pair;
 pub mod pick;
+mod quest_marker;
+mod rescue;
 mod runway;
+mod sector;
+mod turbulence;
 mod wake;
 mod waypoint;
 
@@ -20,10 +28,18 @@ impl Plugin for Plug {
             camera::Plug,
             object::Plug,
             pick::Plug,
+            pair::Plug,
             waypoint::Plug,
+            airway::Plug,
+            sector::Plug,
+            departure_queue::Plug,
             runway::Plug,
             aerodrome::Plug,
             wake::Plug,
+            turbulence::Plug,
+            quest_marker::Plug,
+            rescue::Plug,
+            cull::Plug,
         ));
     }
 }
@@ -39,23 +55,35 @@ pub struct Active(pub bool);
 #[repr(u16)]
 pub enum Zorder {
     Terrain,
+    TurbulenceOverlay,
+    SectorBoundary,
     GroundSegmentBackground,
     GroundSegmentCenterline,
     RunwayStrip,
     Localizer,
     LocalizerGlidePoint,
+    Airway,
     GroundSegmentLabel,
+    AirwayLabel,
+    SectorLabel,
     ObjectTrack,
     WaypointSprite,
     WaypointLabel,
+    WakeRibbon,
     WakeOverlay,
     ObjectSprite,
     ObjectSeparationRing,
     ObjectVector,
     ObjectLabel,
+    DepartureQueueLabel,
     RoutePresetPreview,
     ObjectTrackPreview,
+    TransientRoutePreview,
+    TransientRouteLabel,
     PossibleGroundPathPreview,
+    PairSeparationLine,
+    PairSeparationLabel,
+    QuestMarker,
     ScaleRuler,
     ScaleRulerLabel,
 }
@@ -1,30 +1,32 @@
+use std::collections::HashMap;
+
 use bevy::app::{self, App, Plugin};
 use bevy::asset::{Assets, Handle, RenderAssetUsages};
 use bevy::camera::visibility::Visibility;
-use bevy::color::Color;
+use bevy::color::{Color, Mix};
 use bevy::ecs::bundle::Bundle;
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::{Entity, EntityHashSet};
 use bevy::ecs::message::MessageReader;
 use bevy::ecs::name::Name;
 use bevy::ecs::query::{QueryData, With};
-use bevy::ecs::relationship::{Relationship, RelationshipTarget};
 use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Local, ParamSet, Query, Res, ResMut, Single, SystemParam};
-use bevy::math::{Dir2, Vec2};
+use bevy::math::{Dir2, IVec2, Rect, Vec2};
 use bevy::mesh::{Mesh, Mesh2d, PrimitiveTopology, VertexAttributeValues};
 use bevy::sprite::{Anchor, Text2d};
 use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy::time::{self, Time};
 use bevy::transform::components::GlobalTransform;
 use bevy_mod_config::{self, AppExt, Config, ReadConfig, ReadConfigChange};
 use itertools::Itertools;
 use math::{Angle, Heading, Length, LengthUnit, Position};
 use omniatc::QueryTryLog;
 use omniatc::level::aerodrome::Aerodrome;
-use omniatc::level::ground;
+use omniatc::level::{clock, ground};
 
-use crate::render::twodim::{Zorder, camera};
+use crate::render::twodim::{Zorder, camera, cull};
 use crate::util::{ActiveCamera2d, AnchorConf, billboard};
 use crate::{ConfigManager, render};
 
@@ -50,10 +52,11 @@ impl Plugin for Plug {
 fn update_visibility_system(
     conf: ReadConfig<Conf>,
     camera: ActiveCamera2d,
-    mesh_query: Query<(&mut Visibility, &MeshType)>,
+    viewport: Res<cull::Viewport>,
+    mesh_query: Query<(&mut Visibility, &MeshType, Option<&Chunk>)>,
 ) {
     let pixel_length = camera.pixel_length();
-    for (mut vis, mesh_type) in mesh_query {
+    for (mut vis, mesh_type, chunk) in mesh_query {
         let min_width = match mesh_type {
             MeshType::TaxiwayCenterline => conf.read().taxiway.centerline_render_zoom,
             MeshType::ApronCenterline => conf.read().apron.centerline_render_zoom,
@@ -63,7 +66,10 @@ fn update_visibility_system(
             MeshType::ApronLabel => conf.read().apron.label_render_zoom,
         };
 
-        if pixel_length <= min_width {
+        let on_screen = chunk.is_none_or(|chunk| {
+            viewport.overlaps([Position::new(chunk.bounds.min), Position::new(chunk.bounds.max)])
+        });
+        if pixel_length <= min_width && on_screen {
             *vis = Visibility::Visible;
         } else {
             *vis = Visibility::Hidden;
@@ -86,8 +92,12 @@ struct FindOutdatedAerodromesParam<'w, 's> {
     changes:           MessageReader<'w, 's, ground::ChangedMessage>,
     conf:              ReadConfig<'w, 's, Conf>,
     camera:            Single<'w, 's, &'static GlobalTransform, With<camera::UiState>>,
-    aerodrome_query:   Query<'w, 's, Entity, With<Aerodrome>>,
+    viewport:          Res<'w, cull::Viewport>,
+    aerodrome_query: Query<'w, 's, (Entity, &'static ground::AerodromeEndpoints), With<Aerodrome>>,
+    endpoint_query:    Query<'w, 's, &'static ground::Endpoint>,
     last_render_width: Local<'s, Option<Length<f32>>>,
+    /// Outdated aerodromes whose regeneration is deferred until they enter the viewport.
+    pending:           Local<'s, EntityHashSet>,
 }
 
 impl FindOutdatedAerodromesParam<'_, '_> {
@@ -101,15 +111,33 @@ impl FindOutdatedAerodromesParam<'_, '_> {
             SCALE_REGEN_BASE.powi(self.camera.scale().y.log(SCALE_REGEN_BASE).floor() as i32);
         let min_width = Length::new(scale_y) * conf.segment_thickness;
 
-        let mut aerodromes_outdated = EntityHashSet::new();
         if self.last_render_width.replace(min_width) == Some(min_width) {
             for &ground::ChangedMessage { aerodrome } in self.changes.read() {
-                aerodromes_outdated.insert(aerodrome);
+                self.pending.insert(aerodrome);
             }
         } else {
-            aerodromes_outdated.extend(self.aerodrome_query.iter());
+            self.pending.extend(self.aerodrome_query.iter().map(|(entity, _)| entity));
             self.changes.read().for_each(drop);
         }
+
+        let mut aerodromes_outdated = EntityHashSet::new();
+        self.pending.retain(|&aerodrome| {
+            let visible = match self.aerodrome_query.get(aerodrome) {
+                Ok((_, endpoints)) => self.viewport.overlaps(
+                    endpoints
+                        .endpoints()
+                        .iter()
+                        .filter_map(|&endpoint| self.endpoint_query.get(endpoint).ok())
+                        .map(|endpoint| endpoint.position),
+                ),
+                // let regenerate() report the missing aerodrome
+                Err(_) => true,
+            };
+            if visible {
+                aerodromes_outdated.insert(aerodrome);
+            }
+            !visible
+        });
         (aerodromes_outdated, min_width)
     }
 }
@@ -136,24 +164,24 @@ impl RegenerateMeshParam<'_, '_> {
 
         let aerodrome = self.aerodrome_query.log_get(aerodrome_entity)?;
 
-        let mut taxiway_centerline_positions = Vec::new();
-        let mut apron_centerline_positions = Vec::new();
-        let mut taxiway_background_positions = Vec::new();
-        let mut apron_background_positions = Vec::new();
+        let mut taxiway_centerline_chunks = Chunks::new(conf.chunk_size);
+        let mut apron_centerline_chunks = Chunks::new(conf.chunk_size);
+        let mut taxiway_background_chunks = Chunks::new(conf.chunk_size);
+        let mut apron_background_chunks = Chunks::new(conf.chunk_size);
 
         {
             let regen_lines = self.steps.p0();
             for &segment_entity in aerodrome.segments.segments() {
                 regen_lines.push_segment(
                     segment_entity,
-                    &mut taxiway_centerline_positions,
-                    &mut apron_centerline_positions,
+                    &mut taxiway_centerline_chunks,
+                    &mut apron_centerline_chunks,
                     |_| centerline_width,
                 )?;
                 regen_lines.push_segment(
                     segment_entity,
-                    &mut taxiway_background_positions,
-                    &mut apron_background_positions,
+                    &mut taxiway_background_chunks,
+                    &mut apron_background_chunks,
                     |segment| segment.width,
                 )?;
             }
@@ -161,54 +189,31 @@ impl RegenerateMeshParam<'_, '_> {
             for &endpoint_entity in aerodrome.endpoints.endpoints() {
                 regen_lines.push_endpoint(
                     endpoint_entity,
-                    &mut taxiway_centerline_positions,
+                    &mut taxiway_centerline_chunks,
                     |_, _| centerline_width,
                     &conf,
                 )?;
                 regen_lines.push_endpoint(
                     endpoint_entity,
-                    &mut taxiway_background_positions,
+                    &mut taxiway_background_chunks,
                     |from, to| from.width.min(to.width),
                     &conf,
                 )?;
             }
         }
 
-        {
-            let mut commit_ctx = self.steps.p1();
-            commit_ctx.commit_mesh(
-                aerodrome.taxiway_centerline_mesh,
-                self.materials.taxiway_centerline.as_ref().expect("initialized during startup"),
-                taxiway_centerline_positions,
-                aerodrome_entity,
-                Zorder::GroundSegmentCenterline,
-                MeshType::TaxiwayCenterline,
-            );
-            commit_ctx.commit_mesh(
-                aerodrome.taxiway_background_mesh,
-                self.materials.taxiway_background.as_ref().expect("initialized during startup"),
-                taxiway_background_positions,
-                aerodrome_entity,
-                Zorder::GroundSegmentBackground,
-                MeshType::TaxiwayBackground,
-            );
-            commit_ctx.commit_mesh(
-                aerodrome.apron_centerline_mesh,
-                self.materials.apron_centerline.as_ref().expect("initialized during startup"),
-                apron_centerline_positions,
-                aerodrome_entity,
-                Zorder::GroundSegmentCenterline,
-                MeshType::ApronCenterline,
-            );
-            commit_ctx.commit_mesh(
-                aerodrome.apron_background_mesh,
-                self.materials.apron_background.as_ref().expect("initialized during startup"),
-                apron_background_positions,
-                aerodrome_entity,
-                Zorder::GroundSegmentBackground,
-                MeshType::ApronBackground,
-            );
-        }
+        let chunks = [
+            (MeshType::TaxiwayCenterline, taxiway_centerline_chunks),
+            (MeshType::ApronCenterline, apron_centerline_chunks),
+            (MeshType::TaxiwayBackground, taxiway_background_chunks),
+            (MeshType::ApronBackground, apron_background_chunks),
+        ]
+        .into_iter()
+        .flat_map(|(mesh_type, chunks)| {
+            chunks.chunks.into_iter().map(move |(cell, positions)| ((mesh_type, cell), positions))
+        })
+        .collect();
+        self.steps.p1().commit_chunks(aerodrome_entity, aerodrome.chunks, chunks, &self.materials);
 
         {
             let mut regen_labels = self.steps.p2();
@@ -228,12 +233,9 @@ impl RegenerateMeshParam<'_, '_> {
 
 #[derive(QueryData)]
 struct RegenerateAerodromeData {
-    segments:                &'static ground::AerodromeSegments,
-    endpoints:               &'static ground::AerodromeEndpoints,
-    taxiway_centerline_mesh: Option<&'static AerodromeHasTaxiwayCenterlineMesh>,
-    taxiway_background_mesh: Option<&'static AerodromeHasTaxiwayBackgroundMesh>,
-    apron_centerline_mesh:   Option<&'static AerodromeHasApronCenterlineMesh>,
-    apron_background_mesh:   Option<&'static AerodromeHasApronBackgroundMesh>,
+    segments:  &'static ground::AerodromeSegments,
+    endpoints: &'static ground::AerodromeEndpoints,
+    chunks:    Option<&'static AerodromeHasChunks>,
 }
 
 #[derive(SystemParam)]
@@ -427,14 +429,49 @@ impl DrawLineSegment for Vec<[f32; 3]> {
     }
 }
 
+/// Triangle vertices of ground segments, grouped by the square chunk containing their midpoints.
+///
+/// Each chunk is rendered as a single mesh,
+/// so that a large aerodrome is drawn with a few batched meshes
+/// while off-screen chunks can still be culled.
+struct Chunks {
+    size:   Length<f32>,
+    chunks: HashMap<IVec2, Vec<[f32; 3]>>,
+}
+
+impl Chunks {
+    fn new(size: Length<f32>) -> Self { Self { size, chunks: HashMap::new() } }
+
+    fn cell(&self, position: Position<Vec2>) -> IVec2 {
+        (position.get() / self.size.0).floor().as_ivec2()
+    }
+}
+
+impl DrawLineSegment for Chunks {
+    fn draw_segment_trunc(
+        &mut self,
+        from: Position<Vec2>,
+        from_trunc: bool,
+        to: Position<Vec2>,
+        to_trunc: bool,
+        width: Length<f32>,
+    ) {
+        let cell = self.cell(from.midpoint(to));
+        self.chunks
+            .entry(cell)
+            .or_default()
+            .draw_segment_trunc(from, from_trunc, to, to_trunc, width);
+    }
+}
+
 #[derive(SystemParam)]
 struct CommitContext<'w, 's> {
-    mesh_query: Query<'w, 's, &'static Mesh2d>,
-    commands:   Commands<'w, 's>,
-    meshes:     ResMut<'w, Assets<Mesh>>,
+    chunk_query: Query<'w, 's, (&'static MeshType, &'static mut Chunk, &'static Mesh2d)>,
+    commands:    Commands<'w, 's>,
+    meshes:      ResMut<'w, Assets<Mesh>>,
 }
 
-#[derive(Component)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
 enum MeshType {
     TaxiwayCenterline,
     ApronCenterline,
@@ -444,57 +481,96 @@ enum MeshType {
     ApronLabel,
 }
 
+impl MeshType {
+    fn zorder(self) -> Zorder {
+        match self {
+            Self::TaxiwayCenterline | Self::ApronCenterline => Zorder::GroundSegmentCenterline,
+            Self::TaxiwayBackground | Self::ApronBackground => Zorder::GroundSegmentBackground,
+            Self::TaxiwayLabel | Self::ApronLabel => Zorder::GroundSegmentLabel,
+        }
+    }
+}
+
+/// A batched mesh of the ground segments of a [`MeshType`] in one square of the world.
+#[derive(Component)]
+struct Chunk {
+    cell:   IVec2,
+    /// Bounding box of the mesh vertices, used for culling.
+    bounds: Rect,
+}
+
 impl CommitContext<'_, '_> {
-    fn commit_mesh<R>(
+    /// Replaces the chunk meshes of `aerodrome` with `chunks`.
+    ///
+    /// Mesh assets of unchanged chunks are not touched,
+    /// so that they are not uploaded to the GPU again.
+    fn commit_chunks(
         &mut self,
-        mesh_entity: Option<&R>,
-        material: &Handle<ColorMaterial>,
-        positions: Vec<[f32; 3]>,
         aerodrome: Entity,
-        zorder: Zorder,
-        mesh_type: MeshType,
-    ) where
-        R: RelationshipTarget + Copy + Into<Entity>,
-    {
-        match mesh_entity {
-            None => {
-                let mesh = self.meshes.add(
-                    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all())
-                        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions),
-                );
-
-                self.commands.spawn((
-                    Name::new(format!("{zorder:?} background mesh")),
-                    Mesh2d(mesh),
-                    MeshMaterial2d(material.clone()),
-                    zorder.local_translation(),
-                    <R::Relationship as Relationship>::from(aerodrome),
-                    mesh_type,
-                ));
-            }
-            Some(rel) => {
-                let mesh_entity: Entity = (*rel).into();
-                let mesh_handle = self
-                    .mesh_query
-                    .get(mesh_entity)
-                    .expect("linked child entity must be a mesh entity")
-                    .0
-                    .clone();
-                let mesh = self
-                    .meshes
-                    .get_mut(&mesh_handle)
-                    .expect("asset from strong reference must exist");
-                let Some(VertexAttributeValues::Float32x3(mesh_positions)) =
-                    mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
-                else {
-                    panic!("Position attribute was initialized as Float32x3 during spawn");
-                };
-                *mesh_positions = positions;
+        existing: Option<&AerodromeHasChunks>,
+        mut chunks: HashMap<(MeshType, IVec2), Vec<[f32; 3]>>,
+        materials: &ColorMaterials,
+    ) {
+        for &chunk_entity in existing.map_or(&[][..], |existing| &existing.0) {
+            let Some((&mesh_type, mut chunk, mesh)) = self.chunk_query.log_get_mut(chunk_entity)
+            else {
+                continue;
+            };
+            let Some(positions) = chunks.remove(&(mesh_type, chunk.cell)) else {
+                self.commands.entity(chunk_entity).despawn();
+                continue;
+            };
+
+            let unchanged = self.meshes.get(&mesh.0).is_some_and(|mesh| {
+                matches!(
+                    mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+                    Some(VertexAttributeValues::Float32x3(mesh_positions)) if *mesh_positions == positions
+                )
+            });
+            if unchanged {
+                continue;
             }
+
+            chunk.bounds = bounding_rect(&positions);
+            let mesh =
+                self.meshes.get_mut(&mesh.0).expect("asset from strong reference must exist");
+            let Some(VertexAttributeValues::Float32x3(mesh_positions)) =
+                mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("Position attribute was initialized as Float32x3 during spawn");
+            };
+            *mesh_positions = positions;
+        }
+
+        for ((mesh_type, cell), positions) in chunks {
+            let bounds = bounding_rect(&positions);
+            let mesh = self.meshes.add(
+                Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::all())
+                    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions),
+            );
+            let zorder = mesh_type.zorder();
+
+            self.commands.spawn((
+                Name::new(format!("{mesh_type:?} chunk {cell}")),
+                Mesh2d(mesh),
+                MeshMaterial2d(materials.of_mesh_type(mesh_type).clone()),
+                zorder.local_translation(),
+                ChunkOfAerodrome(aerodrome),
+                Chunk { cell, bounds },
+                mesh_type,
+            ));
         }
     }
 }
 
+fn bounding_rect(positions: &[[f32; 3]]) -> Rect {
+    positions
+        .iter()
+        .fold(Rect { min: Vec2::INFINITY, max: Vec2::NEG_INFINITY }, |rect, &[x, y, _]| {
+            rect.union_point(Vec2::new(x, y))
+        })
+}
+
 #[derive(SystemParam)]
 struct RegenerateLabelsParam<'w, 's> {
     endpoint_query: Query<'w, 's, &'static ground::Endpoint>,
@@ -577,40 +653,12 @@ fn common_label_bundle(
 }
 
 #[derive(Component)]
-#[relationship(relationship_target = AerodromeHasTaxiwayCenterlineMesh)]
-struct TaxiwayCenterlineMeshOfAerodrome(pub Entity);
+#[relationship(relationship_target = AerodromeHasChunks)]
+struct ChunkOfAerodrome(pub Entity);
 
 #[derive(Component)]
-#[relationship_target(relationship = TaxiwayCenterlineMeshOfAerodrome, linked_spawn)]
-#[derive(Clone, Copy, derive_more::Into)]
-struct AerodromeHasTaxiwayCenterlineMesh(Entity);
-
-#[derive(Component)]
-#[relationship(relationship_target = AerodromeHasTaxiwayBackgroundMesh)]
-struct TaxiwayBackgroundMeshOfAerodrome(pub Entity);
-
-#[derive(Component)]
-#[relationship_target(relationship = TaxiwayBackgroundMeshOfAerodrome, linked_spawn)]
-#[derive(Clone, Copy, derive_more::Into)]
-struct AerodromeHasTaxiwayBackgroundMesh(Entity);
-
-#[derive(Component)]
-#[relationship(relationship_target = AerodromeHasApronCenterlineMesh)]
-struct ApronCenterlineMeshOfAerodrome(pub Entity);
-
-#[derive(Component)]
-#[relationship_target(relationship = ApronCenterlineMeshOfAerodrome, linked_spawn)]
-#[derive(Clone, Copy, derive_more::Into)]
-struct AerodromeHasApronCenterlineMesh(Entity);
-
-#[derive(Component)]
-#[relationship(relationship_target = AerodromeHasApronBackgroundMesh)]
-struct ApronBackgroundMeshOfAerodrome(pub Entity);
-
-#[derive(Component)]
-#[relationship_target(relationship = ApronBackgroundMeshOfAerodrome, linked_spawn)]
-#[derive(Clone, Copy, derive_more::Into)]
-struct AerodromeHasApronBackgroundMesh(Entity);
+#[relationship_target(relationship = ChunkOfAerodrome, linked_spawn)]
+struct AerodromeHasChunks(Vec<Entity>);
 
 #[derive(Component)]
 #[relationship(relationship_target = AerodromeHasSegmentLabels)]
@@ -632,6 +680,19 @@ struct ColorMaterials {
 }
 
 impl ColorMaterials {
+    fn of_mesh_type(&self, mesh_type: MeshType) -> &Handle<ColorMaterial> {
+        match mesh_type {
+            MeshType::TaxiwayCenterline => &self.taxiway_centerline,
+            MeshType::ApronCenterline => &self.apron_centerline,
+            MeshType::TaxiwayBackground => &self.taxiway_background,
+            MeshType::ApronBackground => &self.apron_background,
+            MeshType::TaxiwayLabel => &self.taxiway_label,
+            MeshType::ApronLabel => &self.apron_label,
+        }
+        .as_ref()
+        .expect("initialized during startup")
+    }
+
     fn init_system(
         mut handles: ResMut<Self>,
         mut materials: ResMut<Assets<ColorMaterial>>,
@@ -654,22 +715,47 @@ impl ColorMaterials {
             Some(materials.add(ColorMaterial::from_color(conf.apron.label_color)));
     }
 
+    /// Updates the materials when the config changes or the scenario gets darker.
     fn reload_config_system(
         handles: Res<Self>,
         mut materials: ResMut<Assets<ColorMaterial>>,
         mut conf: ReadConfigChange<Conf>,
+        time: Res<Time<time::Virtual>>,
+        clock: Res<clock::Clock>,
+        mut last_night_level: Local<Option<u8>>,
     ) {
-        if !conf.consume_change() {
+        const NIGHT_LEVEL_STEPS: f32 = 16.0;
+
+        #[expect(clippy::cast_possible_truncation, reason = "value is within 0..=16")]
+        #[expect(clippy::cast_sign_loss, reason = "daylight is within 0..=1")]
+        let night_level =
+            ((1.0 - clock.daylight(time.elapsed())) * NIGHT_LEVEL_STEPS).round() as u8;
+        let night_changed = last_night_level.replace(night_level) != Some(night_level);
+
+        if !conf.consume_change() && !night_changed {
             return;
         }
         let conf = conf.read();
+        let night = f32::from(night_level) / NIGHT_LEVEL_STEPS;
 
         for (handle, color) in [
-            (&handles.taxiway_centerline, conf.taxiway.centerline_color),
-            (&handles.taxiway_background, conf.taxiway.background_color),
+            (
+                &handles.taxiway_centerline,
+                conf.taxiway.centerline_color.mix(&conf.taxiway.night_centerline_color, night),
+            ),
+            (
+                &handles.taxiway_background,
+                conf.taxiway.background_color.mix(&conf.taxiway.night_background_color, night),
+            ),
             (&handles.taxiway_label, conf.taxiway.label_color),
-            (&handles.apron_centerline, conf.apron.centerline_color),
-            (&handles.apron_background, conf.apron.background_color),
+            (
+                &handles.apron_centerline,
+                conf.apron.centerline_color.mix(&conf.apron.night_centerline_color, night),
+            ),
+            (
+                &handles.apron_background,
+                conf.apron.background_color.mix(&conf.apron.night_background_color, night),
+            ),
             (&handles.apron_label, conf.apron.label_color),
         ] {
             materials
@@ -689,6 +775,9 @@ struct Conf {
     /// Density of straight lines to interpolate a curved intersection turn.
     #[config(default = Length::from_meters(1.0), min = Length::from_meters(0.1), max = Length::from_meters(100.0))]
     curve_segment_length: Length<f32>,
+    /// Side length of the square chunks that ground segment meshes are batched into.
+    #[config(default = Length::from_nm(0.5), min = Length::from_nm(0.05), max = Length::from_nm(10.0))]
+    chunk_size:           Length<f32>,
     taxiway:              SegmentTypeConf,
     apron:                SegmentTypeConf,
 }
@@ -702,6 +791,12 @@ struct SegmentTypeConf {
     /// Color of the segment background.
     #[config(default = Color::srgb(0.3, 0.3, 0.3))]
     background_color:       Color,
+    /// Color of the segment centerline at night.
+    #[config(default = Color::srgb(0.2, 0.8, 0.3))]
+    night_centerline_color: Color,
+    /// Color of the segment background at night.
+    #[config(default = Color::srgb(0.1, 0.1, 0.1))]
+    night_background_color: Color,
     /// Minimum zoom level (in maximum distance per pixel) to display centerline.
     #[config(default = Length::from_meters(50.0), min = Length::ZERO, max = Length::from_meters(500.), unit = LengthUnit::Meters)]
     centerline_render_zoom: Length<f32>,
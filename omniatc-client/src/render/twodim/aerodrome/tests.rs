@@ -2,7 +2,7 @@ use std::io::Cursor;
 
 use bevy::app::App;
 use bevy::ecs::system::{EntityCommand, SystemState};
-use bevy::math::Vec2;
+use bevy::math::{IVec2, Vec2};
 use bevy_mod_config::{AppExt, ReadConfig, manager};
 use math::{Heading, Length, Position, Speed};
 use omniatc::level::aerodrome::Aerodrome;
@@ -18,6 +18,20 @@ const ELEVATION: Position<f32> = Position::new(0.1);
 const UNITS_PER_HALF_WIDTH: f32 = 0.0125 / 0.001;
 fn pos(x: f32, y: f32) -> Position<Vec2> { REF_POS + Length::from_components(UNIT * x, UNIT * y) }
 
+#[test]
+fn chunks_group_segments_by_midpoint() {
+    use super::{Chunks, DrawLineSegment};
+
+    let mut chunks = Chunks::new(UNIT * 100.0);
+    chunks.draw_segment_trunc(pos(10.0, 10.0), false, pos(90.0, 10.0), false, WIDTH);
+    chunks.draw_segment_trunc(pos(90.0, 10.0), false, pos(130.0, 10.0), false, WIDTH);
+    chunks.draw_segment_trunc(pos(-10.0, -10.0), false, pos(-20.0, -40.0), false, WIDTH);
+
+    let mut cells: Vec<_> = chunks.chunks.keys().copied().collect();
+    cells.sort_by_key(|cell| (cell.x, cell.y));
+    assert_eq!(cells, [IVec2::new(-1, -1), IVec2::new(0, 0), IVec2::new(1, 0)]);
+}
+
 #[test]
 fn push_endpoint_aligned_right_angle() {
     test_push_endpoint(
@@ -0,0 +1,167 @@
+use bevy::app::{self, App, Plugin};
+use bevy::asset::Assets;
+use bevy::color::Color;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::math::Vec2;
+use bevy::sprite::{Anchor, Text2d};
+use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy::text::TextColor;
+use bevy::transform::components::Transform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::{Length, Position};
+use omniatc::level::airway::{self, Airway};
+use omniatc::level::waypoint::Waypoint;
+use omniatc::{QueryTryLog, try_log};
+
+use super::Zorder;
+use crate::util::{AnchorConf, billboard, shapes};
+use crate::{ConfigManager, render};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:airway");
+        app.add_systems(app::Update, spawn_system.in_set(render::SystemSets::Spawn));
+        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = HasSegments)]
+struct IsSegmentOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsSegmentOf, linked_spawn)]
+struct HasSegments(Vec<Entity>);
+
+/// Waypoints at the ends of a line between two consecutive waypoints of an airway.
+#[derive(Component)]
+struct SegmentEnds([Entity; 2]);
+
+#[derive(Component)]
+#[relationship(relationship_target = HasLabel)]
+struct IsLabelOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsLabelOf, linked_spawn)]
+struct HasLabel(Entity);
+
+/// Material shared by the segments of an airway.
+#[derive(Component)]
+struct SegmentMaterial(MeshMaterial2d<ColorMaterial>);
+
+fn spawn_system(
+    mut commands: Commands,
+    mut spawns: MessageReader<airway::SpawnMessage>,
+    conf: ReadConfig<Conf>,
+    shapes: Res<shapes::Meshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    airway_query: Query<&Airway>,
+) {
+    let conf = conf.read();
+
+    for &airway::SpawnMessage(airway_entity) in spawns.read() {
+        let airway = airway_query.get(airway_entity).expect("airway was just spawned");
+
+        let material = MeshMaterial2d(materials.add(ColorMaterial::from_color(conf.color)));
+        for ends in airway.waypoints.windows(2) {
+            commands.spawn((
+                IsSegmentOf(airway_entity),
+                SegmentEnds([ends[0], ends[1]]),
+                shapes.line(conf.thickness, Zorder::Airway),
+                material.clone(),
+            ));
+        }
+        commands.entity(airway_entity).insert(SegmentMaterial(material));
+
+        commands.spawn((
+            IsLabelOf(airway_entity),
+            Zorder::AirwayLabel.local_translation(),
+            billboard::MaintainScale { size: conf.label_size },
+            billboard::MaintainRotation,
+            billboard::Label { offset: Length::ZERO, distance: conf.label_distance },
+            Text2d::new(label_text(airway)),
+            TextColor(conf.color),
+            conf.label_anchor,
+        ));
+    }
+}
+
+fn update_system(
+    conf: ReadConfig<Conf>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    airway_query: Query<(&Airway, &SegmentMaterial, &HasLabel)>,
+    waypoint_query: Query<&Waypoint>,
+    mut segment_query: Query<(&SegmentEnds, &mut Transform, &mut shapes::MaintainThickness)>,
+    mut label_query: Query<(&mut billboard::Label, &mut TextColor)>,
+) {
+    let conf = conf.read();
+
+    for (&SegmentEnds(ends), mut tf, mut thickness) in &mut segment_query {
+        let Ok([start, end]) = waypoint_query.get_many(ends) else { continue };
+        shapes::set_square_line_transform(
+            &mut tf,
+            start.position.horizontal(),
+            end.position.horizontal(),
+        );
+        thickness.0 = conf.thickness;
+    }
+
+    for (airway, material, &HasLabel(label_entity)) in &airway_query {
+        let material = try_log!(
+            materials.get_mut(&material.0.0),
+            expect "asset referenced by strong handle must exist"
+            or continue
+        );
+        material.color = conf.color;
+
+        let Some((mut label, mut color)) = label_query.log_get_mut(label_entity) else { continue };
+        if let Some(midpoint) = label_position(airway, &waypoint_query) {
+            label.offset = midpoint - Position::ORIGIN;
+        }
+        label.distance = conf.label_distance;
+        color.0 = conf.color;
+    }
+}
+
+/// Labels the airway at the midpoint of its middle segment.
+fn label_position(airway: &Airway, waypoint_query: &Query<&Waypoint>) -> Option<Position<Vec2>> {
+    let middle = airway.waypoints.len().checked_sub(1)? / 2;
+    let [start, end] = waypoint_query
+        .get_many([*airway.waypoints.get(middle)?, *airway.waypoints.get(middle + 1)?])
+        .ok()?;
+    Some(start.position.horizontal().midpoint(end.position.horizontal()))
+}
+
+fn label_text(airway: &Airway) -> String {
+    let feet = |altitude: Position<f32>| altitude.amsl().into_feet();
+    match (airway.min_altitude, airway.max_altitude) {
+        (Some(min), Some(max)) => {
+            format!("{}\n{:.0}-{:.0}ft", airway.name, feet(min), feet(max))
+        }
+        (Some(min), None) => format!("{}\nabove {:.0}ft", airway.name, feet(min)),
+        (None, Some(max)) => format!("{}\nbelow {:.0}ft", airway.name, feet(max)),
+        (None, None) => airway.name.clone(),
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Color of airway lines and labels.
+    #[config(default = Color::srgba(0.4, 0.6, 0.8, 0.6))]
+    color:          Color,
+    /// Thickness of airway lines.
+    #[config(default = 1.0, min = 0.0, max = 10.0)]
+    thickness:      f32,
+    #[config(default = 0.5, min = 0.0, max = 3.0)]
+    label_size:     f32,
+    #[config(default = 10.0, min = 0.0, max = 100.0)]
+    label_distance: f32,
+    #[config(default = Anchor::CENTER)]
+    label_anchor:   AnchorConf,
+}
@@ -7,6 +7,7 @@ use bevy::camera::{Camera, Camera2d, ClearColor, ImageRenderTarget, RenderTarget
 use bevy::color::{Color, Mix};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
 use bevy::ecs::query::With;
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Query, Res, ResMut, Single, SystemParam};
@@ -23,11 +24,12 @@ use bevy_egui::helpers::egui_vec2_into_vec2;
 use bevy_egui::{EguiContexts, EguiTextureHandle, EguiUserTextures, egui};
 use bevy_mod_config::{AppExt, Config};
 use math::{Angle, Length, Position};
+use omniatc::level::object::Object;
 use omniatc::level::quest;
 use omniatc::{QueryTryLog, load, try_log};
 use serde::{Deserialize, Serialize};
 
-use crate::render::{SystemSets, dock, tutorial_popup};
+use crate::render::{SystemSets, callsign, dock, tutorial_popup};
 use crate::{ConfigManager, UpdateSystemSets, input};
 
 mod inputs;
@@ -39,6 +41,7 @@ impl Plugin for Plug {
         app.init_config::<ConfigManager, Conf>("2d:camera");
 
         app.add_systems(app::Update, consume_camera_advice.before(UpdateSystemSets::Input));
+        app.add_systems(app::Update, center_on_focus_system.before(UpdateSystemSets::Input));
 
         app.add_systems(
             app::Update,
@@ -156,6 +159,21 @@ fn consume_camera_advice(
     advice.0 = None;
 }
 
+/// Centers the radar view on objects focused by clicking their callsign.
+fn center_on_focus_system(
+    mut messages: MessageReader<callsign::FocusMessage>,
+    object_query: Query<&Object>,
+    camera_query: Query<&mut Transform, With<UiState>>,
+) {
+    let Some(&callsign::FocusMessage(entity)) = messages.read().last() else { return };
+    let Some(object) = object_query.log_get(entity) else { return };
+
+    let center = object.position.horizontal();
+    for mut camera_tf in camera_query {
+        camera_tf.translation = Vec3::from((center.get(), camera_tf.translation.z));
+    }
+}
+
 fn clear_color_system(
     window: Option<Single<&mut Window>>,
     request_highlight: Option<
@@ -0,0 +1,116 @@
+//! Viewport culling for off-screen level geometry.
+//!
+//! The [`Viewport`] is computed once per frame in [`render::SystemSets::Cull`],
+//! before meshes are regenerated and viewables are updated,
+//! so that geometry outside the visible region can be skipped.
+
+use bevy::app::{self, App, Plugin};
+use bevy::camera::Camera;
+use bevy::ecs::query::With;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, ResMut};
+use bevy::math::{Rect, Vec2};
+use bevy::transform::components::GlobalTransform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::Position;
+
+use super::camera;
+use crate::{ConfigManager, render};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:culling");
+        app.init_resource::<Viewport>();
+        app.add_systems(app::Update, update_viewport_system.in_set(render::SystemSets::Cull));
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Whether to skip updating level geometry outside the viewport.
+    #[config(default = true)]
+    enabled: bool,
+    /// Extra region around the viewport treated as visible,
+    /// as a fraction of the larger viewport dimension,
+    /// to avoid popping in geometry when panning.
+    #[config(default = 0.25, min = 0.0, max = 2.0)]
+    margin:  f32,
+}
+
+/// The visible region of the 2D view in world coordinates.
+#[derive(Resource, Default)]
+pub struct Viewport {
+    /// Axis-aligned bounds of the visible region including the margin,
+    /// or `None` if everything should be treated as visible.
+    rect: Option<Rect>,
+}
+
+impl Viewport {
+    /// Creates a viewport covering the bounding box of `corners`, extended by `margin`.
+    #[must_use]
+    pub fn from_corners(corners: impl IntoIterator<Item = Position<Vec2>>, margin: f32) -> Self {
+        let rect = bounding_rect(corners).map(|rect| {
+            let size = rect.size();
+            rect.inflate(size.x.max(size.y) * margin)
+        });
+        Self { rect }
+    }
+
+    /// Whether `position` is within the viewport.
+    #[must_use]
+    pub fn contains(&self, position: Position<Vec2>) -> bool {
+        self.rect.is_none_or(|rect| rect.contains(position.get()))
+    }
+
+    /// Whether the bounding box of `points` overlaps the viewport.
+    ///
+    /// This is conservative for line segments and polygons,
+    /// which may be reported as visible even if they only pass near a corner.
+    #[must_use]
+    pub fn overlaps(&self, points: impl IntoIterator<Item = Position<Vec2>>) -> bool {
+        let Some(rect) = self.rect else { return true };
+        let Some(bounds) = bounding_rect(points) else { return false };
+        bounds.min.x <= rect.max.x
+            && bounds.max.x >= rect.min.x
+            && bounds.min.y <= rect.max.y
+            && bounds.max.y >= rect.min.y
+    }
+}
+
+fn bounding_rect(points: impl IntoIterator<Item = Position<Vec2>>) -> Option<Rect> {
+    points.into_iter().fold(None, |rect: Option<Rect>, point| {
+        let point = point.get();
+        Some(match rect {
+            Some(rect) => rect.union_point(point),
+            None => Rect::from_corners(point, point),
+        })
+    })
+}
+
+fn update_viewport_system(
+    conf: ReadConfig<Conf>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<camera::UiState>>,
+    mut viewport: ResMut<Viewport>,
+) {
+    let conf = conf.read();
+
+    let rect = if conf.enabled
+        && let Ok((camera, global_tf)) = camera_query.single()
+        && let Some(size) = camera.logical_viewport_size()
+    {
+        let corners = [Vec2::ZERO, Vec2::new(size.x, 0.), size, Vec2::new(0., size.y)]
+            .into_iter()
+            .map(|corner| camera.viewport_to_world_2d(global_tf, corner).ok().map(Position::new))
+            .collect::<Option<Vec<_>>>();
+        corners.and_then(|corners| Viewport::from_corners(corners, conf.margin).rect)
+    } else {
+        None
+    };
+    viewport.rect = rect;
+}
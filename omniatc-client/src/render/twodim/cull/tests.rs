@@ -0,0 +1,34 @@
+use bevy::math::Vec2;
+use math::Position;
+
+use super::Viewport;
+
+fn pos(x: f32, y: f32) -> Position<Vec2> { Position::new(Vec2::new(x, y)) }
+
+#[test]
+fn test_viewport_contains_with_margin() {
+    let viewport = Viewport::from_corners([pos(0., 0.), pos(10., 0.), pos(10., 5.)], 0.1);
+
+    assert!(viewport.contains(pos(5., 2.)));
+    assert!(viewport.contains(pos(-0.5, 5.5)));
+    assert!(!viewport.contains(pos(-2., 2.)));
+    assert!(!viewport.contains(pos(5., 8.)));
+}
+
+#[test]
+fn test_viewport_overlaps_segment() {
+    let viewport = Viewport::from_corners([pos(0., 0.), pos(10., 10.)], 0.);
+
+    assert!(viewport.overlaps([pos(-5., 5.), pos(15., 5.)]));
+    assert!(viewport.overlaps([pos(5., -5.), pos(5., 5.)]));
+    assert!(!viewport.overlaps([pos(11., 0.), pos(20., 10.)]));
+    assert!(!viewport.overlaps([]));
+}
+
+#[test]
+fn test_viewport_without_rect_is_unbounded() {
+    let viewport = Viewport::default();
+
+    assert!(viewport.contains(pos(1e6, -1e6)));
+    assert!(viewport.overlaps([pos(1e6, 1e6)]));
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use bevy::app::{self, App, Plugin};
+use bevy::color::Color;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query};
+use bevy::sprite::{Anchor, Text2d};
+use bevy::text::TextColor;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::Length;
+use omniatc::level::departure_queue;
+
+use super::Zorder;
+use crate::util::{AnchorConf, billboard};
+use crate::{ConfigManager, render};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:departure_queue");
+        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = HasQueueLabel)]
+struct IsQueueLabelOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsQueueLabelOf, linked_spawn)]
+struct HasQueueLabel(Entity);
+
+/// Describes the position of a departure in its queue.
+///
+/// `index` is the zero-based position of `entry` in the queue.
+#[must_use]
+pub fn entry_text(index: usize, entry: &departure_queue::Entry) -> String {
+    let sequence = index + 1;
+    if entry.lined_up {
+        format!("#{sequence} lined up")
+    } else if entry.expected_wait.is_zero() {
+        format!("#{sequence}")
+    } else {
+        format!("#{sequence} +{}s", entry.expected_wait.as_secs())
+    }
+}
+
+fn update_system(
+    mut commands: Commands,
+    conf: ReadConfig<Conf>,
+    queue_query: Query<&departure_queue::Queue>,
+    mut label_query: Query<(
+        Entity,
+        &IsQueueLabelOf,
+        &mut Text2d,
+        &mut TextColor,
+        &mut billboard::Label,
+        &mut billboard::MaintainScale,
+        &mut Anchor,
+    )>,
+) {
+    let conf = conf.read();
+
+    let mut texts: HashMap<Entity, String> = queue_query
+        .iter()
+        .flat_map(|queue| queue.entries.iter().enumerate())
+        .map(|(index, entry)| (entry.object, entry_text(index, entry)))
+        .collect();
+
+    for (
+        label_entity,
+        &IsQueueLabelOf(object),
+        mut text,
+        mut color,
+        mut label,
+        mut scale,
+        mut anchor,
+    ) in &mut label_query
+    {
+        let Some(new_text) = texts.remove(&object) else {
+            commands.entity(label_entity).despawn();
+            continue;
+        };
+        if text.0 != new_text {
+            text.0 = new_text;
+        }
+        color.0 = conf.color;
+        label.distance = conf.distance;
+        scale.size = conf.size;
+        *anchor = conf.anchor;
+    }
+
+    // Remaining objects do not have a label yet.
+    for (object, text) in texts {
+        commands.spawn((
+            ChildOf(object),
+            IsQueueLabelOf(object),
+            Zorder::DepartureQueueLabel.local_translation(),
+            billboard::MaintainScale { size: conf.size },
+            billboard::MaintainRotation,
+            billboard::Label { offset: Length::ZERO, distance: conf.distance },
+            Text2d::new(text),
+            TextColor(conf.color),
+            conf.anchor,
+        ));
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Color of departure queue labels.
+    #[config(default = Color::srgb(1.0, 0.8, 0.3))]
+    color:    Color,
+    #[config(default = 0.5, min = 0.0, max = 3.0)]
+    size:     f32,
+    /// Distance of the label from the object, in screen coordinates.
+    #[config(default = 20.0, min = 0.0, max = 100.0)]
+    distance: f32,
+    #[config(default = Anchor::TOP_CENTER)]
+    anchor:   AnchorConf,
+}
@@ -14,7 +14,7 @@ use bevy::transform::components::Transform;
 use bevy_mod_config::{self, AppExt as _, Config, ReadConfig, ReadConfigChange};
 use math::Length;
 use omniatc::level::object::{self, Object};
-use omniatc::level::plane;
+use omniatc::level::{plane, transition};
 use omniatc::util::EnumScheduleConfig;
 use serde::{Deserialize, Serialize};
 
@@ -23,11 +23,13 @@ use crate::util::{AnchorConf, billboard};
 use crate::{ConfigManager, render};
 
 mod base_color;
+pub mod ghost;
 
 mod label;
 use label::IsLabelOf;
 
 pub mod preview;
+pub mod radar;
 mod separation_ring;
 mod track;
 mod vector;
@@ -53,6 +55,8 @@ impl Plugin for Plug {
         app.add_plugins(track::Plug);
         app.add_plugins(preview::Plug);
         app.add_plugins(base_color::Plug);
+        app.add_plugins(ghost::Plug);
+        app.add_plugins(radar::Plug);
         omniatc::util::configure_ordered_system_sets::<SetColorThemeSystemSet>(app, app::Update);
     }
 }
@@ -71,15 +75,15 @@ impl HasSprite {
 }
 
 fn spawn_plane_system(
-    mut spawn_events: MessageReader<plane::SpawnMessage>,
+    mut spawn_events: MessageReader<object::SpawnMessage>,
     mut params: ParamSet<(
-        (Commands, ReadConfig<Conf>, Res<AssetServer>),
+        (Commands, ReadConfig<Conf>, Res<AssetServer>, Query<Option<&object::Category>>),
         separation_ring::SpawnSubsystemParam,
         vector::SpawnSubsystemParam,
     )>,
 ) {
-    for &plane::SpawnMessage(plane_entity) in spawn_events.read() {
-        let (mut commands, conf, asset_server) = params.p0();
+    for &object::SpawnMessage(plane_entity) in spawn_events.read() {
+        let (mut commands, conf, asset_server, category_query) = params.p0();
         let conf = conf.read();
 
         commands.entity(plane_entity).insert((
@@ -93,12 +97,14 @@ fn spawn_plane_system(
             },
         ));
 
+        let category = category_query.get(plane_entity).ok().flatten().copied();
         commands.spawn((
             IsSpriteOf(plane_entity),
             ChildOf(plane_entity),
             Zorder::ObjectSprite.local_translation(),
-            Sprite::from_image(asset_server.load(conf.plane.sprite.path())),
+            Sprite::from_image(asset_server.load(conf.plane.sprite.path(category))),
             billboard::MaintainScale { size: conf.plane.sprite_size },
+            conf.plane.zoom_bands(),
         ));
         commands.spawn((
             IsLabelOf(plane_entity),
@@ -136,7 +142,7 @@ fn maintain_plane_system(
     conf: ReadConfig<Conf>,
     mut object_query: Query<(
         &HasSprite,
-        &Object,
+        &radar::DisplayPosition,
         &object::Rotation,
         label::ObjectData,
         &mut Transform,
@@ -147,26 +153,27 @@ fn maintain_plane_system(
         (query::With<IsSpriteOf>, query::Without<Object>),
     >,
     mut label_writer: label::Writer,
+    transition: Res<transition::Transition>,
 ) {
     let conf = conf.read();
 
     object_query.iter_mut().for_each(
         |(
             &HasSprite(sprite_entity),
-            object,
+            display,
             object_rot,
             label_data,
             mut object_tf,
             color_theme,
         )| {
-            object_tf.translation = Zorder::base_translation(object.position);
+            object_tf.translation = Zorder::base_translation(display.position);
 
             if let Ok((mut sprite, mut sprite_tf)) = sprite_query.get_mut(sprite_entity) {
                 sprite.color = color_theme.body;
                 sprite_tf.rotation = object_rot.0;
             }
 
-            label_data.write_label(&conf.plane, &mut label_writer);
+            label_data.write_label(&conf.plane, &transition, &mut label_writer);
         },
     );
 }
@@ -174,9 +181,10 @@ fn maintain_plane_system(
 fn handle_config_change_system(
     mut conf: ReadConfigChange<Conf>,
     mut queries: ParamSet<(
-        Query<(&mut Sprite, &mut billboard::MaintainScale), With<IsSpriteOf>>,
+        Query<(&IsSpriteOf, &mut Sprite, &mut billboard::MaintainScale, &mut billboard::ZoomBands)>,
         Query<(&mut billboard::MaintainScale, &mut billboard::Label, &mut Anchor), With<IsLabelOf>>,
     )>,
+    category_query: Query<&object::Category>,
     asset_server: Res<AssetServer>,
 ) {
     if !conf.consume_change() {
@@ -184,9 +192,11 @@ fn handle_config_change_system(
     }
     let conf = conf.read();
 
-    for (mut sprite, mut scale) in queries.p0() {
-        *sprite = Sprite::from_image(asset_server.load(conf.plane.sprite.path()));
+    for (&IsSpriteOf(object), mut sprite, mut scale, mut bands) in queries.p0() {
+        let category = category_query.get(object).ok().copied();
+        *sprite = Sprite::from_image(asset_server.load(conf.plane.sprite.path(category)));
         scale.size = conf.plane.sprite_size;
+        *bands = conf.plane.zoom_bands();
     }
 
     for (mut scale, mut label, mut anchor) in queries.p1() {
@@ -204,44 +214,96 @@ struct Conf {
     vector:          vector::Conf,
     track:           track::Conf,
     preview_line:    preview::Conf,
+    ghost:           ghost::Conf,
+    radar:           radar::Conf,
 }
 
 #[derive(Config)]
 #[config(expose(read))]
 struct PlaneConf {
-    /// Sprite for planes.
-    sprite:             SpriteType,
-    /// Size of plane sprites.
+    /// Style of object icons.
+    sprite:              SpriteStyle,
+    /// Size of object icons.
     #[config(default = 1.0, min = 0.0, max = 5.0)]
-    sprite_size:        f32,
+    sprite_size:         f32,
+    /// Object icons are enlarged when the view is zoomed in beyond this many nautical miles per
+    /// pixel.
+    #[config(default = Length::from_nm(0.01), min = Length::ZERO, max = Length::from_nm(1.0))]
+    zoom_near_threshold: Length<f32>,
+    /// Size multiplier of object icons when the view is zoomed in.
+    #[config(default = 1.5, min = 0.0, max = 5.0)]
+    zoom_near_factor:    f32,
+    /// Object icons are shrunk when the view is zoomed out beyond this many nautical miles per
+    /// pixel.
+    #[config(default = Length::from_nm(0.2), min = Length::ZERO, max = Length::from_nm(1.0))]
+    zoom_far_threshold:  Length<f32>,
+    /// Size multiplier of object icons when the view is zoomed out.
+    #[config(default = 0.6, min = 0.0, max = 5.0)]
+    zoom_far_factor:     f32,
     /// Object color will be based on this scheme.
-    color_scheme:       base_color::Scheme,
+    color_scheme:        base_color::Scheme,
     /// Size of object labels.
     #[config(default = 0.5, min = 0.0, max = 3.0)]
-    label_size:         f32,
+    label_size:          f32,
     /// Distance of object labels from the object center, in screen coordinates.
     #[config(default = 50.0, min = 0., max = 300.)]
-    label_distance:     f32,
+    label_distance:      f32,
     /// Direction of the object relative to the label.
     #[config(default = Anchor::BOTTOM_LEFT)]
-    label_anchor:       AnchorConf,
+    label_anchor:        AnchorConf,
     /// Label color will be based on this scheme.
-    label_color_scheme: base_color::Scheme,
+    label_color_scheme:  base_color::Scheme,
+    /// Color of the minimum fuel tag in object labels.
+    #[config(default = Color::srgb(1.0, 0.6, 0.2))]
+    minimum_fuel_color:  Color,
+    /// Color of the emergency and deviation tags in object labels.
+    #[config(default = Color::srgb(1.0, 0.2, 0.2))]
+    emergency_color:     Color,
+    /// Whether to show the current altitude and the acknowledged clearance in object labels.
+    #[config(default = true)]
+    show_clearance:      bool,
+    /// Color of the acknowledged clearance values in object labels.
+    #[config(default = Color::srgb(0.4, 0.9, 1.0))]
+    clearance_color:     Color,
 }
 
 #[derive(
     strum::EnumIter, Clone, Copy, PartialEq, Eq, strum::Display, Serialize, Deserialize, Config,
 )]
 #[config(expose(read))]
-enum SpriteType {
-    #[strum(message = "Plane")]
-    Plane,
+enum SpriteStyle {
+    /// Top-down outlines resembling the object.
+    #[strum(message = "Silhouette")]
+    Silhouette,
+    /// Abstract radar symbols.
+    #[strum(message = "Symbolic")]
+    Symbolic,
 }
 
-impl SpriteTypeRead {
-    fn path(self) -> &'static str {
-        match self {
-            Self::Plane => "sprites/plane.png",
+impl SpriteStyleRead {
+    /// Returns the sprite for objects of `category`, which defaults to jets.
+    fn path(self, category: Option<object::Category>) -> &'static str {
+        let category = category.map_or(store::Category::Jet, |object::Category(category)| category);
+        match (self, category) {
+            (Self::Silhouette, store::Category::Jet) => "sprites/plane.png",
+            (Self::Silhouette, store::Category::Prop) => "sprites/prop.png",
+            (Self::Silhouette, store::Category::Helicopter) => "sprites/helicopter.png",
+            (Self::Silhouette, store::Category::Vehicle) => "sprites/vehicle.png",
+            (Self::Symbolic, store::Category::Jet) => "sprites/symbol-jet.png",
+            (Self::Symbolic, store::Category::Prop) => "sprites/symbol-prop.png",
+            (Self::Symbolic, store::Category::Helicopter) => "sprites/symbol-helicopter.png",
+            (Self::Symbolic, store::Category::Vehicle) => "sprites/symbol-vehicle.png",
+        }
+    }
+}
+
+impl PlaneConfRead<'_> {
+    fn zoom_bands(&self) -> billboard::ZoomBands {
+        billboard::ZoomBands {
+            near_threshold: self.zoom_near_threshold,
+            near_factor:    self.zoom_near_factor,
+            far_threshold:  self.zoom_far_threshold,
+            far_factor:     self.zoom_far_factor,
         }
     }
 }
@@ -10,7 +10,7 @@ use bevy::time::{self, Time};
 use bevy_mod_config::{Config, ConfigField, ReadConfig};
 use math::{LengthUnit, Position, Speed, SpeedUnit, TROPOPAUSE_ALTITUDE};
 use omniatc::level::object::Object;
-use omniatc::level::{conflict, dest};
+use omniatc::level::{conflict, deadlock, dest};
 use omniatc::util::OptionalConfigField;
 use serde::{Deserialize, Serialize};
 
@@ -32,9 +32,10 @@ impl Plugin for Plug {
 
 #[derive(QueryData)]
 struct UpdateData {
-    object:   &'static Object,
-    dest:     Option<&'static dest::Destination>,
-    conflict: Has<conflict::ActiveObject>,
+    object:     &'static Object,
+    dest:       Option<&'static dest::Destination>,
+    conflict:   Has<conflict::ActiveObject>,
+    deadlocked: Has<deadlock::Deadlocked>,
 }
 
 fn update_system(
@@ -53,7 +54,7 @@ fn update_system(
 }
 
 fn select_color(scheme: &SchemeRead, time: &Time<time::Real>, data: &UpdateDataItem) -> Color {
-    if data.conflict
+    if (data.conflict || data.deadlocked)
         && let Some(scheme) = scheme.conflict.as_option()
     {
         let flash_cycle_duration = scheme.flash_on_duration + scheme.flash_off_duration;
@@ -171,7 +172,7 @@ pub enum SchemeBase {
 
 #[derive(Serialize, Deserialize, Config)]
 pub struct ConflictScheme {
-    /// Color when object is in conflict.
+    /// Color when object is in conflict or a ground deadlock.
     #[config(default = Color::oklch(0.628, 0.2317, 32.82))]
     pub color:              Color,
     /// Duration of displaying `flash_color` when an object enters conflict.
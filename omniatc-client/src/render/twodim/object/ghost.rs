@@ -0,0 +1,309 @@
+//! Fade-out of objects that completed their destination.
+//!
+//! When a completed object is despawned, a [`Ghost`] is spawned in its place.
+//! The sprite fades out along with a toast of the awarded score,
+//! while the datablock stays dimmed and clickable for the grace period
+//! so that the user can [review](Reviewed) the completion.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::asset::AssetServer;
+use bevy::camera::visibility::Visibility;
+use bevy::color::{Alpha, Color};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::lifecycle::Remove;
+use bevy::ecs::observer::On;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::math::Vec2;
+use bevy::sprite::{Sprite, Text2d};
+use bevy::text::TextColor;
+use bevy::time::{self, Time};
+use bevy::transform::components::Transform;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use bevy_mod_config::{Config, ReadConfig};
+use math::{Length, Position, Speed};
+use omniatc::level::clock::Clock;
+use omniatc::level::object::{self, Object};
+use omniatc::level::{dest, transition};
+use omniatc::{QueryTryLog, load};
+use store::Score;
+
+use super::Zorder;
+use crate::util::billboard;
+use crate::{EguiSystemSets, render};
+
+#[cfg(test)]
+mod tests;
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Reviewed>();
+        app.add_observer(spawn_observer);
+        app.add_systems(app::Update, maintain_system.in_set(render::SystemSets::Update));
+        app.add_systems(
+            EguiPrimaryContextPass,
+            review_window_system.in_set(EguiSystemSets::Review),
+        );
+    }
+}
+
+/// A faded copy of an object that completed its destination.
+#[derive(Component)]
+pub struct Ghost {
+    /// Display name of the object.
+    pub name:     String,
+    /// Horizontal position of the object upon completion.
+    pub position: Position<Vec2>,
+    /// Altitude of the object upon completion.
+    altitude:     transition::Reading,
+    /// Ground speed of the object upon completion.
+    speed:        Speed<f32>,
+    /// Score awarded for the completion, if any.
+    score:        Option<Score>,
+    /// Virtual time at which the object completed.
+    completed_at: Duration,
+    sprite:       Entity,
+    label:        Entity,
+    toast:        Option<Entity>,
+}
+
+/// The ghost whose completion is shown in the review window.
+#[derive(Resource, Default)]
+pub struct Reviewed(pub Option<Entity>);
+
+/// Opacities of the parts of a ghost at a given age.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Opacity {
+    sprite: f32,
+    label:  f32,
+    toast:  f32,
+}
+
+/// Computes the opacities of a ghost that completed `age` ago,
+/// or `None` if the grace period has elapsed.
+///
+/// The sprite and the toast fade out linearly over `fade_duration`,
+/// while the label only dims down to `label_opacity`.
+fn opacity(
+    age: Duration,
+    fade_duration: Duration,
+    grace_period: Duration,
+    label_opacity: f32,
+) -> Option<Opacity> {
+    if age >= grace_period {
+        return None;
+    }
+
+    let fading = if fade_duration.is_zero() {
+        0.
+    } else {
+        (1. - age.as_secs_f32() / fade_duration.as_secs_f32()).max(0.)
+    };
+    Some(Opacity {
+        sprite: fading,
+        label:  label_opacity + (1. - label_opacity) * fading,
+        toast:  fading,
+    })
+}
+
+fn spawn_observer(
+    event: On<Remove, dest::Completed>,
+    object_query: Query<(
+        &Object,
+        &object::Display,
+        &object::Rotation,
+        &super::ColorTheme,
+        Option<&object::Category>,
+        Option<&dest::CompletionScore>,
+    )>,
+    conf: ReadConfig<super::Conf>,
+    asset_server: Res<AssetServer>,
+    transition: Res<transition::Transition>,
+    time: Res<Time<time::Virtual>>,
+    mut commands: Commands,
+) {
+    let Some((object, display, rotation, theme, category, score)) =
+        object_query.log_get(event.entity)
+    else {
+        return;
+    };
+    let conf = conf.read();
+
+    let ghost = commands
+        .spawn((
+            Transform::from_translation(Zorder::base_translation(object.position)),
+            Visibility::Visible,
+        ))
+        .id();
+
+    let sprite = commands
+        .spawn((
+            ChildOf(ghost),
+            Transform { rotation: rotation.0, ..Zorder::ObjectSprite.local_translation() },
+            Sprite {
+                color: theme.body,
+                ..Sprite::from_image(asset_server.load(conf.plane.sprite.path(category.copied())))
+            },
+            billboard::MaintainScale { size: conf.plane.sprite_size },
+            conf.plane.zoom_bands(),
+        ))
+        .id();
+
+    let label = commands
+        .spawn((
+            ChildOf(ghost),
+            Zorder::ObjectLabel.local_translation(),
+            billboard::MaintainScale { size: conf.plane.label_size },
+            billboard::MaintainRotation,
+            billboard::Label { offset: Length::ZERO, distance: conf.plane.label_distance },
+            Text2d::new(display.name.clone()),
+            TextColor(theme.label),
+            conf.plane.label_anchor,
+        ))
+        .id();
+
+    let score = score.map(|score| score.score);
+    let toast = score.map(|score| {
+        let color = if score.0 < 0 { conf.ghost.penalty_color } else { conf.ghost.score_color };
+        commands
+            .spawn((
+                ChildOf(ghost),
+                Zorder::ObjectLabel.local_translation(),
+                billboard::MaintainScale { size: conf.plane.label_size },
+                billboard::MaintainRotation,
+                billboard::Label {
+                    offset:   Length::ZERO,
+                    distance: conf.plane.label_distance * 2.,
+                },
+                Text2d::new(format!("{:+}", score.0)),
+                TextColor(color),
+                conf.plane.label_anchor,
+            ))
+            .id()
+    });
+
+    commands.entity(ghost).insert(Ghost {
+        name: display.name.clone(),
+        position: object.position.horizontal(),
+        altitude: transition.reading(object.position.altitude()),
+        speed: object.ground_speed.horizontal().magnitude_exact(),
+        score,
+        completed_at: time.elapsed(),
+        sprite,
+        label,
+        toast,
+    });
+}
+
+fn maintain_system(
+    conf: ReadConfig<super::Conf>,
+    time: Res<Time<time::Virtual>>,
+    meta: Res<load::LoadedMeta>,
+    ghost_query: Query<(Entity, &Ghost)>,
+    mut sprite_query: Query<&mut Sprite>,
+    mut text_query: Query<(&mut TextColor, &mut billboard::Label)>,
+    mut reviewed: ResMut<Reviewed>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (entity, ghost) in ghost_query {
+        let age = time.elapsed().saturating_sub(ghost.completed_at);
+        let opacity = opacity(
+            age,
+            conf.ghost.fade_duration,
+            conf.ghost.grace_period,
+            conf.ghost.label_opacity,
+        );
+        let Some(opacity) = opacity.filter(|_| !meta.is_changed()) else {
+            commands.entity(entity).despawn();
+            if reviewed.0 == Some(entity) {
+                reviewed.0 = None;
+            }
+            continue;
+        };
+
+        if let Some(mut sprite) = sprite_query.log_get_mut(ghost.sprite) {
+            sprite.color.set_alpha(opacity.sprite);
+        }
+        if let Some((mut color, _)) = text_query.log_get_mut(ghost.label) {
+            color.0.set_alpha(opacity.label);
+        }
+        if let Some(toast) = ghost.toast
+            && let Some((mut color, mut label)) = text_query.log_get_mut(toast)
+        {
+            color.0.set_alpha(opacity.toast);
+            label.distance =
+                conf.plane.label_distance * 2. + conf.ghost.toast_rise * age.as_secs_f32();
+        }
+    }
+}
+
+fn review_window_system(
+    mut contexts: EguiContexts,
+    mut reviewed: ResMut<Reviewed>,
+    ghost_query: Query<&Ghost>,
+    clock: Res<Clock>,
+) {
+    let Some(entity) = reviewed.0 else { return };
+    let Ok(ghost) = ghost_query.get(entity) else {
+        reviewed.0 = None;
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+
+    let mut open = true;
+    egui::Window::new(format!("{} (completed)", ghost.name))
+        .id(egui::Id::new("ghost-review"))
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let time_of_day = clock.time_of_day(ghost.completed_at).as_secs();
+            ui.label(format!(
+                "Completed at {:02}:{:02}:{:02}",
+                time_of_day / 3600,
+                (time_of_day / 60) % 60,
+                time_of_day % 60
+            ));
+            ui.label(format!("Final altitude: {}", ghost.altitude.hundreds()));
+            ui.label(format!("Final ground speed: {:.0} kt", ghost.speed.into_knots()));
+            match ghost.score {
+                Some(score) => ui.label(format!("Score: {:+}", score.0)),
+                None => ui.label("No score awarded"),
+            };
+        });
+    if !open {
+        reviewed.0 = None;
+    }
+}
+
+#[derive(Config)]
+pub(super) struct Conf {
+    /// Duration over which the sprite and the score toast of a completed object fade out.
+    #[config(default = Duration::from_secs(2), min = Duration::ZERO, max = Duration::from_secs(10))]
+    fade_duration: Duration,
+    /// Duration for which the datablock of a completed object remains clickable.
+    #[config(default = Duration::from_secs(10), min = Duration::ZERO, max = Duration::from_mins(1))]
+    grace_period:  Duration,
+    /// Opacity of the datablock of a completed object after the sprite has faded out.
+    #[config(default = 0.4, min = 0.0, max = 1.0)]
+    label_opacity: f32,
+    /// Distance the score toast rises per second, in screen coordinates.
+    #[config(default = 15.0, min = 0.0, max = 100.0)]
+    toast_rise:    f32,
+    /// Color of the score toast for positive scores.
+    #[config(default = Color::srgb(0.5, 1.0, 0.5))]
+    score_color:   Color,
+    /// Color of the score toast for negative scores.
+    #[config(default = Color::srgb(1.0, 0.5, 0.4))]
+    penalty_color: Color,
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use super::{Opacity, opacity};
+
+const FADE: Duration = Duration::from_secs(2);
+const GRACE: Duration = Duration::from_secs(10);
+
+#[test]
+fn test_sprite_fades_while_label_dims() {
+    assert_eq!(
+        opacity(Duration::ZERO, FADE, GRACE, 0.5),
+        Some(Opacity { sprite: 1., label: 1., toast: 1. })
+    );
+    assert_eq!(
+        opacity(Duration::from_secs(1), FADE, GRACE, 0.5),
+        Some(Opacity { sprite: 0.5, label: 0.75, toast: 0.5 })
+    );
+    assert_eq!(
+        opacity(Duration::from_secs(5), FADE, GRACE, 0.5),
+        Some(Opacity { sprite: 0., label: 0.5, toast: 0. })
+    );
+}
+
+#[test]
+fn test_grace_period_elapsed() {
+    assert_eq!(opacity(Duration::from_secs(10), FADE, GRACE, 0.5), None);
+    assert_eq!(
+        opacity(Duration::ZERO, Duration::ZERO, GRACE, 0.5),
+        Some(Opacity { sprite: 0., label: 0.5, toast: 0. })
+    );
+}
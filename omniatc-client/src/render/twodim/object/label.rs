@@ -7,6 +7,9 @@ use bevy::ecs::query::{self, QueryData, QueryEntityError};
 use bevy::ecs::system::{Commands, EntityCommands, Query, SystemParam};
 use bevy::text::{TextColor, TextSpan};
 use omniatc::level::object::{self};
+use omniatc::level::{
+    bird, deviation, formation, fuel, hold, note, pilot, radio, route, transition, transponder, vfr,
+};
 
 use super::PlaneConfRead;
 
@@ -26,17 +29,112 @@ pub struct ObjectData {
     label_entity: &'static HasLabel,
     display:      &'static object::Display,
     theme:        &'static super::ColorTheme,
+    min_fuel:     query::Has<fuel::MinimumFuel>,
+    vfr:          query::Has<vfr::Vfr>,
+    primary_only: query::Has<transponder::Failed>,
+    following:    query::Has<vfr::FlightFollowing>,
+    formation:    Option<&'static formation::Formation>,
+    emergency:    query::Has<bird::Emergency>,
+    nordo:        query::Has<radio::Failed>,
+    deviation:    query::Has<deviation::Alert>,
+    holding:      query::Has<hold::Holding>,
+    notes:        Option<&'static note::Notes>,
+    position:     &'static super::radar::DisplayPosition,
+    airborne:     query::Has<object::Airborne>,
+    clearance:    Option<&'static pilot::Clearance>,
+    next_speed:   Option<&'static route::ConditionalSpeed>,
 }
 
 impl ObjectDataItem<'_, '_> {
-    pub fn write_label(&self, _conf: &PlaneConfRead, label_writer: &mut Writer) {
+    pub fn write_label(
+        &self,
+        conf: &PlaneConfRead,
+        transition: &transition::Transition,
+        label_writer: &mut Writer,
+    ) {
         label_writer.rewrite(self.label_entity.0, |mut s| {
+            if self.primary_only {
+                // Primary targets have neither callsign nor altitude readouts.
+                return;
+            }
+            if self.vfr && !self.following {
+                // The callsign is unknown until the object is identified for flight following.
+                s.write("VFR").color(self.theme.label);
+                return;
+            }
+
             s.write(&self.display.name).color(self.theme.label);
+            if let Some(formation) = self.formation {
+                s.write(format!(" x{}", formation.size)).color(self.theme.label);
+            }
+            if self.vfr {
+                s.write(" VFR").color(self.theme.label);
+            }
+            if self.min_fuel {
+                s.write(" MINFUEL").color(conf.minimum_fuel_color);
+            }
+            if self.emergency {
+                s.write(" EMERG").color(conf.emergency_color);
+            }
+            if self.nordo {
+                s.write(format!(" {}", radio::SQUAWK)).color(conf.emergency_color);
+            }
+            if self.deviation {
+                s.write(" DEV").color(conf.emergency_color);
+            }
+            if self.holding {
+                s.write(" HLD").color(self.theme.label);
+            }
+            if let Some(notes) = self.notes {
+                if !notes.reminders.is_empty() {
+                    s.write(" RMD").color(self.theme.label);
+                }
+                if let Some(line) =
+                    notes.scratchpad.lines().map(str::trim).find(|line| !line.is_empty())
+                {
+                    s.write(format!("\n{line}")).color(self.theme.label);
+                }
+            }
+            if conf.show_clearance && self.airborne {
+                self.write_clearance(conf, transition, &mut s);
+            }
             // TODO add additional information based on conf
         });
     }
 }
 
+impl ObjectDataItem<'_, '_> {
+    /// Writes the current altitude followed by the acknowledged clearance values,
+    /// in hundreds of feet, degrees and knots respectively.
+    ///
+    /// Altitudes at or above the transition level are written as flight levels, e.g. `F350`.
+    /// A pending conditional speed is appended after the cleared speed, e.g. `S250>180`.
+    fn write_clearance(
+        &self,
+        conf: &PlaneConfRead,
+        transition: &transition::Transition,
+        s: &mut WriterScope,
+    ) {
+        let altitude = transition.reading(self.position.position.altitude()).hundreds();
+        s.write(format!("\n{altitude}")).color(self.theme.label);
+
+        let Some(clearance) = self.clearance else { return };
+        if let Some(altitude) = clearance.altitude {
+            let altitude = transition.reading(altitude).hundreds();
+            s.write(format!(" C{altitude}")).color(conf.clearance_color);
+        }
+        if let Some(heading) = clearance.heading {
+            s.write(format!(" H{:03.0}", heading.degrees())).color(conf.clearance_color);
+        }
+        if let Some(speed) = clearance.speed {
+            s.write(format!(" S{:.0}", speed.into_knots())).color(conf.clearance_color);
+        }
+        if let Some(next) = self.next_speed {
+            s.write(format!(">{:.0}", next.speed.into_knots())).color(conf.clearance_color);
+        }
+    }
+}
+
 #[derive(SystemParam)]
 pub struct Writer<'w, 's> {
     owner_query: Query<'w, 's, &'static Children, query::With<IsLabelOf>>,
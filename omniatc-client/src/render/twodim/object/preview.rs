@@ -23,6 +23,12 @@
 //! If the current target is a waypoint,
 //! each available route preset from that waypoint is drawn similarly to the route viewable.
 //!
+//! ## Transient viewable
+//! Draws a route source set in [`TransientPreview`] for the current frame only,
+//! e.g. the preset hovered in the route picker before it is committed.
+//! The full lateral path is drawn similarly to the route viewable,
+//! with a label at each waypoint showing the altitude planned at that waypoint.
+//!
 //! # Ground objects
 //! ## Ground path viewable
 //! Only displayed when the current active node is a taxi node.
@@ -39,12 +45,15 @@ use bevy::ecs::bundle::Bundle;
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::{Has, QueryData, With, Without};
+use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Local, ParamSet, Query, Res, ResMut, Single, SystemParam};
 use bevy::ecs::world::Mut;
 use bevy::math::Vec2;
 use bevy::mesh::{Mesh, Mesh2d, PrimitiveTopology, VertexAttributeValues};
+use bevy::sprite::{Anchor, Text2d};
 use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy::text::TextColor;
 use bevy::transform::components::Transform;
 use bevy_mod_config::{Config, ReadConfig};
 use either::Either;
@@ -54,15 +63,15 @@ use omniatc::QueryTryLog;
 use omniatc::level::object::{self, Object};
 use omniatc::level::route::{self, Route};
 use omniatc::level::waypoint::Waypoint;
-use omniatc::level::{ground, nav, plane};
+use omniatc::level::{ground, nav, plane, transition};
 use omniatc::util::EnumScheduleConfig;
 use store::{NavLimits, YawTarget};
 
 use super::SetColorThemeSystemSet;
 use crate::render;
 use crate::render::object_info;
-use crate::render::twodim::Zorder;
-use crate::util::{ActiveCamera2d, shapes};
+use crate::render::twodim::{Zorder, cull};
+use crate::util::{ActiveCamera2d, AnchorConf, billboard, shapes};
 
 const ARC_DENSITY: Angle = Angle::from_degrees(10.0);
 
@@ -70,6 +79,7 @@ pub(super) struct Plug;
 
 impl Plugin for Plug {
     fn build(&self, app: &mut App) {
+        app.init_resource::<TransientPreview>();
         app.add_systems(
             app::Update,
             update_system.in_set(render::SystemSets::Update).after_all::<SetColorThemeSystemSet>(),
@@ -87,10 +97,21 @@ struct GroundViewable;
 
 fn update_system(
     mut materials: Local<Materials>,
-    mut stages: ParamSet<(Init, DrawCurrent, DrawMainRoute, DrawPresets, DrawGroundPaths)>,
+    mut stages: ParamSet<(
+        Init,
+        DrawCurrent,
+        DrawMainRoute,
+        DrawPresets,
+        DrawGroundPaths,
+        DrawTransient,
+    )>,
 ) {
     let materials = &mut *materials;
-    let Some(init) = stages.p0().init(materials) else { return };
+    let Some(init) = stages.p0().init(materials) else {
+        stages.p5().draw(None);
+        return;
+    };
+    stages.p5().draw(init.is_airborne.then_some(init.materials.transient));
 
     if init.is_airborne {
         let target = stages.p1().draw(init.object, init.materials.current);
@@ -119,6 +140,7 @@ struct Materials {
     normal:           Option<Handle<ColorMaterial>>,
     set_heading:      Option<Handle<ColorMaterial>>,
     preset:           Option<Handle<ColorMaterial>>,
+    transient:        Option<Handle<ColorMaterial>>,
     ground_path_best: Option<Handle<ColorMaterial>>,
     ground_path_alt:  Option<Handle<ColorMaterial>>,
 }
@@ -159,12 +181,14 @@ impl Init<'_, '_> {
             normal_material,
             set_heading_material,
             preset_material,
+            transient_material,
             ground_path_material_best,
             ground_path_material_alt,
         ] = [
             (&mut materials.normal, conf.preview_line.color_normal),
             (&mut materials.set_heading, conf.preview_line.color_set_heading),
             (&mut materials.preset, conf.preview_line.color_preset),
+            (&mut materials.transient, conf.preview_line.color_transient),
             (&mut materials.ground_path_best, conf.preview_line.color_ground_path_best),
             (&mut materials.ground_path_alt, conf.preview_line.color_ground_path_alt),
         ]
@@ -193,6 +217,7 @@ impl Init<'_, '_> {
                 current:             current_material,
                 route:               normal_material,
                 preset:              preset_material,
+                transient:           transient_material,
                 ground_path_best:    ground_path_material_best,
                 ground_path_alt:     Some(ground_path_material_alt)
                     .filter(|_| conf.preview_line.render_ground_path_alt),
@@ -213,6 +238,7 @@ struct UsedMaterials<'a> {
     current:             &'a Handle<ColorMaterial>,
     route:               &'a Handle<ColorMaterial>,
     preset:              &'a Handle<ColorMaterial>,
+    transient:           &'a Handle<ColorMaterial>,
     ground_path_best:    &'a Handle<ColorMaterial>,
     ground_path_alt:     Option<&'a Handle<ColorMaterial>>,
     ground_path_preview: &'a Handle<ColorMaterial>,
@@ -249,6 +275,19 @@ pub enum TargetOverrideCause {
     SetRoute,
 }
 
+/// A route source to preview in addition to the routes of the current object.
+///
+/// The source is consumed by the preview renderer on the next frame,
+/// so it must be set again on every frame it should remain visible.
+#[derive(Resource, Default)]
+pub struct TransientPreview(pub Option<TransientSource>);
+
+#[derive(Clone, Copy)]
+pub enum TransientSource {
+    /// A route preset entity, e.g. hovered in the route picker.
+    Preset(Entity),
+}
+
 #[derive(SystemParam)]
 struct DrawCurrent<'w, 's> {
     conf:           ReadConfig<'w, 's, super::Conf>,
@@ -626,6 +665,53 @@ impl DrawPresets<'_, '_> {
     }
 }
 
+#[derive(SystemParam)]
+struct DrawTransient<'w, 's> {
+    source:         ResMut<'w, TransientPreview>,
+    preset_query:   Query<'w, 's, &'static route::Preset>,
+    viewable_query: Query<'w, 's, (Entity, &'static mut Transform), With<TransientViewable>>,
+    label_query: Query<
+        'w,
+        's,
+        (Entity, &'static mut billboard::Label, &'static mut Text2d, &'static mut TextColor),
+        With<TransientLabelViewable>,
+    >,
+    draw_once:      DrawRouteOnce<'w, 's>,
+}
+
+impl DrawTransient<'_, '_> {
+    /// Draws the transient source, or clears it if `material` is `None`.
+    fn draw(&mut self, material: Option<&Handle<ColorMaterial>>) {
+        let source = self.source.0.take();
+        let preset = match (source, material) {
+            (Some(TransientSource::Preset(entity)), Some(_)) => self.preset_query.log_get(entity),
+            _ => None,
+        };
+
+        let mut viewables = self.viewable_query.iter_mut();
+        let mut labels = self.label_query.iter_mut();
+        if let (Some(preset), Some(material)) = (preset, material) {
+            self.draw_once.draw_route::<TransientViewable>(
+                preset.nodes.iter(),
+                material,
+                &mut viewables.by_ref().map(|(_, tf)| tf),
+                Zorder::TransientRoutePreview,
+            );
+            self.draw_once.draw_altitude_labels::<TransientLabelViewable>(
+                preset.nodes.iter(),
+                &mut labels.by_ref().map(|(_, label, text, color)| (label, text, color)),
+            );
+        }
+
+        for (entity, _) in viewables {
+            self.draw_once.commands.entity(entity).despawn();
+        }
+        for (entity, ..) in labels {
+            self.draw_once.commands.entity(entity).despawn();
+        }
+    }
+}
+
 /// Shared code for drawing route lines, used in [`DrawMainRoute`] and [`DrawPresets`].
 #[derive(SystemParam)]
 struct DrawRouteOnce<'w, 's> {
@@ -634,6 +720,8 @@ struct DrawRouteOnce<'w, 's> {
     conf:           ReadConfig<'w, 's, super::Conf>,
     waypoint_query: Query<'w, 's, &'static Waypoint>,
     camera:         ActiveCamera2d<'w, 's>,
+    transition:     Res<'w, transition::Transition>,
+    viewport:       Res<'w, cull::Viewport>,
 }
 
 impl DrawRouteOnce<'_, '_> {
@@ -663,6 +751,9 @@ impl DrawRouteOnce<'_, '_> {
 
         let conf = self.conf.read();
         for (&start, &end) in positions.iter().tuple_windows() {
+            if !self.viewport.overlaps([start, end]) {
+                continue;
+            }
             if let Some(mut tf) = viewables.next() {
                 shapes::set_square_line_transform(&mut tf, start, end);
             } else {
@@ -682,6 +773,60 @@ impl DrawRouteOnce<'_, '_> {
     }
 }
 
+impl DrawRouteOnce<'_, '_> {
+    /// Labels each waypoint in the route with the altitude planned at that waypoint.
+    ///
+    /// The planned altitude is the altitude restriction of the waypoint if any,
+    /// otherwise the target of an altitude change started since the previous waypoint.
+    fn draw_altitude_labels<'w, MarkerT: Bundle + Default>(
+        &mut self,
+        nodes: impl Iterator<Item = &'w route::Node>,
+        labels: &mut impl Iterator<
+            Item = (Mut<'w, billboard::Label>, Mut<'w, Text2d>, Mut<'w, TextColor>),
+        >,
+    ) {
+        let conf = self.conf.read();
+
+        let mut started_altitude = None;
+        for node in nodes {
+            let (waypoint, altitude) = match node {
+                route::Node::StartSetAltitude(node) => {
+                    started_altitude = Some(node.altitude);
+                    continue;
+                }
+                route::Node::DirectWaypoint(node) => (node.waypoint, node.altitude),
+                route::Node::AlignRunway(node) => (node.runway, None),
+                _ => continue,
+            };
+            let Some(altitude) = altitude.or(started_altitude.take()) else { continue };
+            let Some(waypoint) = self.waypoint_query.log_get(waypoint) else { continue };
+            if !self.viewport.contains(waypoint.position.horizontal()) {
+                continue;
+            }
+
+            let offset = waypoint.position.horizontal() - Position::ORIGIN;
+            let content = self.transition.reading(altitude).to_string();
+            if let Some((mut label, mut text, mut color)) = labels.next() {
+                label.offset = offset;
+                label.distance = conf.preview_line.label_distance;
+                text.0 = content;
+                color.0 = conf.preview_line.color_transient;
+            } else {
+                self.commands.spawn((
+                    Text2d(content),
+                    TextColor(conf.preview_line.color_transient),
+                    billboard::Label { offset, distance: conf.preview_line.label_distance },
+                    billboard::MaintainRotation,
+                    billboard::MaintainScale { size: conf.preview_line.label_size },
+                    conf.preview_line.label_anchor,
+                    Zorder::TransientRouteLabel.local_translation(),
+                    MarkerT::default(),
+                ));
+            }
+        }
+    }
+}
+
 /// Marks an entity as an extended route viewable to follow the current target.
 #[derive(Component, Default)]
 #[require(AirborneViewable)]
@@ -693,6 +838,16 @@ struct RouteViewable;
 #[require(AirborneViewable)]
 struct PresetViewable;
 
+/// Marks an entity as a segment of the route viewable for the [`TransientPreview`] source.
+#[derive(Component, Default)]
+#[require(AirborneViewable)]
+struct TransientViewable;
+
+/// Marks an entity as an altitude label for the [`TransientPreview`] source.
+#[derive(Component, Default)]
+#[require(AirborneViewable)]
+struct TransientLabelViewable;
+
 #[derive(SystemParam)]
 struct DrawGroundPaths<'w, 's> {
     object_query:
@@ -750,6 +905,7 @@ struct DrawGroundPathOnce<'w, 's> {
     conf:           ReadConfig<'w, 's, super::Conf>,
     endpoint_query: Query<'w, 's, &'static ground::Endpoint>,
     camera:         ActiveCamera2d<'w, 's>,
+    viewport:       Res<'w, cull::Viewport>,
 }
 
 impl DrawGroundPathOnce<'_, '_> {
@@ -768,6 +924,9 @@ impl DrawGroundPathOnce<'_, '_> {
             })
             .tuple_windows();
         for (start, end) in endpoint_pairs {
+            if !self.viewport.overlaps([start, end]) {
+                continue;
+            }
             if let Some((_, mut tf, mut material_ref)) = viewables.next() {
                 shapes::set_square_line_transform(&mut tf, start, end);
                 if material_ref.0 != *material {
@@ -815,6 +974,17 @@ pub(super) struct Conf {
     /// Color of available route presets from the current target waypoint.
     #[config(default = Color::srgb(0.5, 0.6, 0.8))]
     color_preset:           Color,
+    /// Color of transient route previews, e.g. the preset hovered in the route picker.
+    #[config(default = Color::srgb(0.6, 0.9, 1.0))]
+    color_transient:        Color,
+    /// Size of altitude labels on transient route previews.
+    #[config(default = 0.5, min = 0.0, max = 3.0)]
+    label_size:             f32,
+    /// Distance of altitude labels from their waypoints, in screen coordinates.
+    #[config(default = 20.0, min = 0.0, max = 100.0)]
+    label_distance:         f32,
+    #[config(default = Anchor::TOP_CENTER)]
+    label_anchor:           AnchorConf,
     /// Color of the best path found by the ground pathfinder.
     #[config(default = Color::srgb(0.5, 0.8, 0.6))]
     color_ground_path_best: Color,
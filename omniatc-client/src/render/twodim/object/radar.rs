@@ -0,0 +1,124 @@
+//! Simulated radar sweeps for displayed object positions.
+//!
+//! Renderers read [`DisplayPosition`] instead of [`Object`]
+//! so that the realism mode can update displayed positions once per sweep
+//! while the simulation remains continuous.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::math::Vec3;
+use bevy::time::{self, Time};
+use bevy_mod_config::{Config, ReadConfig};
+use math::{Position, Speed};
+use omniatc::level::object::{self, Object};
+
+use crate::render;
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(app::Update, spawn_system.in_set(render::SystemSets::Spawn));
+        app.add_systems(
+            app::Update,
+            update_system.after(render::SystemSets::Spawn).before(render::SystemSets::Update),
+        );
+    }
+}
+
+/// Position and velocity of an object as displayed on the radar view.
+#[derive(Component)]
+pub struct DisplayPosition {
+    /// Displayed position of the object.
+    pub position:     Position<Vec3>,
+    /// Displayed ground speed of the object, used for speed vectors.
+    pub ground_speed: Speed<Vec3>,
+}
+
+impl From<&Object> for DisplayPosition {
+    fn from(object: &Object) -> Self {
+        Self { position: object.position, ground_speed: object.ground_speed }
+    }
+}
+
+/// State of the alpha-beta tracking filter of an object.
+#[derive(Component, Default)]
+struct Track {
+    /// Index of the last sweep that updated the track, or `None` if not yet plotted.
+    last_sweep: Option<u64>,
+}
+
+fn spawn_system(
+    mut spawn_events: MessageReader<object::SpawnMessage>,
+    object_query: Query<&Object>,
+    mut commands: Commands,
+) {
+    for &object::SpawnMessage(entity) in spawn_events.read() {
+        let Ok(object) = object_query.get(entity) else { continue };
+        commands.entity(entity).insert((DisplayPosition::from(object), Track::default()));
+    }
+}
+
+fn update_system(
+    conf: ReadConfig<super::Conf>,
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<(&Object, &mut DisplayPosition, &mut Track)>,
+) {
+    let conf = conf.read();
+    let conf = &conf.radar;
+
+    if !conf.realism {
+        for (object, mut display, mut track) in object_query {
+            *display = DisplayPosition::from(object);
+            track.last_sweep = None;
+        }
+        return;
+    }
+
+    let interval = conf.sweep_interval;
+    let elapsed = time.elapsed();
+    #[expect(clippy::cast_possible_truncation, reason = "sweep count never exceeds u64")]
+    let sweep = (elapsed.as_nanos() / interval.as_nanos()) as u64;
+
+    for (object, mut display, mut track) in object_query {
+        match track.last_sweep {
+            Some(last) if last == sweep => {}
+            Some(last) => {
+                #[expect(clippy::cast_precision_loss, reason = "sweep gaps are small")]
+                let dt = interval.mul_f32((sweep - last) as f32);
+                let predicted = display.position + display.ground_speed * dt;
+                let residual = object.position - predicted;
+                display.position = predicted + residual * conf.position_gain;
+                display.ground_speed += residual * conf.velocity_gain / dt;
+                track.last_sweep = Some(sweep);
+            }
+            None => {
+                *display = DisplayPosition::from(object);
+                track.last_sweep = Some(sweep);
+            }
+        }
+    }
+}
+
+#[derive(Config)]
+pub(super) struct Conf {
+    /// Update displayed positions once per radar sweep instead of continuously.
+    #[config(default = false)]
+    realism:        bool,
+    /// Interval between consecutive radar sweeps.
+    #[config(default = Duration::from_millis(4800), min = Duration::from_secs(1), max = Duration::from_secs(15))]
+    sweep_interval: Duration,
+    /// Fraction of the deviation from the predicted position applied to the displayed position
+    /// on each sweep.
+    #[config(default = 0.5, min = 0.0, max = 1.0)]
+    position_gain:  f32,
+    /// Fraction of the deviation from the predicted position, per sweep interval,
+    /// applied to the displayed velocity on each sweep.
+    #[config(default = 0.3, min = 0.0, max = 1.0)]
+    velocity_gain:  f32,
+}
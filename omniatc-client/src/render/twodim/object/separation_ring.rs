@@ -24,6 +24,7 @@ use super::{ColorTheme, SetColorThemeSystemSet};
 use crate::render;
 use crate::render::twodim::Zorder;
 use crate::render::twodim::object::base_color;
+use crate::util::palette::PaletteParam;
 
 pub(super) struct Plug;
 
@@ -55,9 +56,9 @@ fn init_system(mut handle: ResMut<SeparationRingMesh>, mut assets: ResMut<Assets
 
 #[derive(SystemParam)]
 pub(super) struct SpawnSubsystemParam<'w, 's> {
-    commands:  Commands<'w, 's>,
-    mesh:      Res<'w, SeparationRingMesh>,
-    materials: ResMut<'w, Assets<ColorMaterial>>,
+    commands: Commands<'w, 's>,
+    mesh:     Res<'w, SeparationRingMesh>,
+    palette:  PaletteParam<'w>,
 }
 
 #[derive(Component)]
@@ -69,7 +70,7 @@ struct IsRingOf(Entity);
 struct HasRing(Entity);
 
 pub(super) fn spawn_subsystem(plane_entity: Entity, p: &mut SpawnSubsystemParam) {
-    let material = p.materials.add(ColorMaterial { color: Color::WHITE, ..Default::default() });
+    let material = p.palette.material(Color::WHITE);
 
     p.commands.spawn((
         ChildOf(plane_entity),
@@ -82,15 +83,15 @@ pub(super) fn spawn_subsystem(plane_entity: Entity, p: &mut SpawnSubsystemParam)
 
 fn maintain_color_system(
     object_query: Query<(&ColorTheme, &HasRing)>,
-    ring_query: Query<&MeshMaterial2d<ColorMaterial>>,
-    mut material_assets: ResMut<Assets<ColorMaterial>>,
+    mut ring_query: Query<&mut MeshMaterial2d<ColorMaterial>>,
+    mut palette: PaletteParam,
 ) {
     for (color, &HasRing(ring_entity)) in object_query {
-        let Some(material_handle) = ring_query.log_get(ring_entity) else { continue };
-        let material = material_assets
-            .get_mut(&material_handle.0)
-            .expect("asset from strong handle must exist");
-        material.color = color.ring;
+        let Some(mut material) = ring_query.log_get_mut(ring_entity) else { continue };
+        let handle = palette.material(color.ring);
+        if material.0 != handle {
+            material.0 = handle;
+        }
     }
 }
 
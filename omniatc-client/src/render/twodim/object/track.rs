@@ -1,12 +1,11 @@
 use bevy::app::{self, App, Plugin};
-use bevy::asset::Assets;
 use bevy::color::{Color, Mix};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::name::Name;
 use bevy::ecs::query::With;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::ecs::system::{Commands, Query, Res};
 use bevy::math::Vec3;
 use bevy::mesh::Mesh2d;
 use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
@@ -21,6 +20,7 @@ use super::SetColorThemeSystemSet;
 use crate::render;
 use crate::render::object_info;
 use crate::render::twodim::Zorder;
+use crate::util::palette::{self, PaletteParam};
 use crate::util::{billboard, shapes};
 
 pub(super) struct Plug;
@@ -44,9 +44,9 @@ struct PointList(Vec<Entity>);
 
 fn respawn_system(
     object_query: Query<(Entity, &object::Track, Option<&PointList>)>,
-    mut point_query: Query<(&mut Transform, &MeshMaterial2d<ColorMaterial>), With<IsPointOf>>,
+    mut point_query: Query<(&mut Transform, &mut MeshMaterial2d<ColorMaterial>), With<IsPointOf>>,
     shapes: Res<shapes::Meshes>,
-    mut material_assets: ResMut<Assets<ColorMaterial>>,
+    mut palette: PaletteParam,
     mut commands: Commands,
     conf: ReadConfig<super::Conf>,
     current_object: Res<object_info::CurrentObject>,
@@ -66,9 +66,12 @@ fn respawn_system(
             .map(|&pos| {
                 let color = conf.track.point_base_color.mix(
                     &conf.track.point_top_color,
-                    pos.altitude().ratio_between(
-                        conf.track.point_base_altitude,
-                        conf.track.point_top_altitude,
+                    palette::quantize(
+                        pos.altitude().ratio_between(
+                            conf.track.point_base_altitude,
+                            conf.track.point_top_altitude,
+                        ),
+                        conf.track.color_steps,
                     ),
                 );
 
@@ -78,8 +81,8 @@ fn respawn_system(
         manage_entity_vec(
             object_entity,
             point_list,
-            &mut (point_data, &mut material_assets),
-            |_, (point_data, material_assets)| {
+            &mut (point_data, &mut palette),
+            |_, (point_data, palette)| {
                 let (translation, color) = point_data.next()?;
 
                 Some((
@@ -87,21 +90,21 @@ fn respawn_system(
                     Transform { translation, scale: Vec3::ZERO, ..Default::default() },
                     billboard::MaintainScale { size: conf.track.point_size },
                     Mesh2d(shapes.circle().clone()),
-                    MeshMaterial2d(material_assets.add(color)),
+                    MeshMaterial2d(palette.material(color)),
                 ))
             },
-            |_, (point_data, material_assets), point_entity| {
+            |_, (point_data, palette), point_entity| {
                 let (translation, color) = point_data.next().ok_or(())?;
 
-                let Some((mut tf, material_ref)) = point_query.log_get_mut(point_entity) else {
+                let Some((mut tf, mut material)) = point_query.log_get_mut(point_entity) else {
                     return Err(());
                 };
 
                 tf.translation = translation;
-                material_assets
-                    .get_mut(&material_ref.0)
-                    .expect("strong handle must be valid")
-                    .color = color;
+                let handle = palette.material(color);
+                if material.0 != handle {
+                    material.0 = handle;
+                }
 
                 Ok(())
             },
@@ -130,4 +133,9 @@ pub(super) struct Conf {
     /// Top altitude for track point coloring.
     #[config(default = TROPOPAUSE_ALTITUDE)]
     point_top_altitude:  Position<f32>,
+    /// Number of distinct colors in the track point gradient.
+    ///
+    /// Track points of the same color are drawn in a single batch.
+    #[config(default = 16, min = 1, max = 256)]
+    color_steps:         u32,
 }
@@ -14,10 +14,9 @@ use bevy::transform::components::Transform;
 use bevy_mod_config::{Config, ReadConfig};
 use math::Length;
 use omniatc::QueryTryLog;
-use omniatc::level::object::Object;
 use omniatc::util::EnumScheduleConfig;
 
-use super::{ColorTheme, SetColorThemeSystemSet};
+use super::{ColorTheme, SetColorThemeSystemSet, radar};
 use crate::render;
 use crate::render::twodim::Zorder;
 use crate::render::twodim::object::base_color;
@@ -80,13 +79,13 @@ fn maintain_color_system(
 
 fn maintain_length_system(
     conf: ReadConfig<super::Conf>,
-    object_query: Query<(&Object, &HasVector)>,
+    object_query: Query<(&radar::DisplayPosition, &HasVector)>,
     mut vector_query: Query<&mut Transform, With<IsVectorOf>>,
 ) {
     let conf = conf.read();
 
-    for (object, &HasVector(vector_entity)) in object_query {
-        let vector_dist = object.ground_speed.horizontal() * conf.vector.lookahead_time;
+    for (display, &HasVector(vector_entity)) in object_query {
+        let vector_dist = display.ground_speed.horizontal() * conf.vector.lookahead_time;
         let Some(mut transform) = vector_query.log_get_mut(vector_entity) else { continue };
         shapes::set_square_line_transform_relative(&mut transform, Length::ZERO, vector_dist);
     }
@@ -0,0 +1,291 @@
+//! Separation readout between a [pair of selected objects](input::PairSelection).
+//!
+//! A line connects the two objects,
+//! labelled with their lateral and vertical separation and the lateral closure rate.
+//! If the objects are closing in on each other,
+//! a second line connects their predicted positions at the closest point of approach,
+//! labelled with the predicted minimum lateral separation and the time until then.
+//! The prediction extrapolates the current ground speed of both objects linearly.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::asset::{Assets, Handle};
+use bevy::camera::visibility::Visibility;
+use bevy::color::Color;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Local, Query, Res, ResMut, SystemParam};
+use bevy::math::Vec2;
+use bevy::sprite::{Anchor, Text2d};
+use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy::text::TextColor;
+use bevy::transform::components::Transform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::{Length, Position, Speed};
+use omniatc::level::object::Object;
+
+use super::Zorder;
+use crate::util::{ActiveCamera2d, AnchorConf, billboard, shapes};
+use crate::{ConfigManager, input, render};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:pair");
+        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Color of the line and labels between the selected pair.
+    #[config(default = Color::srgb(1.0, 0.9, 0.5))]
+    color:          Color,
+    /// Color of the line and label at the predicted closest point of approach.
+    #[config(default = Color::srgb(1.0, 0.5, 0.4))]
+    closest_color:  Color,
+    /// Thickness of the lines, in window coordinates.
+    #[config(default = 1.0, min = 0.0, max = 5.0)]
+    thickness:      f32,
+    /// Maximum time ahead to predict the closest point of approach.
+    #[config(default = Duration::from_mins(10))]
+    horizon:        Duration,
+    #[config(default = 0.6, min = 0.0, max = 3.0)]
+    label_size:     f32,
+    #[config(default = 10.0, min = 0.0, max = 100.0)]
+    label_distance: f32,
+    #[config(default = Anchor::CENTER_LEFT)]
+    label_anchor:   AnchorConf,
+}
+
+/// Live separation between a pair of objects.
+#[derive(Debug, Clone, Copy)]
+pub struct Analysis {
+    /// Current horizontal distance between the objects.
+    pub lateral:      Length<f32>,
+    /// Current absolute altitude difference between the objects.
+    pub vertical:     Length<f32>,
+    /// Rate at which the lateral separation decreases.
+    ///
+    /// Negative if the objects are diverging.
+    pub closure_rate: Speed<f32>,
+    /// Predicted closest point of approach,
+    /// `None` if the objects are diverging or only closest beyond the horizon.
+    pub closest:      Option<ClosestApproach>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClosestApproach {
+    /// Time until the closest point of approach.
+    pub time:      Duration,
+    /// Predicted lateral separation at the closest point of approach.
+    pub lateral:   Length<f32>,
+    /// Predicted horizontal positions of the two objects at the closest point of approach.
+    pub positions: [Position<Vec2>; 2],
+}
+
+/// Computes the separation between two objects,
+/// predicting the closest point of approach within `horizon`.
+#[must_use]
+pub fn analyze(first: &Object, second: &Object, horizon: Duration) -> Analysis {
+    let offset = second.position - first.position;
+    let rel_pos = offset.horizontal();
+    let rel_vel = (second.ground_speed - first.ground_speed).horizontal();
+
+    let lateral = rel_pos.magnitude_exact();
+    let closure_rate = if lateral > Length::ZERO {
+        Speed::new(-rel_pos.0.dot(rel_vel.0) / lateral.0)
+    } else {
+        Speed::ZERO
+    };
+
+    let rel_speed_sq = rel_vel.0.length_squared();
+    let closest = (closure_rate > Speed::ZERO && rel_speed_sq > 0.0)
+        .then(|| Duration::from_secs_f32(-rel_pos.0.dot(rel_vel.0) / rel_speed_sq))
+        .filter(|&time| time <= horizon)
+        .map(|time| ClosestApproach {
+            time,
+            lateral: (rel_pos + rel_vel * time).magnitude_exact(),
+            positions: [
+                first.position.horizontal() + first.ground_speed.horizontal() * time,
+                second.position.horizontal() + second.ground_speed.horizontal() * time,
+            ],
+        });
+
+    Analysis { lateral, vertical: offset.vertical().abs(), closure_rate, closest }
+}
+
+/// Marks a viewable entity of the pair readout.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum Viewable {
+    /// Line between the current positions.
+    Line,
+    /// Label of the current separation.
+    Label,
+    /// Line between the predicted positions at the closest point of approach.
+    ClosestLine,
+    /// Label of the closest point of approach.
+    ClosestLabel,
+}
+
+#[derive(Default)]
+struct Materials {
+    normal:  Option<Handle<ColorMaterial>>,
+    closest: Option<Handle<ColorMaterial>>,
+}
+
+#[derive(SystemParam)]
+struct UpdateParams<'w, 's> {
+    conf:         ReadConfig<'w, 's, Conf>,
+    selection:    ResMut<'w, input::PairSelection>,
+    object_query: Query<'w, 's, &'static Object>,
+    line_query: Query<
+        'w,
+        's,
+        (&'static Viewable, &'static mut Transform, &'static mut Visibility),
+        Without<Text2d>,
+    >,
+    label_query: Query<
+        'w,
+        's,
+        (
+            &'static Viewable,
+            &'static mut billboard::Label,
+            &'static mut Text2d,
+            &'static mut TextColor,
+            &'static mut Visibility,
+        ),
+    >,
+    materials:    ResMut<'w, Assets<ColorMaterial>>,
+    shapes:       Res<'w, shapes::Meshes>,
+    camera:       ActiveCamera2d<'w, 's>,
+    commands:     Commands<'w, 's>,
+}
+
+fn update_system(
+    mut params: UpdateParams,
+    mut materials: Local<Materials>,
+    mut spawned: Local<bool>,
+) {
+    let conf = params.conf.read();
+    let materials = &mut *materials;
+
+    let objects = params.selection.0.and_then(|[first, second]| {
+        Some((params.object_query.get(first).ok()?, params.object_query.get(second).ok()?))
+    });
+    if objects.is_none() && params.selection.0.is_some() {
+        // one of the objects has despawned
+        params.selection.0 = None;
+    }
+    let analysis = objects.map(|(first, second)| {
+        (
+            first.position.horizontal(),
+            second.position.horizontal(),
+            analyze(first, second, conf.horizon),
+        )
+    });
+
+    let [normal_material, closest_material] =
+        [(&mut materials.normal, conf.color), (&mut materials.closest, conf.closest_color)].map(
+            |(local, color)| match local {
+                None => local.insert(params.materials.add(color)).clone(),
+                Some(handle) => {
+                    *params.materials.get_mut(&*handle).expect("strong reference must exist") =
+                        ColorMaterial::from_color(color);
+                    handle.clone()
+                }
+            },
+        );
+
+    if !*spawned {
+        if analysis.is_none() {
+            return;
+        }
+        *spawned = true;
+
+        for (viewable, material) in
+            [(Viewable::Line, &normal_material), (Viewable::ClosestLine, &closest_material)]
+        {
+            params.commands.spawn((
+                params.shapes.line(conf.thickness, Zorder::PairSeparationLine),
+                MeshMaterial2d(material.clone()),
+                Visibility::Hidden,
+                viewable,
+            ));
+        }
+        for viewable in [Viewable::Label, Viewable::ClosestLabel] {
+            params.commands.spawn((
+                Text2d::default(),
+                TextColor::default(),
+                billboard::Label { offset: Length::ZERO, distance: conf.label_distance },
+                billboard::MaintainRotation,
+                billboard::MaintainScale { size: conf.label_size },
+                conf.label_anchor,
+                Zorder::PairSeparationLabel.local_translation(),
+                Visibility::Hidden,
+                viewable,
+            ));
+        }
+        // the spawned entities are updated from the next frame
+        return;
+    }
+
+    for (&viewable, mut tf, mut vis) in &mut params.line_query {
+        let endpoints = analysis.as_ref().and_then(|&(first, second, analysis)| match viewable {
+            Viewable::Line => Some([first, second]),
+            Viewable::ClosestLine => analysis.closest.map(|closest| closest.positions),
+            Viewable::Label | Viewable::ClosestLabel => None,
+        });
+        match endpoints {
+            Some([start, end]) => {
+                shapes::set_square_line_transform(&mut tf, start, end);
+                *vis = Visibility::Visible;
+            }
+            None => *vis = Visibility::Hidden,
+        }
+    }
+
+    for (&viewable, mut label, mut text, mut color, mut vis) in &mut params.label_query {
+        let content = analysis.as_ref().and_then(|&(first, second, analysis)| match viewable {
+            Viewable::Label => {
+                Some((first.midpoint(second), format_separation(&analysis), conf.color))
+            }
+            Viewable::ClosestLabel => analysis.closest.map(|closest| {
+                let [first, second] = closest.positions;
+                (first.midpoint(second), format_closest(&closest), conf.closest_color)
+            }),
+            Viewable::Line | Viewable::ClosestLine => None,
+        });
+        match content {
+            Some((position, content, label_color)) => {
+                label.offset = position - Position::ORIGIN;
+                label.distance = conf.label_distance;
+                text.0 = content;
+                color.0 = label_color;
+                *vis = Visibility::Visible;
+            }
+            None => *vis = Visibility::Hidden,
+        }
+    }
+}
+
+fn format_separation(analysis: &Analysis) -> String {
+    format!(
+        "{:.1} nm {:.0} ft\n{:+.0} kt",
+        analysis.lateral.into_nm(),
+        analysis.vertical.into_feet(),
+        analysis.closure_rate.into_knots(),
+    )
+}
+
+fn format_closest(closest: &ClosestApproach) -> String {
+    let secs = closest.time.as_secs();
+    format!("CPA {:.1} nm in {}:{:02}", closest.lateral.into_nm(), secs / 60, secs % 60)
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use math::{Length, Position, Speed};
+use omniatc::level::object::Object;
+
+use super::analyze;
+
+fn object(x_nm: f32, y_nm: f32, feet: f32, east_kt: f32, north_kt: f32) -> Object {
+    Object {
+        position:     Position::from_origin_nm(x_nm, y_nm)
+            .with_altitude(Position::from_amsl_feet(feet)),
+        ground_speed: Speed::from_knots_vec2(east_kt, north_kt).horizontally(),
+    }
+}
+
+#[test]
+fn test_head_on_closure() {
+    let first = object(0.0, 0.0, 5000.0, 0.0, 240.0);
+    let second = object(0.0, 10.0, 6000.0, 0.0, -360.0);
+    let analysis = analyze(&first, &second, Duration::from_mins(10));
+
+    analysis.lateral.assert_near(Length::from_nm(10.0), Length::from_nm(0.01)).expect("lateral");
+    analysis
+        .vertical
+        .assert_near(Length::from_feet(1000.0), Length::from_feet(1.0))
+        .expect("vertical");
+    analysis
+        .closure_rate
+        .assert_near(Speed::from_knots(600.0), Speed::from_knots(1.0))
+        .expect("closure rate is the sum of speeds");
+
+    let closest = analysis.closest.expect("objects are closing");
+    assert!((closest.time.as_secs_f32() - 60.0).abs() < 0.1, "{:?}", closest.time);
+    closest.lateral.assert_near(Length::ZERO, Length::from_nm(0.01)).expect("collision course");
+    closest.positions[0]
+        .assert_near(Position::from_origin_nm(0.0, 4.0), Length::from_nm(0.01))
+        .expect("first object flies 4 nm in a minute");
+}
+
+#[test]
+fn test_crossing_miss_distance() {
+    let first = object(-5.0, 0.0, 5000.0, 300.0, 0.0);
+    let second = object(0.0, -3.0, 5000.0, 0.0, 300.0);
+    let closest =
+        analyze(&first, &second, Duration::from_mins(10)).closest.expect("objects are converging");
+
+    // relative position (5, -3) nm, relative velocity (-300, 300) kt
+    assert!((closest.time.as_secs_f32() - 48.0).abs() < 0.1, "{:?}", closest.time);
+    closest
+        .lateral
+        .assert_near(Length::from_nm(2.0_f32.sqrt()), Length::from_nm(0.01))
+        .expect("miss distance");
+}
+
+#[test]
+fn test_diverging_or_beyond_horizon() {
+    let first = object(0.0, 0.0, 5000.0, 0.0, -240.0);
+    let second = object(0.0, 10.0, 5000.0, 0.0, 240.0);
+    let analysis = analyze(&first, &second, Duration::from_mins(10));
+    assert!(analysis.closure_rate < Speed::ZERO);
+    assert!(analysis.closest.is_none(), "diverging objects have no closest approach ahead");
+
+    let first = object(0.0, 0.0, 5000.0, 0.0, 60.0);
+    let second = object(0.0, 100.0, 5000.0, 0.0, 0.0);
+    assert!(
+        analyze(&first, &second, Duration::from_mins(10)).closest.is_none(),
+        "closest approach in 100 minutes is beyond the horizon"
+    );
+}
@@ -23,7 +23,7 @@ use omniatc::{QueryTryLog, try_log, try_log_return};
 use ordered_float::OrderedFloat;
 use store::{TaxiLimits, YawTarget};
 
-use super::object::preview;
+use super::object::{ghost, preview, radar};
 use crate::render::object_info::{self, CurrentObjectSelectorSystemSet};
 use crate::{ConfigManager, EguiState, UpdateSystemSets, input};
 
@@ -84,8 +84,9 @@ pub(super) fn input_system(
     if let Some(hover) = determine_mode.current_cursor_camera.hovered {
         let mode = determine_mode.determine();
         let clicked = determine_mode.current_cursor_camera.left.clicked.is_some();
+        let right_clicked = determine_mode.current_cursor_camera.right.clicked.is_some();
         match mode {
-            Mode::SelectObject => params.p1().run(hover, clicked),
+            Mode::SelectObject => params.p1().run(hover, clicked, right_clicked),
             Mode::SetRoute(set_route) => {
                 params.p2().run(hover, set_route);
                 is_preview = !set_route.commit;
@@ -156,12 +157,15 @@ struct SetRoute {
 pub(super) struct SelectObjectParams<'w, 's> {
     current_hovered_object: ResMut<'w, object_info::CurrentHoveredObject>,
     current_object:         ResMut<'w, object_info::CurrentObject>,
-    object_query:           Query<'w, 's, (Entity, &'static object::Object)>,
+    pair_selection:         ResMut<'w, input::PairSelection>,
+    object_query:           Query<'w, 's, (Entity, &'static radar::DisplayPosition)>,
+    ghost_query:            Query<'w, 's, (Entity, &'static ghost::Ghost)>,
+    reviewed_ghost:         ResMut<'w, ghost::Reviewed>,
     conf:                   ReadConfig<'w, 's, Conf>,
 }
 
 impl SelectObjectParams<'_, '_> {
-    fn run(&mut self, hover: input::CursorTarget, clicked: bool) {
+    fn run(&mut self, hover: input::CursorTarget, clicked: bool, right_clicked: bool) {
         let (Some(hover_position), Some(precision)) =
             (hover.ground_position(), hover.ground_precision())
         else {
@@ -175,8 +179,8 @@ impl SelectObjectParams<'_, '_> {
         let closest_object = self
             .object_query
             .iter()
-            .map(|(entity, object)| {
-                (entity, object.position.horizontal().distance_squared(hover_position))
+            .map(|(entity, display)| {
+                (entity, display.position.horizontal().distance_squared(hover_position))
             })
             .filter(|(_, dist_sq)| *dist_sq < click_tolerance.squared())
             .min_by_key(|(_, dist_sq)| OrderedFloat(dist_sq.0))
@@ -185,6 +189,26 @@ impl SelectObjectParams<'_, '_> {
         self.current_hovered_object.0 = closest_object;
         if clicked {
             self.current_object.0 = closest_object;
+
+            // Completed objects remain clickable for review until their grace period elapses.
+            self.reviewed_ghost.0 = if closest_object.is_some() {
+                None
+            } else {
+                self.ghost_query
+                    .iter()
+                    .map(|(entity, ghost)| {
+                        (entity, ghost.position.distance_squared(hover_position))
+                    })
+                    .filter(|(_, dist_sq)| *dist_sq < click_tolerance.squared())
+                    .min_by_key(|(_, dist_sq)| OrderedFloat(dist_sq.0))
+                    .map(|(ghost, _)| ghost)
+            };
+        }
+        if right_clicked {
+            self.pair_selection.0 = match (self.current_object.0, closest_object) {
+                (Some(first), Some(second)) if first != second => Some([first, second]),
+                _ => None,
+            };
         }
     }
 }
@@ -0,0 +1,171 @@
+//! Pulsing markers pointing at the object or waypoint highlighted by the focused tutorial quest.
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::asset::Assets;
+use bevy::camera::visibility::Visibility;
+use bevy::color::{Alpha, Color};
+use bevy::ecs::change_detection::DetectChangesMut;
+use bevy::ecs::component::Component;
+use bevy::ecs::hierarchy::{ChildOf, Children};
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut, Single};
+use bevy::math::Vec2;
+use bevy::math::primitives::{Annulus, Triangle2d};
+use bevy::mesh::{Mesh, Mesh2d};
+use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy::time::{self, Time};
+use bevy::transform::components::Transform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::{Length, Position};
+use omniatc::level::object::Object;
+use omniatc::level::quest;
+use omniatc::level::waypoint::Waypoint;
+
+use super::Zorder;
+use crate::render::tutorial_popup;
+use crate::util::ActiveCamera2d;
+use crate::{ConfigManager, render};
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:quest-marker");
+        app.add_systems(app::Startup, spawn_system);
+        app.add_systems(app::Update, maintain_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Clone, Copy, Component)]
+enum Marker {
+    Object,
+    Waypoint,
+}
+
+#[derive(Component)]
+enum MarkerPart {
+    Ring,
+    Arrow,
+}
+
+fn spawn_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let ring = meshes.add(Annulus::new(0.85, 1.0));
+    let arrow = meshes.add(Triangle2d::new(Vec2::ZERO, Vec2::new(0.5, 1.0), Vec2::new(-0.5, 1.0)));
+
+    for marker in [Marker::Object, Marker::Waypoint] {
+        let material = materials.add(ColorMaterial { color: Color::WHITE, ..Default::default() });
+        let root = commands.spawn((marker, Transform::IDENTITY, Visibility::Hidden)).id();
+        commands.spawn((
+            ChildOf(root),
+            MarkerPart::Ring,
+            Zorder::QuestMarker.local_translation(),
+            Mesh2d(ring.clone()),
+            MeshMaterial2d(material.clone()),
+        ));
+        commands.spawn((
+            ChildOf(root),
+            MarkerPart::Arrow,
+            Zorder::QuestMarker.local_translation(),
+            Mesh2d(arrow.clone()),
+            MeshMaterial2d(material),
+        ));
+    }
+}
+
+fn maintain_system(
+    conf: ReadConfig<Conf>,
+    camera: ActiveCamera2d,
+    time: Res<Time<time::Real>>,
+    focused: Option<
+        Single<
+            (Option<&quest::highlight::Object>, Option<&quest::highlight::Waypoint>),
+            With<tutorial_popup::Focused>,
+        >,
+    >,
+    object_query: Query<&Object>,
+    waypoint_query: Query<&Waypoint>,
+    mut marker_query: Query<(&Marker, &Children, &mut Transform, &mut Visibility)>,
+    mut part_query: Query<
+        (&MarkerPart, &mut Transform, &MeshMaterial2d<ColorMaterial>),
+        Without<Marker>,
+    >,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let conf = conf.read();
+
+    let (object_highlight, waypoint_highlight) = focused.map_or((None, None), |f| *f);
+    let object_position = object_highlight
+        .and_then(|highlight| highlight.entity)
+        .and_then(|entity| object_query.get(entity).ok())
+        .map(|object| object.position.horizontal());
+    let waypoint_position = waypoint_highlight
+        .and_then(|&quest::highlight::Waypoint(entity)| waypoint_query.get(entity).ok())
+        .map(|waypoint| waypoint.position.horizontal());
+
+    let millis = time.elapsed().as_millis() % conf.period.as_millis();
+    #[expect(clippy::cast_precision_loss, reason = "period restricts millis to a small value")]
+    let fract = millis as f32 / conf.period.as_millis() as f32;
+    let phase = ((fract * PI * 2.0).sin() + 1.0) * 0.5;
+
+    for (&marker, children, mut marker_tf, mut vis) in &mut marker_query {
+        let position: Option<Position<Vec2>> = match marker {
+            Marker::Object => object_position,
+            Marker::Waypoint => waypoint_position,
+        };
+        let Some(position) = position else {
+            vis.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        vis.set_if_neq(Visibility::Inherited);
+        marker_tf.translation = position.get().extend(0.);
+
+        let ring_radius = conf.ring_radius * (1.0 + conf.pulse_amplitude * phase);
+        for &child in children {
+            let Ok((part, mut tf, material)) = part_query.get_mut(child) else { continue };
+            match part {
+                MarkerPart::Ring => {
+                    let scale = camera.scale() * ring_radius;
+                    tf.scale = (scale, scale, 1.0).into();
+                }
+                MarkerPart::Arrow => {
+                    let scale = camera.scale() * conf.arrow_size;
+                    tf.scale = (scale, scale, 1.0).into();
+                    tf.rotation = camera.rotation();
+                    let offset = camera.affine_transform(Vec2::Y)
+                        * (ring_radius + conf.arrow_size * phase * conf.pulse_amplitude);
+                    tf.translation = Zorder::QuestMarker.dist2_to_translation(Length::new(offset));
+                }
+            }
+            if let Some(material) = materials.get_mut(&material.0) {
+                material.color = conf.color.with_alpha(1.0 - 0.5 * phase);
+            }
+        }
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Color of the markers.
+    #[config(default = Color::srgb(1.0, 0.7, 0.4))]
+    color:           Color,
+    /// Radius of the pulsing ring around the highlighted target, in screen coordinates.
+    #[config(default = 24.0, min = 0.0, max = 200.0)]
+    ring_radius:     f32,
+    /// Size of the arrow above the highlighted target, in screen coordinates.
+    #[config(default = 16.0, min = 0.0, max = 100.0)]
+    arrow_size:      f32,
+    /// Relative amplitude of the pulse animation.
+    #[config(default = 0.3, min = 0.0, max = 1.0)]
+    pulse_amplitude: f32,
+    /// Duration of one pulse cycle.
+    #[config(default = Duration::from_millis(1500), min = Duration::from_millis(100), max = Duration::from_secs(10))]
+    period:          Duration,
+}
@@ -0,0 +1,79 @@
+use bevy::app::{self, App, Plugin};
+use bevy::asset::AssetServer;
+use bevy::camera::visibility::Visibility;
+use bevy::color::Color;
+use bevy::ecs::change_detection::{DetectChangesMut, Mut};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::query::Added;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::sprite::Sprite;
+use bevy::transform::components::Transform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::Position;
+use omniatc::level::rescue;
+
+use super::Zorder;
+use crate::util::billboard;
+use crate::{ConfigManager, render};
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:rescue");
+        app.add_systems(app::Update, spawn_system.in_set(render::SystemSets::Spawn));
+        app.add_systems(app::Update, move_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = HasSprite)]
+struct IsSpriteOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsSpriteOf, linked_spawn)]
+struct HasSprite(Entity);
+
+fn spawn_system(
+    mut commands: Commands,
+    conf: ReadConfig<Conf>,
+    asset_server: Res<AssetServer>,
+    vehicle_query: Query<Entity, Added<rescue::Vehicle>>,
+) {
+    let conf = conf.read();
+
+    for vehicle_entity in vehicle_query {
+        commands.entity(vehicle_entity).insert((Transform::IDENTITY, Visibility::Visible));
+        commands.spawn((
+            IsSpriteOf(vehicle_entity),
+            ChildOf(vehicle_entity),
+            Zorder::ObjectSprite.local_translation(),
+            Sprite { color: conf.color, ..Sprite::from_image(asset_server.load(SPRITE_PATH)) },
+            billboard::MaintainScale { size: conf.sprite_size },
+            billboard::MaintainRotation,
+        ));
+    }
+}
+
+fn move_system(mut vehicle_query: Query<(&rescue::Vehicle, &mut Transform)>) {
+    vehicle_query.iter_mut().for_each(|(vehicle, tf)| {
+        Mut::map_unchanged(tf, |tf| &mut tf.translation).set_if_neq(Zorder::base_translation(
+            vehicle.position.with_altitude(Position::SEA_LEVEL),
+        ));
+    });
+}
+
+const SPRITE_PATH: &str = "sprites/symbol-vehicle.png";
+
+#[derive(Config)]
+struct Conf {
+    /// Size of emergency vehicle sprites.
+    #[config(default = 0.6, min = 0.0, max = 5.0)]
+    sprite_size: f32,
+    /// Color of emergency vehicle sprites.
+    #[config(default = Color::srgb(1.0, 0.3, 0.3))]
+    color:       Color,
+}
@@ -1,9 +1,9 @@
-use bevy::asset::Assets;
+use bevy::asset::{Assets, Handle};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::hierarchy::ChildOf;
 use bevy::ecs::query::With;
-use bevy::ecs::system::{Commands, Query, Res, ResMut, SystemParam};
+use bevy::ecs::system::{Commands, Local, Query, Res, ResMut, SystemParam};
 use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
 use bevy::transform::components::Transform;
 use bevy_mod_config::{self, ReadConfig};
@@ -21,6 +21,8 @@ pub struct SpawnParam<'w, 's> {
     shapes:    Res<'w, shapes::Meshes>,
     conf:      ReadConfig<'w, 's, Conf>,
     materials: ResMut<'w, Assets<ColorMaterial>>,
+    /// Material shared by all runway strips so that they can be batched.
+    material:  Local<'s, Option<Handle<ColorMaterial>>>,
 }
 
 impl SpawnParam<'_, '_> {
@@ -32,7 +34,12 @@ impl SpawnParam<'_, '_> {
             ChildOf(runway),
             self.shapes.line(conf.strip_thickness, Zorder::RunwayStrip),
             MeshMaterial2d(
-                self.materials.add(ColorMaterial { color: conf.strip_color, ..Default::default() }),
+                self.material
+                    .get_or_insert_with(|| {
+                        self.materials
+                            .add(ColorMaterial { color: conf.strip_color, ..Default::default() })
+                    })
+                    .clone(),
             ),
         ));
     }
@@ -65,10 +72,15 @@ impl UpdateParam<'_, '_> {
         };
 
         let material = try_log_return!(
-            self.materials.get_mut(&material_handle.0),
+            self.materials.get(&material_handle.0),
             expect "asset referenced by strong handle must exist"
         );
-        material.color = conf.strip_color;
+        if material.color != conf.strip_color {
+            // avoid marking the shared material as changed every frame
+            if let Some(material) = self.materials.get_mut(&material_handle.0) {
+                material.color = conf.strip_color;
+            }
+        }
 
         shapes::set_square_line_transform_relative(
             &mut line_tf,
@@ -0,0 +1,185 @@
+use bevy::app::{self, App, Plugin};
+use bevy::asset::Assets;
+use bevy::camera::visibility::Visibility;
+use bevy::color::Color;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::math::Vec2;
+use bevy::mesh::Mesh2d;
+use bevy::sprite::{Anchor, Text2d};
+use bevy::sprite_render::{ColorMaterial, MeshMaterial2d};
+use bevy::text::TextColor;
+use bevy::transform::components::Transform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::{Length, Position};
+use omniatc::level::sector::{self, Sector};
+use omniatc::{QueryTryLog, try_log};
+
+use super::Zorder;
+use crate::util::{AnchorConf, billboard, shapes};
+use crate::{ConfigManager, render};
+
+#[cfg(test)]
+mod tests;
+
+/// Length of a dash and the gap after it along a sector boundary.
+const DASH_PERIOD: Length<f32> = Length::from_nm(2.0);
+/// Fraction of [`DASH_PERIOD`] drawn as a dash.
+const DASH_RATIO: f32 = 0.5;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:sector");
+        app.add_systems(app::Update, spawn_system.in_set(render::SystemSets::Spawn));
+        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+    }
+}
+
+#[derive(Component)]
+#[relationship(relationship_target = HasDashes)]
+struct IsDashOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsDashOf, linked_spawn)]
+struct HasDashes(Vec<Entity>);
+
+#[derive(Component)]
+#[relationship(relationship_target = HasLabel)]
+struct IsLabelOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsLabelOf, linked_spawn)]
+struct HasLabel(Entity);
+
+/// Material shared by the dashes of a sector boundary.
+#[derive(Component)]
+struct DashMaterial(MeshMaterial2d<ColorMaterial>);
+
+fn spawn_system(
+    mut commands: Commands,
+    mut spawns: MessageReader<sector::SpawnMessage>,
+    conf: ReadConfig<Conf>,
+    shapes: Res<shapes::Meshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    sector_query: Query<&Sector>,
+) {
+    let conf = conf.read();
+
+    for &sector::SpawnMessage(sector_entity) in spawns.read() {
+        let sector = sector_query.get(sector_entity).expect("sector was just spawned");
+
+        let material = MeshMaterial2d(materials.add(ColorMaterial::from_color(conf.color)));
+        commands.entity(sector_entity).insert((
+            Transform::IDENTITY,
+            Visibility::Visible,
+            DashMaterial(material.clone()),
+        ));
+
+        for [start, end] in sector.edges().flat_map(|[start, end]| dashes(start, end)) {
+            let mut tf = Zorder::SectorBoundary.local_translation();
+            shapes::set_square_line_transform(&mut tf, start, end);
+            commands.spawn((
+                IsDashOf(sector_entity),
+                ChildOf(sector_entity),
+                Mesh2d(shapes.square().clone()),
+                tf,
+                shapes::MaintainThickness(conf.thickness),
+                material.clone(),
+            ));
+        }
+
+        commands.spawn((
+            IsLabelOf(sector_entity),
+            ChildOf(sector_entity),
+            Zorder::SectorLabel.local_translation(),
+            billboard::MaintainScale { size: conf.label_size },
+            billboard::MaintainRotation,
+            billboard::Label { offset: label_position(sector) - Position::ORIGIN, distance: 0.0 },
+            Text2d::new(label_text(sector)),
+            TextColor(conf.color),
+            conf.label_anchor,
+        ));
+    }
+}
+
+fn update_system(
+    conf: ReadConfig<Conf>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    sector_query: Query<(&DashMaterial, &HasLabel)>,
+    mut dash_query: Query<&mut shapes::MaintainThickness, With<IsDashOf>>,
+    mut label_query: Query<&mut TextColor>,
+) {
+    let conf = conf.read();
+
+    for mut thickness in &mut dash_query {
+        thickness.0 = conf.thickness;
+    }
+
+    for (material, &HasLabel(label_entity)) in &sector_query {
+        let material = try_log!(
+            materials.get_mut(&material.0.0),
+            expect "asset referenced by strong handle must exist"
+            or continue
+        );
+        material.color = conf.color;
+
+        if let Some(mut color) = label_query.log_get_mut(label_entity) {
+            color.0 = conf.color;
+        }
+    }
+}
+
+/// Splits an edge into dashes of [`DASH_PERIOD`] times [`DASH_RATIO`],
+/// starting with a dash at `start`.
+fn dashes(start: Position<Vec2>, end: Position<Vec2>) -> impl Iterator<Item = [Position<Vec2>; 2]> {
+    let length = start.distance_exact(end);
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "edges are short")]
+    let count = (length / DASH_PERIOD).ceil() as u32;
+    (0..count).map(move |index| {
+        #[expect(clippy::cast_precision_loss, reason = "edges have few dashes")]
+        let dash_start = DASH_PERIOD * index as f32;
+        let dash_end = (dash_start + DASH_PERIOD * DASH_RATIO).min(length);
+        [start.lerp(end, dash_start / length), start.lerp(end, dash_end / length)]
+    })
+}
+
+/// Labels the sector at the mean of its boundary vertices.
+fn label_position(sector: &Sector) -> Position<Vec2> {
+    let sum: Vec2 = sector.boundary.iter().map(|vertex| vertex.get()).sum();
+    #[expect(clippy::cast_precision_loss, reason = "boundaries have few vertices")]
+    let count = sector.boundary.len().max(1) as f32;
+    Position::new(sum / count)
+}
+
+fn label_text(sector: &Sector) -> String {
+    let feet = |altitude: Position<f32>| altitude.amsl().into_feet();
+    match (sector.min_altitude, sector.max_altitude) {
+        (Some(min), Some(max)) => {
+            format!("{}\n{:.0}-{:.0}ft", sector.name, feet(min), feet(max))
+        }
+        (Some(min), None) => format!("{}\nabove {:.0}ft", sector.name, feet(min)),
+        (None, Some(max)) => format!("{}\nbelow {:.0}ft", sector.name, feet(max)),
+        (None, None) => sector.name.clone(),
+    }
+}
+
+#[derive(Config)]
+struct Conf {
+    /// Color of sector boundaries and labels.
+    #[config(default = Color::srgba(0.7, 0.7, 0.4, 0.5))]
+    color:        Color,
+    /// Thickness of sector boundary dashes.
+    #[config(default = 1.0, min = 0.0, max = 10.0)]
+    thickness:    f32,
+    #[config(default = 0.6, min = 0.0, max = 3.0)]
+    label_size:   f32,
+    #[config(default = Anchor::CENTER)]
+    label_anchor: AnchorConf,
+}
@@ -0,0 +1,29 @@
+use math::{Length, Position};
+
+use super::dashes;
+
+#[test]
+fn test_dashes_cover_edge() {
+    let start = Position::from_origin_nm(0.0, 0.0);
+    let end = Position::from_origin_nm(0.0, 5.0);
+    let dashes: Vec<_> = dashes(start, end).collect();
+
+    assert_eq!(dashes.len(), 3);
+    for (index, [dash_start, dash_end]) in dashes.into_iter().enumerate() {
+        #[expect(clippy::cast_precision_loss, reason = "small test indices")]
+        let expected_start = index as f32 * 2.0;
+        let expected_end = (expected_start + 1.0).min(5.0);
+        dash_start
+            .assert_near(Position::from_origin_nm(0.0, expected_start), Length::from_nm(0.001))
+            .expect("dash start");
+        dash_end
+            .assert_near(Position::from_origin_nm(0.0, expected_end), Length::from_nm(0.001))
+            .expect("dash end");
+    }
+}
+
+#[test]
+fn test_dashes_of_degenerate_edge() {
+    let point = Position::from_origin_nm(3.0, 4.0);
+    assert_eq!(dashes(point, point).count(), 0);
+}
@@ -0,0 +1,124 @@
+//! Stippled overlay of turbulence areas.
+
+use bevy::app::{self, App, Plugin};
+use bevy::asset::Assets;
+use bevy::camera::visibility::Visibility;
+use bevy::color::Color;
+use bevy::ecs::change_detection::DetectChangesMut;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Added, With};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::math::Vec2;
+use bevy::mesh::Mesh2d;
+use bevy::sprite_render::{AlphaMode2d, ColorMaterial, MeshMaterial2d};
+use bevy::transform::components::Transform;
+use bevy_mod_config::{AppExt, Config, ReadConfig};
+use math::Length;
+use omniatc::level::turbulence;
+
+use super::Zorder;
+use crate::util::{ActiveCamera2d, shapes};
+use crate::{ConfigManager, render};
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_config::<ConfigManager, Conf>("2d:turbulence");
+        app.add_systems(app::Update, spawn_system.in_set(render::SystemSets::Spawn));
+        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+    }
+}
+
+/// A dot of the stipple pattern of a turbulence area.
+#[derive(Component)]
+#[relationship(relationship_target = HasDots)]
+struct IsDotOf(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsDotOf, linked_spawn)]
+struct HasDots(Vec<Entity>);
+
+fn spawn_system(
+    conf: ReadConfig<Conf>,
+    area_query: Query<(Entity, &turbulence::Area), Added<turbulence::Area>>,
+    meshes: Res<shapes::Meshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (area_entity, area) in area_query {
+        let material = materials.add(ColorMaterial {
+            color: match area.intensity {
+                turbulence::Intensity::Light => conf.light_color,
+                turbulence::Intensity::Moderate => conf.moderate_color,
+                turbulence::Intensity::Severe => conf.severe_color,
+            },
+            alpha_mode: AlphaMode2d::Blend,
+            ..Default::default()
+        });
+
+        #[expect(clippy::cast_possible_truncation, reason = "the number of dots is small")]
+        let steps = (area.radius.0 / conf.dot_spacing.0).ceil() as i32;
+        for row in -steps..=steps {
+            // Alternate rows are staggered by half the spacing for a stippled pattern.
+            let stagger = if row % 2 == 0 { 0.0 } else { 0.5 };
+            for column in -steps..=steps {
+                #[expect(clippy::cast_precision_loss, reason = "the number of dots is small")]
+                let offset = conf.dot_spacing * Vec2::new(column as f32 + stagger, row as f32);
+                if offset.magnitude_cmp() > area.radius {
+                    continue;
+                }
+
+                commands.spawn((
+                    IsDotOf(area_entity),
+                    Mesh2d(meshes.circle().clone()),
+                    MeshMaterial2d(material.clone()),
+                    Transform::from_translation(
+                        Zorder::TurbulenceOverlay.pos2_to_translation(area.center + offset),
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn update_system(
+    conf: ReadConfig<Conf>,
+    camera: ActiveCamera2d,
+    dot_query: Query<(&mut Transform, &mut Visibility), With<IsDotOf>>,
+) {
+    let conf = conf.read();
+
+    let scale = camera.scale() * conf.dot_radius;
+    for (mut tf, mut vis) in dot_query {
+        vis.set_if_neq(if conf.display { Visibility::Inherited } else { Visibility::Hidden });
+        tf.scale = (scale, scale, 1.0).into();
+    }
+}
+
+#[derive(Config)]
+#[config(expose(read))]
+struct Conf {
+    /// Display turbulence areas.
+    #[config(default = true)]
+    display:        bool,
+    /// Distance between adjacent dots of the stipple pattern.
+    #[config(default = Length::from_nm(0.5), min = Length::from_nm(0.1), max = Length::from_nm(5.0))]
+    dot_spacing:    Length<f32>,
+    /// Radius of each dot in screen pixels.
+    #[config(default = 1.5, min = 0.5, max = 10.0)]
+    dot_radius:     f32,
+    /// Color of dots in light turbulence areas.
+    #[config(default = Color::srgba(0.8, 0.8, 0.3, 0.3))]
+    light_color:    Color,
+    /// Color of dots in moderate turbulence areas.
+    #[config(default = Color::srgba(0.9, 0.6, 0.2, 0.5))]
+    moderate_color: Color,
+    /// Color of dots in severe turbulence areas.
+    #[config(default = Color::srgba(0.9, 0.2, 0.2, 0.6))]
+    severe_color:   Color,
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::mem;
 
 use bevy::app::{self, App, Plugin};
@@ -10,7 +11,9 @@ use bevy::ecs::query::{With, Without};
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Local, Query, Res, ResMut, SystemParam};
 use bevy::math::Vec2;
+use bevy::mesh::Mesh2d;
 use bevy::sprite_render::{AlphaMode2d, ColorMaterial, MeshMaterial2d};
+use bevy::transform::components::Transform;
 use bevy_mod_config::{self, AppExt, Config, ReadConfig};
 use itertools::Itertools;
 use math::Length;
@@ -28,8 +31,14 @@ impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.init_config::<ConfigManager, Conf>("2d:wake");
 
-        app.add_systems(app::Update, spawn_system.in_set(render::SystemSets::Spawn));
-        app.add_systems(app::Update, update_system.in_set(render::SystemSets::Update));
+        app.add_systems(
+            app::Update,
+            (spawn_system, spawn_ribbon_system).in_set(render::SystemSets::Spawn),
+        );
+        app.add_systems(
+            app::Update,
+            (update_system, update_ribbon_system).in_set(render::SystemSets::Update),
+        );
     }
 }
 
@@ -41,6 +50,22 @@ struct IsSpriteOf(Entity);
 #[relationship_target(relationship = IsSpriteOf, linked_spawn)]
 struct HasSprite(SmallVec<[Entity; 4]>);
 
+/// A segment of a wake corridor ribbon,
+/// spanning from a vortex to the next vortex spawned by the same source.
+///
+/// The ribbon is despawned together with the older vortex.
+#[derive(Component)]
+#[relationship(relationship_target = HasRibbon)]
+struct IsRibbonFrom(Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = IsRibbonFrom, linked_spawn)]
+struct HasRibbon(SmallVec<[Entity; 1]>);
+
+/// The newer vortex that a ribbon segment extends to.
+#[derive(Component)]
+struct RibbonTo(Entity);
+
 fn spawn_system(
     mut spawns: MessageReader<wake::SpawnMessage>,
     conf: ReadConfig<Conf>,
@@ -109,6 +134,54 @@ fn spawn_vortex(vortex_entity: Entity, vortex: &wake::Vortex, params: &mut Spawn
     }
 }
 
+fn spawn_ribbon_system(
+    mut spawns: MessageReader<wake::SpawnMessage>,
+    conf: ReadConfig<Conf>,
+    mut latest_vortex: Local<HashMap<Entity, Entity>>,
+    vortex_query: Query<&wake::Vortex>,
+    ribbon_query: Query<Entity, With<IsRibbonFrom>>,
+    mut commands: Commands,
+    meshes: Res<shapes::Meshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let conf = conf.read();
+
+    if !conf.display_ribbons {
+        for ribbon in ribbon_query {
+            commands.entity(ribbon).despawn();
+        }
+        latest_vortex.clear();
+        spawns.clear();
+        return;
+    }
+
+    latest_vortex.retain(|_, &mut vortex| vortex_query.contains(vortex));
+
+    for &wake::SpawnMessage(vortex_entity) in spawns.read() {
+        let Some(vortex) = vortex_query.log_get(vortex_entity) else { continue };
+        let Some(prev_vortex) = latest_vortex.insert(vortex.source, vortex_entity) else {
+            continue;
+        };
+
+        #[expect(clippy::cast_precision_loss, reason = "acceptable precision loss")]
+        if (vortex.intensity.0 as f32) < conf.ribbon_min_intensity {
+            continue;
+        }
+
+        commands.spawn((
+            IsRibbonFrom(prev_vortex),
+            RibbonTo(vortex_entity),
+            Mesh2d(meshes.square().clone()),
+            Zorder::WakeRibbon.local_translation(),
+            MeshMaterial2d(materials.add(ColorMaterial {
+                color: conf.ribbon_color_for_intensity(vortex.intensity),
+                alpha_mode: AlphaMode2d::Blend,
+                ..Default::default()
+            })),
+        ));
+    }
+}
+
 fn update_system(
     conf: ReadConfig<Conf>,
     vortex_query: Query<&wake::Vortex>,
@@ -128,6 +201,46 @@ fn update_system(
     }
 }
 
+fn update_ribbon_system(
+    conf: ReadConfig<Conf>,
+    vortex_query: Query<&wake::Vortex>,
+    ribbon_query: Query<(
+        Entity,
+        &IsRibbonFrom,
+        &RibbonTo,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (ribbon, &IsRibbonFrom(from), &RibbonTo(to), mut tf, MeshMaterial2d(handle)) in ribbon_query
+    {
+        let Some(from) = vortex_query.log_get(from) else { continue };
+        let Ok(to) = vortex_query.get(to) else {
+            // the newer vortex may dissipate first if it was spawned at a higher airspeed
+            commands.entity(ribbon).despawn();
+            continue;
+        };
+
+        shapes::set_square_line_transform(
+            &mut tf,
+            from.position.horizontal(),
+            to.position.horizontal(),
+        );
+        tf.scale.x = conf.ribbon_width.0;
+
+        let material = try_log!(
+            materials.get_mut(handle),
+            expect "material referenced by strong handle must exist"
+            or continue
+        );
+        material.color = conf.ribbon_color_for_intensity(from.intensity.min(to.intensity));
+    }
+}
+
 #[derive(Config)]
 #[config(expose(read))]
 struct Conf {
@@ -142,14 +255,39 @@ struct Conf {
     /// Intensity of vortex overlay squares to reach full opacity.
     #[config(default = 300e3)]
     square_opaque_intensity: f32,
+    /// Display wake corridor ribbons behind heavy objects.
+    #[config(default = true)]
+    display_ribbons:         bool,
+    /// Width of wake corridor ribbons.
+    #[config(default = Length::from_nm(0.25), min = Length::ZERO, max = Length::from_nm(2.0))]
+    ribbon_width:            Length<f32>,
+    /// Color of wake corridor ribbons at full opacity.
+    #[config(default = Color::srgba(0.6, 0.4, 0.9, 0.4))]
+    ribbon_color:            Color,
+    /// Minimum intensity of a vortex at spawn time to display its ribbon.
+    ///
+    /// Light objects produce vortices below this intensity and are not displayed.
+    #[config(default = 150e3)]
+    ribbon_min_intensity:    f32,
+    /// Intensity of wake corridor ribbons to reach full opacity.
+    #[config(default = 300e3)]
+    ribbon_opaque_intensity: f32,
 }
 
 impl ConfRead<'_> {
     fn color_for_intensity(&self, intensity: wake::Intensity) -> Color {
-        #[expect(clippy::cast_precision_loss, reason = "acceptable precision loss")]
-        let opacity = (intensity.0 as f32) / self.square_opaque_intensity;
-        let mut out = self.square_color;
-        out.set_alpha(out.alpha() * opacity.clamp(0.0, 1.0));
-        out
+        fade_color(self.square_color, intensity, self.square_opaque_intensity)
+    }
+
+    fn ribbon_color_for_intensity(&self, intensity: wake::Intensity) -> Color {
+        fade_color(self.ribbon_color, intensity, self.ribbon_opaque_intensity)
     }
 }
+
+fn fade_color(color: Color, intensity: wake::Intensity, opaque_intensity: f32) -> Color {
+    #[expect(clippy::cast_precision_loss, reason = "acceptable precision loss")]
+    let opacity = (intensity.0 as f32) / opaque_intensity;
+    let mut out = color;
+    out.set_alpha(out.alpha() * opacity.clamp(0.0, 1.0));
+    out
+}
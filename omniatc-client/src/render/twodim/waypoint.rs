@@ -14,7 +14,7 @@ use bevy_mod_config::{AppExt, Config, ReadConfig};
 use math::Length;
 use omniatc::level::waypoint::{self, Waypoint};
 
-use super::Zorder;
+use super::{Zorder, cull};
 use crate::util::{AnchorConf, billboard};
 use crate::{ConfigManager, render};
 
@@ -85,11 +85,18 @@ fn spawn_system(
     }
 }
 
-fn move_system(mut waypoint_query: Query<(&Waypoint, &mut Transform, &mut Visibility)>) {
+fn move_system(
+    viewport: Res<cull::Viewport>,
+    mut waypoint_query: Query<(&Waypoint, &mut Transform, &mut Visibility)>,
+) {
     waypoint_query.iter_mut().for_each(|(waypoint, tf, mut vis)| {
-        Mut::map_unchanged(tf, |tf| &mut tf.translation)
-            .set_if_neq(Zorder::base_translation(waypoint.position));
-        vis.set_if_neq(if waypoint.hidden { Visibility::Hidden } else { Visibility::Visible });
+        // Off-screen waypoints are hidden so that their labels are not laid out.
+        let visible = !waypoint.hidden && viewport.contains(waypoint.position.horizontal());
+        vis.set_if_neq(if visible { Visibility::Visible } else { Visibility::Hidden });
+        if visible {
+            Mut::map_unchanged(tf, |tf| &mut tf.translation)
+                .set_if_neq(Zorder::base_translation(waypoint.position));
+        }
     });
 }
 
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 use std::future::Future;
+use std::time::Duration;
 
 use bevy::app::{self, App, Plugin};
 use bevy::asset::AssetApp;
@@ -13,7 +14,16 @@ use omniatc::load;
 use omniatc::util::{AsyncManager, AsyncResult, run_async_local};
 use serde::{Deserialize, Serialize};
 
+pub(crate) mod achievement;
+mod bug_report;
+pub(crate) mod config_profile;
+#[cfg(not(target_family = "wasm"))]
+mod emergency;
+pub(crate) mod library;
 pub(crate) mod scenario_loader;
+#[cfg(feature = "cloud-sync")]
+pub(crate) mod sync;
+pub(crate) mod theme;
 
 #[cfg(target_family = "wasm")]
 mod web;
@@ -24,31 +34,65 @@ pub type StorageImpl = web::Impl;
 mod fs;
 #[cfg(not(target_family = "wasm"))]
 pub type StorageImpl = fs::Impl;
+#[cfg(not(target_family = "wasm"))]
+pub use emergency::install_panic_hook;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ScenarioMeta {
-    id:      String,
-    title:   String,
-    created: Timestamp,
+    pub id:      String,
+    pub title:   String,
+    pub created: Timestamp,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LevelMeta {
-    id:       String,
-    title:    String,
-    created:  Timestamp,
-    modified: Timestamp,
+    pub id:       String,
+    pub title:    String,
+    pub created:  Timestamp,
+    pub modified: Timestamp,
+}
+
+/// Metadata of a named save slot.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SaveSlotMeta {
+    /// Storage key of the slot, derived from its name.
+    pub id:        String,
+    /// Name of the slot as entered by the user.
+    pub name:      String,
+    /// Time at which the slot was last written.
+    pub saved:     Timestamp,
+    /// Title of the saved map.
+    pub map_title: String,
+    /// Simulated time elapsed in the session when the slot was written.
+    pub sim_time:  Duration,
+    /// Score when the slot was written.
+    pub score:     store::Score,
+    /// Positions of the objects when the slot was written,
+    /// scaled into a square of [`THUMBNAIL_SIZE`] units.
+    pub thumbnail: Vec<[u8; 2]>,
+}
+
+/// Side length of the square into which [`SaveSlotMeta::thumbnail`] positions are scaled.
+pub const THUMBNAIL_SIZE: u8 = u8::MAX;
+
+impl SaveSlotMeta {
+    /// Derives the storage key of a slot from its name.
+    #[must_use]
+    pub fn id_for_name(name: &str) -> String {
+        name.trim()
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() { ch.to_ascii_lowercase() } else { '-' })
+            .collect()
+    }
 }
 
 pub trait Storage: Default + 'static {
     type Error: fmt::Debug + Send + Sync + 'static;
 
-    #[expect(dead_code, reason = "TODO implement save management")]
     fn list_scenarios_by_tag(
         &self,
         tag_key: String,
     ) -> impl Future<Output = anyhow::Result<Vec<ScenarioMeta>>> + 'static;
-    #[expect(dead_code, reason = "TODO implement save management")]
     fn load_scenario(
         &self,
         key: String,
@@ -68,6 +112,68 @@ pub trait Storage: Default + 'static {
         &self,
         key: String,
     ) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + 'static;
+    fn insert_level(
+        &self,
+        meta: LevelMeta,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + 'static;
+
+    /// Lists the save slots, most recently written first.
+    fn list_save_slots(&self) -> impl Future<Output = anyhow::Result<Vec<SaveSlotMeta>>> + 'static;
+    fn load_save_slot(
+        &self,
+        id: String,
+    ) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + 'static;
+    /// Writes a save slot, replacing any slot with the same ID.
+    fn write_save_slot(
+        &self,
+        meta: SaveSlotMeta,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + 'static;
+    fn delete_save_slot(
+        &self,
+        id: String,
+    ) -> impl Future<Output = Result<(), Self::Error>> + 'static;
+
+    fn list_config_profiles(&self) -> impl Future<Output = anyhow::Result<Vec<String>>> + 'static;
+    fn load_config_profile(
+        &self,
+        name: String,
+    ) -> impl Future<Output = Result<Option<String>, Self::Error>> + 'static;
+    fn save_config_profile(
+        &self,
+        name: String,
+        data: String,
+    ) -> impl Future<Output = Result<(), Self::Error>> + 'static;
+
+    /// Loads the serialized global achievement progress.
+    ///
+    /// Resolves to `None` if no progress has been saved yet.
+    fn load_achievements(
+        &self,
+    ) -> impl Future<Output = Result<Option<String>, Self::Error>> + 'static;
+    /// Replaces the serialized global achievement progress.
+    fn save_achievements(
+        &self,
+        data: String,
+    ) -> impl Future<Output = Result<(), Self::Error>> + 'static;
+
+    /// Reads a file chosen by the user for import.
+    ///
+    /// `path` is only used on platforms without a native file picker.
+    /// Resolves to `None` if the user cancels the selection.
+    fn pick_import_file(
+        &self,
+        path: String,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<u8>>>> + 'static;
+    /// Hands over exported data to the user under the given file name.
+    ///
+    /// Resolves to a description of where the file was delivered.
+    fn export_file(
+        &self,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<String>> + 'static;
 }
 
 pub struct Plug<S> {
@@ -84,17 +190,50 @@ impl<S: Storage> Plugin for Plug<S> {
         app.insert_non_send_resource((self.new_storage)());
         app.init_resource::<scenario_loader::CurrentImportingScenarios>();
         app.init_resource::<scenario_loader::CurrentLoadOnImport>();
+        app.init_resource::<config_profile::Profiles>();
+        app.init_resource::<config_profile::Overlay>();
+        app.init_resource::<config_profile::ThemeLayer>();
+        app.init_resource::<theme::Active>();
+        app.init_resource::<library::Library>();
         app.init_asset::<scenario_loader::ScenarioAsset>();
         app.init_asset_loader::<scenario_loader::ScenarioAssetLoader>();
+        app.init_asset::<theme::Theme>();
+        app.init_asset_loader::<theme::ThemeLoader>();
         app.insert_resource(self.startup_level_options.clone());
         app.add_systems(app::Startup, scenario_loader::import_builtin_scenarios_system);
         app.add_systems(app::Startup, load_startup_level_system::<S>);
+        app.add_systems(app::Startup, theme::startup_system);
         app.add_systems(app::Update, scenario_loader::handle_loaded_scenario_system::<S>);
         app.add_systems(
             app::Update,
             scenario_loader::warn_failed_default_scenario_system
                 .after(scenario_loader::handle_loaded_scenario_system::<S>),
         );
+        app.add_systems(
+            app::Update,
+            (
+                config_profile::handle_requests_system::<S>,
+                theme::apply_system,
+                config_profile::apply_display_settings_system,
+            )
+                .chain(),
+        );
+        app.add_systems(app::Update, library::handle_requests_system::<S>);
+        app.add_systems(app::Startup, achievement::load_system::<S>);
+        app.add_systems(app::Update, achievement::save_system::<S>);
+        #[cfg(not(target_family = "wasm"))]
+        app.add_systems(app::Update, emergency::snapshot_system);
+
+        #[cfg(feature = "cloud-sync")]
+        {
+            bevy_mod_config::AppExt::init_config::<crate::ConfigManager, sync::Conf>(app, "sync");
+            app.init_resource::<sync::LocalTimes>();
+            app.init_non_send_resource::<sync::ActiveBackend>();
+            app.add_systems(
+                app::Startup,
+                sync::startup_system::<S>.after(achievement::load_system::<S>),
+            );
+        }
     }
 }
 
@@ -104,6 +243,9 @@ pub struct StartupLevelOptions {
     pub default_scenario: String,
 }
 
+/// Loads the level requested on the command line,
+/// or resumes the most recently written save slot,
+/// or the most recently modified level if there are no save slots.
 fn load_startup_level_system<S: Storage>(
     mut commands: Commands,
     mut poll_list: ResMut<AsyncManager>,
@@ -113,33 +255,43 @@ fn load_startup_level_system<S: Storage>(
     let default_scenario = startup_options.default_scenario.clone();
     run_async_local({
         let open_level_id = startup_options.open_level_id.clone();
-        let fut = storage.list_levels_by_time(1);
+        let save_slots = storage.list_save_slots();
+        let levels = storage.list_levels_by_time(1);
 
         async move {
             if let Some(open_level_id) = open_level_id {
-                Some(open_level_id)
-            } else {
-                match fut.await {
-                    Ok(list) => list.into_iter().next().map(|level| level.id),
-                    Err(err) => {
-                        bevy::log::error!("Cannot locate last available level: {err:?}");
-                        None
+                return Some((library::Kind::Level, open_level_id));
+            }
+
+            match save_slots.await {
+                Ok(list) => {
+                    if let Some(slot) = list.into_iter().next() {
+                        return Some((library::Kind::SaveSlot, slot.id));
                     }
                 }
+                Err(err) => bevy::log::error!("Cannot list save slots: {err:?}"),
+            }
+
+            match levels.await {
+                Ok(list) => list.into_iter().next().map(|level| (library::Kind::Level, level.id)),
+                Err(err) => {
+                    bevy::log::error!("Cannot locate last available level: {err:?}");
+                    None
+                }
             }
         }
     })
     .then(
         &mut commands,
         &mut poll_list,
-        move |mut ret: AsyncResult<Option<String>>,
+        move |mut ret: AsyncResult<Option<(library::Kind, String)>>,
               storage: NonSend<S>,
               mut current_load_on_import: ResMut<scenario_loader::CurrentLoadOnImport>,
               mut poll_list: ResMut<AsyncManager>,
               mut commands: Commands| {
-            if let Some(key) = ret.get() {
+            if let Some((kind, key)) = ret.get() {
                 let default_scenario = default_scenario.clone();
-                run_async_local(storage.load_level(key)).then(
+                run_async_local(library::read(&*storage, kind, key)).then(
                     &mut commands,
                     &mut poll_list,
                     move |mut ret: AsyncResult<Result<Vec<u8>, S::Error>>,
@@ -149,7 +301,7 @@ fn load_startup_level_system<S: Storage>(
                     >| match ret.get() {
                         Ok(data) => {
                             let source = load::Source::Raw(Cow::Owned(data));
-                            commands.queue(load::Command {
+                            commands.queue(load::StagedCommand {
                                 source,
                                 on_error: Box::new(|_world, err| {
                                     bevy::log::error!("Error loading level: {err:?}");
@@ -0,0 +1,82 @@
+//! Persistence of the global achievement progress.
+
+use std::time::Duration;
+
+use bevy::ecs::system::{Local, ResMut};
+use bevy::ecs::world::{Mut, World};
+use bevy::time::{self, Time};
+use omniatc::util::{AsyncManager, AsyncResult, run_async_local};
+
+use super::Storage;
+use crate::achievement::{Achievements, Progress};
+
+/// Minimum real time between two saves of the achievement progress.
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Loads the persisted achievement progress on startup.
+pub(super) fn load_system<S: Storage>(world: &mut World) {
+    let fut = world.non_send_resource::<S>().load_achievements();
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            |mut ret: AsyncResult<Result<Option<String>, S::Error>>,
+             mut achievements: ResMut<Achievements>| {
+                let progress = match ret.get() {
+                    Ok(Some(data)) => serde_json::from_str(&data).unwrap_or_else(|err| {
+                        bevy::log::error!("Achievement progress is corrupted: {err}");
+                        Progress::default()
+                    }),
+                    Ok(None) => Progress::default(),
+                    Err(err) => {
+                        bevy::log::error!("Cannot load achievement progress: {err:?}");
+                        Progress::default()
+                    }
+                };
+                achievements.progress = progress;
+                achievements.loaded = true;
+            },
+        );
+    });
+}
+
+/// Saves the achievement progress when it has changed,
+/// at most once every [`SAVE_INTERVAL`].
+pub(super) fn save_system<S: Storage>(world: &mut World, mut last_save: Local<Option<Duration>>) {
+    let now = world.resource::<Time<time::Real>>().elapsed();
+    if last_save.is_some_and(|last_save| now.saturating_sub(last_save) < SAVE_INTERVAL) {
+        return;
+    }
+
+    let mut achievements = world.resource_mut::<Achievements>();
+    if !achievements.unsaved {
+        return;
+    }
+    achievements.unsaved = false;
+    *last_save = Some(now);
+
+    let data = match serde_json::to_string(&achievements.progress) {
+        Ok(data) => data,
+        Err(err) => {
+            bevy::log::error!("Cannot serialize achievement progress: {err:?}");
+            return;
+        }
+    };
+    #[cfg(feature = "cloud-sync")]
+    world
+        .resource_mut::<super::sync::LocalTimes>()
+        .mark_modified(super::sync::Collection::Progress, super::sync::PROGRESS_KEY);
+
+    let fut = world.non_send_resource::<S>().save_achievements(data);
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            |mut ret: AsyncResult<Result<(), S::Error>>| {
+                if let Err(err) = ret.get() {
+                    bevy::log::error!("Cannot save achievement progress: {err:?}");
+                }
+            },
+        );
+    });
+}
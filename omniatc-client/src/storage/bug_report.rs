@@ -0,0 +1,276 @@
+//! Bundles diagnostic information into a zip archive to attach to an issue report.
+//!
+//! The bundle contains:
+//! - `level.osav`: the live level, as captured for save slots
+//! - `messages.txt`: the message scrollback of the current level
+//! - `config.json`: the config values without map recommendations
+//! - `version.json`: the build and platform information
+//!
+//! Callsigns can be replaced with placeholders before the bundle is written.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use bevy::ecs::world::World;
+use itertools::Itertools;
+use omniatc::level::{message, object, phraseology};
+
+use super::{config_profile, library};
+use crate::render::messages;
+
+#[cfg(test)]
+mod tests;
+
+/// A message in the scrollback, as its creation time, sender and content.
+type Message = (Duration, String, String);
+
+/// Builds the bug report bundle of the current level.
+pub(super) fn build(world: &mut World, anonymize: bool) -> anyhow::Result<Vec<u8>> {
+    let file = library::capture_file(world)?;
+    let messages: Vec<Message> = world
+        .get_resource::<messages::History>()
+        .map(|history| {
+            history
+                .iter()
+                .map(|entry| {
+                    (entry.created(), entry.sender().to_owned(), entry.content().to_owned())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let config = serde_json::to_vec_pretty(&config_profile::base_layer(world))
+        .context("serialize config")?;
+
+    let anonymizer = anonymize.then(|| {
+        let callsigns = live_callsigns(world);
+        Anonymizer::for_level(&file, &messages, callsigns.iter().map(String::as_str))
+    });
+    bundle(file, messages, &config, anonymizer.as_ref())
+}
+
+/// Returns the names of the live objects and all forms of their radio telephony callsigns.
+fn live_callsigns(world: &mut World) -> Vec<String> {
+    let mut query = world.query::<(&object::Display, Option<&message::Telephony>)>();
+    query
+        .iter(world)
+        .flat_map(|(display, telephony)| {
+            let spoken = telephony.into_iter().flat_map(|telephony| {
+                [
+                    telephony.0.clone(),
+                    phraseology::render_callsign(&telephony.0, phraseology::Style::Icao),
+                    phraseology::render_callsign(&telephony.0, phraseology::Style::Faa),
+                ]
+            });
+            [display.name.clone()].into_iter().chain(spoken)
+        })
+        .collect()
+}
+
+/// Writes the bundle, replacing callsigns everywhere in it if `anonymizer` is given.
+fn bundle(
+    mut file: store::File,
+    mut messages: Vec<Message>,
+    config: &[u8],
+    anonymizer: Option<&Anonymizer>,
+) -> anyhow::Result<Vec<u8>> {
+    if let Some(anonymizer) = anonymizer {
+        file = anonymizer.apply_file(&file)?;
+        for (_, sender, content) in &mut messages {
+            *sender = anonymizer.apply(sender);
+            *content = anonymizer.apply(content);
+        }
+    }
+
+    let level = file.to_osav().context("serialize level")?;
+    let messages = messages
+        .iter()
+        .map(|(created, sender, content)| {
+            let secs = created.as_secs();
+            format!(
+                "{}:{:02}:{:02} {sender}: {content}\n",
+                secs / 3600,
+                (secs / 60) % 60,
+                secs % 60
+            )
+        })
+        .join("");
+    let version = serde_json::to_vec_pretty(&serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "map": file.meta.title,
+        "anonymized": anonymizer.is_some(),
+    }))
+    .context("serialize version info")?;
+
+    write_zip(&[
+        ("level.osav", &level),
+        ("messages.txt", messages.as_bytes()),
+        ("config.json", config),
+        ("version.json", &version),
+    ])
+}
+
+/// Returns the callsigns stored in `file`,
+/// i.e. object names and IDs and the callsigns of timetabled flights.
+fn file_callsigns(file: &store::File) -> impl Iterator<Item = &str> {
+    let objects = file.objects.iter().flat_map(|object| {
+        let (name, id) = match object {
+            store::Object::Plane(plane) => (&plane.aircraft.name, &plane.aircraft.id),
+            store::Object::Drifter(drifter) => (&drifter.name, &drifter.id),
+        };
+        [Some(name.as_str()), id.as_ref().map(|id| id.0.as_str())].into_iter().flatten()
+    });
+    let flights = match &file.level.spawn_trigger {
+        store::SpawnTrigger::Timetable(timetable) => timetable.flights.as_slice(),
+        _ => &[],
+    };
+    let flights = flights.iter().flat_map(|flight| {
+        [Some(flight.callsign.as_str()), flight.telephony.as_deref()].into_iter().flatten()
+    });
+    objects.chain(flights)
+}
+
+/// Replaces callsigns with numbered placeholders.
+struct Anonymizer {
+    /// Pairs of callsigns and their placeholders, longest callsign first.
+    replacements: Vec<(String, String)>,
+}
+
+impl Anonymizer {
+    /// Collects the callsigns in `file` and the message senders in addition to `callsigns`.
+    fn for_level<'a>(
+        file: &'a store::File,
+        messages: &'a [Message],
+        callsigns: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self::new(
+            callsigns
+                .into_iter()
+                .chain(file_callsigns(file))
+                .chain(messages.iter().map(|(_, sender, _)| sender.as_str())),
+        )
+    }
+
+    /// Assigns placeholders to callsigns in order of first appearance.
+    fn new<'a>(callsigns: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut replacements: Vec<(String, String)> = callsigns
+            .into_iter()
+            .filter(|callsign| !callsign.is_empty())
+            .unique()
+            .enumerate()
+            .map(|(index, callsign)| (callsign.to_owned(), format!("ACFT{:03}", index + 1)))
+            .collect();
+        replacements.sort_by_key(|(callsign, _)| std::cmp::Reverse(callsign.len()));
+        Self { replacements }
+    }
+
+    /// Replaces all callsigns in `text` in a single pass,
+    /// so that placeholders are never replaced again.
+    fn apply(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(ch) = rest.chars().next() {
+            if let Some((callsign, placeholder)) =
+                self.replacements.iter().find(|(callsign, _)| rest.starts_with(callsign.as_str()))
+            {
+                output.push_str(placeholder);
+                rest = &rest[callsign.len()..];
+            } else {
+                output.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+        output
+    }
+
+    /// Replaces callsigns in every string of `file`,
+    /// including notes, quests and object references.
+    fn apply_file(&self, file: &store::File) -> anyhow::Result<store::File> {
+        let mut value = ciborium::Value::serialized(file).context("serialize level")?;
+        self.apply_value(&mut value);
+        value.deserialized().context("deserialize anonymized level")
+    }
+
+    fn apply_value(&self, value: &mut ciborium::Value) {
+        match value {
+            ciborium::Value::Text(text) => *text = self.apply(text),
+            ciborium::Value::Array(items) => {
+                for item in items {
+                    self.apply_value(item);
+                }
+            }
+            ciborium::Value::Map(entries) => {
+                for (key, value) in entries {
+                    self.apply_value(key);
+                    self.apply_value(value);
+                }
+            }
+            ciborium::Value::Tag(_, value) => self.apply_value(value),
+            _ => {}
+        }
+    }
+}
+
+/// DOS date of 1980-01-01, the earliest representable date.
+const ZIP_EPOCH_DATE: u16 = (1 << 5) | 1;
+
+/// Writes `entries` into an uncompressed zip archive.
+///
+/// The files are stored without compression since `.osav` files are already compressed.
+fn write_zip(entries: &[(&str, &[u8])]) -> anyhow::Result<Vec<u8>> {
+    fn put_u16(buf: &mut Vec<u8>, value: u16) { buf.extend_from_slice(&value.to_le_bytes()); }
+    fn put_u32(buf: &mut Vec<u8>, value: u32) { buf.extend_from_slice(&value.to_le_bytes()); }
+
+    /// Writes the fields shared by local and central headers, from "version needed" onwards.
+    fn put_common(buf: &mut Vec<u8>, crc: u32, size: u32, name: &str) -> anyhow::Result<()> {
+        put_u16(buf, 20); // version needed to extract
+        put_u16(buf, 1 << 11); // flags: file names are UTF-8
+        put_u16(buf, 0); // compression method: stored
+        put_u16(buf, 0); // modification time
+        put_u16(buf, ZIP_EPOCH_DATE); // modification date
+        put_u32(buf, crc);
+        put_u32(buf, size); // compressed size
+        put_u32(buf, size); // uncompressed size
+        put_u16(buf, u16::try_from(name.len()).context("file name too long")?);
+        put_u16(buf, 0); // extra field length
+        Ok(())
+    }
+
+    let mut archive = Vec::new();
+    let mut central = Vec::new();
+    for &(name, data) in entries {
+        let offset = u32::try_from(archive.len()).context("archive too large")?;
+        let size = u32::try_from(data.len()).context("file too large")?;
+        let crc = crc32fast::hash(data);
+
+        put_u32(&mut archive, 0x0403_4b50);
+        put_common(&mut archive, crc, size, name)?;
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(data);
+
+        put_u32(&mut central, 0x0201_4b50);
+        put_u16(&mut central, 20); // version made by
+        put_common(&mut central, crc, size, name)?;
+        put_u16(&mut central, 0); // file comment length
+        put_u16(&mut central, 0); // disk number start
+        put_u16(&mut central, 0); // internal file attributes
+        put_u32(&mut central, 0); // external file attributes
+        put_u32(&mut central, offset);
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = u32::try_from(archive.len()).context("archive too large")?;
+    let central_size = u32::try_from(central.len()).context("archive too large")?;
+    let count = u16::try_from(entries.len()).context("too many files")?;
+    archive.extend_from_slice(&central);
+    put_u32(&mut archive, 0x0605_4b50);
+    put_u16(&mut archive, 0); // number of this disk
+    put_u16(&mut archive, 0); // disk with the central directory
+    put_u16(&mut archive, count);
+    put_u16(&mut archive, count);
+    put_u32(&mut archive, central_size);
+    put_u32(&mut archive, central_offset);
+    put_u16(&mut archive, 0); // comment length
+    Ok(archive)
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use super::{Anonymizer, bundle, file_callsigns, write_zip};
+
+#[test]
+fn test_anonymize_callsigns() {
+    let anonymizer = Anonymizer::new(["ABC12", "ABC123", "ABC12", "XYZ9"]);
+    assert_eq!(
+        anonymizer.apply("ABC123, descend to 5000, then ABC12"),
+        "ACFT002, descend to 5000, then ACFT001"
+    );
+    assert_eq!(anonymizer.apply("XYZ9 ACFT001"), "ACFT003 ACFT001");
+}
+
+#[test]
+fn test_write_zip_layout() {
+    let archive = write_zip(&[("a.txt", b"hello"), ("b.txt", b"")]).unwrap();
+
+    assert_eq!(archive[..4], 0x0403_4b50_u32.to_le_bytes());
+    // CRC-32 of the first file.
+    assert_eq!(archive[14..18], 0x3610_a686_u32.to_le_bytes());
+    // Name and contents of the first file follow its 30-byte header.
+    assert_eq!(&archive[30..40], b"a.txthello");
+
+    let eocd = &archive[archive.len() - 22..];
+    assert_eq!(eocd[..4], 0x0605_4b50_u32.to_le_bytes());
+    assert_eq!(eocd[10..12], 2_u16.to_le_bytes());
+    let central_offset = u32::from_le_bytes(eocd[16..20].try_into().unwrap()) as usize;
+    assert_eq!(archive[central_offset..central_offset + 4], 0x0201_4b50_u32.to_le_bytes());
+}
+
+/// Returns the names and contents of the files in an archive written by [`write_zip`].
+fn read_zip(archive: &[u8]) -> Vec<(&str, &[u8])> {
+    let mut entries = Vec::new();
+    let mut rest = archive;
+    while rest[..4] == 0x0403_4b50_u32.to_le_bytes() {
+        let size = u32::from_le_bytes(rest[18..22].try_into().unwrap()) as usize;
+        let name_len = usize::from(u16::from_le_bytes(rest[26..28].try_into().unwrap()));
+        let name = std::str::from_utf8(&rest[30..30 + name_len]).unwrap();
+        entries.push((name, &rest[30 + name_len..30 + name_len + size]));
+        rest = &rest[30 + name_len + size..];
+    }
+    entries
+}
+
+#[test]
+fn test_bundle_anonymizes_every_callsign() {
+    let mut file = omniatc_maps::timetable::file();
+    file.objects = omniatc_maps::demo::file().objects;
+    let callsigns: Vec<String> = file_callsigns(&file).map(str::to_owned).collect();
+    let [first, other, ..] = &callsigns[..] else { panic!("demo map has multiple objects") };
+
+    let plane = file
+        .objects
+        .iter_mut()
+        .find_map(|object| match object {
+            store::Object::Plane(plane) => Some(plane),
+            store::Object::Drifter(_) => None,
+        })
+        .expect("demo map has planes");
+    plane.aircraft.notes = store::Notes {
+        scratchpad: format!("follow {other}"),
+        reminders:  vec![store::Reminder {
+            text:      format!("handover {other}"),
+            remaining: Duration::from_mins(1),
+        }],
+    };
+    let messages = vec![(Duration::ZERO, first.clone(), format!("{other}, traffic"))];
+
+    let anonymizer = Anonymizer::for_level(&file, &messages, []);
+    let archive = bundle(file, messages, b"{}", Some(&anonymizer)).unwrap();
+
+    let entries = read_zip(&archive);
+    assert_eq!(entries.len(), 4);
+    for (name, data) in entries {
+        let data = if name == "level.osav" {
+            let file = store::File::from_osav(data).unwrap();
+            let mut bytes = Vec::new();
+            ciborium::into_writer(&file, &mut bytes).unwrap();
+            bytes
+        } else {
+            data.to_vec()
+        };
+        for callsign in &callsigns {
+            assert!(
+                !data.windows(callsign.len()).any(|window| window == callsign.as_bytes()),
+                "{callsign} remains in {name}"
+            );
+        }
+    }
+}
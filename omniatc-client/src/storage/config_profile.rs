@@ -0,0 +1,423 @@
+//! Named config profiles, color themes and per-map display setting overlays.
+//!
+//! Config values are resolved in three layers:
+//! the theme layer replaces the compiled defaults with the values of the active [theme](super::theme),
+//! the base layer is the active profile as edited by the user,
+//! and the map layer contains the display settings recommended by the loaded map.
+//!
+//! Theme values only apply to keys whose base value is the compiled default,
+//! and are replaced by the compiled default when a profile is saved,
+//! so that switching or editing themes affects all keys not customized by the user.
+//!
+//! The map layer is written on top of the base layer when a map is loaded.
+//! The base value of each overridden key is recorded,
+//! so that the overlay can be removed or excluded from a saved profile
+//! without clobbering the values in the user's profile.
+//! Keys modified by the user after the overlay was applied
+//! are considered part of the base layer.
+
+use std::io;
+use std::time::Duration;
+
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Commands, ResMut};
+use bevy::ecs::world::{Mut, World};
+use bevy_mod_config::manager::Instance;
+use bevy_mod_config::{ConfigNode, ScalarData, ScalarMetadata};
+use math::config::Constrained;
+use math::{Angle, Length, Position, Speed};
+use omniatc::load;
+use omniatc::util::{AsyncManager, AsyncResult, run_async_local};
+use serde_json::{Map, Value};
+
+use super::Storage;
+use crate::ConfigManager;
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the profile loaded on startup.
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Resource)]
+pub struct Profiles {
+    /// Name of the profile last loaded or saved.
+    pub active:             String,
+    /// Names of all saved profiles.
+    pub names:              Vec<String>,
+    /// Whether display settings recommended by the loaded map should be applied.
+    pub apply_map_settings: bool,
+    pending:                Vec<Request>,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            active:             DEFAULT_PROFILE.into(),
+            names:              Vec::new(),
+            apply_map_settings: true,
+            pending:            vec![Request::Refresh, Request::Load(DEFAULT_PROFILE.into())],
+        }
+    }
+}
+
+impl Profiles {
+    /// Saves the current base layer as the named profile.
+    pub fn save(&mut self, name: String) { self.pending.push(Request::Save(name)); }
+
+    /// Replaces the base layer with the named profile.
+    pub fn load(&mut self, name: String) { self.pending.push(Request::Load(name)); }
+}
+
+enum Request {
+    Refresh,
+    Save(String),
+    Load(String),
+}
+
+/// Values of the active theme, layered under the base layer.
+#[derive(Resource, Default)]
+pub(super) struct ThemeLayer {
+    /// Compiled default values, keyed by config path.
+    defaults: Map<String, Value>,
+    /// Values of the active theme, keyed by config path.
+    values:   Map<String, Value>,
+    /// Values written by the theme, as read back from the config.
+    applied:  Map<String, Value>,
+}
+
+impl ThemeLayer {
+    /// Whether `value` of `key` comes from the theme or the compiled defaults
+    /// rather than from the user.
+    fn is_default(&self, key: &str, value: &Value) -> bool {
+        self.applied.get(key) == Some(value) || self.defaults.get(key) == Some(value)
+    }
+}
+
+/// Display settings recommended by the loaded map.
+#[derive(Resource, Default)]
+pub(super) struct Overlay {
+    /// Recommended values parsed from the map, keyed by config path.
+    recommended: Map<String, Value>,
+    /// Whether the overlay is currently applied.
+    active:      bool,
+    /// Values written by the overlay, as read back from the config.
+    applied:     Map<String, Value>,
+    /// Values of the base layer before the overlay was applied.
+    base:        Map<String, Value>,
+}
+
+/// Returns all config values keyed by config path.
+pub(crate) fn snapshot(world: &mut World) -> Map<String, Value> {
+    let json = world.resource_scope::<Instance<ConfigManager>, _>(|world, manager| {
+        manager.instance.1.to_string(world)
+    });
+    match json.and_then(|json| serde_json::from_str(&json)) {
+        Ok(map) => map,
+        Err(err) => {
+            bevy::log::error!("Cannot serialize config: {err:?}");
+            Map::new()
+        }
+    }
+}
+
+/// Writes the given config values, leaving other keys unchanged.
+pub(crate) fn write(world: &mut World, values: &Map<String, Value>) {
+    let data = match serde_json::to_vec(values) {
+        Ok(data) => data,
+        Err(err) => {
+            bevy::log::error!("Cannot serialize config values: {err:?}");
+            return;
+        }
+    };
+    let result = world.resource_scope::<Instance<ConfigManager>, _>(|world, manager| {
+        manager.instance.1.from_reader(world, io::Cursor::new(data))
+    });
+    if let Err(err) = result {
+        bevy::log::error!("Cannot deserialize config values: {err:?}");
+    }
+    constrain_all(world);
+}
+
+/// Restricts all config values to the constraints declared in their metadata,
+/// so that out-of-range values in stored profiles or imports are never observed.
+fn constrain_all(world: &mut World) {
+    constrain::<f32>(world);
+    constrain::<f64>(world);
+    constrain::<u8>(world);
+    constrain::<u16>(world);
+    constrain::<u32>(world);
+    constrain::<u64>(world);
+    constrain::<usize>(world);
+    constrain::<i32>(world);
+    constrain::<Duration>(world);
+    constrain::<Length<f32>>(world);
+    constrain::<Speed<f32>>(world);
+    constrain::<Angle>(world);
+    constrain::<Position<f32>>(world);
+}
+
+fn constrain<T: Constrained + Copy + PartialEq + Send + Sync + 'static>(world: &mut World) {
+    let mut query = world.query::<(&mut ConfigNode, &mut ScalarData<T>, &ScalarMetadata<T>)>();
+    for (mut node, mut data, metadata) in query.iter_mut(world) {
+        let constrained = T::constrain(data.0, &metadata.0);
+        if constrained != data.0 {
+            bevy::log::warn!(
+                "Config value {} is out of range, using a valid value",
+                node.path.join(".")
+            );
+            data.0 = constrained;
+            node.generation = node.generation.next();
+        }
+    }
+}
+
+/// Returns the config values with the map layer replaced by the base layer
+/// and theme values replaced by the compiled defaults.
+pub(super) fn base_layer(world: &mut World) -> Map<String, Value> {
+    let mut values = snapshot(world);
+    let overlay = world.resource::<Overlay>();
+    for (key, applied) in &overlay.applied {
+        if values.get(key) == Some(applied)
+            && let Some(base) = overlay.base.get(key)
+        {
+            values.insert(key.clone(), base.clone());
+        }
+    }
+    let theme = world.resource::<ThemeLayer>();
+    for (key, applied) in &theme.applied {
+        if values.get(key) == Some(applied)
+            && let Some(default) = theme.defaults.get(key)
+        {
+            values.insert(key.clone(), default.clone());
+        }
+    }
+    values
+}
+
+/// Records the current config values as the compiled defaults.
+///
+/// Must be called before any profile or theme is applied.
+pub(super) fn record_defaults(world: &mut World) {
+    let defaults = snapshot(world);
+    world.resource_mut::<ThemeLayer>().defaults = defaults;
+}
+
+/// Replaces the theme layer with `values`.
+///
+/// Keys customized by the user are left unchanged,
+/// and keys applied by the previous theme but absent in `values` revert to the compiled default.
+pub(super) fn apply_theme(world: &mut World, values: Map<String, Value>) {
+    let overlay_active = world.resource::<Overlay>().active;
+    if overlay_active {
+        remove_overlay(world);
+    }
+
+    let current = snapshot(world);
+    let theme = world.resource::<ThemeLayer>();
+    let mut writes = Map::new();
+    for (key, applied) in &theme.applied {
+        if !values.contains_key(key)
+            && current.get(key) == Some(applied)
+            && let Some(default) = theme.defaults.get(key)
+        {
+            writes.insert(key.clone(), default.clone());
+        }
+    }
+    let mut themed = Vec::new();
+    for (key, value) in &values {
+        match current.get(key) {
+            Some(current) if theme.is_default(key, current) => {
+                writes.insert(key.clone(), value.clone());
+                themed.push(key.clone());
+            }
+            Some(_) => {}
+            None => bevy::log::warn!("Theme contains unknown config key {key:?}"),
+        }
+    }
+    write(world, &writes);
+
+    let after = snapshot(world);
+    let mut theme = world.resource_mut::<ThemeLayer>();
+    theme.applied = themed
+        .into_iter()
+        .filter_map(|key| Some((key.clone(), after.get(&key)?.clone())))
+        .collect();
+    theme.values = values;
+
+    if overlay_active {
+        apply_overlay(world);
+    }
+}
+
+fn remove_overlay(world: &mut World) {
+    let current = snapshot(world);
+    let mut overlay = world.resource_mut::<Overlay>();
+    overlay.active = false;
+    let applied = std::mem::take(&mut overlay.applied);
+    let base = std::mem::take(&mut overlay.base);
+
+    let restore: Map<_, _> = applied
+        .into_iter()
+        .filter(|(key, applied)| current.get(key) == Some(applied))
+        .filter_map(|(key, _)| {
+            let base = base.get(&key)?.clone();
+            Some((key, base))
+        })
+        .collect();
+    write(world, &restore);
+}
+
+fn apply_overlay(world: &mut World) {
+    let before = snapshot(world);
+    let recommended = world.resource::<Overlay>().recommended.clone();
+
+    let mut base = Map::new();
+    let mut values = Map::new();
+    for (key, value) in recommended {
+        if let Some(base_value) = before.get(&key) {
+            base.insert(key.clone(), base_value.clone());
+            values.insert(key, value);
+        } else {
+            bevy::log::warn!("Map recommends display setting for unknown config key {key:?}");
+        }
+    }
+    write(world, &values);
+
+    let after = snapshot(world);
+    let applied = values.keys().filter_map(|key| Some((key.clone(), after.get(key)?.clone())));
+    let mut overlay = world.resource_mut::<Overlay>();
+    overlay.active = true;
+    overlay.applied = applied.collect();
+    overlay.base = base;
+}
+
+/// Applies the display settings recommended by a newly loaded map,
+/// and adds or removes the map layer when the user toggles it.
+pub(super) fn apply_display_settings_system(world: &mut World) {
+    if let Some(advice) = world.resource_mut::<load::DisplaySettingsAdvice>().0.take() {
+        let recommended = advice
+            .into_iter()
+            .filter_map(|(key, value)| match serde_json::from_str(&value) {
+                Ok(value) => Some((key, value)),
+                Err(err) => {
+                    bevy::log::warn!("Invalid recommended display setting {key:?}: {err}");
+                    None
+                }
+            })
+            .collect();
+
+        remove_overlay(world);
+        world.resource_mut::<Overlay>().recommended = recommended;
+        if world.resource::<Profiles>().apply_map_settings {
+            apply_overlay(world);
+        }
+        return;
+    }
+
+    let enabled = world.resource::<Profiles>().apply_map_settings;
+    let overlay = world.resource::<Overlay>();
+    if enabled && !overlay.active {
+        apply_overlay(world);
+    } else if !enabled && overlay.active {
+        remove_overlay(world);
+    }
+}
+
+fn load_profile(world: &mut World, name: String, data: &str) {
+    let values: Map<String, Value> = match serde_json::from_str(data) {
+        Ok(values) => values,
+        Err(err) => {
+            bevy::log::error!("Config profile {name:?} is corrupted: {err}");
+            return;
+        }
+    };
+
+    remove_overlay(world);
+    write(world, &values);
+    let theme = world.resource::<ThemeLayer>().values.clone();
+    apply_theme(world, theme);
+    if world.resource::<Profiles>().apply_map_settings {
+        apply_overlay(world);
+    }
+    world.resource_mut::<Profiles>().active = name;
+}
+
+/// Executes pending profile requests against the storage.
+pub(super) fn handle_requests_system<S: Storage>(world: &mut World) {
+    let requests = std::mem::take(&mut world.resource_mut::<Profiles>().pending);
+    for request in requests {
+        match request {
+            Request::Refresh => refresh::<S>(world),
+            Request::Save(name) => {
+                let data = match serde_json::to_string(&base_layer(world)) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        bevy::log::error!("Cannot serialize config profile: {err:?}");
+                        continue;
+                    }
+                };
+                world.resource_mut::<Profiles>().active.clone_from(&name);
+                #[cfg(feature = "cloud-sync")]
+                world
+                    .resource_mut::<super::sync::LocalTimes>()
+                    .mark_modified(super::sync::Collection::ConfigProfiles, name.clone());
+                let fut = world.non_send_resource::<S>().save_config_profile(name.clone(), data);
+                world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+                    run_async_local(fut).then(
+                        &mut world.commands(),
+                        &mut poll_list,
+                        move |mut ret: AsyncResult<Result<(), S::Error>>,
+                              mut profiles: ResMut<Profiles>| {
+                            match ret.get() {
+                                Ok(()) => profiles.pending.push(Request::Refresh),
+                                Err(err) => bevy::log::error!(
+                                    "Cannot save config profile {name:?}: {err:?}"
+                                ),
+                            }
+                        },
+                    );
+                });
+            }
+            Request::Load(name) => {
+                let fut = world.non_send_resource::<S>().load_config_profile(name.clone());
+                world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+                    run_async_local(fut).then(
+                        &mut world.commands(),
+                        &mut poll_list,
+                        move |mut ret: AsyncResult<Result<Option<String>, S::Error>>,
+                              mut commands: Commands| match ret.get() {
+                            Ok(Some(data)) => {
+                                let name = name.clone();
+                                commands.queue(move |world: &mut World| {
+                                    load_profile(world, name, &data);
+                                });
+                            }
+                            Ok(None) => bevy::log::debug!("Config profile {name:?} does not exist"),
+                            Err(err) => {
+                                bevy::log::error!("Cannot load config profile {name:?}: {err:?}");
+                            }
+                        },
+                    );
+                });
+            }
+        }
+    }
+    world.flush();
+}
+
+fn refresh<S: Storage>(world: &mut World) {
+    let fut = world.non_send_resource::<S>().list_config_profiles();
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            |mut ret: AsyncResult<anyhow::Result<Vec<String>>>, mut profiles: ResMut<Profiles>| {
+                match ret.get() {
+                    Ok(names) => profiles.names = names,
+                    Err(err) => bevy::log::error!("Cannot list config profiles: {err:?}"),
+                }
+            },
+        );
+    });
+}
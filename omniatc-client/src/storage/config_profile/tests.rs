@@ -0,0 +1,87 @@
+use bevy::app::App;
+use bevy::ecs::world::World;
+use bevy_mod_config::{AppExt, Config, ConfigNode, FieldGeneration, ScalarData, ScalarMetadata};
+use math::{Length, LengthMetadata};
+use serde_json::{Map, Value, json};
+
+use super::{
+    Overlay, ThemeLayer, apply_theme, base_layer, constrain, record_defaults, snapshot, write,
+};
+use crate::ConfigManager;
+
+#[test]
+fn test_constrain_out_of_range_length() {
+    let mut world = World::new();
+    let metadata = LengthMetadata {
+        default: Length::from_nm(1.0),
+        min: Length::ZERO,
+        max: Length::from_nm(2.0),
+        ..Default::default()
+    };
+    let node = |name: &str| ConfigNode {
+        path:       vec!["test".into(), name.into()],
+        generation: FieldGeneration::default(),
+    };
+    let negative = world
+        .spawn((
+            node("negative"),
+            ScalarData(Length::from_nm(-1.0)),
+            ScalarMetadata::<Length<f32>>(metadata.clone()),
+        ))
+        .id();
+    let valid = world
+        .spawn((
+            node("valid"),
+            ScalarData(Length::from_nm(1.5)),
+            ScalarMetadata::<Length<f32>>(metadata),
+        ))
+        .id();
+
+    constrain::<Length<f32>>(&mut world);
+
+    assert_eq!(world.get::<ScalarData<Length<f32>>>(negative).unwrap().0, Length::ZERO);
+    assert!(world.get::<ConfigNode>(negative).unwrap().generation > FieldGeneration::default());
+    assert_eq!(world.get::<ScalarData<Length<f32>>>(valid).unwrap().0, Length::from_nm(1.5));
+    assert_eq!(world.get::<ConfigNode>(valid).unwrap().generation, FieldGeneration::default());
+}
+
+#[derive(Config)]
+struct Conf {
+    #[config(default = 1)]
+    themed: u32,
+    #[config(default = 2)]
+    custom: u32,
+}
+
+fn values(pairs: &[(&str, u32)]) -> Map<String, Value> {
+    pairs.iter().map(|&(key, value)| (format!("test.{key}"), json!(value))).collect()
+}
+
+fn value(world: &mut World, key: &str) -> Value { snapshot(world)[&format!("test.{key}")].clone() }
+
+#[test]
+fn test_theme_layered_under_user_values() {
+    let mut app = App::new();
+    app.init_config::<ConfigManager, Conf>("test");
+    app.init_resource::<Overlay>();
+    app.init_resource::<ThemeLayer>();
+    let world = app.world_mut();
+    record_defaults(world);
+
+    apply_theme(world, values(&[("themed", 10)]));
+    assert_eq!(value(world, "themed"), json!(10));
+    assert_eq!(value(world, "custom"), json!(2));
+
+    write(world, &values(&[("custom", 5)]));
+    apply_theme(world, values(&[("themed", 11), ("custom", 20)]));
+    assert_eq!(value(world, "themed"), json!(11));
+    assert_eq!(value(world, "custom"), json!(5), "user values must not be replaced by the theme");
+
+    let base = base_layer(world);
+    assert_eq!(base["test.themed"], json!(1), "saved profiles must not contain theme values");
+    assert_eq!(base["test.custom"], json!(5));
+
+    apply_theme(world, Map::new());
+    assert_eq!(value(world, "themed"), json!(1));
+    assert_eq!(value(world, "custom"), json!(5));
+}
@@ -0,0 +1,123 @@
+//! Emergency autosave and diagnostic report on panic.
+//!
+//! The world cannot be accessed from a panic hook,
+//! so the live level is captured periodically into a save slot snapshot,
+//! which is encoded on the async compute task pool.
+//! When the client panics, the latest snapshot is written as the newest save slot,
+//! which is resumed on the next startup,
+//! and a diagnostic report is written to the crash report directory.
+
+use std::fmt::Write as _;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use bevy::ecs::system::Local;
+use bevy::ecs::world::World;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy::time::{self, Time};
+use jiff::Timestamp;
+use omniatc::load;
+
+use super::{SaveSlotMeta, fs, library};
+
+/// Name of the save slot written on panic.
+const SLOT_NAME: &str = "Emergency autosave";
+
+/// Real time between consecutive snapshots.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The latest snapshot of the current level, written on panic.
+static SNAPSHOT: Mutex<Option<(SaveSlotMeta, Vec<u8>)>> = Mutex::new(None);
+
+/// Captures the current level into the emergency snapshot periodically.
+///
+/// A new snapshot is not captured while the previous one is still being encoded.
+pub(super) fn snapshot_system(
+    world: &mut World,
+    mut last_snapshot: Local<Option<Duration>>,
+    mut encoding: Local<Option<Task<()>>>,
+) {
+    let now = world.resource::<Time<time::Real>>().elapsed();
+    if last_snapshot.is_some_and(|last| now.saturating_sub(last) < SNAPSHOT_INTERVAL) {
+        return;
+    }
+    if encoding.as_ref().is_some_and(|task| !task.is_finished()) {
+        return;
+    }
+    *last_snapshot = Some(now);
+
+    if world.get_resource::<load::LoadedFile>().is_none_or(|loaded| loaded.0.is_none()) {
+        return;
+    }
+    let (meta, file) = match library::capture_slot(world, SLOT_NAME) {
+        Ok(capture) => capture,
+        Err(err) => {
+            bevy::log::warn!("Cannot capture emergency snapshot: {err:?}");
+            return;
+        }
+    };
+    *encoding = Some(AsyncComputeTaskPool::get().spawn(async move {
+        match file.to_osav() {
+            Ok(data) => {
+                if let Ok(mut slot) = SNAPSHOT.lock() {
+                    *slot = Some((meta, data));
+                }
+            }
+            Err(err) => bevy::log::warn!("Cannot encode emergency snapshot: {err:?}"),
+        }
+    }));
+}
+
+/// Installs a panic hook that writes the emergency autosave and a diagnostic report
+/// after the previous hook has run.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+
+        let autosave = write_autosave();
+        match write_report(info, autosave.as_ref()) {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(err) => eprintln!("Cannot write crash report: {err:?}"),
+        }
+    }));
+}
+
+/// Writes the latest snapshot as a save slot, returning its name if written.
+fn write_autosave() -> anyhow::Result<String> {
+    // The lock may be held by the panicking thread, so it must not be waited for.
+    let slot = SNAPSHOT.try_lock().ok().context("snapshot is unavailable")?;
+    let (meta, data) = slot.as_ref().context("no level was captured")?;
+
+    // Mark the slot as the most recent one so that it is resumed on the next startup.
+    let meta = SaveSlotMeta { saved: Timestamp::now(), ..meta.clone() };
+    fs::write_save_slot_files(&meta, data)?;
+    Ok(meta.name)
+}
+
+/// Writes the diagnostic report of a panic.
+fn write_report(
+    info: &PanicHookInfo<'_>,
+    autosave: Result<&String, &anyhow::Error>,
+) -> anyhow::Result<PathBuf> {
+    let now = Timestamp::now();
+    let mut report = String::new();
+    _ = writeln!(report, "omniatc {} crashed at {now}", env!("CARGO_PKG_VERSION"));
+    _ = writeln!(report, "Platform: {} {}", std::env::consts::OS, std::env::consts::ARCH);
+    _ = writeln!(report, "Thread: {}", std::thread::current().name().unwrap_or("<unnamed>"));
+    _ = writeln!(report, "Panic: {info}");
+    match autosave {
+        Ok(name) => _ = writeln!(report, "Emergency autosave: written to slot {name:?}"),
+        Err(err) => _ = writeln!(report, "Emergency autosave: not written: {err:?}"),
+    }
+    _ = writeln!(report, "\nBacktrace:\n{}", std::backtrace::Backtrace::force_capture());
+
+    let dir = fs::crash_report_dir().context("cannot find data path")?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    let path = dir.join(format!("crash-{}.txt", now.strftime("%Y%m%d-%H%M%S")));
+    std::fs::write(&path, report).with_context(|| format!("write {}", path.display()))?;
+    Ok(path)
+}
@@ -6,8 +6,9 @@ use std::rc::Rc;
 
 use anyhow::Context as _;
 use jiff::{SignedDuration, Timestamp};
+use rusqlite::OptionalExtension as _;
 
-use super::{LevelMeta, ScenarioMeta};
+use super::{LevelMeta, SaveSlotMeta, ScenarioMeta};
 
 fn data_path() -> Option<PathBuf> {
     let mut path = dirs::data_dir()?;
@@ -15,12 +16,59 @@ fn data_path() -> Option<PathBuf> {
     Some(path)
 }
 
+fn export_dir() -> Option<PathBuf> {
+    if let Some(path) = dirs::download_dir() {
+        return Some(path);
+    }
+    let mut path = data_path()?;
+    path.push("exports");
+    Some(path)
+}
+
+fn save_dir() -> Option<PathBuf> {
+    let mut path = data_path()?;
+    path.push("saves");
+    Some(path)
+}
+
+/// Path of a file of the save slot `id` with the given extension.
+fn save_slot_path(id: &str, extension: &str) -> anyhow::Result<PathBuf> {
+    let mut path = save_dir().context("cannot find data path")?;
+    path.push(format!("{id}.{extension}"));
+    Ok(path)
+}
+
+/// Writes the files of a save slot synchronously.
+pub(super) fn write_save_slot_files(meta: &SaveSlotMeta, data: &[u8]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(save_dir().context("cannot find data path")?)
+        .context("create save directory")?;
+
+    let data_path = save_slot_path(&meta.id, "osav")?;
+    std::fs::write(&data_path, data).with_context(|| format!("write {}", data_path.display()))?;
+
+    // The metadata is written last so that listed slots always have data.
+    let meta_path = save_slot_path(&meta.id, "json")?;
+    let meta = serde_json::to_vec(meta).context("serialize save slot metadata")?;
+    std::fs::write(&meta_path, meta).with_context(|| format!("write {}", meta_path.display()))?;
+    Ok(())
+}
+
+/// Directory of diagnostic reports written on crash.
+pub(super) fn crash_report_dir() -> Option<PathBuf> {
+    let mut path = data_path()?;
+    path.push("crash-reports");
+    Some(path)
+}
+
 fn index_path() -> Option<PathBuf> {
     let mut path = data_path()?;
     path.push("index.db");
     Some(path)
 }
 
+/// Row name of the global achievement progress.
+const ACHIEVEMENT_KEY: &str = "global";
+
 #[derive(Default)]
 pub struct Impl {
     db: Rc<OnceCell<rusqlite::Connection>>,
@@ -77,6 +125,24 @@ fn new_db() -> anyhow::Result<rusqlite::Connection> {
     )
     .context("prepare scenario_tag table")?;
 
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS config_profile (
+        name TEXT PRIMARY KEY,
+        data TEXT
+    )",
+        (),
+    )
+    .context("prepare config_profile table")?;
+
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS achievement (
+        name TEXT PRIMARY KEY,
+        data TEXT
+    )",
+        (),
+    )
+    .context("prepare achievement table")?;
+
     Ok(db)
 }
 
@@ -208,6 +274,205 @@ impl super::Storage for Impl {
         })();
         async move { run }
     }
+
+    fn insert_level(
+        &self,
+        meta: LevelMeta,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        let run = (|| {
+            let db = get_db(&db)?;
+            db.execute(
+                "INSERT OR REPLACE INTO level (id, title, created, modified, data) VALUES (?, ?, \
+                 ?, ?, ?)",
+                (
+                    &meta.id,
+                    &meta.title,
+                    i64::try_from(meta.created.duration_since(Timestamp::UNIX_EPOCH).as_millis())
+                        .expect("system time is too late"),
+                    i64::try_from(meta.modified.duration_since(Timestamp::UNIX_EPOCH).as_millis())
+                        .expect("system time is too late"),
+                    data,
+                ),
+            )
+            .context("insert level")?;
+            Ok(())
+        })();
+        async move { run }
+    }
+
+    fn list_save_slots(&self) -> impl Future<Output = anyhow::Result<Vec<SaveSlotMeta>>> + 'static {
+        let run = (|| {
+            let dir = save_dir().context("cannot find data path")?;
+            if !dir.exists() {
+                return Ok(Vec::new());
+            }
+
+            let mut slots = Vec::new();
+            for entry in std::fs::read_dir(&dir).context("list save directory")? {
+                let path = entry.context("read save directory entry")?.path();
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+                let meta =
+                    std::fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+                match serde_json::from_slice::<SaveSlotMeta>(&meta) {
+                    Ok(meta) => slots.push(meta),
+                    Err(err) => bevy::log::warn!("Invalid save slot {}: {err}", path.display()),
+                }
+            }
+            slots.sort_by_key(|slot| std::cmp::Reverse(slot.saved));
+            Ok(slots)
+        })();
+        async move { run }
+    }
+
+    fn load_save_slot(
+        &self,
+        id: String,
+    ) -> impl Future<Output = anyhow::Result<Vec<u8>>> + 'static {
+        let run = (|| {
+            let path = save_slot_path(&id, "osav")?;
+            std::fs::read(&path).with_context(|| format!("read {}", path.display()))
+        })();
+        async move { run }
+    }
+
+    fn write_save_slot(
+        &self,
+        meta: SaveSlotMeta,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let run = write_save_slot_files(&meta, &data);
+        async move { run }
+    }
+
+    fn delete_save_slot(&self, id: String) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let run = (|| {
+            for extension in ["json", "osav"] {
+                let path = save_slot_path(&id, extension)?;
+                match std::fs::remove_file(&path) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => {
+                        return Err(err).with_context(|| format!("delete {}", path.display()));
+                    }
+                }
+            }
+            Ok(())
+        })();
+        async move { run }
+    }
+
+    fn list_config_profiles(&self) -> impl Future<Output = anyhow::Result<Vec<String>>> + 'static {
+        let db = self.db.clone();
+        let run = (|| {
+            let db = get_db(&db)?;
+            let mut stmt = db
+                .prepare("SELECT name FROM config_profile ORDER BY name")
+                .context("prepare config profile list statement")?;
+            let names =
+                stmt.query_map((), |row| row.get(0)).context("query config profile list")?;
+            names
+                .map(|result| result.context("convert config profile row"))
+                .collect::<anyhow::Result<Vec<_>>>()
+        })();
+        async move { run }
+    }
+
+    fn load_config_profile(
+        &self,
+        name: String,
+    ) -> impl Future<Output = anyhow::Result<Option<String>>> + 'static {
+        let db = self.db.clone();
+        let run = (|| {
+            let db = get_db(&db)?;
+            let mut stmt = db
+                .prepare("SELECT data FROM config_profile WHERE name = ?")
+                .context("prepare config profile select query")?;
+            stmt.query_row((name,), |row| row.get(0))
+                .optional()
+                .context("query config profile data")
+        })();
+        async move { run }
+    }
+
+    fn save_config_profile(
+        &self,
+        name: String,
+        data: String,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        let run = (|| {
+            let db = get_db(&db)?;
+            db.execute(
+                "INSERT OR REPLACE INTO config_profile (name, data) VALUES (?, ?)",
+                (&name, &data),
+            )
+            .context("insert config profile")?;
+            Ok(())
+        })();
+        async move { run }
+    }
+
+    fn load_achievements(&self) -> impl Future<Output = anyhow::Result<Option<String>>> + 'static {
+        let db = self.db.clone();
+        let run = (|| {
+            let db = get_db(&db)?;
+            let mut stmt = db
+                .prepare("SELECT data FROM achievement WHERE name = ?")
+                .context("prepare achievement select query")?;
+            stmt.query_row((ACHIEVEMENT_KEY,), |row| row.get(0))
+                .optional()
+                .context("query achievement data")
+        })();
+        async move { run }
+    }
+
+    fn save_achievements(
+        &self,
+        data: String,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        let run = (|| {
+            let db = get_db(&db)?;
+            db.execute(
+                "INSERT OR REPLACE INTO achievement (name, data) VALUES (?, ?)",
+                (ACHIEVEMENT_KEY, &data),
+            )
+            .context("insert achievement")?;
+            Ok(())
+        })();
+        async move { run }
+    }
+
+    fn pick_import_file(
+        &self,
+        path: String,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<u8>>>> + 'static {
+        let run = if path.is_empty() {
+            Ok(None)
+        } else {
+            std::fs::read(&path).with_context(|| format!("read {path}")).map(Some)
+        };
+        async move { run }
+    }
+
+    fn export_file(
+        &self,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<String>> + 'static {
+        let run = (|| {
+            let mut path = export_dir().context("cannot find export directory")?;
+            std::fs::create_dir_all(&path).context("create export directory")?;
+            path.push(file_name);
+            std::fs::write(&path, data).with_context(|| format!("write {}", path.display()))?;
+            Ok(path.display().to_string())
+        })();
+        async move { run }
+    }
 }
 
 fn get_db(cell: &Rc<OnceCell<rusqlite::Connection>>) -> anyhow::Result<&rusqlite::Connection> {
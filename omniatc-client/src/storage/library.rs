@@ -0,0 +1,578 @@
+//! Management of stored scenarios, levels and save slots.
+//!
+//! Files are exchanged with the user as `.osav` files.
+//! Imported files tagged with `type = scenario` are stored as scenarios;
+//! all other files, including savefiles, are stored as levels.
+//!
+//! The current level is saved into named save slots chosen by the user.
+//! The most recently written save slot is restored on the next startup.
+
+use std::borrow::Cow;
+use std::future::Future;
+
+use anyhow::Context as _;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Commands, NonSend, ResMut};
+use bevy::ecs::world::{Mut, World};
+use bevy::math::Vec2;
+use bevy::time::{self, Time};
+use jiff::Timestamp;
+#[cfg(feature = "debug")]
+use omniatc::level::ground;
+use omniatc::level::object::{self, Object};
+use omniatc::level::{clock, score, session, track};
+use omniatc::load;
+use omniatc::util::{AsyncManager, AsyncResult, run_async_local};
+
+use super::{LevelMeta, SaveSlotMeta, ScenarioMeta, Storage, THUMBNAIL_SIZE};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of levels listed in the library.
+const LEVEL_LIST_LIMIT: usize = 50;
+
+#[derive(Resource)]
+pub struct Library {
+    /// Stored scenarios, as of the last refresh.
+    pub scenarios:  Vec<ScenarioMeta>,
+    /// Stored levels, most recently modified first, as of the last refresh.
+    pub levels:     Vec<LevelMeta>,
+    /// Save slots, most recently written first, as of the last refresh.
+    pub save_slots: Vec<SaveSlotMeta>,
+    /// Result of the last completed operation, for display to the user.
+    pub status:     Option<String>,
+    pending:        Vec<Request>,
+}
+
+impl Default for Library {
+    fn default() -> Self {
+        Self {
+            scenarios:  Vec::new(),
+            levels:     Vec::new(),
+            save_slots: Vec::new(),
+            status:     None,
+            pending:    vec![Request::Refresh],
+        }
+    }
+}
+
+impl Library {
+    /// Reloads the lists of stored scenarios, levels and save slots.
+    pub fn refresh(&mut self) { self.pending.push(Request::Refresh); }
+
+    /// Replaces the current world with a stored scenario, level or save slot.
+    pub fn load(&mut self, kind: Kind, id: String) { self.pending.push(Request::Load(kind, id)); }
+
+    /// Exports a stored scenario, level or save slot as a file.
+    pub fn export(&mut self, kind: Kind, id: String) {
+        self.pending.push(Request::Export(kind, id));
+    }
+
+    /// Saves the current level into the save slot with the given name,
+    /// replacing any slot with the same name.
+    pub fn save(&mut self, name: String) { self.pending.push(Request::Save(name)); }
+
+    /// Deletes a save slot.
+    pub fn delete_save_slot(&mut self, id: String) {
+        self.pending.push(Request::DeleteSaveSlot(id));
+    }
+
+    /// Imports a file chosen by the user and loads it.
+    ///
+    /// `path` is only used on platforms without a native file picker.
+    pub fn import(&mut self, path: String) { self.pending.push(Request::Import(path)); }
+
+    /// Exports the result of the current session as a file.
+    pub fn export_session_result(&mut self) { self.pending.push(Request::ExportSessionResult); }
+
+    /// Exports the tracks flown in the current session in a geographic format.
+    pub fn export_tracks(&mut self, format: track::export::Format) {
+        self.pending.push(Request::ExportTracks(format));
+    }
+
+    /// Exports a bundle of diagnostic information to attach to an issue report.
+    ///
+    /// If `anonymize` is set, callsigns are replaced with placeholders.
+    pub fn export_bug_report(&mut self, anonymize: bool) {
+        self.pending.push(Request::ExportBugReport { anonymize });
+    }
+
+    /// Exports the resolved ground network of the current level for debugging.
+    #[cfg(feature = "debug")]
+    pub fn export_ground_graph(&mut self, format: ground::export::Format) {
+        self.pending.push(Request::ExportGroundGraph(format));
+    }
+
+    /// Synchronizes save slots, progress and config profiles with the configured sync backend.
+    #[cfg(feature = "cloud-sync")]
+    pub fn sync(&mut self) { self.pending.push(Request::Sync); }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Scenario,
+    Level,
+    SaveSlot,
+}
+
+enum Request {
+    Refresh,
+    Load(Kind, String),
+    Export(Kind, String),
+    Save(String),
+    DeleteSaveSlot(String),
+    Import(String),
+    ExportSessionResult,
+    ExportTracks(track::export::Format),
+    ExportBugReport {
+        anonymize: bool,
+    },
+    #[cfg(feature = "debug")]
+    ExportGroundGraph(ground::export::Format),
+    #[cfg(feature = "cloud-sync")]
+    Sync,
+}
+
+/// Executes pending library requests against the storage.
+pub(super) fn handle_requests_system<S: Storage>(world: &mut World) {
+    let requests = std::mem::take(&mut world.resource_mut::<Library>().pending);
+    for request in requests {
+        match request {
+            Request::Refresh => refresh::<S>(world),
+            Request::Load(kind, id) => load::<S>(world, kind, id),
+            Request::Export(kind, id) => export::<S>(world, kind, id),
+            Request::Save(name) => save::<S>(world, name),
+            Request::DeleteSaveSlot(id) => delete_save_slot::<S>(world, id),
+            Request::Import(path) => import::<S>(world, path),
+            Request::ExportSessionResult => export_session_result::<S>(world),
+            Request::ExportTracks(format) => export_tracks::<S>(world, format),
+            Request::ExportBugReport { anonymize } => export_bug_report::<S>(world, anonymize),
+            #[cfg(feature = "debug")]
+            Request::ExportGroundGraph(format) => export_ground_graph::<S>(world, format),
+            #[cfg(feature = "cloud-sync")]
+            Request::Sync => super::sync::start::<S>(world),
+        }
+    }
+    world.flush();
+}
+
+fn refresh<S: Storage>(world: &mut World) {
+    let storage = world.non_send_resource::<S>();
+    let scenarios = storage.list_scenarios_by_tag("type".into());
+    let levels = storage.list_levels_by_time(LEVEL_LIST_LIMIT);
+    let save_slots = storage.list_save_slots();
+    let fut = async move { (scenarios.await, levels.await, save_slots.await) };
+
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            |mut ret: AsyncResult<(
+                anyhow::Result<Vec<ScenarioMeta>>,
+                anyhow::Result<Vec<LevelMeta>>,
+                anyhow::Result<Vec<SaveSlotMeta>>,
+            )>,
+             mut library: ResMut<Library>| {
+                let (scenarios, levels, save_slots) = ret.get();
+                match scenarios {
+                    Ok(scenarios) => library.scenarios = scenarios,
+                    Err(err) => bevy::log::error!("Cannot list scenarios: {err:?}"),
+                }
+                match levels {
+                    Ok(levels) => library.levels = levels,
+                    Err(err) => bevy::log::error!("Cannot list levels: {err:?}"),
+                }
+                match save_slots {
+                    Ok(save_slots) => library.save_slots = save_slots,
+                    Err(err) => bevy::log::error!("Cannot list save slots: {err:?}"),
+                }
+            },
+        );
+    });
+}
+
+/// Reads the data of a stored scenario, level or save slot.
+pub(super) fn read<S: Storage>(
+    storage: &S,
+    kind: Kind,
+    id: String,
+) -> impl Future<Output = Result<Vec<u8>, S::Error>> + 'static {
+    let futs = match kind {
+        Kind::Scenario => (Some(storage.load_scenario(id)), None, None),
+        Kind::Level => (None, Some(storage.load_level(id)), None),
+        Kind::SaveSlot => (None, None, Some(storage.load_save_slot(id))),
+    };
+    async move {
+        match futs {
+            (Some(fut), _, _) => fut.await,
+            (_, Some(fut), _) => fut.await,
+            (_, _, Some(fut)) => fut.await,
+            (None, None, None) => unreachable!("exactly one future is created"),
+        }
+    }
+}
+
+fn load<S: Storage>(world: &mut World, kind: Kind, id: String) {
+    let fut = read(world.non_send_resource::<S>(), kind, id.clone());
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            move |mut ret: AsyncResult<Result<Vec<u8>, S::Error>>,
+                  mut commands: Commands,
+                  mut library: ResMut<Library>| match ret.get() {
+                Ok(data) => {
+                    commands.queue(load::StagedCommand {
+                        source:   load::Source::Raw(Cow::Owned(data)),
+                        on_error: Box::new(|_world, err| bevy::log::error!("Load error: {err}")),
+                    });
+                    library.status = Some(format!("Loaded {id}"));
+                }
+                Err(err) => {
+                    bevy::log::error!("Cannot read {id:?}: {err:?}");
+                    library.status = Some(format!("Cannot read {id}"));
+                }
+            },
+        );
+    });
+}
+
+fn export<S: Storage>(world: &mut World, kind: Kind, id: String) {
+    let fut = read(world.non_send_resource::<S>(), kind, id.clone());
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            move |mut ret: AsyncResult<Result<Vec<u8>, S::Error>>,
+                  storage: NonSend<S>,
+                  mut poll_list: ResMut<AsyncManager>,
+                  mut commands: Commands,
+                  mut library: ResMut<Library>| {
+                let data = match ret.get() {
+                    Ok(data) => data,
+                    Err(err) => {
+                        bevy::log::error!("Cannot read {id:?}: {err:?}");
+                        library.status = Some(format!("Cannot read {id}"));
+                        return;
+                    }
+                };
+
+                let id = id.clone();
+                run_async_local(storage.export_file(format!("{id}.osav"), data)).then(
+                    &mut commands,
+                    &mut poll_list,
+                    move |mut ret: AsyncResult<anyhow::Result<String>>,
+                          mut library: ResMut<Library>| {
+                        library.status = Some(match ret.get() {
+                            Ok(location) => format!("Exported {id} to {location}"),
+                            Err(err) => {
+                                bevy::log::error!("Cannot export {id:?}: {err:?}");
+                                format!("Cannot export {id}: {err}")
+                            }
+                        });
+                    },
+                );
+            },
+        );
+    });
+}
+
+fn save<S: Storage>(world: &mut World, name: String) {
+    let (meta, data) = match snapshot(world, &name) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            bevy::log::error!("Cannot save {name:?}: {err:?}");
+            world.resource_mut::<Library>().status = Some(format!("Cannot save {name}: {err}"));
+            return;
+        }
+    };
+
+    #[cfg(feature = "cloud-sync")]
+    world
+        .resource_mut::<super::sync::LocalTimes>()
+        .mark_modified(super::sync::Collection::SaveSlots, meta.id.clone());
+
+    let fut = world.non_send_resource::<S>().write_save_slot(meta, data);
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            move |mut ret: AsyncResult<Result<(), S::Error>>, mut library: ResMut<Library>| {
+                if let Err(err) = ret.get() {
+                    bevy::log::error!("Cannot write save slot {name:?}: {err:?}");
+                    library.status = Some(format!("Cannot save {name}"));
+                    return;
+                }
+                library.status = Some(format!("Saved {name}"));
+                library.pending.push(Request::Refresh);
+            },
+        );
+    });
+}
+
+/// Captures and encodes the current level for a save slot named `name`.
+pub(super) fn snapshot(world: &mut World, name: &str) -> anyhow::Result<(SaveSlotMeta, Vec<u8>)> {
+    let (meta, file) = capture_slot(world, name)?;
+    let data = file.to_osav().context("serialize level")?;
+    Ok((meta, data))
+}
+
+/// Captures the current level for a save slot named `name` without encoding it.
+pub(super) fn capture_slot(
+    world: &mut World,
+    name: &str,
+) -> anyhow::Result<(SaveSlotMeta, store::File)> {
+    let id = SaveSlotMeta::id_for_name(name);
+    anyhow::ensure!(id.chars().any(|ch| ch != '-'), "the slot name is empty");
+
+    let file = capture_file(world)?;
+    let score = file.stats.score;
+
+    let elapsed = world.resource::<Time<time::Virtual>>().elapsed();
+    let sim_time = world
+        .get_resource::<session::Log>()
+        .map_or(elapsed, |log| elapsed.saturating_sub(log.start_time));
+    let positions: Vec<Vec2> = world
+        .query::<&Object>()
+        .iter(world)
+        .map(|object| object.position.horizontal().get())
+        .collect();
+
+    let meta = SaveSlotMeta {
+        id,
+        name: name.trim().to_owned(),
+        saved: Timestamp::now(),
+        map_title: file.meta.title.clone(),
+        sim_time,
+        score,
+        thumbnail: thumbnail(&positions),
+    };
+    Ok((meta, file))
+}
+
+/// Returns the loaded file with the live objects, the current clock and statistics.
+pub(super) fn capture_file(world: &mut World) -> anyhow::Result<store::File> {
+    let loaded = world.resource::<load::LoadedFile>().0.clone().context("no level is loaded")?;
+    let mut file = store::File::clone(&loaded);
+    file.objects = object::saver::save(world);
+
+    let elapsed = world.resource::<Time<time::Virtual>>().elapsed();
+    if let Some(clock) = world.get_resource::<clock::Clock>() {
+        file.clock = clock.to_store(elapsed);
+    }
+    let stats = world.resource::<score::Stats>();
+    file.stats = store::Stats {
+        score: stats.total,
+        num_runway_arrivals: stats.num_runway_arrivals,
+        num_apron_arrivals: stats.num_apron_arrivals,
+        num_departures: stats.num_departures,
+        num_conflicts: stats.num_conflicts,
+        total_conflict_time: stats.total_conflict_time,
+        num_pilot_requests_approved: stats.num_pilot_requests_approved,
+        num_pilot_requests_denied: stats.num_pilot_requests_denied,
+        go_arounds: stats.go_arounds,
+        ..file.stats
+    };
+    Ok(file)
+}
+
+/// Scales object positions into the square thumbnail of a save slot.
+fn thumbnail(positions: &[Vec2]) -> Vec<[u8; 2]> {
+    let Some(min) = positions.iter().copied().reduce(Vec2::min) else { return Vec::new() };
+    let max = positions.iter().copied().reduce(Vec2::max).unwrap_or(min);
+    let center = (min + max) / 2.;
+    let half_extent = ((max - min).max_element() / 2.).max(f32::EPSILON);
+    let size = f32::from(THUMBNAIL_SIZE);
+
+    positions
+        .iter()
+        .map(|&position| {
+            let scaled = ((position - center) / half_extent + 1.) / 2. * size;
+            // y is flipped since the thumbnail is drawn with y pointing down.
+            #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "clamped")]
+            [scaled.x.clamp(0., size) as u8, (size - scaled.y).clamp(0., size) as u8]
+        })
+        .collect()
+}
+
+fn delete_save_slot<S: Storage>(world: &mut World, id: String) {
+    let fut = world.non_send_resource::<S>().delete_save_slot(id.clone());
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            move |mut ret: AsyncResult<Result<(), S::Error>>, mut library: ResMut<Library>| {
+                if let Err(err) = ret.get() {
+                    bevy::log::error!("Cannot delete save slot {id:?}: {err:?}");
+                    library.status = Some(format!("Cannot delete {id}"));
+                    return;
+                }
+                library.status = Some(format!("Deleted {id}"));
+                library.pending.push(Request::Refresh);
+            },
+        );
+    });
+}
+
+fn export_session_result<S: Storage>(world: &mut World) {
+    let result = session::build_result(world);
+    let data = match serde_json::to_vec_pretty(&result) {
+        Ok(data) => data,
+        Err(err) => {
+            bevy::log::error!("Cannot serialize session result: {err:?}");
+            world.resource_mut::<Library>().status = Some("Cannot export result".into());
+            return;
+        }
+    };
+
+    let id = result.map_id;
+    export_file::<S>(world, format!("{id}-result.json"), data, format!("result of {id}"));
+}
+
+fn export_tracks<S: Storage>(world: &mut World, format: track::export::Format) {
+    let log = world.resource::<track::Log>();
+    let Some(origin) = log.origin else {
+        world.resource_mut::<Library>().status =
+            Some("Cannot export tracks: the map has no geographic origin".into());
+        return;
+    };
+    let data = format.write(log, origin).into_bytes();
+
+    let id = world
+        .resource::<load::LoadedMeta>()
+        .0
+        .as_ref()
+        .map_or_else(|| "session".into(), |meta| meta.id.clone());
+    let file_name = format!("{id}-tracks.{}", format.extension());
+    export_file::<S>(world, file_name, data, format!("tracks of {id}"));
+}
+
+#[cfg(feature = "debug")]
+fn export_ground_graph<S: Storage>(world: &mut World, format: ground::export::Format) {
+    let graph = ground::export::collect(world);
+    let data = format.write(&graph, world.resource::<track::Log>().origin).into_bytes();
+
+    let id = world
+        .resource::<load::LoadedMeta>()
+        .0
+        .as_ref()
+        .map_or_else(|| "level".into(), |meta| meta.id.clone());
+    let file_name = format!("{id}-ground.{}", format.extension());
+    export_file::<S>(world, file_name, data, format!("ground graph of {id}"));
+}
+
+/// Exports a generated file and reports the outcome in the library status.
+fn export_bug_report<S: Storage>(world: &mut World, anonymize: bool) {
+    let data = match super::bug_report::build(world, anonymize) {
+        Ok(data) => data,
+        Err(err) => {
+            bevy::log::error!("Cannot build bug report: {err:?}");
+            world.resource_mut::<Library>().status =
+                Some(format!("Cannot build bug report: {err}"));
+            return;
+        }
+    };
+
+    let stamp = Timestamp::now().strftime("%Y%m%d-%H%M%S");
+    export_file::<S>(world, format!("omniatc-report-{stamp}.zip"), data, "bug report".into());
+}
+
+fn export_file<S: Storage>(world: &mut World, file_name: String, data: Vec<u8>, what: String) {
+    let fut = world.non_send_resource::<S>().export_file(file_name, data);
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            move |mut ret: AsyncResult<anyhow::Result<String>>, mut library: ResMut<Library>| {
+                library.status = Some(match ret.get() {
+                    Ok(location) => format!("Exported {what} to {location}"),
+                    Err(err) => {
+                        bevy::log::error!("Cannot export {what}: {err:?}");
+                        format!("Cannot export {what}: {err}")
+                    }
+                });
+            },
+        );
+    });
+}
+
+fn import<S: Storage>(world: &mut World, path: String) {
+    let fut = world.non_send_resource::<S>().pick_import_file(path);
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(
+            &mut world.commands(),
+            &mut poll_list,
+            |mut ret: AsyncResult<anyhow::Result<Option<Vec<u8>>>>,
+             storage: NonSend<S>,
+             mut poll_list: ResMut<AsyncManager>,
+             mut commands: Commands,
+             mut library: ResMut<Library>| {
+                let bytes = match ret.get() {
+                    Ok(Some(bytes)) => bytes,
+                    Ok(None) => return,
+                    Err(err) => {
+                        library.status = Some(format!("Cannot read file: {err}"));
+                        return;
+                    }
+                };
+
+                let file = match store::File::from_osav(&bytes[..]) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        library.status = Some(format!("Invalid file: {err}"));
+                        return;
+                    }
+                };
+
+                let now = Timestamp::now();
+                let futs = if file.meta.tags.get("type").is_some_and(|ty| ty == "scenario") {
+                    let meta = ScenarioMeta {
+                        id:      file.meta.id.clone(),
+                        title:   file.meta.title.clone(),
+                        created: now,
+                    };
+                    (Some(storage.insert_scenario(meta, bytes, file.meta.tags.clone())), None)
+                } else {
+                    let meta = LevelMeta {
+                        id:       file.meta.id.clone(),
+                        title:    file.meta.title.clone(),
+                        created:  now,
+                        modified: now,
+                    };
+                    (None, Some(storage.insert_level(meta, bytes)))
+                };
+
+                run_async_local(async move {
+                    match futs {
+                        (Some(fut), _) => fut.await,
+                        (_, Some(fut)) => fut.await,
+                        (None, None) => unreachable!("exactly one future is created"),
+                    }
+                })
+                .then(&mut commands, &mut poll_list, {
+                    let mut file = Some(file);
+                    move |mut ret: AsyncResult<Result<(), S::Error>>,
+                          mut commands: Commands,
+                          mut library: ResMut<Library>| {
+                        let file = file.take().expect("then closure should only be called once");
+                        if let Err(err) = ret.get() {
+                            bevy::log::error!("Cannot store imported file: {err:?}");
+                            library.status = Some(format!("Cannot store {}", file.meta.id));
+                            return;
+                        }
+
+                        library.status = Some(format!("Imported {}", file.meta.id));
+                        library.pending.push(Request::Refresh);
+                        commands.queue(load::StagedCommand {
+                            source:   load::Source::Parsed(Box::new(file)),
+                            on_error: Box::new(|_world, err| {
+                                bevy::log::error!("Load error: {err}");
+                            }),
+                        });
+                    }
+                });
+            },
+        );
+    });
+}
@@ -0,0 +1,20 @@
+use bevy::math::Vec2;
+
+use super::thumbnail;
+use crate::storage::{SaveSlotMeta, THUMBNAIL_SIZE};
+
+#[test]
+fn test_thumbnail_fits_square() {
+    let points = thumbnail(&[Vec2::new(-10., 0.), Vec2::new(10., 0.), Vec2::new(0., 5.)]);
+    assert_eq!(points, [[0, 159], [THUMBNAIL_SIZE, 159], [127, 95]]);
+}
+
+#[test]
+fn test_thumbnail_empty() {
+    assert!(thumbnail(&[]).is_empty());
+}
+
+#[test]
+fn test_slot_id_from_name() {
+    assert_eq!(SaveSlotMeta::id_for_name(" Before Rush/Hour "), "before-rush-hour");
+}
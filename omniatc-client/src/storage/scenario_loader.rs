@@ -106,7 +106,7 @@ pub(super) fn handle_loaded_scenario_system<S: Storage>(
 
                 if current_load_on_import.0.as_ref() == Some(&file.meta.id) {
                     current_load_on_import.0 = None;
-                    commands.queue(load::Command {
+                    commands.queue(load::StagedCommand {
                         source:   load::Source::Parsed(Box::new(file)),
                         on_error: Box::new(|_world, err| bevy::log::error!("Load error: {err}")),
                     });
@@ -116,7 +116,8 @@ pub(super) fn handle_loaded_scenario_system<S: Storage>(
     }
 }
 
-pub const BUILTIN_SCENARIOS: &[&str] = &["maps/tutorial.osav", "maps/demo.osav", "maps/blank.osav"];
+pub const BUILTIN_SCENARIOS: &[&str] =
+    &["maps/tutorial.osav", "maps/demo.osav", "maps/timetable.osav", "maps/blank.osav"];
 pub const DEFAULT_SCENARIO: &str = "omniatc.tutorial";
 
 pub(super) fn import_builtin_scenarios_system(mut commands: Commands) {
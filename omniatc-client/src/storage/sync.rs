@@ -0,0 +1,403 @@
+//! Synchronization of save slots, achievement progress and config profiles
+//! with a remote [`Backend`].
+//!
+//! Each synchronized entry is compared by its modification time,
+//! and the newer copy replaces the older one.
+//! Local entries not modified since startup have no known modification time,
+//! so the remote copy is preferred if one exists.
+//!
+//! Only compiled with the `cloud-sync` feature.
+//! The built-in backend writes to a directory,
+//! which may be a `WebDAV` share or an S3-compatible bucket mounted by the operating system,
+//! so that no network dependency is compiled into the client.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use anyhow::Context as _;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Commands, NonSend, Res, ResMut, SystemState};
+use bevy::ecs::world::{Mut, World};
+use bevy_mod_config::{Config, ReadConfig};
+use jiff::Timestamp;
+use omniatc::util::{AsyncManager, AsyncResult, run_async_local};
+use serde::{Deserialize, Serialize};
+
+use super::library::Library;
+use super::{SaveSlotMeta, Storage};
+use crate::achievement::{Achievements, Progress};
+
+#[cfg(not(target_family = "wasm"))]
+mod directory;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Config)]
+pub(super) struct Conf {
+    /// Synchronize saves, progress and config profiles on startup and on request.
+    #[config(default = false)]
+    enabled:   bool,
+    /// Directory to synchronize with,
+    /// e.g. a mounted `WebDAV` share or S3-compatible bucket.
+    #[config(default = "")]
+    directory: String,
+}
+
+/// A category of synchronized entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collection {
+    SaveSlots,
+    Progress,
+    ConfigProfiles,
+}
+
+impl Collection {
+    const ALL: [Self; 3] = [Self::SaveSlots, Self::Progress, Self::ConfigProfiles];
+
+    /// Name of the collection in the remote storage.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SaveSlots => "saves",
+            Self::Progress => "progress",
+            Self::ConfigProfiles => "config",
+        }
+    }
+}
+
+/// Key of the achievement progress entry in [`Collection::Progress`].
+pub(super) const PROGRESS_KEY: &str = "achievements";
+
+/// An entry stored in a backend.
+#[derive(Debug, Clone)]
+pub struct RemoteEntry {
+    pub key:      String,
+    pub modified: Timestamp,
+}
+
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = anyhow::Result<T>>>>;
+
+/// Remote storage of synchronized entries.
+pub trait Backend {
+    /// Lists the entries of a collection.
+    fn list(&self, collection: Collection) -> BoxFuture<Vec<RemoteEntry>>;
+
+    /// Reads the data of an entry.
+    fn get(&self, collection: Collection, key: String) -> BoxFuture<Vec<u8>>;
+
+    /// Writes the data of an entry, replacing any existing entry with the same key.
+    fn put(
+        &self,
+        collection: Collection,
+        key: String,
+        modified: Timestamp,
+        data: Vec<u8>,
+    ) -> BoxFuture<()>;
+}
+
+/// Direction of the transfer of a synchronized entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transfer {
+    /// Upload the local entry.
+    Push(String),
+    /// Download the remote entry.
+    Pull(String),
+}
+
+/// Plans the transfers to synchronize a collection.
+///
+/// `local` lists the local keys with their modification time, if known.
+/// The newer copy of each entry wins; entries with equal modification times are left alone.
+#[must_use]
+pub fn plan(local: &[(String, Option<Timestamp>)], remote: &[RemoteEntry]) -> Vec<Transfer> {
+    let remote_times: HashMap<&str, Timestamp> =
+        remote.iter().map(|entry| (entry.key.as_str(), entry.modified)).collect();
+
+    let mut transfers = Vec::new();
+    for (key, local_time) in local {
+        let transfer = match (local_time, remote_times.get(key.as_str())) {
+            (_, None) => Transfer::Push(key.clone()),
+            (None, Some(_)) => Transfer::Pull(key.clone()),
+            (Some(local_time), Some(remote_time)) => match local_time.cmp(remote_time) {
+                std::cmp::Ordering::Greater => Transfer::Push(key.clone()),
+                std::cmp::Ordering::Less => Transfer::Pull(key.clone()),
+                std::cmp::Ordering::Equal => continue,
+            },
+        };
+        transfers.push(transfer);
+    }
+    for entry in remote {
+        if !local.iter().any(|(key, _)| *key == entry.key) {
+            transfers.push(Transfer::Pull(entry.key.clone()));
+        }
+    }
+    transfers
+}
+
+/// Local modification times of entries modified since startup.
+#[derive(Resource, Default)]
+pub struct LocalTimes(HashMap<(Collection, String), Timestamp>);
+
+impl LocalTimes {
+    /// Records that a local entry has just been modified.
+    pub fn mark_modified(&mut self, collection: Collection, key: impl Into<String>) {
+        self.0.insert((collection, key.into()), Timestamp::now());
+    }
+
+    fn get(&self, collection: Collection, key: &str) -> Option<Timestamp> {
+        self.0.get(&(collection, key.to_owned())).copied()
+    }
+}
+
+/// The backend of the ongoing synchronization.
+#[derive(Default)]
+pub(super) struct ActiveBackend(Option<Rc<dyn Backend>>);
+
+/// A save slot with its data, as stored in [`Collection::SaveSlots`].
+#[derive(Serialize, Deserialize)]
+struct SlotPayload {
+    meta: SaveSlotMeta,
+    data: Vec<u8>,
+}
+
+/// An entry downloaded from the backend.
+struct Pulled {
+    collection: Collection,
+    key:        String,
+    data:       Vec<u8>,
+}
+
+fn new_backend(directory: &str) -> anyhow::Result<Rc<dyn Backend>> {
+    anyhow::ensure!(!directory.is_empty(), "no sync directory is configured");
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        Ok(Rc::new(directory::Backend::new(directory.into())))
+    }
+    #[cfg(target_family = "wasm")]
+    {
+        anyhow::bail!("no sync backend is available in browsers")
+    }
+}
+
+/// Synchronizes on startup if enabled.
+pub(super) fn startup_system<S: Storage>(world: &mut World) {
+    let mut state = SystemState::<ReadConfig<Conf>>::new(world);
+    if state.get(world).read().enabled {
+        start::<S>(world);
+    }
+}
+
+/// Synchronizes all collections with the configured backend,
+/// reporting the outcome in the library status.
+pub(super) fn start<S: Storage>(world: &mut World) {
+    let mut state = SystemState::<ReadConfig<Conf>>::new(world);
+    let backend = {
+        let conf = state.get(world);
+        let conf = conf.read();
+        if conf.enabled {
+            new_backend(conf.directory)
+        } else {
+            Err(anyhow::anyhow!("sync is disabled in settings"))
+        }
+    };
+    let backend = match backend {
+        Ok(backend) => backend,
+        Err(err) => {
+            world.resource_mut::<Library>().status = Some(format!("Cannot sync: {err}"));
+            return;
+        }
+    };
+    world.non_send_resource_mut::<ActiveBackend>().0 = Some(Rc::clone(&backend));
+
+    let storage = world.non_send_resource::<S>();
+    let slots = storage.list_save_slots();
+    let profiles = storage.list_config_profiles();
+    let remote = Collection::ALL.map(|collection| backend.list(collection));
+    let fut = async move {
+        let slots = slots.await?;
+        let profiles = profiles.await?;
+        let mut remote_lists = Vec::new();
+        for list in remote {
+            remote_lists.push(list.await?);
+        }
+        anyhow::Ok((slots, profiles, remote_lists))
+    };
+
+    world.resource_scope(|world, mut poll_list: Mut<AsyncManager>| {
+        run_async_local(fut).then(&mut world.commands(), &mut poll_list, transfer_all::<S>);
+    });
+}
+
+type Listing = (Vec<SaveSlotMeta>, Vec<String>, Vec<Vec<RemoteEntry>>);
+
+fn transfer_all<S: Storage>(
+    mut ret: AsyncResult<anyhow::Result<Listing>>,
+    storage: NonSend<S>,
+    backend: NonSend<ActiveBackend>,
+    local_times: Res<LocalTimes>,
+    achievements: Res<Achievements>,
+    mut poll_list: ResMut<AsyncManager>,
+    mut commands: Commands,
+    mut library: ResMut<Library>,
+) {
+    let (slots, profiles, remote_lists) = match ret.get() {
+        Ok(listing) => listing,
+        Err(err) => {
+            bevy::log::error!("Cannot list sync entries: {err:?}");
+            library.status = Some(format!("Cannot sync: {err}"));
+            return;
+        }
+    };
+    let Some(backend) = backend.0.clone() else { return };
+
+    let local: [Vec<_>; 3] = [
+        slots.iter().map(|slot| (slot.id.clone(), Some(slot.saved))).collect(),
+        vec![(PROGRESS_KEY.to_owned(), local_times.get(Collection::Progress, PROGRESS_KEY))],
+        profiles
+            .iter()
+            .map(|name| (name.clone(), local_times.get(Collection::ConfigProfiles, name)))
+            .collect(),
+    ];
+
+    let mut num_transfers = 0;
+    for ((collection, local), remote) in Collection::ALL.into_iter().zip(local).zip(remote_lists) {
+        for transfer in plan(&local, &remote) {
+            num_transfers += 1;
+            let fut: BoxFuture<Option<Pulled>> = match transfer {
+                Transfer::Push(key) => {
+                    let modified = local
+                        .iter()
+                        .find(|(local_key, _)| *local_key == key)
+                        .and_then(|&(_, modified)| modified)
+                        .unwrap_or_else(Timestamp::now);
+                    push(
+                        &*storage,
+                        &backend,
+                        &achievements.progress,
+                        &slots,
+                        collection,
+                        key,
+                        modified,
+                    )
+                }
+                Transfer::Pull(key) => {
+                    let get = backend.get(collection, key.clone());
+                    Box::pin(async move { Ok(Some(Pulled { collection, key, data: get.await? })) })
+                }
+            };
+            run_async_local(fut).then(&mut commands, &mut poll_list, apply::<S>);
+        }
+    }
+    library.status = Some(format!("Synchronizing {num_transfers} entries"));
+}
+
+/// Uploads a local entry.
+fn push<S: Storage>(
+    storage: &S,
+    backend: &Rc<dyn Backend>,
+    progress: &Progress,
+    slots: &[SaveSlotMeta],
+    collection: Collection,
+    key: String,
+    modified: Timestamp,
+) -> BoxFuture<Option<Pulled>> {
+    let backend = Rc::clone(backend);
+    match collection {
+        Collection::SaveSlots => {
+            let meta = slots.iter().find(|slot| slot.id == key).cloned();
+            let load = storage.load_save_slot(key.clone());
+            Box::pin(async move {
+                let meta = meta.context("save slot is not listed")?;
+                let data = load.await.map_err(|err| anyhow::anyhow!("{err:?}"))?;
+                let mut payload = Vec::new();
+                ciborium::into_writer(&SlotPayload { meta, data }, &mut payload)
+                    .context("serialize save slot")?;
+                backend.put(collection, key, modified, payload).await?;
+                Ok(None)
+            })
+        }
+        Collection::Progress => {
+            let data = serde_json::to_vec(progress);
+            Box::pin(async move {
+                let data = data.context("serialize achievement progress")?;
+                backend.put(collection, key, modified, data).await?;
+                Ok(None)
+            })
+        }
+        Collection::ConfigProfiles => {
+            let load = storage.load_config_profile(key.clone());
+            Box::pin(async move {
+                let data = load
+                    .await
+                    .map_err(|err| anyhow::anyhow!("{err:?}"))?
+                    .context("config profile does not exist")?;
+                backend.put(collection, key, modified, data.into_bytes()).await?;
+                Ok(None)
+            })
+        }
+    }
+}
+
+/// Writes a downloaded entry into the local storage.
+fn apply<S: Storage>(
+    mut ret: AsyncResult<anyhow::Result<Option<Pulled>>>,
+    storage: NonSend<S>,
+    mut achievements: ResMut<Achievements>,
+    mut poll_list: ResMut<AsyncManager>,
+    mut commands: Commands,
+    mut library: ResMut<Library>,
+) {
+    let pulled = match ret.get() {
+        Ok(Some(pulled)) => pulled,
+        Ok(None) => return,
+        Err(err) => {
+            bevy::log::error!("Cannot synchronize entry: {err:?}");
+            library.status = Some(format!("Cannot sync: {err}"));
+            return;
+        }
+    };
+
+    let Pulled { collection, key, data } = pulled;
+    let write: Pin<Box<dyn Future<Output = Result<(), S::Error>>>> = match collection {
+        Collection::SaveSlots => match ciborium::from_reader::<SlotPayload, _>(&data[..]) {
+            Ok(SlotPayload { meta, data }) => Box::pin(storage.write_save_slot(meta, data)),
+            Err(err) => {
+                bevy::log::error!("Synchronized save slot {key:?} is corrupted: {err}");
+                return;
+            }
+        },
+        Collection::Progress => {
+            match serde_json::from_slice(&data) {
+                Ok(progress) => {
+                    achievements.progress = progress;
+                    achievements.unsaved = true;
+                }
+                Err(err) => bevy::log::error!("Synchronized progress is corrupted: {err}"),
+            }
+            return;
+        }
+        Collection::ConfigProfiles => match String::from_utf8(data) {
+            Ok(data) => Box::pin(storage.save_config_profile(key.clone(), data)),
+            Err(err) => {
+                bevy::log::error!("Synchronized config profile {key:?} is corrupted: {err}");
+                return;
+            }
+        },
+    };
+
+    run_async_local(write).then(
+        &mut commands,
+        &mut poll_list,
+        move |mut ret: AsyncResult<Result<(), S::Error>>, mut library: ResMut<Library>| {
+            if let Err(err) = ret.get() {
+                bevy::log::error!("Cannot store synchronized {collection:?} {key:?}: {err:?}");
+                return;
+            }
+            library.refresh();
+        },
+    );
+}
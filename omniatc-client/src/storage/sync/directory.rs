@@ -0,0 +1,81 @@
+//! Sync backend writing entries as files in a directory.
+//!
+//! Each collection is a subdirectory, each entry is a file named by its key,
+//! and the modification time of the entry is the modification time of the file.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use anyhow::Context as _;
+use jiff::Timestamp;
+
+use super::{BoxFuture, Collection, RemoteEntry};
+
+pub struct Backend {
+    root: PathBuf,
+}
+
+impl Backend {
+    pub fn new(root: PathBuf) -> Self { Self { root } }
+
+    fn collection_dir(&self, collection: Collection) -> PathBuf {
+        self.root.join(collection.name())
+    }
+}
+
+impl super::Backend for Backend {
+    fn list(&self, collection: Collection) -> BoxFuture<Vec<RemoteEntry>> {
+        let dir = self.collection_dir(collection);
+        let run = (|| {
+            if !dir.exists() {
+                return Ok(Vec::new());
+            }
+
+            let mut entries = Vec::new();
+            for entry in fs::read_dir(&dir).with_context(|| format!("list {}", dir.display()))? {
+                let entry = entry.context("read sync directory entry")?;
+                let Ok(key) = entry.file_name().into_string() else { continue };
+                let modified = entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .with_context(|| format!("read modification time of {key}"))?;
+                let modified =
+                    Timestamp::try_from(modified).context("convert modification time")?;
+                entries.push(RemoteEntry { key, modified });
+            }
+            Ok(entries)
+        })();
+        Box::pin(async move { run })
+    }
+
+    fn get(&self, collection: Collection, key: String) -> BoxFuture<Vec<u8>> {
+        let path = self.collection_dir(collection).join(key);
+        let run = fs::read(&path).with_context(|| format!("read {}", path.display()));
+        Box::pin(async move { run })
+    }
+
+    fn put(
+        &self,
+        collection: Collection,
+        key: String,
+        modified: Timestamp,
+        data: Vec<u8>,
+    ) -> BoxFuture<()> {
+        let dir = self.collection_dir(collection);
+        let run = (|| {
+            fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+            let path = dir.join(key);
+            fs::write(&path, data).with_context(|| format!("write {}", path.display()))?;
+
+            let file = fs::File::options()
+                .write(true)
+                .open(&path)
+                .with_context(|| format!("open {}", path.display()))?;
+            file.set_modified(SystemTime::from(modified))
+                .with_context(|| format!("set modification time of {}", path.display()))?;
+            Ok(())
+        })();
+        Box::pin(async move { run })
+    }
+}
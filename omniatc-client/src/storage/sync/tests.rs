@@ -0,0 +1,42 @@
+use jiff::Timestamp;
+
+use super::{RemoteEntry, Transfer, plan};
+
+fn time(seconds: i64) -> Timestamp { Timestamp::from_second(seconds).unwrap() }
+
+fn remote(key: &str, seconds: i64) -> RemoteEntry {
+    RemoteEntry { key: key.to_owned(), modified: time(seconds) }
+}
+
+#[test]
+fn test_push_missing_remote() {
+    let transfers = plan(&[("a".to_owned(), None)], &[]);
+    assert_eq!(transfers, [Transfer::Push("a".to_owned())]);
+}
+
+#[test]
+fn test_pull_unknown_local_time() {
+    let transfers = plan(&[("a".to_owned(), None)], &[remote("a", 100)]);
+    assert_eq!(transfers, [Transfer::Pull("a".to_owned())]);
+}
+
+#[test]
+fn test_newer_copy_wins() {
+    let transfers = plan(
+        &[("a".to_owned(), Some(time(200))), ("b".to_owned(), Some(time(100)))],
+        &[remote("a", 100), remote("b", 200)],
+    );
+    assert_eq!(transfers, [Transfer::Push("a".to_owned()), Transfer::Pull("b".to_owned())]);
+}
+
+#[test]
+fn test_equal_times_skipped() {
+    let transfers = plan(&[("a".to_owned(), Some(time(100)))], &[remote("a", 100)]);
+    assert!(transfers.is_empty());
+}
+
+#[test]
+fn test_pull_remote_only() {
+    let transfers = plan(&[], &[remote("a", 100)]);
+    assert_eq!(transfers, [Transfer::Pull("a".to_owned())]);
+}
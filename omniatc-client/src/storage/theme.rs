@@ -0,0 +1,114 @@
+//! Color themes loaded from asset files.
+//!
+//! A theme is a JSON object in `assets/themes/{name}.theme.json`
+//! mapping config paths to values, in the same format as config profiles.
+//! Theme values replace the compiled defaults of the listed keys,
+//! but never values customized by the user;
+//! see [`config_profile::apply_theme`] for how the layers interact.
+//! The bundled `default` theme lists every color and defines the default look;
+//! other themes only list the keys they change from the compiled defaults.
+//!
+//! In dev builds, edits to the active theme file are applied as soon as they are saved.
+
+use std::io;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{
+    Asset, AssetEvent, AssetLoadFailedEvent, AssetLoader, AssetServer, Assets, Handle, LoadContext,
+};
+use bevy::ecs::message::{MessageCursor, Messages};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::Local;
+use bevy::ecs::world::World;
+use bevy::reflect::TypePath;
+use bevy::tasks::ConditionalSendFuture;
+use serde_json::{Map, Value};
+
+use super::config_profile;
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the theme loaded when no theme is specified.
+pub const DEFAULT_THEME: &str = "default";
+
+#[derive(Asset, TypePath)]
+pub struct Theme {
+    /// Config values keyed by config path.
+    values: Map<String, Value>,
+}
+
+#[derive(Default, TypePath)]
+pub struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = io::Error;
+
+    fn load(
+        &self,
+        reader: &mut dyn Reader,
+        (): &Self::Settings,
+        _load_context: &mut LoadContext,
+    ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
+        async {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let values = serde_json::from_slice(&bytes)?;
+            Ok(Theme { values })
+        }
+    }
+
+    fn extensions(&self) -> &[&str] { &["theme.json"] }
+}
+
+/// The theme selected on startup.
+#[derive(Resource)]
+pub struct Active {
+    pub name: String,
+    handle:   Option<Handle<Theme>>,
+}
+
+impl Active {
+    pub fn new(name: String) -> Self { Self { name, handle: None } }
+}
+
+impl Default for Active {
+    fn default() -> Self { Self::new(DEFAULT_THEME.into()) }
+}
+
+/// Records the compiled defaults and starts loading the active theme.
+pub(super) fn startup_system(world: &mut World) {
+    config_profile::record_defaults(world);
+
+    let path = format!("themes/{}.theme.json", world.resource::<Active>().name);
+    let handle = world.resource::<AssetServer>().load(path);
+    world.resource_mut::<Active>().handle = Some(handle);
+}
+
+/// Applies the active theme whenever it is loaded or its file is modified.
+pub(super) fn apply_system(
+    world: &mut World,
+    mut cursor: Local<MessageCursor<AssetEvent<Theme>>>,
+    mut failed_cursor: Local<MessageCursor<AssetLoadFailedEvent<Theme>>>,
+) {
+    let Some(handle) = world.resource::<Active>().handle.clone() else { return };
+
+    for event in failed_cursor.read(world.resource::<Messages<AssetLoadFailedEvent<Theme>>>()) {
+        if event.id == handle.id() {
+            bevy::log::error!("Cannot load theme {}: {}", event.path, event.error);
+        }
+    }
+
+    let changed = cursor.read(world.resource::<Messages<AssetEvent<Theme>>>()).any(|event| {
+        matches!(event, AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } if *id == handle.id())
+    });
+    if !changed {
+        return;
+    }
+
+    let Some(theme) = world.resource::<Assets<Theme>>().get(&handle) else { return };
+    let values = theme.values.clone();
+    config_profile::apply_theme(world, values);
+}
@@ -0,0 +1,44 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::color::Color;
+use bevy::render::settings::WgpuSettings;
+use serde_json::{Map, Value};
+
+use crate::storage::config_profile;
+
+#[test]
+fn test_bundled_themes_contain_known_keys() {
+    let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("assets");
+    let mut app = crate::build_app(
+        crate::Options {
+            assets_dir:       assets_dir.to_string_lossy().into_owned(),
+            headless_test:    true,
+            open_level_id:    None,
+            default_scenario: String::new(),
+            theme:            super::DEFAULT_THEME.into(),
+        },
+        WgpuSettings { backends: None, ..Default::default() },
+    );
+    let keys = config_profile::snapshot(app.world_mut());
+
+    for entry in fs::read_dir(assets_dir.join("themes")).unwrap() {
+        let path = entry.unwrap().path();
+        let theme: Map<String, Value> = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        for key in theme.keys() {
+            assert!(
+                keys.contains_key(key),
+                "{} contains unknown config key {key:?}",
+                path.display()
+            );
+        }
+
+        if path.file_name() == Some(format!("{}.theme.json", super::DEFAULT_THEME).as_ref()) {
+            for (key, value) in &keys {
+                if serde_json::from_value::<Color>(value.clone()).is_ok() {
+                    assert!(theme.contains_key(key), "default theme is missing color {key:?}");
+                }
+            }
+        }
+    }
+}
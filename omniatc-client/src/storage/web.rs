@@ -11,9 +11,12 @@ use js_sys::JsString;
 use serde::{Deserialize, Serialize};
 use serde_with::base64::Base64;
 use serde_with::serde_as;
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsError, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlAnchorElement, HtmlInputElement};
 
-use super::{LevelMeta, ScenarioMeta};
+use super::{LevelMeta, SaveSlotMeta, ScenarioMeta};
 
 #[derive(Default)]
 pub struct Impl {
@@ -22,6 +25,9 @@ pub struct Impl {
 
 const DB_NAME: &str = "omniatc-index";
 
+/// Key of the global achievement progress record.
+const ACHIEVEMENT_KEY: &str = "global";
+
 impl super::Storage for Impl {
     type Error = anyhow::Error;
 
@@ -42,33 +48,25 @@ impl super::Storage for Impl {
                 .context("create transaction")?;
             let ids = {
                 let store = tx.object_store("scenario_tag").anyhow().context("get tag store")?;
-                let index = store.index("kv").anyhow().context("get kv index")?;
-                let cursor = index
-                    .open_cursor(None, Some(idb::CursorDirection::Next))
+                let values = store
+                    .get_all(None, None)
                     .anyhow()
-                    .context("open kv cursor")?
+                    .context("list scenario tags")?
                     .await
                     .anyhow()
-                    .context("open kv cursor")?;
-
-                let Some(cursor) = cursor else { return Ok(Vec::new()) };
-
-                let mut ids = Vec::new();
+                    .context("list scenario tags")?;
 
-                while let Ok(value) = cursor.value() {
+                let mut entries = Vec::new();
+                for value in values {
                     let entry: TagKv = serde_wasm_bindgen::from_value(value)
                         .anyhow()
                         .context("convert js value to TagKv")?;
-                    ids.push(entry.id.clone());
-                    cursor
-                        .next(None)
-                        .anyhow()
-                        .context("advance tag cursor")?
-                        .await
-                        .anyhow()
-                        .context("advance tag cursor")?;
+                    if entry.tag_key == tag_key {
+                        entries.push(entry);
+                    }
                 }
-                ids
+                entries.sort_by(|a, b| a.tag_value.cmp(&b.tag_value));
+                entries.into_iter().map(|entry| entry.id).collect::<Vec<_>>()
             };
 
             let store = tx.object_store("scenario").anyhow().context("get scenario store")?;
@@ -159,7 +157,7 @@ impl super::Storage for Impl {
                 let value = serde_wasm_bindgen::to_value(&Data { id: meta.id.to_string(), data })
                     .anyhow()
                     .context("convert Data to js value")?;
-                store.add(&value, None).anyhow().context("add scenario to meta store")?;
+                store.put(&value, None).anyhow().context("put scenario to data store")?;
             }
 
             {
@@ -172,8 +170,8 @@ impl super::Storage for Impl {
                         tag_value,
                     })
                     .anyhow()
-                    .context("convert Data to js value")?;
-                    store.add(&value, None).anyhow().context("add scenario to meta store")?;
+                    .context("convert TagKv to js value")?;
+                    store.put(&value, None).anyhow().context("put scenario tag")?;
                 }
             }
 
@@ -182,7 +180,7 @@ impl super::Storage for Impl {
                 let value = serde_wasm_bindgen::to_value(&meta)
                     .anyhow()
                     .context("convert ScenarioMeta to js value")?;
-                store.add(&value, None).anyhow().context("add scenario to meta store")?;
+                store.put(&value, None).anyhow().context("put scenario to meta store")?;
             }
 
             tx.commit().anyhow().context("commit transaction")?;
@@ -268,6 +266,410 @@ impl super::Storage for Impl {
             Ok(data.data)
         }
     }
+
+    fn insert_level(
+        &self,
+        meta: LevelMeta,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["level", "level_data"], TransactionMode::ReadWrite)
+                .anyhow()
+                .context("create transaction")?;
+
+            {
+                let store =
+                    tx.object_store("level_data").anyhow().context("get level_data store")?;
+                let value = serde_wasm_bindgen::to_value(&Data { id: meta.id.clone(), data })
+                    .anyhow()
+                    .context("convert Data to js value")?;
+                store.put(&value, None).anyhow().context("put level to data store")?;
+            }
+
+            {
+                let store = tx.object_store("level").anyhow().context("get level store")?;
+                let value = serde_wasm_bindgen::to_value(&meta)
+                    .anyhow()
+                    .context("convert LevelMeta to js value")?;
+                store.put(&value, None).anyhow().context("put level to meta store")?;
+            }
+
+            tx.commit().anyhow().context("commit transaction")?;
+            Ok(())
+        }
+    }
+
+    fn list_save_slots(&self) -> impl Future<Output = anyhow::Result<Vec<SaveSlotMeta>>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["save_slot"], TransactionMode::ReadOnly)
+                .anyhow()
+                .context("create transaction")?;
+            let store = tx.object_store("save_slot").anyhow().context("get save_slot store")?;
+            let values = store
+                .get_all(None, None)
+                .anyhow()
+                .context("list save slots")?
+                .await
+                .anyhow()
+                .context("list save slots")?;
+
+            let mut output = Vec::with_capacity(values.len());
+            for value in values {
+                let meta: SaveSlotMeta = serde_wasm_bindgen::from_value(value)
+                    .anyhow()
+                    .context("convert js value to SaveSlotMeta")?;
+                output.push(meta);
+            }
+            output.sort_by_key(|slot| std::cmp::Reverse(slot.saved));
+
+            tx.await.anyhow().context("transaction close")?;
+            Ok(output)
+        }
+    }
+
+    fn load_save_slot(
+        &self,
+        id: String,
+    ) -> impl Future<Output = anyhow::Result<Vec<u8>>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["save_slot_data"], TransactionMode::ReadOnly)
+                .anyhow()
+                .context("create transaction")?;
+            let store =
+                tx.object_store("save_slot_data").anyhow().context("get save_slot_data store")?;
+
+            let value = store
+                .get(idb::Query::Key(JsString::from(id.as_str()).into()))
+                .anyhow()
+                .context("fetch by id from save_slot_data store")?
+                .await
+                .anyhow()?
+                .context("save slot does not exist")?;
+            let data: Data = serde_wasm_bindgen::from_value(value)
+                .anyhow()
+                .context("convert js value to Data")?;
+
+            tx.await.anyhow().context("transaction close")?;
+            Ok(data.data)
+        }
+    }
+
+    fn write_save_slot(
+        &self,
+        meta: SaveSlotMeta,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["save_slot", "save_slot_data"], TransactionMode::ReadWrite)
+                .anyhow()
+                .context("create transaction")?;
+
+            {
+                let store = tx
+                    .object_store("save_slot_data")
+                    .anyhow()
+                    .context("get save_slot_data store")?;
+                let value = serde_wasm_bindgen::to_value(&Data { id: meta.id.clone(), data })
+                    .anyhow()
+                    .context("convert Data to js value")?;
+                store.put(&value, None).anyhow().context("put save slot to data store")?;
+            }
+
+            {
+                let store = tx.object_store("save_slot").anyhow().context("get save_slot store")?;
+                let value = serde_wasm_bindgen::to_value(&meta)
+                    .anyhow()
+                    .context("convert SaveSlotMeta to js value")?;
+                store.put(&value, None).anyhow().context("put save slot to meta store")?;
+            }
+
+            tx.commit().anyhow().context("commit transaction")?;
+            Ok(())
+        }
+    }
+
+    fn delete_save_slot(&self, id: String) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["save_slot", "save_slot_data"], TransactionMode::ReadWrite)
+                .anyhow()
+                .context("create transaction")?;
+            for store_name in ["save_slot", "save_slot_data"] {
+                let store = tx
+                    .object_store(store_name)
+                    .anyhow()
+                    .with_context(|| format!("get {store_name} store"))?;
+                store
+                    .delete(idb::Query::Key(JsString::from(id.as_str()).into()))
+                    .anyhow()
+                    .with_context(|| format!("delete from {store_name} store"))?;
+            }
+
+            tx.commit().anyhow().context("commit transaction")?;
+            Ok(())
+        }
+    }
+
+    fn list_config_profiles(&self) -> impl Future<Output = anyhow::Result<Vec<String>>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["config_profile"], TransactionMode::ReadOnly)
+                .anyhow()
+                .context("create transaction")?;
+            let store =
+                tx.object_store("config_profile").anyhow().context("get config_profile store")?;
+            let keys = store
+                .get_all_keys(None, None)
+                .anyhow()
+                .context("list config_profile keys")?
+                .await
+                .anyhow()
+                .context("list config_profile keys")?;
+
+            tx.await.anyhow().context("transaction close")?;
+            Ok(keys.into_iter().filter_map(|key| key.as_string()).collect())
+        }
+    }
+
+    fn load_config_profile(
+        &self,
+        name: String,
+    ) -> impl Future<Output = anyhow::Result<Option<String>>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["config_profile"], TransactionMode::ReadOnly)
+                .anyhow()
+                .context("create transaction")?;
+            let store =
+                tx.object_store("config_profile").anyhow().context("get config_profile store")?;
+
+            let value = store
+                .get(idb::Query::Key(JsString::from(name.as_str()).into()))
+                .anyhow()
+                .context("fetch by name from config_profile store")?
+                .await
+                .anyhow()?;
+            let profile = value
+                .map(|value| {
+                    serde_wasm_bindgen::from_value::<ConfigProfile>(value)
+                        .anyhow()
+                        .context("convert js value to ConfigProfile")
+                })
+                .transpose()?;
+
+            tx.await.anyhow().context("transaction close")?;
+            Ok(profile.map(|profile| profile.data))
+        }
+    }
+
+    fn save_config_profile(
+        &self,
+        name: String,
+        data: String,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["config_profile"], TransactionMode::ReadWrite)
+                .anyhow()
+                .context("create transaction")?;
+            let store =
+                tx.object_store("config_profile").anyhow().context("get config_profile store")?;
+            let value = serde_wasm_bindgen::to_value(&ConfigProfile { name, data })
+                .anyhow()
+                .context("convert ConfigProfile to js value")?;
+            store.put(&value, None).anyhow().context("put config profile")?;
+
+            tx.commit().anyhow().context("commit transaction")?;
+            Ok(())
+        }
+    }
+
+    fn load_achievements(&self) -> impl Future<Output = anyhow::Result<Option<String>>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["achievement"], TransactionMode::ReadOnly)
+                .anyhow()
+                .context("create transaction")?;
+            let store = tx.object_store("achievement").anyhow().context("get achievement store")?;
+
+            let value = store
+                .get(idb::Query::Key(JsString::from(ACHIEVEMENT_KEY).into()))
+                .anyhow()
+                .context("fetch from achievement store")?
+                .await
+                .anyhow()?;
+            let achievement = value
+                .map(|value| {
+                    serde_wasm_bindgen::from_value::<Achievement>(value)
+                        .anyhow()
+                        .context("convert js value to Achievement")
+                })
+                .transpose()?;
+
+            tx.await.anyhow().context("transaction close")?;
+            Ok(achievement.map(|achievement| achievement.data))
+        }
+    }
+
+    fn save_achievements(
+        &self,
+        data: String,
+    ) -> impl Future<Output = anyhow::Result<()>> + 'static {
+        let db = self.db.clone();
+        async move {
+            let db = {
+                let mut db = db.lock().await;
+                get_db(&mut db).await?
+            };
+
+            let tx = db
+                .transaction(&["achievement"], TransactionMode::ReadWrite)
+                .anyhow()
+                .context("create transaction")?;
+            let store = tx.object_store("achievement").anyhow().context("get achievement store")?;
+            let value =
+                serde_wasm_bindgen::to_value(&Achievement { name: ACHIEVEMENT_KEY.into(), data })
+                    .anyhow()
+                    .context("convert Achievement to js value")?;
+            store.put(&value, None).anyhow().context("put achievement")?;
+
+            tx.commit().anyhow().context("commit transaction")?;
+            Ok(())
+        }
+    }
+
+    fn pick_import_file(
+        &self,
+        _path: String,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<u8>>>> + 'static {
+        async move {
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .context("no document available")?;
+            let input: HtmlInputElement = document
+                .create_element("input")
+                .anyhow()
+                .context("create file input")?
+                .dyn_into()
+                .map_err(|_| anyhow::anyhow!("created element is not an input"))?;
+            input.set_type("file");
+            input.set_accept(".osav");
+
+            // Resolves with `true` when a file is selected and `false` when cancelled.
+            let selected = js_sys::Promise::new(&mut |resolve, _reject| {
+                let on_change = Closure::once_into_js({
+                    let resolve = resolve.clone();
+                    move || resolve.call1(&JsValue::NULL, &JsValue::TRUE)
+                });
+                let on_cancel =
+                    Closure::once_into_js(move || resolve.call1(&JsValue::NULL, &JsValue::FALSE));
+                input.set_onchange(Some(on_change.unchecked_ref()));
+                input.set_oncancel(Some(on_cancel.unchecked_ref()));
+            });
+            input.click();
+
+            let selected = JsFuture::from(selected).await.anyhow().context("pick file")?;
+            if !selected.is_truthy() {
+                return Ok(None);
+            }
+
+            let Some(file) = input.files().and_then(|files| files.get(0)) else { return Ok(None) };
+            let buffer =
+                JsFuture::from(file.array_buffer()).await.anyhow().context("read picked file")?;
+            Ok(Some(js_sys::Uint8Array::new(&buffer).to_vec()))
+        }
+    }
+
+    fn export_file(
+        &self,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> impl Future<Output = anyhow::Result<String>> + 'static {
+        async move {
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .context("no document available")?;
+
+            let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(&data[..]));
+            let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)
+                .anyhow()
+                .context("create blob")?;
+            let url = web_sys::Url::create_object_url_with_blob(&blob)
+                .anyhow()
+                .context("create object url")?;
+
+            let anchor: HtmlAnchorElement = document
+                .create_element("a")
+                .anyhow()
+                .context("create download anchor")?
+                .dyn_into()
+                .map_err(|_| anyhow::anyhow!("created element is not an anchor"))?;
+            anchor.set_href(&url);
+            anchor.set_download(&file_name);
+            anchor.click();
+
+            web_sys::Url::revoke_object_url(&url).anyhow().context("revoke object url")?;
+            Ok("browser downloads".into())
+        }
+    }
 }
 
 async fn get_db(db: &mut Option<Rc<idb::Database>>) -> anyhow::Result<Rc<idb::Database>> {
@@ -281,7 +683,7 @@ async fn get_db(db: &mut Option<Rc<idb::Database>>) -> anyhow::Result<Rc<idb::Da
 
 async fn new_db() -> anyhow::Result<idb::Database> {
     let factory = idb::Factory::new().anyhow().context("new idb factory")?;
-    let mut open = factory.open("omniatc", Some(1)).anyhow().context("open omniatc idb")?;
+    let mut open = factory.open("omniatc", Some(4)).anyhow().context("open omniatc idb")?;
 
     open.on_upgrade_needed(|event| {
         if let Err(err) = migrate_db(event) {
@@ -294,7 +696,57 @@ async fn new_db() -> anyhow::Result<idb::Database> {
 
 fn migrate_db(event: VersionChangeEvent) -> anyhow::Result<()> {
     let database = event.database().unwrap();
+    let old_version = event.old_version().anyhow().context("get old idb version")?;
 
+    if old_version < 1 {
+        migrate_db_v1(&database)?;
+    }
+
+    if old_version < 2 {
+        database
+            .create_object_store("config_profile", {
+                let mut params = ObjectStoreParams::new();
+                params.key_path(Some(idb::KeyPath::new_single("name")));
+                params
+            })
+            .anyhow()
+            .context("create config_profile store")?;
+    }
+
+    if old_version < 3 {
+        database
+            .create_object_store("achievement", {
+                let mut params = ObjectStoreParams::new();
+                params.key_path(Some(idb::KeyPath::new_single("name")));
+                params
+            })
+            .anyhow()
+            .context("create achievement store")?;
+    }
+
+    if old_version < 4 {
+        database
+            .create_object_store("save_slot", {
+                let mut params = ObjectStoreParams::new();
+                params.key_path(Some(idb::KeyPath::new_single("id")));
+                params
+            })
+            .anyhow()
+            .context("create save_slot store")?;
+        database
+            .create_object_store("save_slot_data", {
+                let mut params = ObjectStoreParams::new();
+                params.key_path(Some(idb::KeyPath::new_single("id")));
+                params
+            })
+            .anyhow()
+            .context("create save_slot_data store")?;
+    }
+
+    Ok(())
+}
+
+fn migrate_db_v1(database: &idb::Database) -> anyhow::Result<()> {
     let scenario_store = database
         .create_object_store("scenario", {
             let mut params = ObjectStoreParams::new();
@@ -372,6 +824,18 @@ fn migrate_db(event: VersionChangeEvent) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[derive(Serialize, Deserialize)]
+struct ConfigProfile {
+    name: String,
+    data: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Achievement {
+    name: String,
+    data: String,
+}
+
 #[derive(Serialize, Deserialize)]
 struct TagKv {
     id:        String,
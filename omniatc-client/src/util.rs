@@ -5,6 +5,7 @@ use bevy::transform::components::GlobalTransform;
 use math::{Angle, Heading, Length};
 
 pub mod billboard;
+pub mod palette;
 pub mod shapes;
 
 macro_rules! new_type_id {
@@ -10,6 +10,9 @@ use math::Length;
 use crate::render::{self};
 use crate::util::ActiveCamera2d;
 
+#[cfg(test)]
+mod tests;
+
 pub struct Plug;
 
 impl Plugin for Plug {
@@ -29,6 +32,34 @@ pub struct MaintainScale {
     pub size: f32,
 }
 
+/// Multiplies the size of a [`MaintainScale`] entity depending on the camera zoom,
+/// so that it is larger when zoomed in and smaller when zoomed out.
+#[derive(Component, Clone, Copy)]
+pub struct ZoomBands {
+    /// Camera scale, in world distance per screen pixel,
+    /// below which the size is multiplied by `near_factor`.
+    pub near_threshold: Length<f32>,
+    pub near_factor:    f32,
+    /// Camera scale, in world distance per screen pixel,
+    /// above which the size is multiplied by `far_factor`.
+    pub far_threshold:  Length<f32>,
+    pub far_factor:     f32,
+}
+
+impl ZoomBands {
+    /// Returns the size multiplier at the camera scale `scale`.
+    #[must_use]
+    pub fn factor(&self, scale: Length<f32>) -> f32 {
+        if scale < self.near_threshold {
+            self.near_factor
+        } else if scale > self.far_threshold {
+            self.far_factor
+        } else {
+            1.0
+        }
+    }
+}
+
 /// Entities with this component always have the same orientation regardless of camera rotation.
 #[derive(Component)]
 #[require(Transform)]
@@ -57,10 +88,12 @@ fn translate_label_system(
 
 fn maintain_scale_system(
     camera: ActiveCamera2d,
-    mut query: Query<(&MaintainScale, &mut Transform)>,
+    mut query: Query<(&MaintainScale, Option<&ZoomBands>, &mut Transform)>,
 ) {
-    query.iter_mut().for_each(|(maintain, mut tf)| {
-        let scale = camera.scale() * maintain.size;
+    let camera_scale = camera.scale();
+    query.iter_mut().for_each(|(maintain, bands, mut tf)| {
+        let factor = bands.map_or(1.0, |bands| bands.factor(Length::from_nm(camera_scale)));
+        let scale = camera_scale * maintain.size * factor;
         tf.scale = Vec3::new(scale, scale, 1.0);
     });
 }
@@ -0,0 +1,17 @@
+use math::Length;
+
+use super::ZoomBands;
+
+#[test]
+fn test_zoom_band_factor() {
+    let bands = ZoomBands {
+        near_threshold: Length::from_nm(0.01),
+        near_factor:    1.5,
+        far_threshold:  Length::from_nm(0.2),
+        far_factor:     0.5,
+    };
+
+    assert!((bands.factor(Length::from_nm(0.005)) - 1.5).abs() < 1e-6);
+    assert!((bands.factor(Length::from_nm(0.05)) - 1.0).abs() < 1e-6);
+    assert!((bands.factor(Length::from_nm(0.5)) - 0.5).abs() < 1e-6);
+}
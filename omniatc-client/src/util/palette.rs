@@ -0,0 +1,63 @@
+//! Shared color materials.
+//!
+//! Entities with the same mesh and material are drawn in a single instanced batch,
+//! so renderers spawning many small meshes in few distinct colors,
+//! e.g. track points and separation rings,
+//! take their materials from the [`Palette`] instead of creating one material per entity.
+
+use std::collections::HashMap;
+
+use bevy::app::{App, Plugin};
+use bevy::asset::{Assets, Handle};
+use bevy::color::{Color, ColorToPacked};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{ResMut, SystemParam};
+use bevy::sprite_render::ColorMaterial;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) { app.init_resource::<Palette>(); }
+}
+
+/// Color materials keyed by their 8-bit sRGBA color.
+#[derive(Default, Resource)]
+pub struct Palette {
+    materials: HashMap<[u8; 4], Handle<ColorMaterial>>,
+}
+
+#[derive(SystemParam)]
+pub struct PaletteParam<'w> {
+    palette: ResMut<'w, Palette>,
+    assets:  ResMut<'w, Assets<ColorMaterial>>,
+}
+
+impl PaletteParam<'_> {
+    /// Returns the shared material of `color`, creating it if necessary.
+    pub fn material(&mut self, color: Color) -> Handle<ColorMaterial> {
+        let key = color.to_srgba().to_u8_array();
+        self.palette
+            .materials
+            .entry(key)
+            .or_insert_with(|| {
+                self.assets
+                    .add(ColorMaterial::from_color(Color::srgba_u8(key[0], key[1], key[2], key[3])))
+            })
+            .clone()
+    }
+}
+
+/// Rounds `ratio` to the nearest of `steps` evenly spaced levels within `[0, 1]`,
+/// so that continuous color gradients map to a bounded number of materials.
+#[must_use]
+pub fn quantize(ratio: f32, steps: u32) -> f32 {
+    if steps <= 1 {
+        return ratio.clamp(0., 1.);
+    }
+    #[expect(clippy::cast_precision_loss, reason = "steps is a small config value")]
+    let max = (steps - 1) as f32;
+    (ratio.clamp(0., 1.) * max).round() / max
+}
@@ -0,0 +1,33 @@
+use bevy::app::App;
+use bevy::asset::{AssetApp, AssetPlugin};
+use bevy::color::Color;
+use bevy::ecs::system::SystemState;
+use bevy::sprite_render::ColorMaterial;
+
+use super::{Palette, PaletteParam, quantize};
+
+#[test]
+#[expect(clippy::float_cmp, reason = "quantized levels are exactly representable")]
+fn test_quantize() {
+    assert_eq!(quantize(0.3, 5), 0.25);
+    assert_eq!(quantize(0.9, 5), 1.0);
+    assert_eq!(quantize(-1.0, 5), 0.0);
+    assert_eq!(quantize(0.3, 1), 0.3);
+}
+
+#[test]
+fn test_material_shared_by_color() {
+    let mut app = App::new();
+    app.add_plugins(AssetPlugin::default());
+    app.init_asset::<ColorMaterial>();
+    app.init_resource::<Palette>();
+
+    let mut state = SystemState::<PaletteParam>::new(app.world_mut());
+    let mut palette = state.get_mut(app.world_mut());
+    let red = palette.material(Color::srgb(1., 0., 0.));
+    let red_again = palette.material(Color::srgb(1., 0., 0.));
+    let blue = palette.material(Color::srgb(0., 0., 1.));
+
+    assert_eq!(red, red_again);
+    assert_ne!(red, blue);
+}
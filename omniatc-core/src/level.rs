@@ -9,22 +9,59 @@ use itertools::Itertools;
 use strum::IntoEnumIterator;
 
 pub mod aerodrome;
+pub mod airway;
+pub mod bird;
+pub mod clock;
 pub mod conflict;
+pub mod crossing;
+pub mod deadlock;
+pub mod deice;
+pub mod departure_queue;
 pub mod dest;
+pub mod deviation;
+pub mod divert;
+pub mod drift;
+pub mod equipage;
+pub mod facility;
+pub mod fog;
+pub mod formation;
+pub mod frequency;
+pub mod fuel;
+pub mod goaround;
 pub mod ground;
+pub mod hold;
+pub mod icing;
 pub mod index;
 pub mod instr;
 pub mod message;
 pub mod nav;
 pub mod navaid;
+pub mod note;
 pub mod object;
+pub mod phraseology;
+pub mod pilot;
+pub mod pilot_request;
 pub mod plane;
 pub mod quest;
+pub mod radio;
+pub mod rescue;
 pub mod route;
 pub mod runway;
 pub mod score;
+pub mod script;
+pub mod sector;
+pub mod session;
 pub mod spawn;
+pub mod surface;
 pub mod taxi;
+pub mod terrain;
+#[cfg(test)]
+mod test_util;
+pub mod track;
+pub mod transition;
+pub mod transponder;
+pub mod turbulence;
+pub mod vfr;
 pub mod wake;
 pub mod waypoint;
 pub mod weather;
@@ -42,12 +79,37 @@ where
     wake::Conf: ConfigFieldFor<M>,
     weather::Conf: ConfigFieldFor<M>,
     instr::Conf: ConfigFieldFor<M>,
+    phraseology::Conf: ConfigFieldFor<M>,
+    frequency::Conf: ConfigFieldFor<M>,
+    pilot::Conf: ConfigFieldFor<M>,
+    deviation::Conf: ConfigFieldFor<M>,
+    pilot_request::Conf: ConfigFieldFor<M>,
+    fuel::Conf: ConfigFieldFor<M>,
+    divert::Conf: ConfigFieldFor<M>,
+    goaround::Conf: ConfigFieldFor<M>,
+    taxi::Conf: ConfigFieldFor<M>,
+    crossing::Conf: ConfigFieldFor<M>,
+    hold::Conf: ConfigFieldFor<M>,
+    vfr::Conf: ConfigFieldFor<M>,
+    formation::Conf: ConfigFieldFor<M>,
+    drift::Conf: ConfigFieldFor<M>,
+    bird::Conf: ConfigFieldFor<M>,
+    surface::Conf: ConfigFieldFor<M>,
+    turbulence::Conf: ConfigFieldFor<M>,
+    icing::Conf: ConfigFieldFor<M>,
+    fog::Conf: ConfigFieldFor<M>,
+    transition::Conf: ConfigFieldFor<M>,
+    transponder::Conf: ConfigFieldFor<M>,
+    radio::Conf: ConfigFieldFor<M>,
+    rescue::Conf: ConfigFieldFor<M>,
+    clock::Conf: ConfigFieldFor<M>,
 {
     fn build(&self, app: &mut App) {
         SystemSets::configure_ordering(app);
 
         app.add_plugins(message::Plug);
         app.add_plugins(score::Plug);
+        app.add_plugins(session::Plug);
         app.add_plugins(quest::Plug);
         app.add_plugins(aerodrome::Plug);
         app.add_plugins(object::Plug::<M>::default());
@@ -55,16 +117,49 @@ where
         app.add_plugins(plane::Plug);
         app.add_plugins(nav::Plug);
         app.add_plugins(navaid::Plug);
+        app.add_plugins(note::Plug);
         app.add_plugins(route::Plug);
         app.add_plugins(instr::Plug::<M>::default());
+        app.add_plugins(phraseology::Plug::<M>::default());
+        app.add_plugins(frequency::Plug::<M>::default());
+        app.add_plugins(facility::Plug);
+        app.add_plugins(pilot::Plug::<M>::default());
+        app.add_plugins(deviation::Plug::<M>::default());
+        app.add_plugins(pilot_request::Plug::<M>::default());
         app.add_plugins(runway::Plug);
         app.add_plugins(waypoint::Plug);
+        app.add_plugins(airway::Plug);
+        app.add_plugins(sector::Plug);
         app.add_plugins(ground::Plug);
-        app.add_plugins(taxi::Plug);
+        app.add_plugins(taxi::Plug::<M>::default());
+        app.add_plugins(crossing::Plug::<M>::default());
+        app.add_plugins(deadlock::Plug);
+        app.add_plugins(departure_queue::Plug);
+        app.add_plugins(terrain::Plug);
+        app.add_plugins(deice::Plug);
         app.add_plugins(weather::Plug::<M>::default());
         app.add_plugins(dest::Plug);
+        app.add_plugins(fuel::Plug::<M>::default());
+        app.add_plugins(divert::Plug::<M>::default());
+        app.add_plugins(goaround::Plug::<M>::default());
+        app.add_plugins(hold::Plug::<M>::default());
+        app.add_plugins(vfr::Plug::<M>::default());
+        app.add_plugins(formation::Plug::<M>::default());
+        app.add_plugins(drift::Plug::<M>::default());
+        app.add_plugins(bird::Plug::<M>::default());
+        app.add_plugins(surface::Plug::<M>::default());
+        app.add_plugins(turbulence::Plug::<M>::default());
+        app.add_plugins(icing::Plug::<M>::default());
+        app.add_plugins(fog::Plug::<M>::default());
+        app.add_plugins(transition::Plug::<M>::default());
+        app.add_plugins(transponder::Plug::<M>::default());
+        app.add_plugins(radio::Plug::<M>::default());
+        app.add_plugins(rescue::Plug::<M>::default());
+        app.add_plugins(clock::Plug::<M>::default());
         app.add_plugins(wake::Plug::<M>::default());
         app.add_plugins(spawn::Plug);
+        app.add_plugins(script::Plug);
+        app.add_plugins(track::Plug);
     }
 }
 
@@ -7,6 +7,7 @@ use bevy::ecs::relationship::{RelatedSpawner, Relationship};
 use bevy::ecs::system::EntityCommand;
 use bevy::ecs::world::World;
 use bevy::math::Vec2;
+use bevy::time::{self, Time};
 use itertools::Itertools;
 use math::sweep::{self, LineSweeper};
 use math::{Angle, Heading, Length, Position, Speed};
@@ -16,7 +17,7 @@ use super::Aerodrome;
 use crate::level::navaid::{self, Navaid};
 use crate::level::runway::{self, Runway};
 use crate::level::waypoint::{self, Waypoint};
-use crate::level::{aerodrome, ground};
+use crate::level::{aerodrome, deice, ground, message};
 use crate::load::{self, StoredEntity};
 
 pub const APRON_FORWARD_HEADING_DIRECTION: ground::SegmentDirection =
@@ -69,6 +70,12 @@ pub fn spawn(
                     runway_pair.forward_start,
                     aerodrome_entity,
                 );
+                for spawned in [&forward, &backward] {
+                    if let Some(mut condition) = world.get_mut::<runway::Condition>(spawned.runway)
+                    {
+                        condition.surface = runway_pair.surface;
+                    }
+                }
                 runway_entities.insert(
                     runway_pair.forward.name.clone(),
                     PairedSpawnedRunway {
@@ -87,6 +94,10 @@ pub fn spawn(
                 );
             }
 
+            spawn_closures(world, aerodrome, &runway_entities)?;
+            let configurations =
+                spawn_configurations(world, aerodrome, &runway_entities, aerodrome_entity)?;
+
             let spawned_segments = spawn_ground_segments(
                 world,
                 &aerodrome.ground_network,
@@ -96,12 +107,20 @@ pub fn spawn(
                 aerodrome.elevation,
             )?;
 
+            for taxiway in aerodrome.ground_network.taxiways.iter().filter(|t| t.deicing_pad) {
+                let label = ground::SegmentLabel::Taxiway { name: taxiway.name.clone() };
+                for segment in spawned_segments.get(&label).into_iter().flatten() {
+                    world.entity_mut(segment.entity).insert(deice::Pad);
+                }
+            }
+
             Ok((
                 aerodrome.code.clone(),
                 SpawnedAerodrome {
                     aerodrome_entity,
                     index: aerodrome_id,
                     runways: runway_entities,
+                    configurations,
                     spawned_segments,
                 },
             ))
@@ -110,6 +129,72 @@ pub fn spawn(
         .map(AerodromeMap)
 }
 
+/// Spawns the scheduled runway closures of an aerodrome.
+///
+/// Closure times are relative to the current virtual time.
+fn spawn_closures(
+    world: &mut World,
+    aerodrome: &store::Aerodrome,
+    runway_entities: &HashMap<String, PairedSpawnedRunway>,
+) -> Result<(), load::Error> {
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    for closure in &aerodrome.closures {
+        let runway =
+            runway_entities.get(&closure.runway).ok_or_else(|| load::Error::UnresolvedRunway {
+                aerodrome: aerodrome.code.clone(),
+                runway:    closure.runway.clone(),
+            })?;
+        world.spawn((
+            StoredEntity,
+            Name::new(format!("Runway closure: {}/{}", aerodrome.code, closure.runway)),
+            runway::Closure {
+                runway: runway.runway.runway,
+                kind:   closure.kind,
+                start:  now.saturating_add(closure.start),
+                end:    now.saturating_add(closure.start).saturating_add(closure.duration),
+            },
+        ));
+    }
+    Ok(())
+}
+
+/// Spawns the runway configurations of an aerodrome in order of preference.
+fn spawn_configurations(
+    world: &mut World,
+    aerodrome: &store::Aerodrome,
+    runway_entities: &HashMap<String, PairedSpawnedRunway>,
+    aerodrome_entity: Entity,
+) -> Result<HashMap<String, Entity>, load::Error> {
+    let mut configurations = HashMap::new();
+    for configuration in &aerodrome.configurations {
+        let runways = configuration
+            .runways
+            .iter()
+            .map(|name| {
+                runway_entities.get(name).map(|runway| runway.runway.runway).ok_or_else(|| {
+                    load::Error::UnresolvedRunway {
+                        aerodrome: aerodrome.code.clone(),
+                        runway:    name.clone(),
+                    }
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let entity = world
+            .spawn((
+                StoredEntity,
+                Name::new(format!(
+                    "Runway configuration: {}/{}",
+                    aerodrome.code, configuration.name
+                )),
+                runway::Configuration { name: configuration.name.clone(), runways },
+                runway::ConfigurationOf(aerodrome_entity),
+            ))
+            .id();
+        configurations.insert(configuration.name.clone(), entity);
+    }
+    Ok(configurations)
+}
+
 /// Stores the mapping from loaded aerodromes to their spawned entities.
 #[derive(Debug, Default)]
 pub struct AerodromeMap(HashMap<String, SpawnedAerodrome>);
@@ -123,6 +208,23 @@ impl AerodromeMap {
         self.0.get(&code.0).ok_or_else(|| load::Error::UnresolvedAerodrome(code.0.clone()))
     }
 
+    /// Resolves the stored runway configuration reference into the configuration entity.
+    ///
+    /// # Errors
+    /// If the referenced aerodrome or configuration does not exist.
+    pub fn resolve_configuration(
+        &self,
+        configuration: &store::RunwayConfigurationRef,
+    ) -> Result<Entity, load::Error> {
+        let aerodrome = self.resolve(&configuration.aerodrome)?;
+        aerodrome.configurations.get(&configuration.configuration).copied().ok_or_else(|| {
+            load::Error::UnresolvedRunwayConfiguration {
+                aerodrome:     configuration.aerodrome.0.clone(),
+                configuration: configuration.configuration.clone(),
+            }
+        })
+    }
+
     /// Resolves the stored segment reference
     /// into a runtime segment label reference.
     ///
@@ -243,6 +345,8 @@ pub struct SpawnedAerodrome {
     pub aerodrome_entity: Entity,
     pub index:            u32,
     pub runways:          HashMap<String, PairedSpawnedRunway>,
+    /// Runway configuration entities by name.
+    pub configurations:   HashMap<String, Entity>,
     pub spawned_segments: SpawnedSegments,
 }
 
@@ -281,7 +385,11 @@ fn spawn_runway(
     aerodrome_entity: Entity,
 ) -> SpawnedRunway {
     let runway_entity = world
-        .spawn((StoredEntity, Name::new(format!("Runway: {}/{}", aerodrome.code, runway.name))))
+        .spawn((
+            StoredEntity,
+            Name::new(format!("Runway: {}/{}", aerodrome.code, runway.name)),
+            message::Sender { display: format!("{}/{}", aerodrome.code, runway.name) },
+        ))
         .id();
 
     let heading = Heading::from_vec2((end_pos - start_pos).0);
@@ -352,7 +460,7 @@ fn spawn_runway_navaids(
             max_dist_horizontal: runway.max_visual_distance,
             max_dist_vertical:   Length::from_km(10.),
         },
-        navaid::Visual { max_range: runway.max_visual_distance },
+        navaid::Visual { max_range: runway.max_visual_distance, obscured_range: None },
     ));
 
     if let Some(ils) = &runway.ils {
@@ -416,9 +524,13 @@ fn collect_non_apron_ground_lines(
         .collect()
 }
 
+/// Appends a line from each apron to the closest ground line behind it.
+///
+/// Aprons not facing any ground line are skipped and reported in `issues`.
 fn generate_apron_lines(
     ground_network: &store::GroundNetwork,
     lines: &mut Vec<GroundLine>,
+    issues: &mut load::Issues,
 ) -> Result<(), load::Error> {
     let mut aprons: Vec<_> = ground_network
         .aprons
@@ -434,17 +546,8 @@ fn generate_apron_lines(
         .collect::<Result<_, load::Error>>()?;
     aprons.sort_by_key(|apron| apron.0);
 
-    let non_apron_lines_len = lines.len();
-    lines.extend(aprons.iter().map(|(_, apron)| GroundLine {
-        label:     ground::SegmentLabel::Apron { name: apron.name.clone() },
-        width:     apron.width,
-        max_speed: ground_network.apron_speed,
-        alpha:     apron.position,
-        beta:      apron.position, // we will update this later
-    }));
-    let (non_apron_lines, apron_lines) = lines.split_at_mut(non_apron_lines_len);
-
-    for (apron_index, &(_apron_back, apron)) in aprons.iter().enumerate() {
+    let mut apron_lines = Vec::new();
+    for &(_apron_back, apron) in &aprons {
         let sweeper = LineSweeper::new(
             |index| match index.0.checked_sub(1) {
                 None => sweep::Line {
@@ -453,7 +556,7 @@ fn generate_apron_lines(
                     need_intersect: true,
                 },
                 Some(non_apron_index) => {
-                    let line = &non_apron_lines[non_apron_index];
+                    let line = &lines[non_apron_index];
                     sweep::Line {
                         alpha:          line.alpha,
                         beta:           line.beta,
@@ -461,7 +564,7 @@ fn generate_apron_lines(
                     }
                 }
             },
-            non_apron_lines.len() + 1,
+            lines.len() + 1,
             GROUND_EPSILON,
             apron.forward_heading.opposite().into_dir2(),
         )
@@ -470,10 +573,20 @@ fn generate_apron_lines(
         let intersect = sweeper
             .intersections_after(apron.position)
             .next()
-            .ok_or_else(|| load::Error::UnreachableApron(apron.name.clone()))?;
-        // previously inited with a dummy value
-        apron_lines[apron_index].beta = intersect.position;
+            .ok_or_else(|| load::Error::UnreachableApron(apron.name.clone()));
+        let Some(intersect) = issues.recover(|| format!("apron {}", apron.name), intersect)? else {
+            continue;
+        };
+
+        apron_lines.push(GroundLine {
+            label:     ground::SegmentLabel::Apron { name: apron.name.clone() },
+            width:     apron.width,
+            max_speed: ground_network.apron_speed,
+            alpha:     apron.position,
+            beta:      intersect.position,
+        });
     }
+    lines.extend(apron_lines);
 
     Ok(())
 }
@@ -627,7 +740,11 @@ fn spawn_ground_segments(
     elevation: Position<f32>,
 ) -> Result<SpawnedSegments, load::Error> {
     let mut lines = collect_non_apron_ground_lines(ground_network, runway_pairs, runways);
-    generate_apron_lines(ground_network, &mut lines)?;
+    generate_apron_lines(
+        ground_network,
+        &mut lines,
+        &mut world.get_resource_or_init::<load::Issues>(),
+    )?;
     let intersect_groups = find_ground_intersects(&lines)?;
     let segments = ground_lines_to_segments(&lines, &intersect_groups);
 
@@ -0,0 +1,66 @@
+//! Published airways connecting waypoints for enroute navigation.
+//!
+//! Routes follow an airway through [`store::RouteNode::FollowAirway`],
+//! which is expanded into direct legs to its waypoints when the route is loaded.
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::Message;
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::EntityWorldMut;
+use math::Position;
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) { app.add_message::<SpawnMessage>(); }
+}
+
+/// A published route through an ordered chain of waypoints.
+#[derive(Component)]
+pub struct Airway {
+    /// Display name of the airway.
+    pub name:         String,
+    /// Waypoint entities along the airway in order.
+    pub waypoints:    Vec<Entity>,
+    /// Lowest usable altitude on the airway.
+    pub min_altitude: Option<Position<f32>>,
+    /// Highest usable altitude on the airway.
+    pub max_altitude: Option<Position<f32>>,
+}
+
+/// Returns the waypoints passed when following an airway with the ordered `waypoints`
+/// from `from` to `to`, including both ends.
+///
+/// The airway may be followed in either direction.
+/// Returns `None` if either waypoint is not on the airway.
+#[must_use]
+pub fn legs(waypoints: &[Entity], from: Entity, to: Entity) -> Option<Vec<Entity>> {
+    let start = waypoints.iter().position(|&waypoint| waypoint == from)?;
+    let end = waypoints.iter().position(|&waypoint| waypoint == to)?;
+    Some(if start <= end {
+        waypoints[start..=end].to_vec()
+    } else {
+        waypoints[end..=start].iter().rev().copied().collect()
+    })
+}
+
+pub struct SpawnCommand {
+    pub airway: Airway,
+}
+
+impl EntityCommand for SpawnCommand {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert(self.airway);
+        let entity_id = entity.id();
+        entity.world_scope(|world| world.write_message(SpawnMessage(entity_id)));
+    }
+}
+
+#[derive(Message)]
+pub struct SpawnMessage(pub Entity);
@@ -0,0 +1,45 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::World;
+
+use crate::level::airway::{self, Airway};
+use crate::level::waypoint::loader::WaypointMap;
+use crate::load::{self, StoredEntity};
+
+/// Spawns the airways declared in a store and registers them in `waypoints`.
+///
+/// Airways with an unresolvable waypoint are skipped and reported as [issues](load::Issues).
+///
+/// # Errors
+/// Currently never fails.
+pub fn spawn(
+    world: &mut World,
+    waypoints: &mut WaypointMap,
+    airways: &[store::Airway],
+) -> Result<(), load::Error> {
+    for airway in airways {
+        let chain = airway
+            .waypoints
+            .iter()
+            .map(|waypoint| waypoints.resolve(waypoint))
+            .collect::<Result<Vec<_>, _>>();
+        let Some(chain) = load::recover(world, || format!("airway {}", airway.name.0), chain)?
+        else {
+            continue;
+        };
+
+        let airway_entity =
+            world.spawn((StoredEntity, Name::new(format!("Airway: {}", airway.name.0)))).id();
+        airway::SpawnCommand {
+            airway: Airway {
+                name:         airway.name.0.clone(),
+                waypoints:    chain.clone(),
+                min_altitude: airway.min_altitude,
+                max_altitude: airway.max_altitude,
+            },
+        }
+        .apply(world.entity_mut(airway_entity));
+        waypoints.insert_airway(airway.name.0.clone(), chain);
+    }
+    Ok(())
+}
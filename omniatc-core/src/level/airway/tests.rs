@@ -0,0 +1,139 @@
+use std::num::NonZero;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::Position;
+
+use super::{Airway, legs, loader};
+use crate::level::aerodrome::loader::AerodromeMap;
+use crate::level::route::loader::{RoutePresetMap, convert_route};
+use crate::level::route::{DirectWaypointNode, Node};
+use crate::level::waypoint::loader::WaypointMap;
+use crate::level::{airway, waypoint};
+use crate::load;
+
+fn store_waypoint(name: &str, east_nm: f32) -> store::Waypoint {
+    store::Waypoint {
+        name:      name.into(),
+        position:  Position::from_origin_nm(east_nm, 0.0),
+        elevation: None,
+        navaids:   Vec::new(),
+        visual:    None,
+        hidden:    false,
+    }
+}
+
+fn setup() -> (App, WaypointMap) {
+    let mut app = App::new();
+    app.add_plugins((waypoint::Plug, airway::Plug));
+    let world = app.world_mut();
+    let mut waypoints = waypoint::loader::spawn(
+        world,
+        &["ALPHA", "BRAVO", "CHARL", "DELTA", "ECHO"]
+            .into_iter()
+            .zip([0.0, 20.0, 40.0, 60.0, 80.0])
+            .map(|(name, east_nm)| store_waypoint(name, east_nm))
+            .collect::<Vec<_>>(),
+    );
+    loader::spawn(
+        world,
+        &mut waypoints,
+        &[
+            store::Airway {
+                name:         "J1".into(),
+                waypoints:    ["ALPHA", "BRAVO", "CHARL", "DELTA"].map(Into::into).into(),
+                min_altitude: Some(Position::from_amsl_feet(5000.0)),
+                max_altitude: None,
+            },
+            store::Airway {
+                name:         "J2".into(),
+                waypoints:    ["ALPHA", "ZULU"].map(Into::into).into(),
+                min_altitude: None,
+                max_altitude: None,
+            },
+        ],
+    )
+    .expect("airways are recoverable");
+    (app, waypoints)
+}
+
+fn follow(from: &str, to: &str) -> store::RouteNode {
+    store::RouteNode::FollowAirway { airway: "J1".into(), from: from.into(), to: to.into() }
+}
+
+fn convert(waypoints: &WaypointMap, node: store::RouteNode) -> Result<Vec<Node>, load::Error> {
+    let mut next_standby_id = NonZero::<u32>::MIN;
+    convert_route(
+        &AerodromeMap::default(),
+        waypoints,
+        &RoutePresetMap::default(),
+        &mut next_standby_id,
+        &[node],
+    )
+    .collect()
+}
+
+fn names(app: &App, nodes: &[Node]) -> Vec<String> {
+    nodes
+        .iter()
+        .map(|node| {
+            let Node::DirectWaypoint(DirectWaypointNode { waypoint, .. }) = node else {
+                panic!("airways expand into direct legs")
+            };
+            app.world().get::<waypoint::Waypoint>(*waypoint).expect("waypoint").name.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn legs_follow_either_direction() {
+    let chain = [1, 2, 3, 4].map(Entity::from_raw_u32).map(Option::unwrap);
+    assert_eq!(legs(&chain, chain[1], chain[3]), Some(chain[1..].to_vec()));
+    assert_eq!(legs(&chain, chain[2], chain[0]), Some(vec![chain[2], chain[1], chain[0]]));
+    assert_eq!(legs(&chain[..2], chain[0], chain[3]), None);
+}
+
+#[test]
+fn follow_airway_expands_into_direct_legs() {
+    let (app, waypoints) = setup();
+
+    let nodes = convert(&waypoints, follow("BRAVO", "DELTA")).expect("convert");
+    assert_eq!(names(&app, &nodes), ["BRAVO", "CHARL", "DELTA"]);
+
+    let nodes = convert(&waypoints, follow("CHARL", "ALPHA")).expect("convert");
+    assert_eq!(names(&app, &nodes), ["CHARL", "BRAVO", "ALPHA"]);
+}
+
+#[test]
+fn unresolved_airways_are_reported() {
+    let (mut app, waypoints) = setup();
+
+    let airways: Vec<_> = app
+        .world_mut()
+        .query::<&Airway>()
+        .iter(app.world())
+        .map(|airway| (airway.name.clone(), airway.waypoints.len()))
+        .collect();
+    assert_eq!(airways, [("J1".to_string(), 4)]);
+    assert_eq!(app.world().resource::<load::Issues>().0.len(), 1);
+
+    let Err(err) = convert(
+        &waypoints,
+        store::RouteNode::FollowAirway {
+            airway: "J2".into(),
+            from:   "ALPHA".into(),
+            to:     "BRAVO".into(),
+        },
+    ) else {
+        panic!("J2 was skipped")
+    };
+    assert!(matches!(err, load::Error::UnresolvedAirway(_)), "{err}");
+
+    let Err(err) = convert(&waypoints, follow("ALPHA", "ECHO")) else {
+        panic!("ECHO is not on J1")
+    };
+    assert!(
+        matches!(&err, load::Error::NotOnAirway { waypoint, .. } if waypoint == "ECHO"),
+        "{err}"
+    );
+}
@@ -0,0 +1,234 @@
+//! Transient bird activity near runways.
+//!
+//! A [`Source`] is a latent area that occasionally spawns an [`Activity`] for a limited duration.
+//! Airborne objects flying through an active area below its top
+//! may suffer a bird strike,
+//! causing a go-around on approach or an emergency otherwise.
+//! The probability of a strike is reduced for objects [`Cautioned`] by the controller.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::name::Name;
+use bevy::ecs::query::{Has, With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Local, Query, Res};
+use bevy::math::{Vec2, Vec3};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Length, Position};
+use rand::Rng;
+
+use super::SystemSets;
+use crate::level::object::{self, Object};
+use crate::level::route::{self, Route};
+use crate::level::{dest, divert, drift, goaround, message, session};
+use crate::load::StoredEntity;
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:bird");
+        app.init_resource::<session::Seed>();
+        app.add_systems(app::Update, activity_system.in_set(SystemSets::PrepareEnviron));
+        app.add_systems(app::Update, strike_system.in_set(SystemSets::Action));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Expected number of bird strikes per minute an uncautioned object spends in an active area.
+    #[config(default = 0.5, min = 0.0, max = 10.0)]
+    pub strike_rate:    f32,
+    /// Multiplier on the strike rate for objects cautioned about bird activity.
+    #[config(default = 0.3, min = 0.0, max = 1.0)]
+    pub caution_factor: f32,
+}
+
+/// An area where bird activity occasionally occurs.
+#[derive(Component)]
+pub struct Source {
+    pub center:        Position<Vec2>,
+    pub radius:        Length<f32>,
+    pub top:           Position<f32>,
+    /// Mean time between the end of an activity and the start of the next one.
+    pub mean_interval: Duration,
+    /// Duration of each activity.
+    pub duration:      Duration,
+    /// The currently active [`Activity`] entity spawned from this source.
+    pub active:        Option<Entity>,
+}
+
+/// An area with ongoing bird activity.
+#[derive(Component)]
+pub struct Activity {
+    pub center: Position<Vec2>,
+    pub radius: Length<f32>,
+    pub top:    Position<f32>,
+    /// Virtual time at which the activity ends.
+    pub expiry: Duration,
+}
+
+impl Activity {
+    /// Whether the position is within the affected area.
+    #[must_use]
+    pub fn contains(&self, position: Position<Vec3>) -> bool {
+        position.altitude() <= self.top
+            && self.center.distance_cmp(position.horizontal()) < self.radius
+    }
+}
+
+/// Marks an object that has been cautioned about bird activity.
+#[derive(Component)]
+pub struct Cautioned;
+
+/// Marks an object that has suffered a bird strike.
+///
+/// An object can only suffer one bird strike.
+#[derive(Component)]
+pub struct Struck;
+
+/// Marks an object that has declared an emergency due to a bird strike.
+#[derive(Component)]
+pub struct Emergency;
+
+fn activity_system(
+    time: Res<Time<time::Virtual>>,
+    mut source_query: Query<&mut Source>,
+    activity_query: Query<&Activity>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let rng = rng.get(session::STREAM_BIRD_ACTIVITY);
+
+    for mut source in &mut source_query {
+        if let Some(active) = source.active {
+            match activity_query.get(active) {
+                Ok(activity) if activity.expiry > time.elapsed() => continue,
+                Ok(_) => commands.entity(active).despawn(),
+                Err(_) => {}
+            }
+            source.active = None;
+        }
+
+        let probability = time.delta().as_secs_f64() / source.mean_interval.as_secs_f64();
+        if !rng.random_bool(probability.clamp(0.0, 1.0)) {
+            continue;
+        }
+
+        let activity = commands
+            .spawn((
+                StoredEntity,
+                Name::new("Bird activity"),
+                Activity {
+                    center: source.center,
+                    radius: source.radius,
+                    top:    source.top,
+                    expiry: time.elapsed() + source.duration,
+                },
+            ))
+            .id();
+        source.active = Some(activity);
+    }
+}
+
+fn strike_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    activity_query: Query<&Activity>,
+    object_query: Query<
+        (Entity, &Object, Has<Cautioned>, Option<&Route>, Option<&dest::Origin>),
+        (With<object::Airborne>, Without<Struck>, Without<drift::Drifter>),
+    >,
+    preset_query: Query<&route::Preset>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    if time.is_paused() || activity_query.is_empty() {
+        return;
+    }
+    let conf = conf.read();
+    let rng = rng.get(session::STREAM_BIRD_STRIKE);
+
+    for (object_entity, object, cautioned, route, origin) in object_query {
+        if !activity_query.iter().any(|activity| activity.contains(object.position)) {
+            continue;
+        }
+
+        let rate =
+            if cautioned { conf.strike_rate * conf.caution_factor } else { conf.strike_rate };
+        let probability = f64::from(rate) * time.delta().as_secs_f64() / 60.0;
+        if !rng.random_bool(probability.clamp(0.0, 1.0)) {
+            continue;
+        }
+
+        let mut object_commands = commands.entity(object_entity);
+        object_commands.insert(Struck);
+
+        if let StrikeResponse::GoAround { preset } =
+            route.and_then(Route::current).map_or(StrikeResponse::Emergency, strike_response)
+        {
+            let nodes = preset
+                .and_then(|preset| preset_query.get(preset).ok())
+                .map(|preset| preset.nodes.clone())
+                .unwrap_or_default();
+            object_commands
+                .queue(goaround::Record(goaround::Cause::BirdStrike))
+                .queue(route::ReplaceNodes(nodes));
+            commands.queue(message::SendExpiring {
+                source:   object_entity,
+                content:  "Going around, bird strike".into(),
+                class:    message::Class::AnomalyInfo,
+                duration: Duration::from_secs(10),
+            });
+        } else {
+            object_commands.insert(Emergency);
+            if let Some(&dest::Origin { aerodrome }) = origin {
+                object_commands.queue(divert::DivertCommand { aerodrome });
+            }
+            commands.queue(message::SendExpiring {
+                source:   object_entity,
+                content:  "Mayday, bird strike".into(),
+                class:    message::Class::Urgent,
+                duration: Duration::from_secs(20),
+            });
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum StrikeResponse {
+    /// The object is on a landing approach and goes around with the given preset.
+    GoAround { preset: Option<Entity> },
+    /// The object declares an emergency.
+    Emergency,
+}
+
+fn strike_response(node: &route::Node) -> StrikeResponse {
+    match node {
+        route::Node::AlignRunway(node) => StrikeResponse::GoAround { preset: node.goaround_preset },
+        route::Node::ShortFinal(node) => StrikeResponse::GoAround { preset: node.goaround_preset },
+        route::Node::VisualLanding(node) => {
+            StrikeResponse::GoAround { preset: node.goaround_preset }
+        }
+        _ => StrikeResponse::Emergency,
+    }
+}
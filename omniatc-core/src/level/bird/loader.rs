@@ -0,0 +1,23 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+
+use crate::level::bird;
+use crate::load::StoredEntity;
+
+/// Spawns the bird activity sources declared in a store into the world.
+pub fn spawn_sources(world: &mut World, sources: &[store::BirdActivitySource]) {
+    for source in sources {
+        world.spawn((
+            StoredEntity,
+            Name::new("Bird activity source"),
+            bird::Source {
+                center:        source.center,
+                radius:        source.radius,
+                top:           source.top,
+                mean_interval: source.mean_interval,
+                duration:      source.duration,
+                active:        None,
+            },
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::world::World;
+use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Length, Position, Speed};
+
+use super::{Activity, Emergency, Source, StrikeResponse, Struck};
+use crate::level::dest::{self, Destination};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::{bird, divert, message, route};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, divert::Plug::<()>::default(), bird::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+fn spawn_activity(app: &mut App) -> Entity {
+    app.world_mut()
+        .spawn(Activity {
+            center: Position::ORIGIN,
+            radius: Length::from_nm(1.0),
+            top:    Position::from_amsl_feet(2000.0),
+            expiry: Duration::from_hours(1),
+        })
+        .id()
+}
+
+fn spawn_object(app: &mut App, altitude: Position<f32>) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(altitude),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            message::Sender { display: "ABC123".into() },
+            Destination::Departure { min_altitude: None, waypoint_proximity: None },
+        ))
+        .id()
+}
+
+#[test]
+fn test_source_activation_lifecycle() {
+    let mut app = base_app();
+    let source = app
+        .world_mut()
+        .spawn(Source {
+            center:        Position::ORIGIN,
+            radius:        Length::from_nm(1.0),
+            top:           Position::from_amsl_feet(2000.0),
+            mean_interval: Duration::from_millis(1),
+            duration:      Duration::from_mins(1),
+            active:        None,
+        })
+        .id();
+
+    advance(&mut app, Duration::from_secs(1));
+    let active = app.world().get::<Source>(source).and_then(|source| source.active);
+    let active = active.expect("activity should start with a tiny mean interval");
+    assert!(app.world().get::<Activity>(active).is_some());
+
+    advance(&mut app, Duration::from_mins(2));
+    assert!(app.world().get_entity(active).is_err(), "expired activity should be despawned");
+}
+
+#[test]
+fn test_departure_strike_declares_emergency() {
+    let mut app = base_app();
+    spawn_activity(&mut app);
+    let aerodrome = app.world_mut().spawn_empty().id();
+    let object = spawn_object(&mut app, Position::from_amsl_feet(1000.0));
+    app.world_mut().entity_mut(object).insert(dest::Origin { aerodrome });
+
+    // The strike probability saturates over a long enough exposure.
+    advance(&mut app, Duration::from_mins(10));
+
+    assert!(app.world().get::<Struck>(object).is_some());
+    assert!(app.world().get::<Emergency>(object).is_some());
+    assert!(
+        matches!(
+            app.world().get::<Destination>(object),
+            Some(&Destination::Landing { aerodrome: landing }) if landing == aerodrome
+        ),
+        "struck departure should return to its origin"
+    );
+}
+
+#[test]
+fn test_no_strike_above_activity() {
+    let mut app = base_app();
+    spawn_activity(&mut app);
+    let object = spawn_object(&mut app, Position::from_amsl_feet(5000.0));
+
+    advance(&mut app, Duration::from_mins(10));
+
+    assert!(app.world().get::<Struck>(object).is_none());
+}
+
+#[test]
+fn test_goaround_only_on_approach() {
+    let mut world = World::new();
+    let preset = world.spawn_empty().id();
+    let runway = world.spawn_empty().id();
+
+    let node =
+        route::Node::ShortFinal(route::ShortFinalNode { runway, goaround_preset: Some(preset) });
+    assert_eq!(super::strike_response(&node), StrikeResponse::GoAround { preset: Some(preset) });
+
+    let node = route::Node::DirectWaypoint(route::DirectWaypointNode {
+        waypoint:  runway,
+        distance:  Length::ZERO,
+        proximity: store::WaypointProximity::FlyBy,
+        altitude:  None,
+    });
+    assert_eq!(super::strike_response(&node), StrikeResponse::Emergency);
+}
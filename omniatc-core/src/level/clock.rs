@@ -0,0 +1,127 @@
+//! Scenario clock and day/night cycle.
+//!
+//! The [`Clock`] maps the virtual time to a time of day.
+//! If a day/night [`Cycle`] is configured,
+//! the range of visual navaids is reduced after dark.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+
+use super::SystemSets;
+use crate::level::navaid::{self, Navaid};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+const DAY: Duration = Duration::from_hours(24);
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:clock");
+        app.init_resource::<Clock>();
+        app.add_systems(app::Update, visual_range_system.in_set(SystemSets::PrepareEnviron));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Multiplier on the range of visual navaids at night.
+    #[config(default = 0.5, min = 0.0, max = 1.0)]
+    pub night_visual_range_factor: f32,
+}
+
+/// Maps virtual time to the scenario time of day.
+#[derive(Resource)]
+pub struct Clock {
+    /// Time of day when the virtual time is zero.
+    pub epoch: Duration,
+    /// The day/night cycle. It is always daytime if `None`.
+    pub cycle: Option<Cycle>,
+}
+
+impl Default for Clock {
+    fn default() -> Self { Self { epoch: Duration::from_hours(12), cycle: None } }
+}
+
+/// Times of sunrise and sunset, measured from midnight.
+#[derive(Clone, Copy)]
+pub struct Cycle {
+    /// Time of day when it becomes fully bright.
+    pub sunrise:  Duration,
+    /// Time of day when it starts getting dark.
+    pub sunset:   Duration,
+    /// Duration of the transition between day and night.
+    pub twilight: Duration,
+}
+
+impl Clock {
+    /// Returns the time of day, measured from midnight, at the given virtual time.
+    #[must_use]
+    pub fn time_of_day(&self, elapsed: Duration) -> Duration {
+        Duration::from_secs_f64((self.epoch + elapsed).as_secs_f64() % DAY.as_secs_f64())
+    }
+
+    /// Returns the amount of daylight at the given virtual time,
+    /// from 0.0 (night) to 1.0 (day).
+    #[must_use]
+    pub fn daylight(&self, elapsed: Duration) -> f32 {
+        let Some(cycle) = self.cycle else { return 1.0 };
+        let now = self.time_of_day(elapsed).as_secs_f32();
+        let twilight = cycle.twilight.as_secs_f32().max(1.0);
+
+        let dawn = (now - cycle.sunrise.as_secs_f32()) / twilight + 1.0;
+        let dusk = (cycle.sunset.as_secs_f32() - now) / twilight + 1.0;
+        dawn.min(dusk).clamp(0.0, 1.0)
+    }
+
+    /// Converts the clock state at the given virtual time into a store.
+    #[must_use]
+    pub fn to_store(&self, elapsed: Duration) -> store::Clock {
+        store::Clock {
+            time_of_day: self.time_of_day(elapsed),
+            day_night:   self.cycle.map(|cycle| store::DayNightCycle {
+                sunrise:  cycle.sunrise,
+                sunset:   cycle.sunset,
+                twilight: cycle.twilight,
+            }),
+        }
+    }
+}
+
+fn visual_range_system(
+    time: Res<Time<time::Virtual>>,
+    clock: Res<Clock>,
+    conf: ReadConfig<Conf>,
+    navaid_query: Query<(&mut Navaid, &navaid::Visual)>,
+) {
+    let conf = conf.read();
+    let daylight = clock.daylight(time.elapsed());
+    let factor = conf.night_visual_range_factor + (1.0 - conf.night_visual_range_factor) * daylight;
+
+    for (mut navaid, visual) in navaid_query {
+        let mut range = visual.max_range * factor;
+        if let Some(obscured) = visual.obscured_range {
+            range = range.min(obscured);
+        }
+        if navaid.max_dist_horizontal != range {
+            navaid.max_dist_horizontal = range;
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+
+use crate::level::clock;
+
+/// Configures the scenario clock from a store.
+pub fn spawn(world: &mut World, clock: &store::Clock) {
+    let elapsed = world.resource::<Time<time::Virtual>>().elapsed();
+    let day = clock::DAY.as_secs_f64();
+    let epoch = (clock.time_of_day.as_secs_f64() - elapsed.as_secs_f64()).rem_euclid(day);
+
+    world.insert_resource(clock::Clock {
+        epoch: std::time::Duration::from_secs_f64(epoch),
+        cycle: clock.day_night.as_ref().map(|cycle| clock::Cycle {
+            sunrise:  cycle.sunrise,
+            sunset:   cycle.sunset,
+            twilight: cycle.twilight,
+        }),
+    });
+}
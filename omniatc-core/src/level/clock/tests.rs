@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use math::Length;
+
+use super::{Clock, Cycle};
+use crate::level::navaid::{self, Navaid};
+use crate::level::{clock, test_util};
+
+fn cycle() -> Cycle {
+    Cycle {
+        sunrise:  Duration::from_hours(6),
+        sunset:   Duration::from_hours(18),
+        twilight: Duration::from_mins(30),
+    }
+}
+
+#[test]
+fn test_daylight_transitions() {
+    let clock = Clock { epoch: Duration::from_hours(17), cycle: Some(cycle()) };
+
+    assert!((clock.daylight(Duration::ZERO) - 1.0).abs() < 1e-4);
+    assert!((clock.daylight(Duration::from_mins(75)) - 0.5).abs() < 1e-4);
+    assert!(clock.daylight(Duration::from_hours(3)).abs() < 1e-4);
+    assert!(
+        (clock.daylight(Duration::from_hours(13)) - 1.0).abs() < 1e-4,
+        "should wrap to the next day"
+    );
+    assert_eq!(clock.time_of_day(Duration::from_hours(8)), Duration::from_hours(1));
+}
+
+#[test]
+fn test_visual_range_reduced_at_night() {
+    let mut app = test_util::app();
+    app.add_plugins(clock::Plug::<()>::default());
+    app.insert_resource(Clock { epoch: Duration::ZERO, cycle: Some(cycle()) });
+
+    let max_range = Length::from_nm(10.0);
+    let navaid = app
+        .world_mut()
+        .spawn((
+            Navaid {
+                kind:                navaid::Kind::Visual,
+                heading_range:       math::Heading::NORTH..math::Heading::NORTH,
+                pitch_range_tan:     -1.0..1.0,
+                min_dist_horizontal: Length::ZERO,
+                min_dist_vertical:   Length::ZERO,
+                max_dist_horizontal: max_range,
+                max_dist_vertical:   Length::from_nm(1.0),
+            },
+            navaid::Visual { max_range, obscured_range: None },
+        ))
+        .id();
+    app.update();
+
+    let range = app.world().get::<Navaid>(navaid).expect("navaid should exist").max_dist_horizontal;
+    assert_eq!(range, max_range * 0.5);
+}
@@ -10,6 +10,12 @@
 //!
 //! Objects with at least one active conflict pair have the [`ActiveObject`] marker component
 //! inserted; it is removed when no active pairs remain.
+//!
+//! Each new conflict pair is also recorded as a standalone [`Event`] entity
+//! for review after the level.
+//!
+//! When enabled in [`Conf`], active pairs also carry [`advisory::Options`]
+//! with suggested resolutions for training use.
 
 use std::marker::PhantomData;
 use std::time::Duration;
@@ -20,8 +26,9 @@ use bevy::ecs::entity::{Entity, EntityHashMap};
 use bevy::ecs::message::MessageReader;
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Query};
+use bevy::math::Vec3;
 use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager};
-use math::Length;
+use math::{Length, Position};
 
 use super::{SystemSets, message, object, score};
 
@@ -40,7 +47,10 @@ where
     fn build(&self, app: &mut App) {
         app.init_config::<M, Conf>("core:conflict");
         app.add_systems(app::Update, handle_despawn_system.in_set(SystemSets::Statistics));
-        app.add_systems(app::Update, detect::system().after(handle_despawn_system));
+        app.add_systems(
+            app::Update,
+            (detect::system(), advisory::system()).chain().after(handle_despawn_system),
+        );
     }
 }
 
@@ -61,6 +71,11 @@ impl Record {
     /// Returns the pair entity for the given peer, if one exists.
     #[must_use]
     pub fn pair_for(&self, peer: Entity) -> Option<Entity> { self.peers.get(&peer).copied() }
+
+    /// Iterates over `(peer, pair)` entities of all known pairs.
+    pub fn pairs(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.peers.iter().map(|(&peer, &pair)| (peer, pair))
+    }
 }
 
 /// Persistent state stored on a dedicated pair entity — one per conflict pair.
@@ -110,20 +125,42 @@ impl PairState {
 #[derive(Component)]
 pub struct ActiveObject;
 
+/// A record of a conflict pair first violating separation minima.
+///
+/// Spawned as a standalone entity that persists after the objects are despawned.
+#[derive(Component)]
+pub struct Event {
+    /// Elapsed level time when the conflict started.
+    pub time:     Duration,
+    /// Display names of the two objects when the conflict started.
+    pub names:    [String; 2],
+    /// Midpoint between the two objects when the conflict started.
+    pub position: Position<Vec3>,
+}
+
 /// Configuration for conflict detection, keyed `core:conflict`.
 #[derive(Config)]
 pub struct Conf {
     /// Horizontal separation minimum.
     #[config(default = Length::from_nm(3.0), min = Length::ZERO, max = Length::from_nm(20.0))]
-    pub horiz_sep:        Length<f32>,
+    pub horiz_sep:          Length<f32>,
     /// Vertical separation minimum.
     #[config(default = Length::from_feet(1000.0), min = Length::ZERO, max = Length::from_feet(10000.0))]
-    pub vert_sep:         Length<f32>,
+    pub vert_sep:           Length<f32>,
     /// Multiplier applied to the conflict penalty.
     #[config(default = 10.0, min = 0.0, max = 10.0)]
-    pub score_multiplier: f32,
+    pub score_multiplier:   f32,
+    /// Whether to compute resolution advisories for active conflicts.
+    ///
+    /// Advisories are a training aid and are disabled by default.
+    #[config(default = false)]
+    pub advisory:           bool,
+    /// Duration over which advisories extrapolate the conflict geometry.
+    #[config(default = Duration::from_mins(2))]
+    pub advisory_lookahead: Duration,
 }
 
+pub mod advisory;
 mod detect;
 
 #[cfg(test)]
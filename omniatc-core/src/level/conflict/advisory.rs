@@ -0,0 +1,302 @@
+//! Geometric resolution advisories for active conflict pairs.
+//!
+//! Advisories are a training aid only.
+//! They are computed by extrapolating both objects along straight lines at constant ground speed,
+//! ignoring turn and climb performance, wind changes, terrain and all other traffic.
+//! The controller remains responsible for verifying every suggestion before issuing it.
+
+use std::cmp;
+use std::time::Duration;
+
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, With};
+use bevy::ecs::schedule::{IntoScheduleConfigs, ScheduleConfigs};
+use bevy::ecs::system::{Commands, Query, ScheduleSystem};
+use bevy::math::Vec2;
+use bevy_mod_config::ReadConfig;
+use math::{Angle, Heading, Length, Position, Speed, TurnDirection};
+use store::YawTarget;
+
+use super::{PairState, SystemSets};
+use crate::level::instr::{self, Instruction};
+use crate::level::{nav, object};
+
+/// Heading change step tried by the solver.
+const HEADING_STEP: Angle = Angle::from_degrees(10.0);
+/// Number of heading change steps tried in each direction.
+const HEADING_STEPS: u8 = 9;
+/// Assigned altitudes are rounded away from the conflict to a multiple of this value.
+const ALTITUDE_ROUNDING_FEET: f32 = 1000.0;
+/// Lowest altitude that the solver would suggest a descent to.
+const MIN_ALTITUDE: Position<f32> = Position::from_amsl_feet(2000.0);
+
+pub(super) fn system() -> ScheduleConfigs<ScheduleSystem> {
+    update_system.in_set(SystemSets::Statistics)
+}
+
+/// Resolution options for an active conflict pair, inserted on the [`PairState`] entity.
+///
+/// Only maintained when advisories are enabled in [`Conf`](super::Conf),
+/// and removed when the pair is no longer active.
+#[derive(Component, Default)]
+pub struct Options(pub Vec<Resolution>);
+
+/// A suggested instruction to one object of a conflict pair.
+#[derive(Debug, Clone, Copy)]
+pub struct Resolution {
+    /// The object to instruct.
+    pub object: Entity,
+    /// The suggested maneuver.
+    pub kind:   ResolutionKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ResolutionKind {
+    /// Turn to a new heading.
+    Heading {
+        /// The heading to assign.
+        target:    Heading,
+        /// Direction of the turn from the current track.
+        dir:       TurnDirection,
+        /// Magnitude of the turn from the current track.
+        magnitude: Angle,
+    },
+    /// Climb or descend to a new altitude.
+    Altitude {
+        /// The altitude to assign.
+        target: Position<f32>,
+        /// Signed altitude change from the current altitude.
+        change: Length<f32>,
+    },
+}
+
+impl Resolution {
+    /// Short description of the maneuver for display, excluding the object name.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self.kind {
+            ResolutionKind::Heading { target, dir, magnitude } => format!(
+                "Turn {} {:.0}\u{b0} to heading {:03.0}",
+                match dir {
+                    TurnDirection::CounterClockwise => "left",
+                    TurnDirection::Clockwise => "right",
+                },
+                magnitude.into_degrees(),
+                target.degrees(),
+            ),
+            ResolutionKind::Altitude { target, change } => format!(
+                "{} {:.0} ft to {:.0} ft",
+                if change.is_positive() { "Climb" } else { "Descend" },
+                change.abs().into_feet(),
+                target.amsl().into_feet(),
+            ),
+        }
+    }
+
+    /// The instruction that applies this resolution.
+    #[must_use]
+    pub fn instruction(&self) -> Instruction {
+        match self.kind {
+            ResolutionKind::Heading { target, .. } => {
+                instr::SetHeading { target: YawTarget::Heading(target) }.into()
+            }
+            ResolutionKind::Altitude { target, .. } => instr::SetAltitude {
+                target: nav::TargetAltitude { altitude: target, expedite: true },
+            }
+            .into(),
+        }
+    }
+}
+
+/// Separation parameters for [`solve`].
+#[derive(Debug, Clone, Copy)]
+pub struct Params {
+    pub horiz_sep: Length<f32>,
+    pub vert_sep:  Length<f32>,
+    /// Duration over which the straight-line extrapolation is evaluated.
+    pub lookahead: Duration,
+}
+
+/// Computes up to one heading resolution and one altitude resolution for a conflict pair.
+///
+/// The heading resolution is the smallest turn of either object, in steps of 10 degrees,
+/// after which the pair no longer converges within the lookahead period
+/// or passes at least the horizontal separation minimum apart.
+/// If no turn up to 90 degrees achieves this,
+/// the turn that maximizes the predicted miss distance is suggested instead.
+/// The altitude resolution moves the object requiring the smaller altitude change
+/// to the next rounded altitude providing vertical separation.
+#[must_use]
+pub fn solve(
+    [entity_a, entity_b]: [Entity; 2],
+    [object_a, object_b]: [&object::Object; 2],
+    params: &Params,
+) -> Vec<Resolution> {
+    let mut options = Vec::with_capacity(2);
+
+    let heading = [(entity_a, object_a, object_b), (entity_b, object_b, object_a)]
+        .into_iter()
+        .filter_map(|(entity, subject, peer)| {
+            solve_heading(subject, peer, params).map(|candidate| (entity, candidate))
+        })
+        .min_by(|(_, a), (_, b)| a.preference(b));
+    if let Some((object, candidate)) = heading {
+        options.push(Resolution { object, kind: candidate.kind });
+    }
+
+    let altitude = [(entity_a, object_a, object_b), (entity_b, object_b, object_a)]
+        .into_iter()
+        .filter_map(|(entity, subject, peer)| {
+            solve_altitude(subject, peer, params).map(|kind| (entity, kind))
+        })
+        .min_by(|(_, a), (_, b)| a.1.total_cmp(&b.1));
+    if let Some((object, (kind, _))) = altitude {
+        options.push(Resolution { object, kind });
+    }
+
+    options
+}
+
+/// A candidate heading resolution for one object of the pair.
+struct HeadingCandidate {
+    kind:      ResolutionKind,
+    /// Whether the turn achieves the required miss distance.
+    accepted:  bool,
+    /// Magnitude of the turn in radians.
+    magnitude: f32,
+    /// Predicted minimum horizontal distance after the turn.
+    miss:      Length<f32>,
+}
+
+impl HeadingCandidate {
+    /// Prefers accepted turns, then smaller turns, then larger miss distances.
+    fn preference(&self, other: &Self) -> cmp::Ordering {
+        other
+            .accepted
+            .cmp(&self.accepted)
+            .then(self.magnitude.total_cmp(&other.magnitude))
+            .then(other.miss.0.total_cmp(&self.miss.0))
+    }
+}
+
+/// Returns the smallest turn of `subject` that achieves the required miss distance.
+///
+/// If no turn within the tried range is sufficient,
+/// returns the turn with the largest miss distance if it improves on the current track.
+fn solve_heading(
+    subject: &object::Object,
+    peer: &object::Object,
+    params: &Params,
+) -> Option<HeadingCandidate> {
+    let subject_vel = subject.ground_speed.horizontal();
+    if subject_vel.magnitude_squared().0 <= 0.0 {
+        return None;
+    }
+    let track = Heading::from_vec2(subject_vel.0);
+    let offset = peer.position.horizontal() - subject.position.horizontal();
+    let peer_vel = peer.ground_speed.horizontal();
+    let required = offset.magnitude_exact().min(params.horiz_sep);
+    let unturned_miss = min_distance(offset, peer_vel - subject_vel, params.lookahead);
+
+    let mut best: Option<HeadingCandidate> = None;
+    for step in 1..=HEADING_STEPS {
+        let magnitude = HEADING_STEP * f32::from(step);
+        for dir in [TurnDirection::Clockwise, TurnDirection::CounterClockwise] {
+            let target = track.add_direction(dir, magnitude);
+            let turned_vel = Speed::new(target.into_dir2() * subject_vel.magnitude_exact().0);
+            let miss = min_distance(offset, peer_vel - turned_vel, params.lookahead);
+            let accepted = miss >= required;
+            if accepted && miss > unturned_miss {
+                return Some(HeadingCandidate {
+                    kind: ResolutionKind::Heading { target, dir, magnitude },
+                    accepted,
+                    magnitude: magnitude.0,
+                    miss,
+                });
+            }
+            if best.as_ref().is_none_or(|best| miss > best.miss) {
+                best = Some(HeadingCandidate {
+                    kind: ResolutionKind::Heading { target, dir, magnitude },
+                    accepted,
+                    magnitude: magnitude.0,
+                    miss,
+                });
+            }
+        }
+    }
+
+    best.filter(|best| best.miss > unturned_miss)
+}
+
+/// Returns the altitude change of `subject` that provides vertical separation from `peer`,
+/// together with the magnitude of the change in feet.
+fn solve_altitude(
+    subject: &object::Object,
+    peer: &object::Object,
+    params: &Params,
+) -> Option<(ResolutionKind, f32)> {
+    let current = subject.position.altitude();
+    let peer_alt = peer.position.altitude();
+    let vert_sep_feet = params.vert_sep.into_feet();
+
+    // The object at or above its peer climbs; the lower one descends.
+    let target_feet = if current >= peer_alt {
+        ((peer_alt.amsl().into_feet() + vert_sep_feet) / ALTITUDE_ROUNDING_FEET).ceil()
+            * ALTITUDE_ROUNDING_FEET
+    } else {
+        ((peer_alt.amsl().into_feet() - vert_sep_feet) / ALTITUDE_ROUNDING_FEET).floor()
+            * ALTITUDE_ROUNDING_FEET
+    };
+    let target = Position::from_amsl_feet(target_feet);
+    if target < MIN_ALTITUDE {
+        return None;
+    }
+
+    let change = target - current;
+    Some((ResolutionKind::Altitude { target, change }, change.abs().into_feet()))
+}
+
+/// Minimum horizontal distance between two objects within `lookahead`,
+/// where `offset` and `rel_vel` are the position and velocity of the peer relative to the subject.
+fn min_distance(offset: Length<Vec2>, rel_vel: Speed<Vec2>, lookahead: Duration) -> Length<f32> {
+    let speed_sq = rel_vel.0.length_squared();
+    let time = if speed_sq > 0.0 {
+        (-offset.0.dot(rel_vel.0) / speed_sq).clamp(0.0, lookahead.as_secs_f32())
+    } else {
+        0.0
+    };
+    (offset + rel_vel * Duration::from_secs_f32(time)).magnitude_exact()
+}
+
+fn update_system(
+    conf: ReadConfig<super::Conf>,
+    pair_query: Query<(Entity, &PairState, Has<Options>)>,
+    object_query: Query<&object::Object, With<object::Airborne>>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+    let params = Params {
+        horiz_sep: conf.horiz_sep,
+        vert_sep:  conf.vert_sep,
+        lookahead: conf.advisory_lookahead,
+    };
+
+    for (pair_entity, pair, has_options) in &pair_query {
+        let objects =
+            object_query.get(pair.entity_a).ok().zip(object_query.get(pair.entity_b).ok());
+        match objects {
+            Some((object_a, object_b)) if conf.advisory && pair.is_active => {
+                let options = solve([pair.entity_a, pair.entity_b], [object_a, object_b], &params);
+                commands.entity(pair_entity).insert(Options(options));
+            }
+            _ if has_options => {
+                commands.entity(pair_entity).remove::<Options>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::world::World;
+use math::{Length, Position, Speed, TurnDirection};
+
+use super::{Params, ResolutionKind, solve};
+use crate::level::object::Object;
+
+const PARAMS: Params = Params {
+    horiz_sep: Length::from_nm(3.0),
+    vert_sep:  Length::from_feet(1000.0),
+    lookahead: Duration::from_mins(2),
+};
+
+fn object(x_nm: f32, y_nm: f32, alt_ft: f32, east_kt: f32, north_kt: f32) -> Object {
+    Object {
+        position:     Position::from_origin_nm(x_nm, y_nm)
+            .with_altitude(Position::from_amsl_feet(alt_ft)),
+        ground_speed: Speed::from_knots_vec2(east_kt, north_kt).with_vertical(Speed::ZERO),
+    }
+}
+
+fn entities() -> [Entity; 2] {
+    let mut world = World::new();
+    [world.spawn_empty().id(), world.spawn_empty().id()]
+}
+
+/// Crossing traffic at the same altitude is resolved by a turn away from the peer.
+#[test]
+fn crossing_traffic_turns_away() {
+    let [entity_a, entity_b] = entities();
+    let object_a = object(0.0, -2.0, 10000.0, 0.0, 300.0);
+    let object_b = object(-2.0, 0.0, 10000.0, 300.0, 0.0);
+
+    let options = solve([entity_a, entity_b], [&object_a, &object_b], &PARAMS);
+    let Some(heading) = options.iter().find(|r| matches!(r.kind, ResolutionKind::Heading { .. }))
+    else {
+        panic!("expected a heading resolution in {options:?}");
+    };
+    let ResolutionKind::Heading { dir, magnitude, .. } = heading.kind else { unreachable!() };
+    assert!(magnitude.into_degrees() <= 90.0, "turn {magnitude:?} exceeds the tried range");
+    // Both objects are symmetric, so either object turning away from the other is acceptable.
+    if heading.object == entity_a {
+        assert_eq!(dir, TurnDirection::Clockwise);
+    } else {
+        assert_eq!(dir, TurnDirection::CounterClockwise);
+    }
+}
+
+/// The higher object climbs to the next rounded altitude separated from the lower one.
+#[test]
+fn altitude_resolution_climbs_higher_object() {
+    let [entity_a, entity_b] = entities();
+    let object_a = object(0.0, 0.0, 10000.0, 300.0, 0.0);
+    let object_b = object(1.0, 0.0, 10400.0, -300.0, 0.0);
+
+    let options = solve([entity_a, entity_b], [&object_a, &object_b], &PARAMS);
+    let Some(altitude) = options.iter().find(|r| matches!(r.kind, ResolutionKind::Altitude { .. }))
+    else {
+        panic!("expected an altitude resolution in {options:?}");
+    };
+    let ResolutionKind::Altitude { target, change } = altitude.kind else { unreachable!() };
+
+    // Climbing B to 11000 ft requires 600 ft; descending A to 9000 ft requires 1000 ft.
+    assert_eq!(altitude.object, entity_b);
+    Position::from_amsl_feet(11000.0)
+        .assert_near(target, Length::from_feet(1.0))
+        .expect("target altitude");
+    assert!(change.is_positive());
+}
+
+/// Descents below the minimum suggested altitude are not proposed.
+#[test]
+fn altitude_resolution_respects_floor() {
+    let [entity_a, entity_b] = entities();
+    let object_a = object(0.0, 0.0, 2500.0, 300.0, 0.0);
+    let object_b = object(1.0, 0.0, 2600.0, -300.0, 0.0);
+
+    let options = solve([entity_a, entity_b], [&object_a, &object_b], &PARAMS);
+    for option in &options {
+        if let ResolutionKind::Altitude { target, .. } = option.kind {
+            assert!(target >= Position::from_amsl_feet(2000.0), "{option:?}");
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::time::Duration;
 
+use bevy::ecs::bundle::Bundle;
 use bevy::ecs::entity::{Entity, EntityHashSet};
+use bevy::ecs::name::Name;
 use bevy::ecs::query::{Has, With};
 use bevy::ecs::schedule::{IntoScheduleConfigs, ScheduleConfigs};
 use bevy::ecs::system::{Commands, ParamSet, Query, Res, ResMut, ScheduleSystem, SystemParam};
@@ -10,8 +12,10 @@ use math::Length;
 use store::Score;
 
 use super::{SystemSets, message, object, score};
-use crate::level::conflict::{ActiveObject, PairState, Record};
+use crate::level::conflict::{ActiveObject, Event, PairState, Record};
+use crate::level::drift;
 use crate::level::index::OctreeIndex;
+use crate::load::StoredEntity;
 
 // Wraps `system_impl` to suppress private interface compile error.
 pub(super) fn system() -> ScheduleConfigs<ScheduleSystem> {
@@ -36,7 +40,12 @@ fn system_impl(
 struct CollectPairsParams<'w, 's> {
     octree:       Res<'w, OctreeIndex<object::Object>>,
     /// Filters to airborne objects only; ground objects are never conflict candidates.
-    object_query: Query<'w, 's, (Entity, &'static object::Object), With<object::Airborne>>,
+    object_query: Query<
+        'w,
+        's,
+        (Entity, &'static object::Object, Has<drift::Drifter>),
+        With<object::Airborne>,
+    >,
     conf:         ReadConfig<'w, 's, super::Conf>,
 }
 
@@ -52,7 +61,7 @@ fn collect_pairs(params: CollectPairsParams) -> Vec<CollectedPair> {
     let aabb_half_size = Length::from([conf.horiz_sep; 2]).with_vertical(conf.vert_sep);
 
     let mut pairs = Vec::new();
-    for (entity_a, object_a) in &params.object_query {
+    for (entity_a, object_a, is_drifter_a) in &params.object_query {
         for entity_b in params.octree.entities_in_bounds([
             object_a.position - aabb_half_size,
             object_a.position + aabb_half_size,
@@ -61,7 +70,13 @@ fn collect_pairs(params: CollectPairsParams) -> Vec<CollectedPair> {
             if entity_b <= entity_a {
                 continue;
             }
-            let Ok((_, object_b)) = params.object_query.get(entity_b) else { continue };
+            let Ok((_, object_b, is_drifter_b)) = params.object_query.get(entity_b) else {
+                continue;
+            };
+            // Drifters only need to be separated from controlled traffic.
+            if is_drifter_a && is_drifter_b {
+                continue;
+            }
 
             let distance = object_a.position - object_b.position;
             let horiz_dist_sq = distance.horizontal().magnitude_squared();
@@ -88,6 +103,7 @@ struct UpdatePairsParams<'w, 's> {
     record_query:  Query<'w, 's, &'static mut Record>,
     pair_query:    Query<'w, 's, (Has<message::Message>, &'static mut PairState)>,
     display_query: Query<'w, 's, &'static object::Display>,
+    object_query:  Query<'w, 's, &'static object::Object>,
     time:          Res<'w, Time<time::Virtual>>,
     conf:          ReadConfig<'w, 's, super::Conf>,
     score:         ResMut<'w, score::Stats>,
@@ -164,6 +180,16 @@ fn update_pairs(pairs: &[CollectedPair], mut params: UpdatePairsParams) -> Entit
                 ))
                 .id();
 
+            if let Some(event) = build_conflict_event(
+                entity_a,
+                entity_b,
+                &params.display_query,
+                &params.object_query,
+                params.time.elapsed(),
+            ) {
+                params.commands.spawn(event);
+            }
+
             record_a.peers.insert(entity_b, pair_entity);
             record_b.peers.insert(entity_a, pair_entity);
 
@@ -282,3 +308,23 @@ fn build_conflict_message(
         class: message::Class::Urgent,
     }
 }
+
+fn build_conflict_event(
+    entity_a: Entity,
+    entity_b: Entity,
+    display_query: &Query<&object::Display>,
+    object_query: &Query<&object::Object>,
+    time: Duration,
+) -> Option<impl Bundle> {
+    let name_of = |entity| display_query.get(entity).map_or("?", |d| d.name.as_str()).to_owned();
+    let [object_a, object_b] = object_query.get_many([entity_a, entity_b]).ok()?;
+    Some((
+        Event {
+            time,
+            names: [name_of(entity_a), name_of(entity_b)],
+            position: object_a.position.lerp(object_b.position, 0.5),
+        },
+        Name::new("ConflictEvent"),
+        StoredEntity,
+    ))
+}
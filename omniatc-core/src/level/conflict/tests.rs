@@ -6,7 +6,7 @@ use bevy::time::{self, Time};
 use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position, Speed};
 use store::Score;
 
-use super::{ActiveObject, Record};
+use super::{ActiveObject, Event, Record};
 use crate::level::object::{self, Object};
 use crate::level::{SystemSets, conflict, message, score, weather};
 
@@ -263,3 +263,38 @@ fn test_multiobject_ab_then_ab_bc_then_bc() {
     assert!(is_active_object(&app, entity_b), "B still has active conflict with C");
     assert!(is_active_object(&app, entity_c), "C has active conflict with B");
 }
+
+/// A conflict event is only recorded when a pair first violates separation minima.
+#[test]
+fn test_event_recorded_once_per_new_pair() {
+    let mut app = base_app();
+    spawn_airborne(&mut app, 0.0, 3000.0);
+    let entity_b = spawn_airborne(&mut app, 2.0, 3600.0);
+    app.world_mut().flush();
+    app.update(); // octree warm-up
+
+    for _ in 0..5 {
+        advance(&mut app);
+    }
+
+    let events: Vec<_> = app.world_mut().query::<&Event>().iter(app.world()).collect();
+    assert_eq!(events.len(), 1, "a continuing conflict is only recorded once");
+    let event = events[0];
+    assert_eq!(event.time, Duration::ZERO, "detected during octree warm-up");
+    assert!((event.position.horizontal().get().x - 1.0).abs() < 1e-4);
+    assert!((event.position.altitude().amsl().into_feet() - 3300.0).abs() < 1.0);
+
+    app.world_mut().get_mut::<Object>(entity_b).unwrap().position =
+        Position::from_origin_nm(20.0, 0.0).with_altitude(Position::from_amsl_feet(3600.0));
+    advance(&mut app);
+    app.world_mut().get_mut::<Object>(entity_b).unwrap().position =
+        Position::from_origin_nm(2.0, 0.0).with_altitude(Position::from_amsl_feet(3600.0));
+    app.update(); // octree warm-up for resumed position
+    advance(&mut app);
+
+    assert_eq!(
+        app.world_mut().query::<&Event>().iter(app.world()).count(),
+        1,
+        "resumed conflicts of a known pair are not new events",
+    );
+}
@@ -0,0 +1,115 @@
+//! Runway crossing clearances.
+//!
+//! Ground objects whose taxi path enters or crosses a runway
+//! hold short of it until cleared, tracked in [`object::RunwayCrossing`].
+//! The taxi plugin reports each object that starts holding short
+//! as a [`HoldShortMessage`],
+//! and each object entering a runway without clearance as an [`IncursionMessage`],
+//! which is penalized with the configured score deduction.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::{Message, MessageReader};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, ResMut};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use store::Score;
+
+use super::SystemSets;
+use crate::level::waypoint::Waypoint;
+use crate::level::{message, score};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:crossing");
+        app.add_message::<HoldShortMessage>();
+        app.add_message::<IncursionMessage>();
+        app.add_systems(app::Update, notify_hold_short_system.in_set(SystemSets::Statistics));
+        app.add_systems(
+            app::Update,
+            incursion_system.in_set(SystemSets::Statistics).in_set(score::Writer),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Score deducted when an object enters a runway without clearance.
+    #[config(default = 100, min = 0, max = 1000)]
+    pub incursion_penalty: i32,
+}
+
+/// Sent when an object starts holding short of a runway pending a crossing clearance.
+#[derive(Message)]
+pub struct HoldShortMessage {
+    pub object: Entity,
+    /// Either runway entity of the runway pair.
+    pub runway: Entity,
+}
+
+/// Sent when an object enters a runway without clearance.
+#[derive(Message)]
+pub struct IncursionMessage {
+    pub object: Entity,
+    /// Either runway entity of the runway pair.
+    pub runway: Entity,
+}
+
+fn runway_name<'a>(waypoint_query: &'a Query<&Waypoint>, runway: Entity) -> &'a str {
+    waypoint_query.get(runway).map_or("?", |waypoint| waypoint.name.as_str())
+}
+
+fn notify_hold_short_system(
+    mut reader: MessageReader<HoldShortMessage>,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        commands.queue(message::SendExpiring {
+            source:   message.object,
+            content:  format!(
+                "Holding short of runway {}, request crossing",
+                runway_name(&waypoint_query, message.runway)
+            ),
+            class:    message::Class::NeedAck,
+            duration: Duration::from_secs(30),
+        });
+    }
+}
+
+fn incursion_system(
+    mut reader: MessageReader<IncursionMessage>,
+    conf: ReadConfig<Conf>,
+    waypoint_query: Query<&Waypoint>,
+    mut stats: ResMut<score::Stats>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+    for message in reader.read() {
+        stats.total -= Score(conf.incursion_penalty);
+        commands.queue(message::SendExpiring {
+            source:   message.object,
+            content:  format!(
+                "Runway incursion: entered runway {} without clearance",
+                runway_name(&waypoint_query, message.runway)
+            ),
+            class:    message::Class::Urgent,
+            duration: Duration::from_secs(30),
+        });
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::Speed;
+use store::Score;
+
+use super::IncursionMessage;
+use crate::level::instr::{self, Kind};
+use crate::level::object::{self, RunwayCrossing};
+use crate::level::{crossing, ground, message, score, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, score::Plug, crossing::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+#[test]
+fn test_permits_either_runway_of_pair() {
+    let mut app = base_app();
+    let [runway, paired, other] = [(); 3].map(|()| app.world_mut().spawn_empty().id());
+
+    assert!(RunwayCrossing::Cleared { runway: paired }.permits([runway, paired]));
+    assert!(RunwayCrossing::Crossing { runway }.permits([runway, paired]));
+    assert!(!RunwayCrossing::Cleared { runway: other }.permits([runway, paired]));
+    assert!(!RunwayCrossing::Holding { runway }.permits([runway, paired]));
+    assert!(!RunwayCrossing::None.permits([runway, paired]));
+}
+
+#[test]
+fn test_cross_runway_clears_holding_object() {
+    let mut app = base_app();
+    let runway = app.world_mut().spawn_empty().id();
+    let segment = app.world_mut().spawn_empty().id();
+    let object = app
+        .world_mut()
+        .spawn(object::OnGround {
+            segment,
+            direction: ground::SegmentDirection::AlphaToBeta,
+            target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+            crossing: RunwayCrossing::Holding { runway },
+        })
+        .id();
+
+    let world = app.world_mut();
+    instr::CrossRunway { runway }.process(&mut world.commands().entity(object));
+    world.flush();
+
+    let ground = app.world().get::<object::OnGround>(object).expect("object is on ground");
+    assert_eq!(ground.crossing, RunwayCrossing::Cleared { runway });
+}
+
+#[test]
+fn test_incursion_penalized_and_reported() {
+    let mut app = base_app();
+    let runway = app.world_mut().spawn_empty().id();
+    let object = spawn_sender(&mut app);
+
+    app.world_mut().write_message(IncursionMessage { object, runway });
+    app.update();
+
+    assert_eq!(app.world().resource::<score::Stats>().total, Score(-100));
+
+    let world = app.world_mut();
+    let alerts: Vec<_> = world
+        .query::<&message::Message>()
+        .iter(world)
+        .filter(|message| message.source == object)
+        .map(|message| message.class)
+        .collect();
+    assert_eq!(alerts, [message::Class::Urgent]);
+}
+
+fn spawn_sender(app: &mut App) -> Entity {
+    app.world_mut().spawn(message::Sender { display: "ABC123".into() }).id()
+}
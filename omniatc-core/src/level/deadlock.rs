@@ -0,0 +1,141 @@
+//! Detection of ground deadlocks.
+//!
+//! Each object [waiting for](taxi::WaitingFor) another object
+//! forms an edge of a wait-for graph.
+//! Since each object waits for at most one other object,
+//! a deadlock is a cycle in this graph,
+//! where no object can move until the controller reroutes one of them.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query};
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+
+use super::SystemSets;
+use crate::level::{message, object, taxi};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(app::Update, detect_system.in_set(SystemSets::ReconcileForRead));
+    }
+}
+
+/// Marks an object involved in a ground deadlock.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct Deadlocked {
+    /// All objects in the deadlock, in waiting order,
+    /// i.e. each object waits for the next one, and the last one waits for the first.
+    pub cycle:   Vec<Entity>,
+    /// The object suggested to be rerouted to resolve the deadlock.
+    pub reroute: Entity,
+}
+
+/// Finds the cycles in a wait-for graph.
+///
+/// `edges` maps each waiting object to the object it waits for.
+/// Each returned cycle starts with its smallest entity.
+#[must_use]
+pub fn find_cycles(edges: &HashMap<Entity, Entity>) -> Vec<Vec<Entity>> {
+    let mut visited = HashSet::new();
+    let mut cycles = Vec::new();
+
+    for &start in edges.keys().sorted() {
+        let mut path = Vec::new();
+        let mut path_index = HashMap::new();
+        let mut current = start;
+        loop {
+            if let Some(&index) = path_index.get(&current) {
+                let mut cycle: Vec<Entity> = path.split_off(index);
+                let min_index = cycle.iter().position_min().expect("cycle is nonempty");
+                cycle.rotate_left(min_index);
+                cycles.push(cycle);
+                break;
+            }
+            if !visited.insert(current) {
+                break;
+            }
+            path_index.insert(current, path.len());
+            path.push(current);
+
+            let Some(&next) = edges.get(&current) else { break };
+            current = next;
+        }
+    }
+
+    cycles
+}
+
+/// Selects the object to reroute for resolving a deadlock.
+///
+/// Objects that can reverse are preferred, then smaller objects.
+fn select_reroute(cycle: &[Entity], limits_query: &Query<&taxi::Limits>) -> Entity {
+    cycle
+        .iter()
+        .copied()
+        .min_by_key(|&entity| match limits_query.get(entity) {
+            Ok(limits) => (!limits.min_speed.is_negative(), OrderedFloat(limits.half_length.0)),
+            Err(_) => (true, OrderedFloat(f32::INFINITY)),
+        })
+        .expect("cycle is nonempty")
+}
+
+fn detect_system(
+    waiting_query: Query<(Entity, &taxi::WaitingFor)>,
+    deadlocked_query: Query<(Entity, &Deadlocked)>,
+    limits_query: Query<&taxi::Limits>,
+    display_query: Query<&object::Display>,
+    mut commands: Commands,
+) {
+    let edges: HashMap<Entity, Entity> =
+        waiting_query.iter().map(|(entity, &taxi::WaitingFor(other))| (entity, other)).collect();
+
+    let mut members = HashMap::new();
+    for cycle in find_cycles(&edges) {
+        let reroute = select_reroute(&cycle, &limits_query);
+        let deadlocked = Deadlocked { cycle: cycle.clone(), reroute };
+
+        let is_new = cycle.iter().any(|&entity| {
+            deadlocked_query.get(entity).map_or(true, |(_, existing)| *existing != deadlocked)
+        });
+        if is_new {
+            let name =
+                |entity| display_query.get(entity).map_or("?", |display| display.name.as_str());
+            commands.queue(message::SendExpiring {
+                source:   reroute,
+                content:  format!(
+                    "Ground deadlock between {}, suggest rerouting {}",
+                    cycle.iter().map(|&entity| name(entity)).join(", "),
+                    name(reroute),
+                ),
+                class:    message::Class::Urgent,
+                duration: Duration::from_secs(30),
+            });
+        }
+
+        for &entity in &cycle {
+            members.insert(entity, deadlocked.clone());
+        }
+    }
+
+    for (entity, existing) in &deadlocked_query {
+        if !members.contains_key(&entity) {
+            commands.entity(entity).remove::<Deadlocked>();
+        } else if members.get(&entity) == Some(existing) {
+            members.remove(&entity);
+        }
+    }
+    for (entity, deadlocked) in members {
+        commands.entity(entity).insert(deadlocked);
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+
+use super::{Deadlocked, find_cycles};
+use crate::level::{deadlock, message, taxi, test_util};
+
+#[test]
+fn test_find_cycles_ignores_chains() {
+    let mut app = App::new();
+    let [a, b, c, d] = [(); 4].map(|()| app.world_mut().spawn_empty().id());
+
+    // d -> c -> b -> a, with a not waiting
+    let edges = HashMap::from([(d, c), (c, b), (b, a)]);
+    assert!(find_cycles(&edges).is_empty());
+}
+
+#[test]
+fn test_find_cycles_with_tail() {
+    let mut app = App::new();
+    let [a, b, c, tail] = [(); 4].map(|()| app.world_mut().spawn_empty().id());
+
+    // tail -> b -> c -> a -> b
+    let edges = HashMap::from([(tail, b), (b, c), (c, a), (a, b)]);
+    let cycles = find_cycles(&edges);
+    let [cycle] = &cycles[..] else { panic!("expected one cycle, got {cycles:?}") };
+    assert_eq!(cycle[0], *[a, b, c].iter().min().unwrap(), "cycles start with the smallest entity");
+    let a_index = cycle.iter().position(|&entity| entity == a).expect("a is in the cycle");
+    let mut from_a = cycle.clone();
+    from_a.rotate_left(a_index);
+    assert_eq!(from_a, [a, b, c]);
+}
+
+#[test]
+fn test_detect_marks_members_and_warns() {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, deadlock::Plug));
+
+    let [a, b, behind] = [(); 3].map(|()| app.world_mut().spawn_empty().id());
+    app.world_mut().entity_mut(a).insert(taxi::WaitingFor(b));
+    app.world_mut().entity_mut(b).insert(taxi::WaitingFor(a));
+    app.world_mut().entity_mut(behind).insert(taxi::WaitingFor(a));
+    app.update();
+
+    let members: Vec<Entity> = [a, b, behind]
+        .into_iter()
+        .filter(|&entity| app.world().get::<Deadlocked>(entity).is_some())
+        .collect();
+    assert_eq!(members, [a, b], "objects waiting behind a deadlock are not part of it");
+    assert_eq!(count_messages(&mut app), 1);
+
+    app.update();
+    assert_eq!(count_messages(&mut app), 1, "an unchanged deadlock is only reported once");
+
+    app.world_mut().entity_mut(b).remove::<taxi::WaitingFor>();
+    app.update();
+    assert!(app.world().get::<Deadlocked>(a).is_none());
+}
+
+fn count_messages(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&message::Message>().iter(world).count()
+}
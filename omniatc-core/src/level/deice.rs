@@ -0,0 +1,97 @@
+//! De-icing operations in winter conditions.
+//!
+//! When [`WinterOps`] is enabled, departures must taxi through a de-icing [`Pad`]
+//! and hold there for a randomized treatment time before takeoff.
+//! The treatment remains effective until its holdover time expires,
+//! after which the object must be de-iced again before it can take off.
+
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+
+use crate::level::{ground, object};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) { app.init_resource::<WinterOps>(); }
+}
+
+/// Whether winter operations are in effect.
+#[derive(Resource, Default)]
+pub struct WinterOps(pub Option<Params>);
+
+/// Parameters of winter operations.
+#[derive(Clone, Copy)]
+pub struct Params {
+    /// Minimum time to treat an object on a de-icing pad.
+    pub min_treatment: Duration,
+    /// Maximum time to treat an object on a de-icing pad.
+    pub max_treatment: Duration,
+    /// Time after treatment within which the object must take off.
+    pub holdover:      Duration,
+}
+
+/// Marks a ground segment as part of a de-icing pad.
+#[derive(Component)]
+pub struct Pad;
+
+/// An object currently being treated on a de-icing pad.
+#[derive(Component)]
+pub struct Treatment {
+    /// Virtual time at which the treatment completes.
+    pub until: Duration,
+}
+
+/// An object that has completed de-icing treatment.
+#[derive(Component)]
+pub struct Deiced {
+    /// Virtual time after which the object must be de-iced again.
+    pub holdover_expiry: Duration,
+}
+
+/// Whether the object must be de-iced before it can take off.
+#[must_use]
+pub fn requires_deicing(world: &World, object: Entity) -> bool {
+    if world.resource::<WinterOps>().0.is_none() {
+        return false;
+    }
+
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    world.get::<Deiced>(object).is_none_or(|deiced| deiced.holdover_expiry <= now)
+}
+
+/// Finds the label of the de-icing pad closest to an object on ground
+/// at the same aerodrome.
+#[must_use]
+pub fn nearest_pad(world: &World, object: Entity) -> Option<ground::SegmentLabel> {
+    let object_position = world.get::<object::Object>(object)?.position.horizontal();
+    let &ground::SegmentOf(aerodrome) =
+        world.get::<ground::SegmentOf>(world.get::<object::OnGround>(object)?.segment)?;
+
+    let mut query = world.try_query_filtered::<(
+        &ground::Segment,
+        &ground::SegmentLabel,
+        &ground::SegmentOf,
+    ), With<Pad>>()?;
+    query
+        .iter(world)
+        .filter(|&(_, _, &ground::SegmentOf(segment_aerodrome))| segment_aerodrome == aerodrome)
+        .filter_map(|(segment, label, _)| {
+            let alpha = world.get::<ground::Endpoint>(segment.alpha)?.position;
+            let beta = world.get::<ground::Endpoint>(segment.beta)?.position;
+            Some((alpha.midpoint(beta).distance_cmp(object_position), label))
+        })
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, label)| label.clone())
+}
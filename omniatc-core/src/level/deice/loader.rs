@@ -0,0 +1,12 @@
+use bevy::ecs::world::World;
+
+use crate::level::deice;
+
+/// Configures winter operations from a store.
+pub fn spawn(world: &mut World, winter_ops: Option<&store::WinterOps>) {
+    world.insert_resource(deice::WinterOps(winter_ops.map(|winter_ops| deice::Params {
+        min_treatment: winter_ops.min_treatment,
+        max_treatment: winter_ops.max_treatment,
+        holdover:      winter_ops.holdover,
+    })));
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::World;
+use bevy::math::Vec2;
+use bevy::time::{self, Time};
+use math::{Length, Position, Speed};
+use smallvec::SmallVec;
+
+use super::{Deiced, Pad, Params, Treatment, WinterOps};
+use crate::level::object::{self, Object};
+use crate::level::{deice, ground, route};
+
+const PARAMS: Params = Params {
+    min_treatment: Duration::from_mins(2),
+    max_treatment: Duration::from_mins(4),
+    holdover:      Duration::from_mins(15),
+};
+
+fn base_world(params: Option<Params>) -> World {
+    let mut world = World::new();
+    world.init_resource::<Time<time::Virtual>>();
+    world.insert_resource(WinterOps(params));
+    world
+}
+
+fn advance(world: &mut World, dt: Duration) {
+    world.resource_mut::<Time<time::Virtual>>().advance_by(dt);
+}
+
+fn spawn_segment(world: &mut World, aerodrome: Entity, name: &str, x_nm: f32) -> Entity {
+    let [alpha, beta] = [0.0, 0.1].map(|y_nm| {
+        world
+            .spawn(ground::Endpoint {
+                position:  Position::from_origin_nm(x_nm, y_nm),
+                adjacency: SmallVec::new(),
+            })
+            .id()
+    });
+    world
+        .spawn((
+            ground::Segment {
+                alpha,
+                beta,
+                width: Length::from_meters(30.0),
+                max_speed: Speed::from_knots(20.0),
+                elevation: Position::SEA_LEVEL,
+            },
+            ground::SegmentLabel::Taxiway { name: name.into() },
+            ground::SegmentOf(aerodrome),
+        ))
+        .id()
+}
+
+fn spawn_object(world: &mut World, segment: Entity, position: Position<Vec2>) -> Entity {
+    world
+        .spawn((
+            Object {
+                position:     position.with_altitude(Position::SEA_LEVEL),
+                ground_speed: Speed::ZERO,
+            },
+            object::OnGround {
+                segment,
+                direction: ground::SegmentDirection::AlphaToBeta,
+                target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+                crossing: object::RunwayCrossing::None,
+            },
+        ))
+        .id()
+}
+
+#[test]
+fn test_requires_deicing() {
+    let mut world = base_world(None);
+    let object = world.spawn_empty().id();
+    assert!(!deice::requires_deicing(&world, object), "no de-icing outside winter operations");
+
+    world.insert_resource(WinterOps(Some(PARAMS)));
+    assert!(deice::requires_deicing(&world, object));
+
+    world.entity_mut(object).insert(Deiced { holdover_expiry: Duration::from_mins(10) });
+    assert!(!deice::requires_deicing(&world, object));
+
+    advance(&mut world, Duration::from_mins(11));
+    assert!(deice::requires_deicing(&world, object), "expired holdover requires de-icing again");
+}
+
+#[test]
+fn test_nearest_pad() {
+    let mut world = base_world(Some(PARAMS));
+    let aerodrome = world.spawn_empty().id();
+    let taxiway = spawn_segment(&mut world, aerodrome, "A", 0.0);
+    let near_pad = spawn_segment(&mut world, aerodrome, "D1", 1.0);
+    let far_pad = spawn_segment(&mut world, aerodrome, "D2", 5.0);
+    world.entity_mut(near_pad).insert(Pad);
+    world.entity_mut(far_pad).insert(Pad);
+
+    let object = spawn_object(&mut world, taxiway, Position::ORIGIN);
+    assert_eq!(
+        deice::nearest_pad(&world, object),
+        Some(ground::SegmentLabel::Taxiway { name: "D1".into() })
+    );
+}
+
+#[test]
+fn test_deice_node_treatment() {
+    let mut world = base_world(Some(PARAMS));
+    let aerodrome = world.spawn_empty().id();
+    let pad = spawn_segment(&mut world, aerodrome, "D1", 0.0);
+    world.entity_mut(pad).insert(Pad);
+
+    let object = spawn_object(&mut world, pad, Position::ORIGIN);
+    let mut route = route::Route::default();
+    route.push(route::DeiceNode.into());
+    world.entity_mut(object).insert(route);
+
+    route::RunCurrentNode.apply(world.entity_mut(object));
+    let until = world.get::<Treatment>(object).expect("treatment should start on a pad").until;
+    assert!(until >= PARAMS.min_treatment && until <= PARAMS.max_treatment);
+
+    advance(&mut world, PARAMS.max_treatment + Duration::from_secs(1));
+    route::RunCurrentNode.apply(world.entity_mut(object));
+    assert!(world.get::<Treatment>(object).is_none());
+    assert!(world.get::<Deiced>(object).is_some());
+    assert!(!deice::requires_deicing(&world, object));
+    let route = world.get::<route::Route>(object).expect("route should exist");
+    assert!(route.current().is_none(), "deice node should be completed");
+}
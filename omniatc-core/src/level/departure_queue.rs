@@ -0,0 +1,257 @@
+//! Departure queues inferred from ground positions near runway hold-short points.
+//!
+//! A departure joins the queue of the runway it lines up on
+//! once it is within [`QUEUE_DISTANCE`] of a hold-short point of the runway,
+//! i.e. an endpoint connecting a runway segment to a non-runway segment.
+//! Queued departures are sequenced in the order they join the queue,
+//! which may be overridden with [`ResequenceCommand`].
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Command, Commands, Local, Query};
+use bevy::ecs::world::World;
+use bevy::math::Vec2;
+use math::{Length, Position};
+
+use super::object::{self, Object};
+use super::route::{self, TaxiStopMode};
+use super::{SystemSets, ground, nav, wake};
+use crate::QueryTryLog;
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum distance from a hold-short point for a departure to join the queue.
+pub const QUEUE_DISTANCE: Length<f32> = Length::from_meters(300.0);
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(app::Update, infer_system.in_set(SystemSets::ReconcileForRead));
+    }
+}
+
+/// The departures queued at a runway, in sequence order.
+///
+/// Component on runway entities that have ever had queued departures.
+#[derive(Component, Default)]
+pub struct Queue {
+    pub entries: Vec<Entry>,
+}
+
+/// A departure queued at a runway.
+pub struct Entry {
+    /// The queued object.
+    pub object:        Entity,
+    /// Whether the object has already lined up on the runway.
+    pub lined_up:      bool,
+    /// Expected wait for the wake of the departures ahead to dissipate.
+    pub expected_wait: Duration,
+}
+
+/// Position of a queued departure in the sequence of its runway.
+///
+/// Objects with lower values depart earlier.
+/// Only the relative order between objects is meaningful.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Sequence(pub u32);
+
+/// Reorders queued departures.
+///
+/// `order` lists queued objects in the desired sequence.
+/// The sequence numbers already held by these objects are redistributed in this order,
+/// so objects not listed retain their positions.
+pub struct ResequenceCommand {
+    pub order: Vec<Entity>,
+}
+
+impl Command for ResequenceCommand {
+    fn apply(self, world: &mut World) {
+        let mut sequences: Vec<Sequence> = self
+            .order
+            .iter()
+            .filter_map(|&object| world.get::<Sequence>(object).copied())
+            .collect();
+        sequences.sort_unstable();
+
+        let queued =
+            self.order.into_iter().filter(|&object| world.get::<Sequence>(object).is_some());
+        for (object, sequence) in queued.collect::<Vec<_>>().into_iter().zip(sequences) {
+            world.entity_mut(object).insert(sequence);
+        }
+    }
+}
+
+/// Returns the runway that an object departing along `route` lines up on.
+///
+/// Returns `None` if the route does not lead to a takeoff.
+#[must_use]
+pub fn lineup_runway(route: &route::Route) -> Option<Entity> {
+    if !route.iter().any(|node| matches!(node, route::Node::Takeoff(_))) {
+        return None;
+    }
+
+    route.iter().find_map(|node| match node {
+        route::Node::Taxi(route::TaxiNode {
+            label: ground::SegmentLabel::RunwayPair([runway, _]),
+            stop: TaxiStopMode::LineUp,
+            ..
+        }) => Some(*runway),
+        _ => None,
+    })
+}
+
+/// Returns the standby node holding an object before it lines up,
+/// i.e. the node to clear for the object to line up on the runway.
+#[must_use]
+pub fn lineup_standby(route: &route::Route) -> Option<&route::StandbyNode> {
+    let mut standby = None;
+    for node in route.iter() {
+        match node {
+            route::Node::Standby(node) => standby = Some(node),
+            route::Node::Taxi(route::TaxiNode { stop: TaxiStopMode::LineUp, .. }) => {
+                return standby;
+            }
+            _ => standby = None,
+        }
+    }
+    None
+}
+
+/// Estimates the time for the wake of a departing object to dissipate.
+#[must_use]
+pub fn wake_duration(producer: &wake::Producer, limits: &nav::Limits) -> Duration {
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "takeoff speed is a small positive value"
+    )]
+    let takeoff_knots = limits.takeoff_speed.into_knots().max(1.0) as u32;
+    Duration::from_millis((producer.base_intensity.0 / takeoff_knots).into())
+}
+
+struct Candidate {
+    object:        Entity,
+    lined_up:      bool,
+    sequence:      Sequence,
+    wake_duration: Duration,
+}
+
+fn infer_system(
+    mut next_sequence: Local<u32>,
+    object_query: Query<(
+        Entity,
+        &Object,
+        &object::OnGround,
+        &route::Route,
+        Option<&Sequence>,
+        &wake::Producer,
+        &nav::Limits,
+    )>,
+    mut runway_query: Query<(Entity, &ground::RunwaySegments, Option<&mut Queue>)>,
+    segment_query: Query<&ground::Segment>,
+    runway_of_query: Query<&ground::SegmentOfRunway>,
+    endpoint_query: Query<&ground::Endpoint>,
+    mut commands: Commands,
+) {
+    let mut hold_points = HashMap::<Entity, Vec<Position<Vec2>>>::new();
+    let mut queues = HashMap::<Entity, Vec<Candidate>>::new();
+
+    for (entity, object, ground, route, sequence, producer, limits) in object_query {
+        let queued_runway = if let Some(runway) = lineup_runway(route) {
+            let points = hold_points.entry(runway).or_insert_with(|| {
+                find_hold_points(
+                    runway,
+                    &runway_query,
+                    &segment_query,
+                    &runway_of_query,
+                    &endpoint_query,
+                )
+            });
+            let position = object.position.horizontal();
+            points
+                .iter()
+                .any(|&point| position.distance_cmp(point) <= QUEUE_DISTANCE)
+                .then_some((runway, false))
+        } else if route.iter().any(|node| matches!(node, route::Node::Takeoff(_)))
+            && let Ok(runways) = runway_of_query.get(ground.segment)
+        {
+            Some((runways.by_direction(ground.direction), true))
+        } else {
+            None
+        };
+
+        let Some((runway, lined_up)) = queued_runway else {
+            if sequence.is_some() {
+                commands.entity(entity).remove::<Sequence>();
+            }
+            continue;
+        };
+
+        let sequence = sequence.copied().unwrap_or_else(|| {
+            *next_sequence += 1;
+            let sequence = Sequence(*next_sequence);
+            commands.entity(entity).insert(sequence);
+            sequence
+        });
+        queues.entry(runway).or_default().push(Candidate {
+            object: entity,
+            lined_up,
+            sequence,
+            wake_duration: wake_duration(producer, limits),
+        });
+    }
+
+    for (runway, _, queue) in &mut runway_query {
+        let mut candidates = queues.remove(&runway).unwrap_or_default();
+        candidates.sort_by_key(|candidate| (!candidate.lined_up, candidate.sequence));
+
+        let mut expected_wait = Duration::ZERO;
+        let entries = candidates
+            .into_iter()
+            .map(|candidate| {
+                let entry =
+                    Entry { object: candidate.object, lined_up: candidate.lined_up, expected_wait };
+                expected_wait += candidate.wake_duration;
+                entry
+            })
+            .collect();
+
+        match queue {
+            Some(mut queue) => queue.entries = entries,
+            None if !entries.is_empty() => {
+                commands.entity(runway).insert(Queue { entries });
+            }
+            None => {}
+        }
+    }
+}
+
+/// Finds the endpoints of a runway leading to non-runway segments.
+fn find_hold_points(
+    runway: Entity,
+    runway_query: &Query<(Entity, &ground::RunwaySegments, Option<&mut Queue>)>,
+    segment_query: &Query<&ground::Segment>,
+    runway_of_query: &Query<&ground::SegmentOfRunway>,
+    endpoint_query: &Query<&ground::Endpoint>,
+) -> Vec<Position<Vec2>> {
+    let Some((_, segments, _)) = runway_query.log_get(runway) else { return Vec::new() };
+
+    segments
+        .0
+        .iter()
+        .filter_map(|&segment| segment_query.log_get(segment))
+        .flat_map(|segment| [segment.alpha, segment.beta])
+        .filter_map(|endpoint| endpoint_query.log_get(endpoint))
+        .filter(|endpoint| {
+            endpoint.adjacency.iter().any(|&adjacent| !runway_of_query.contains(adjacent))
+        })
+        .map(|endpoint| endpoint.position)
+        .collect()
+}
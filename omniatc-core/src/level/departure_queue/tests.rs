@@ -0,0 +1,78 @@
+use std::num::NonZero;
+
+use bevy::ecs::system::Command;
+use bevy::ecs::world::World;
+use math::Position;
+
+use super::{ResequenceCommand, Sequence, lineup_runway, lineup_standby};
+use crate::level::ground;
+use crate::level::route::{self, TaxiStopMode};
+
+#[test]
+fn test_resequence_redistributes_sequences() {
+    let mut world = World::new();
+    let first = world.spawn(Sequence(3)).id();
+    let second = world.spawn(Sequence(5)).id();
+    let third = world.spawn(Sequence(8)).id();
+    let unqueued = world.spawn_empty().id();
+
+    ResequenceCommand { order: vec![third, unqueued, first] }.apply(&mut world);
+
+    assert_eq!(world.get::<Sequence>(third), Some(&Sequence(3)));
+    assert_eq!(world.get::<Sequence>(first), Some(&Sequence(8)));
+    assert_eq!(world.get::<Sequence>(second), Some(&Sequence(5)), "unlisted objects are kept");
+    assert_eq!(world.get::<Sequence>(unqueued), None, "unqueued objects are not sequenced");
+}
+
+#[test]
+fn test_lineup_runway_requires_takeoff() {
+    let mut world = World::new();
+    let runway = world.spawn_empty().id();
+    let paired = world.spawn_empty().id();
+
+    let lineup = route::Node::Taxi(route::TaxiNode {
+        label:     ground::SegmentLabel::RunwayPair([runway, paired]),
+        direction: None,
+        stop:      TaxiStopMode::LineUp,
+    });
+    let takeoff = route::Node::Takeoff(route::TakeoffNode {
+        target_altitude: Position::from_amsl_feet(4000.),
+    });
+
+    let mut taxi_only = route::Route::default();
+    taxi_only.push(lineup.clone());
+    assert_eq!(lineup_runway(&taxi_only), None);
+
+    let mut departure = route::Route::default();
+    departure.extend([lineup, takeoff]);
+    assert_eq!(lineup_runway(&departure), Some(runway));
+}
+
+#[test]
+fn test_lineup_standby() {
+    let mut world = World::new();
+    let runway = world.spawn_empty().id();
+    let paired = world.spawn_empty().id();
+
+    let taxi = |stop| {
+        route::Node::Taxi(route::TaxiNode {
+            label: ground::SegmentLabel::RunwayPair([runway, paired]),
+            direction: None,
+            stop,
+        })
+    };
+    let standby = |id| route::Node::Standby(route::StandbyNode { skip_id: NonZero::new(id) });
+
+    let mut route = route::Route::default();
+    route.extend([
+        taxi(TaxiStopMode::HoldShort),
+        standby(1),
+        taxi(TaxiStopMode::LineUp),
+        standby(2),
+    ]);
+    assert_eq!(lineup_standby(&route).map(|node| node.skip_id), Some(NonZero::new(1)));
+
+    let mut cleared = route::Route::default();
+    cleared.extend([taxi(TaxiStopMode::HoldShort), taxi(TaxiStopMode::LineUp), standby(2)]);
+    assert!(lineup_standby(&cleared).is_none());
+}
@@ -1,7 +1,8 @@
 use bevy::app::{self, App, Plugin};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
-use bevy::ecs::query::QueryData;
+use bevy::ecs::message::{Message, MessageWriter};
+use bevy::ecs::query::{Has, QueryData};
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Query, ResMut, SystemParam};
 use bevy::math::Dir2;
@@ -21,6 +22,7 @@ pub struct Plug;
 
 impl Plugin for Plug {
     fn build(&self, app: &mut App) {
+        app.add_message::<CompletedMessage>();
         app.add_systems(
             app::Update,
             completion_system.in_set(SystemSets::Statistics).in_set(score::Writer),
@@ -49,6 +51,31 @@ pub enum Destination {
     },
 }
 
+/// The aerodrome from which an object departed.
+///
+/// Used for attributing completed departures to per-aerodrome statistics.
+#[derive(Component)]
+pub struct Origin {
+    pub aerodrome: Entity,
+}
+
+/// Marks an object on the ground with an arrival destination that has not taken off yet,
+/// e.g. a local flight in the traffic pattern of its origin aerodrome.
+///
+/// Arrival destinations of such objects are not evaluated until they become airborne.
+#[derive(Component)]
+pub struct PendingTakeoff;
+
+/// Sent when an object completes its destination, right before it is despawned.
+#[derive(Message)]
+pub struct CompletedMessage(pub Entity);
+
+/// Marks an object that has completed its destination and is about to be despawned.
+///
+/// This allows observers of the despawn to distinguish completions from other removals.
+#[derive(Component)]
+pub struct Completed;
+
 /// Objects with this component award a score upon completion of their destination.
 #[derive(Component)]
 pub struct CompletionScore {
@@ -61,6 +88,7 @@ fn completion_system(
     params: CompletionParams,
     mut commands: Commands,
     mut score: ResMut<score::Stats>,
+    mut completed_writer: MessageWriter<CompletedMessage>,
 ) {
     let mut runway_arrivals = 0;
     let mut apron_arrivals = 0;
@@ -68,6 +96,13 @@ fn completion_system(
     let mut delta = Score::default();
 
     for (object, mut dest, reward) in object_query {
+        if object.pending_takeoff {
+            if object.airborne {
+                commands.entity(object.entity).remove::<PendingTakeoff>();
+            }
+            continue;
+        }
+
         let result = match *dest {
             Destination::Landing { aerodrome } => {
                 detect_runway_vacation(&object, Some(aerodrome), &params)
@@ -81,17 +116,39 @@ fn completion_system(
             }
         };
         if let Some(DetectResult::Completed) = result {
-            commands.entity(object.entity).queue(object::DespawnCommand);
+            completed_writer.write(CompletedMessage(object.entity));
+            commands.entity(object.entity).insert(Completed).queue(object::DespawnCommand);
 
-            match *dest {
-                Destination::Landing { .. } | Destination::VacateAnyRunway => {
+            let aerodrome = match *dest {
+                Destination::Landing { aerodrome } => {
                     runway_arrivals += 1;
+                    Some(aerodrome)
                 }
-                Destination::Parking { .. } => {
+                Destination::VacateAnyRunway => {
+                    runway_arrivals += 1;
+                    object.ground.and_then(|(ground, _)| {
+                        let (_, _, &ground::SegmentOf(aerodrome)) =
+                            params.segment_query.log_get(ground.segment)?;
+                        Some(aerodrome)
+                    })
+                }
+                Destination::Parking { aerodrome } => {
                     apron_arrivals += 1;
+                    Some(aerodrome)
                 }
                 Destination::Departure { .. } => {
                     departures += 1;
+                    object.origin.map(|origin| origin.aerodrome)
+                }
+            };
+            if let Some(aerodrome) = aerodrome {
+                let aerodrome_stats = score.aerodromes.entry(aerodrome).or_default();
+                match *dest {
+                    Destination::Landing { .. } | Destination::VacateAnyRunway => {
+                        aerodrome_stats.num_runway_arrivals += 1;
+                    }
+                    Destination::Parking { .. } => aerodrome_stats.num_apron_arrivals += 1,
+                    Destination::Departure { .. } => aerodrome_stats.num_departures += 1,
                 }
             }
 
@@ -109,10 +166,13 @@ fn completion_system(
 
 #[derive(QueryData)]
 struct CompletionObjectQuery {
-    entity:      Entity,
-    object:      &'static Object,
-    ground:      Option<(&'static object::OnGround, &'static object::TaxiStatus)>,
-    taxi_limits: &'static taxi::Limits,
+    entity:          Entity,
+    object:          &'static Object,
+    ground:          Option<(&'static object::OnGround, &'static object::TaxiStatus)>,
+    taxi_limits:     &'static taxi::Limits,
+    origin:          Option<&'static Origin>,
+    pending_takeoff: Has<PendingTakeoff>,
+    airborne:        Has<object::Airborne>,
 }
 
 #[derive(SystemParam)]
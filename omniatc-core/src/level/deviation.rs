@@ -0,0 +1,298 @@
+//! Monitoring of deviations from acknowledged clearances.
+//!
+//! An airborne object is monitored against its [`pilot::Clearance`].
+//! An [`Alert`] is raised when the object busts its cleared altitude after capturing it,
+//! or when it flies off its cleared heading for longer than the configured duration
+//! without turning towards it.
+//! The alert is cleared once the object is back within tolerance.
+//!
+//! A [`route::CrossingRestriction`] is checked when the object crosses the restricted waypoint,
+//! and a missed restriction is reported once.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{QueryData, With};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Angle, Heading, Length, Position};
+use store::YawTarget;
+
+use super::waypoint::Waypoint;
+use super::{SystemSets, message, nav, object, pilot, plane, route};
+use crate::level::instr::{self, Instruction};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:deviation");
+        app.add_systems(
+            app::Update,
+            (monitor_system, crossing_system).in_set(SystemSets::Statistics),
+        );
+    }
+}
+
+#[derive(Config)]
+#[config(expose(read))]
+pub struct Conf {
+    /// Altitude deviation from a captured clearance that raises an alert.
+    #[config(default = Length::from_feet(300.0), min = Length::ZERO, max = Length::from_feet(2000.0))]
+    pub altitude_tolerance: Length<f32>,
+    /// Heading deviation from the clearance that starts the heading timer.
+    #[config(default = Angle::from_degrees(15.0))]
+    pub heading_tolerance:  Angle,
+    /// Duration of a heading deviation after which an alert is raised.
+    #[config(default = Duration::from_secs(30))]
+    pub heading_duration:   Duration,
+}
+
+/// Maximum distance from a restricted waypoint at which
+/// leaving its route node counts as crossing the waypoint.
+///
+/// Routes replaced further away from the waypoint drop the restriction without checking it.
+const CROSSING_DISTANCE: Length<f32> = Length::from_nm(3.0);
+
+/// An active deviation of an object from its acknowledged clearance.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Alert {
+    pub kind:  AlertKind,
+    /// Virtual time at which the alert was raised.
+    pub since: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AlertKind {
+    /// The object has left its cleared altitude.
+    Altitude { cleared: Position<f32>, actual: Position<f32> },
+    /// The object has been flying off its cleared heading.
+    Heading { cleared: Heading, actual: Heading },
+}
+
+impl AlertKind {
+    /// Short description of the deviation for display.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match *self {
+            AlertKind::Altitude { cleared, actual } => format!(
+                "Altitude {:.0} ft, cleared {:.0} ft",
+                actual.amsl().into_feet(),
+                cleared.amsl().into_feet()
+            ),
+            AlertKind::Heading { cleared, actual } => {
+                format!("Heading {:03.0}, cleared {:03.0}", actual.degrees(), cleared.degrees())
+            }
+        }
+    }
+
+    /// The instruction that reissues the deviated clearance.
+    #[must_use]
+    pub fn corrective_instruction(&self) -> Instruction {
+        match *self {
+            AlertKind::Altitude { cleared, .. } => instr::SetAltitude {
+                target: nav::TargetAltitude { altitude: cleared, expedite: false },
+            }
+            .into(),
+            AlertKind::Heading { cleared, .. } => {
+                instr::SetHeading { target: YawTarget::Heading(cleared) }.into()
+            }
+        }
+    }
+}
+
+/// Monitoring state of an object.
+#[derive(Component, Default)]
+pub struct Status {
+    /// The cleared altitude that the object has reached.
+    captured_altitude: Option<Position<f32>>,
+    /// The cleared heading that the object has reached.
+    captured_heading:  Option<Heading>,
+    /// Virtual time since which the object has been off its cleared heading.
+    heading_off_since: Option<Duration>,
+}
+
+#[derive(QueryData)]
+struct MonitorQueryData {
+    entity:    Entity,
+    object:    &'static object::Object,
+    control:   &'static plane::Control,
+    clearance: &'static pilot::Clearance,
+    queue:     Option<&'static pilot::ReactionQueue>,
+    target:    Option<&'static nav::VelocityTarget>,
+    glide:     Option<&'static nav::TargetGlide>,
+    alert:     Option<&'static Alert>,
+}
+
+fn monitor_system(
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    mut object_query: Query<(MonitorQueryData, Option<&mut Status>), With<object::Airborne>>,
+    mut commands: Commands,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let conf = conf.read();
+    let now = time.elapsed();
+
+    for (data, mut status) in &mut object_query {
+        let mut default_status = Status::default();
+        let status_mut = match status {
+            Some(ref mut status) => &mut **status,
+            None => &mut default_status,
+        };
+
+        // Instructions still awaiting execution are not deviations yet.
+        let executing = data.queue.is_some_and(|queue| queue.iter().next().is_some());
+        let kind = if executing {
+            None
+        } else {
+            check_altitude(&conf, &data, status_mut)
+                .or_else(|| check_heading(&conf, &data, status_mut, now))
+        };
+
+        if status.is_none() {
+            commands.entity(data.entity).insert(default_status);
+        }
+
+        match (kind, data.alert) {
+            (Some(kind), None) => {
+                commands.entity(data.entity).insert(Alert { kind, since: now });
+                commands.queue(message::SendExpiring {
+                    source:   data.entity,
+                    content:  format!("Deviation: {}", kind.describe()),
+                    class:    message::Class::Urgent,
+                    duration: Duration::from_secs(15),
+                });
+            }
+            (Some(kind), Some(alert)) => {
+                commands.entity(data.entity).insert(Alert { kind, since: alert.since });
+            }
+            (None, Some(_)) => {
+                commands.entity(data.entity).remove::<Alert>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn check_altitude(
+    conf: &ConfRead,
+    data: &MonitorQueryDataItem,
+    status: &mut Status,
+) -> Option<AlertKind> {
+    let cleared = data.clearance.altitude?;
+    if data.glide.is_some() {
+        // The glidepath supersedes the cleared altitude.
+        return None;
+    }
+
+    let actual = data.object.position.altitude();
+    if (actual - cleared).abs() <= conf.altitude_tolerance {
+        status.captured_altitude = Some(cleared);
+        return None;
+    }
+    if status.captured_altitude != Some(cleared) {
+        return None;
+    }
+    Some(AlertKind::Altitude { cleared, actual })
+}
+
+fn check_heading(
+    conf: &ConfRead,
+    data: &MonitorQueryDataItem,
+    status: &mut Status,
+    now: Duration,
+) -> Option<AlertKind> {
+    let Some(cleared) = data.clearance.heading else {
+        status.heading_off_since = None;
+        return None;
+    };
+
+    let actual = data.control.heading;
+    if actual.closest_distance(cleared).abs() <= conf.heading_tolerance {
+        status.captured_heading = Some(cleared);
+        status.heading_off_since = None;
+        return None;
+    }
+
+    // An object still turning towards its cleared heading is not deviating.
+    let turning_towards = data.target.is_some_and(|target| {
+        target.yaw.heading().closest_distance(cleared).abs() <= conf.heading_tolerance
+    });
+    if turning_towards && !status.captured_heading.is_some_and(|h| same_heading(h, cleared)) {
+        status.heading_off_since = None;
+        return None;
+    }
+
+    let since = *status.heading_off_since.get_or_insert(now);
+    if now.saturating_sub(since) < conf.heading_duration {
+        return None;
+    }
+    Some(AlertKind::Heading { cleared, actual })
+}
+
+fn same_heading(a: Heading, b: Heading) -> bool { a.closest_distance(b).is_zero() }
+
+fn crossing_system(
+    conf: ReadConfig<Conf>,
+    object_query: Query<(
+        Entity,
+        &object::Object,
+        &route::CrossingRestriction,
+        Option<&route::Route>,
+    )>,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (entity, object, restriction, route) in object_query {
+        let pending = route.is_some_and(|route| {
+            route.iter().any(|node| {
+                matches!(node, route::Node::DirectWaypoint(node) if node.waypoint == restriction.waypoint)
+            })
+        });
+        if pending {
+            continue;
+        }
+
+        commands.entity(entity).remove::<route::CrossingRestriction>();
+
+        let Ok(waypoint) = waypoint_query.get(restriction.waypoint) else { continue };
+        if object.position.horizontal_distance_exact(waypoint.position) > CROSSING_DISTANCE {
+            continue;
+        }
+
+        let actual = object.position.altitude();
+        if (actual - restriction.altitude).abs() > conf.altitude_tolerance {
+            commands.queue(message::SendExpiring {
+                source:   entity,
+                content:  format!(
+                    "Deviation: crossed {} at {:.0} ft, restricted {:.0} ft",
+                    waypoint.name,
+                    actual.amsl().into_feet(),
+                    restriction.altitude.amsl().into_feet()
+                ),
+                class:    message::Class::Urgent,
+                duration: Duration::from_secs(15),
+            });
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::time::{self, Time};
+use math::{
+    Accel, AngularSpeed, Heading, ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position,
+    Speed,
+};
+use store::YawTarget;
+
+use super::{Alert, AlertKind};
+use crate::level::instr::Instruction;
+use crate::level::object::{self, Object};
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{deviation, message, nav, pilot, plane, route, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, deviation::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App, clearance: pilot::Clearance) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(5000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  Position::from_amsl_feet(5000.0),
+            },
+            plane::Control {
+                heading:     Heading::from_degrees(90.0),
+                yaw_speed:   AngularSpeed::ZERO,
+                horiz_accel: Accel::ZERO,
+            },
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::from_degrees(90.0)),
+                horiz_speed: Speed::from_knots(250.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+            clearance,
+        ))
+        .id()
+}
+
+fn set_altitude(app: &mut App, object: Entity, feet: f32) {
+    let mut entity = app.world_mut().entity_mut(object);
+    let mut object = entity.get_mut::<Object>().expect("object was spawned with Object");
+    object.position = object.position.horizontal().with_altitude(Position::from_amsl_feet(feet));
+}
+
+fn advance(app: &mut App, secs: u64) {
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(secs));
+    app.update();
+}
+
+#[test]
+fn test_altitude_bust_after_capture() {
+    let mut app = base_app();
+    let object = spawn_object(
+        &mut app,
+        pilot::Clearance { altitude: Some(Position::from_amsl_feet(5000.0)), ..Default::default() },
+    );
+
+    advance(&mut app, 1);
+    assert!(app.world().get::<Alert>(object).is_none());
+
+    set_altitude(&mut app, object, 5200.0);
+    advance(&mut app, 1);
+    assert!(app.world().get::<Alert>(object).is_none(), "deviation within tolerance");
+
+    set_altitude(&mut app, object, 5400.0);
+    advance(&mut app, 1);
+    let alert = app.world().get::<Alert>(object).expect("altitude bust should raise an alert");
+    assert!(matches!(alert.kind, AlertKind::Altitude { .. }));
+    assert!(matches!(
+        alert.kind.corrective_instruction(),
+        Instruction::SetAltitude(ref instr) if instr.target.altitude == Position::from_amsl_feet(5000.0)
+    ));
+
+    set_altitude(&mut app, object, 5100.0);
+    advance(&mut app, 1);
+    assert!(app.world().get::<Alert>(object).is_none(), "alert should clear within tolerance");
+}
+
+#[test]
+fn test_altitude_not_captured_yet() {
+    let mut app = base_app();
+    let object = spawn_object(
+        &mut app,
+        pilot::Clearance { altitude: Some(Position::from_amsl_feet(9000.0)), ..Default::default() },
+    );
+
+    advance(&mut app, 1);
+    assert!(app.world().get::<Alert>(object).is_none(), "climbing to a new clearance");
+}
+
+#[test]
+fn test_heading_deviation_after_duration() {
+    let mut app = base_app();
+    let object = spawn_object(
+        &mut app,
+        pilot::Clearance { heading: Some(Heading::from_degrees(90.0)), ..Default::default() },
+    );
+    advance(&mut app, 1);
+
+    {
+        let mut entity = app.world_mut().entity_mut(object);
+        entity.get_mut::<plane::Control>().expect("spawned").heading = Heading::from_degrees(120.0);
+        entity.get_mut::<nav::VelocityTarget>().expect("spawned").yaw =
+            YawTarget::Heading(Heading::from_degrees(120.0));
+    }
+    advance(&mut app, 1);
+    advance(&mut app, 20);
+    assert!(app.world().get::<Alert>(object).is_none(), "deviation shorter than duration");
+
+    advance(&mut app, 11);
+    let alert = app.world().get::<Alert>(object).expect("heading deviation should raise an alert");
+    assert!(matches!(alert.kind, AlertKind::Heading { .. }));
+}
+
+fn urgent_messages(app: &mut App) -> Vec<String> {
+    let world = app.world_mut();
+    world
+        .query::<&message::Message>()
+        .iter(world)
+        .filter(|message| message.class == message::Class::Urgent)
+        .map(|message| message.content.clone())
+        .collect()
+}
+
+fn spawn_crossing(app: &mut App, restricted_feet: f32) -> Entity {
+    let waypoint = app
+        .world_mut()
+        .spawn(Waypoint {
+            name:         "DWIND".into(),
+            position:     Position::from_origin_nm(0.5, 0.0)
+                .with_altitude(Position::from_amsl_feet(0.0)),
+            display_type: waypoint::DisplayType::Waypoint,
+            hidden:       false,
+        })
+        .id();
+    let object = spawn_object(app, pilot::Clearance::default());
+    app.world_mut().entity_mut(object).insert((
+        route::Route::default(),
+        route::CrossingRestriction {
+            waypoint,
+            altitude: Position::from_amsl_feet(restricted_feet),
+        },
+    ));
+    object
+}
+
+#[test]
+fn test_crossing_restriction_missed() {
+    let mut app = base_app();
+    let object = spawn_crossing(&mut app, 4000.0);
+
+    advance(&mut app, 1);
+    assert!(app.world().get::<route::CrossingRestriction>(object).is_none());
+    assert_eq!(
+        urgent_messages(&mut app),
+        ["Deviation: crossed DWIND at 5000 ft, restricted 4000 ft"]
+    );
+}
+
+#[test]
+fn test_crossing_restriction_met() {
+    let mut app = base_app();
+    let object = spawn_crossing(&mut app, 5100.0);
+
+    advance(&mut app, 1);
+    assert!(app.world().get::<route::CrossingRestriction>(object).is_none());
+    assert!(urgent_messages(&mut app).is_empty());
+}
@@ -0,0 +1,188 @@
+//! Diversion of arrivals to another aerodrome.
+//!
+//! A diversion replaces the [`Destination`] of an arrival,
+//! selects a new route from the arrival presets of the new aerodrome,
+//! and reduces the completion score of the object.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, EntityCommand, Query, Res, SystemState};
+use bevy::ecs::world::{EntityWorldMut, World};
+use bevy::math::Vec3;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::Position;
+use store::Score;
+
+use super::SystemSets;
+use crate::EntityMutTryLog;
+use crate::level::aerodrome::Aerodrome;
+use crate::level::dest::{CompletionScore, Destination};
+use crate::level::object::{self, Object};
+use crate::level::runway::AerodromeRunways;
+use crate::level::waypoint::Waypoint;
+use crate::level::{fuel, message, route};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:divert");
+        app.add_systems(app::Update, fuel_divert_system.in_set(SystemSets::Action));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// The completion score of a diverted object is multiplied by this factor.
+    #[config(default = 0.5, min = 0.0, max = 1.0)]
+    pub score_multiplier:      f32,
+    /// Objects automatically divert to the nearest aerodrome
+    /// when the remaining endurance drops below this duration.
+    #[config(default = Duration::from_mins(10))]
+    pub auto_divert_endurance: Duration,
+}
+
+/// Marks an object that has been diverted from its original destination.
+#[derive(Component)]
+pub struct Diverted;
+
+/// Diverts an arrival to another aerodrome.
+///
+/// Landing and parking destinations are retained as landing and parking respectively.
+/// Other destinations are replaced with a landing at the new aerodrome.
+///
+/// The route is replaced with the arrival preset for the new aerodrome
+/// whose starting waypoint is closest to the object.
+/// If no such preset exists, the route is cleared.
+pub struct DivertCommand {
+    pub aerodrome: Entity,
+}
+
+impl EntityCommand for DivertCommand {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let score_multiplier = entity.world_scope(|world| {
+            let mut state = SystemState::<ReadConfig<Conf>>::new(world);
+            state.get(world).read().score_multiplier
+        });
+
+        let Some(&Object { position, .. }) = entity.log_get::<Object>() else { return };
+
+        let destination = match entity.get::<Destination>() {
+            Some(Destination::Parking { .. }) => Destination::Parking { aerodrome: self.aerodrome },
+            _ => Destination::Landing { aerodrome: self.aerodrome },
+        };
+        let preset = entity.world_scope(|world| find_arrival_preset(world, &destination, position));
+
+        entity.insert((destination, Diverted));
+        if let Some(mut completion_score) = entity.get_mut::<CompletionScore>() {
+            #[expect(
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                reason = "scores are small integers"
+            )]
+            let score = (completion_score.score.0 as f32 * score_multiplier).round() as i32;
+            completion_score.score = Score(score);
+        }
+
+        if let Some(preset) = preset {
+            entity.insert(route::Id(Some(preset.id)));
+            route::ReplaceNodes(preset.nodes).apply(entity);
+        } else {
+            entity.remove::<route::Id>();
+            route::ClearAllNodes.apply(entity);
+        }
+    }
+}
+
+/// Finds the route preset towards `destination`
+/// whose starting waypoint is closest to `position`.
+#[must_use]
+pub fn find_arrival_preset(
+    world: &mut World,
+    destination: &Destination,
+    position: Position<Vec3>,
+) -> Option<route::Preset> {
+    let mut state = SystemState::<(
+        Query<(&route::Preset, &route::DestinationMatcher, &route::PresetFromWaypoint)>,
+        Query<&Waypoint>,
+    )>::new(world);
+    let (preset_query, waypoint_query) = state.get(world);
+
+    preset_query
+        .iter()
+        .filter(|(_, matcher, _)| matcher.matches(destination))
+        .filter_map(|(preset, _, &route::PresetFromWaypoint(waypoint))| {
+            let waypoint = waypoint_query.get(waypoint).ok()?;
+            Some((preset, position.horizontal_distance_cmp(waypoint.position)))
+        })
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(preset, _)| preset.clone())
+}
+
+fn fuel_divert_system(
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<
+        (Entity, &Object, &fuel::Endurance, &Destination),
+        (With<object::Airborne>, Without<Diverted>),
+    >,
+    aerodrome_query: Query<(Entity, &Aerodrome, &AerodromeRunways)>,
+    runway_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (object_entity, object, endurance, destination) in object_query {
+        if endurance.remaining >= conf.auto_divert_endurance {
+            continue;
+        }
+
+        let current_aerodrome = match *destination {
+            Destination::Landing { aerodrome } | Destination::Parking { aerodrome } => aerodrome,
+            Destination::VacateAnyRunway | Destination::Departure { .. } => continue,
+        };
+
+        let nearest = aerodrome_query
+            .iter()
+            .filter_map(|(aerodrome_entity, aerodrome, runways)| {
+                let distance = runways
+                    .as_ref()
+                    .iter()
+                    .filter_map(|&runway| runway_query.get(runway).ok())
+                    .map(|waypoint| object.position.horizontal_distance_cmp(waypoint.position))
+                    .min()?;
+                Some((aerodrome_entity, aerodrome, distance))
+            })
+            .min_by_key(|&(_, _, distance)| distance);
+        let Some((nearest_entity, nearest_aerodrome, _)) = nearest else { continue };
+
+        if nearest_entity == current_aerodrome {
+            continue; // still closest to the planned destination
+        }
+
+        commands.entity(object_entity).queue(DivertCommand { aerodrome: nearest_entity });
+        commands.spawn(message::Message {
+            source:  object_entity,
+            created: time.elapsed(),
+            content: format!("Diverting to {} due to fuel", nearest_aerodrome.name),
+            class:   message::Class::AnomalyInfo,
+        });
+    }
+}
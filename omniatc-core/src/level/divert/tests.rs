@@ -0,0 +1,69 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Position, Speed};
+use store::Score;
+
+use super::{DivertCommand, Diverted};
+use crate::level::dest::{CompletionScore, Destination};
+use crate::level::object::Object;
+use crate::level::{divert, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins(divert::Plug::<()>::default());
+    app.update();
+    app
+}
+
+fn spawn_arrival(app: &mut App, destination: Destination) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(5000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            destination,
+            CompletionScore { score: Score(10) },
+        ))
+        .id()
+}
+
+fn divert(app: &mut App, object: Entity, aerodrome: Entity) {
+    let world = app.world_mut();
+    world.commands().entity(object).queue(DivertCommand { aerodrome });
+    world.flush();
+}
+
+#[test]
+fn test_divert_landing() {
+    let mut app = base_app();
+    let original = app.world_mut().spawn_empty().id();
+    let alternate = app.world_mut().spawn_empty().id();
+    let object = spawn_arrival(&mut app, Destination::Landing { aerodrome: original });
+
+    divert(&mut app, object, alternate);
+
+    let world = app.world();
+    assert!(matches!(
+        world.get::<Destination>(object),
+        Some(&Destination::Landing { aerodrome }) if aerodrome == alternate
+    ));
+    assert!(world.get::<Diverted>(object).is_some());
+    assert_eq!(world.get::<CompletionScore>(object).map(|c| c.score), Some(Score(5)));
+}
+
+#[test]
+fn test_divert_parking() {
+    let mut app = base_app();
+    let original = app.world_mut().spawn_empty().id();
+    let alternate = app.world_mut().spawn_empty().id();
+    let object = spawn_arrival(&mut app, Destination::Parking { aerodrome: original });
+
+    divert(&mut app, object, alternate);
+
+    assert!(matches!(
+        app.world().get::<Destination>(object),
+        Some(&Destination::Parking { aerodrome }) if aerodrome == alternate
+    ));
+}
@@ -0,0 +1,233 @@
+//! Unpowered objects drifting with the wind.
+//!
+//! A [`Drifter`] is airborne but has no engine.
+//! Balloons move along with the wind,
+//! while gliders circle in [`Thermal`]s to gain altitude and glide between them.
+//! Drifters cannot comply with instructions,
+//! but they are still subject to conflict detection against other traffic.
+
+use std::marker::PhantomData;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, EntityCommand, Query, Res};
+use bevy::ecs::world::EntityWorldMut;
+use bevy::math::{Vec2, Vec3};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Angle, Heading, Length, Position, Speed};
+
+use super::SystemSets;
+use crate::level::instr::{self, Instruction};
+use crate::level::message;
+use crate::level::object::{self, Object};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:drift");
+        app.add_systems(app::Update, (balloon_system, glider_system).in_set(SystemSets::Aviate));
+        app.add_systems(
+            app::Update,
+            reject_instruction_system
+                .in_set(SystemSets::Communicate)
+                .before(instr::dispatch_system),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Radius of the circle flown by a glider in a thermal.
+    #[config(default = Length::from_nm(0.2), min = Length::from_nm(0.05), max = Length::from_nm(1.0))]
+    pub glider_circle_radius: Length<f32>,
+}
+
+/// Marks an unpowered object that cannot comply with instructions.
+#[derive(Component, Default)]
+pub struct Drifter;
+
+/// A drifter that rises until its ceiling and otherwise moves with the air mass.
+#[derive(Component)]
+#[require(Drifter)]
+pub struct Balloon {
+    /// Rate of altitude change until the ceiling is reached.
+    pub vert_rate: Speed<f32>,
+    /// Altitude at which the balloon levels off.
+    pub ceiling:   Position<f32>,
+}
+
+/// A drifter that circles in thermals and glides between them.
+#[derive(Component)]
+#[require(Drifter)]
+pub struct Glider {
+    /// Constant forward airspeed.
+    pub airspeed:     Speed<f32>,
+    /// Rate of altitude loss in still air. Always positive.
+    pub sink_rate:    Speed<f32>,
+    /// Current heading.
+    pub heading:      Heading,
+    /// The last thermal the glider has climbed to the top of.
+    ///
+    /// The glider leaves this thermal to search for another one.
+    pub last_thermal: Option<Entity>,
+}
+
+/// A cylindrical column of rising air.
+#[derive(Component)]
+pub struct Thermal {
+    /// Horizontal center of the thermal.
+    pub center: Position<Vec2>,
+    /// Horizontal radius of the thermal.
+    pub radius: Length<f32>,
+    /// Vertical speed of the rising air.
+    pub lift:   Speed<f32>,
+    /// Altitude of the top of the thermal.
+    pub top:    Position<f32>,
+}
+
+/// Spawns a drifter object.
+///
+/// The kind-specific component ([`Balloon`] or [`Glider`])
+/// should be inserted separately.
+pub struct SpawnCommand {
+    pub position: Position<Vec3>,
+    pub heading:  Heading,
+    pub display:  object::Display,
+}
+
+impl EntityCommand for SpawnCommand {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert((
+            object::Rotation(self.heading.into_rotation_quat()),
+            Object { position: self.position, ground_speed: Speed::ZERO },
+            message::Sender { display: self.display.name.clone() },
+            self.display,
+            object::Track::default(),
+            Drifter,
+        ));
+
+        let entity_id = entity.id();
+        entity.world_scope(|world| {
+            object::SetAirborneCommand.apply(world.entity_mut(entity_id));
+            world.write_message(object::SpawnMessage(entity_id));
+        });
+    }
+}
+
+fn balloon_system(
+    time: Res<Time<time::Virtual>>,
+    query: Query<(&Object, &Balloon, &mut object::Airborne)>,
+) {
+    if time.is_paused() {
+        return;
+    }
+
+    for (object, balloon, mut airborne) in query {
+        let vert_rate = if object.position.altitude() < balloon.ceiling {
+            balloon.vert_rate
+        } else {
+            Speed::ZERO
+        };
+        airborne.airspeed = Speed::<Vec2>::ZERO.with_vertical(vert_rate);
+    }
+}
+
+fn glider_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    query: Query<(&Object, &mut Glider, &mut object::Airborne, &mut object::Rotation)>,
+    thermal_query: Query<(Entity, &Thermal)>,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let conf = conf.read();
+
+    for (object, mut glider, mut airborne, mut rotation) in query {
+        let position = object.position;
+        let current_thermal = thermal_query.iter().find(|(_, thermal)| {
+            thermal.center.distance_cmp(position.horizontal()) < thermal.radius
+        });
+
+        let mut vert_rate = -glider.sink_rate;
+        match current_thermal {
+            Some((thermal_entity, thermal))
+                if glider.last_thermal != Some(thermal_entity)
+                    && position.altitude() < thermal.top =>
+            {
+                let turn = glider.airspeed * time.delta() / conf.glider_circle_radius;
+                glider.heading += Angle::from_radians(turn);
+                vert_rate += thermal.lift;
+                if position.altitude() + vert_rate * time.delta() >= thermal.top {
+                    glider.last_thermal = Some(thermal_entity);
+                }
+            }
+            _ => {
+                let next_thermal = thermal_query
+                    .iter()
+                    .filter(|&(entity, thermal)| {
+                        Some(entity) != glider.last_thermal && thermal.top > position.altitude()
+                    })
+                    .min_by_key(|(_, thermal)| thermal.center.distance_cmp(position.horizontal()));
+                if let Some((_, thermal)) = next_thermal {
+                    glider.heading = (thermal.center - position.horizontal()).heading();
+                }
+            }
+        }
+
+        if position.altitude() <= Position::SEA_LEVEL {
+            vert_rate = vert_rate.max(Speed::ZERO);
+        }
+
+        airborne.airspeed = (glider.airspeed * glider.heading).with_vertical(vert_rate);
+        rotation.0 = glider.heading.into_rotation_quat();
+    }
+}
+
+fn reject_instruction_system(
+    conf: ReadConfig<instr::Conf>,
+    time: Res<Time<time::Virtual>>,
+    instr_query: Query<
+        (Entity, &instr::Recipient, &instr::TransmitDelay),
+        Without<instr::PendingAck>,
+    >,
+    drifter_query: Query<(), With<Drifter>>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (instr_entity, &instr::Recipient(object_entity), delay) in instr_query {
+        if time.elapsed() < delay.expiry || !drifter_query.contains(object_entity) {
+            continue;
+        }
+
+        commands
+            .entity(instr_entity)
+            .remove::<(Instruction, instr::Recipient, instr::TransmitDelay, instr::DispatchAfter)>()
+            .insert(message::Expiry {
+                expiry: time.elapsed() + conf.message_duration_after_dispatch,
+            });
+        commands.spawn(message::Message {
+            source:  object_entity,
+            created: time.elapsed(),
+            content: "Unable, no maneuvering capability".into(),
+            class:   message::Class::VerboseInfo,
+        });
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::name::Name;
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::World;
+
+use crate::level::{drift, object};
+use crate::load::StoredEntity;
+
+/// Spawns the thermals declared in a store into the world.
+pub fn spawn_thermals(world: &mut World, thermals: &[store::Thermal]) {
+    for thermal in thermals {
+        world.spawn((
+            StoredEntity,
+            Name::new("Thermal"),
+            drift::Thermal {
+                center: thermal.center,
+                radius: thermal.radius,
+                lift:   thermal.lift,
+                top:    thermal.top,
+            },
+        ));
+    }
+}
+
+/// Spawns a drifter object declared in a store into the world.
+pub fn spawn(world: &mut World, drifter: &store::Drifter) -> Entity {
+    let entity = world.spawn((StoredEntity, Name::new(format!("Drifter: {}", drifter.name)))).id();
+
+    match drifter.kind {
+        store::DrifterKind::Balloon { vert_rate, ceiling } => {
+            world.entity_mut(entity).insert(drift::Balloon { vert_rate, ceiling });
+        }
+        store::DrifterKind::Glider { airspeed, sink_rate } => {
+            world.entity_mut(entity).insert(drift::Glider {
+                airspeed,
+                sink_rate,
+                heading: drifter.heading,
+                last_thermal: None,
+            });
+        }
+    }
+
+    drift::SpawnCommand {
+        position: drifter.position.with_altitude(drifter.altitude),
+        heading:  drifter.heading,
+        display:  object::Display { name: drifter.name.clone() },
+    }
+    .apply(world.entity_mut(entity));
+
+    entity
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::entity::Entity;
+use bevy::math::Vec2;
+use bevy::math::bounding::Aabb2d;
+use math::{Heading, Length, Position, Speed};
+
+use super::{Balloon, Glider, Thermal};
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::{drift, message, phraseology, weather};
+
+fn wind() -> Speed<Vec2> { Speed::from_knots(20.0).with_heading(Heading::EAST) }
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        object::Plug::<()>::default(),
+        weather::Plug::<()>::default(),
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        drift::Plug::<()>::default(),
+    ));
+
+    app.world_mut().commands().spawn_empty().queue(weather::SpawnCommand {
+        bundle: weather::Comps {
+            weather:       weather::Weather { sea_wind: wind(), ..Default::default() },
+            effect_region: weather::EffectRegion(Aabb2d {
+                min: Vec2::splat(-1000.0),
+                max: Vec2::splat(1000.0),
+            }),
+        },
+    });
+    app.world_mut().flush();
+    app.update();
+    app
+}
+
+fn spawn_drifter(app: &mut App, position: Position<Vec2>, kind: impl Bundle) -> Entity {
+    let world = app.world_mut();
+    let entity = world.spawn(kind).id();
+    world.commands().entity(entity).queue(drift::SpawnCommand {
+        position: position.with_altitude(Position::from_amsl_feet(3000.0)),
+        heading:  Heading::NORTH,
+        display:  object::Display { name: "BALLOON".into() },
+    });
+    world.flush();
+    entity
+}
+
+#[test]
+fn test_balloon_drifts_with_wind() {
+    let mut app = base_app();
+    let balloon = spawn_drifter(
+        &mut app,
+        Position::ORIGIN,
+        Balloon { vert_rate: Speed::from_fpm(300.0), ceiling: Position::from_amsl_feet(5000.0) },
+    );
+
+    for _ in 0..3 {
+        advance(&mut app, Duration::from_secs(1));
+    }
+
+    let object = app.world().get::<Object>(balloon).expect("balloon should exist");
+    let drift = object.ground_speed.horizontal() - wind();
+    assert!(
+        drift.magnitude_exact() < Speed::from_knots(1.0),
+        "balloon should move with the wind, got {:?}",
+        object.ground_speed
+    );
+    assert!(object.ground_speed.vertical() > Speed::ZERO, "balloon should climb below ceiling");
+}
+
+#[test]
+fn test_glider_climbs_in_thermal() {
+    let mut app = base_app();
+    app.world_mut().spawn(Thermal {
+        center: Position::ORIGIN,
+        radius: Length::from_nm(1.0),
+        lift:   Speed::from_fpm(600.0),
+        top:    Position::from_amsl_feet(6000.0),
+    });
+    let glider = spawn_drifter(
+        &mut app,
+        Position::ORIGIN,
+        Glider {
+            airspeed:     Speed::from_knots(50.0),
+            sink_rate:    Speed::from_fpm(150.0),
+            heading:      Heading::NORTH,
+            last_thermal: None,
+        },
+    );
+
+    for _ in 0..3 {
+        advance(&mut app, Duration::from_secs(1));
+    }
+
+    let object = app.world().get::<Object>(glider).expect("glider should exist");
+    assert!(object.ground_speed.vertical() > Speed::ZERO, "glider should climb in the thermal");
+    let heading = app.world().get::<Glider>(glider).expect("glider should exist").heading;
+    assert!(heading.degrees() > 1.0, "glider should circle in the thermal, got {heading:?}");
+}
+
+#[test]
+fn test_drifter_rejects_instruction() {
+    let mut app = base_app();
+    let balloon = spawn_drifter(
+        &mut app,
+        Position::ORIGIN,
+        Balloon { vert_rate: Speed::ZERO, ceiling: Position::from_amsl_feet(5000.0) },
+    );
+
+    let world = app.world_mut();
+    world.commands().send_instruction(balloon, instr::ClearRoute);
+    world.flush();
+    app.update();
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&instr::Instruction>().iter(world).count(), 0);
+    assert!(
+        world
+            .query::<&message::Message>()
+            .iter(world)
+            .any(|message| message.source == balloon && message.content.starts_with("Unable")),
+        "drifter should reply unable"
+    );
+}
@@ -0,0 +1,91 @@
+//! Validation of instructions against the navigation equipment of objects.
+//!
+//! Objects lacking the [equipage](object::Equipage) required by a route preset
+//! decline to follow it, and objects without RVSM approval
+//! decline altitudes within the [RVSM band](RVSM_BOTTOM).
+//! ILS equipage for approaches is validated by [`route::ClearApproach`].
+
+use std::time::Duration;
+
+use bevy::ecs::system::Command;
+use bevy::ecs::world::EntityWorldMut;
+use itertools::Itertools;
+use math::{Length, Position};
+
+use crate::level::{message, object, pilot, route};
+
+#[cfg(test)]
+mod tests;
+
+/// Duration for which unable replies are displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(10);
+
+/// Lowest altitude of the band in which reduced vertical separation minima apply.
+pub const RVSM_BOTTOM: Position<f32> = Position::from_amsl_feet(29000.0);
+
+/// Highest altitude of the band in which reduced vertical separation minima apply.
+pub const RVSM_TOP: Position<f32> = Position::from_amsl_feet(41000.0);
+
+/// Altitude difference between the RVSM band and the closest levels
+/// available to objects without RVSM approval.
+const NON_RVSM_MARGIN: Length<f32> = Length::from_feet(1000.0);
+
+/// Refuses `preset` if the object lacks any equipment required by the preset.
+///
+/// Returns `true` after replying unable, in which case the preset must not be applied.
+pub fn refuse_preset(entity: &mut EntityWorldMut, preset: &route::Preset) -> bool {
+    let Some(&object::Equipage(equipage)) = entity.get() else { return false };
+    let missing = preset.equipage.difference(equipage);
+    if missing.is_empty() {
+        return false;
+    }
+
+    let content = format!(
+        "Unable {}, not {} equipped, request vectors or another procedure",
+        preset.title,
+        describe(missing),
+    );
+    send_unable(entity, content);
+    true
+}
+
+/// Refuses a target altitude within the RVSM band if the object is not RVSM approved.
+///
+/// Returns `true` after replying unable, in which case the target must not be applied.
+pub fn refuse_altitude(entity: &mut EntityWorldMut, altitude: Position<f32>) -> bool {
+    if object::Equipage::has(entity.get(), store::Equipage::RVSM)
+        || !(RVSM_BOTTOM..=RVSM_TOP).contains(&altitude)
+    {
+        return false;
+    }
+
+    if let Some(mut clearance) = entity.get_mut::<pilot::Clearance>() {
+        clearance.altitude = None;
+    }
+    let content = format!(
+        "Unable {:.0} feet, not RVSM approved, request {:.0} feet or below, or {:.0} feet or above",
+        altitude.amsl().into_feet(),
+        (RVSM_BOTTOM - NON_RVSM_MARGIN).amsl().into_feet(),
+        (RVSM_TOP + NON_RVSM_MARGIN).amsl().into_feet(),
+    );
+    send_unable(entity, content);
+    true
+}
+
+/// Lists the equipment flags for display, e.g. `RNAV/CAT III`.
+fn describe(equipage: store::Equipage) -> String {
+    equipage.iter_names().map(|(name, _)| name.replace('_', " ")).join("/")
+}
+
+fn send_unable(entity: &mut EntityWorldMut, content: String) {
+    let source = entity.id();
+    entity.world_scope(|world| {
+        message::SendExpiring {
+            source,
+            content,
+            class: message::Class::AnomalyInfo,
+            duration: MESSAGE_DURATION,
+        }
+        .apply(world);
+    });
+}
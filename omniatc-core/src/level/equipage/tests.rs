@@ -0,0 +1,69 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::Position;
+
+use super::{describe, refuse_altitude, refuse_preset};
+use crate::level::{message, object, pilot, route, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins(message::Plug);
+    app
+}
+
+fn spawn_object(app: &mut App, equipage: Option<store::Equipage>) -> Entity {
+    let mut object = app.world_mut().spawn((
+        pilot::Clearance {
+            altitude: Some(Position::from_amsl_feet(33000.0)),
+            ..Default::default()
+        },
+        message::Sender { display: "ABC123".into() },
+    ));
+    if let Some(equipage) = equipage {
+        object.insert(object::Equipage(equipage));
+    }
+    object.id()
+}
+
+fn rnav_preset() -> route::Preset {
+    route::Preset {
+        id:       "POLAR18L".into(),
+        title:    "POLAR 18L".into(),
+        nodes:    Vec::new(),
+        equipage: store::Equipage::RNAV,
+    }
+}
+
+#[test]
+fn test_refuse_preset_without_rnav() {
+    let mut app = base_app();
+    let conventional = spawn_object(&mut app, Some(store::Equipage::ILS));
+    let rnav = spawn_object(&mut app, Some(store::Equipage::RNAV | store::Equipage::ILS));
+    let unspecified = spawn_object(&mut app, None);
+
+    let world = app.world_mut();
+    assert!(refuse_preset(&mut world.entity_mut(conventional), &rnav_preset()));
+    assert!(!refuse_preset(&mut world.entity_mut(rnav), &rnav_preset()));
+    assert!(!refuse_preset(&mut world.entity_mut(unspecified), &rnav_preset()));
+}
+
+#[test]
+fn test_refuse_altitude_in_rvsm_band() {
+    let mut app = base_app();
+    let non_rvsm = spawn_object(&mut app, Some(store::Equipage::RNAV));
+    let rvsm = spawn_object(&mut app, Some(store::Equipage::RVSM));
+
+    let world = app.world_mut();
+    assert!(refuse_altitude(&mut world.entity_mut(non_rvsm), Position::from_amsl_feet(33000.0)));
+    assert_eq!(world.get::<pilot::Clearance>(non_rvsm).expect("spawned").altitude, None);
+    assert!(!refuse_altitude(&mut world.entity_mut(non_rvsm), Position::from_amsl_feet(28000.0)));
+    assert!(!refuse_altitude(&mut world.entity_mut(non_rvsm), Position::from_amsl_feet(43000.0)));
+    assert!(!refuse_altitude(&mut world.entity_mut(rvsm), Position::from_amsl_feet(33000.0)));
+    assert!(world.get::<pilot::Clearance>(rvsm).expect("spawned").altitude.is_some());
+}
+
+#[test]
+fn test_describe_missing_equipage() {
+    assert_eq!(describe(store::Equipage::RNAV), "RNAV");
+    assert_eq!(describe(store::Equipage::RNAV | store::Equipage::CAT_III), "RNAV/CAT III");
+}
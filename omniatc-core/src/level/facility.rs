@@ -0,0 +1,302 @@
+//! ATC facility modes restricting the player to a subset of controller duties.
+//!
+//! In [`store::FacilityMode::TowerOnly`],
+//! airborne arrivals spawn established on final approach,
+//! the initial camera is zoomed in to the aerodrome,
+//! and instructions outside the duties of a tower controller are rejected.
+//!
+//! In [`store::FacilityMode::ApproachOnly`],
+//! instructions to objects on the ground are rejected and issued by an automated tower instead,
+//! and arrivals award a score when handed off established on final approach.
+//!
+//! In [`store::FacilityMode::GroundOnly`],
+//! airborne arrivals spawn on an exit taxiway of their landing runway,
+//! instructions to airborne objects are rejected
+//! and their clearances are issued automatically.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Added, Has, With, Without};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::time::{self, Time};
+use math::Length;
+use store::Score;
+
+use super::instr::Kind;
+use super::object::{self, Object};
+use super::runway::Runway;
+use super::waypoint::Waypoint;
+use super::{SystemSets, dest, ground, instr, message, route, score};
+use crate::QueryTryLog;
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum length of the scale axis of the initial camera in tower-only mode.
+const TOWER_CAMERA_SCALE: Length<f32> = Length::from_nm(8.0);
+/// Maximum length of the scale axis of the initial camera in ground-only mode.
+const GROUND_CAMERA_SCALE: Length<f32> = Length::from_nm(3.0);
+/// Duration for which a rejected instruction is displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(10);
+/// Score awarded for each arrival handed off to the tower in approach-only mode.
+const HANDOFF_SCORE: Score = Score(5);
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Mode>();
+        app.add_systems(
+            app::Update,
+            reject_system.before(instr::dispatch_system).in_set(SystemSets::Communicate),
+        );
+        app.add_systems(app::Update, auto_tower_system.in_set(SystemSets::Action));
+        app.add_systems(
+            app::Update,
+            handoff_system.in_set(SystemSets::Statistics).in_set(score::Writer),
+        );
+    }
+}
+
+/// The facility mode of the loaded level.
+#[derive(Resource, Default)]
+pub struct Mode(pub store::FacilityMode);
+
+impl Mode {
+    /// Whether the player may issue `instr` to an object in this mode.
+    #[must_use]
+    pub fn allows(&self, instr: &instr::Instruction, on_ground: bool) -> bool {
+        match self.0 {
+            store::FacilityMode::Full => true,
+            store::FacilityMode::TowerOnly => is_tower_instruction(instr),
+            store::FacilityMode::ApproachOnly => !on_ground,
+            store::FacilityMode::GroundOnly => on_ground,
+        }
+    }
+
+    /// Whether the clearances of an object are issued automatically in this mode.
+    #[must_use]
+    pub fn automates(&self, on_ground: bool) -> bool {
+        match self.0 {
+            store::FacilityMode::Full | store::FacilityMode::TowerOnly => false,
+            store::FacilityMode::ApproachOnly => on_ground,
+            store::FacilityMode::GroundOnly => !on_ground,
+        }
+    }
+
+    /// Whether the player may issue airborne vectors in this mode.
+    #[must_use]
+    pub fn allows_vectors(&self) -> bool {
+        matches!(self.0, store::FacilityMode::Full | store::FacilityMode::ApproachOnly)
+    }
+
+    /// Whether the player only acts as the aerodrome tower.
+    #[must_use]
+    pub fn is_tower_only(&self) -> bool { self.0 == store::FacilityMode::TowerOnly }
+
+    /// Whether the player only acts as the approach controller.
+    #[must_use]
+    pub fn is_approach_only(&self) -> bool { self.0 == store::FacilityMode::ApproachOnly }
+
+    /// Whether the player only manages surface movement.
+    #[must_use]
+    pub fn is_ground_only(&self) -> bool { self.0 == store::FacilityMode::GroundOnly }
+
+    /// Suffix appended to instructions rejected in this mode.
+    fn rejection_suffix(&self) -> &'static str {
+        match self.0 {
+            store::FacilityMode::Full | store::FacilityMode::TowerOnly => {
+                " (not available to tower)"
+            }
+            store::FacilityMode::ApproachOnly => " (not available to approach)",
+            store::FacilityMode::GroundOnly => " (not available to ground)",
+        }
+    }
+}
+
+/// Marks an arrival that has been handed off to the tower in approach-only mode.
+#[derive(Component)]
+pub struct HandedOff;
+
+/// Whether `instr` is within the duties of an aerodrome tower controller,
+/// i.e. clearances, runway crossings and taxi instructions.
+#[must_use]
+pub fn is_tower_instruction(instr: &instr::Instruction) -> bool {
+    match instr {
+        instr::Instruction::ClearRoute(_)
+        | instr::Instruction::RemoveStandby(_)
+        | instr::Instruction::SelectRoute(_)
+        | instr::Instruction::AppendSegment(_)
+        | instr::Instruction::ContactFrequency(_)
+        | instr::Instruction::ClearApproach(_)
+        | instr::Instruction::BirdCaution(_)
+        | instr::Instruction::CrossRunway(_) => true,
+        instr::Instruction::SetHeading(_)
+        | instr::Instruction::SetWaypoint(_)
+        | instr::Instruction::SetSpeed(_)
+        | instr::Instruction::SetAltitude(_)
+        | instr::Instruction::AirborneVector(_)
+        | instr::Instruction::SkipToWaypoint(_)
+        | instr::Instruction::Divert(_)
+        | instr::Instruction::GrantFlightFollowing(_)
+        | instr::Instruction::BreakupFormation(_)
+        | instr::Instruction::CrossAltitude(_)
+        | instr::Instruction::SetSpeedUntil(_)
+        | instr::Instruction::VectorThenResume(_)
+        | instr::Instruction::CycleTransponder(_) => false,
+    }
+}
+
+/// Adjusts the initial camera of a level for the facility mode.
+pub fn advise_camera(mode: store::FacilityMode, camera: &mut store::Camera) {
+    match (mode, camera) {
+        (store::FacilityMode::Full | store::FacilityMode::ApproachOnly, _) => {}
+        (store::FacilityMode::TowerOnly, store::Camera::TwoDimension(camera)) => {
+            camera.scale_length = camera.scale_length.min(TOWER_CAMERA_SCALE);
+        }
+        (store::FacilityMode::GroundOnly, store::Camera::TwoDimension(camera)) => {
+            camera.scale_length = camera.scale_length.min(GROUND_CAMERA_SCALE);
+        }
+    }
+}
+
+fn reject_system(
+    mode: Res<Mode>,
+    time: Res<Time<time::Virtual>>,
+    mut instr_query: Query<
+        (Entity, &instr::Instruction, &instr::Recipient, &mut message::Message),
+        Added<instr::Instruction>,
+    >,
+    recipient_query: Query<Has<object::OnGround>>,
+    mut commands: Commands,
+) {
+    for (entity, instr, &instr::Recipient(recipient), mut message) in &mut instr_query {
+        let on_ground = recipient_query.get(recipient).unwrap_or(false);
+        if mode.allows(instr, on_ground) {
+            continue;
+        }
+
+        message.class = message::Class::AnomalyInfo;
+        message.content.push_str(mode.rejection_suffix());
+        commands
+            .entity(entity)
+            .remove::<(instr::Instruction, instr::Recipient, instr::TransmitDelay)>()
+            .insert(message::Expiry { expiry: time.elapsed() + MESSAGE_DURATION });
+    }
+}
+
+/// Issues the clearances and taxi instructions of objects not controlled by the player.
+///
+/// Standby nodes and runway crossings of [automated](Mode::automates) objects
+/// are cleared immediately, and automated arrivals that have landed without further route
+/// taxi to an apron of the aerodrome, or to any taxiway if the aerodrome has no aprons.
+fn auto_tower_system(
+    mode: Res<Mode>,
+    object_query: Query<
+        (Entity, Option<&object::OnGround>, Option<&route::Route>, Option<&dest::Destination>),
+        With<Object>,
+    >,
+    segment_query: Query<(&ground::SegmentLabel, &ground::SegmentOf)>,
+    aerodrome_query: Query<&ground::AerodromeSegments>,
+    mut commands: Commands,
+) {
+    if !mode.automates(true) && !mode.automates(false) {
+        return;
+    }
+
+    for (entity, ground, route, dest) in object_query {
+        if !mode.automates(ground.is_some()) {
+            continue;
+        }
+
+        if let Some(runway) = ground.and_then(|ground| ground.crossing.holding_runway()) {
+            instr::CrossRunway { runway }.process(&mut commands.entity(entity));
+        }
+
+        match route.and_then(route::Route::current) {
+            Some(route::Node::Standby(node)) => {
+                commands.entity(entity).queue(route::RemoveStandby { skip_id: node.skip_id });
+            }
+            Some(_) => {}
+            None => {
+                let Some(ground) = ground else { continue };
+                let Some(
+                    dest::Destination::Landing { .. }
+                    | dest::Destination::Parking { .. }
+                    | dest::Destination::VacateAnyRunway,
+                ) = dest
+                else {
+                    continue;
+                };
+
+                let Some((label, &ground::SegmentOf(aerodrome))) =
+                    segment_query.log_get(ground.segment)
+                else {
+                    continue;
+                };
+                if !label.is_runway() {
+                    continue;
+                }
+                let Some(segments) = aerodrome_query.log_get(aerodrome) else { continue };
+                let Some(target) = find_taxi_target(segments, &segment_query) else { continue };
+
+                instr::AppendSegment {
+                    clear_existing: false,
+                    segment:        target,
+                    stop_mode:      route::TaxiStopMode::Exhaust,
+                }
+                .process(&mut commands.entity(entity));
+            }
+        }
+    }
+}
+
+/// Selects the segment label for a landed arrival to taxi to.
+fn find_taxi_target(
+    segments: &ground::AerodromeSegments,
+    segment_query: &Query<(&ground::SegmentLabel, &ground::SegmentOf)>,
+) -> Option<ground::SegmentLabel> {
+    let labels: Vec<&ground::SegmentLabel> = segments
+        .segments()
+        .iter()
+        .filter_map(|&segment| Some(segment_query.log_get(segment)?.0))
+        .collect();
+    labels
+        .iter()
+        .find(|label| label.is_apron())
+        .or_else(|| labels.iter().find(|label| label.is_taxiway()))
+        .map(|&label| label.clone())
+}
+
+/// Awards the handoff score for arrivals established on final approach in approach-only mode.
+///
+/// An arrival is established when its current route node aligns with a runway
+/// and it is within the intercept envelope of [`route::check_intercept`].
+fn handoff_system(
+    mode: Res<Mode>,
+    object_query: Query<
+        (Entity, &Object, &route::Route),
+        (With<object::Airborne>, Without<HandedOff>),
+    >,
+    runway_query: Query<(&Waypoint, &Runway)>,
+    mut stats: ResMut<score::Stats>,
+    mut commands: Commands,
+) {
+    if !mode.is_approach_only() {
+        return;
+    }
+
+    for (entity, object, route) in object_query {
+        let Some(route::Node::AlignRunway(node)) = route.current() else { continue };
+        let Some((waypoint, runway)) = runway_query.log_get(node.runway) else { continue };
+        if route::check_intercept(object, waypoint.position, runway).is_ok() {
+            stats.total += HANDOFF_SCORE;
+            commands.entity(entity).insert(HandedOff);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::num::NonZero;
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::math::Vec2;
+use math::{Heading, Length, Position, Speed};
+use store::YawTarget;
+
+use super::{Mode, advise_camera};
+use crate::level::frequency::Frequency;
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::{self, Object};
+use crate::level::{facility, ground, message, phraseology, route, score, test_util};
+
+fn base_app(mode: store::FacilityMode) -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        score::Plug,
+        facility::Plug,
+    ));
+    app.insert_resource(Mode(mode));
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(Position::from_amsl_feet(3000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            object::Display { name: "ABC123".into() },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id()
+}
+
+fn send(app: &mut App, object: Entity, instr: impl Into<instr::Instruction>) {
+    let world = app.world_mut();
+    world.commands().send_instruction(object, instr);
+    world.flush();
+    app.update();
+}
+
+fn outgoing_messages(app: &mut App) -> Vec<(String, message::Class)> {
+    let world = app.world_mut();
+    world
+        .query::<&message::Message>()
+        .iter(world)
+        .filter(|message| message.content.starts_with("ABC123,"))
+        .map(|message| (message.content.clone(), message.class))
+        .collect()
+}
+
+fn land(app: &mut App, object: Entity) {
+    app.world_mut().entity_mut(object).insert(object::OnGround {
+        segment:      Entity::PLACEHOLDER,
+        direction:    ground::SegmentDirection::AlphaToBeta,
+        target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+        crossing:     object::RunwayCrossing::None,
+    });
+}
+
+fn heading() -> instr::SetHeading {
+    instr::SetHeading { target: YawTarget::Heading(Heading::EAST) }
+}
+
+#[test]
+fn test_tower_only_rejects_vectors() {
+    let mut app = base_app(store::FacilityMode::TowerOnly);
+    let object = spawn_object(&mut app);
+
+    send(&mut app, object, heading());
+    send(&mut app, object, instr::ContactFrequency { frequency: Frequency::Ground });
+
+    let messages = outgoing_messages(&mut app);
+    assert_eq!(messages.len(), 2);
+    assert!(messages.iter().any(|(content, class)| {
+        content.ends_with("(not available to tower)") && *class == message::Class::AnomalyInfo
+    }));
+    assert!(messages.iter().any(|(content, _)| content == "ABC123, contact ground"));
+}
+
+#[test]
+fn test_full_mode_accepts_vectors() {
+    let mut app = base_app(store::FacilityMode::Full);
+    let object = spawn_object(&mut app);
+
+    send(&mut app, object, heading());
+
+    let messages = outgoing_messages(&mut app);
+    assert_eq!(messages.len(), 1);
+    assert!(!messages[0].0.contains("not available"));
+}
+
+#[test]
+fn test_tower_only_zooms_in_camera() {
+    let camera = |scale_nm| {
+        store::Camera::TwoDimension(store::Camera2d {
+            center:       Position::new(Vec2::ZERO),
+            up:           Heading::NORTH,
+            scale_axis:   store::AxisDirection::X,
+            scale_length: Length::from_nm(scale_nm),
+        })
+    };
+    let scale = |camera: &store::Camera| match camera {
+        store::Camera::TwoDimension(camera) => camera.scale_length,
+    };
+
+    let mut full = camera(50.0);
+    advise_camera(store::FacilityMode::Full, &mut full);
+    assert_eq!(scale(&full), Length::from_nm(50.0));
+
+    let mut tower = camera(50.0);
+    advise_camera(store::FacilityMode::TowerOnly, &mut tower);
+    assert_eq!(scale(&tower), Length::from_nm(8.0));
+
+    let mut close = camera(4.0);
+    advise_camera(store::FacilityMode::TowerOnly, &mut close);
+    assert_eq!(scale(&close), Length::from_nm(4.0));
+
+    let mut ground = camera(50.0);
+    advise_camera(store::FacilityMode::GroundOnly, &mut ground);
+    assert_eq!(scale(&ground), Length::from_nm(3.0));
+}
+
+#[test]
+fn test_approach_only_rejects_ground_instructions() {
+    let mut app = base_app(store::FacilityMode::ApproachOnly);
+    let object = spawn_object(&mut app);
+
+    send(&mut app, object, heading());
+    assert_eq!(
+        outgoing_messages(&mut app),
+        [("ABC123, fly heading zero niner zero".to_owned(), message::Class::Outgoing)],
+        "airborne objects are controlled by approach"
+    );
+
+    land(&mut app, object);
+    send(&mut app, object, instr::ContactFrequency { frequency: Frequency::Ground });
+    let messages = outgoing_messages(&mut app);
+    assert!(messages.iter().any(|(content, class)| {
+        content.ends_with("(not available to approach)") && *class == message::Class::AnomalyInfo
+    }));
+}
+
+#[test]
+fn test_approach_only_clears_ground_standby() {
+    let mut app = base_app(store::FacilityMode::ApproachOnly);
+    let object = spawn_object(&mut app);
+    land(&mut app, object);
+
+    let mut route = route::Route::default();
+    route.push(route::StandbyNode { skip_id: NonZero::new(1) }.into());
+    app.world_mut().entity_mut(object).insert(route);
+    app.update();
+
+    let route = app.world().get::<route::Route>(object).expect("route should be retained");
+    assert!(route.current().is_none(), "standby should be cleared by the automated tower");
+}
+
+#[test]
+fn test_ground_only_rejects_airborne_instructions() {
+    let mut app = base_app(store::FacilityMode::GroundOnly);
+    let object = spawn_object(&mut app);
+
+    send(&mut app, object, heading());
+    let messages = outgoing_messages(&mut app);
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].0.ends_with("(not available to ground)"));
+    assert_eq!(messages[0].1, message::Class::AnomalyInfo);
+
+    land(&mut app, object);
+    send(&mut app, object, instr::ContactFrequency { frequency: Frequency::Tower });
+    assert!(
+        outgoing_messages(&mut app)
+            .iter()
+            .any(|(content, class)| content == "ABC123, contact tower"
+                && *class == message::Class::Outgoing)
+    );
+}
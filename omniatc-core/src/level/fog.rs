@@ -0,0 +1,227 @@
+//! Fog banks and low visibility procedures.
+//!
+//! Scheduled fog [`Bank`]s limit the [runway visual range](Rvr) of the runways they cover,
+//! which in turn limits the range at which approaching objects see the runway.
+//! Objects without [CAT III](object::Equipage) equipment
+//! go around if they do not see the runway before losing the ILS.
+//!
+//! An aerodrome enters low visibility procedures ([`Lvp`])
+//! while the runway visual range of any of its ILS runways is below the ILS minima.
+//! During LVP, departures are spaced by [`Conf::departure_interval`]
+//! and arrivals go around if any ground object is within
+//! [`Conf::protection_distance`] of the runway.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, With};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::math::Vec2;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Length, Position};
+
+use super::SystemSets;
+use crate::level::aerodrome::Aerodrome;
+use crate::level::message;
+use crate::level::navaid::{self, Navaid};
+use crate::level::object::{self, Object};
+use crate::level::runway::{self, Runway};
+use crate::level::waypoint::Waypoint;
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+/// Duration for which LVP changes are displayed.
+const MESSAGE_DURATION: Duration = Duration::from_mins(1);
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:fog");
+        app.add_systems(
+            app::Update,
+            (rvr_system, lvp_system, protection_system).chain().in_set(SystemSets::PrepareEnviron),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Minimum interval between consecutive departures from an aerodrome during LVP.
+    #[config(default = Duration::from_mins(3))]
+    pub departure_interval:  Duration,
+    /// During LVP, arrivals go around if a ground object is within this distance
+    /// from the runway centerline.
+    #[config(default = Length::from_meters(150.0), min = Length::ZERO, max = Length::from_meters(1000.0))]
+    pub protection_distance: Length<f32>,
+}
+
+/// A fog bank over a circular area during a period of virtual time.
+#[derive(Component)]
+pub struct Bank {
+    pub center:     Position<Vec2>,
+    pub radius:     Length<f32>,
+    /// Runway visual range within the area.
+    pub visibility: Length<f32>,
+    /// Virtual time at which the fog forms.
+    pub start:      Duration,
+    /// Virtual time at which the fog clears.
+    pub end:        Duration,
+}
+
+impl Bank {
+    /// Whether the fog covers `position` at virtual time `now`.
+    #[must_use]
+    pub fn covers(&self, position: Position<Vec2>, now: Duration) -> bool {
+        (self.start..self.end).contains(&now) && self.center.distance_cmp(position) < self.radius
+    }
+}
+
+/// Runway visual range of a runway covered by fog.
+///
+/// Component on runway entities. Absent if the runway is not covered by fog.
+#[derive(Debug, Component, Clone, Copy, PartialEq)]
+pub struct Rvr(pub Length<f32>);
+
+/// Low visibility procedures in effect at an aerodrome.
+///
+/// Component on aerodrome entities.
+#[derive(Component)]
+pub struct Lvp {
+    /// Minimum interval between consecutive departures.
+    pub departure_interval: Duration,
+    /// Virtual time at which the last departure started its takeoff roll.
+    pub last_departure:     Option<Duration>,
+}
+
+impl Lvp {
+    /// Returns the virtual time before which the next departure must wait,
+    /// or `None` if a departure may start its takeoff roll at `now`.
+    #[must_use]
+    pub fn departure_wait(&self, now: Duration) -> Option<Duration> {
+        let next = self.last_departure? + self.departure_interval;
+        (now < next).then_some(next)
+    }
+}
+
+/// Marks a runway whose LVP protection zone contains a ground object.
+#[derive(Component)]
+pub struct ProtectionZoneOccupied;
+
+fn rvr_system(
+    time: Res<Time<time::Virtual>>,
+    bank_query: Query<&Bank>,
+    runway_query: Query<(Entity, &Waypoint, &navaid::ListAtWaypoint, Option<&Rvr>), With<Runway>>,
+    mut visual_query: Query<&mut navaid::Visual>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+
+    for (runway_entity, waypoint, navaids, current) in runway_query {
+        let rvr = bank_query
+            .iter()
+            .filter(|bank| bank.covers(waypoint.position.horizontal(), now))
+            .map(|bank| bank.visibility)
+            .reduce(Length::min)
+            .map(Rvr);
+        if rvr == current.copied() {
+            continue;
+        }
+
+        match rvr {
+            Some(rvr) => commands.entity(runway_entity).insert(rvr),
+            None => commands.entity(runway_entity).remove::<Rvr>(),
+        };
+        for &navaid in navaids.navaids() {
+            if let Ok(mut visual) = visual_query.get_mut(navaid) {
+                visual.obscured_range = rvr.map(|Rvr(range)| range);
+            }
+        }
+    }
+}
+
+fn lvp_system(
+    conf: ReadConfig<Conf>,
+    aerodrome_query: Query<(Entity, &Aerodrome, Option<&mut Lvp>)>,
+    runway_query: Query<(&runway::RunwayOf, &Rvr, &navaid::ListAtWaypoint)>,
+    ils_query: Query<&Navaid, With<navaid::LandingAid>>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (aerodrome_entity, aerodrome, lvp) in aerodrome_query {
+        let below_minima =
+            runway_query.iter().any(|(&runway::RunwayOf(runway_aerodrome), &Rvr(rvr), navaids)| {
+                runway_aerodrome == aerodrome_entity
+                    && navaids.navaids().iter().any(|&navaid| {
+                        ils_query.get(navaid).is_ok_and(|ils| rvr < ils.min_dist_horizontal)
+                    })
+            });
+
+        let content = match (lvp, below_minima) {
+            (Some(mut lvp), true) => {
+                lvp.departure_interval = conf.departure_interval;
+                continue;
+            }
+            (None, false) => continue,
+            (None, true) => {
+                commands.entity(aerodrome_entity).insert(Lvp {
+                    departure_interval: conf.departure_interval,
+                    last_departure:     None,
+                });
+                format!("Low visibility procedures in effect at {}", aerodrome.code)
+            }
+            (Some(_), false) => {
+                commands.entity(aerodrome_entity).remove::<Lvp>();
+                format!("Low visibility procedures cancelled at {}", aerodrome.code)
+            }
+        };
+        commands.queue(message::SendExpiring {
+            source: aerodrome_entity,
+            content,
+            class: message::Class::AnomalyInfo,
+            duration: MESSAGE_DURATION,
+        });
+    }
+}
+
+fn protection_system(
+    conf: ReadConfig<Conf>,
+    runway_query: Query<(Entity, &Runway, &runway::RunwayOf, Has<ProtectionZoneOccupied>)>,
+    lvp_query: Query<(), With<Lvp>>,
+    object_query: Query<&Object, With<object::OnGround>>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (runway_entity, runway, &runway::RunwayOf(aerodrome), was_occupied) in runway_query {
+        let start = runway.display_start.horizontal();
+        let end = runway.display_end.horizontal();
+        let occupied = lvp_query.contains(aerodrome)
+            && object_query.iter().any(|object| {
+                let position = object.position.horizontal();
+                math::point_segment_closest(position, start, end).distance_cmp(position)
+                    < conf.protection_distance
+            });
+
+        if occupied && !was_occupied {
+            commands.entity(runway_entity).insert(ProtectionZoneOccupied);
+        } else if !occupied && was_occupied {
+            commands.entity(runway_entity).remove::<ProtectionZoneOccupied>();
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+
+use crate::level::fog;
+use crate::load::StoredEntity;
+
+/// Spawns the fog banks declared in a store into the world.
+///
+/// Fog times are relative to the current virtual time.
+pub fn spawn_banks(world: &mut World, banks: &[store::FogBank]) {
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    for bank in banks {
+        world.spawn((
+            StoredEntity,
+            Name::new("Fog bank"),
+            fog::Bank {
+                center:     bank.center,
+                radius:     bank.radius,
+                visibility: bank.visibility,
+                start:      now.saturating_add(bank.start),
+                end:        now.saturating_add(bank.start).saturating_add(bank.duration),
+            },
+        ));
+    }
+}
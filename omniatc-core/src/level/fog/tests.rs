@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Angle, Heading, Length, Position, Speed};
+
+use super::{Bank, Lvp, ProtectionZoneOccupied, Rvr};
+use crate::level::aerodrome::Aerodrome;
+use crate::level::navaid::{self, Navaid};
+use crate::level::object::{self, Object};
+use crate::level::runway::{self, Runway};
+use crate::level::test_util::{self, advance};
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{fog, ground, message};
+
+struct Entities {
+    aerodrome: Entity,
+    runway:    Entity,
+    visual:    Entity,
+}
+
+fn base_app() -> (App, Entities) {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, fog::Plug::<()>::default()));
+
+    let world = app.world_mut();
+    let aerodrome = world
+        .spawn((
+            Aerodrome {
+                id:        0,
+                code:      "MAIN".into(),
+                name:      "Main".into(),
+                elevation: Position::SEA_LEVEL,
+            },
+            message::Sender { display: "MAIN".into() },
+        ))
+        .id();
+
+    let position = Position::ORIGIN.with_altitude(Position::SEA_LEVEL);
+    let runway = world
+        .spawn((
+            Waypoint {
+                name: "36".into(),
+                display_type: waypoint::DisplayType::Runway,
+                position,
+                hidden: false,
+            },
+            Runway {
+                landing_length: Length::from_meters(3000.0).with_heading(Heading::NORTH),
+                display_start:  position,
+                display_end:    position
+                    + Length::from_meters(3000.0).with_heading(Heading::NORTH).horizontally(),
+                width:          Length::from_meters(60.0),
+                glide_descent:  Angle::from_degrees(3.0),
+            },
+            runway::RunwayOf(aerodrome),
+        ))
+        .id();
+
+    let max_range = Length::from_nm(10.0);
+    let visual = world
+        .spawn((
+            Navaid {
+                kind:                navaid::Kind::Visual,
+                heading_range:       Heading::NORTH..Heading::NORTH,
+                pitch_range_tan:     -1.0..1.0,
+                min_dist_horizontal: Length::ZERO,
+                min_dist_vertical:   Length::ZERO,
+                max_dist_horizontal: max_range,
+                max_dist_vertical:   Length::from_nm(1.0),
+            },
+            navaid::Visual { max_range, obscured_range: None },
+            navaid::OwnerWaypoint(runway),
+        ))
+        .id();
+    world.spawn((
+        Navaid {
+            kind:                navaid::Kind::Localizer,
+            heading_range:       Heading::SOUTH..Heading::SOUTH,
+            pitch_range_tan:     -1.0..1.0,
+            min_dist_horizontal: Length::from_meters(200.0),
+            min_dist_vertical:   Length::from_feet(100.0),
+            max_dist_horizontal: Length::from_nm(20.0),
+            max_dist_vertical:   Length::from_feet(5000.0),
+        },
+        navaid::LandingAid,
+        navaid::OwnerWaypoint(runway),
+    ));
+
+    world.spawn(Bank {
+        center:     Position::ORIGIN,
+        radius:     Length::from_nm(5.0),
+        visibility: Length::from_meters(150.0),
+        start:      Duration::from_mins(1),
+        end:        Duration::from_mins(10),
+    });
+
+    app.update();
+    (app, Entities { aerodrome, runway, visual })
+}
+
+#[test]
+fn test_fog_limits_rvr_and_triggers_lvp() {
+    let (mut app, entities) = base_app();
+    assert!(app.world().get::<Rvr>(entities.runway).is_none());
+    assert!(!app.world().entity(entities.aerodrome).contains::<Lvp>());
+
+    advance(&mut app, Duration::from_mins(2));
+    advance(&mut app, Duration::from_secs(1));
+    assert_eq!(
+        app.world().get::<Rvr>(entities.runway).copied(),
+        Some(Rvr(Length::from_meters(150.0)))
+    );
+    let visual = app.world().get::<navaid::Visual>(entities.visual).expect("spawned");
+    assert_eq!(visual.obscured_range, Some(Length::from_meters(150.0)));
+    assert!(app.world().entity(entities.aerodrome).contains::<Lvp>());
+
+    advance(&mut app, Duration::from_mins(10));
+    advance(&mut app, Duration::from_secs(1));
+    assert!(app.world().get::<Rvr>(entities.runway).is_none());
+    let visual = app.world().get::<navaid::Visual>(entities.visual).expect("spawned");
+    assert_eq!(visual.obscured_range, None);
+    assert!(!app.world().entity(entities.aerodrome).contains::<Lvp>());
+}
+
+#[test]
+fn test_lvp_protection_zone() {
+    let (mut app, entities) = base_app();
+    let segment = app.world_mut().spawn_empty().id();
+    let object = app
+        .world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.05, 0.5)
+                    .with_altitude(Position::SEA_LEVEL),
+                ground_speed: Speed::ZERO.horizontally(),
+            },
+            object::OnGround {
+                segment,
+                direction: ground::SegmentDirection::AlphaToBeta,
+                target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+                crossing: object::RunwayCrossing::None,
+            },
+        ))
+        .id();
+
+    advance(&mut app, Duration::from_secs(30));
+    assert!(!app.world().entity(entities.runway).contains::<ProtectionZoneOccupied>());
+
+    advance(&mut app, Duration::from_mins(2));
+    advance(&mut app, Duration::from_secs(1));
+    assert!(app.world().entity(entities.runway).contains::<ProtectionZoneOccupied>());
+
+    app.world_mut().get_mut::<Object>(object).expect("spawned").position =
+        Position::from_origin_nm(0.5, 0.5).with_altitude(Position::SEA_LEVEL);
+    advance(&mut app, Duration::from_secs(1));
+    assert!(!app.world().entity(entities.runway).contains::<ProtectionZoneOccupied>());
+}
+
+#[test]
+fn test_lvp_departure_wait() {
+    let mut lvp = Lvp { departure_interval: Duration::from_mins(3), last_departure: None };
+    assert_eq!(lvp.departure_wait(Duration::from_mins(10)), None);
+
+    lvp.last_departure = Some(Duration::from_mins(10));
+    assert_eq!(lvp.departure_wait(Duration::from_mins(11)), Some(Duration::from_mins(13)));
+    assert_eq!(lvp.departure_wait(Duration::from_mins(13)), None);
+}
@@ -0,0 +1,139 @@
+//! Formation flights represented by a single object.
+//!
+//! A [`Formation`] object is a single radar target representing multiple aircraft.
+//! Since the formation only has one entity,
+//! it is treated as a single unit by separation logic.
+//! Upon a breakup instruction,
+//! the formation splits into individual objects trailing behind the lead.
+
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::name::Name;
+use bevy::ecs::system::{EntityCommand, SystemState};
+use bevy::ecs::world::EntityWorldMut;
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+
+use crate::level::object::{self, Object};
+use crate::level::route::{self, Route};
+use crate::level::{conflict, dest, fuel, nav, plane, taxi, wake};
+use crate::load::StoredEntity;
+use crate::{EntityMutTryLog, EntityTryLog};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) { app.init_config::<M, Conf>("core:formation"); }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Trailing distance between consecutive members after breakup,
+    /// as a multiple of the horizontal separation minimum.
+    #[config(default = 1.2, min = 1.0, max = 3.0)]
+    pub breakup_spacing: f32,
+}
+
+/// Marks an object that represents a formation of multiple aircraft.
+#[derive(Component)]
+pub struct Formation {
+    /// Total number of aircraft in the formation, including the lead.
+    pub size: u32,
+}
+
+/// Splits a formation into individual objects.
+///
+/// The entity this command is applied on becomes the lead aircraft.
+/// The other members are spawned in trail behind the lead
+/// with the same velocity, destination, and route.
+/// Formations on the ground cannot be broken up.
+pub struct BreakupCommand;
+
+impl EntityCommand for BreakupCommand {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let Some(&Formation { size }) = entity.get::<Formation>() else { return };
+        if !entity.contains::<object::Airborne>() {
+            return;
+        }
+
+        let spacing = entity.world_scope(|world| {
+            let mut state =
+                SystemState::<(ReadConfig<Conf>, ReadConfig<conflict::Conf>)>::new(world);
+            let (conf, conflict_conf) = state.get(world);
+            conflict_conf.read().horiz_sep * conf.read().breakup_spacing
+        });
+
+        let Some(&Object { position, ground_speed }) = entity.log_get::<Object>() else { return };
+        let Some(display) = entity.log_get::<object::Display>() else { return };
+        let lead_name = display.name.clone();
+        let Some(destination) = entity.log_get::<dest::Destination>().cloned() else { return };
+        let Some(control) = entity.log_get::<plane::Control>() else { return };
+        let heading = control.heading;
+        let yaw_speed = control.yaw_speed;
+        let horiz_accel = control.horiz_accel;
+        let Some(nav_limits) = entity.log_get::<nav::Limits>().cloned() else { return };
+        let Some(taxi_limits) = entity.log_get::<taxi::Limits>().cloned() else { return };
+        let velocity_target = entity.get::<nav::VelocityTarget>().cloned();
+        let target_altitude = entity.get::<nav::TargetAltitude>().cloned();
+        let completion_score = entity.get::<dest::CompletionScore>().map(|score| score.score);
+        let endurance = entity.get::<fuel::Endurance>().map(|endurance| endurance.remaining);
+        let wake_intensity = entity.get::<wake::Producer>().map(|producer| producer.base_intensity);
+        let route_id = entity.get::<route::Id>().and_then(|id| id.0.clone());
+        let route_nodes: Vec<_> =
+            entity.get::<Route>().map(|route| route.iter().cloned().collect()).unwrap_or_default();
+
+        entity.remove::<Formation>();
+        entity.world_scope(|world| {
+            for index in 2..=size {
+                #[expect(clippy::cast_precision_loss, reason = "formation size is small")]
+                let trail = spacing * (index - 1) as f32 * heading;
+                let name = format!("{lead_name}-{index}");
+
+                let member = world.spawn((StoredEntity, Name::new(format!("Plane: {name}")))).id();
+                object::SpawnCommand {
+                    position: position - trail.horizontally(),
+                    ground_speed,
+                    display: object::Display { name },
+                    destination: destination.clone(),
+                    completion_score,
+                }
+                .apply(world.entity_mut(member));
+                world.entity_mut(member).insert(taxi_limits.clone());
+                plane::SpawnCommand {
+                    control: Some(plane::Control { heading, yaw_speed, horiz_accel }),
+                    limits:  nav_limits.clone(),
+                }
+                .apply(world.entity_mut(member));
+                object::SetAirborneCommand.apply(world.entity_mut(member));
+
+                let mut member = world.entity_mut(member);
+                if let Some(target) = &velocity_target {
+                    member.insert(target.clone());
+                }
+                if let Some(target) = &target_altitude {
+                    member.insert(target.clone());
+                }
+                if let Some(remaining) = endurance {
+                    member.insert(fuel::Endurance { remaining });
+                }
+                if let Some(base_intensity) = wake_intensity {
+                    member.insert((wake::Producer { base_intensity }, wake::Detector::default()));
+                }
+
+                member.insert(route::Id(route_id.clone()));
+                route::ReplaceNodes(route_nodes.clone()).apply(member);
+            }
+        });
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::EntityCommand;
+use math::{Accel, AngularSpeed, Heading, Length, Position, Speed};
+use store::TaxiLimits;
+
+use super::{BreakupCommand, Formation};
+use crate::level::dest::Destination;
+use crate::level::object::{self, Object};
+use crate::level::test_util::NAV_LIMITS;
+use crate::level::{conflict, formation, message, nav, plane, score, taxi, test_util, weather};
+
+const TAXI_LIMITS: TaxiLimits = TaxiLimits {
+    accel:        Accel::from_knots_per_sec(3.),
+    base_braking: Accel::from_knots_per_sec(5.),
+    max_speed:    Speed::from_knots(30.),
+    min_speed:    Speed::from_knots(-5.),
+    turn_rate:    AngularSpeed::from_degrees_per_sec(15.),
+    width:        Length::from_meters(30.),
+    half_length:  Length::from_meters(20.),
+};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        score::Plug,
+        object::Plug::<()>::default(),
+        weather::Plug::<()>::default(),
+        plane::Plug,
+        conflict::Plug::<()>::default(),
+        formation::Plug::<()>::default(),
+    ));
+    app.update();
+    app
+}
+
+fn spawn_formation(app: &mut App, size: u32) -> Entity {
+    let world = app.world_mut();
+    let entity = world.spawn(Formation { size }).id();
+    object::SpawnCommand {
+        position:         Position::ORIGIN.with_altitude(Position::from_amsl_feet(5000.0)),
+        ground_speed:     (Speed::from_knots(250.0) * Heading::NORTH).horizontally(),
+        display:          object::Display { name: "VIPER1".into() },
+        destination:      Destination::VacateAnyRunway,
+        completion_score: None,
+    }
+    .apply(world.entity_mut(entity));
+    world.entity_mut(entity).insert(taxi::Limits(TAXI_LIMITS));
+    plane::SpawnCommand {
+        control: Some(plane::Control {
+            heading:     Heading::NORTH,
+            yaw_speed:   AngularSpeed::ZERO,
+            horiz_accel: Accel::ZERO,
+        }),
+        limits:  nav::Limits(NAV_LIMITS),
+    }
+    .apply(world.entity_mut(entity));
+    object::SetAirborneCommand.apply(world.entity_mut(entity));
+    entity
+}
+
+#[test]
+fn test_breakup_spawns_members_in_trail() {
+    let mut app = base_app();
+    let lead = spawn_formation(&mut app, 3);
+
+    BreakupCommand.apply(app.world_mut().entity_mut(lead));
+
+    assert!(app.world().get::<Formation>(lead).is_none(), "lead should no longer be a formation");
+
+    let world = app.world_mut();
+    let mut members: Vec<_> = world
+        .query::<(Entity, &Object, &object::Display)>()
+        .iter(world)
+        .filter(|&(entity, _, _)| entity != lead)
+        .map(|(_, object, display)| (display.name.clone(), object.position))
+        .collect();
+    members.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let names: Vec<_> = members.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["VIPER1-2", "VIPER1-3"]);
+
+    let lead_position = app.world().get::<Object>(lead).expect("lead should exist").position;
+    for (index, &(_, position)) in members.iter().enumerate() {
+        #[expect(clippy::cast_precision_loss, reason = "test index is small")]
+        let expected = Length::from_nm(3.0 * 1.2) * (index + 1) as f32;
+        let distance = lead_position.horizontal_distance_exact(position);
+        assert!(
+            distance > expected - Length::from_nm(0.01)
+                && distance < expected + Length::from_nm(0.01),
+            "member {index} should trail the lead by {expected:?}, got {distance:?}"
+        );
+        assert!(position.y() < lead_position.y(), "member {index} should be behind the lead");
+    }
+}
+
+#[test]
+fn test_ground_formation_not_split() {
+    let mut app = base_app();
+    let lead = spawn_formation(&mut app, 2);
+    app.world_mut().entity_mut(lead).remove::<object::Airborne>();
+
+    BreakupCommand.apply(app.world_mut().entity_mut(lead));
+
+    assert!(app.world().get::<Formation>(lead).is_some());
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Object>().iter(world).count(), 1);
+}
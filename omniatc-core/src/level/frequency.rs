@@ -0,0 +1,193 @@
+//! Split of the aerodrome radio into ground and tower frequencies.
+//!
+//! When [`Conf::split`] is enabled, every object is tuned to a single [`Frequency`]
+//! and only hears instructions transmitted on the frequency selected in [`Transmitting`].
+//! Instructions transmitted on another frequency are ignored and reported as a missed call.
+//!
+//! An object is tuned to the frequency of its current phase when it is first seen.
+//! Afterwards it only changes frequency when instructed with [`instr::ContactFrequency`].
+//! Departures call for the tower when they are cleared to taxi onto the runway,
+//! and arrivals call for the ground after vacating the runway;
+//! the object is marked [`HandoffDue`] until it is transferred.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Added, Has, With, Without};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+
+use super::{SystemSets, ground, instr, message, object, route};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:frequency");
+        app.init_resource::<Transmitting>();
+        app.add_systems(
+            app::Update,
+            (
+                (assign_system, missed_call_system).chain().before(instr::dispatch_system),
+                handoff_system,
+            )
+                .in_set(SystemSets::Communicate),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Whether ground and tower are controlled on separate frequencies.
+    #[config(default = false)]
+    pub split: bool,
+}
+
+/// Duration for which a missed call or a handoff call is displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(30);
+
+/// A controller position that objects can be tuned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, strum::EnumIter, strum::Display)]
+pub enum Frequency {
+    /// Controls objects taxiing to and from the runways.
+    #[strum(to_string = "ground")]
+    Ground,
+    /// Controls objects on the runways and in the air.
+    #[strum(to_string = "tower")]
+    Tower,
+}
+
+/// The frequency on which the controller currently transmits instructions.
+#[derive(Resource)]
+pub struct Transmitting(pub Frequency);
+
+impl Default for Transmitting {
+    fn default() -> Self { Self(Frequency::Tower) }
+}
+
+/// The object has reached a handoff point and is waiting to be transferred to this frequency.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct HandoffDue(pub Frequency);
+
+/// Determines the frequency that should control an object in its current phase.
+#[must_use]
+pub fn phase_frequency(airborne: bool, on_runway: bool, route: Option<&route::Route>) -> Frequency {
+    let entering_runway = route.and_then(route::Route::current).is_some_and(|node| match node {
+        route::Node::Takeoff(_) => true,
+        route::Node::Taxi(node) => node.label.is_runway(),
+        _ => false,
+    });
+    if airborne || on_runway || entering_runway { Frequency::Tower } else { Frequency::Ground }
+}
+
+fn assign_system(
+    conf: ReadConfig<Conf>,
+    object_query: Query<
+        (Entity, Has<object::Airborne>, Option<&object::OnGround>, Option<&route::Route>),
+        (With<object::Object>, Without<Frequency>),
+    >,
+    segment_query: Query<&ground::SegmentLabel>,
+    mut commands: Commands,
+) {
+    if !conf.read().split {
+        return;
+    }
+
+    for (entity, airborne, on_ground, route) in object_query {
+        let on_runway = is_on_runway(on_ground, &segment_query);
+        commands.entity(entity).insert(phase_frequency(airborne, on_runway, route));
+    }
+}
+
+fn is_on_runway(
+    on_ground: Option<&object::OnGround>,
+    segment_query: &Query<&ground::SegmentLabel>,
+) -> bool {
+    on_ground.is_some_and(|on_ground| {
+        segment_query.get(on_ground.segment).is_ok_and(ground::SegmentLabel::is_runway)
+    })
+}
+
+fn missed_call_system(
+    conf: ReadConfig<Conf>,
+    transmitting: Res<Transmitting>,
+    time: Res<Time<time::Virtual>>,
+    mut instr_query: Query<
+        (Entity, &instr::Recipient, &mut message::Message),
+        Added<instr::Instruction>,
+    >,
+    frequency_query: Query<&Frequency>,
+    mut commands: Commands,
+) {
+    if !conf.read().split {
+        return;
+    }
+
+    for (entity, &instr::Recipient(recipient), mut message) in &mut instr_query {
+        let Ok(&tuned) = frequency_query.get(recipient) else { continue };
+        if tuned == transmitting.0 {
+            continue;
+        }
+
+        message.class = message::Class::AnomalyInfo;
+        message.content.push_str(" (no reply)");
+        commands
+            .entity(entity)
+            .remove::<(instr::Instruction, instr::Recipient, instr::TransmitDelay)>()
+            .insert(message::Expiry { expiry: time.elapsed() + MESSAGE_DURATION });
+    }
+}
+
+fn handoff_system(
+    conf: ReadConfig<Conf>,
+    object_query: Query<(
+        Entity,
+        &Frequency,
+        Has<object::Airborne>,
+        Option<&object::OnGround>,
+        Option<&route::Route>,
+        Option<&HandoffDue>,
+    )>,
+    segment_query: Query<&ground::SegmentLabel>,
+    mut commands: Commands,
+) {
+    if !conf.read().split {
+        return;
+    }
+
+    for (entity, &tuned, airborne, on_ground, route, due) in object_query {
+        let on_runway = is_on_runway(on_ground, &segment_query);
+        let expected = phase_frequency(airborne, on_runway, route);
+
+        if expected == tuned {
+            if due.is_some() {
+                commands.entity(entity).remove::<HandoffDue>();
+            }
+        } else if due.is_none_or(|due| due.0 != expected) {
+            commands.entity(entity).insert(HandoffDue(expected));
+            commands.queue(message::SendExpiring {
+                source:   entity,
+                content:  format!("Request contact {expected}"),
+                class:    message::Class::NeedAck,
+                duration: MESSAGE_DURATION,
+            });
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy_mod_config::{ConfigNode, ScalarData};
+use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position, Speed};
+
+use super::{Frequency, HandoffDue, Transmitting};
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::{frequency, message, phraseology};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        frequency::Plug::<()>::default(),
+    ));
+    app.update();
+
+    let world = app.world_mut();
+    let mut query = world.query::<(&ConfigNode, &mut ScalarData<bool>)>();
+    for (node, mut data) in query.iter_mut(world) {
+        if node.path.iter().any(|segment| segment.contains("frequency"))
+            && node.path.last().is_some_and(|segment| segment == "split")
+        {
+            data.0 = true;
+        }
+    }
+    app
+}
+
+fn spawn_airborne(app: &mut App) -> Entity {
+    let altitude = Position::from_amsl_feet(3000.0);
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(altitude),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            object::Display { name: "ABC123".into() },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id()
+}
+
+fn outgoing_messages(app: &mut App) -> Vec<(String, message::Class)> {
+    let world = app.world_mut();
+    world
+        .query::<&message::Message>()
+        .iter(world)
+        .filter(|message| message.content.starts_with("ABC123,"))
+        .map(|message| (message.content.clone(), message.class))
+        .collect()
+}
+
+#[test]
+fn test_airborne_object_tuned_to_tower() {
+    let mut app = base_app();
+    let object = spawn_airborne(&mut app);
+
+    advance(&mut app, Duration::from_secs(1));
+
+    assert_eq!(app.world().get::<Frequency>(object), Some(&Frequency::Tower));
+    assert!(app.world().get::<HandoffDue>(object).is_none());
+}
+
+#[test]
+fn test_missed_call_on_other_frequency() {
+    let mut app = base_app();
+    let object = spawn_airborne(&mut app);
+    advance(&mut app, Duration::from_secs(1));
+
+    app.world_mut().resource_mut::<Transmitting>().0 = Frequency::Ground;
+    let world = app.world_mut();
+    world
+        .commands()
+        .send_instruction(object, instr::ContactFrequency { frequency: Frequency::Ground });
+    world.flush();
+    advance(&mut app, Duration::from_secs(10));
+
+    assert_eq!(app.world().get::<Frequency>(object), Some(&Frequency::Tower));
+    assert_eq!(
+        outgoing_messages(&mut app),
+        [("ABC123, contact ground (no reply)".into(), message::Class::AnomalyInfo)]
+    );
+}
+
+#[test]
+fn test_contact_frequency_changes_tuning() {
+    let mut app = base_app();
+    let object = spawn_airborne(&mut app);
+    advance(&mut app, Duration::from_secs(1));
+
+    let world = app.world_mut();
+    world
+        .commands()
+        .send_instruction(object, instr::ContactFrequency { frequency: Frequency::Ground });
+    world.flush();
+    advance(&mut app, Duration::from_secs(10));
+
+    assert_eq!(app.world().get::<Frequency>(object), Some(&Frequency::Ground));
+
+    advance(&mut app, Duration::from_secs(1));
+    // The airborne object now expects to be transferred back to the tower.
+    assert_eq!(app.world().get::<HandoffDue>(object).map(|due| due.0), Some(Frequency::Tower));
+}
@@ -0,0 +1,140 @@
+//! A simplified fuel model based on remaining flight time.
+//!
+//! Objects with an [`Endurance`] consume fuel while airborne.
+//! When the endurance drops below a threshold,
+//! the object declares minimum fuel and requests priority handling.
+//! A [`MinimumFuel`] object receives a completion bonus upon declaration,
+//! which is gradually deducted for every minute it is kept airborne afterwards.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use store::Score;
+
+use super::SystemSets;
+use crate::level::{dest, message, object, score};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:fuel");
+        app.add_systems(app::Update, consume_system.in_set(SystemSets::Aviate));
+        app.add_systems(app::Update, declare_system.in_set(SystemSets::Communicate));
+        app.add_systems(
+            app::Update,
+            delay_penalty_system.in_set(SystemSets::Statistics).in_set(score::Writer),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Objects declare minimum fuel when the remaining endurance drops below this duration.
+    #[config(default = Duration::from_mins(20))]
+    pub minimum_fuel_threshold:   Duration,
+    /// Completion score added to an object when it declares minimum fuel.
+    #[config(default = 5, min = 0, max = 100)]
+    pub priority_bonus:           i32,
+    /// Completion score deducted for every minute a minimum fuel object remains airborne.
+    #[config(default = 1, min = 0, max = 100)]
+    pub delay_penalty_per_minute: i32,
+}
+
+/// Remaining flight time of an object before fuel exhaustion.
+#[derive(Component)]
+pub struct Endurance {
+    pub remaining: Duration,
+}
+
+/// Marks an object that has declared minimum fuel.
+#[derive(Component)]
+pub struct MinimumFuel {
+    /// The virtual time elapsed when minimum fuel was declared.
+    pub declared_at:       Duration,
+    /// Number of minutes since declaration for which the delay penalty has been applied.
+    pub penalized_minutes: u32,
+}
+
+fn consume_system(
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<&mut Endurance, With<object::Airborne>>,
+) {
+    if time.is_paused() {
+        return;
+    }
+
+    for mut endurance in object_query {
+        endurance.remaining = endurance.remaining.saturating_sub(time.delta());
+    }
+}
+
+fn declare_system(
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<
+        (Entity, &Endurance, Option<&mut dest::CompletionScore>),
+        (With<object::Airborne>, Without<MinimumFuel>),
+    >,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (object, endurance, completion_score) in object_query {
+        if endurance.remaining >= conf.minimum_fuel_threshold {
+            continue;
+        }
+
+        commands
+            .entity(object)
+            .insert(MinimumFuel { declared_at: time.elapsed(), penalized_minutes: 0 });
+        if let Some(mut completion_score) = completion_score {
+            completion_score.score += Score(conf.priority_bonus);
+        }
+        commands.spawn(message::Message {
+            source:  object,
+            created: time.elapsed(),
+            content: format!(
+                "Minimum fuel, {:.0} minutes remaining, request priority",
+                endurance.remaining.as_secs_f32() / 60.
+            ),
+            class:   message::Class::Urgent,
+        });
+    }
+}
+
+fn delay_penalty_system(
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<(&mut MinimumFuel, &mut dest::CompletionScore), With<object::Airborne>>,
+) {
+    let conf = conf.read();
+
+    for (mut min_fuel, mut completion_score) in object_query {
+        #[expect(clippy::unchecked_time_subtraction, reason = "time.elapsed() is monotonic")]
+        let minutes = (time.elapsed() - min_fuel.declared_at).as_secs() / 60;
+        let minutes = u32::try_from(minutes).unwrap_or(u32::MAX);
+        while min_fuel.penalized_minutes < minutes {
+            min_fuel.penalized_minutes += 1;
+            completion_score.score -= Score(conf.delay_penalty_per_minute);
+        }
+    }
+}
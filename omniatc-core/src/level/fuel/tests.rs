@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position, Speed};
+use store::Score;
+
+use super::{Endurance, MinimumFuel};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::{dest, fuel, message, score};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, score::Plug, fuel::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+fn spawn_airborne(app: &mut App, endurance: Duration) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(5000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  Position::from_amsl_feet(5000.0),
+            },
+            dest::CompletionScore { score: Score(10) },
+            Endurance { remaining: endurance },
+        ))
+        .id()
+}
+
+fn completion_score(app: &App, entity: Entity) -> i32 {
+    app.world().get::<dest::CompletionScore>(entity).expect("object should exist").score.0
+}
+
+#[test]
+fn test_declare_below_threshold() {
+    let mut app = base_app();
+    let entity = spawn_airborne(&mut app, Duration::from_mins(21));
+
+    advance(&mut app, Duration::from_secs(30));
+    assert!(app.world().get::<MinimumFuel>(entity).is_none(), "endurance above threshold");
+    assert_eq!(completion_score(&app, entity), 10);
+
+    // Declaration is only observed in the next tick after consumption.
+    advance(&mut app, Duration::from_secs(31));
+    advance(&mut app, Duration::from_secs(1));
+    assert!(app.world().get::<MinimumFuel>(entity).is_some(), "endurance below threshold");
+    assert_eq!(completion_score(&app, entity), 15, "priority bonus should be granted");
+}
+
+#[test]
+fn test_delay_penalty() {
+    let mut app = base_app();
+    let entity = spawn_airborne(&mut app, Duration::from_mins(10));
+
+    advance(&mut app, Duration::from_secs(1));
+    assert_eq!(completion_score(&app, entity), 15, "priority bonus should be granted");
+
+    advance(&mut app, Duration::from_secs(150));
+    assert_eq!(completion_score(&app, entity), 13, "two minutes of delay penalty");
+
+    let endurance = app.world().get::<Endurance>(entity).expect("object should exist");
+    assert_eq!(endurance.remaining, Duration::from_secs(600 - 151));
+}
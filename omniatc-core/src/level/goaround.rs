@@ -0,0 +1,119 @@
+//! Accounting of missed approaches.
+//!
+//! Each go-around is reported as a [`GoAroundMessage`] with its [cause](Cause),
+//! counted in [`score::Stats::go_arounds`]
+//! and penalized with the score deduction configured for the cause.
+
+use std::marker::PhantomData;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::{Message, MessageReader};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{EntityCommand, ResMut};
+use bevy::ecs::world::EntityWorldMut;
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+pub use store::GoAroundCause as Cause;
+use store::Score;
+
+use super::SystemSets;
+use crate::level::route::{self, Route};
+use crate::level::score;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:goaround");
+        app.add_message::<GoAroundMessage>();
+        app.add_systems(
+            app::Update,
+            score_system.in_set(SystemSets::Statistics).in_set(score::Writer),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Score deducted when the controller vectors an object off its final approach.
+    #[config(default = 5, min = 0, max = 1000)]
+    pub player_issued_penalty:   i32,
+    /// Score deducted when an object goes around due to an unstable approach.
+    #[config(default = 20, min = 0, max = 1000)]
+    pub unstable_penalty:        i32,
+    /// Score deducted when an object goes around due to an occupied runway.
+    #[config(default = 50, min = 0, max = 1000)]
+    pub runway_occupied_penalty: i32,
+    /// Score deducted when an object goes around due to lack of visual contact.
+    #[config(default = 0, min = 0, max = 1000)]
+    pub weather_penalty:         i32,
+    /// Score deducted when an object goes around due to a bird strike.
+    #[config(default = 0, min = 0, max = 1000)]
+    pub bird_strike_penalty:     i32,
+}
+
+/// Sent when an object abandons its landing approach.
+#[derive(Message)]
+pub struct GoAroundMessage {
+    pub object: Entity,
+    pub cause:  Cause,
+}
+
+/// Reports a go-around of the object with the given cause.
+///
+/// This command only records the go-around;
+/// the caller is responsible for replacing the approach nodes of the route.
+pub struct Record(pub Cause);
+
+impl EntityCommand for Record {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let object = entity.id();
+        entity.world_scope(|world| world.write_message(GoAroundMessage { object, cause: self.0 }));
+    }
+}
+
+/// Reports a [player-issued](Cause::PlayerIssued) go-around
+/// if the object is currently established on short final.
+///
+/// Queued by instructions that vector the object off its route.
+pub struct BreakOff;
+
+impl EntityCommand for BreakOff {
+    fn apply(self, entity: EntityWorldMut) {
+        let on_final = entity.get::<Route>().and_then(Route::current).is_some_and(|node| {
+            matches!(node, route::Node::ShortFinal(_) | route::Node::VisualLanding(_))
+        });
+        if on_final {
+            Record(Cause::PlayerIssued).apply(entity);
+        }
+    }
+}
+
+fn score_system(
+    mut reader: MessageReader<GoAroundMessage>,
+    conf: ReadConfig<Conf>,
+    mut stats: ResMut<score::Stats>,
+) {
+    let conf = conf.read();
+    for message in reader.read() {
+        let penalty = match message.cause {
+            Cause::PlayerIssued => conf.player_issued_penalty,
+            Cause::Unstable => conf.unstable_penalty,
+            Cause::RunwayOccupied => conf.runway_occupied_penalty,
+            Cause::Weather => conf.weather_penalty,
+            Cause::BirdStrike => conf.bird_strike_penalty,
+        };
+        *stats.go_arounds.count_mut(message.cause) += 1;
+        stats.total -= Score(penalty);
+    }
+}
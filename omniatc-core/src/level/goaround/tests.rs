@@ -0,0 +1,68 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::EntityCommand;
+use math::Length;
+use store::Score;
+
+use super::{BreakOff, Cause, Record};
+use crate::level::route::{self, Route};
+use crate::level::{goaround, score, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((score::Plug, goaround::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+fn spawn_with_node(app: &mut App, node: route::Node) -> Entity {
+    app.world_mut().spawn(Route::from_iter([node])).id()
+}
+
+#[test]
+fn test_record_counts_cause_and_deducts_penalty() {
+    let mut app = base_app();
+    let object = app.world_mut().spawn_empty().id();
+
+    Record(Cause::Unstable).apply(app.world_mut().entity_mut(object));
+    Record(Cause::Weather).apply(app.world_mut().entity_mut(object));
+    app.update();
+
+    let stats = app.world().resource::<score::Stats>();
+    assert_eq!(stats.go_arounds.unstable, 1);
+    assert_eq!(stats.go_arounds.weather, 1);
+    assert_eq!(stats.go_arounds.count(Cause::RunwayOccupied), 0);
+    assert_eq!(stats.go_arounds.total(), 2);
+    // default penalties: 20 for unstable approaches, none for weather
+    assert_eq!(stats.total, Score(-20));
+}
+
+#[test]
+fn test_break_off_only_on_final() {
+    let mut app = base_app();
+    let runway = app.world_mut().spawn_empty().id();
+
+    let on_final = spawn_with_node(
+        &mut app,
+        route::Node::ShortFinal(route::ShortFinalNode { runway, goaround_preset: None }),
+    );
+    let en_route = spawn_with_node(
+        &mut app,
+        route::Node::DirectWaypoint(route::DirectWaypointNode {
+            waypoint:  runway,
+            distance:  Length::ZERO,
+            proximity: store::WaypointProximity::FlyBy,
+            altitude:  None,
+        }),
+    );
+
+    BreakOff.apply(app.world_mut().entity_mut(en_route));
+    app.update();
+    assert_eq!(app.world().resource::<score::Stats>().go_arounds.total(), 0);
+
+    BreakOff.apply(app.world_mut().entity_mut(on_final));
+    app.update();
+    let stats = app.world().resource::<score::Stats>();
+    assert_eq!(stats.go_arounds.player_issued, 1);
+    assert_eq!(stats.go_arounds.total(), 1);
+}
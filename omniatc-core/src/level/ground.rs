@@ -15,6 +15,8 @@ use smallvec::SmallVec;
 use crate::level::waypoint::Waypoint;
 use crate::util::QueryWith;
 
+pub mod export;
+
 pub struct Plug;
 
 impl Plugin for Plug {
@@ -0,0 +1,352 @@
+//! Serialization of resolved ground networks for debugging.
+//!
+//! The exported graph reflects the endpoints and segments spawned after line sweeping,
+//! so map authors can inspect why a taxi route is not found,
+//! e.g. taxiways that fail to intersect or disconnected parts of the network.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use bevy::app::{App, TaskPoolPlugin};
+use bevy::ecs::entity::{Entity, EntityHashMap};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::Command;
+use bevy::ecs::world::World;
+use bevy::math::Vec2;
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use math::{GeoPoint, Length, Position, Speed};
+
+use super::{AerodromeEndpoints, AerodromeSegments, Endpoint, Segment, SegmentLabel};
+use crate::level::aerodrome::Aerodrome;
+use crate::{level, load, util};
+
+#[cfg(test)]
+mod tests;
+
+/// A file format for exported ground networks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter, strum::Display)]
+pub enum Format {
+    /// Graphviz DOT graph with one cluster per aerodrome.
+    #[strum(to_string = "DOT")]
+    Dot,
+    /// `GeoJSON` feature collection of endpoints and segments.
+    #[strum(to_string = "GeoJSON")]
+    GeoJson,
+}
+
+impl Format {
+    /// File extension of the format, without the leading dot.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::GeoJson => "geojson",
+        }
+    }
+
+    /// Serializes `graph` with the map origin at `origin`.
+    ///
+    /// `origin` is only used for `GeoJSON`; see [`to_geojson`].
+    #[must_use]
+    pub fn write(self, graph: &Graph, origin: Option<GeoPoint>) -> String {
+        match self {
+            Self::Dot => to_dot(graph),
+            Self::GeoJson => to_geojson(graph, origin),
+        }
+    }
+}
+
+/// The resolved ground networks of all aerodromes.
+#[derive(Debug, Default)]
+pub struct Graph {
+    /// Aerodromes in the order of their serial ID.
+    pub aerodromes: Vec<AerodromeGraph>,
+}
+
+/// The resolved ground network of an aerodrome.
+#[derive(Debug)]
+pub struct AerodromeGraph {
+    /// Identifier code of the aerodrome.
+    pub code:      String,
+    pub endpoints: Vec<Node>,
+    pub segments:  Vec<Edge>,
+}
+
+/// An endpoint in the ground network.
+#[derive(Debug, Clone, Copy)]
+pub struct Node {
+    pub position: Position<Vec2>,
+}
+
+/// A segment in the ground network.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    /// Index of the alpha endpoint in [`AerodromeGraph::endpoints`].
+    pub alpha:     usize,
+    /// Index of the beta endpoint in [`AerodromeGraph::endpoints`].
+    pub beta:      usize,
+    /// Display label of the taxiway, runway or apron containing the segment.
+    pub label:     String,
+    pub width:     Length<f32>,
+    pub max_speed: Speed<f32>,
+}
+
+impl AerodromeGraph {
+    /// Number of segments connected to each endpoint.
+    #[must_use]
+    pub fn degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.endpoints.len()];
+        for edge in &self.segments {
+            degrees[edge.alpha] += 1;
+            degrees[edge.beta] += 1;
+        }
+        degrees
+    }
+
+    /// Index of the connected component containing each endpoint.
+    ///
+    /// Components are numbered in the order of their first endpoint.
+    /// Objects cannot taxi between endpoints in different components.
+    #[must_use]
+    pub fn components(&self) -> Vec<usize> {
+        let mut adjacency = vec![Vec::new(); self.endpoints.len()];
+        for edge in &self.segments {
+            adjacency[edge.alpha].push(edge.beta);
+            adjacency[edge.beta].push(edge.alpha);
+        }
+
+        let mut components = vec![usize::MAX; self.endpoints.len()];
+        let mut next_component = 0;
+        for start in 0..self.endpoints.len() {
+            if components[start] != usize::MAX {
+                continue;
+            }
+
+            components[start] = next_component;
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for &neighbor in &adjacency[node] {
+                    if components[neighbor] == usize::MAX {
+                        components[neighbor] = next_component;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            next_component += 1;
+        }
+        components
+    }
+
+    /// Number of connected components in the network.
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.components().into_iter().max().map_or(0, |max| max + 1)
+    }
+}
+
+/// Collects the ground networks spawned in `world`.
+#[must_use]
+pub fn collect(world: &mut World) -> Graph {
+    let mut aerodromes: Vec<_> = world
+        .query::<(&Aerodrome, Option<&AerodromeEndpoints>, Option<&AerodromeSegments>)>()
+        .iter(world)
+        .map(|(aerodrome, endpoints, segments)| {
+            (
+                aerodrome.id,
+                aerodrome.code.clone(),
+                endpoints.map_or_else(Vec::new, |endpoints| endpoints.endpoints().to_vec()),
+                segments.map_or_else(Vec::new, |segments| segments.segments().to_vec()),
+            )
+        })
+        .collect();
+    aerodromes.sort_by_key(|&(id, ..)| id);
+
+    let aerodromes = aerodromes
+        .into_iter()
+        .map(|(_, code, endpoint_entities, segment_entities)| {
+            collect_aerodrome(world, code, &endpoint_entities, &segment_entities)
+        })
+        .collect();
+    Graph { aerodromes }
+}
+
+fn collect_aerodrome(
+    world: &World,
+    code: String,
+    endpoint_entities: &[Entity],
+    segment_entities: &[Entity],
+) -> AerodromeGraph {
+    let mut indices = EntityHashMap::default();
+    let endpoints = endpoint_entities
+        .iter()
+        .filter_map(|&entity| {
+            let endpoint = world.get::<Endpoint>(entity)?;
+            let index = indices.len();
+            indices.insert(entity, index);
+            Some(Node { position: endpoint.position })
+        })
+        .collect();
+
+    let segments = segment_entities
+        .iter()
+        .filter_map(|&entity| {
+            let segment = world.get::<Segment>(entity)?;
+            let label = world
+                .get::<SegmentLabel>(entity)
+                .map_or_else(String::new, |label| label.display_segment_label(world));
+            Some(Edge {
+                alpha: *indices.get(&segment.alpha)?,
+                beta: *indices.get(&segment.beta)?,
+                label,
+                width: segment.width,
+                max_speed: segment.max_speed,
+            })
+        })
+        .collect();
+
+    AerodromeGraph { code, endpoints, segments }
+}
+
+#[derive(Resource, Default)]
+struct LoadFailure(Option<load::Error>);
+
+/// Loads `file` in a headless world and collects its resolved ground networks.
+pub fn from_file(file: store::File) -> Result<Graph, load::Error> {
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TimePlugin,
+        level::Plug::<()>::default(),
+        load::Plug,
+        util::Plug,
+    ));
+    app.init_resource::<LoadFailure>();
+    app.finish();
+    app.cleanup();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    app.update();
+
+    let world = app.world_mut();
+    load::Command {
+        source:   load::Source::Parsed(Box::new(file)),
+        on_error: Box::new(|world, err| world.resource_mut::<LoadFailure>().0 = Some(err)),
+    }
+    .apply(world);
+    if let Some(err) = world.resource_mut::<LoadFailure>().0.take() {
+        return Err(err);
+    }
+    Ok(collect(world))
+}
+
+/// Serializes `graph` as an undirected Graphviz graph.
+///
+/// Each aerodrome is a cluster.
+/// Nodes are pinned to their map position in meters,
+/// so layout engines that respect `pos` (e.g. `neato -n`) reproduce the map geometry.
+/// Dead-end endpoints are drawn as boxes.
+#[must_use]
+pub fn to_dot(graph: &Graph) -> String {
+    let mut output = String::from("graph ground {\n  node [shape=point];\n");
+    for (cluster, aerodrome) in graph.aerodromes.iter().enumerate() {
+        writeln!(output, "  subgraph cluster_{cluster} {{").expect("write to string");
+        output.push_str("    label=");
+        write_dot_string(&mut output, &aerodrome.code);
+        output.push_str(";\n");
+
+        let degrees = aerodrome.degrees();
+        let components = aerodrome.components();
+        for (index, node) in aerodrome.endpoints.iter().enumerate() {
+            let Vec2 { x, y } = node.position.0.into_meters();
+            let shape = if degrees[index] <= 1 { "box" } else { "point" };
+            writeln!(
+                output,
+                "    a{cluster}_{index} [xlabel=\"{index}\", pos=\"{x:.1},{y:.1}!\", \
+                 shape={shape}, comment=\"component {}\"];",
+                components[index],
+            )
+            .expect("write to string");
+        }
+
+        for edge in &aerodrome.segments {
+            write!(output, "    a{cluster}_{} -- a{cluster}_{} [label=", edge.alpha, edge.beta)
+                .expect("write to string");
+            write_dot_string(&mut output, &edge.label);
+            output.push_str("];\n");
+        }
+        output.push_str("  }\n");
+    }
+    output.push_str("}\n");
+    output
+}
+
+fn write_dot_string(output: &mut String, text: &str) {
+    output.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            _ => output.push(ch),
+        }
+    }
+    output.push('"');
+}
+
+/// Serializes `graph` as a `GeoJSON` feature collection.
+///
+/// Endpoints are points with the properties `aerodrome`, `index`, `degree` and `component`.
+/// Segments are line strings with the properties `aerodrome`, `label`, `alpha`, `beta`,
+/// `width_m` and `max_speed_kt`.
+///
+/// If `origin` is `None`, coordinates are map positions in meters instead of longitude and
+/// latitude, which can still be inspected in viewers that accept planar coordinates.
+#[must_use]
+pub fn to_geojson(graph: &Graph, origin: Option<GeoPoint>) -> String {
+    let coordinates = |position: Position<Vec2>| {
+        if let Some(origin) = origin {
+            let geo = GeoPoint::from_map(position, origin);
+            serde_json::json!([geo.lon, geo.lat])
+        } else {
+            let Vec2 { x, y } = position.0.into_meters();
+            serde_json::json!([x, y])
+        }
+    };
+
+    let mut features = Vec::new();
+    for aerodrome in &graph.aerodromes {
+        let degrees = aerodrome.degrees();
+        let components = aerodrome.components();
+        for (index, node) in aerodrome.endpoints.iter().enumerate() {
+            features.push(serde_json::json!({
+                "type": "Feature",
+                "properties": {
+                    "aerodrome": aerodrome.code,
+                    "index": index,
+                    "degree": degrees[index],
+                    "component": components[index],
+                },
+                "geometry": { "type": "Point", "coordinates": coordinates(node.position) },
+            }));
+        }
+        for edge in &aerodrome.segments {
+            let alpha = aerodrome.endpoints[edge.alpha].position;
+            let beta = aerodrome.endpoints[edge.beta].position;
+            features.push(serde_json::json!({
+                "type": "Feature",
+                "properties": {
+                    "aerodrome": aerodrome.code,
+                    "label": edge.label,
+                    "alpha": edge.alpha,
+                    "beta": edge.beta,
+                    "width_m": edge.width.into_meters(),
+                    "max_speed_kt": edge.max_speed.into_knots(),
+                },
+                "geometry": {
+                    "type": "LineString",
+                    "coordinates": [coordinates(alpha), coordinates(beta)],
+                },
+            }));
+        }
+    }
+    serde_json::json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
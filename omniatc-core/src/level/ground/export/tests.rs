@@ -0,0 +1,84 @@
+use math::{GeoPoint, Length, Position, Speed};
+
+use super::{AerodromeGraph, Edge, Graph, Node, to_dot, to_geojson};
+
+fn edge(alpha: usize, beta: usize, label: &str) -> Edge {
+    Edge {
+        alpha,
+        beta,
+        label: label.into(),
+        width: Length::from_meters(20.),
+        max_speed: Speed::from_knots(20.),
+    }
+}
+
+/// A T-junction of taxiways A and B, plus an isolated apron path.
+fn graph() -> Graph {
+    Graph {
+        aerodromes: vec![AerodromeGraph {
+            code:      "MAIN".into(),
+            endpoints: vec![
+                Node { position: Position::from_origin_nm(0., 0.) },
+                Node { position: Position::from_origin_nm(1., 0.) },
+                Node { position: Position::from_origin_nm(2., 0.) },
+                Node { position: Position::from_origin_nm(1., 1.) },
+                Node { position: Position::from_origin_nm(3., 3.) },
+                Node { position: Position::from_origin_nm(3., 4.) },
+            ],
+            segments:  vec![
+                edge(0, 1, "taxiway A"),
+                edge(1, 2, "taxiway A"),
+                edge(1, 3, "taxiway B"),
+                edge(4, 5, "apron \"north\""),
+            ],
+        }],
+    }
+}
+
+#[test]
+fn components_split_disconnected_parts() {
+    let graph = graph();
+    let aerodrome = &graph.aerodromes[0];
+    assert_eq!(aerodrome.components(), [0, 0, 0, 0, 1, 1]);
+    assert_eq!(aerodrome.component_count(), 2);
+    assert_eq!(aerodrome.degrees(), [1, 3, 1, 1, 1, 1]);
+}
+
+#[test]
+fn dot_contains_nodes_and_escaped_edges() {
+    let dot = to_dot(&graph());
+    assert!(dot.starts_with("graph ground {"));
+    assert!(dot.contains("label=\"MAIN\";"));
+    assert!(dot.contains("a0_1 [xlabel=\"1\", pos=\"1852.0,0.0!\", shape=point"));
+    assert!(dot.contains("a0_0 [xlabel=\"0\", pos=\"0.0,0.0!\", shape=box"));
+    assert!(dot.contains("a0_1 -- a0_3 [label=\"taxiway B\"];"));
+    assert!(dot.contains("a0_4 -- a0_5 [label=\"apron \\\"north\\\"\"];"));
+}
+
+#[test]
+fn geojson_features() {
+    let origin = GeoPoint { lat: 0., lon: 0. };
+    let json: serde_json::Value = serde_json::from_str(&to_geojson(&graph(), Some(origin)))
+        .expect("output should be valid JSON");
+    let features = json["features"].as_array().expect("features should be an array");
+    assert_eq!(features.len(), 10);
+
+    let junction = &features[1];
+    assert_eq!(junction["geometry"]["type"], "Point");
+    assert_eq!(junction["properties"]["degree"], 3);
+    let lon = junction["geometry"]["coordinates"][0].as_f64().expect("longitude");
+    assert!((lon - 1. / 60.).abs() < 1e-6, "1 nm east of the equator origin, got {lon}");
+
+    let apron = &features[9];
+    assert_eq!(apron["geometry"]["type"], "LineString");
+    assert_eq!(apron["properties"]["label"], "apron \"north\"");
+    assert_eq!(apron["properties"]["alpha"], 4);
+}
+
+#[test]
+fn geojson_without_origin_uses_meters() {
+    let json: serde_json::Value =
+        serde_json::from_str(&to_geojson(&graph(), None)).expect("output should be valid JSON");
+    let x = json["features"][2]["geometry"]["coordinates"][0].as_f64().expect("x");
+    assert!((x - 3704.).abs() < 1e-3, "2 nm east of the origin, got {x}");
+}
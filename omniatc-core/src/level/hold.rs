@@ -0,0 +1,400 @@
+//! Published holding patterns for arrivals while no runway accepts arrivals.
+//!
+//! When no runway of an aerodrome accepts arrivals,
+//! airborne arrivals to the aerodrome enter the nearest [`Pattern`] with a free level,
+//! stacked upwards from the lowest level.
+//! Once a runway accepts arrivals again,
+//! the lowest object of each stack is released every [`Conf::release_interval`]
+//! to resume its route, and the rest of the stack descends to fill the gap.
+//!
+//! Vectoring a holding object or clearing it to continue its route
+//! removes it from the stack.
+//! Such objects are not stacked again until the aerodrome accepts arrivals.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, EntityCommand, Query, Res};
+use bevy::ecs::world::EntityWorldMut;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Angle, Heading, Length, Position, TurnDirection};
+use ordered_float::OrderedFloat;
+use store::YawTarget;
+
+use super::SystemSets;
+use crate::QueryTryLog;
+use crate::level::dest::Destination;
+use crate::level::object::{self, Object};
+use crate::level::waypoint::Waypoint;
+use crate::level::{message, nav, pilot, route, runway};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+/// Distance from the fix within which the object turns outbound.
+const FIX_PASSAGE_DISTANCE: Length<f32> = Length::from_nm(1.0);
+/// The outbound leg is timed once the track is within this angle of the outbound heading.
+const OUTBOUND_TRACK_TOLERANCE: Angle = Angle::from_degrees(10.0);
+/// Duration for which stack entry and release messages are displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(30);
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:hold");
+        app.add_systems(
+            app::Update,
+            (compact_system, release_system, entry_system).chain().in_set(SystemSets::Action),
+        );
+        app.add_systems(app::Update, pattern_system.in_set(SystemSets::Navigate));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Interval between releasing consecutive objects from the bottom of a stack.
+    #[config(default = Duration::from_secs(90))]
+    pub release_interval: Duration,
+}
+
+/// A published holding pattern over a fix.
+#[derive(Component)]
+#[require(Stack)]
+pub struct Pattern {
+    /// The aerodrome whose arrivals hold in this pattern.
+    pub aerodrome:     Entity,
+    /// The waypoint entity of the holding fix.
+    pub fix:           Entity,
+    /// Track of the inbound leg towards the fix.
+    pub inbound:       Heading,
+    /// Direction of the turns in the pattern.
+    pub direction:     TurnDirection,
+    /// Time to fly the outbound leg after turning outbound.
+    pub leg_time:      Duration,
+    /// Altitude of the lowest level in the stack.
+    pub min_altitude:  Position<f32>,
+    /// Vertical separation between consecutive levels.
+    pub level_spacing: Length<f32>,
+    /// Number of levels in the stack.
+    pub levels:        u32,
+}
+
+impl Pattern {
+    /// Returns the altitude of a level, counting from 0 at the bottom of the stack.
+    #[must_use]
+    pub fn level_altitude(&self, level: u32) -> Position<f32> {
+        #[expect(clippy::cast_precision_loss, reason = "stacks have few levels")]
+        let level = level as f32;
+        self.min_altitude + self.level_spacing * level
+    }
+}
+
+/// Release state of the stack of a [`Pattern`].
+#[derive(Component, Default)]
+pub struct Stack {
+    /// Virtual time at which the last object was released from the stack.
+    ///
+    /// Reset to `None` when the stack is empty.
+    pub last_release: Option<Duration>,
+}
+
+/// An object holding in a [`Pattern`].
+#[derive(Component)]
+pub struct Holding {
+    /// The pattern entity.
+    pub pattern: Entity,
+    /// Level in the stack, counting from 0 at the bottom.
+    pub level:   u32,
+    pub phase:   Phase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// Flying towards the fix.
+    Inbound,
+    /// Flying away from the fix.
+    Outbound {
+        /// Time flown on the outbound track.
+        elapsed: Duration,
+    },
+}
+
+/// Marks an arrival removed from a stack by the player,
+/// which is not stacked again until its destination accepts arrivals.
+#[derive(Component)]
+pub struct Released;
+
+/// Removes an object from its stack when the player overrides its navigation.
+pub struct Override;
+
+impl EntityCommand for Override {
+    fn apply(self, mut entity: EntityWorldMut) {
+        if entity.take::<Holding>().is_some() {
+            entity.insert(Released);
+        }
+    }
+}
+
+/// Returns the aerodrome that an arrival lands at.
+fn arrival_aerodrome(destination: &Destination) -> Option<Entity> {
+    match *destination {
+        Destination::Landing { aerodrome } | Destination::Parking { aerodrome } => Some(aerodrome),
+        Destination::VacateAnyRunway | Destination::Departure { .. } => None,
+    }
+}
+
+fn accepts_arrivals(
+    runway_query: &Query<(&runway::RunwayOf, &runway::Availability)>,
+    aerodrome: Entity,
+) -> bool {
+    runway_query.iter().any(|(runway_of, availability)| {
+        runway_of.0 == aerodrome && availability.accepts_arrivals()
+    })
+}
+
+/// Moves objects down to fill the gaps in their stacks.
+fn compact_system(
+    pattern_query: Query<(Entity, &Pattern, &mut Stack)>,
+    mut holding_query: Query<(Entity, &mut Holding, Option<&mut pilot::Clearance>)>,
+    mut commands: Commands,
+) {
+    for (pattern_entity, pattern, mut stack) in pattern_query {
+        let mut members: Vec<_> = holding_query
+            .iter()
+            .filter(|(_, holding, _)| holding.pattern == pattern_entity)
+            .map(|(object, holding, _)| (holding.level, object))
+            .collect();
+        if members.is_empty() {
+            stack.last_release = None;
+            continue;
+        }
+        members.sort_unstable();
+
+        for (level, (old_level, object)) in (0..).zip(members) {
+            if level == old_level {
+                continue;
+            }
+            let Ok((_, mut holding, clearance)) = holding_query.get_mut(object) else { continue };
+            holding.level = level;
+
+            let altitude = pattern.level_altitude(level);
+            if let Some(mut clearance) = clearance {
+                clearance.altitude = Some(altitude);
+            }
+            commands.entity(object).insert(nav::TargetAltitude { altitude, expedite: false });
+        }
+    }
+}
+
+/// Releases the lowest object of each stack whose aerodrome accepts arrivals.
+fn release_system(
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    pattern_query: Query<(Entity, &Pattern, &mut Stack)>,
+    runway_query: Query<(&runway::RunwayOf, &runway::Availability)>,
+    holding_query: Query<(Entity, &Holding)>,
+    released_query: Query<(Entity, &Destination), With<Released>>,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+    let now = time.elapsed();
+
+    for (pattern_entity, pattern, mut stack) in pattern_query {
+        if !accepts_arrivals(&runway_query, pattern.aerodrome) {
+            continue;
+        }
+        if stack.last_release.is_some_and(|last| now < last + conf.release_interval) {
+            continue;
+        }
+        let Some((object, _)) = holding_query
+            .iter()
+            .filter(|(_, holding)| holding.pattern == pattern_entity)
+            .min_by_key(|(_, holding)| holding.level)
+        else {
+            continue;
+        };
+
+        stack.last_release = Some(now);
+        commands
+            .entity(object)
+            .remove::<(Holding, nav::TargetWaypoint, nav::TargetGroundDirection)>()
+            .queue(route::RemoveStandby { skip_id: None });
+
+        let fix_name = waypoint_query.log_get(pattern.fix).map_or("unknown", |w| w.name.as_str());
+        commands.queue(message::SendExpiring {
+            source:   object,
+            content:  format!("Leaving the hold at {fix_name}"),
+            class:    message::Class::VerboseInfo,
+            duration: MESSAGE_DURATION,
+        });
+    }
+
+    for (object, destination) in released_query {
+        if arrival_aerodrome(destination)
+            .is_none_or(|aerodrome| accepts_arrivals(&runway_query, aerodrome))
+        {
+            commands.entity(object).remove::<Released>();
+        }
+    }
+}
+
+/// Stacks arrivals to aerodromes that do not accept arrivals.
+fn entry_system(
+    pattern_query: Query<(Entity, &Pattern)>,
+    runway_query: Query<(&runway::RunwayOf, &runway::Availability)>,
+    holding_query: Query<&Holding>,
+    arrival_query: Query<
+        (Entity, &Object, &Destination, Option<&route::Route>, Option<&mut pilot::Clearance>),
+        (
+            With<object::Airborne>,
+            With<nav::VelocityTarget>,
+            Without<Holding>,
+            Without<Released>,
+            Without<nav::TargetGlide>,
+        ),
+    >,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    let mut occupancy = HashMap::<Entity, u32>::new();
+    for holding in &holding_query {
+        *occupancy.entry(holding.pattern).or_default() += 1;
+    }
+
+    for (object, &Object { position, .. }, destination, route, clearance) in arrival_query {
+        let Some(aerodrome) = arrival_aerodrome(destination) else { continue };
+        if accepts_arrivals(&runway_query, aerodrome) {
+            continue;
+        }
+        if route.is_some_and(|route| matches!(route.current(), Some(route::Node::AlignRunway(_)))) {
+            continue; // already established on the approach
+        }
+
+        let nearest = pattern_query
+            .iter()
+            .filter(|&(entity, pattern)| {
+                pattern.aerodrome == aerodrome
+                    && occupancy.get(&entity).copied().unwrap_or_default() < pattern.levels
+            })
+            .filter_map(|(entity, pattern)| {
+                Some((entity, pattern, waypoint_query.log_get(pattern.fix)?))
+            })
+            .min_by_key(|(_, _, fix)| {
+                OrderedFloat(position.horizontal_distance_exact(fix.position).0)
+            });
+        let Some((pattern_entity, pattern, fix)) = nearest else { continue };
+
+        let occupied = occupancy.entry(pattern_entity).or_default();
+        let level = *occupied;
+        *occupied += 1;
+
+        let altitude = pattern.level_altitude(level);
+        if let Some(mut clearance) = clearance {
+            clearance.heading = None;
+            clearance.altitude = Some(altitude);
+        }
+        commands
+            .entity(object)
+            .queue(route::PrependStandby)
+            .remove::<(nav::TargetAlignment, nav::TargetGlideStatus, nav::ScheduledResume)>()
+            .insert((
+                Holding { pattern: pattern_entity, level, phase: Phase::Inbound },
+                nav::TargetWaypoint { waypoint_entity: pattern.fix },
+                nav::TargetAltitude { altitude, expedite: false },
+            ));
+        commands.queue(message::SendExpiring {
+            source:   object,
+            content:  format!(
+                "Runway unavailable, holding at {} at {:.0} ft",
+                fix.name,
+                altitude.amsl().into_feet()
+            ),
+            class:    message::Class::AnomalyInfo,
+            duration: MESSAGE_DURATION,
+        });
+    }
+}
+
+/// Flies the racetrack of holding objects.
+fn pattern_system(
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<(
+        Entity,
+        &Object,
+        &mut Holding,
+        &mut nav::VelocityTarget,
+        Option<&route::Route>,
+    )>,
+    pattern_query: Query<&Pattern>,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    if time.is_paused() {
+        return;
+    }
+
+    for (entity, object, mut holding, mut target, route) in object_query {
+        let on_standby = route.is_some_and(|route| {
+            matches!(
+                route.current(),
+                Some(route::Node::Standby(route::StandbyNode { skip_id: None }))
+            )
+        });
+        if !on_standby {
+            // The player has cleared the object to continue its route.
+            commands.entity(entity).queue(Override);
+            continue;
+        }
+
+        let Some(pattern) = pattern_query.log_get(holding.pattern) else { continue };
+        match holding.phase {
+            Phase::Inbound => {
+                let Some(fix) = waypoint_query.log_get(pattern.fix) else { continue };
+                if object.position.horizontal_distance_exact(fix.position) < FIX_PASSAGE_DISTANCE {
+                    holding.phase = Phase::Outbound { elapsed: Duration::ZERO };
+                    commands
+                        .entity(entity)
+                        .remove::<(nav::TargetWaypoint, nav::TargetGroundDirection)>();
+                }
+            }
+            Phase::Outbound { elapsed } => {
+                let outbound = pattern.inbound.opposite();
+                let track = object.ground_speed.horizontal().heading();
+                if track.closest_distance(outbound).abs() < OUTBOUND_TRACK_TOLERANCE {
+                    target.yaw = YawTarget::Heading(outbound);
+                    let elapsed = elapsed + time.delta();
+                    holding.phase = Phase::Outbound { elapsed };
+                    if elapsed >= pattern.leg_time {
+                        holding.phase = Phase::Inbound;
+                        commands
+                            .entity(entity)
+                            .insert(nav::TargetWaypoint { waypoint_entity: pattern.fix });
+                    }
+                } else {
+                    target.yaw = YawTarget::TurnHeading {
+                        heading:           outbound,
+                        direction:         pattern.direction,
+                        remaining_crosses: 0,
+                    };
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+
+use crate::level::aerodrome::loader::AerodromeMap;
+use crate::level::hold;
+use crate::level::waypoint::loader::WaypointMap;
+use crate::load::{self, StoredEntity};
+
+/// Spawns the published holding patterns of the aerodromes declared in a store.
+///
+/// Holding patterns with an unresolvable fix are skipped and reported as [issues](load::Issues).
+///
+/// # Errors
+/// If an aerodrome does not exist.
+pub fn spawn(
+    world: &mut World,
+    aerodromes: &[store::Aerodrome],
+    aerodrome_map: &AerodromeMap,
+    waypoints: &WaypointMap,
+) -> Result<(), load::Error> {
+    for aerodrome in aerodromes {
+        let aerodrome_entity =
+            aerodrome_map.resolve(&aerodrome.code.as_str().into())?.aerodrome_entity;
+        for pattern in &aerodrome.holds {
+            let Some(fix) = load::recover(
+                world,
+                || format!("holding pattern of {}", aerodrome.code),
+                waypoints.resolve_ref(aerodrome_map, &pattern.fix),
+            )?
+            else {
+                continue;
+            };
+            world.spawn((
+                StoredEntity,
+                Name::new(format!("Holding pattern: {}", aerodrome.code)),
+                hold::Pattern {
+                    aerodrome: aerodrome_entity,
+                    fix,
+                    inbound: pattern.inbound_heading,
+                    direction: pattern.direction,
+                    leg_time: pattern.leg_time,
+                    min_altitude: pattern.min_altitude,
+                    level_spacing: pattern.level_spacing,
+                    levels: pattern.levels,
+                },
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::EntityCommand;
+use math::{
+    Heading, ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Length, Position, Speed,
+    TurnDirection,
+};
+use store::{WaypointProximity, YawTarget};
+
+use super::{Holding, Override, Pattern, Released};
+use crate::level::dest::Destination;
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{hold, message, nav, route, runway};
+
+struct Entities {
+    runway:  Entity,
+    pattern: Entity,
+    route:   Entity,
+}
+
+fn base_app() -> (App, Entities) {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, hold::Plug::<()>::default()));
+
+    let world = app.world_mut();
+    let aerodrome = world.spawn_empty().id();
+    let runway = world.spawn((runway::RunwayOf(aerodrome), runway::Availability::Closed)).id();
+    let fix = world
+        .spawn(Waypoint {
+            name:         "POLAR".into(),
+            position:     Position::from_origin_nm(0.0, 20.0).with_altitude(Position::SEA_LEVEL),
+            display_type: waypoint::DisplayType::Waypoint,
+            hidden:       false,
+        })
+        .id();
+    let route = world
+        .spawn(Waypoint {
+            name:         "APPNE".into(),
+            position:     Position::from_origin_nm(0.0, 10.0).with_altitude(Position::SEA_LEVEL),
+            display_type: waypoint::DisplayType::Waypoint,
+            hidden:       false,
+        })
+        .id();
+    let pattern = world
+        .spawn(Pattern {
+            aerodrome,
+            fix,
+            inbound: Heading::SOUTH,
+            direction: TurnDirection::Clockwise,
+            leg_time: Duration::from_mins(1),
+            min_altitude: Position::from_amsl_feet(6000.0),
+            level_spacing: Length::from_feet(1000.0),
+            levels: 4,
+        })
+        .id();
+
+    app.update();
+    (app, Entities { runway, pattern, route })
+}
+
+fn spawn_arrival(app: &mut App, entities: &Entities, name: &str) -> Entity {
+    let world = app.world_mut();
+    let aerodrome = world.get::<runway::RunwayOf>(entities.runway).expect("spawned").0;
+    let altitude = Position::from_amsl_feet(9000.0);
+
+    let mut route = route::Route::default();
+    route.push(
+        route::DirectWaypointNode {
+            waypoint:  entities.route,
+            distance:  Length::from_nm(1.0),
+            proximity: WaypointProximity::FlyOver,
+            altitude:  None,
+        }
+        .into(),
+    );
+
+    world
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 30.0).with_altitude(altitude),
+                ground_speed: (Speed::from_knots(250.0) * Heading::SOUTH).horizontally(),
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::SOUTH),
+                horiz_speed: Speed::from_knots(250.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+            Destination::Landing { aerodrome },
+            route,
+            message::Sender { display: name.into() },
+        ))
+        .id()
+}
+
+fn holding_level(app: &App, object: Entity) -> Option<u32> {
+    app.world().get::<Holding>(object).map(|holding| holding.level)
+}
+
+fn target_altitude_feet(app: &App, object: Entity) -> f32 {
+    app.world().get::<nav::TargetAltitude>(object).expect("holding").altitude.amsl().into_feet()
+}
+
+#[test]
+fn test_stack_and_release_lowest_first() {
+    let (mut app, entities) = base_app();
+    let first = spawn_arrival(&mut app, &entities, "FIRST");
+    advance(&mut app, Duration::from_secs(1));
+    let second = spawn_arrival(&mut app, &entities, "SECOND");
+    advance(&mut app, Duration::from_secs(1));
+
+    assert_eq!(holding_level(&app, first), Some(0));
+    assert_eq!(holding_level(&app, second), Some(1));
+    assert!((target_altitude_feet(&app, first) - 6000.0).abs() < 1.0);
+    assert!((target_altitude_feet(&app, second) - 7000.0).abs() < 1.0);
+    let holding = app.world().get::<Holding>(first).expect("holding");
+    assert_eq!(holding.pattern, entities.pattern);
+
+    *app.world_mut().get_mut::<runway::Availability>(entities.runway).expect("spawned") =
+        runway::Availability::Open;
+    advance(&mut app, Duration::from_secs(1));
+
+    assert_eq!(holding_level(&app, first), None);
+    let route = app.world().get::<route::Route>(first).expect("spawned");
+    assert!(matches!(route.current(), Some(route::Node::DirectWaypoint(_))));
+
+    // The rest of the stack descends, but waits for the release interval.
+    advance(&mut app, Duration::from_secs(1));
+    assert_eq!(holding_level(&app, second), Some(0));
+    assert!((target_altitude_feet(&app, second) - 6000.0).abs() < 1.0);
+
+    advance(&mut app, Duration::from_secs(100));
+    assert_eq!(holding_level(&app, second), None);
+}
+
+#[test]
+fn test_override_is_not_restacked() {
+    let (mut app, entities) = base_app();
+    let object = spawn_arrival(&mut app, &entities, "ARRIVAL");
+    advance(&mut app, Duration::from_secs(1));
+    assert_eq!(holding_level(&app, object), Some(0));
+
+    Override.apply(app.world_mut().entity_mut(object));
+    advance(&mut app, Duration::from_secs(1));
+
+    assert_eq!(holding_level(&app, object), None);
+    assert!(app.world().get::<Released>(object).is_some());
+
+    *app.world_mut().get_mut::<runway::Availability>(entities.runway).expect("spawned") =
+        runway::Availability::Open;
+    advance(&mut app, Duration::from_secs(1));
+    assert!(app.world().get::<Released>(object).is_none());
+}
+
+#[test]
+fn test_departures_only_does_not_accept_arrivals() {
+    let (mut app, entities) = base_app();
+    *app.world_mut().get_mut::<runway::Availability>(entities.runway).expect("spawned") =
+        runway::Availability::DeparturesOnly;
+    let object = spawn_arrival(&mut app, &entities, "ARRIVAL");
+    advance(&mut app, Duration::from_secs(1));
+
+    assert_eq!(holding_level(&app, object), Some(0));
+}
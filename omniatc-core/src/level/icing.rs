@@ -0,0 +1,213 @@
+//! Ice accretion in icing bands.
+//!
+//! Airborne objects holding within an icing [`Band`] accumulate ice,
+//! which progressively reduces their climb performance.
+//! Once the accretion reaches [`Conf::request_threshold`],
+//! the object requests a descent below the band.
+//! If the accretion becomes critical while still holding,
+//! the object leaves the stack and descends below the band on its own.
+//! Ice is shed gradually after leaving the band,
+//! restoring the original climb performance.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::ecs::world::EntityWorldMut;
+use bevy::math::{Vec2, Vec3};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Length, Position, Speed};
+
+use super::SystemSets;
+use crate::level::object::{self, Object};
+use crate::level::{hold, message, nav, pilot, pilot_request};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+/// Duration for which icing reports are displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(30);
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:icing");
+        app.add_systems(app::Update, accretion_system.in_set(SystemSets::Action));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Time holding within an icing band until the accretion becomes critical.
+    #[config(default = Duration::from_mins(10))]
+    pub accretion_time:    Duration,
+    /// Time outside icing bands to shed critical accretion completely.
+    #[config(default = Duration::from_mins(5))]
+    pub shedding_time:     Duration,
+    /// Fraction of the climb rate lost at critical accretion.
+    #[config(default = 0.6, min = 0.0, max = 1.0)]
+    pub climb_degradation: f32,
+    /// Fraction of critical accretion at which the object requests a descent.
+    #[config(default = 0.5, min = 0.0, max = 1.0)]
+    pub request_threshold: f32,
+    /// Margin below the bottom of the band to descend to.
+    #[config(default = Length::from_feet(1000.0), min = Length::ZERO, max = Length::from_feet(5000.0))]
+    pub descent_margin:    Length<f32>,
+}
+
+/// A cylindrical volume of icing conditions.
+#[derive(Component)]
+pub struct Band {
+    pub center: Position<Vec2>,
+    pub radius: Length<f32>,
+    pub bottom: Position<f32>,
+    pub top:    Position<f32>,
+}
+
+impl Band {
+    /// Whether the position is within the band.
+    #[must_use]
+    pub fn contains(&self, position: Position<Vec3>) -> bool {
+        (self.bottom..=self.top).contains(&position.altitude())
+            && self.center.distance_cmp(position.horizontal()) < self.radius
+    }
+}
+
+/// Ice accumulated on an object.
+#[derive(Component)]
+pub struct Accretion {
+    /// Fraction of critical accretion, between 0 and 1.
+    pub ice:       f32,
+    /// Expedited climb rate of the object without ice.
+    pub exp_climb: Speed<f32>,
+    /// Standard climb rate of the object without ice.
+    pub std_climb: Speed<f32>,
+    /// Whether the object has already requested a descent for this accretion.
+    pub requested: bool,
+}
+
+fn accretion_system(
+    conf: ReadConfig<Conf>,
+    request_conf: ReadConfig<pilot_request::Conf>,
+    time: Res<Time<time::Virtual>>,
+    band_query: Query<&Band>,
+    object_query: Query<
+        (
+            Entity,
+            &Object,
+            &mut nav::Limits,
+            Option<&mut Accretion>,
+            Option<&hold::Holding>,
+            Option<&pilot_request::RequestList>,
+        ),
+        With<object::Airborne>,
+    >,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+    if time.delta().is_zero() {
+        return;
+    }
+
+    for (object_entity, object, mut limits, accretion, holding, requests) in object_query {
+        let band = band_query.iter().find(|band| band.contains(object.position));
+        let delta = match (band, holding) {
+            (Some(_), Some(_)) => time.delta().div_duration_f32(conf.accretion_time),
+            (Some(_), None) => continue,
+            (None, _) => -time.delta().div_duration_f32(conf.shedding_time),
+        };
+
+        let mut new_accretion = None;
+        let accretion = match accretion {
+            Some(accretion) => accretion.into_inner(),
+            None if delta > 0.0 => new_accretion.insert(Accretion {
+                ice:       0.0,
+                exp_climb: limits.exp_climb.vert_rate,
+                std_climb: limits.std_climb.vert_rate,
+                requested: false,
+            }),
+            None => continue,
+        };
+
+        accretion.ice = (accretion.ice + delta).min(1.0);
+        if accretion.ice <= 0.0 {
+            limits.0.exp_climb.vert_rate = accretion.exp_climb;
+            limits.0.std_climb.vert_rate = accretion.std_climb;
+            commands.entity(object_entity).remove::<Accretion>();
+            continue;
+        }
+
+        let factor = 1.0 - accretion.ice * conf.climb_degradation;
+        limits.0.exp_climb.vert_rate = accretion.exp_climb * factor;
+        limits.0.std_climb.vert_rate = accretion.std_climb * factor;
+
+        if let (Some(band), Some(_)) = (band, holding) {
+            let altitude = band.bottom - conf.descent_margin;
+            if accretion.ice >= 1.0 {
+                descend(&mut commands, object_entity, altitude);
+            } else if accretion.ice >= conf.request_threshold
+                && !accretion.requested
+                && requests.is_none()
+            {
+                accretion.requested = true;
+                commands.spawn((
+                    message::Message {
+                        source:  object_entity,
+                        created: time.elapsed(),
+                        content: format!(
+                            "Picking up ice, requesting descent to {:.0} feet",
+                            altitude.amsl().into_feet()
+                        ),
+                        class:   message::Class::NeedAck,
+                    },
+                    pilot_request::Requester(object_entity),
+                    pilot_request::Request {
+                        body:     pilot_request::Body::Descend { altitude },
+                        deadline: time.elapsed() + request_conf.read().response_timeout,
+                    },
+                ));
+            }
+        }
+
+        if let Some(accretion) = new_accretion {
+            commands.entity(object_entity).insert(accretion);
+        }
+    }
+}
+
+/// Leaves the holding stack and descends below the icing band.
+fn descend(commands: &mut Commands, object: Entity, altitude: Position<f32>) {
+    commands
+        .entity(object)
+        .queue(hold::Override)
+        .insert(nav::TargetAltitude { altitude, expedite: true })
+        .queue(move |mut entity: EntityWorldMut| {
+            if let Some(mut clearance) = entity.get_mut::<pilot::Clearance>() {
+                clearance.altitude = Some(altitude);
+            }
+        });
+    commands.queue(message::SendExpiring {
+        source:   object,
+        content:  format!(
+            "Severe icing, leaving the hold and descending to {:.0} feet",
+            altitude.amsl().into_feet()
+        ),
+        class:    message::Class::Urgent,
+        duration: MESSAGE_DURATION,
+    });
+}
@@ -0,0 +1,21 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+
+use crate::level::icing;
+use crate::load::StoredEntity;
+
+/// Spawns the icing bands declared in a store into the world.
+pub fn spawn_bands(world: &mut World, bands: &[store::IcingBand]) {
+    for band in bands {
+        world.spawn((
+            StoredEntity,
+            Name::new("Icing band"),
+            icing::Band {
+                center: band.center,
+                radius: band.radius,
+                bottom: band.bottom,
+                top:    band.top,
+            },
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Heading, ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Length, Position, Speed};
+use store::YawTarget;
+
+use super::{Accretion, Band};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, NAV_LIMITS, advance};
+use crate::level::{hold, icing, message, nav, pilot_request, score};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        score::Plug,
+        pilot_request::Plug::<()>::default(),
+        icing::Plug::<()>::default(),
+    ));
+    app.world_mut().spawn(Band {
+        center: Position::from_origin_nm(0.0, 0.0),
+        radius: Length::from_nm(10.0),
+        bottom: Position::from_amsl_feet(8000.0),
+        top:    Position::from_amsl_feet(11000.0),
+    });
+    app.update();
+    app
+}
+
+fn spawn_holding(app: &mut App) -> Entity {
+    let altitude = Position::from_amsl_feet(9000.0);
+    let pattern = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0).with_altitude(altitude),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::NORTH),
+                horiz_speed: Speed::from_knots(220.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+            nav::Limits(NAV_LIMITS),
+            hold::Holding { pattern, level: 0, phase: hold::Phase::Inbound },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id()
+}
+
+fn std_climb_fpm(app: &App, object: Entity) -> f32 {
+    app.world().get::<nav::Limits>(object).expect("spawned").std_climb.vert_rate.into_fpm()
+}
+
+#[test]
+fn test_accretion_requests_descent_and_degrades_climb() {
+    let mut app = base_app();
+    let object = spawn_holding(&mut app);
+
+    advance(&mut app, Duration::from_mins(2));
+    let ice = app.world().get::<Accretion>(object).expect("holding in band").ice;
+    assert!((ice - 0.2).abs() < 0.01);
+    assert!((std_climb_fpm(&app, object) - 1500.0 * (1.0 - 0.2 * 0.6)).abs() < 1.0);
+    assert!(!app.world().entity(object).contains::<pilot_request::RequestList>());
+
+    for _ in 0..3 {
+        advance(&mut app, Duration::from_mins(1));
+    }
+    assert!(app.world().entity(object).contains::<pilot_request::RequestList>());
+    assert!(app.world().entity(object).contains::<hold::Holding>());
+
+    app.world_mut().get_mut::<Object>(object).expect("spawned").position =
+        Position::from_origin_nm(0.0, 0.0).with_altitude(Position::from_amsl_feet(7000.0));
+    for _ in 0..4 {
+        advance(&mut app, Duration::from_mins(1));
+    }
+    assert!(!app.world().entity(object).contains::<Accretion>());
+    assert!((std_climb_fpm(&app, object) - 1500.0).abs() < 0.01);
+}
+
+#[test]
+fn test_critical_accretion_leaves_hold() {
+    let mut app = base_app();
+    let object = spawn_holding(&mut app);
+
+    for _ in 0..10 {
+        advance(&mut app, Duration::from_mins(1));
+    }
+
+    assert!(!app.world().entity(object).contains::<hold::Holding>());
+    assert!(app.world().entity(object).contains::<hold::Released>());
+    let target = app.world().get::<nav::TargetAltitude>(object).expect("descending");
+    assert!((target.altitude.amsl().into_feet() - 7000.0).abs() < 1.0);
+    assert!(target.expedite);
+}
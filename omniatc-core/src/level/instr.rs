@@ -1,4 +1,3 @@
-use std::borrow::Cow;
 use std::cmp;
 use std::marker::PhantomData;
 use std::num::NonZero;
@@ -7,23 +6,30 @@ use std::time::Duration;
 use bevy::app::{self, App, Plugin};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
-use bevy::ecs::query::{Has, Without};
+use bevy::ecs::query::{Has, With, Without};
 use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use bevy::ecs::system::{Commands, EntityCommand, EntityCommands, Query, Res, SystemState};
+use bevy::ecs::system::{
+    Command, Commands, EntityCommand, EntityCommands, Query, Res, SystemState,
+};
 use bevy::ecs::world::{EntityWorldMut, FromWorld, World};
 use bevy::time::{self, Time};
 use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
 use itertools::Itertools;
-use math::{Speed, TurnDirection};
+use math::{Position, Speed, TurnDirection};
 use store::YawTarget;
 use wordvec::WordVec;
 
-use super::{SystemSets, nav, route};
+use super::{SystemSets, nav, pilot, route};
+use crate::level::aerodrome::Aerodrome;
 use crate::level::object::Object;
+use crate::level::phraseology::Phrase;
 use crate::level::route::TaxiStopMode;
 use crate::level::waypoint::Waypoint;
-use crate::level::{ground, message, object};
+use crate::level::{
+    bird, divert, equipage, formation, frequency, goaround, ground, hold, message, object,
+    phraseology, transition, transponder, turbulence, vfr,
+};
 use crate::{EntityMutTryLog, EntityTryLog, WorldTryLog};
 
 pub struct Plug<M>(PhantomData<M>);
@@ -43,19 +49,24 @@ where
     }
 }
 
-fn dispatch_system(
+pub(super) fn dispatch_system(
     conf: ReadConfig<Conf>,
+    phraseology_conf: ReadConfig<phraseology::Conf>,
     mut commands: Commands,
     instr_query: Query<
-        (Entity, &Instruction, &Recipient, &TransmitDelay, Option<&DispatchAfter>),
-        Without<PendingAck>,
+        (Entity, &Recipient, &TransmitDelay, Option<&DispatchAfter>),
+        (With<Instruction>, Without<PendingAck>),
     >,
     instr_liveness_query: Query<Has<Instruction>>,
     time: Res<Time<time::Virtual>>,
 ) {
     let conf = conf.read();
+    let phraseology_conf = phraseology_conf.read();
+    let style = phraseology::Style::from(phraseology_conf.style);
+    let readback_duration =
+        phraseology_conf.readback.then_some(conf.message_duration_after_dispatch);
 
-    for (instr_entity, instr, recipient, delay, deps) in instr_query {
+    for (instr_entity, &Recipient(recipient), delay, deps) in instr_query {
         if time.elapsed() < delay.expiry {
             continue;
         }
@@ -68,10 +79,19 @@ fn dispatch_system(
             continue;
         }
 
-        let mut entity = commands.entity(recipient.0);
-        instr.process(&mut entity);
         commands
             .entity(instr_entity)
+            .queue(move |mut entity: EntityWorldMut| {
+                let Some(instr) = entity.take::<Instruction>() else { return };
+                entity.world_scope(|world| {
+                    if let Some(duration) = readback_duration {
+                        send_readback(world, recipient, &instr, style, duration);
+                    }
+                    if let Ok(recipient) = world.get_entity_mut(recipient) {
+                        pilot::acknowledge(recipient, instr);
+                    }
+                });
+            })
             .remove::<(Instruction, Recipient, TransmitDelay, PendingAck, DispatchAfter)>()
             .insert(message::Expiry {
                 expiry: time.elapsed() + conf.message_duration_after_dispatch,
@@ -79,6 +99,31 @@ fn dispatch_system(
     }
 }
 
+/// Sends the readback of `instr` from the recipient object.
+fn send_readback(
+    world: &mut World,
+    recipient: Entity,
+    instr: &Instruction,
+    style: phraseology::Style,
+    duration: Duration,
+) {
+    if world.get_entity(recipient).is_err() {
+        return;
+    }
+    let phrase = instr.phrase(world, recipient);
+    if phrase.is_empty() {
+        return;
+    }
+    let callsign = phraseology::callsign(world, recipient, style);
+    message::SendExpiring {
+        source: recipient,
+        content: phraseology::readback(&callsign, &phrase, style),
+        class: message::Class::VerboseInfo,
+        duration,
+    }
+    .apply(world);
+}
+
 #[derive(Resource)]
 struct MessageSenderId(pub Entity);
 
@@ -123,7 +168,7 @@ pub struct PendingAck;
 pub trait Kind {
     fn process(&self, entity: &mut EntityCommands);
 
-    fn format_message(&self, world: &World, object: Entity) -> String;
+    fn phrase(&self, world: &World, object: Entity) -> Phrase;
 }
 
 #[derive(Component, derive_more::From)]
@@ -138,6 +183,18 @@ pub enum Instruction {
     RemoveStandby(RemoveStandby),
     SelectRoute(SelectRoute),
     AppendSegment(AppendSegment),
+    SkipToWaypoint(SkipToWaypoint),
+    Divert(Divert),
+    GrantFlightFollowing(GrantFlightFollowing),
+    BreakupFormation(BreakupFormation),
+    BirdCaution(BirdCaution),
+    ContactFrequency(ContactFrequency),
+    ClearApproach(ClearApproach),
+    CrossAltitude(CrossAltitude),
+    SetSpeedUntil(SetSpeedUntil),
+    VectorThenResume(VectorThenResume),
+    CrossRunway(CrossRunway),
+    CycleTransponder(CycleTransponder),
 }
 
 pub struct SetHeading {
@@ -146,13 +203,14 @@ pub struct SetHeading {
 
 impl Kind for SetHeading {
     fn process(&self, entity: &mut EntityCommands) {
-        entity.queue(route::PrependStandby);
+        entity.queue(goaround::BreakOff).queue(hold::Override).queue(route::PrependStandby);
         entity.remove::<(
             nav::TargetWaypoint,
             nav::TargetGroundDirection,
             nav::TargetAlignment,
             nav::TargetGlide,
             nav::TargetGlideStatus,
+            nav::ScheduledResume,
         )>();
 
         let target = self.target;
@@ -162,32 +220,35 @@ impl Kind for SetHeading {
         });
     }
 
-    fn format_message(&self, _world: &World, _object: Entity) -> String {
-        match self.target {
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        let (heading, remaining_crosses, direction) = match self.target {
             YawTarget::Heading(heading) => {
-                format!("Fly heading {:03.0} degrees", heading.degrees())
-            }
-            YawTarget::TurnHeading { heading, remaining_crosses: 0, direction } => {
-                format!(
-                    "Turn {} to heading {:.0} degrees",
-                    match direction {
-                        TurnDirection::CounterClockwise => "left",
-                        TurnDirection::Clockwise => "right",
-                    },
-                    heading.degrees(),
-                )
+                return Phrase::default()
+                    .words("fly heading")
+                    .then(phraseology::Element::Heading(heading));
             }
             YawTarget::TurnHeading { heading, remaining_crosses, direction } => {
-                format!(
-                    "Turn {} in {remaining_crosses} circles and then stop at heading {:.0} degrees",
-                    match direction {
-                        TurnDirection::CounterClockwise => "left",
-                        TurnDirection::Clockwise => "right",
-                    },
-                    heading.degrees(),
-                )
+                (heading, remaining_crosses, direction)
             }
+        };
+        let direction = match direction {
+            TurnDirection::CounterClockwise => "left",
+            TurnDirection::Clockwise => "right",
+        };
+        let turn = Phrase::default().words(format!("turn {direction} heading"));
+        let turn = turn.then(phraseology::Element::Heading(heading));
+        if remaining_crosses == 0 {
+            return turn;
         }
+        Phrase::default()
+            .words("make")
+            .then(phraseology::Element::Count(remaining_crosses.into()))
+            .words(format!(
+                "{direction} {}",
+                if remaining_crosses == 1 { "orbit" } else { "orbits" }
+            ))
+            .words("then")
+            .clause(turn)
     }
 }
 
@@ -198,15 +259,20 @@ pub struct SetWaypoint {
 impl Kind for SetWaypoint {
     fn process(&self, entity: &mut EntityCommands) {
         entity
+            .queue(goaround::BreakOff)
+            .queue(hold::Override)
             .queue(route::PrependStandby)
-            .remove::<(nav::TargetAlignment, nav::TargetGlide, nav::TargetGlideStatus)>()
+            .remove::<(
+                nav::TargetAlignment,
+                nav::TargetGlide,
+                nav::TargetGlideStatus,
+                nav::ScheduledResume,
+            )>()
             .insert(nav::TargetWaypoint { waypoint_entity: self.waypoint });
     }
 
-    fn format_message(&self, world: &World, _object: Entity) -> String {
-        let waypoint = world.entity(self.waypoint);
-        let waypoint_name = waypoint.log_get::<Waypoint>().map_or("unknown", |n| n.name.as_str());
-        format!("Proceed direct to {waypoint_name}")
+    fn phrase(&self, world: &World, _object: Entity) -> Phrase {
+        Phrase::default().words("proceed direct").words(waypoint_name(world, self.waypoint))
     }
 }
 
@@ -217,23 +283,25 @@ pub struct SetSpeed {
 impl Kind for SetSpeed {
     fn process(&self, entity: &mut EntityCommands) {
         let target = self.target;
-        entity.queue(move |mut entity: EntityWorldMut| {
+        entity.remove::<route::ConditionalSpeed>().queue(move |mut entity: EntityWorldMut| {
+            if turbulence::refuse_speed_up(&mut entity, target) {
+                return;
+            }
             let Some(mut comp) = entity.log_get_mut::<nav::VelocityTarget>() else { return };
             comp.horiz_speed = target;
         });
     }
 
-    fn format_message(&self, world: &World, object: Entity) -> String {
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
         let object = world.entity(object);
         let current_speed =
             object.log_get::<object::Airborne>().map(|a| a.airspeed.magnitude_cmp());
         let verb = match current_speed {
-            Some(v) if v > self.target => "Reduce speed to",
-            Some(v) if v < self.target => "Increase speed to",
-            Some(_) => "Maintain speed",
-            None => "Change speed to",
+            Some(v) if v > self.target => "reduce speed to",
+            Some(v) if v < self.target => "increase speed to",
+            Some(_) | None => "maintain",
         };
-        format!("{verb} {:.0} knots", self.target.into_knots())
+        Phrase::default().words(verb).then(phraseology::Element::Speed(self.target))
     }
 }
 
@@ -242,22 +310,33 @@ pub struct SetAltitude {
 }
 
 impl Kind for SetAltitude {
-    fn process(&self, entity: &mut EntityCommands) { entity.insert(self.target.clone()); }
+    fn process(&self, entity: &mut EntityCommands) {
+        let target = self.target.clone();
+        entity.queue(move |mut entity: EntityWorldMut| {
+            if !equipage::refuse_altitude(&mut entity, target.altitude) {
+                entity.insert(target);
+            }
+        });
+    }
 
-    fn format_message(&self, world: &World, object: Entity) -> String {
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
         let object = world.entity(object);
         let current_altitude = object.log_get::<Object>().map(|o| o.position.altitude());
-        let verb = match current_altitude {
-            Some(a) => match (a.partial_cmp(&self.target.altitude), self.target.expedite) {
-                (Some(cmp::Ordering::Greater), true) => "Expedite descent to",
-                (Some(cmp::Ordering::Less), true) => "Expedite climb to",
-                (Some(cmp::Ordering::Greater), false) => "Descend to",
-                (Some(cmp::Ordering::Less), false) => "Climb to",
-                _ => "Maintain altitude",
-            },
-            None => "Change altitude to",
+        let vertical = match current_altitude.and_then(|a| a.partial_cmp(&self.target.altitude)) {
+            Some(cmp::Ordering::Greater) => Some(phraseology::Vertical::Descend),
+            Some(cmp::Ordering::Less) => Some(phraseology::Vertical::Climb),
+            _ => None,
         };
-        format!("{verb} {} feet", self.target.altitude.amsl().into_feet())
+        let mut phrase = Phrase::default();
+        if self.target.expedite && vertical.is_some() {
+            phrase = phrase.words("expedite");
+        }
+        phrase = match vertical {
+            Some(vertical) => phrase.then(phraseology::Element::Vertical(vertical)),
+            None => phrase.words("maintain"),
+        };
+        let reading = transition::reading(world, self.target.altitude);
+        phrase.then(phraseology::Element::Altitude(reading))
     }
 }
 
@@ -288,22 +367,21 @@ impl Kind for AirborneVector {
         }
     }
 
-    fn format_message(&self, world: &World, object: Entity) -> String {
-        let mut parts = Vec::new();
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
+        let mut phrase = Phrase::default();
         if let Some(ref cmd) = self.directional {
-            let part = match cmd {
-                AirborneVectorDirectional::SetHeading(cmd) => cmd.format_message(world, object),
-                AirborneVectorDirectional::SetWaypoint(cmd) => cmd.format_message(world, object),
-            };
-            parts.push(part);
+            phrase = phrase.clause(match cmd {
+                AirborneVectorDirectional::SetHeading(cmd) => cmd.phrase(world, object),
+                AirborneVectorDirectional::SetWaypoint(cmd) => cmd.phrase(world, object),
+            });
         }
         if let Some(ref cmd) = self.speed {
-            parts.push(cmd.format_message(world, object));
+            phrase = phrase.clause(cmd.phrase(world, object));
         }
         if let Some(ref cmd) = self.altitude {
-            parts.push(cmd.format_message(world, object));
+            phrase = phrase.clause(cmd.phrase(world, object));
         }
-        parts.join(", ")
+        phrase
     }
 }
 
@@ -314,8 +392,8 @@ impl Kind for ClearRoute {
         entity.queue(route::ClearAllNodes).remove::<route::Id>();
     }
 
-    fn format_message(&self, _world: &World, _object: Entity) -> String {
-        "Cancel clearance for current route".into()
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        Phrase::default().words("cancel route clearance")
     }
 }
 
@@ -328,42 +406,41 @@ impl Kind for RemoveStandby {
         entity.queue(route::RemoveStandby { skip_id: self.skip_id });
     }
 
-    fn format_message(&self, world: &World, object: Entity) -> String {
-        let Some(route) = world.log_get::<route::Route>(object) else { return String::new() };
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
+        let Some(route) = world.log_get::<route::Route>(object) else { return Phrase::default() };
         for (standby, next) in route.iter().tuple_windows() {
             let route::Node::Standby(node) = standby else { continue };
             if node.skip_id != self.skip_id {
                 continue;
             }
 
-            let route_id = world
-                .log_get::<route::Id>(object)
-                .and_then(|id| id.0.as_deref())
-                .unwrap_or("route");
+            let route_id = route_name(world, object);
+            let phrase = Phrase::default();
 
             return match next {
-                route::Node::Standby(_) => "(no clearance)".into(),
-                route::Node::DirectWaypoint(node) => {
-                    let waypoint = world.log_get::<Waypoint>(node.waypoint);
-                    let waypoint_name =
-                        waypoint.map_or("(unknown waypoint)", |waypoint| waypoint.name.as_str());
-                    format!("Proceed direct to {waypoint_name} and continue on {route_id}")
-                }
+                route::Node::Standby(_) => phrase.words("standby"),
+                route::Node::DirectWaypoint(node) => phrase
+                    .words("proceed direct")
+                    .words(waypoint_name(world, node.waypoint))
+                    .words("and continue on")
+                    .words(route_id),
                 route::Node::SetAirSpeed(_) | route::Node::StartSetAltitude(_) => {
-                    format!("Cleared to continue on {route_id}")
+                    phrase.words("continue on").words(route_id)
+                }
+                route::Node::AlignRunway(_) => phrase.words("cleared ILS approach"),
+                route::Node::ShortFinal(_) => phrase.words("reduce to final approach speed"),
+                route::Node::VisualLanding(_) => phrase.words("cleared visual approach"),
+                route::Node::Takeoff(_) => {
+                    phrase.words("cleared for takeoff, departure").words(route_id)
                 }
-                route::Node::AlignRunway(_) => "Cleared for ILS".into(),
-                route::Node::ShortFinal(_) => "Reduce to final".into(),
-                route::Node::VisualLanding(_) => "Cleared for visual approach".into(),
-                route::Node::Takeoff(_) => format!("Cleared for takeoff to SID {route_id}"),
-                route::Node::Taxi(node) => format!(
-                    "Cleared to continue taxi to {}",
-                    node.label.display_segment_label(world)
-                ),
+                route::Node::Taxi(node) => {
+                    phrase.words("continue taxi to").words(node.label.display_segment_label(world))
+                }
+                route::Node::Deice(_) => phrase.words("cleared for de-icing"),
             };
         }
 
-        "(invalid clearance)".into()
+        Phrase::default()
     }
 }
 
@@ -373,13 +450,18 @@ pub struct SelectRoute {
 
 impl Kind for SelectRoute {
     fn process(&self, entity: &mut EntityCommands) {
-        entity
-            .queue(route::ReplaceNodes(self.preset.nodes.clone()))
-            .insert(route::Id(Some(self.preset.id.clone())));
+        let preset = self.preset.clone();
+        entity.queue(move |mut entity: EntityWorldMut| {
+            if equipage::refuse_preset(&mut entity, &preset) {
+                return;
+            }
+            entity.reborrow_scope(|entity| route::ReplaceNodes(preset.nodes).apply(entity));
+            entity.insert(route::Id(Some(preset.id)));
+        });
     }
 
-    fn format_message(&self, _: &World, _: Entity) -> String {
-        format!("Follow {}", &self.preset.title)
+    fn phrase(&self, _: &World, _: Entity) -> Phrase {
+        Phrase::default().words("cleared").words(self.preset.title.clone())
     }
 }
 
@@ -399,12 +481,27 @@ impl Kind for AppendSegment {
         let stop_mode = self.stop_mode;
         entity.queue(move |mut entity: EntityWorldMut| {
             entity.insert_if_new(route::Route::default());
+
+            // Departures lining up for takeoff during winter operations
+            // must be de-iced on the way to the runway.
+            let deice_nodes = if label.is_runway() && stop_mode == TaxiStopMode::LineUp {
+                let route = entity.get::<route::Route>().expect("just inserted");
+                if route::has_deice_node(route) {
+                    None
+                } else {
+                    route::deice_nodes(entity.world(), entity.id())
+                }
+            } else {
+                None
+            };
+
             let mut route = entity.get_mut::<route::Route>().expect("just inserted");
             if let Some(route::Node::Taxi(route::TaxiNode { stop: stop_mode, .. })) =
                 route.last_mut()
             {
                 *stop_mode = TaxiStopMode::Exhaust;
             }
+            route.extend(deice_nodes.into_iter().flatten());
             route.push(route::Node::Taxi(route::TaxiNode {
                 label,
                 direction: None,
@@ -414,16 +511,257 @@ impl Kind for AppendSegment {
         entity.queue(route::RunCurrentNode);
     }
 
-    fn format_message(&self, world: &World, _object: Entity) -> String {
-        let append_message = self.stop_mode.message(self.segment.display_segment_label(world));
+    fn phrase(&self, world: &World, _object: Entity) -> Phrase {
+        let append = Phrase::default()
+            .words(self.stop_mode.verb())
+            .words(self.segment.display_segment_label(world));
         if self.clear_existing {
-            format!("Cancel current path, {append_message}")
+            Phrase::default().words("cancel current taxi route").clause(append)
+        } else {
+            append.words("after current taxi route")
+        }
+    }
+}
+
+pub struct SkipToWaypoint {
+    pub waypoint: Entity,
+}
+
+impl Kind for SkipToWaypoint {
+    fn process(&self, entity: &mut EntityCommands) {
+        entity.queue(route::SkipToWaypoint { waypoint: self.waypoint });
+    }
+
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
+        Phrase::default()
+            .words("proceed direct")
+            .words(waypoint_name(world, self.waypoint))
+            .words("and continue on")
+            .words(route_name(world, object))
+    }
+}
+
+pub struct Divert {
+    pub aerodrome: Entity,
+}
+
+impl Kind for Divert {
+    fn process(&self, entity: &mut EntityCommands) {
+        entity.queue(divert::DivertCommand { aerodrome: self.aerodrome });
+    }
+
+    fn phrase(&self, world: &World, _object: Entity) -> Phrase {
+        let aerodrome = world.log_get::<Aerodrome>(self.aerodrome);
+        let aerodrome_name = aerodrome.map_or("unknown aerodrome", |data| data.name.as_str());
+        Phrase::default().words("divert to").words(aerodrome_name.to_owned())
+    }
+}
+
+pub struct GrantFlightFollowing;
+
+impl Kind for GrantFlightFollowing {
+    fn process(&self, entity: &mut EntityCommands) { entity.insert(vfr::FlightFollowing); }
+
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        Phrase::default()
+            .words("radar contact")
+            .clause(Phrase::default().words("flight following approved"))
+    }
+}
+
+pub struct BreakupFormation;
+
+impl Kind for BreakupFormation {
+    fn process(&self, entity: &mut EntityCommands) { entity.queue(formation::BreakupCommand); }
+
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        Phrase::default().words("break up formation and continue individually")
+    }
+}
+
+pub struct BirdCaution;
+
+impl Kind for BirdCaution {
+    fn process(&self, entity: &mut EntityCommands) { entity.insert(bird::Cautioned); }
+
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        Phrase::default()
+            .words("caution")
+            .clause(Phrase::default().words("bird activity reported in the vicinity"))
+    }
+}
+
+pub struct CycleTransponder;
+
+impl Kind for CycleTransponder {
+    fn process(&self, entity: &mut EntityCommands) {
+        entity.queue(|mut entity: EntityWorldMut| {
+            if entity.contains::<transponder::Failed>() {
+                entity.insert_if_new(transponder::Recycling::default());
+            }
+        });
+    }
+
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        Phrase::default().words("recycle transponder")
+    }
+}
+
+pub struct ContactFrequency {
+    pub frequency: frequency::Frequency,
+}
+
+impl Kind for ContactFrequency {
+    fn process(&self, entity: &mut EntityCommands) {
+        entity.insert(self.frequency).remove::<frequency::HandoffDue>();
+    }
+
+    fn phrase(&self, _world: &World, _object: Entity) -> Phrase {
+        Phrase::default().words("contact").words(self.frequency.to_string())
+    }
+}
+
+pub struct ClearApproach;
+
+impl Kind for ClearApproach {
+    fn process(&self, entity: &mut EntityCommands) { entity.queue(route::ClearApproach); }
+
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
+        let runway = world.log_get::<route::Route>(object).and_then(|route| {
+            route.iter().find_map(|node| match node {
+                route::Node::AlignRunway(node) => world.log_get::<Waypoint>(node.runway),
+                _ => None,
+            })
+        });
+        let approach = if object::Equipage::has(
+            world.entity(object).get::<object::Equipage>(),
+            store::Equipage::ILS,
+        ) {
+            "ILS"
         } else {
-            format!("{append_message} after current path")
+            "visual"
+        };
+        let phrase = Phrase::default().words(format!("cleared {approach} approach"));
+        match runway {
+            Some(runway) => phrase.then(phraseology::Element::Runway(runway.name.clone())),
+            None => phrase,
         }
     }
 }
 
+pub struct CrossAltitude {
+    pub waypoint: Entity,
+    pub altitude: Position<f32>,
+}
+
+impl Kind for CrossAltitude {
+    fn process(&self, entity: &mut EntityCommands) {
+        entity
+            .queue(route::SetCrossingAltitude { waypoint: self.waypoint, altitude: self.altitude });
+    }
+
+    fn phrase(&self, world: &World, _object: Entity) -> Phrase {
+        Phrase::default()
+            .words("cross")
+            .words(waypoint_name(world, self.waypoint))
+            .words("at")
+            .then(phraseology::Element::Altitude(transition::reading(world, self.altitude)))
+    }
+}
+
+pub struct SetSpeedUntil {
+    /// Speed to maintain until the condition is met.
+    pub initial:   SetSpeed,
+    pub condition: route::SpeedCondition,
+    /// Speed to set when the condition is met.
+    pub then:      Speed<f32>,
+}
+
+impl Kind for SetSpeedUntil {
+    fn process(&self, entity: &mut EntityCommands) {
+        self.initial.process(entity);
+        entity.insert(route::ConditionalSpeed { speed: self.then, condition: self.condition });
+    }
+
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
+        let waypoint_name = waypoint_name(world, self.condition.waypoint());
+        let mut phrase = self.initial.phrase(world, object).words("until");
+        if let route::SpeedCondition::Distance { distance, .. } = self.condition {
+            phrase = phrase.then(phraseology::Element::Miles(distance)).words("from");
+        }
+        phrase
+            .words(waypoint_name)
+            .clause(Phrase::default().words("then").then(phraseology::Element::Speed(self.then)))
+    }
+}
+
+pub struct VectorThenResume {
+    pub heading:   SetHeading,
+    pub condition: nav::ResumeCondition,
+    /// The waypoint in the route to proceed direct to when resuming.
+    pub waypoint:  Option<Entity>,
+}
+
+impl Kind for VectorThenResume {
+    fn process(&self, entity: &mut EntityCommands) {
+        self.heading.process(entity);
+        entity.insert(nav::ScheduledResume::new(self.condition, self.waypoint));
+    }
+
+    fn phrase(&self, world: &World, object: Entity) -> Phrase {
+        let expect = match self.waypoint {
+            Some(waypoint) => {
+                Phrase::default().words("expect direct").words(waypoint_name(world, waypoint))
+            }
+            None => Phrase::default().words("expect to resume own navigation"),
+        };
+        let expect = match self.condition {
+            nav::ResumeCondition::Distance(distance) => {
+                expect.words("in").then(phraseology::Element::Miles(distance))
+            }
+            nav::ResumeCondition::Abeam(fix) => {
+                expect.words("abeam").words(waypoint_name(world, fix))
+            }
+            nav::ResumeCondition::Time(duration) => {
+                let minutes = u32::try_from(duration.as_secs().div_ceil(60)).unwrap_or(u32::MAX);
+                expect
+                    .words("in")
+                    .then(phraseology::Element::Count(minutes))
+                    .words(if minutes == 1 { "minute" } else { "minutes" })
+            }
+        };
+        self.heading.phrase(world, object).clause(expect)
+    }
+}
+
+pub struct CrossRunway {
+    /// Either runway entity of the runway pair to cross.
+    pub runway: Entity,
+}
+
+impl Kind for CrossRunway {
+    fn process(&self, entity: &mut EntityCommands) {
+        let runway = self.runway;
+        entity.queue(move |mut entity: EntityWorldMut| {
+            let Some(mut ground) = entity.log_get_mut::<object::OnGround>() else { return };
+            ground.crossing = object::RunwayCrossing::Cleared { runway };
+        });
+    }
+
+    fn phrase(&self, world: &World, _object: Entity) -> Phrase {
+        let runway = waypoint_name(world, self.runway);
+        Phrase::default().words("cross").then(phraseology::Element::Runway(runway))
+    }
+}
+
+fn waypoint_name(world: &World, waypoint: Entity) -> String {
+    world.log_get::<Waypoint>(waypoint).map_or_else(|| "unknown".into(), |w| w.name.clone())
+}
+
+fn route_name(world: &World, object: Entity) -> String {
+    world.log_get::<route::Id>(object).and_then(|id| id.0.clone()).unwrap_or_else(|| "route".into())
+}
+
 pub struct SpawnCommand {
     pub object: Entity,
     pub body:   Instruction,
@@ -439,13 +777,9 @@ impl EntityCommand for SpawnCommand {
         let current_time = entity.world().resource::<Time<time::Virtual>>().elapsed();
         let sender = entity.world().resource::<MessageSenderId>().0;
 
-        let object_display = entity.world().log_get::<object::Display>(self.object);
-        let object_name = object_display.map_or_else(
-            || Cow::Owned(format!("object#{:?}", self.object)),
-            |disp| Cow::Borrowed(disp.name.as_str()),
-        );
-
-        let message_content = self.body.format_message(entity.world(), self.object);
+        let style = entity.world_scope(phraseology::current_style);
+        let callsign = phraseology::callsign(entity.world(), self.object, style);
+        let phrase = self.body.phrase(entity.world(), self.object);
         entity.insert((
             self.body,
             Recipient(self.object),
@@ -454,7 +788,7 @@ impl EntityCommand for SpawnCommand {
                 class:   message::Class::Outgoing,
                 source:  sender,
                 created: current_time,
-                content: format!("{object_name}, {message_content}"),
+                content: phraseology::address(&callsign, &phrase, style),
             },
         ));
     }
@@ -69,6 +69,12 @@ pub struct Sender {
     pub display: String,
 }
 
+/// The radio telephony callsign of a [`Sender`], e.g. `Speedbird 123` for `BAW123`.
+///
+/// Senders without this component are addressed by their display name.
+#[derive(Component)]
+pub struct Telephony(pub String);
+
 pub struct SendExpiring {
     pub source:   Entity,
     pub content:  String,
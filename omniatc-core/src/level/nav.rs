@@ -8,7 +8,8 @@ use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::QueryData;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use bevy::ecs::system::{Query, Res};
+use bevy::ecs::system::{Command, Commands, EntityCommand, Query, Res};
+use bevy::ecs::world::EntityWorldMut;
 use bevy::math::Vec2;
 use bevy::time::{self, Time};
 use math::{
@@ -19,9 +20,9 @@ use store::{ClimbProfile, YawTarget};
 
 use super::object::Object;
 use super::waypoint::Waypoint;
-use super::{SystemSets, navaid, object};
-use crate::QueryTryLog;
+use super::{SystemSets, message, navaid, object, pilot, route};
 use crate::level::weather;
+use crate::{QueryTryLog, WorldTryLog};
 
 #[cfg(test)]
 mod tests;
@@ -36,6 +37,7 @@ impl Plugin for Plug {
             glide_control_system.after(altitude_control_system).in_set(SystemSets::Navigate),
         );
         app.add_systems(app::Update, ground_heading_control_system.in_set(SystemSets::Navigate));
+        app.add_systems(app::Update, scheduled_resume_system.in_set(SystemSets::Navigate));
         app.add_systems(
             app::Update,
             (waypoint_control_system, alignment_control_system)
@@ -123,38 +125,67 @@ pub struct TargetAltitude {
 
 fn altitude_control_system(
     time: Res<Time<time::Virtual>>,
-    mut query: Query<(&TargetAltitude, &Object, &Limits, &object::Airborne, &mut VelocityTarget)>,
+    mut query: Query<(
+        &TargetAltitude,
+        &Object,
+        &Limits,
+        &object::Airborne,
+        &mut VelocityTarget,
+        Option<&pilot::Response>,
+    )>,
 ) {
     if time.is_paused() {
         return;
     }
 
     query.par_iter_mut().for_each(
-        |(altitude, &Object { position, .. }, limits, airborne, mut target)| {
-            let (max_speed, min_speed) = if altitude.expedite {
-                (limits.exp_climb.vert_rate, limits.exp_descent.vert_rate)
-            } else {
-                (limits.std_climb.vert_rate, limits.std_descent.vert_rate)
-            };
-
-            let setpoint = linear_speed_setpoint(LinearSpeedSetpoint {
-                deviation: position.altitude() - altitude.altitude,
-                current_speed: airborne.true_airspeed.vertical(),
-                max_forward_accel: limits.max_vert_accel,
-                max_forward_brake: limits.max_vert_accel,
-                max_backward_accel: limits.max_vert_accel,
-                max_backward_brake: limits.max_vert_accel,
-                max_speed,
-                min_speed,
-                dt: time.delta(),
-            });
-
-            target.vert_rate = setpoint;
+        |(altitude, &Object { position, .. }, limits, airborne, mut target, response)| {
+            target.vert_rate = altitude_setpoint(
+                altitude,
+                limits,
+                response,
+                position.altitude(),
+                airborne.true_airspeed.vertical(),
+                time.delta(),
+            );
             target.expedite = altitude.expedite;
         },
     );
 }
 
+/// Computes the vertical rate to capture `altitude`
+/// from `current_altitude` and `current_vert_rate`,
+/// held for a control step of duration `dt`.
+#[must_use]
+pub fn altitude_setpoint(
+    altitude: &TargetAltitude,
+    limits: &Limits,
+    response: Option<&pilot::Response>,
+    current_altitude: Position<f32>,
+    current_vert_rate: Speed<f32>,
+    dt: Duration,
+) -> Speed<f32> {
+    let (max_speed, min_speed) = if altitude.expedite {
+        (limits.exp_climb.vert_rate, limits.exp_descent.vert_rate)
+    } else {
+        (limits.std_climb.vert_rate, limits.std_descent.vert_rate)
+    };
+
+    // A sloppy pilot overestimates the available braking and levels off late.
+    let brake = limits.max_vert_accel * response.map_or(1.0, |response| response.altitude_capture);
+    linear_speed_setpoint(LinearSpeedSetpoint {
+        deviation: current_altitude - altitude.altitude,
+        current_speed: current_vert_rate,
+        max_forward_accel: limits.max_vert_accel,
+        max_forward_brake: brake,
+        max_backward_accel: limits.max_vert_accel,
+        max_backward_brake: brake,
+        max_speed,
+        min_speed,
+        dt,
+    })
+}
+
 /// Pitch towards a glidepath of depression angle `glide_angle` towards `target_waypoint`,
 /// without pitching beyond `min_pitch` (usually negative) and `max_pitch` (usually zero).
 /// until the angle of depression from the object to `target_waypoint`
@@ -311,7 +342,11 @@ fn ground_heading_control_system(
 
             let gs = (-b + discrim.sqrt()) * 0.5;
             let target_tas_vector = gs * data.objective.target - wind;
-            data.signal.yaw = YawTarget::Heading(target_tas_vector.heading());
+            // The vector is zero if the wind alone achieves the target ground speed,
+            // in which case any heading works and the current target is retained.
+            if let Some(heading) = target_tas_vector.try_heading() {
+                data.signal.yaw = YawTarget::Heading(heading);
+            }
         }
     });
 }
@@ -336,10 +371,143 @@ fn waypoint_control_system(
 
     object_query.par_iter_mut().for_each(|(mut ground_dir, waypoint, &Object { position, .. })| {
         let Some(waypoint_pos) = waypoint_query.log_get(waypoint.waypoint_entity) else { return };
-        ground_dir.target = (waypoint_pos.position.horizontal() - position.horizontal()).heading();
+        // Retain the previous direction when exactly above the waypoint.
+        if let Some(heading) =
+            (waypoint_pos.position.horizontal() - position.horizontal()).try_heading()
+        {
+            ground_dir.target = heading;
+        }
     });
 }
 
+/// Resumes the route after a temporary vector once `condition` is met.
+///
+/// The vector is cancelled and this component is removed
+/// if the route is no longer on the standby node inserted by the vector.
+#[derive(Component)]
+pub struct ScheduledResume {
+    pub condition: ResumeCondition,
+    /// The waypoint in the route to proceed direct to when resuming.
+    ///
+    /// The route resumes from the node after the standby if this is `None`
+    /// or the waypoint is not in the route.
+    pub waypoint:  Option<Entity>,
+    /// Distance flown since the vector was issued.
+    pub flown:     Length<f32>,
+    /// Time elapsed since the vector was issued.
+    pub elapsed:   Duration,
+}
+
+impl ScheduledResume {
+    #[must_use]
+    pub fn new(condition: ResumeCondition, waypoint: Option<Entity>) -> Self {
+        Self { condition, waypoint, flown: Length::ZERO, elapsed: Duration::ZERO }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResumeCondition {
+    /// The object has flown the given ground distance.
+    Distance(Length<f32>),
+    /// The given waypoint is abeam or behind the object.
+    Abeam(Entity),
+    /// The given duration has elapsed.
+    Time(Duration),
+}
+
+fn scheduled_resume_system(
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<(
+        Entity,
+        &Object,
+        &mut ScheduledResume,
+        Option<&route::Route>,
+        Option<&mut pilot::Clearance>,
+    )>,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    if time.is_paused() {
+        return;
+    }
+
+    for (entity, object, mut resume, route, clearance) in object_query {
+        let on_vector = route.is_some_and(|route| {
+            matches!(
+                route.current(),
+                Some(route::Node::Standby(route::StandbyNode { skip_id: None }))
+            )
+        });
+        if !on_vector {
+            commands.entity(entity).remove::<ScheduledResume>();
+            continue;
+        }
+
+        let ground_speed = object.ground_speed.horizontal();
+        resume.elapsed += time.delta();
+        resume.flown += ground_speed.magnitude_exact() * time.delta();
+
+        let met = match resume.condition {
+            ResumeCondition::Distance(distance) => resume.flown >= distance,
+            ResumeCondition::Abeam(fix) => {
+                let Some(fix) = waypoint_query.log_get(fix) else { continue };
+                (fix.position.horizontal() - object.position.horizontal()).dot(ground_speed.0)
+                    <= 0.0
+            }
+            ResumeCondition::Time(duration) => resume.elapsed >= duration,
+        };
+        if !met {
+            continue;
+        }
+
+        if let Some(mut clearance) = clearance {
+            clearance.heading = None;
+        }
+        commands
+            .entity(entity)
+            .remove::<ScheduledResume>()
+            .queue(ResumeRoute { waypoint: resume.waypoint });
+    }
+}
+
+/// Leaves the standby node of a vector,
+/// skipping to `waypoint` if it is in the route.
+struct ResumeRoute {
+    waypoint: Option<Entity>,
+}
+
+impl EntityCommand for ResumeRoute {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let object_id = entity.id();
+        let waypoint = self.waypoint.filter(|&waypoint| {
+            entity.get::<route::Route>().is_some_and(|route| {
+                route.iter().any(|node| {
+                    matches!(node, route::Node::DirectWaypoint(node) if node.waypoint == waypoint)
+                })
+            })
+        });
+
+        let content = if let Some(waypoint) = waypoint {
+            entity.reborrow_scope(|entity| route::SkipToWaypoint { waypoint }.apply(entity));
+            let waypoint_name =
+                entity.world().log_get::<Waypoint>(waypoint).map_or("unknown", |w| w.name.as_str());
+            format!("Resuming own navigation direct {waypoint_name}")
+        } else {
+            entity.reborrow_scope(|entity| route::RemoveStandby { skip_id: None }.apply(entity));
+            "Resuming own navigation".into()
+        };
+        entity.world_scope(|world| {
+            message::SendExpiring {
+                source: object_id,
+                content,
+                class: message::Class::VerboseInfo,
+                duration: Duration::from_secs(10),
+            }
+            .apply(world);
+        });
+    }
+}
+
 /// Maintain the current heading until the line segment between `start_waypoint` and `end_waypoint`
 /// is within the circle of radius `ground_speed * lookahead` around the object.
 /// The object is then set to direct towards the closest point in the circle
@@ -468,9 +636,11 @@ fn alignment_control_system(
                 )
             };
 
-            if let TargetAlignmentActivationStatus::PurePursuit(high_pos) = activation_status {
+            if let TargetAlignmentActivationStatus::PurePursuit(high_pos) = activation_status
+                && let Some(target) = (high_pos - position).try_heading()
+            {
                 signal.active = true;
-                signal.target = (high_pos - position).heading();
+                signal.target = target;
             } else {
                 // too far from path, maintain current heading
                 signal.active = false;
@@ -478,7 +648,10 @@ fn alignment_control_system(
 
             status.activation = activation_status;
             status.orthogonal_deviation = ortho_dist;
-            status.angular_deviation = (end - position).heading() - (end - start).heading();
+            status.angular_deviation = match (end - position).try_heading() {
+                Some(heading) => heading - (end - start).heading(),
+                None => Angle::ZERO,
+            };
         },
     );
 }
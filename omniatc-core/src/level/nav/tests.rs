@@ -1,59 +1,23 @@
 use std::mem;
 use std::time::Duration;
 
-use bevy::app::App;
+use bevy::app::{self, App};
 use bevy::ecs::entity::Entity;
+use bevy::ecs::system::EntityCommand;
 use bevy::math::bounding::Aabb2d;
-use bevy::math::{Quat, Vec2};
+use bevy::math::{Quat, Vec2, Vec3};
 use bevy::time::{self, Time};
 use math::{
-    Accel, AccelRate, Angle, AngularAccel, AngularSpeed, Heading, ISA_TROPOPAUSE_PRESSURE,
-    ISA_TROPOPAUSE_TEMPERATURE, Length, Position, Speed,
+    Angle, Heading, ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Length, Position, Speed,
+    TurnDirection,
 };
-use store::{NavLimits, YawTarget};
+use store::{WaypointProximity, YawTarget};
 
 use crate::level::object::{self, Object};
 use crate::level::runway::Runway;
+use crate::level::test_util::NAV_LIMITS;
 use crate::level::waypoint::{self, Waypoint};
-use crate::level::{SystemSets, aerodrome, nav, plane, runway, weather};
-
-const NAV_LIMITS: NavLimits = NavLimits {
-    min_horiz_speed:   Speed::from_knots(120.),
-    max_yaw_speed:     AngularSpeed::from_degrees_per_sec(3.),
-    max_vert_accel:    Accel::from_fpm_per_sec(200.),
-    exp_climb:         store::ClimbProfile {
-        vert_rate: Speed::from_fpm(3000.),
-        accel:     Accel::from_knots_per_sec(0.2),
-        decel:     Accel::from_knots_per_sec(-1.8),
-    },
-    std_climb:         store::ClimbProfile {
-        vert_rate: Speed::from_fpm(1500.),
-        accel:     Accel::from_knots_per_sec(0.6),
-        decel:     Accel::from_knots_per_sec(-1.4),
-    },
-    level:             store::ClimbProfile {
-        vert_rate: Speed::from_fpm(0.),
-        accel:     Accel::from_knots_per_sec(1.),
-        decel:     Accel::from_knots_per_sec(-1.),
-    },
-    std_descent:       store::ClimbProfile {
-        vert_rate: Speed::from_fpm(-1500.),
-        accel:     Accel::from_knots_per_sec(1.4),
-        decel:     Accel::from_knots_per_sec(-0.6),
-    },
-    exp_descent:       store::ClimbProfile {
-        vert_rate: Speed::from_fpm(-3000.),
-        accel:     Accel::from_knots_per_sec(1.8),
-        decel:     Accel::from_knots_per_sec(-0.2),
-    },
-    weight:            1e5,
-    accel_change_rate: AccelRate::from_knots_per_sec2(0.3),
-    drag_coef:         3. / 500. / 500.,
-    max_yaw_accel:     AngularAccel::from_degrees_per_sec2(1.),
-    takeoff_speed:     Speed::from_knots(150.),
-    short_final_dist:  Length::from_nm(4.),
-    short_final_speed: Speed::from_knots(150.),
-};
+use crate::level::{SystemSets, aerodrome, nav, pilot, plane, route, runway, weather};
 
 struct Entities {
     object: Entity,
@@ -501,3 +465,271 @@ fn test_target_waypoint() {
             .expect("heading towards runway");
     }
 }
+
+/// A target heading exactly behind the object is reached by a turn in one direction,
+/// without the yaw speed reversing midway.
+#[test]
+fn test_reversal_turn() {
+    let (mut app, entities) = base_world();
+
+    app.world_mut().get_mut::<nav::VelocityTarget>(entities.object).unwrap().yaw =
+        YawTarget::Heading(Heading::SOUTH);
+
+    let mut direction = None;
+    for _ in 0..120 {
+        advance_world(&mut app, Duration::from_millis(500));
+
+        let yaw_speed = app.world().get::<plane::Control>(entities.object).unwrap().yaw_speed;
+        let heading = app
+            .world()
+            .get::<object::Airborne>(entities.object)
+            .unwrap()
+            .airspeed
+            .horizontal()
+            .heading();
+        if heading.closest_distance(Heading::SOUTH).abs() < Angle::from_degrees(10.0) {
+            break;
+        }
+
+        if yaw_speed.is_zero() {
+            continue;
+        }
+        let current = yaw_speed.is_positive();
+        assert_eq!(*direction.get_or_insert(current), current, "yaw speed reversed at {heading:?}");
+    }
+
+    app.world()
+        .get::<object::Airborne>(entities.object)
+        .unwrap()
+        .airspeed
+        .horizontal()
+        .heading()
+        .assert_approx(Heading::SOUTH, Angle::from_degrees(10.0))
+        .expect("completes the reversal turn");
+}
+
+/// An object at the origin heading east at 360 knots,
+/// vectored off a route via `FIRST` and `SECOND` to the north.
+fn vectored_world() -> (App, Entity, [Entity; 2]) {
+    let mut app = App::new();
+    app.add_systems(app::Update, super::scheduled_resume_system);
+    app.init_resource::<Time<time::Virtual>>();
+
+    let waypoints = [("FIRST", 10.0), ("SECOND", 20.0)].map(|(name, north_nm)| {
+        spawn_waypoint(&mut app, name, Position::from_origin_nm(0.0, north_nm))
+    });
+
+    let mut route = route::Route::default();
+    route.push(route::StandbyNode { skip_id: None }.into());
+    for waypoint in waypoints {
+        route.push(
+            route::DirectWaypointNode {
+                waypoint,
+                distance: Length::from_nm(1.0),
+                proximity: WaypointProximity::FlyOver,
+                altitude: None,
+            }
+            .into(),
+        );
+    }
+
+    let object = app
+        .world_mut()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(Position::from_amsl_feet(3000.0)),
+                ground_speed: (Speed::from_knots(360.0) * Heading::EAST).horizontally(),
+            },
+            route,
+            pilot::Clearance { heading: Some(Heading::EAST), ..Default::default() },
+        ))
+        .id();
+    (app, object, waypoints)
+}
+
+fn spawn_waypoint(app: &mut App, name: &str, position: Position<Vec2>) -> Entity {
+    app.world_mut()
+        .spawn(Waypoint {
+            name:         name.into(),
+            position:     position.with_altitude(AERODROME_ELEVATION),
+            display_type: waypoint::DisplayType::Waypoint,
+            hidden:       false,
+        })
+        .id()
+}
+
+fn current_waypoint(app: &App, object: Entity) -> Option<Entity> {
+    match app.world().get::<route::Route>(object)?.current()? {
+        route::Node::DirectWaypoint(node) => Some(node.waypoint),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_scheduled_resume_after_distance() {
+    let (mut app, object, waypoints) = vectored_world();
+    app.world_mut().entity_mut(object).insert(nav::ScheduledResume::new(
+        nav::ResumeCondition::Distance(Length::from_nm(5.0)),
+        Some(waypoints[1]),
+    ));
+
+    // 0.1 nm/s for 30 s is 3 nm.
+    advance_world(&mut app, Duration::from_secs(30));
+    assert_eq!(current_waypoint(&app, object), None);
+    assert!(app.world().get::<nav::ScheduledResume>(object).is_some());
+
+    advance_world(&mut app, Duration::from_secs(30));
+    assert_eq!(current_waypoint(&app, object), Some(waypoints[1]));
+    assert!(app.world().get::<nav::ScheduledResume>(object).is_none());
+    let clearance = app.world().get::<pilot::Clearance>(object).expect("spawned");
+    assert!(clearance.heading.is_none());
+}
+
+#[test]
+fn test_scheduled_resume_abeam_fix() {
+    let (mut app, object, waypoints) = vectored_world();
+    let fix = spawn_waypoint(&mut app, "ABEAM", Position::from_origin_nm(5.0, -3.0));
+    app.world_mut()
+        .entity_mut(object)
+        .insert(nav::ScheduledResume::new(nav::ResumeCondition::Abeam(fix), None));
+
+    advance_world(&mut app, Duration::from_secs(1));
+    assert_eq!(current_waypoint(&app, object), None);
+
+    app.world_mut().get_mut::<Object>(object).expect("spawned").position =
+        Position::from_origin_nm(5.5, 0.0).with_altitude(Position::from_amsl_feet(3000.0));
+    advance_world(&mut app, Duration::from_secs(1));
+    // Without a resume waypoint, the route continues after the vector.
+    assert_eq!(current_waypoint(&app, object), Some(waypoints[0]));
+}
+
+#[test]
+fn test_scheduled_resume_cancelled_by_route_change() {
+    let (mut app, object, waypoints) = vectored_world();
+    app.world_mut().entity_mut(object).insert(nav::ScheduledResume::new(
+        nav::ResumeCondition::Time(Duration::from_mins(5)),
+        Some(waypoints[1]),
+    ));
+
+    route::RemoveStandby { skip_id: None }.apply(app.world_mut().entity_mut(object));
+    advance_world(&mut app, Duration::from_secs(1));
+
+    assert!(app.world().get::<nav::ScheduledResume>(object).is_none());
+    assert_eq!(current_waypoint(&app, object), Some(waypoints[0]));
+}
+
+/// Frame duration at 1x speed and 64 frames per second.
+///
+/// Durations are chosen to be exact in nanoseconds
+/// so that samples at different frame rates are taken at the same simulated time.
+const FRAME_1X: Duration = Duration::from_micros(15_625);
+
+struct TrajectorySample {
+    position: Position<Vec3>,
+    airspeed: Speed<Vec3>,
+}
+
+/// Flies the object from [`base_world`] after `setup` for `duration` with frames of `dt`,
+/// sampling the trajectory every second.
+fn fly_trajectory(
+    dt: Duration,
+    duration: Duration,
+    setup: impl FnOnce(&mut App, Entity),
+) -> Vec<TrajectorySample> {
+    let (mut app, entities) = base_world();
+    setup(&mut app, entities.object);
+
+    let frames_per_sample = Duration::from_secs(1).as_nanos() / dt.as_nanos();
+    let mut samples = Vec::new();
+    for _ in 0..duration.as_secs() {
+        for _ in 0..frames_per_sample {
+            advance_world(&mut app, dt);
+        }
+        let object = app.world().get::<Object>(entities.object).unwrap();
+        let airborne = app.world().get::<object::Airborne>(entities.object).unwrap();
+        samples.push(TrajectorySample { position: object.position, airspeed: airborne.airspeed });
+    }
+    samples
+}
+
+/// Asserts that the maneuver configured by `setup` flies the same trajectory
+/// at 1x speed, at 8x speed, and at 1x speed with a low frame rate.
+fn assert_time_scale_independent(duration: Duration, setup: impl Fn(&mut App, Entity)) {
+    let baseline = fly_trajectory(FRAME_1X, duration, &setup);
+    for (label, dt) in [("8x", FRAME_1X * 8), ("16 fps", FRAME_1X * 4)] {
+        let trajectory = fly_trajectory(dt, duration, &setup);
+        for (second, (expected, actual)) in baseline.iter().zip(&trajectory).enumerate() {
+            actual
+                .position
+                .horizontal()
+                .assert_near(expected.position.horizontal(), Length::from_meters(5.0))
+                .unwrap_or_else(|err| panic!("{label} horizontal position at {second}s: {err}"));
+            actual
+                .position
+                .altitude()
+                .assert_near(expected.position.altitude(), Length::from_feet(5.0))
+                .unwrap_or_else(|err| panic!("{label} altitude at {second}s: {err}"));
+            actual
+                .airspeed
+                .horizontal()
+                .assert_near(expected.airspeed.horizontal(), Speed::from_knots(0.5))
+                .unwrap_or_else(|err| panic!("{label} airspeed at {second}s: {err}"));
+            actual
+                .airspeed
+                .vertical()
+                .assert_near(expected.airspeed.vertical(), Speed::from_fpm(50.0))
+                .unwrap_or_else(|err| panic!("{label} vertical rate at {second}s: {err}"));
+        }
+    }
+}
+
+#[test]
+fn test_time_scale_heading_capture() {
+    assert_time_scale_independent(Duration::from_mins(1), |app, object| {
+        app.world_mut().get_mut::<nav::VelocityTarget>(object).unwrap().yaw =
+            YawTarget::Heading(Heading::EAST);
+    });
+}
+
+#[test]
+fn test_time_scale_turn_through_heading() {
+    assert_time_scale_independent(Duration::from_secs(150), |app, object| {
+        app.world_mut().get_mut::<nav::VelocityTarget>(object).unwrap().yaw =
+            YawTarget::TurnHeading {
+                heading:           Heading::EAST,
+                remaining_crosses: 0,
+                direction:         TurnDirection::CounterClockwise,
+            };
+    });
+}
+
+#[test]
+fn test_time_scale_altitude_capture() {
+    assert_time_scale_independent(Duration::from_mins(3), |app, object| {
+        app.world_mut().entity_mut(object).insert(nav::TargetAltitude {
+            altitude: Position::from_amsl_feet(6000.0),
+            expedite: true,
+        });
+    });
+}
+
+#[test]
+fn test_time_scale_speed_capture() {
+    assert_time_scale_independent(Duration::from_mins(2), |app, object| {
+        app.world_mut().get_mut::<nav::VelocityTarget>(object).unwrap().horiz_speed =
+            Speed::from_knots(250.0);
+    });
+}
+
+#[test]
+fn test_time_scale_combined_maneuver() {
+    assert_time_scale_independent(Duration::from_mins(3), |app, object| {
+        app.world_mut().entity_mut(object).insert(nav::TargetAltitude {
+            altitude: Position::from_amsl_feet(1500.0),
+            expedite: false,
+        });
+        let mut target = app.world_mut().get_mut::<nav::VelocityTarget>(object).unwrap();
+        target.yaw = YawTarget::Heading(Heading::WEST);
+        target.horiz_speed = Speed::from_knots(160.0);
+    });
+}
@@ -145,7 +145,12 @@ pub struct Visual {
     /// Maximum visual range to see the runway.
     ///
     /// The actual visual range is the minimum of this value and the actual visibility.
-    pub max_range: Length<f32>,
+    /// The range is further reduced after dark by the [scenario clock](super::clock).
+    pub max_range:      Length<f32>,
+    /// Runway visual range imposed by a [fog bank](super::fog), if any.
+    ///
+    /// The actual visual range never exceeds this value.
+    pub obscured_range: Option<Length<f32>>,
 }
 
 /// Marks that the navaid entity has an ILS critical region subject to ground interference.
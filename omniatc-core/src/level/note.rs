@@ -0,0 +1,118 @@
+//! Controller notes and reminders attached to objects.
+//!
+//! An object with [`Notes`] carries a free-text scratchpad and a list of timed [`Reminder`]s.
+//! When a reminder is due, it is removed from the object
+//! and surfaces as a message from the object.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+
+use super::SystemSets;
+use crate::level::message;
+
+#[cfg(test)]
+mod tests;
+
+/// Duration for which a due reminder message is displayed.
+const REMINDER_MESSAGE_DURATION: Duration = Duration::from_mins(2);
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(app::Update, remind_system.in_set(SystemSets::Communicate));
+    }
+}
+
+/// Notes attached to an object by the controller.
+#[derive(Component, Default)]
+pub struct Notes {
+    /// Free-text scratchpad.
+    pub scratchpad: String,
+    /// Pending reminders, in no particular order.
+    pub reminders:  Vec<Reminder>,
+}
+
+pub struct Reminder {
+    /// Text of the reminder.
+    pub text: String,
+    /// The virtual time elapsed when the reminder is due.
+    pub due:  Duration,
+}
+
+impl Notes {
+    /// Whether the notes contain no information and need not be displayed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.scratchpad.trim().is_empty() && self.reminders.is_empty()
+    }
+
+    /// Returns the reminder that is due first.
+    #[must_use]
+    pub fn next_reminder(&self) -> Option<&Reminder> {
+        self.reminders.iter().min_by_key(|reminder| reminder.due)
+    }
+
+    #[must_use]
+    pub fn from_store(notes: &store::Notes, elapsed: Duration) -> Self {
+        Self {
+            scratchpad: notes.scratchpad.clone(),
+            reminders:  notes
+                .reminders
+                .iter()
+                .map(|reminder| Reminder {
+                    text: reminder.text.clone(),
+                    due:  elapsed + reminder.remaining,
+                })
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_store(&self, elapsed: Duration) -> store::Notes {
+        store::Notes {
+            scratchpad: self.scratchpad.clone(),
+            reminders:  self
+                .reminders
+                .iter()
+                .map(|reminder| store::Reminder {
+                    text:      reminder.text.clone(),
+                    remaining: reminder.due.saturating_sub(elapsed),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn remind_system(
+    time: Res<Time<time::Virtual>>,
+    object_query: Query<(Entity, &mut Notes)>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+
+    for (object, mut notes) in object_query {
+        if !notes.reminders.iter().any(|reminder| reminder.due <= now) {
+            continue;
+        }
+
+        let (due, pending) =
+            notes.reminders.drain(..).partition::<Vec<_>, _>(|reminder| reminder.due <= now);
+        notes.reminders = pending;
+
+        for reminder in due {
+            commands.queue(message::SendExpiring {
+                source:   object,
+                content:  format!("Reminder: {}", reminder.text),
+                class:    message::Class::NeedAck,
+                duration: REMINDER_MESSAGE_DURATION,
+            });
+        }
+    }
+}
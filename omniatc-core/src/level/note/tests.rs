@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+
+use super::{Notes, Reminder};
+use crate::level::test_util::{self, advance};
+use crate::level::{message, note};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, note::Plug));
+    app.update();
+    app
+}
+
+fn reminder_messages(app: &mut App, object: Entity) -> Vec<String> {
+    let mut query = app.world_mut().query::<&message::Message>();
+    query
+        .iter(app.world())
+        .filter(|message| message.source == object)
+        .map(|message| message.content.clone())
+        .collect()
+}
+
+#[test]
+fn reminder_surfaces_when_due() {
+    let mut app = base_app();
+    let object = app
+        .world_mut()
+        .spawn(Notes {
+            scratchpad: "expect ILS 18L".into(),
+            reminders:  vec![
+                Reminder { text: "descend".into(), due: Duration::from_mins(2) },
+                Reminder { text: "handover".into(), due: Duration::from_mins(5) },
+            ],
+        })
+        .id();
+
+    advance(&mut app, Duration::from_mins(1));
+    assert!(reminder_messages(&mut app, object).is_empty());
+
+    advance(&mut app, Duration::from_mins(1));
+    assert_eq!(reminder_messages(&mut app, object), ["Reminder: descend"]);
+
+    let notes = app.world().get::<Notes>(object).expect("notes are retained");
+    assert_eq!(notes.reminders.len(), 1);
+    assert_eq!(notes.reminders[0].text, "handover");
+    assert_eq!(notes.scratchpad, "expect ILS 18L");
+}
+
+#[test]
+fn store_roundtrip_preserves_remaining_time() {
+    let notes = Notes {
+        scratchpad: String::new(),
+        reminders:  vec![Reminder { text: "call back".into(), due: Duration::from_mins(10) }],
+    };
+
+    let stored = notes.to_store(Duration::from_mins(4));
+    assert_eq!(stored.reminders[0].remaining, Duration::from_mins(6));
+
+    let loaded = Notes::from_store(&stored, Duration::from_mins(1));
+    assert_eq!(loaded.reminders[0].due, Duration::from_mins(7));
+}
@@ -31,8 +31,9 @@ use crate::try_log::EntityWorldMutExt;
 use crate::{QueryTryLog, WorldTryLog};
 
 pub mod loader;
+pub mod saver;
 pub mod types;
-pub use types::Type;
+pub use types::{Type, TypeRef};
 
 #[cfg(test)]
 mod tests;
@@ -78,6 +79,11 @@ pub struct Display {
     pub name: String,
 }
 
+/// Stable identifier assigned to an object by the scenario,
+/// resolved through [`loader::ObjectMap`].
+#[derive(Component)]
+pub struct ScenarioId(pub store::ObjectRef);
+
 #[derive(Debug, Component)]
 pub struct Object {
     /// Position relative to level origin at mean sea level.
@@ -90,6 +96,26 @@ pub struct Object {
     pub ground_speed: Speed<Vec3>,
 }
 
+/// Navigation and approach equipment of an object.
+///
+/// Objects without this component are considered fully equipped.
+#[derive(Component, Clone, Copy)]
+pub struct Equipage(pub store::Equipage);
+
+impl Equipage {
+    /// Whether the object has all equipment in `required`.
+    #[must_use]
+    pub fn has(this: Option<&Self>, required: store::Equipage) -> bool {
+        this.is_none_or(|Self(equipage)| equipage.contains(required))
+    }
+}
+
+/// Broad category of an object, for display only.
+///
+/// Objects without this component are displayed as jets.
+#[derive(Component, Clone, Copy)]
+pub struct Category(pub store::Category);
+
 /// Rotation of the object, for display only.
 #[derive(Component, Default)]
 pub struct Rotation(pub Quat);
@@ -128,7 +154,7 @@ impl EntityCommand for SpawnCommand {
             message::Sender { display: self.display.name.clone() },
             self.display,
             self.destination,
-            Track { log: VecDeque::new(), timer: Timer::new(Duration::ZERO, TimerMode::Once) },
+            Track::default(),
         ));
 
         if let Some(score) = self.completion_score {
@@ -235,6 +261,8 @@ pub struct OnGround {
     /// The Aviate phase updates [`Object::ground_speed`] to attain this target speed subject to
     /// taxi limits.
     pub target_speed: OnGroundTargetSpeed,
+    /// Clearance state for crossing or entering runways on the taxi path.
+    pub crossing:     RunwayCrossing,
 }
 
 impl OnGround {
@@ -250,6 +278,46 @@ impl OnGround {
     }
 }
 
+/// Runway crossing state of an object on ground.
+///
+/// Objects taxiing onto or across a runway hold short of it
+/// until they are cleared with [`instr::CrossRunway`](super::instr::CrossRunway)
+/// or a taxi instruction that explicitly enters the runway.
+///
+/// `runway` is either runway entity of the runway pair.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RunwayCrossing {
+    /// No runway is involved in the current segment transition.
+    #[default]
+    None,
+    /// Holding short of `runway` pending a crossing clearance.
+    Holding { runway: Entity },
+    /// Cleared to enter `runway`, but not entered yet.
+    Cleared { runway: Entity },
+    /// On or passing through `runway`.
+    Crossing { runway: Entity },
+}
+
+impl RunwayCrossing {
+    /// Whether the object may enter the runway pair `pair`.
+    #[must_use]
+    pub fn permits(self, pair: [Entity; 2]) -> bool {
+        match self {
+            Self::Cleared { runway } | Self::Crossing { runway } => pair.contains(&runway),
+            Self::None | Self::Holding { .. } => false,
+        }
+    }
+
+    /// The runway that the object is holding short of, if any.
+    #[must_use]
+    pub fn holding_runway(self) -> Option<Entity> {
+        match self {
+            Self::Holding { runway } => Some(runway),
+            _ => None,
+        }
+    }
+}
+
 pub enum OnGroundTargetSpeed {
     /// Attempt to reach an exact speed.
     Exact(Speed<f32>),
@@ -338,13 +406,14 @@ impl EntityCommand for SetOnGroundCommand {
                 segment:      self.segment,
                 direction:    self.direction,
                 target_speed: OnGroundTargetSpeed::Exact(Speed::ZERO),
+                crossing:     RunwayCrossing::None,
             },
             TaxiStatus { heading },
         ));
     }
 }
 
-fn move_object_system(
+pub fn move_object_system(
     time: Res<Time<time::Virtual>>,
     mut object_query: Query<(&mut Object, Option<&mut weather::Detector>)>,
 ) {
@@ -497,6 +566,10 @@ impl GroundSpeedCalculator<'_, '_> {
                 weather.wind_at_altitude(altitude),
                 ground_dir,
             );
+            if !ground_speed.is_positive() {
+                // The object never traverses this segment, e.g. due to zero airspeed.
+                continue;
+            }
             let segment_duration = (earlier_distance - later_distance).abs() / ground_speed;
 
             match ref_altitude_type {
@@ -538,6 +611,12 @@ pub struct Track {
     timer:   Timer,
 }
 
+impl Default for Track {
+    fn default() -> Self {
+        Self { log: VecDeque::new(), timer: Timer::new(Duration::ZERO, TimerMode::Once) }
+    }
+}
+
 fn track_position_system(
     time: Res<Time<time::Virtual>>,
     conf: ReadConfig<Conf>,
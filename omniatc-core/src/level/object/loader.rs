@@ -1,19 +1,22 @@
 use std::collections::HashMap;
 use std::num::NonZero;
+use std::time::Duration;
 
 use bevy::ecs::entity::Entity;
 use bevy::ecs::name::Name;
+use bevy::ecs::resource::Resource;
 use bevy::ecs::system::EntityCommand;
 use bevy::ecs::world::{EntityWorldMut, World};
+use bevy::time::{self, Time};
 use math::Speed;
 use store::YawTarget;
 
 use crate::level::aerodrome::loader::AerodromeMap;
-use crate::level::dest::Destination;
+use crate::level::dest::{self, Destination};
 use crate::level::route::loader::RoutePresetMap;
 use crate::level::route::{self, Route};
 use crate::level::waypoint::loader::WaypointMap;
-use crate::level::{nav, object, plane, taxi, wake};
+use crate::level::{drift, formation, fuel, nav, note, object, plane, taxi, vfr, wake};
 use crate::load::{self, StoredEntity};
 
 /// Spawns object types declared in a store into the world.
@@ -29,9 +32,12 @@ pub fn spawn_types(
                     .spawn((
                         StoredEntity,
                         Name::new(format!("Type: {}", ty.full_name)),
+                        object::TypeRef(ref_id.clone()),
                         object::types::Type::Plane {
-                            taxi: taxi::Limits(ty.taxi_limits.clone()),
-                            nav:  nav::Limits(nav_limits.clone()),
+                            taxi:     taxi::Limits(ty.taxi_limits.clone()),
+                            nav:      nav::Limits(nav_limits.clone()),
+                            equipage: ty.equipage,
+                            category: ty.category,
                         },
                     ))
                     .id();
@@ -42,6 +48,7 @@ pub fn spawn_types(
     ObjectTypeMap(out)
 }
 
+#[derive(Default)]
 pub struct ObjectTypeMap(HashMap<store::ObjectTypeRef, Entity>);
 
 impl ObjectTypeMap {
@@ -68,13 +75,99 @@ pub fn spawn(
     route_presets: &RoutePresetMap,
     next_standby_id: &mut NonZero<u32>,
     object: &store::Object,
-) -> Result<(), load::Error> {
-    match object {
+) -> Result<Entity, load::Error> {
+    let entity = match object {
         store::Object::Plane(plane) => {
-            spawn_plane(world, aerodromes, waypoints, route_presets, next_standby_id, plane)?;
+            spawn_plane(world, aerodromes, waypoints, route_presets, next_standby_id, plane)?
+        }
+        store::Object::Drifter(drifter) => drift::loader::spawn(world, drifter),
+    };
+    if let Some(id) = id(object) {
+        world.entity_mut(entity).insert(object::ScenarioId(id.clone()));
+    }
+    Ok(entity)
+}
+
+/// Maps object references to the objects spawned from [`store::File::objects`]
+/// and by quest completion hooks.
+///
+/// Available as a resource after the level is loaded.
+#[derive(Debug, Default, Resource)]
+pub struct ObjectMap {
+    ids:   HashMap<String, Entity>,
+    names: HashMap<String, Entity>,
+}
+
+impl ObjectMap {
+    pub fn insert(&mut self, object: &store::Object, entity: Entity) {
+        if let Some(id) = id(object) {
+            self.ids.insert(id.0.clone(), entity);
         }
+        self.names.insert(name(object).to_owned(), entity);
     }
 
+    /// Resolves an object reference,
+    /// matching scenario-assigned IDs before names.
+    ///
+    /// # Errors
+    /// If no object with the referenced ID or name was declared in the file.
+    pub fn resolve(&self, object: &store::ObjectRef) -> Result<Entity, load::Error> {
+        self.ids
+            .get(&object.0)
+            .or_else(|| self.names.get(&object.0))
+            .copied()
+            .ok_or_else(|| load::Error::UnresolvedObject(object.0.clone()))
+    }
+}
+
+/// Returns the scenario-assigned ID of a stored object, if any.
+#[must_use]
+pub fn id(object: &store::Object) -> Option<&store::ObjectRef> {
+    match object {
+        store::Object::Plane(plane) => plane.aircraft.id.as_ref(),
+        store::Object::Drifter(drifter) => drifter.id.as_ref(),
+    }
+}
+
+/// Returns the name of a stored object.
+#[must_use]
+pub fn name(object: &store::Object) -> &str {
+    match object {
+        store::Object::Plane(plane) => &plane.aircraft.name,
+        store::Object::Drifter(drifter) => &drifter.name,
+    }
+}
+
+/// Inserts the optional components described by the common attributes of an aircraft.
+fn insert_aircraft_attributes(
+    world: &mut World,
+    waypoints: &WaypointMap,
+    plane_entity: Entity,
+    aircraft: &store::BaseAircraft,
+) -> load::Result<()> {
+    if let Some(remaining) = aircraft.endurance {
+        world.entity_mut(plane_entity).insert(fuel::Endurance { remaining });
+    }
+    if let Some(vfr) = &aircraft.vfr {
+        let reporting_points = vfr
+            .reporting_points
+            .iter()
+            .map(|point| waypoints.resolve(point))
+            .collect::<load::Result<_>>()?;
+        let mut entity = world.entity_mut(plane_entity);
+        entity.insert(vfr::Vfr { reporting_points, remaining_legs: vfr.remaining_legs });
+        if vfr.flight_following {
+            entity.insert(vfr::FlightFollowing);
+        }
+    }
+    if let Some(size) = aircraft.formation_size.filter(|&size| size > 1) {
+        world.entity_mut(plane_entity).insert(formation::Formation { size });
+    }
+    if !aircraft.notes.scratchpad.is_empty() || !aircraft.notes.reminders.is_empty() {
+        let elapsed =
+            world.get_resource::<Time<time::Virtual>>().map_or(Duration::ZERO, Time::elapsed);
+        world.entity_mut(plane_entity).insert(note::Notes::from_store(&aircraft.notes, elapsed));
+    }
     Ok(())
 }
 
@@ -85,10 +178,33 @@ fn spawn_plane(
     route_presets: &RoutePresetMap,
     next_standby_id: &mut NonZero<u32>,
     plane: &store::Plane,
-) -> Result<(), load::Error> {
+) -> Result<Entity, load::Error> {
     let plane_entity =
         world.spawn((StoredEntity, Name::new(format!("Plane: {}", plane.aircraft.name)))).id();
 
+    // Do not leave a partially initialized plane if the object is skipped.
+    populate_plane(
+        world,
+        aerodromes,
+        waypoints,
+        route_presets,
+        next_standby_id,
+        plane,
+        plane_entity,
+    )
+    .inspect_err(|_| world.entity_mut(plane_entity).despawn())?;
+    Ok(plane_entity)
+}
+
+fn populate_plane(
+    world: &mut World,
+    aerodromes: &AerodromeMap,
+    waypoints: &WaypointMap,
+    route_presets: &RoutePresetMap,
+    next_standby_id: &mut NonZero<u32>,
+    plane: &store::Plane,
+    plane_entity: Entity,
+) -> Result<(), load::Error> {
     let destination = resolve_destination(aerodromes, waypoints, &plane.aircraft.dest)?;
 
     object::SpawnCommand {
@@ -101,7 +217,13 @@ fn spawn_plane(
     }
     .apply(world.entity_mut(plane_entity));
 
-    world.entity_mut(plane_entity).insert(taxi::Limits(plane.taxi_limits.clone()));
+    world.entity_mut(plane_entity).insert((
+        object::TypeRef(plane.object_type.clone()),
+        taxi::Limits(plane.taxi_limits.clone()),
+        object::Equipage(plane.equipage),
+        object::Category(plane.category),
+    ));
+    insert_aircraft_attributes(world, waypoints, plane_entity, &plane.aircraft)?;
 
     plane::SpawnCommand {
         control: Some(plane::Control {
@@ -148,6 +270,13 @@ fn spawn_plane(
                 heading: Some(plane.aircraft.ground_dir),
             }
             .apply(world.entity_mut(plane_entity));
+
+            if let store::Destination::Departure { .. } = plane.aircraft.dest {
+                let aerodrome = aerodromes.resolve(&target.segment.aerodrome)?;
+                world
+                    .entity_mut(plane_entity)
+                    .insert(dest::Origin { aerodrome: aerodrome.aerodrome_entity });
+            }
         }
     }
 
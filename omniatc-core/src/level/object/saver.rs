@@ -0,0 +1,384 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Query, Res, SystemParam, SystemState};
+use bevy::ecs::world::{EntityRef, World};
+use bevy::time::{self, Time};
+use math::Heading;
+
+use crate::level::dest::{self, Destination};
+use crate::level::object::{self, Object};
+use crate::level::route::{self, Route, TaxiStopMode};
+use crate::level::session::{self, NameParams};
+use crate::level::waypoint::Waypoint;
+use crate::level::{drift, formation, fuel, ground, nav, note, plane, runway, taxi, vfr};
+
+/// Converts the objects in the world into their stored form.
+///
+/// This is the inverse of [`spawn`](super::loader::spawn).
+/// Route nodes referencing entities that cannot be named are omitted,
+/// and objects whose destination or type cannot be named are skipped.
+/// Standby nodes are assigned new skip IDs when the saved objects are loaded.
+#[must_use]
+pub fn save(world: &mut World) -> Vec<store::Object> {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Object>, Without<dest::Completed>)>()
+        .iter(world)
+        .collect();
+
+    let mut params_state = SystemState::<SaveParams>::new(world);
+    let params = params_state.get(world);
+    let saved: Vec<(Entity, store::Object)> = entities
+        .into_iter()
+        .filter_map(|entity| {
+            let entity_ref = world.entity(entity);
+            let object = if entity_ref.contains::<drift::Drifter>() {
+                save_drifter(entity_ref).map(store::Object::Drifter)
+            } else {
+                params.plane(entity_ref).map(store::Object::Plane)
+            };
+            if object.is_none() {
+                bevy::log::warn!("Cannot save object {entity:?}");
+            }
+            Some((entity, object?))
+        })
+        .collect();
+
+    saved
+        .into_iter()
+        .map(|(entity, mut object)| {
+            if let store::Object::Plane(plane) = &mut object {
+                plane.aircraft.instructions = session::record_state(world, entity);
+            }
+            object
+        })
+        .collect()
+}
+
+/// Queries resolving entity references in objects into names.
+#[derive(SystemParam)]
+struct SaveParams<'w, 's> {
+    time:           Res<'w, Time<time::Virtual>>,
+    names:          NameParams<'w, 's>,
+    waypoint_query: Query<'w, 's, &'static Waypoint>,
+    segment_query: Query<
+        'w,
+        's,
+        (
+            &'static ground::SegmentLabel,
+            &'static ground::SegmentOf,
+            Option<&'static ground::SegmentOfRunway>,
+        ),
+    >,
+    runway_query:   Query<'w, 's, &'static runway::RunwayOf>,
+    preset_query:   Query<'w, 's, &'static route::PresetRef>,
+}
+
+impl SaveParams<'_, '_> {
+    fn plane(&self, entity: EntityRef) -> Option<store::Plane> {
+        let object = entity.get::<Object>()?;
+        let control = entity.get::<plane::Control>()?;
+        let on_ground = entity.get::<object::OnGround>();
+        let horiz_speed = object.ground_speed.horizontal();
+        let ground_speed = horiz_speed.magnitude_exact();
+
+        let aircraft = store::BaseAircraft {
+            name: entity.get::<object::Display>()?.name.clone(),
+            id: entity.get::<object::ScenarioId>().map(|id| id.0.clone()),
+            dest: self.destination(entity.get::<Destination>()?)?,
+            completion_score: entity
+                .get::<dest::CompletionScore>()
+                .map_or(store::Score(0), |score| score.score),
+            position: object.position.horizontal(),
+            altitude: object.position.altitude(),
+            ground_speed,
+            ground_dir: if on_ground.is_some() || ground_speed.is_zero() {
+                control.heading
+            } else {
+                horiz_speed.heading()
+            },
+            vert_rate: object.ground_speed.vertical(),
+            endurance: entity.get::<fuel::Endurance>().map(|endurance| endurance.remaining),
+            vfr: entity.get::<vfr::Vfr>().map(|vfr| store::VfrState {
+                reporting_points: vfr
+                    .reporting_points
+                    .iter()
+                    .filter_map(|&point| {
+                        let waypoint = self.waypoint_query.get(point).ok()?;
+                        Some(store::NamedWaypointRef(waypoint.name.clone()))
+                    })
+                    .collect(),
+                remaining_legs:   vfr.remaining_legs,
+                flight_following: entity.contains::<vfr::FlightFollowing>(),
+            }),
+            formation_size: entity.get::<formation::Formation>().map(|formation| formation.size),
+            notes: entity
+                .get::<note::Notes>()
+                .map(|notes| notes.to_store(self.time.elapsed()))
+                .unwrap_or_default(),
+            instructions: store::InstructionState::default(),
+        };
+
+        Some(store::Plane {
+            aircraft,
+            control: store::PlaneControl {
+                heading:     control.heading,
+                yaw_speed:   control.yaw_speed,
+                horiz_accel: control.horiz_accel,
+            },
+            object_type: entity.get::<object::TypeRef>()?.0.clone(),
+            taxi_limits: entity.get::<taxi::Limits>()?.0.clone(),
+            nav_limits: entity.get::<nav::Limits>()?.0.clone(),
+            equipage: entity.get::<object::Equipage>()?.0,
+            category: entity.get::<object::Category>()?.0,
+            nav_target: match on_ground {
+                Some(on_ground) => store::NavTarget::Ground(store::GroundNavTarget {
+                    segment: self.segment(on_ground.segment)?,
+                }),
+                None => store::NavTarget::Airborne(Box::new(self.airborne_target(entity)?)),
+            },
+            route: self.route(entity, on_ground),
+        })
+    }
+
+    fn destination(&self, dest: &Destination) -> Option<store::Destination> {
+        Some(match *dest {
+            Destination::Landing { aerodrome } => {
+                store::Destination::Landing { aerodrome: self.names.aerodrome(aerodrome)? }
+            }
+            Destination::Parking { aerodrome } => {
+                store::Destination::Parking { aerodrome: self.names.aerodrome(aerodrome)? }
+            }
+            Destination::VacateAnyRunway => store::Destination::VacateAnyRunway,
+            Destination::Departure { min_altitude, waypoint_proximity } => {
+                store::Destination::Departure {
+                    min_altitude,
+                    waypoint_proximity: match waypoint_proximity {
+                        Some((waypoint, distance)) => {
+                            Some((self.names.waypoint(waypoint)?, distance))
+                        }
+                        None => None,
+                    },
+                }
+            }
+        })
+    }
+
+    fn airborne_target(&self, entity: EntityRef) -> Option<store::AirborneNavTarget> {
+        let velocity = entity.get::<nav::VelocityTarget>()?;
+        Some(store::AirborneNavTarget {
+            yaw:              velocity.yaw,
+            horiz_ias:        Some(velocity.horiz_speed),
+            vert_rate:        velocity.vert_rate,
+            expedite:         velocity.expedite,
+            target_altitude:  entity.get::<nav::TargetAltitude>().map(|target| {
+                store::TargetAltitude { altitude: target.altitude, expedite: target.expedite }
+            }),
+            target_glide:     entity.get::<nav::TargetGlide>().and_then(|target| {
+                Some(store::TargetGlide {
+                    target_waypoint: self.names.waypoint(target.target_waypoint)?,
+                    glide_angle:     target.glide_angle,
+                    min_pitch:       target.min_pitch,
+                    max_pitch:       target.max_pitch,
+                    lookahead:       target.lookahead,
+                    expedite:        target.expedite,
+                })
+            }),
+            target_waypoint:  entity.get::<nav::TargetWaypoint>().and_then(|target| {
+                Some(store::TargetWaypoint {
+                    waypoint: self.names.waypoint(target.waypoint_entity)?,
+                })
+            }),
+            target_alignment: entity.get::<nav::TargetAlignment>().and_then(|target| {
+                Some(store::TargetAlignment {
+                    start_waypoint:   self.names.waypoint(target.start_waypoint)?,
+                    end_waypoint:     self.names.waypoint(target.end_waypoint)?,
+                    lookahead:        target.lookahead,
+                    activation_range: target.activation_range,
+                })
+            }),
+        })
+    }
+
+    fn segment(&self, segment: Entity) -> Option<store::SegmentRef> {
+        let (label, &ground::SegmentOf(aerodrome), _) = self.segment_query.get(segment).ok()?;
+        self.segment_label(label, Some(aerodrome))
+    }
+
+    /// Names a segment label, where taxiways and aprons belong to `aerodrome`.
+    fn segment_label(
+        &self,
+        label: &ground::SegmentLabel,
+        aerodrome: Option<Entity>,
+    ) -> Option<store::SegmentRef> {
+        Some(match *label {
+            ground::SegmentLabel::Taxiway { ref name } => store::SegmentRef {
+                aerodrome: self.names.aerodrome(aerodrome?)?,
+                label:     store::SegmentLabel::Taxiway(name.clone()),
+            },
+            ground::SegmentLabel::Apron { ref name } => store::SegmentRef {
+                aerodrome: self.names.aerodrome(aerodrome?)?,
+                label:     store::SegmentLabel::Apron(name.clone()),
+            },
+            ground::SegmentLabel::RunwayPair([runway, _]) => {
+                let runway = self.names.runway(runway)?;
+                store::SegmentRef {
+                    aerodrome: runway.aerodrome,
+                    label:     store::SegmentLabel::Runway(runway.runway_name),
+                }
+            }
+        })
+    }
+
+    /// Converts the route of an object.
+    ///
+    /// Taxi nodes only name their segments, so the aerodrome they refer to
+    /// is tracked from the current segment, the destination and the landing runways.
+    fn route(&self, entity: EntityRef, on_ground: Option<&object::OnGround>) -> store::Route {
+        let mut aerodrome = match entity.get::<Destination>() {
+            Some(&(Destination::Landing { aerodrome } | Destination::Parking { aerodrome })) => {
+                Some(aerodrome)
+            }
+            _ => None,
+        };
+        let mut takeoff_runway = None;
+        if let Some(on_ground) = on_ground
+            && let Ok((_, &ground::SegmentOf(segment_aerodrome), runways)) =
+                self.segment_query.get(on_ground.segment)
+        {
+            aerodrome = Some(segment_aerodrome);
+            takeoff_runway = runways
+                .and_then(|runways| self.names.runway(runways.by_direction(on_ground.direction)));
+        }
+
+        let mut landing_runway = None;
+        let mut nodes = Vec::new();
+        for node in entity.get::<Route>().into_iter().flat_map(Route::iter) {
+            // The loader expands a stored landing into consecutive nodes for the same runway.
+            if let route::Node::ShortFinal(route::ShortFinalNode { runway, .. })
+            | route::Node::VisualLanding(route::VisualLandingNode { runway, .. }) = *node
+                && landing_runway == Some(runway)
+            {
+                continue;
+            }
+            landing_runway = None;
+
+            let stored = match *node {
+                route::Node::Standby(_) => Some(store::RouteNode::WaitForClearance),
+                route::Node::DirectWaypoint(ref node) => {
+                    self.names.waypoint(node.waypoint).map(|waypoint| {
+                        store::RouteNode::DirectWaypoint {
+                            waypoint,
+                            distance: node.distance,
+                            proximity: node.proximity,
+                            altitude: node.altitude,
+                        }
+                    })
+                }
+                route::Node::SetAirSpeed(ref node) => {
+                    Some(store::RouteNode::SetAirSpeed { goal: node.speed, error: node.error })
+                }
+                route::Node::StartSetAltitude(ref node) => {
+                    Some(store::RouteNode::StartPitchToAltitude {
+                        goal:     node.altitude,
+                        error:    node.error,
+                        expedite: node.expedite,
+                    })
+                }
+                route::Node::AlignRunway(ref node) => {
+                    landing_runway = Some(node.runway);
+                    self.landing(
+                        node.runway,
+                        node.goaround_preset,
+                        store::LandingPhase::Align,
+                        &mut aerodrome,
+                    )
+                }
+                route::Node::ShortFinal(ref node) => {
+                    landing_runway = Some(node.runway);
+                    self.landing(
+                        node.runway,
+                        node.goaround_preset,
+                        store::LandingPhase::ShortFinal,
+                        &mut aerodrome,
+                    )
+                }
+                route::Node::VisualLanding(ref node) => self.landing(
+                    node.runway,
+                    node.goaround_preset,
+                    store::LandingPhase::Visual,
+                    &mut aerodrome,
+                ),
+                route::Node::Takeoff(ref node) => {
+                    takeoff_runway.clone().map(|runway| store::RouteNode::RunwayTakeoff {
+                        runway,
+                        target_altitude: node.target_altitude,
+                    })
+                }
+                route::Node::Taxi(ref node) => match (node.stop, &node.label) {
+                    (TaxiStopMode::LineUp, &ground::SegmentLabel::RunwayPair([runway, _])) => {
+                        takeoff_runway = self.names.runway(runway);
+                        takeoff_runway
+                            .clone()
+                            .map(|runway| store::RouteNode::RunwayLineup { runway })
+                    }
+                    (TaxiStopMode::HoldShort, label) => self
+                        .segment_label(label, aerodrome)
+                        .map(|segment| store::RouteNode::HoldShort { segment }),
+                    (_, label) => self
+                        .segment_label(label, aerodrome)
+                        .map(|segment| store::RouteNode::Taxi { segment }),
+                },
+                // Deicing is planned by the simulation and has no stored form.
+                route::Node::Deice(_) => None,
+            };
+            nodes.extend(stored);
+        }
+
+        store::Route { id: entity.get::<route::Id>().and_then(|id| id.0.clone()), nodes }
+    }
+
+    fn landing(
+        &self,
+        runway: Entity,
+        goaround_preset: Option<Entity>,
+        current_phase: store::LandingPhase,
+        aerodrome: &mut Option<Entity>,
+    ) -> Option<store::RouteNode> {
+        if let Ok(&runway::RunwayOf(runway_aerodrome)) = self.runway_query.get(runway) {
+            *aerodrome = Some(runway_aerodrome);
+        }
+        Some(store::RouteNode::RunwayLanding {
+            runway: self.names.runway(runway)?,
+            goaround_preset: goaround_preset
+                .and_then(|preset| Some(self.preset_query.get(preset).ok()?.0.clone())),
+            current_phase,
+        })
+    }
+}
+
+fn save_drifter(entity: EntityRef) -> Option<store::Drifter> {
+    let object = entity.get::<Object>()?;
+    let (kind, heading) = if let Some(balloon) = entity.get::<drift::Balloon>() {
+        (
+            store::DrifterKind::Balloon {
+                vert_rate: balloon.vert_rate,
+                ceiling:   balloon.ceiling,
+            },
+            Heading::NORTH,
+        )
+    } else {
+        let glider = entity.get::<drift::Glider>()?;
+        (
+            store::DrifterKind::Glider { airspeed: glider.airspeed, sink_rate: glider.sink_rate },
+            glider.heading,
+        )
+    };
+    Some(store::Drifter {
+        name: entity.get::<object::Display>()?.name.clone(),
+        id: entity.get::<object::ScenarioId>().map(|id| id.0.clone()),
+        kind,
+        position: object.position.horizontal(),
+        altitude: object.position.altitude(),
+        heading,
+    })
+}
@@ -1,8 +1,9 @@
 use bevy::app::App;
 use bevy::ecs::system::SystemState;
-use math::{Length, Position, Speed};
+use bevy::ecs::world::World;
+use math::{Heading, Length, Position, Speed};
 
-use super::GroundSpeedCalculator;
+use super::{GroundSpeedCalculator, loader};
 use crate::level::object::RefAltitudeType;
 
 #[test]
@@ -24,3 +25,31 @@ fn test_estimate_altitude_change() {
     assert!(result.amsl() > Length::from_feet(990.));
     // actual value is somewhere near 993 ft, but we are using an approximation function here.
 }
+
+fn drifter(name: &str, id: Option<&str>) -> store::Object {
+    store::Object::Drifter(store::Drifter {
+        name:     name.into(),
+        id:       id.map(Into::into),
+        kind:     store::DrifterKind::Balloon {
+            vert_rate: Speed::from_fpm(100.),
+            ceiling:   Position::from_amsl_feet(5000.),
+        },
+        position: Position::ORIGIN,
+        altitude: Position::from_amsl_feet(1000.),
+        heading:  Heading::NORTH,
+    })
+}
+
+#[test]
+fn test_object_map_prefers_ids() {
+    let mut world = World::new();
+    let [tagged, named] = [world.spawn_empty().id(), world.spawn_empty().id()];
+
+    let mut map = loader::ObjectMap::default();
+    map.insert(&drifter("ABC123", Some("lead")), tagged);
+    map.insert(&drifter("lead", None), named);
+
+    assert_eq!(map.resolve(&"lead".into()).unwrap(), tagged, "IDs should take precedence");
+    assert_eq!(map.resolve(&"ABC123".into()).unwrap(), tagged);
+    assert!(map.resolve(&"DEF456".into()).is_err());
+}
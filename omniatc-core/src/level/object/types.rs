@@ -5,9 +5,22 @@ use crate::level::{nav, taxi};
 
 #[derive(Component)]
 pub enum Type {
-    Plane { taxi: taxi::Limits, nav: nav::Limits },
+    Plane {
+        taxi:     taxi::Limits,
+        nav:      nav::Limits,
+        /// Navigation and approach equipment of objects of this type.
+        equipage: store::Equipage,
+        /// Display category of objects of this type.
+        category: store::Category,
+    },
 }
 
+/// The stored reference of an object type.
+///
+/// Component on object type entities and on the objects spawned with the type.
+#[derive(Component, Clone)]
+pub struct TypeRef(pub store::ObjectTypeRef);
+
 impl Type {
     #[must_use]
     pub fn half_length(&self) -> Length<f32> {
@@ -0,0 +1,364 @@
+//! Radiotelephony phraseology for instructions and readbacks.
+//!
+//! Instructions describe their transmission as a structured [`Phrase`],
+//! which is rendered into words according to the configured regional [`Style`],
+//! e.g. `Lufthansa one twenty-three, descend and maintain four thousand`
+//! in the FAA style, or `Lufthansa one two three, descend to four thousand feet`
+//! in the ICAO style.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::SystemState;
+use bevy::ecs::world::World;
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Heading, Length, Speed};
+use serde::{Deserialize, Serialize};
+
+use crate::WorldTryLog;
+use crate::level::{message, object, transition};
+
+#[cfg(test)]
+mod tests;
+
+/// Words for digits read out individually.
+const DIGITS: [&str; 10] =
+    ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "niner"];
+
+/// Words for cardinal numbers below ten.
+const ONES: [&str; 10] =
+    ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] =
+    ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) { app.init_config::<M, Conf>("core:phraseology"); }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Regional conventions used to phrase transmissions.
+    pub style:    StyleConf,
+    /// Whether objects read back instructions when they are dispatched.
+    #[config(default = true)]
+    pub readback: bool,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Config)]
+#[config(expose(read))]
+pub enum StyleConf {
+    /// ICAO phraseology.
+    Icao,
+    /// FAA phraseology.
+    Faa,
+}
+
+/// Regional conventions of phraseology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Callsign numbers are read digit by digit,
+    /// altitudes are followed by `feet`, speeds are followed by `knots`,
+    /// and vertical clearances are phrased as `climb to`.
+    Icao,
+    /// Flight numbers are read in group form,
+    /// units are omitted, and vertical clearances are phrased as `climb and maintain`.
+    Faa,
+}
+
+impl From<StyleConfRead> for Style {
+    fn from(conf: StyleConfRead) -> Self {
+        match conf {
+            StyleConfRead::Icao => Self::Icao,
+            StyleConfRead::Faa => Self::Faa,
+        }
+    }
+}
+
+/// Reads the configured style from the world.
+pub fn current_style(world: &mut World) -> Style {
+    let mut state = SystemState::<ReadConfig<Conf>>::new(world);
+    state.get(world).read().style.into()
+}
+
+/// A structured transmission, rendered into words by [`Phrase::render`].
+#[derive(Debug, Clone, Default)]
+pub struct Phrase(Vec<Element>);
+
+#[derive(Debug, Clone)]
+pub enum Element {
+    /// Words spoken verbatim, e.g. verbs and waypoint names.
+    Words(Cow<'static, str>),
+    /// A pause between clauses, rendered as a comma.
+    Pause,
+    /// A magnetic heading, read as three digits.
+    Heading(Heading),
+    /// An altitude or a flight level.
+    Altitude(transition::Reading),
+    /// The verb of a vertical clearance.
+    Vertical(Vertical),
+    /// An indicated airspeed.
+    Speed(Speed<f32>),
+    /// A distance in nautical miles.
+    Miles(Length<f32>),
+    /// A small cardinal number.
+    Count(u32),
+    /// A runway designator, e.g. `18L`.
+    Runway(String),
+}
+
+/// Direction of a vertical clearance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vertical {
+    Climb,
+    Descend,
+}
+
+impl Phrase {
+    /// Appends words spoken verbatim.
+    #[must_use]
+    pub fn words(mut self, words: impl Into<Cow<'static, str>>) -> Self {
+        self.0.push(Element::Words(words.into()));
+        self
+    }
+
+    /// Appends an element.
+    #[must_use]
+    pub fn then(mut self, element: Element) -> Self {
+        self.0.push(element);
+        self
+    }
+
+    /// Appends another clause after a pause.
+    #[must_use]
+    pub fn clause(mut self, other: Phrase) -> Self {
+        if !self.0.is_empty() && !other.0.is_empty() {
+            self.0.push(Element::Pause);
+        }
+        self.0.extend(other.0);
+        self
+    }
+
+    /// Whether the phrase has no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Renders the phrase into lowercase words.
+    #[must_use]
+    pub fn render(&self, style: Style) -> String {
+        let mut output = String::new();
+        for element in &self.0 {
+            if let Element::Pause = element {
+                output.push(',');
+                continue;
+            }
+            if !output.is_empty() {
+                output.push(' ');
+            }
+            output.push_str(&element.render(style));
+        }
+        output
+    }
+}
+
+impl Element {
+    fn render(&self, style: Style) -> Cow<'static, str> {
+        match *self {
+            Self::Words(ref words) => words.clone(),
+            Self::Pause => Cow::Borrowed(","),
+            Self::Heading(heading) => {
+                let degrees = format!("{:03.0}", heading.degrees());
+                Cow::Owned(digits(if degrees == "000" { "360" } else { &degrees }))
+            }
+            Self::Altitude(reading) => Cow::Owned(altitude(reading, style)),
+            Self::Vertical(vertical) => Cow::Borrowed(match (vertical, style) {
+                (Vertical::Climb, Style::Icao) => "climb to",
+                (Vertical::Descend, Style::Icao) => "descend to",
+                (Vertical::Climb, Style::Faa) => "climb and maintain",
+                (Vertical::Descend, Style::Faa) => "descend and maintain",
+            }),
+            Self::Speed(speed) => {
+                let knots = digits(&format!("{:.0}", speed.into_knots().max(0.0)));
+                Cow::Owned(match style {
+                    Style::Icao => format!("{knots} knots"),
+                    Style::Faa => knots,
+                })
+            }
+            Self::Miles(distance) => {
+                let miles = format!("{:.0}", distance.into_nm().max(0.0));
+                let count = miles.parse().map_or(miles, cardinal);
+                let unit = if count == "one" { "mile" } else { "miles" };
+                Cow::Owned(format!("{count} {unit}"))
+            }
+            Self::Count(count) => Cow::Owned(cardinal(count)),
+            Self::Runway(ref name) => Cow::Owned(runway(name)),
+        }
+    }
+}
+
+/// Reads out each digit of `number` individually.
+///
+/// Characters other than ASCII digits are kept verbatim.
+#[must_use]
+pub fn digits(number: &str) -> String {
+    let words: Vec<Cow<str>> = number
+        .chars()
+        .map(|ch| match ch.to_digit(10) {
+            Some(digit) => Cow::Borrowed(DIGITS[digit as usize]),
+            None => Cow::Owned(ch.to_string()),
+        })
+        .collect();
+    words.join(" ")
+}
+
+/// Reads out a cardinal number, e.g. `twenty-three`.
+///
+/// Numbers of 100 or above are read digit by digit.
+#[must_use]
+pub fn cardinal(number: u32) -> String {
+    match number {
+        0..10 => ONES[number as usize].to_owned(),
+        10..20 => TEENS[(number - 10) as usize].to_owned(),
+        20..100 if number.is_multiple_of(10) => TENS[(number / 10) as usize].to_owned(),
+        20..100 => format!("{}-{}", TENS[(number / 10) as usize], ONES[(number % 10) as usize]),
+        _ => digits(&number.to_string()),
+    }
+}
+
+/// Reads out a flight number in group form, e.g. `one twenty-three` or `twelve hundred`.
+fn group_form(number: &str) -> String {
+    let Ok(value) = number.parse::<u32>() else { return digits(number) };
+    if number.starts_with('0') {
+        return digits(number);
+    }
+
+    let pair = |value: u32| {
+        if value < 10 { format!("zero {}", ONES[value as usize]) } else { cardinal(value) }
+    };
+    match number.len() {
+        1 | 2 => cardinal(value),
+        3 if value.is_multiple_of(100) => format!("{} hundred", ONES[(value / 100) as usize]),
+        3 => format!("{} {}", ONES[(value / 100) as usize], pair(value % 100)),
+        4 if value.is_multiple_of(1000) => format!("{} thousand", ONES[(value / 1000) as usize]),
+        4 if value.is_multiple_of(100) => format!("{} hundred", cardinal(value / 100)),
+        4 => format!("{} {}", cardinal(value / 100), pair(value % 100)),
+        _ => digits(number),
+    }
+}
+
+/// Reads out an altitude in thousands and hundreds of feet, or a flight level.
+fn altitude(reading: transition::Reading, style: Style) -> String {
+    let feet = match reading {
+        transition::Reading::FlightLevel(level) => {
+            return format!("flight level {}", digits(&format!("{:03.0}", level.max(0.0))));
+        }
+        transition::Reading::Altitude(feet) => feet,
+    };
+
+    let hundreds = format!("{:.0}", (feet / 100.0).round().max(0.0));
+    let (thousands, hundreds) = hundreds.split_at(hundreds.len() - 1);
+    let mut words = Vec::new();
+    if !thousands.is_empty() {
+        words.push(format!("{} thousand", digits(thousands)));
+    }
+    if hundreds != "0" {
+        words.push(format!("{} hundred", digits(hundreds)));
+    }
+    if words.is_empty() {
+        words.push(DIGITS[0].to_owned());
+    }
+    if style == Style::Icao {
+        words.push("feet".into());
+    }
+    words.join(" ")
+}
+
+/// Reads out a runway designator, e.g. `runway one eight left`.
+fn runway(name: &str) -> String {
+    let mut words = vec!["runway".to_owned()];
+    for ch in name.chars() {
+        match ch {
+            'L' => words.push("left".into()),
+            'R' => words.push("right".into()),
+            'C' => words.push("center".into()),
+            _ => words.push(digits(&ch.to_string())),
+        }
+    }
+    words.join(" ")
+}
+
+/// Renders a radiotelephony callsign, e.g. `Speedbird one two three`.
+///
+/// Numeric words are read per the style, other words are kept verbatim.
+#[must_use]
+pub fn render_callsign(telephony: &str, style: Style) -> String {
+    let words: Vec<_> = telephony
+        .split_whitespace()
+        .map(|word| {
+            if !word.is_empty() && word.chars().all(|ch| ch.is_ascii_digit()) {
+                match style {
+                    Style::Icao => digits(word),
+                    Style::Faa => group_form(word),
+                }
+            } else {
+                word.to_owned()
+            }
+        })
+        .collect();
+    words.join(" ")
+}
+
+/// Returns the callsign by which an object is addressed.
+///
+/// Objects without a [`message::Telephony`] are addressed by their display name.
+#[must_use]
+pub fn callsign(world: &World, object: Entity, style: Style) -> String {
+    if let Some(telephony) = world.get::<message::Telephony>(object) {
+        return render_callsign(&telephony.0, style);
+    }
+    world
+        .log_get::<object::Display>(object)
+        .map_or_else(|| format!("object#{object:?}"), |display| display.name.clone())
+}
+
+/// Formats an instruction addressed to `callsign`.
+#[must_use]
+pub fn address(callsign: &str, phrase: &Phrase, style: Style) -> String {
+    format!("{callsign}, {}", phrase.render(style))
+}
+
+/// Formats the readback of an instruction by `callsign`.
+#[must_use]
+pub fn readback(callsign: &str, phrase: &Phrase, style: Style) -> String {
+    let rendered = phrase.render(style);
+    let mut chars = rendered.chars();
+    let capitalized = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    format!("{capitalized}, {callsign}")
+}
@@ -0,0 +1,82 @@
+use math::{Heading, Length, Speed};
+
+use super::{Element, Phrase, Style, Vertical, address, cardinal, readback, render_callsign};
+use crate::level::transition::Reading;
+
+#[test]
+fn test_callsign_styles() {
+    assert_eq!(render_callsign("Lufthansa 123", Style::Icao), "Lufthansa one two three");
+    assert_eq!(render_callsign("Lufthansa 123", Style::Faa), "Lufthansa one twenty-three");
+    assert_eq!(render_callsign("Speedbird 9", Style::Icao), "Speedbird niner");
+    assert_eq!(render_callsign("American 52", Style::Faa), "American fifty-two");
+    assert_eq!(render_callsign("Delta 100", Style::Faa), "Delta one hundred");
+    assert_eq!(render_callsign("United 205", Style::Faa), "United two zero five");
+    assert_eq!(render_callsign("Cactus 1549", Style::Faa), "Cactus fifteen forty-nine");
+    assert_eq!(render_callsign("Jetblue 1200", Style::Faa), "Jetblue twelve hundred");
+    assert_eq!(render_callsign("Shamrock 012", Style::Faa), "Shamrock zero one two");
+}
+
+#[test]
+fn test_cardinal() {
+    assert_eq!(cardinal(0), "zero");
+    assert_eq!(cardinal(9), "nine");
+    assert_eq!(cardinal(15), "fifteen");
+    assert_eq!(cardinal(40), "forty");
+    assert_eq!(cardinal(99), "ninety-nine");
+    assert_eq!(cardinal(250), "two five zero");
+}
+
+#[test]
+fn test_vertical_clearance_styles() {
+    let phrase = Phrase::default()
+        .then(Element::Vertical(Vertical::Descend))
+        .then(Element::Altitude(Reading::Altitude(4000.0)));
+    assert_eq!(
+        address("Lufthansa one twenty-three", &phrase, Style::Faa),
+        "Lufthansa one twenty-three, descend and maintain four thousand",
+    );
+    assert_eq!(
+        address("Lufthansa one two three", &phrase, Style::Icao),
+        "Lufthansa one two three, descend to four thousand feet",
+    );
+
+    let phrase = Phrase::default()
+        .then(Element::Vertical(Vertical::Climb))
+        .then(Element::Altitude(Reading::FlightLevel(350.0)));
+    assert_eq!(phrase.render(Style::Icao), "climb to flight level three five zero");
+
+    let phrase =
+        Phrase::default().words("maintain").then(Element::Altitude(Reading::Altitude(12_500.0)));
+    assert_eq!(phrase.render(Style::Icao), "maintain one two thousand five hundred feet");
+}
+
+#[test]
+fn test_clauses_and_readback() {
+    let phrase = Phrase::default()
+        .words("fly heading")
+        .then(Element::Heading(Heading::NORTH))
+        .clause(
+            Phrase::default()
+                .words("reduce speed to")
+                .then(Element::Speed(Speed::from_knots(210.0))),
+        )
+        .clause(
+            Phrase::default()
+                .words("expect direct DWIND in")
+                .then(Element::Miles(Length::from_nm(5.0))),
+        );
+    assert_eq!(
+        phrase.render(Style::Icao),
+        "fly heading three six zero, reduce speed to two one zero knots, expect direct DWIND in \
+         five miles",
+    );
+    assert_eq!(
+        readback("Speedbird one two three", &phrase, Style::Faa),
+        "Fly heading three six zero, reduce speed to two one zero, expect direct DWIND in five \
+         miles, Speedbird one two three",
+    );
+
+    let phrase =
+        Phrase::default().words("cleared ILS approach").then(Element::Runway("18L".into()));
+    assert_eq!(phrase.render(Style::Faa), "cleared ILS approach runway one eight left");
+}
@@ -0,0 +1,327 @@
+//! Variability in how pilots execute instructions.
+//!
+//! Each object is assigned a [`Response`] sampled from the difficulty settings in [`Conf`].
+//! An acknowledged instruction is held in the [`ReactionQueue`] of the object
+//! until the reaction delay has elapsed,
+//! and the navigation systems scale their turn anticipation and altitude capture
+//! by the sampled factors, so that identical instructions are executed differently.
+//!
+//! The values read back by the pilot on acknowledgement are recorded in [`Clearance`],
+//! which may differ from the navigation targets until the instruction is executed.
+//! Pilots occasionally commit errors that deviate from the clearance,
+//! which are detected by the [`deviation`](super::deviation) monitor.
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::ecs::world::EntityWorldMut;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Angle, Heading, Length, Position, Speed};
+use rand::Rng;
+use store::YawTarget;
+
+use super::SystemSets;
+use crate::level::instr::{self, Instruction, Kind as _};
+use crate::level::object::{self, Object};
+use crate::level::{nav, session};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:pilot");
+        app.init_resource::<session::Seed>();
+        app.add_systems(
+            app::Update,
+            (
+                assign_system.before(instr::dispatch_system),
+                react_system.after(instr::dispatch_system),
+                error_system,
+            )
+                .in_set(SystemSets::Communicate),
+        );
+    }
+}
+
+/// Difficulty settings for pilot response characteristics.
+#[derive(Config)]
+pub struct Conf {
+    /// Minimum delay between acknowledging an instruction and acting on it.
+    #[config(default = Duration::from_secs(1))]
+    pub min_reaction_delay:          Duration,
+    /// Maximum delay between acknowledging an instruction and acting on it.
+    #[config(default = Duration::from_secs(5))]
+    pub max_reaction_delay:          Duration,
+    /// Maximum relative error of the distance at which a turn before a fly-by waypoint starts.
+    #[config(default = 0.3, min = 0.0, max = 1.0)]
+    pub max_turn_anticipation_error: f32,
+    /// Maximum factor by which a pilot overestimates the vertical deceleration
+    /// available to level off at a target altitude.
+    #[config(default = 1.5, min = 1.0, max = 4.0)]
+    pub max_altitude_capture_factor: f32,
+    /// Expected number of errors per hour an object commits while flying a cleared heading
+    /// or altitude.
+    #[config(default = 0.5, min = 0.0, max = 20.0)]
+    pub error_rate:                  f32,
+    /// Altitude by which an object busts its cleared altitude on an altitude error.
+    #[config(default = Length::from_feet(1000.0), min = Length::ZERO, max = Length::from_feet(5000.0))]
+    pub altitude_error:              Length<f32>,
+    /// Maximum angle by which an object flies off its cleared heading on a heading error.
+    #[config(default = Angle::from_degrees(40.0))]
+    pub max_heading_error:           Angle,
+}
+
+/// Response characteristics of the pilot of an object.
+#[derive(Debug, Clone, Copy, Component)]
+#[require(ReactionQueue)]
+pub struct Response {
+    /// Delay between acknowledging an instruction and acting on it.
+    pub reaction_delay:    Duration,
+    /// Multiplier on the turn distance before a fly-by waypoint.
+    ///
+    /// Values below 1 turn late and overshoot the next leg;
+    /// values above 1 turn early and undershoot it.
+    pub turn_anticipation: f32,
+    /// Multiplier on the vertical deceleration assumed when capturing a target altitude.
+    ///
+    /// Values above 1 level off late and overshoot the target altitude.
+    pub altitude_capture:  f32,
+}
+
+impl Response {
+    /// A pilot that executes every instruction immediately and precisely.
+    pub const PRECISE: Self =
+        Self { reaction_delay: Duration::ZERO, turn_anticipation: 1.0, altitude_capture: 1.0 };
+}
+
+/// Clearance values last acknowledged by the pilot of an object.
+///
+/// A value is `None` if the object has not been cleared to it
+/// or if a later instruction has superseded it, e.g. a direct-to superseding a heading.
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct Clearance {
+    /// Assigned heading.
+    pub heading:  Option<Heading>,
+    /// Assigned altitude.
+    pub altitude: Option<Position<f32>>,
+    /// Assigned indicated airspeed.
+    pub speed:    Option<Speed<f32>>,
+}
+
+impl Clearance {
+    /// Updates the cleared values with an acknowledged instruction.
+    pub fn acknowledge(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::SetHeading(instr) => self.heading = Some(instr.target.heading()),
+            Instruction::VectorThenResume(instr) => {
+                self.heading = Some(instr.heading.target.heading());
+            }
+            Instruction::SetWaypoint(_)
+            | Instruction::SelectRoute(_)
+            | Instruction::SkipToWaypoint(_)
+            | Instruction::Divert(_) => self.heading = None,
+            Instruction::SetSpeed(instr) => self.speed = Some(instr.target),
+            Instruction::SetSpeedUntil(instr) => self.speed = Some(instr.initial.target),
+            Instruction::SetAltitude(instr) => self.altitude = Some(instr.target.altitude),
+            // The vertical profile now follows the route restrictions.
+            Instruction::CrossAltitude(_) => self.altitude = None,
+            Instruction::AirborneVector(instr) => {
+                match &instr.directional {
+                    Some(instr::AirborneVectorDirectional::SetHeading(heading)) => {
+                        self.heading = Some(heading.target.heading());
+                    }
+                    Some(instr::AirborneVectorDirectional::SetWaypoint(_)) => self.heading = None,
+                    None => {}
+                }
+                if let Some(speed) = &instr.speed {
+                    self.speed = Some(speed.target);
+                }
+                if let Some(altitude) = &instr.altitude {
+                    self.altitude = Some(altitude.target.altitude);
+                }
+            }
+            Instruction::ClearRoute(_)
+            | Instruction::RemoveStandby(_)
+            | Instruction::AppendSegment(_)
+            | Instruction::GrantFlightFollowing(_)
+            | Instruction::BreakupFormation(_)
+            | Instruction::BirdCaution(_)
+            | Instruction::ContactFrequency(_)
+            | Instruction::ClearApproach(_)
+            | Instruction::CrossRunway(_)
+            | Instruction::CycleTransponder(_) => {}
+        }
+    }
+}
+
+/// Acknowledged instructions waiting for the reaction delay of the pilot.
+#[derive(Component, Default)]
+pub struct ReactionQueue(VecDeque<Pending>);
+
+impl ReactionQueue {
+    /// Iterates over the pending instructions in the order they will be executed.
+    pub fn iter(&self) -> impl Iterator<Item = &Instruction> {
+        self.0.iter().map(|pending| &pending.instruction)
+    }
+
+    /// Iterates over the pending instructions with the virtual time at which each is executed.
+    pub fn iter_due(&self) -> impl Iterator<Item = (Duration, &Instruction)> {
+        self.0.iter().map(|pending| (pending.due, &pending.instruction))
+    }
+}
+
+struct Pending {
+    /// Virtual time at which the instruction is executed.
+    due:         Duration,
+    instruction: Instruction,
+}
+
+fn assign_system(
+    conf: ReadConfig<Conf>,
+    object_query: Query<Entity, (With<Object>, Without<Response>)>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    if object_query.is_empty() {
+        return;
+    }
+
+    let conf = conf.read();
+    let rng = rng.get(session::STREAM_PILOT);
+
+    for object in object_query {
+        let reaction_delay = if conf.min_reaction_delay < conf.max_reaction_delay {
+            rng.random_range(conf.min_reaction_delay..conf.max_reaction_delay)
+        } else {
+            conf.min_reaction_delay
+        };
+        let turn_error = conf.max_turn_anticipation_error;
+        let turn_anticipation =
+            if turn_error > 0.0 { 1.0 + rng.random_range(-turn_error..=turn_error) } else { 1.0 };
+        let altitude_capture = if conf.max_altitude_capture_factor > 1.0 {
+            rng.random_range(1.0..=conf.max_altitude_capture_factor)
+        } else {
+            1.0
+        };
+        commands.entity(object).insert(Response {
+            reaction_delay,
+            turn_anticipation,
+            altitude_capture,
+        });
+    }
+}
+
+/// Hands an acknowledged instruction to the pilot of its recipient.
+///
+/// Executes the instruction immediately if the recipient has no reaction delay.
+pub(super) fn acknowledge(entity: EntityWorldMut, instruction: Instruction) {
+    let delay = entity.get::<Response>().map_or(Duration::ZERO, |response| response.reaction_delay);
+    acknowledge_with_delay(entity, instruction, delay);
+}
+
+/// Hands an acknowledged instruction to the pilot of its recipient,
+/// to be executed after `delay` instead of the reaction delay of the pilot.
+///
+/// Used to restore instructions acknowledged before a savefile was written.
+pub(super) fn acknowledge_with_delay(
+    mut entity: EntityWorldMut,
+    instruction: Instruction,
+    delay: Duration,
+) {
+    entity.insert_if_new(Clearance::default());
+    entity.get_mut::<Clearance>().expect("just inserted").acknowledge(&instruction);
+
+    let now = entity.world().resource::<Time<time::Virtual>>().elapsed();
+    if delay.is_zero() {
+        let id = entity.id();
+        entity.world_scope(|world| {
+            instruction.process(&mut world.commands().entity(id));
+            world.flush();
+        });
+        return;
+    }
+
+    entity.insert_if_new(ReactionQueue::default());
+    let mut queue = entity.get_mut::<ReactionQueue>().expect("just inserted");
+    // Instructions are executed in the order they were acknowledged,
+    // even if the reaction delay has changed in between.
+    let due = queue.0.back().map_or(now + delay, |last| (now + delay).max(last.due));
+    queue.0.push_back(Pending { due, instruction });
+}
+
+fn error_system(
+    conf: ReadConfig<Conf>,
+    time: Res<Time<time::Virtual>>,
+    mut object_query: Query<
+        (&Clearance, &mut nav::VelocityTarget, Option<&mut nav::TargetAltitude>),
+        (With<object::Airborne>, Without<nav::TargetGlide>),
+    >,
+    mut rng: session::SessionRng,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let conf = conf.read();
+    let probability = f64::from(conf.error_rate) * time.delta().as_secs_f64() / 3600.0;
+    if probability <= 0.0 {
+        return;
+    }
+    let rng = rng.get(session::STREAM_PILOT_ERROR);
+
+    for (clearance, mut target, target_altitude) in &mut object_query {
+        if !rng.random_bool(probability.min(1.0)) {
+            continue;
+        }
+
+        let altitude_error = target_altitude
+            .filter(|target_altitude| Some(target_altitude.altitude) == clearance.altitude);
+        let heading_error = clearance
+            .heading
+            .filter(|&heading| matches!(target.yaw, YawTarget::Heading(yaw) if yaw.closest_distance(heading).is_zero()));
+
+        match (altitude_error, heading_error) {
+            (Some(mut target_altitude), heading) if heading.is_none() || rng.random() => {
+                let error = if rng.random() { conf.altitude_error } else { -conf.altitude_error };
+                target_altitude.altitude += error;
+            }
+            (_, Some(heading)) => {
+                let max_error = conf.max_heading_error.into_degrees();
+                let error = rng.random_range(max_error / 2.0..=max_error);
+                let error = if rng.random() { error } else { -error };
+                target.yaw = YawTarget::Heading(heading + Angle::from_degrees(error));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn react_system(
+    time: Res<Time<time::Virtual>>,
+    mut object_query: Query<(Entity, &mut ReactionQueue)>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+    for (object, mut queue) in &mut object_query {
+        while let Some(pending) = queue.0.pop_front_if(|pending| pending.due <= now) {
+            pending.instruction.process(&mut commands.entity(object));
+        }
+    }
+}
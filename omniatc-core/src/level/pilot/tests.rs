@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::time::{self, Time};
+use math::{Heading, Position, Speed};
+use store::YawTarget;
+
+use super::{Clearance, ReactionQueue, Response};
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::Object;
+use crate::level::{message, nav, phraseology, pilot, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        pilot::Plug::<()>::default(),
+    ));
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(5000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            message::Sender { display: "ABC123".into() },
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::NORTH),
+                horiz_speed: Speed::from_knots(250.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+        ))
+        .id()
+}
+
+fn target_speed(app: &App, object: Entity) -> Speed<f32> {
+    app.world().get::<nav::VelocityTarget>(object).expect("object has velocity target").horiz_speed
+}
+
+#[test]
+fn test_instruction_executed_after_reaction_delay() {
+    let mut app = base_app();
+    let object = spawn_object(&mut app);
+    app.world_mut()
+        .entity_mut(object)
+        .insert(Response { reaction_delay: Duration::from_secs(3), ..Response::PRECISE });
+
+    let world = app.world_mut();
+    world.commands().send_instruction(object, instr::SetSpeed { target: Speed::from_knots(210.0) });
+    world.flush();
+    app.update();
+
+    assert_eq!(target_speed(&app, object), Speed::from_knots(250.0));
+    assert_eq!(app.world().get::<ReactionQueue>(object).map(|queue| queue.iter().count()), Some(1));
+    let clearance = app.world().get::<Clearance>(object).expect("clearance should be read back");
+    assert_eq!(clearance.speed, Some(Speed::from_knots(210.0)));
+    assert_eq!(clearance.altitude, None);
+
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(3));
+    app.update();
+
+    assert_eq!(target_speed(&app, object), Speed::from_knots(210.0));
+    assert_eq!(app.world().get::<ReactionQueue>(object).map(|queue| queue.iter().count()), Some(0));
+}
+
+#[test]
+fn test_response_sampled_within_configured_range() {
+    let mut app = base_app();
+    let objects: Vec<_> = (0..20).map(|_| spawn_object(&mut app)).collect();
+    app.update();
+
+    for object in objects {
+        let response = app.world().get::<Response>(object).expect("response should be assigned");
+        assert!(
+            (Duration::from_secs(1)..Duration::from_secs(5)).contains(&response.reaction_delay),
+            "reaction delay {:?} out of range",
+            response.reaction_delay
+        );
+        assert!((0.7..=1.3).contains(&response.turn_anticipation));
+        assert!((1.0..=1.5).contains(&response.altitude_capture));
+    }
+}
@@ -0,0 +1,271 @@
+//! Requests initiated by objects, e.g. climb, direct or weather deviation requests.
+//!
+//! A request is represented as a [`message::Message`] entity with a [`Request`] component,
+//! which the user may approve or deny with [`Respond`].
+//! Approving a request sends the corresponding instruction to the requesting object.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Command, Commands, Query, Res};
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Heading, Position};
+use rand::Rng;
+use rand::seq::IteratorRandom;
+use store::YawTarget;
+
+use super::SystemSets;
+use crate::WorldTryLog;
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::{self, Object};
+use crate::level::waypoint::Waypoint;
+use crate::level::{message, nav, radio, route, score, session};
+
+pub mod loader;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:pilot_request");
+        app.init_resource::<Settings>();
+        app.init_resource::<session::Seed>();
+        app.add_systems(
+            app::Update,
+            (generate_system, timeout_system).in_set(SystemSets::Communicate),
+        );
+    }
+}
+
+/// Level-defined settings for request generation.
+#[derive(Resource, Default)]
+pub struct Settings(pub store::PilotRequests);
+
+#[derive(Config)]
+pub struct Conf {
+    /// Multiplier applied to the request frequency defined by the level.
+    ///
+    /// Set to zero to disable pilot requests.
+    #[config(default = 1.0, min = 0.0, max = 10.0)]
+    pub frequency_multiplier: f32,
+    /// An unanswered request is considered denied after this duration.
+    #[config(default = Duration::from_mins(1))]
+    pub response_timeout:     Duration,
+}
+
+/// The object that initiated a request.
+#[derive(Component)]
+#[relationship(relationship_target = RequestList)]
+pub struct Requester(pub Entity);
+
+/// Pending requests initiated by an object.
+#[derive(Component)]
+#[relationship_target(relationship = Requester, linked_spawn)]
+pub struct RequestList(Vec<Entity>);
+
+/// A pending request, attached to the [`message::Message`] entity that displays it.
+#[derive(Component)]
+pub struct Request {
+    /// The requested change.
+    pub body:     Body,
+    /// The request is automatically denied when the [virtual](time::Virtual) time elapsed
+    /// exceeds this duration.
+    pub deadline: Duration,
+}
+
+/// The change requested by an object.
+#[derive(Clone, Copy)]
+pub enum Body {
+    /// Climb to the given altitude.
+    Climb { altitude: Position<f32> },
+    /// Descend to the given altitude.
+    Descend { altitude: Position<f32> },
+    /// Skip the route until the given waypoint.
+    Direct { waypoint: Entity },
+    /// Fly the given heading.
+    Deviation { heading: Heading },
+}
+
+impl Body {
+    fn into_instruction(self) -> instr::Instruction {
+        match self {
+            Body::Climb { altitude } | Body::Descend { altitude } => {
+                instr::SetAltitude { target: nav::TargetAltitude { altitude, expedite: false } }
+                    .into()
+            }
+            Body::Direct { waypoint } => instr::SkipToWaypoint { waypoint }.into(),
+            Body::Deviation { heading } => {
+                instr::SetHeading { target: YawTarget::Heading(heading) }.into()
+            }
+        }
+    }
+}
+
+fn generate_system(
+    conf: ReadConfig<Conf>,
+    settings: Res<Settings>,
+    time: Res<Time<time::Virtual>>,
+    mut rng: session::SessionRng,
+    object_query: Query<
+        (Entity, &Object, Option<&nav::TargetAltitude>, Option<&route::Route>),
+        (
+            With<object::Airborne>,
+            With<nav::VelocityTarget>,
+            Without<RequestList>,
+            Without<radio::Failed>,
+        ),
+    >,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    let Some(mean_interval) = settings.0.mean_interval else { return };
+    if mean_interval.is_zero() || time.delta().is_zero() {
+        return;
+    }
+
+    let rng = rng.get(session::STREAM_PILOT_REQUEST);
+
+    let probability =
+        time.delta().as_secs_f32() / mean_interval.as_secs_f32() * conf.frequency_multiplier;
+    if rng.random::<f32>() >= probability {
+        return;
+    }
+
+    let Some(kind) = settings.0.kinds.sample(rng) else { return };
+    let Some((object_entity, object, target_altitude, route)) = object_query.iter().choose(rng)
+    else {
+        return;
+    };
+
+    let (body, content) = match *kind {
+        store::PilotRequestKind::Climb { delta } => {
+            let base = target_altitude.map_or(object.position.altitude(), |t| t.altitude);
+            let altitude = base + delta;
+            (
+                Body::Climb { altitude },
+                format!("Requesting climb to {:.0} feet", altitude.amsl().into_feet()),
+            )
+        }
+        store::PilotRequestKind::Direct => {
+            let Some(route) = route else { return };
+            let Some(waypoint) = route
+                .iter()
+                .filter_map(|node| match node {
+                    route::Node::DirectWaypoint(node) => Some(node.waypoint),
+                    _ => None,
+                })
+                .skip(1)
+                .choose(rng)
+            else {
+                return;
+            };
+            let Ok(waypoint_data) = waypoint_query.get(waypoint) else { return };
+            (Body::Direct { waypoint }, format!("Requesting direct to {}", waypoint_data.name))
+        }
+        store::PilotRequestKind::Deviation { max_angle } => {
+            let current = object.ground_speed.horizontal().heading();
+            let mut offset = max_angle * rng.random_range(0.5..=1.0);
+            if rng.random() {
+                offset = -offset;
+            }
+            let heading = current + offset;
+            (
+                Body::Deviation { heading },
+                format!("Requesting heading {:03.0} to deviate around weather", heading.degrees()),
+            )
+        }
+    };
+
+    commands.spawn((
+        message::Message {
+            source: object_entity,
+            created: time.elapsed(),
+            content,
+            class: message::Class::NeedAck,
+        },
+        Requester(object_entity),
+        Request { body, deadline: time.elapsed() + conf.response_timeout },
+    ));
+}
+
+fn timeout_system(
+    time: Res<Time<time::Virtual>>,
+    request_query: Query<(Entity, &Request)>,
+    mut commands: Commands,
+) {
+    for (entity, request) in request_query {
+        if time.elapsed() > request.deadline {
+            commands.queue(Expire { request: entity });
+        }
+    }
+}
+
+/// Responds to a pending request.
+///
+/// If approved, the corresponding instruction is sent to the requesting object.
+/// The request entity is despawned in either case.
+pub struct Respond {
+    /// The request entity.
+    pub request: Entity,
+    /// Whether the request is approved.
+    pub approve: bool,
+}
+
+impl Command for Respond {
+    fn apply(self, world: &mut World) {
+        if let Some(object) = respond(world, self.request, self.approve) {
+            session::record_response(world, object, self.approve);
+        }
+    }
+}
+
+/// Denies a request that was not responded to before its deadline.
+struct Expire {
+    request: Entity,
+}
+
+impl Command for Expire {
+    fn apply(self, world: &mut World) { respond(world, self.request, false); }
+}
+
+/// Despawns the request and sends the instruction if approved.
+///
+/// Returns the requesting object, or `None` if the request no longer exists.
+fn respond(world: &mut World, request: Entity, approve: bool) -> Option<Entity> {
+    // The request may have been responded to or timed out in the same frame.
+    let &Request { body, .. } = world.get::<Request>(request)?;
+    let &Requester(object) = world.log_get::<Requester>(request)?;
+    world.entity_mut(request).despawn();
+
+    let mut stats = world.resource_mut::<score::Stats>();
+    if approve {
+        stats.num_pilot_requests_approved += 1;
+    } else {
+        stats.num_pilot_requests_denied += 1;
+        return Some(object);
+    }
+
+    world.commands().send_instruction(object, body.into_instruction()).insert(session::Unrecorded);
+    world.flush();
+    Some(object)
+}
@@ -0,0 +1,7 @@
+use bevy::ecs::world::World;
+
+use crate::level::pilot_request;
+
+pub fn spawn(world: &mut World, settings: &store::PilotRequests) {
+    *world.resource_mut::<pilot_request::Settings>() = pilot_request::Settings(settings.clone());
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::time::{self, Time};
+use math::{Position, Speed};
+
+use super::{Body, Request, Requester, Respond};
+use crate::level::object::Object;
+use crate::level::{instr, message, phraseology, pilot_request, score, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        score::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        pilot_request::Plug::<()>::default(),
+    ));
+    app.update();
+    app
+}
+
+fn spawn_request(app: &mut App, deadline: Duration) -> (Entity, Entity) {
+    let object = app
+        .world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(10000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id();
+    let request = app
+        .world_mut()
+        .spawn((
+            message::Message {
+                source:  object,
+                created: Duration::ZERO,
+                content: "Requesting climb to 12000 feet".into(),
+                class:   message::Class::NeedAck,
+            },
+            Requester(object),
+            Request { body: Body::Climb { altitude: Position::from_amsl_feet(12000.0) }, deadline },
+        ))
+        .id();
+    (object, request)
+}
+
+fn pending_instructions(app: &mut App, object: Entity) -> usize {
+    let mut query = app.world_mut().query::<&instr::Recipient>();
+    query.iter(app.world()).filter(|recipient| recipient.0 == object).count()
+}
+
+#[test]
+fn test_approve_sends_instruction() {
+    let mut app = base_app();
+    let (object, request) = spawn_request(&mut app, Duration::from_mins(1));
+
+    app.world_mut().commands().queue(Respond { request, approve: true });
+    app.world_mut().flush();
+
+    assert!(app.world().get_entity(request).is_err(), "request should be despawned");
+    assert_eq!(pending_instructions(&mut app, object), 1);
+    let stats = app.world().resource::<score::Stats>();
+    assert_eq!(stats.num_pilot_requests_approved, 1);
+    assert_eq!(stats.num_pilot_requests_denied, 0);
+}
+
+#[test]
+fn test_deny_sends_nothing() {
+    let mut app = base_app();
+    let (object, request) = spawn_request(&mut app, Duration::from_mins(1));
+
+    app.world_mut().commands().queue(Respond { request, approve: false });
+    app.world_mut().flush();
+
+    assert!(app.world().get_entity(request).is_err(), "request should be despawned");
+    assert_eq!(pending_instructions(&mut app, object), 0);
+    let stats = app.world().resource::<score::Stats>();
+    assert_eq!(stats.num_pilot_requests_approved, 0);
+    assert_eq!(stats.num_pilot_requests_denied, 1);
+}
+
+#[test]
+fn test_timeout_denies() {
+    let mut app = base_app();
+    let (object, request) = spawn_request(&mut app, Duration::from_secs(10));
+
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(5));
+    app.update();
+    assert!(app.world().get_entity(request).is_ok(), "request should not time out early");
+
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(10));
+    app.update();
+    assert!(app.world().get_entity(request).is_err(), "request should time out");
+    assert_eq!(pending_instructions(&mut app, object), 0);
+    assert_eq!(app.world().resource::<score::Stats>().num_pilot_requests_denied, 1);
+}
@@ -6,21 +6,23 @@
 //! [`SpawnCommand`] does not require inserting [`nav::VelocityTarget`] in advance,
 //! but presence of a `VelocityTarget` allows a plane to be controlled by this plugin.
 
+use std::time::Duration;
+
 use bevy::app::{self, App, Plugin};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::message::Message;
-use bevy::ecs::query::{With, Without};
+use bevy::ecs::query::{Has, QueryData, With, Without};
 use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{EntityCommand, Query, Res};
 use bevy::ecs::world::EntityWorldMut;
-use bevy::math::Quat;
+use bevy::math::{Quat, Vec3};
 use bevy::time::{self, Time};
-use math::{Accel, Angle, AngularSpeed, Heading, TurnDirection};
+use math::{Accel, Angle, AngularSpeed, Heading, Length, TurnDirection};
 use store::YawTarget;
 
 use super::object::Object;
-use super::{SystemSets, nav, object};
+use super::{SystemSets, nav, object, pilot};
 
 pub struct Plug;
 
@@ -28,6 +30,12 @@ impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.add_message::<SpawnMessage>();
         app.add_systems(app::Update, apply_forces_system.in_set(SystemSets::Aviate));
+        app.add_systems(
+            app::Update,
+            correct_position_system
+                .after(object::move_object_system)
+                .in_set(SystemSets::ExecuteEnviron),
+        );
         app.add_systems(
             app::Update,
             rotate_object_system
@@ -39,6 +47,7 @@ impl Plugin for Plug {
 
 /// Mutable states modified by control systems.
 #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+#[require(FrameCorrection)]
 pub struct Control {
     /// Heading of the plane, must be a unit vector.
     /// This is the horizontal direction of the thrust generated.
@@ -57,6 +66,15 @@ impl Control {
     }
 }
 
+/// Displacement not yet accounted for by [`object::Object::ground_speed`] in the current frame.
+///
+/// The object is moved by its final ground speed of each frame,
+/// which misses the change of airspeed during the substeps of a long frame.
+/// This is the difference between the mean and the final airspeed of the frame,
+/// integrated over the frame duration.
+#[derive(Debug, Default, Component)]
+pub struct FrameCorrection(pub Length<Vec3>);
+
 pub struct SpawnCommand {
     pub control: Option<Control>,
     pub limits:  nav::Limits,
@@ -87,6 +105,32 @@ impl EntityCommand for SpawnCommand {
 #[derive(Message)]
 pub struct SpawnMessage(pub Entity);
 
+/// Maximum duration of a single integration step of the control loops.
+///
+/// Frames longer than this, e.g. under time acceleration,
+/// are integrated in multiple equal substeps
+/// so that maneuvers converge identically regardless of the frame duration.
+pub const MAX_SUBSTEP: Duration = Duration::from_millis(20);
+
+/// Splits a frame of duration `dt` into equal substeps no longer than [`MAX_SUBSTEP`].
+///
+/// Returns the number of substeps and the duration of each substep.
+#[must_use]
+pub fn substeps(dt: Duration) -> (u32, Duration) {
+    let steps = dt.as_nanos().div_ceil(MAX_SUBSTEP.as_nanos()).max(1);
+    let steps = u32::try_from(steps).unwrap_or(u32::MAX);
+    (steps, dt / steps)
+}
+
+/// Components to recapture the target altitude in each substep.
+#[derive(QueryData)]
+struct AltitudeCapture {
+    object:   &'static Object,
+    altitude: Option<&'static nav::TargetAltitude>,
+    response: Option<&'static pilot::Response>,
+    glide:    Has<nav::TargetGlide>,
+}
+
 fn apply_forces_system(
     time: Res<Time<time::Virtual>>,
     mut plane_query: Query<(
@@ -94,28 +138,60 @@ fn apply_forces_system(
         &mut Control,
         &nav::Limits,
         &mut object::Airborne,
+        &mut FrameCorrection,
+        AltitudeCapture,
     )>,
 ) {
     if time.is_paused() {
         return;
     }
 
-    plane_query.par_iter_mut().for_each(|(mut target, mut control, limits, mut airborne)| {
-        // All components are always changed. Deref first to avoid borrowck issues.
-        maintain_yaw(&time, &mut target, &mut control, limits, &airborne);
-        maintain_accel(&time, &target, &mut control, limits, &mut airborne);
-        maintain_vert(&time, &target, limits, &mut airborne);
-    });
+    let (steps, dt) = substeps(time.delta());
+    plane_query.par_iter_mut().for_each(
+        |(mut target, mut control, limits, mut airborne, mut correction, capture)| {
+            let tas_ratio = tas_ratio(&airborne);
+            // Integrate the airspeed over the substeps by the trapezoidal rule.
+            let mut displacement = Length::<Vec3>::ZERO;
+            for _ in 0..steps {
+                let initial_airspeed = airborne.airspeed;
+                if let AltitudeCaptureItem {
+                    object: &Object { position, .. },
+                    altitude: Some(altitude),
+                    response,
+                    glide: false,
+                } = capture
+                {
+                    // Recapture the altitude at the estimated position of the substep,
+                    // since the setpoint from the navigation systems is only updated per frame.
+                    target.vert_rate = nav::altitude_setpoint(
+                        altitude,
+                        limits,
+                        response,
+                        position.altitude() + displacement.vertical() * tas_ratio,
+                        airborne.airspeed.vertical() * tas_ratio,
+                        dt,
+                    );
+                }
+                // All components are always changed. Deref first to avoid borrowck issues.
+                maintain_yaw(dt, &mut target, &mut control, limits, &airborne);
+                maintain_accel(dt, &target, &mut control, limits, &mut airborne);
+                maintain_vert(dt, &target, limits, &mut airborne);
+                displacement += (initial_airspeed + airborne.airspeed) * 0.5 * dt;
+            }
+            correction.0 = displacement - airborne.airspeed * time.delta();
+        },
+    );
 }
 
 fn maintain_yaw(
-    time: &Time<time::Virtual>,
+    dt: Duration,
     target: &mut nav::VelocityTarget,
     control: &mut Control,
     limits: &nav::Limits,
     airborne: &object::Airborne,
 ) {
-    let current_yaw = airborne.airspeed.horizontal().heading();
+    // The airspeed has no direction when hovering, in which case the control heading is used.
+    let current_yaw = airborne.airspeed.horizontal().try_heading().unwrap_or(control.heading);
     let mut detect_crossing = None;
     let mut set_yaw_target = None;
 
@@ -132,9 +208,16 @@ fn maintain_yaw(
                 // we are going to overshoot the target heading, start reducing speed now.
                 AngularSpeed::ZERO
             } else {
-                let delta = current_yaw.closest_distance(target_heading);
+                // Keep turning in the current direction if the target is exactly behind,
+                // so that the turn direction does not flip between frames.
+                let tie = if control.yaw_speed.is_negative() {
+                    TurnDirection::CounterClockwise
+                } else {
+                    TurnDirection::Clockwise
+                };
+                let delta = current_yaw.closest_distance_or(target_heading, tie);
                 // desired rate is the turn rate to reach the target heading within this frame
-                let desired_rate = delta / time.delta();
+                let desired_rate = delta / dt;
                 if desired_rate.is_finite() {
                     desired_rate.clamp(-limits.max_yaw_speed, limits.max_yaw_speed)
                 } else {
@@ -164,11 +247,10 @@ fn maintain_yaw(
     };
 
     let delta = desired_yaw_speed - control.yaw_speed;
-    control.yaw_speed +=
-        delta.clamp(-limits.max_yaw_accel * time.delta(), limits.max_yaw_accel * time.delta());
+    control.yaw_speed += delta.clamp(-limits.max_yaw_accel * dt, limits.max_yaw_accel * dt);
 
     {
-        let new_heading = control.heading + control.yaw_speed * time.delta();
+        let new_heading = control.heading + control.yaw_speed * dt;
         if let Some((boundary, counter)) = detect_crossing
             && boundary.is_between(control.heading, new_heading)
         {
@@ -183,7 +265,7 @@ fn maintain_yaw(
 }
 
 fn maintain_accel(
-    time: &Time<time::Virtual>,
+    dt: Duration,
     target: &nav::VelocityTarget,
     control: &mut Control,
     limits: &nav::Limits,
@@ -255,24 +337,22 @@ fn maintain_accel(
     match desired_action {
         ThrottleAction::Increase => {
             // We cannot increase acceleration too quickly to avoid compressor stall.
-            let actual_accel =
-                max_accel.min(control.horiz_accel + limits.accel_change_rate * time.delta());
+            let actual_accel = max_accel.min(control.horiz_accel + limits.accel_change_rate * dt);
             control.horiz_accel = actual_accel;
         }
         ThrottleAction::Decrease => {
             // We cannot decelerate too quickly to avoid compressor stall.
-            let actual_accel =
-                max_decel.max(control.horiz_accel - limits.accel_change_rate * time.delta());
+            let actual_accel = max_decel.max(control.horiz_accel - limits.accel_change_rate * dt);
             control.horiz_accel = actual_accel;
         }
     }
 
-    let new_speed = current_speed + control.horiz_accel * time.delta();
+    let new_speed = current_speed + control.horiz_accel * dt;
     airborne.airspeed = (new_speed * control.heading).with_vertical(airborne.airspeed.vertical());
 }
 
 fn maintain_vert(
-    time: &Time<time::Virtual>,
+    dt: Duration,
     target: &nav::VelocityTarget,
     limits: &nav::Limits,
     airborne: &mut object::Airborne,
@@ -283,12 +363,34 @@ fn maintain_vert(
         target.vert_rate.clamp(limits.std_descent.vert_rate, limits.std_climb.vert_rate)
     };
     let actual_vert_rate = desired_vert_rate.clamp(
-        airborne.airspeed.vertical() - limits.max_vert_accel * time.delta(),
-        airborne.airspeed.vertical() + limits.max_vert_accel * time.delta(),
+        airborne.airspeed.vertical() - limits.max_vert_accel * dt,
+        airborne.airspeed.vertical() + limits.max_vert_accel * dt,
     );
     airborne.airspeed.set_vertical(actual_vert_rate);
 }
 
+fn correct_position_system(
+    time: Res<Time<time::Virtual>>,
+    mut query: Query<(&mut object::Object, &object::Airborne, &mut FrameCorrection)>,
+) {
+    if time.is_paused() {
+        return;
+    }
+
+    query.par_iter_mut().for_each(|(mut object, airborne, mut correction)| {
+        // The correction is in terms of indicated airspeed;
+        // scale it to true airspeed like the ground speed the object was moved by.
+        object.position += correction.0 * tas_ratio(airborne);
+        correction.0 = Length::ZERO;
+    });
+}
+
+/// Ratio of true airspeed to indicated airspeed as of the last weather update.
+fn tas_ratio(airborne: &object::Airborne) -> f32 {
+    let indicated = airborne.airspeed.magnitude_exact();
+    if indicated.is_positive() { airborne.true_airspeed.magnitude_exact() / indicated } else { 1.0 }
+}
+
 fn rotate_object_system(
     mut query: Query<
         (&mut object::Rotation, &object::Object, &Control),
@@ -7,13 +7,17 @@
 //! - Pending: The quest is not yet active
 //!   due to having incomplete dependencies.
 //! - Completed: The quest has been completed.
+//! - Failed: A failure condition of the quest has been fulfilled.
+//!   The quest is retried after all its remedial quests are completed.
 //!
 //! All quests have the [`Quest`] component.
 
 use std::mem;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bevy::app::{self, App, Plugin};
+use bevy::ecs::change_detection::Mut;
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::{Entity, EntityHashSet};
 use bevy::ecs::message::Message;
@@ -21,26 +25,38 @@ use bevy::ecs::query::{Has, QueryData};
 use bevy::ecs::schedule::{IntoScheduleConfigs, SystemSet};
 use bevy::ecs::system::{Command, Commands, Query};
 use bevy::ecs::world::World;
+use bevy::time::{self, Time};
 
 use crate::level::waypoint::Waypoint;
-use crate::level::{SystemSets, object};
+use crate::level::{SystemSets, object, runway, spawn};
 use crate::{WorldTryLog, load};
 
 pub mod condition;
+pub mod failure;
 pub mod highlight;
 pub mod loader;
+pub mod outcome;
+pub mod progress;
+
+#[cfg(test)]
+mod tests;
 
 pub struct Plug;
 
 impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.add_plugins(condition::Plug);
+        app.add_plugins(failure::Plug);
+        app.add_plugins(highlight::Plug);
+        app.add_plugins(outcome::Plug);
         app.add_message::<UiEvent>();
+        app.init_resource::<object::loader::ObjectMap>();
         app.add_systems(
             app::Update,
             manage_active_system
                 .in_set(SystemSets::QuestCompletion)
-                .after(condition::RemovalSystemSet),
+                .after(condition::RemovalSystemSet)
+                .after(failure::DetectionSystemSet),
         );
     }
 }
@@ -62,6 +78,11 @@ pub struct Quest {
     /// and the execution logic typically involves invoking store conversion logic,
     /// such as loading an object from a stored template.
     pub completion_hooks: Vec<store::QuestCompletionHook>,
+
+    /// Completion conditions to restore when the quest is retried after failure.
+    pub conditions:         Vec<store::QuestCompletionCondition>,
+    /// Failure conditions to restore when the quest is retried after failure.
+    pub failure_conditions: Vec<store::QuestFailureCondition>,
 }
 
 #[derive(Component, Default)]
@@ -71,12 +92,23 @@ pub struct Topology {
     pub dependencies: Vec<Entity>,
     /// List of quests that depend on this quest,
     pub dependents:   Vec<Entity>,
+    /// List of quests to activate when this quest fails.
+    pub remedial:     Vec<Entity>,
+    /// List of quests whose failure activates this quest.
+    ///
+    /// If non-empty, this quest is only activated when any of these quests has failed.
+    pub remedial_for: Vec<Entity>,
 }
 
 /// Marker component to indicate that a quest is currently active.
 #[derive(Component)]
 pub struct Active;
 
+/// Marker component to indicate that a quest has failed
+/// and is waiting for its remedial quests to complete.
+#[derive(Component)]
+pub struct Failed;
+
 #[derive(QueryData)]
 #[query_data(mutable)]
 struct ActiveQuestQuery {
@@ -84,6 +116,7 @@ struct ActiveQuestQuery {
     quest:    &'static mut Quest,
     topology: &'static Topology,
     active:   Has<Active>,
+    failed:   Has<Failed>,
     counter:  condition::Counter,
 }
 
@@ -99,12 +132,28 @@ fn manage_active_system(mut commands: Commands, mut query: Query<ActiveQuestQuer
         }
     }
 
+    let failed_quests: EntityHashSet = query
+        .iter()
+        .filter(|data| data.failed && !completed_quests.contains(&data.entity))
+        .map(|data| data.entity)
+        .collect();
+
+    for &entity in &failed_quests {
+        let data = query.get(entity).expect("collected from the same query");
+        if data.topology.remedial.iter().all(|remedial| completed_quests.contains(remedial)) {
+            commands.queue(RetryQuest(entity));
+        }
+    }
+
     let mut active_quests = EntityHashSet::new();
     let mut min_active_index = None;
 
     for data in &query {
         if data.counter.count() > 0
+            && !data.failed
             && data.topology.dependencies.iter().all(|dep| completed_quests.contains(dep))
+            && (data.topology.remedial_for.is_empty()
+                || data.topology.remedial_for.iter().any(|quest| failed_quests.contains(quest)))
         {
             active_quests.insert(data.entity);
 
@@ -140,7 +189,7 @@ impl Command for ExecuteCompletionHook {
                 let route_presets = Arc::clone(&contexts.route_presets);
                 let mut next_standby_id = contexts.next_standby_id;
 
-                if let Err(err) = object::loader::spawn(
+                match object::loader::spawn(
                     world,
                     &aerodromes,
                     &waypoints,
@@ -148,7 +197,10 @@ impl Command for ExecuteCompletionHook {
                     &mut next_standby_id,
                     &object,
                 ) {
-                    bevy::log::error!("Failed to spawn object: {err}");
+                    Ok(entity) => {
+                        world.resource_mut::<object::loader::ObjectMap>().insert(&object, entity);
+                    }
+                    Err(err) => bevy::log::error!("Failed to spawn object: {err}"),
                 }
 
                 world.resource_mut::<load::SpawnContext>().next_standby_id = next_standby_id;
@@ -166,10 +218,96 @@ impl Command for ExecuteCompletionHook {
                     waypoint.hidden = false;
                 }
             }
+            store::QuestCompletionHook::UnlockSpawnSet { set } => {
+                let mut sets = world.resource_mut::<spawn::Sets>();
+                let mut unlocked = false;
+                for entry in &mut sets.0.items {
+                    if entry.item.id.as_ref() == Some(&set) {
+                        entry.item.locked = false;
+                        unlocked = true;
+                    }
+                }
+                if !unlocked {
+                    bevy::log::error!("Unresolved spawn set to unlock: {}", set.0);
+                }
+            }
+            store::QuestCompletionHook::ScaleTrafficRate { factor } => {
+                if !(factor.is_finite() && factor > 0.0) {
+                    bevy::log::error!("Invalid traffic rate factor {factor}");
+                    return;
+                }
+                match *world.resource_mut::<spawn::Trigger>() {
+                    spawn::Trigger::Periodic(ref mut period) => {
+                        match Duration::try_from_secs_f32(period.as_secs_f32() / factor) {
+                            Ok(scaled) => *period = scaled,
+                            Err(err) => bevy::log::error!(
+                                "Cannot scale spawn period {period:?} by {factor}: {err}"
+                            ),
+                        }
+                    }
+                    spawn::Trigger::ObjectCount { ref mut count } => {
+                        #[expect(
+                            clippy::cast_possible_truncation,
+                            clippy::cast_sign_loss,
+                            clippy::cast_precision_loss,
+                            reason = "object counts are small and non-negative"
+                        )]
+                        {
+                            *count = (*count as f32 * factor).round() as usize;
+                        }
+                    }
+                    spawn::Trigger::Disabled | spawn::Trigger::Timetable => {}
+                }
+            }
+            store::QuestCompletionHook::OpenRunway { runway } => {
+                let contexts = world.resource::<load::SpawnContext>();
+                let runway = match contexts.aerodromes.resolve_runway_ref(&runway) {
+                    Ok(runway) => runway.runway.runway,
+                    Err(err) => {
+                        bevy::log::error!("Unresolved runway to open: {err}");
+                        return;
+                    }
+                };
+                let now = world.resource::<Time<time::Virtual>>().elapsed();
+                for mut closure in world.query::<&mut runway::Closure>().iter_mut(world) {
+                    if closure.runway == runway && (closure.start..closure.end).contains(&now) {
+                        closure.end = now;
+                    }
+                }
+            }
         }
     }
 }
 
+/// Restores the conditions of a failed quest so that it can be attempted again.
+struct RetryQuest(Entity);
+
+impl Command for RetryQuest {
+    fn apply(self, world: &mut World) {
+        let aerodromes = Arc::clone(&world.resource::<load::SpawnContext>().aerodromes);
+
+        let Some(quest) = world.log_get::<Quest>(self.0) else { return };
+        let conditions = quest.conditions.clone();
+        let failure_conditions = quest.failure_conditions.clone();
+
+        world.resource_scope(|world, objects: Mut<object::loader::ObjectMap>| {
+            let mut entity = world.entity_mut(self.0);
+            entity.remove::<Failed>();
+            entity.remove::<failure::AllBundle>();
+            for condition in &conditions {
+                if let Err(err) =
+                    loader::insert_condition(&mut entity, condition, &aerodromes, &objects)
+                {
+                    bevy::log::error!("Failed to restore quest condition: {err}");
+                }
+            }
+            for condition in &failure_conditions {
+                loader::insert_failure_condition(&mut entity, condition);
+            }
+        });
+    }
+}
+
 #[derive(Message)]
 pub enum UiEvent {
     CameraDragged,
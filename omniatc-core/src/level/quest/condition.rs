@@ -14,8 +14,8 @@ use store::Score;
 use crate::QueryTryLog;
 use crate::level::instr::Instruction;
 use crate::level::object::{self, Object};
-use crate::level::score::Stats;
-use crate::level::{SystemSets, ground, instr, quest};
+use crate::level::score::{AerodromeStats, Stats};
+use crate::level::{SystemSets, goaround, ground, instr, quest};
 
 pub struct Plug;
 
@@ -36,10 +36,14 @@ impl Plugin for Plug {
                 make_ui_event_system::<UiActionObjectSelect>(|event| {
                     matches!(event, quest::UiEvent::ObjectSelected)
                 }),
-                reach_altitude_system,
-                reach_speed_system,
-                reach_heading_system,
-                reach_segment_system,
+                (
+                    reach_altitude_system,
+                    reach_speed_system,
+                    reach_heading_system,
+                    reach_segment_system,
+                    object_land_system,
+                    object_reach_segment_system,
+                ),
                 make_instr_action_system::<InstrActionDirectWaypoint>(|instr| {
                     matches!(
                         instr,
@@ -75,7 +79,22 @@ impl Plugin for Plug {
                     |cond: &MinDeparture| cond.departures,
                     |stats: &Stats| stats.num_departures,
                 ),
+                (
+                    make_min_aerodrome_stat_system(
+                        |cond: &MinAerodromeLanding| (cond.aerodrome, cond.landings),
+                        |stats: &AerodromeStats| stats.num_runway_arrivals,
+                    ),
+                    make_min_aerodrome_stat_system(
+                        |cond: &MinAerodromeParking| (cond.aerodrome, cond.parkings),
+                        |stats: &AerodromeStats| stats.num_apron_arrivals,
+                    ),
+                    make_min_aerodrome_stat_system(
+                        |cond: &MinAerodromeDeparture| (cond.aerodrome, cond.departures),
+                        |stats: &AerodromeStats| stats.num_departures,
+                    ),
+                ),
                 max_conflicts_system,
+                max_go_arounds_system,
                 time_elapsed_system,
             )
                 .in_set(RemovalSystemSet)
@@ -139,6 +158,8 @@ mod all {
         ReachSpeed,
         ReachHeading,
         ReachSegment,
+        ObjectLand,
+        ObjectReachSegment,
         InstrActionDirectWaypoint,
         InstrActionClearIls,
         InstrActionClearLineUp,
@@ -147,8 +168,12 @@ mod all {
         MinLanding,
         MinParking,
         MinDeparture,
+        MinAerodromeLanding,
+        MinAerodromeParking,
+        MinAerodromeDeparture,
         MinScore,
         MaxConflicts,
+        MaxGoArounds,
         TimeElapsed,
     }
 }
@@ -280,6 +305,51 @@ pub(super) fn reach_segment_system(
     }
 }
 
+/// Completes when a specific object is on the ground on a runway in the given direction.
+#[derive(Component)]
+pub struct ObjectLand {
+    pub object:    Entity,
+    pub label:     ground::SegmentLabel,
+    pub direction: ground::SegmentDirection,
+}
+
+pub(super) fn object_land_system(
+    object_query: Query<&object::OnGround>,
+    quest_query: Query<(Entity, &ObjectLand), With<quest::Active>>,
+    segment_query: Query<&ground::SegmentLabel>,
+    mut commands: Commands,
+) {
+    for (quest_entity, cond) in quest_query {
+        let Ok(ground) = object_query.get(cond.object) else { continue };
+        if ground.direction == cond.direction
+            && segment_query.log_get(ground.segment) == Some(&cond.label)
+        {
+            commands.entity(quest_entity).remove::<ObjectLand>();
+        }
+    }
+}
+
+/// Completes when a specific object is on a segment with the given label.
+#[derive(Component)]
+pub struct ObjectReachSegment {
+    pub object: Entity,
+    pub label:  ground::SegmentLabel,
+}
+
+pub(super) fn object_reach_segment_system(
+    object_query: Query<&object::OnGround>,
+    quest_query: Query<(Entity, &ObjectReachSegment), With<quest::Active>>,
+    segment_query: Query<&ground::SegmentLabel>,
+    mut commands: Commands,
+) {
+    for (quest_entity, cond) in quest_query {
+        let Ok(ground) = object_query.get(cond.object) else { continue };
+        if segment_query.log_get(ground.segment) == Some(&cond.label) {
+            commands.entity(quest_entity).remove::<ObjectReachSegment>();
+        }
+    }
+}
+
 /// Completes when the client sends a direct-to-waypoint instruction.
 #[derive(Component)]
 pub struct InstrActionDirectWaypoint;
@@ -366,6 +436,43 @@ fn make_min_stat_system<Cond: Component, T: Ord>(
     .into_configs()
 }
 
+/// Completes when the number of landings at an aerodrome reaches the given minimum.
+#[derive(Component)]
+pub struct MinAerodromeLanding {
+    pub aerodrome: Entity,
+    pub landings:  u32,
+}
+
+/// Completes when the number of parking arrivals at an aerodrome reaches the given minimum.
+#[derive(Component)]
+pub struct MinAerodromeParking {
+    pub aerodrome: Entity,
+    pub parkings:  u32,
+}
+
+/// Completes when the number of departures from an aerodrome reaches the given minimum.
+#[derive(Component)]
+pub struct MinAerodromeDeparture {
+    pub aerodrome:  Entity,
+    pub departures: u32,
+}
+
+fn make_min_aerodrome_stat_system<Cond: Component>(
+    extract_cond: impl Fn(&Cond) -> (Entity, u32) + Send + Sync + 'static,
+    extract_score: impl Fn(&AerodromeStats) -> u32 + Send + Sync + 'static,
+) -> ScheduleConfigs<ScheduleSystem> {
+    (move |mut params: MinScoreSystemParams<Cond>| {
+        for (entity, cond) in params.query {
+            let (aerodrome, min) = extract_cond(cond);
+            let current = params.scores.aerodromes.get(&aerodrome).map_or(0, &extract_score);
+            if current >= min {
+                params.commands.entity(entity).remove::<Cond>();
+            }
+        }
+    })
+    .into_configs()
+}
+
 /// Completes immediately when the number of conflicts is less than the given maximum.
 /// Never completes otherwise.
 #[derive(Component)]
@@ -385,6 +492,29 @@ fn max_conflicts_system(
     }
 }
 
+/// Completes immediately when the number of go-arounds is less than the given maximum.
+/// Never completes otherwise.
+#[derive(Component)]
+pub struct MaxGoArounds {
+    /// Only count go-arounds with this cause, or all go-arounds if `None`.
+    pub cause:      Option<goaround::Cause>,
+    pub go_arounds: u32,
+}
+
+fn max_go_arounds_system(
+    query: Query<(Entity, &MaxGoArounds), With<quest::Active>>,
+    stats: Res<Stats>,
+    mut commands: Commands,
+) {
+    for (entity, cond) in query {
+        let count =
+            cond.cause.map_or(stats.go_arounds.total(), |cause| stats.go_arounds.count(cause));
+        if count <= cond.go_arounds {
+            commands.entity(entity).remove::<MaxGoArounds>();
+        }
+    }
+}
+
 /// Completes when the elapsed time exceeds the given duration.
 #[derive(Component)]
 pub struct TimeElapsed {
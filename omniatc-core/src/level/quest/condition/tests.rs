@@ -11,7 +11,7 @@ use super::*;
 use crate::level::ground::{self, SegmentLabel};
 use crate::level::instr::{self, Instruction};
 use crate::level::object::{self, Object};
-use crate::level::score::Stats;
+use crate::level::score::{AerodromeStats, Stats};
 use crate::level::{quest, route};
 
 fn create_test_app() -> App {
@@ -25,11 +25,13 @@ fn create_test_app() -> App {
 fn quest_with_condition<C: Component>(condition: C) -> impl Bundle {
     (
         quest::Quest {
-            title:            "Test Quest".into(),
-            description:      "Test Description".into(),
-            class:            store::QuestClass::Tutorial,
-            index:            0,
-            completion_hooks: Vec::new(),
+            title:              "Test Quest".into(),
+            description:        "Test Description".into(),
+            class:              store::QuestClass::Tutorial,
+            index:              0,
+            completion_hooks:   Vec::new(),
+            conditions:         Vec::new(),
+            failure_conditions: Vec::new(),
         },
         quest::Active,
         condition,
@@ -186,6 +188,7 @@ fn test_reach_segment() {
             segment:      initial_segment,
             direction:    ground::SegmentDirection::AlphaToBeta,
             target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+            crossing:     object::RunwayCrossing::None,
         })
         .id();
 
@@ -201,6 +204,86 @@ fn test_reach_segment() {
     assert!(!app.world().entity(quest_entity).contains::<ReachSegment>());
 }
 
+fn spawn_on_ground(app: &mut App, segment: Entity, direction: ground::SegmentDirection) -> Entity {
+    app.world_mut()
+        .spawn(object::OnGround {
+            segment,
+            direction,
+            target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+            crossing: object::RunwayCrossing::None,
+        })
+        .id()
+}
+
+#[test]
+fn test_object_reach_segment() {
+    let mut app = create_test_app();
+
+    let [desired_segment, initial_segment] = ["A", "B"]
+        .map(|name| app.world_mut().spawn(SegmentLabel::Taxiway { name: name.into() }).id());
+
+    let other_object =
+        spawn_on_ground(&mut app, initial_segment, ground::SegmentDirection::AlphaToBeta);
+    let object_entity =
+        spawn_on_ground(&mut app, initial_segment, ground::SegmentDirection::AlphaToBeta);
+
+    let quest_entity = app
+        .world_mut()
+        .spawn(quest_with_condition(ObjectReachSegment {
+            object: object_entity,
+            label:  SegmentLabel::Taxiway { name: "A".into() },
+        }))
+        .id();
+
+    app.world_mut().entity_mut(other_object).get_mut::<object::OnGround>().unwrap().segment =
+        desired_segment;
+    app.update();
+
+    assert!(
+        app.world().entity(quest_entity).contains::<ObjectReachSegment>(),
+        "other objects must not complete the condition",
+    );
+
+    app.world_mut().entity_mut(object_entity).get_mut::<object::OnGround>().unwrap().segment =
+        desired_segment;
+    app.update();
+
+    assert!(!app.world().entity(quest_entity).contains::<ObjectReachSegment>());
+}
+
+#[test]
+fn test_object_land() {
+    let mut app = create_test_app();
+
+    let runways = [app.world_mut().spawn_empty().id(), app.world_mut().spawn_empty().id()];
+    let runway_segment = app.world_mut().spawn(SegmentLabel::RunwayPair(runways)).id();
+
+    let object_entity =
+        spawn_on_ground(&mut app, runway_segment, ground::SegmentDirection::BetaToAlpha);
+
+    let quest_entity = app
+        .world_mut()
+        .spawn(quest_with_condition(ObjectLand {
+            object:    object_entity,
+            label:     SegmentLabel::RunwayPair([runways[1], runways[0]]),
+            direction: ground::SegmentDirection::AlphaToBeta,
+        }))
+        .id();
+
+    app.update();
+
+    assert!(
+        app.world().entity(quest_entity).contains::<ObjectLand>(),
+        "landing in the opposite direction must not complete the condition",
+    );
+
+    app.world_mut().entity_mut(object_entity).get_mut::<object::OnGround>().unwrap().direction =
+        ground::SegmentDirection::AlphaToBeta;
+    app.update();
+
+    assert!(!app.world().entity(quest_entity).contains::<ObjectLand>());
+}
+
 #[test]
 fn test_instr_action_direct_waypoint() {
     let mut app = create_test_app();
@@ -253,9 +336,10 @@ fn test_instr_action_follow_route() {
 
     app.world_mut().spawn(Instruction::SelectRoute(instr::SelectRoute {
         preset: route::Preset {
-            id:    "test-route".into(),
-            title: "Test Route".into(),
-            nodes: Vec::new(),
+            id:       "test-route".into(),
+            title:    "Test Route".into(),
+            nodes:    Vec::new(),
+            equipage: store::Equipage::empty(),
         },
     }));
 
@@ -316,6 +400,43 @@ fn test_min_departure() {
     assert!(!app.world().entity(quest_entity).contains::<MinDeparture>());
 }
 
+#[test]
+fn test_min_aerodrome_landing() {
+    let mut app = create_test_app();
+
+    let aerodrome = app.world_mut().spawn_empty().id();
+    let other_aerodrome = app.world_mut().spawn_empty().id();
+    let quest_entity = app
+        .world_mut()
+        .spawn(quest_with_condition(MinAerodromeLanding { aerodrome, landings: 2 }))
+        .id();
+
+    app.update();
+
+    assert!(app.world().entity(quest_entity).contains::<MinAerodromeLanding>());
+
+    app.world_mut()
+        .resource_mut::<Stats>()
+        .aerodromes
+        .insert(other_aerodrome, AerodromeStats { num_runway_arrivals: 2, ..Default::default() });
+
+    app.update();
+
+    assert!(
+        app.world().entity(quest_entity).contains::<MinAerodromeLanding>(),
+        "landings at other aerodromes should not count"
+    );
+
+    app.world_mut()
+        .resource_mut::<Stats>()
+        .aerodromes
+        .insert(aerodrome, AerodromeStats { num_runway_arrivals: 2, ..Default::default() });
+
+    app.update();
+
+    assert!(!app.world().entity(quest_entity).contains::<MinAerodromeLanding>());
+}
+
 #[test]
 fn test_min_score() {
     let mut app = create_test_app();
@@ -0,0 +1,134 @@
+//! Failure conditions of quests.
+//!
+//! When any failure condition of an active quest is fulfilled,
+//! the quest is marked as [`Failed`](quest::Failed)
+//! and its remedial quests are activated.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::{IntoScheduleConfigs, SystemSet};
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+use math::Position;
+
+use crate::level::object::{self, Object};
+use crate::level::score::Stats;
+use crate::level::{SystemSets, goaround, quest};
+
+#[cfg(test)]
+mod tests;
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            app::Update,
+            (exit_altitude_system, conflict_system, go_around_system, time_limit_system)
+                .in_set(DetectionSystemSet)
+                .in_set(SystemSets::QuestCompletion),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub struct DetectionSystemSet;
+
+/// Bundle of all failure condition components.
+///
+/// Used for `entity.remove()` only.
+#[derive(Bundle)]
+pub struct AllBundle(ExitAltitude, Conflict, GoAround, TimeLimit);
+
+/// Fails when any airborne object leaves the altitude range.
+#[derive(Component)]
+pub struct ExitAltitude {
+    pub min: Position<f32>,
+    pub max: Position<f32>,
+}
+
+fn exit_altitude_system(
+    object_query: Query<&Object, With<object::Airborne>>,
+    quest_query: Query<(Entity, &ExitAltitude), With<quest::Active>>,
+    mut commands: Commands,
+) {
+    for (quest_entity, cond) in quest_query {
+        if object_query
+            .iter()
+            .any(|object| !(cond.min..=cond.max).contains(&object.position.altitude()))
+        {
+            commands.entity(quest_entity).insert(quest::Failed);
+        }
+    }
+}
+
+/// Fails when a new conflict occurs while the quest is active.
+#[derive(Component, Default)]
+pub struct Conflict {
+    /// Number of conflicts when the quest was first observed to be active.
+    pub baseline: Option<u32>,
+}
+
+fn conflict_system(
+    quest_query: Query<(Entity, &mut Conflict), With<quest::Active>>,
+    stats: Res<Stats>,
+    mut commands: Commands,
+) {
+    for (quest_entity, mut cond) in quest_query {
+        let baseline = *cond.baseline.get_or_insert(stats.num_conflicts);
+        if stats.num_conflicts > baseline {
+            commands.entity(quest_entity).insert(quest::Failed);
+        }
+    }
+}
+
+/// Fails when an object goes around while the quest is active.
+#[derive(Component, Default)]
+pub struct GoAround {
+    /// Only consider go-arounds with this cause, or all go-arounds if `None`.
+    pub cause:    Option<goaround::Cause>,
+    /// Number of go-arounds when the quest was first observed to be active.
+    pub baseline: Option<u32>,
+}
+
+fn go_around_system(
+    quest_query: Query<(Entity, &mut GoAround), With<quest::Active>>,
+    stats: Res<Stats>,
+    mut commands: Commands,
+) {
+    for (quest_entity, mut cond) in quest_query {
+        let count =
+            cond.cause.map_or(stats.go_arounds.total(), |cause| stats.go_arounds.count(cause));
+        let baseline = *cond.baseline.get_or_insert(count);
+        if count > baseline {
+            commands.entity(quest_entity).insert(quest::Failed);
+        }
+    }
+}
+
+/// Fails when the quest remains active for longer than the given duration.
+#[derive(Component)]
+pub struct TimeLimit {
+    pub limit:        Duration,
+    /// Elapsed time when the quest was first observed to be active.
+    pub activated_at: Option<Duration>,
+}
+
+fn time_limit_system(
+    quest_query: Query<(Entity, &mut TimeLimit), With<quest::Active>>,
+    time: Res<Time<time::Virtual>>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+    for (quest_entity, mut cond) in quest_query {
+        let activated_at = *cond.activated_at.get_or_insert(now);
+        if now.saturating_sub(activated_at) > cond.limit {
+            commands.entity(quest_entity).insert(quest::Failed);
+        }
+    }
+}
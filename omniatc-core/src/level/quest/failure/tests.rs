@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::entity::Entity;
+use bevy::time::{self, Time};
+use math::{Position, Speed};
+
+use super::{Conflict, ExitAltitude, TimeLimit};
+use crate::level::object::{self, Object};
+use crate::level::quest::{self, condition};
+use crate::level::score::Stats;
+use crate::load;
+
+fn create_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(quest::Plug);
+    app.init_resource::<Time<time::Virtual>>();
+    app.init_resource::<Stats>();
+    app.init_resource::<load::SpawnContext>();
+    app
+}
+
+fn test_quest(index: usize, conditions: Vec<store::QuestCompletionCondition>) -> quest::Quest {
+    quest::Quest {
+        title: "Test Quest".into(),
+        description: "Test Description".into(),
+        class: store::QuestClass::Tutorial,
+        index,
+        completion_hooks: Vec::new(),
+        conditions,
+        failure_conditions: Vec::new(),
+    }
+}
+
+fn reach_altitude_6000() -> store::QuestCompletionCondition {
+    store::ObjectControlQuestCompletionCondition::ReachAltitude(store::Range {
+        min: Position::from_amsl_feet(5950.0),
+        max: Position::from_amsl_feet(6050.0),
+    })
+    .into()
+}
+
+fn spawn_airborne(app: &mut App, altitude_feet: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(altitude_feet)),
+                ground_speed: Speed::ZERO.horizontally(),
+            },
+            object::Airborne {
+                pressure_alt:  Position::from_amsl_feet(altitude_feet),
+                pressure:      math::ISA_SEA_LEVEL_PRESSURE,
+                oat:           math::ISA_SEA_LEVEL_TEMPERATURE,
+                airspeed:      Speed::ZERO.horizontally(),
+                true_airspeed: Speed::ZERO.horizontally(),
+            },
+        ))
+        .id()
+}
+
+fn set_altitude(app: &mut App, object: Entity, altitude_feet: f32) {
+    app.world_mut().get_mut::<Object>(object).expect("object exists").position =
+        Position::from_origin_nm(0.0, 0.0).with_altitude(Position::from_amsl_feet(altitude_feet));
+}
+
+/// Spawns an active quest with the given failure condition,
+/// with a pending remedial quest so that the failure is not retried immediately.
+fn spawn_failable(app: &mut App, failure: impl Bundle) -> Entity {
+    let remedial = app
+        .world_mut()
+        .spawn((
+            test_quest(1, Vec::new()),
+            quest::Topology::default(),
+            condition::TimeElapsed { time: Duration::from_hours(1) },
+        ))
+        .id();
+    app.world_mut()
+        .spawn((
+            test_quest(0, Vec::new()),
+            quest::Topology { remedial: vec![remedial], ..Default::default() },
+            quest::Active,
+            condition::TimeElapsed { time: Duration::from_hours(1) },
+            failure,
+        ))
+        .id()
+}
+
+fn is_active(app: &App, quest: Entity) -> bool {
+    app.world().entity(quest).contains::<quest::Active>()
+}
+
+fn is_failed(app: &App, quest: Entity) -> bool {
+    app.world().entity(quest).contains::<quest::Failed>()
+}
+
+#[test]
+fn test_exit_altitude_activates_remedial_and_retries() {
+    let mut app = create_test_app();
+
+    let mut main_quest = test_quest(0, vec![reach_altitude_6000()]);
+    main_quest.failure_conditions =
+        vec![store::QuestFailureCondition::ExitAltitude(store::Range {
+            min: Position::from_amsl_feet(5000.0),
+            max: Position::from_amsl_feet(8500.0),
+        })];
+    let main_entity = app
+        .world_mut()
+        .spawn((
+            main_quest,
+            quest::Topology::default(),
+            condition::ReachAltitude {
+                min: Position::from_amsl_feet(5950.0),
+                max: Position::from_amsl_feet(6050.0),
+            },
+            ExitAltitude {
+                min: Position::from_amsl_feet(5000.0),
+                max: Position::from_amsl_feet(8500.0),
+            },
+        ))
+        .id();
+    let remedial_entity = app
+        .world_mut()
+        .spawn((
+            test_quest(1, vec![reach_altitude_6000()]),
+            quest::Topology { remedial_for: vec![main_entity], ..Default::default() },
+            condition::ReachAltitude {
+                min: Position::from_amsl_feet(5950.0),
+                max: Position::from_amsl_feet(6050.0),
+            },
+        ))
+        .id();
+    app.world_mut()
+        .get_mut::<quest::Topology>(main_entity)
+        .expect("spawned above")
+        .remedial
+        .push(remedial_entity);
+
+    let object = spawn_airborne(&mut app, 8000.0);
+    app.update();
+    assert!(is_active(&app, main_entity));
+    assert!(!is_active(&app, remedial_entity), "remedial quest is only activated on failure");
+
+    set_altitude(&mut app, object, 4000.0);
+    app.update();
+    app.update();
+    assert!(is_failed(&app, main_entity));
+    assert!(!is_active(&app, main_entity));
+    assert!(is_active(&app, remedial_entity));
+
+    set_altitude(&mut app, object, 6000.0);
+    app.update();
+    app.update();
+    assert!(!app.world().entity(remedial_entity).contains::<condition::ReachAltitude>());
+    assert!(!is_failed(&app, main_entity), "quest is retried after remedial completion");
+    assert!(
+        app.world().entity(main_entity).contains::<ExitAltitude>(),
+        "failure conditions are restored on retry",
+    );
+}
+
+#[test]
+fn test_conflict_fails_only_on_new_conflict() {
+    let mut app = create_test_app();
+    app.world_mut().resource_mut::<Stats>().num_conflicts = 3;
+
+    let quest_entity = spawn_failable(&mut app, Conflict::default());
+
+    app.update();
+    assert!(!is_failed(&app, quest_entity));
+
+    app.world_mut().resource_mut::<Stats>().num_conflicts = 4;
+    app.update();
+    assert!(is_failed(&app, quest_entity));
+}
+
+#[test]
+fn test_time_limit() {
+    let mut app = create_test_app();
+
+    let quest_entity =
+        spawn_failable(&mut app, TimeLimit { limit: Duration::from_mins(2), activated_at: None });
+
+    app.update();
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_mins(1));
+    app.update();
+    assert!(!is_failed(&app, quest_entity));
+
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_mins(2));
+    app.update();
+    assert!(is_failed(&app, quest_entity));
+}
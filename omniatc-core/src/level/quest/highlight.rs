@@ -1,8 +1,26 @@
 //! When a quest with these components is the first active quest,
 //! highlight corresponding UI elements.
 
+use bevy::app::{self, App, Plugin};
 use bevy::ecs::bundle::Bundle;
 use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::Query;
+
+use crate::level::{SystemSets, object};
+
+#[cfg(test)]
+mod tests;
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(app::Update, resolve_object_system.in_set(SystemSets::QuestCompletion));
+    }
+}
 
 #[derive(Bundle)]
 pub struct All(RadarView, ObjectSelect, SetAltitude, SetSpeed, SetHeading);
@@ -34,3 +52,35 @@ pub struct SetSpeed;
 /// UI for setting the heading target.
 #[derive(Component)]
 pub struct SetHeading;
+
+/// A specific object on the radar viewport.
+#[derive(Component)]
+pub struct Object {
+    /// Name of the object to highlight.
+    pub name:   String,
+    /// The highlighted object, if an object with the name currently exists.
+    pub entity: Option<Entity>,
+}
+
+/// A specific waypoint on the radar viewport.
+#[derive(Component)]
+pub struct Waypoint(pub Entity);
+
+fn resolve_object_system(
+    highlight_query: Query<&mut Object>,
+    object_query: Query<(Entity, &object::Display), With<object::Object>>,
+) {
+    for mut highlight in highlight_query {
+        if highlight.entity.is_some_and(|entity| object_query.contains(entity)) {
+            continue;
+        }
+
+        let resolved = object_query
+            .iter()
+            .find(|(_, display)| display.name == highlight.name)
+            .map(|(entity, _)| entity);
+        if highlight.entity != resolved {
+            highlight.entity = resolved;
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Position, Speed};
+
+use super::Object as ObjectHighlight;
+use crate::level::object::{self, Object};
+
+fn create_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(super::Plug);
+    app
+}
+
+fn spawn_object(app: &mut App, name: &str) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(5000.0)),
+                ground_speed: Speed::ZERO.horizontally(),
+            },
+            object::Display { name: name.into() },
+        ))
+        .id()
+}
+
+fn resolved(app: &App, quest: Entity) -> Option<Entity> {
+    app.world().get::<ObjectHighlight>(quest).expect("highlight is retained").entity
+}
+
+#[test]
+fn test_resolve_object_spawned_later() {
+    let mut app = create_test_app();
+    let quest = app.world_mut().spawn(ObjectHighlight { name: "ABC123".into(), entity: None }).id();
+
+    spawn_object(&mut app, "DEF456");
+    app.update();
+    assert_eq!(resolved(&app, quest), None);
+
+    let object = spawn_object(&mut app, "ABC123");
+    app.update();
+    assert_eq!(resolved(&app, quest), Some(object));
+
+    app.world_mut().entity_mut(object).despawn();
+    app.update();
+    assert_eq!(resolved(&app, quest), None);
+}
@@ -6,7 +6,9 @@ use bevy::ecs::name::Name;
 use bevy::ecs::world::{EntityWorldMut, World};
 
 use crate::level::aerodrome::loader::AerodromeMap;
-use crate::level::quest::{self, Quest, condition};
+use crate::level::object::loader::ObjectMap;
+use crate::level::quest::{self, Quest, condition, failure};
+use crate::level::waypoint::loader::WaypointMap;
 use crate::load::{self, StoredEntity};
 
 /// Spawns quest entities from stored data.
@@ -17,6 +19,8 @@ pub fn spawn(
     world: &mut World,
     tree: &store::QuestTree,
     aerodromes: &AerodromeMap,
+    waypoints: &WaypointMap,
+    objects: &ObjectMap,
 ) -> load::Result {
     let quests: HashMap<_, _> = tree
         .quests
@@ -26,11 +30,15 @@ pub fn spawn(
             let mut entity = world.spawn(spawn_quest(quest, index));
 
             for condition in &quest.conditions {
-                insert_condition(&mut entity, condition, aerodromes)?;
+                insert_condition(&mut entity, condition, aerodromes, objects)?;
+            }
+
+            for condition in &quest.failure_conditions {
+                insert_failure_condition(&mut entity, condition);
             }
 
             for highlight in &quest.ui_highlight {
-                insert_highlight(&mut entity, highlight);
+                insert_highlight(&mut entity, highlight, waypoints)?;
             }
 
             Ok((&quest.id, (entity.id(), quest)))
@@ -38,6 +46,7 @@ pub fn spawn(
         .collect::<load::Result<_>>()?;
 
     populate_deps(world, &quests)?;
+    world.insert_resource(quest::outcome::Outcome::default());
 
     Ok(())
 }
@@ -50,6 +59,8 @@ fn spawn_quest(quest: &store::Quest, index: usize) -> impl Bundle {
             class: quest.class,
             index,
             completion_hooks: quest.completion_hooks.clone(),
+            conditions: quest.conditions.clone(),
+            failure_conditions: quest.failure_conditions.clone(),
         },
         quest::Topology::default(),
         Name::new("Quest"),
@@ -57,10 +68,11 @@ fn spawn_quest(quest: &store::Quest, index: usize) -> impl Bundle {
     )
 }
 
-fn insert_condition(
+pub(super) fn insert_condition(
     entity: &mut EntityWorldMut,
     condition: &store::QuestCompletionCondition,
     segments: &AerodromeMap,
+    objects: &ObjectMap,
 ) -> load::Result {
     match condition {
         store::QuestCompletionCondition::Ui(condition) => match condition {
@@ -77,36 +89,9 @@ fn insert_condition(
                 entity.insert(condition::UiActionObjectSelect);
             }
         },
-        store::QuestCompletionCondition::ObjectControl(condition) => match condition {
-            store::ObjectControlQuestCompletionCondition::ReachAltitude(range) => {
-                entity.insert(condition::ReachAltitude { min: range.min, max: range.max });
-            }
-            store::ObjectControlQuestCompletionCondition::ReachSpeed(range) => {
-                entity.insert(condition::ReachSpeed { min: range.min, max: range.max });
-            }
-            store::ObjectControlQuestCompletionCondition::ReachHeading(range) => {
-                entity.insert(condition::ReachHeading { min: range.min, max: range.max });
-            }
-            store::ObjectControlQuestCompletionCondition::DirectToWaypoint => {
-                entity.insert(condition::InstrActionDirectWaypoint);
-            }
-            store::ObjectControlQuestCompletionCondition::ClearIls => {
-                entity.insert(condition::InstrActionClearIls);
-            }
-            store::ObjectControlQuestCompletionCondition::TaxiSegment(segment) => {
-                let label = segments.resolve_segment(segment)?;
-                entity.insert(condition::ReachSegment { label });
-            }
-            store::ObjectControlQuestCompletionCondition::ClearLineUp => {
-                entity.insert(condition::InstrActionClearLineUp);
-            }
-            store::ObjectControlQuestCompletionCondition::ClearTakeoff => {
-                entity.insert(condition::InstrActionClearTakeoff);
-            }
-            store::ObjectControlQuestCompletionCondition::FollowRoute => {
-                entity.insert(condition::InstrActionFollowRoute);
-            }
-        },
+        store::QuestCompletionCondition::ObjectControl(condition) => {
+            insert_object_control_condition(entity, condition, segments, objects)?;
+        }
         store::QuestCompletionCondition::Statistic(condition) => match *condition {
             store::StatisticQuestCompletionCondition::MinLanding(landings) => {
                 entity.insert(condition::MinLanding { landings });
@@ -117,12 +102,36 @@ fn insert_condition(
             store::StatisticQuestCompletionCondition::MinDeparture(departures) => {
                 entity.insert(condition::MinDeparture { departures });
             }
+            store::StatisticQuestCompletionCondition::AerodromeMinLanding {
+                ref aerodrome,
+                landings,
+            } => {
+                let aerodrome = segments.resolve(aerodrome)?.aerodrome_entity;
+                entity.insert(condition::MinAerodromeLanding { aerodrome, landings });
+            }
+            store::StatisticQuestCompletionCondition::AerodromeMinParking {
+                ref aerodrome,
+                parkings,
+            } => {
+                let aerodrome = segments.resolve(aerodrome)?.aerodrome_entity;
+                entity.insert(condition::MinAerodromeParking { aerodrome, parkings });
+            }
+            store::StatisticQuestCompletionCondition::AerodromeMinDeparture {
+                ref aerodrome,
+                departures,
+            } => {
+                let aerodrome = segments.resolve(aerodrome)?.aerodrome_entity;
+                entity.insert(condition::MinAerodromeDeparture { aerodrome, departures });
+            }
             store::StatisticQuestCompletionCondition::MinScore(score) => {
                 entity.insert(condition::MinScore { score });
             }
             store::StatisticQuestCompletionCondition::MaxConflicts(conflicts) => {
                 entity.insert(condition::MaxConflicts { conflicts });
             }
+            store::StatisticQuestCompletionCondition::MaxGoArounds { cause, go_arounds } => {
+                entity.insert(condition::MaxGoArounds { cause, go_arounds });
+            }
             store::StatisticQuestCompletionCondition::TimeElapsed(time) => {
                 entity.insert(condition::TimeElapsed { time });
             }
@@ -132,7 +141,85 @@ fn insert_condition(
     Ok(())
 }
 
-fn insert_highlight(entity: &mut EntityWorldMut, highlight: &store::HighlightableUiElement) {
+fn insert_object_control_condition(
+    entity: &mut EntityWorldMut,
+    condition: &store::ObjectControlQuestCompletionCondition,
+    segments: &AerodromeMap,
+    objects: &ObjectMap,
+) -> load::Result {
+    match condition {
+        store::ObjectControlQuestCompletionCondition::ReachAltitude(range) => {
+            entity.insert(condition::ReachAltitude { min: range.min, max: range.max });
+        }
+        store::ObjectControlQuestCompletionCondition::ReachSpeed(range) => {
+            entity.insert(condition::ReachSpeed { min: range.min, max: range.max });
+        }
+        store::ObjectControlQuestCompletionCondition::ReachHeading(range) => {
+            entity.insert(condition::ReachHeading { min: range.min, max: range.max });
+        }
+        store::ObjectControlQuestCompletionCondition::DirectToWaypoint => {
+            entity.insert(condition::InstrActionDirectWaypoint);
+        }
+        store::ObjectControlQuestCompletionCondition::ClearIls => {
+            entity.insert(condition::InstrActionClearIls);
+        }
+        store::ObjectControlQuestCompletionCondition::TaxiSegment(segment) => {
+            let label = segments.resolve_segment(segment)?;
+            entity.insert(condition::ReachSegment { label });
+        }
+        store::ObjectControlQuestCompletionCondition::ClearLineUp => {
+            entity.insert(condition::InstrActionClearLineUp);
+        }
+        store::ObjectControlQuestCompletionCondition::ClearTakeoff => {
+            entity.insert(condition::InstrActionClearTakeoff);
+        }
+        store::ObjectControlQuestCompletionCondition::FollowRoute => {
+            entity.insert(condition::InstrActionFollowRoute);
+        }
+        store::ObjectControlQuestCompletionCondition::ObjectLand { object, runway } => {
+            let object = objects.resolve(object)?;
+            let runway = segments.resolve_runway_ref(runway)?;
+            entity.insert(condition::ObjectLand {
+                object,
+                label: runway.to_segment_label(),
+                direction: runway.direction,
+            });
+        }
+        store::ObjectControlQuestCompletionCondition::ObjectTaxiSegment { object, segment } => {
+            let object = objects.resolve(object)?;
+            let label = segments.resolve_segment(segment)?;
+            entity.insert(condition::ObjectReachSegment { object, label });
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn insert_failure_condition(
+    entity: &mut EntityWorldMut,
+    condition: &store::QuestFailureCondition,
+) {
+    match *condition {
+        store::QuestFailureCondition::ExitAltitude(ref range) => {
+            entity.insert(failure::ExitAltitude { min: range.min, max: range.max });
+        }
+        store::QuestFailureCondition::Conflict => {
+            entity.insert(failure::Conflict::default());
+        }
+        store::QuestFailureCondition::GoAround(cause) => {
+            entity.insert(failure::GoAround { cause, baseline: None });
+        }
+        store::QuestFailureCondition::TimeLimit(limit) => {
+            entity.insert(failure::TimeLimit { limit, activated_at: None });
+        }
+    }
+}
+
+fn insert_highlight(
+    entity: &mut EntityWorldMut,
+    highlight: &store::HighlightableUiElement,
+    waypoints: &WaypointMap,
+) -> load::Result {
     match highlight {
         store::HighlightableUiElement::RadarView => {
             entity.insert(quest::highlight::RadarView);
@@ -155,7 +242,15 @@ fn insert_highlight(entity: &mut EntityWorldMut, highlight: &store::Highlightabl
         store::HighlightableUiElement::SetHeading => {
             entity.insert(quest::highlight::SetHeading);
         }
+        store::HighlightableUiElement::Aircraft(name) => {
+            entity.insert(quest::highlight::Object { name: name.clone(), entity: None });
+        }
+        store::HighlightableUiElement::Waypoint(waypoint) => {
+            entity.insert(quest::highlight::Waypoint(waypoints.resolve(waypoint)?));
+        }
     }
+
+    Ok(())
 }
 
 fn populate_deps(
@@ -181,6 +276,25 @@ fn populate_deps(
                 .dependents
                 .push(entity);
         }
+
+        for remedial in &quest.on_fail {
+            let Some(&(remedial_entity, _)) = quests.get(remedial) else {
+                return Err(load::Error::UnresolvedQuest(remedial.0.clone()));
+            };
+
+            world
+                .entity_mut(entity)
+                .get_mut::<quest::Topology>()
+                .expect("inserted during HashMap construction")
+                .remedial
+                .push(remedial_entity);
+            world
+                .entity_mut(remedial_entity)
+                .get_mut::<quest::Topology>()
+                .expect("inserted during HashMap construction")
+                .remedial_for
+                .push(entity);
+        }
     }
     Ok(())
 }
@@ -0,0 +1,100 @@
+//! Resolution of the scenario from its terminal quests.
+//!
+//! A terminal quest is a quest that no other quest depends on
+//! and that is not a remedial quest of another quest.
+//! The scenario succeeds when all terminal quests are completed,
+//! and fails when a terminal quest fails without any remedial quests to recover from.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, Res, ResMut};
+use bevy::time::{self, Time};
+
+use crate::level::{SystemSets, quest};
+
+#[cfg(test)]
+mod tests;
+
+pub(super) struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Outcome>();
+        app.add_systems(
+            app::Update,
+            resolve_system
+                .in_set(SystemSets::QuestCompletion)
+                .after(quest::condition::RemovalSystemSet)
+                .after(quest::failure::DetectionSystemSet)
+                .before(quest::manage_active_system),
+        );
+    }
+}
+
+/// The resolution state of the current scenario.
+///
+/// Reset when a level is loaded.
+/// Once resolved, the outcome remains unchanged even if the quest states change afterwards.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The scenario has no terminal quests or they are not resolved yet.
+    #[default]
+    Pending,
+    /// All terminal quests have been completed.
+    Succeeded {
+        /// Elapsed level time at resolution.
+        time: Duration,
+    },
+    /// A terminal quest has failed without any remedial quests.
+    Failed {
+        /// Elapsed level time at resolution.
+        time: Duration,
+    },
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn is_resolved(self) -> bool { !matches!(self, Outcome::Pending) }
+}
+
+#[derive(QueryData)]
+struct TerminalQuery {
+    topology: &'static quest::Topology,
+    failed:   Has<quest::Failed>,
+    counter:  quest::condition::Counter,
+}
+
+fn resolve_system(
+    mut outcome: ResMut<Outcome>,
+    query: Query<TerminalQuery>,
+    time: Res<Time<time::Virtual>>,
+) {
+    if outcome.is_resolved() {
+        return;
+    }
+
+    let mut has_terminal = false;
+    let mut all_completed = true;
+    for data in &query {
+        if !data.topology.dependents.is_empty() || !data.topology.remedial_for.is_empty() {
+            continue;
+        }
+        has_terminal = true;
+
+        if data.failed && data.topology.remedial.is_empty() {
+            *outcome = Outcome::Failed { time: time.elapsed() };
+            return;
+        }
+        if data.counter.count() > 0 {
+            all_completed = false;
+        }
+    }
+
+    if has_terminal && all_completed {
+        *outcome = Outcome::Succeeded { time: time.elapsed() };
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::time::{self, Time};
+
+use super::Outcome;
+use crate::level::quest::{self, condition, failure};
+use crate::level::score::Stats;
+use crate::load;
+
+fn create_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(quest::Plug);
+    app.init_resource::<Time<time::Virtual>>();
+    app.init_resource::<Stats>();
+    app.init_resource::<load::SpawnContext>();
+    app
+}
+
+fn test_quest(index: usize) -> quest::Quest {
+    quest::Quest {
+        title: "Test Quest".into(),
+        description: "Test Description".into(),
+        class: store::QuestClass::Achievement,
+        index,
+        completion_hooks: Vec::new(),
+        conditions: Vec::new(),
+        failure_conditions: Vec::new(),
+    }
+}
+
+/// Spawns a quest completing at the given elapsed time.
+fn spawn_timed(app: &mut App, index: usize, time: Duration, topology: quest::Topology) -> Entity {
+    app.world_mut().spawn((test_quest(index), topology, condition::TimeElapsed { time })).id()
+}
+
+fn advance_to(app: &mut App, time: Duration) {
+    let mut virtual_time = app.world_mut().resource_mut::<Time<time::Virtual>>();
+    let delta = time.saturating_sub(virtual_time.elapsed());
+    virtual_time.advance_by(delta);
+    app.update();
+}
+
+fn outcome(app: &App) -> Outcome { *app.world().resource::<Outcome>() }
+
+#[test]
+fn test_no_quests_remain_pending() {
+    let mut app = create_test_app();
+    app.update();
+    assert_eq!(outcome(&app), Outcome::Pending);
+}
+
+#[test]
+fn test_succeeds_when_terminal_quests_complete() {
+    let mut app = create_test_app();
+    let first = spawn_timed(&mut app, 0, Duration::from_mins(1), quest::Topology::default());
+    let second = spawn_timed(
+        &mut app,
+        1,
+        Duration::from_mins(2),
+        quest::Topology { dependencies: vec![first], ..Default::default() },
+    );
+    app.world_mut()
+        .get_mut::<quest::Topology>(first)
+        .expect("spawned above")
+        .dependents
+        .push(second);
+
+    advance_to(&mut app, Duration::from_mins(1));
+    app.update();
+    assert_eq!(outcome(&app), Outcome::Pending, "non-terminal quest completion does not resolve");
+
+    advance_to(&mut app, Duration::from_mins(2));
+    app.update();
+    assert!(matches!(outcome(&app), Outcome::Succeeded { .. }));
+}
+
+#[test]
+fn test_fails_on_unrecoverable_terminal_failure() {
+    let mut app = create_test_app();
+    let quest_entity =
+        spawn_timed(&mut app, 0, Duration::from_hours(1), quest::Topology::default());
+    app.world_mut()
+        .entity_mut(quest_entity)
+        .insert(failure::TimeLimit { limit: Duration::from_mins(1), activated_at: None });
+
+    app.update(); // activate the quest
+    app.update(); // start the time limit
+    advance_to(&mut app, Duration::from_mins(2));
+    assert_eq!(outcome(&app), Outcome::Failed { time: Duration::from_mins(2) });
+
+    advance_to(&mut app, Duration::from_hours(2));
+    assert!(
+        matches!(outcome(&app), Outcome::Failed { .. }),
+        "outcome does not change once resolved",
+    );
+}
@@ -0,0 +1,162 @@
+//! Numeric progress of quest conditions for display purposes.
+//!
+//! Only conditions with a measurable target contribute progress.
+//! Binary conditions such as UI actions are only reflected in the [`Counter`](condition::Counter).
+
+use std::time::Duration;
+
+use bevy::ecs::query::QueryData;
+use store::Score;
+
+use crate::level::quest::{condition, failure};
+use crate::level::score::{AerodromeStats, Stats};
+
+#[cfg(test)]
+mod tests;
+
+/// Progress of a single unfulfilled quest condition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Progress {
+    /// A counter approaching a target number.
+    Count { label: &'static str, current: u32, target: u32 },
+    /// The score approaching a target score.
+    Score { current: Score, target: Score },
+    /// A countdown towards completion or failure.
+    Countdown {
+        label:     &'static str,
+        remaining: Duration,
+        total:     Duration,
+        /// Whether the quest fails when the countdown expires.
+        failing:   bool,
+    },
+}
+
+impl Progress {
+    /// The completed fraction of the progress, between 0 and 1.
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        let fraction = match *self {
+            Progress::Count { current, target, .. } => {
+                if target == 0 {
+                    1.0
+                } else {
+                    #[expect(clippy::cast_precision_loss, reason = "counts are small")]
+                    {
+                        current as f32 / target as f32
+                    }
+                }
+            }
+            Progress::Score { current, target } => {
+                if target.0 <= 0 {
+                    1.0
+                } else {
+                    #[expect(clippy::cast_precision_loss, reason = "scores are small")]
+                    {
+                        current.0 as f32 / target.0 as f32
+                    }
+                }
+            }
+            Progress::Countdown { remaining, total, .. } => {
+                if total.is_zero() {
+                    1.0
+                } else {
+                    1.0 - remaining.as_secs_f32() / total.as_secs_f32()
+                }
+            }
+        };
+        fraction.clamp(0.0, 1.0)
+    }
+}
+
+/// Reads the numeric progress of the remaining conditions of a quest.
+///
+/// This `QueryData` does not filter entities.
+#[derive(QueryData)]
+pub struct ConditionQuery {
+    min_landing:             Option<&'static condition::MinLanding>,
+    min_parking:             Option<&'static condition::MinParking>,
+    min_departure:           Option<&'static condition::MinDeparture>,
+    min_aerodrome_landing:   Option<&'static condition::MinAerodromeLanding>,
+    min_aerodrome_parking:   Option<&'static condition::MinAerodromeParking>,
+    min_aerodrome_departure: Option<&'static condition::MinAerodromeDeparture>,
+    min_score:               Option<&'static condition::MinScore>,
+    time_elapsed:            Option<&'static condition::TimeElapsed>,
+    time_limit:              Option<&'static failure::TimeLimit>,
+}
+
+impl ConditionQueryItem<'_, '_> {
+    /// Lists the progress of each measurable condition.
+    ///
+    /// `now` is the elapsed virtual time of the level.
+    #[must_use]
+    pub fn list(&self, stats: &Stats, now: Duration) -> Vec<Progress> {
+        let aerodrome_stat = |aerodrome, extract: fn(&AerodromeStats) -> u32| {
+            stats.aerodromes.get(&aerodrome).map_or(0, extract)
+        };
+
+        let mut list = Vec::new();
+        if let Some(cond) = self.min_landing {
+            list.push(Progress::Count {
+                label:   "Landings",
+                current: stats.num_runway_arrivals,
+                target:  cond.landings,
+            });
+        }
+        if let Some(cond) = self.min_parking {
+            list.push(Progress::Count {
+                label:   "Parkings",
+                current: stats.num_apron_arrivals,
+                target:  cond.parkings,
+            });
+        }
+        if let Some(cond) = self.min_departure {
+            list.push(Progress::Count {
+                label:   "Departures",
+                current: stats.num_departures,
+                target:  cond.departures,
+            });
+        }
+        if let Some(cond) = self.min_aerodrome_landing {
+            list.push(Progress::Count {
+                label:   "Aerodrome landings",
+                current: aerodrome_stat(cond.aerodrome, |s| s.num_runway_arrivals),
+                target:  cond.landings,
+            });
+        }
+        if let Some(cond) = self.min_aerodrome_parking {
+            list.push(Progress::Count {
+                label:   "Aerodrome parkings",
+                current: aerodrome_stat(cond.aerodrome, |s| s.num_apron_arrivals),
+                target:  cond.parkings,
+            });
+        }
+        if let Some(cond) = self.min_aerodrome_departure {
+            list.push(Progress::Count {
+                label:   "Aerodrome departures",
+                current: aerodrome_stat(cond.aerodrome, |s| s.num_departures),
+                target:  cond.departures,
+            });
+        }
+        if let Some(cond) = self.min_score {
+            list.push(Progress::Score { current: stats.total, target: cond.score });
+        }
+        if let Some(cond) = self.time_elapsed {
+            list.push(Progress::Countdown {
+                label:     "Time",
+                remaining: cond.time.saturating_sub(now),
+                total:     cond.time,
+                failing:   false,
+            });
+        }
+        if let Some(cond) = self.time_limit {
+            let elapsed = cond.activated_at.map_or(Duration::ZERO, |t| now.saturating_sub(t));
+            list.push(Progress::Countdown {
+                label:     "Time limit",
+                remaining: cond.limit.saturating_sub(elapsed),
+                total:     cond.limit,
+                failing:   true,
+            });
+        }
+        list
+    }
+}
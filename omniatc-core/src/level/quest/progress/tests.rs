@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use bevy::ecs::world::World;
+use store::Score;
+
+use super::{ConditionQuery, Progress};
+use crate::level::quest::{condition, failure};
+use crate::level::score::Stats;
+
+fn list_progress(world: &mut World, stats: &Stats, now: Duration) -> Vec<Progress> {
+    let mut query = world.query::<ConditionQuery>();
+    let item = query.single(world).expect("one quest spawned");
+    item.list(stats, now)
+}
+
+#[test]
+fn test_count_and_score() {
+    let mut world = World::new();
+    world.spawn((
+        condition::MinLanding { landings: 10 },
+        condition::MinScore { score: Score(100) },
+        condition::UiActionCameraDrag,
+    ));
+
+    let stats = Stats { num_runway_arrivals: 7, total: Score(25), ..Default::default() };
+    let list = list_progress(&mut world, &stats, Duration::ZERO);
+    assert_eq!(
+        list,
+        [
+            Progress::Count { label: "Landings", current: 7, target: 10 },
+            Progress::Score { current: Score(25), target: Score(100) },
+        ]
+    );
+    assert!((list[0].fraction() - 0.7).abs() < 1e-6);
+    assert!((list[1].fraction() - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_countdown() {
+    let mut world = World::new();
+    world.spawn((
+        condition::TimeElapsed { time: Duration::from_mins(20) },
+        failure::TimeLimit {
+            limit:        Duration::from_mins(10),
+            activated_at: Some(Duration::from_mins(5)),
+        },
+    ));
+
+    let list = list_progress(&mut world, &Stats::default(), Duration::from_mins(7));
+    assert_eq!(
+        list,
+        [
+            Progress::Countdown {
+                label:     "Time",
+                remaining: Duration::from_mins(13),
+                total:     Duration::from_mins(20),
+                failing:   false,
+            },
+            Progress::Countdown {
+                label:     "Time limit",
+                remaining: Duration::from_mins(8),
+                total:     Duration::from_mins(10),
+                failing:   true,
+            },
+        ]
+    );
+    assert!((list[1].fraction() - 0.2).abs() < 1e-6);
+}
+
+#[test]
+fn test_fraction_is_clamped() {
+    let progress = Progress::Count { label: "Departures", current: 12, target: 10 };
+    assert!((progress.fraction() - 1.0).abs() < f32::EPSILON);
+
+    let progress = Progress::Score { current: Score(-50), target: Score(100) };
+    assert!(progress.fraction().abs() < f32::EPSILON);
+}
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::time::{self, Time};
+
+use crate::level::quest::{self, condition};
+use crate::level::score::Stats;
+use crate::level::spawn;
+use crate::load;
+
+fn create_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(quest::Plug);
+    app.init_resource::<Time<time::Virtual>>();
+    app.init_resource::<Stats>();
+    app.init_resource::<load::SpawnContext>();
+    app.init_resource::<spawn::Sets>();
+    app
+}
+
+fn spawn_set(id: &str, locked: bool) -> spawn::Set {
+    spawn::Set {
+        gen_name: store::WeightedList::default(),
+        types: store::WeightedList::default(),
+        route: store::WeightedList::default(),
+        position: store::WeightedList::default(),
+        endurance: None,
+        vfr: None,
+        formation: None,
+        id: Some(id.into()),
+        locked,
+    }
+}
+
+/// Spawns a quest that completes after one minute and executes the given hooks.
+fn spawn_quest(app: &mut App, hooks: Vec<store::QuestCompletionHook>) {
+    app.world_mut().spawn((
+        quest::Quest {
+            title:              "Stage".into(),
+            description:        String::new(),
+            class:              store::QuestClass::Achievement,
+            index:              0,
+            completion_hooks:   hooks,
+            conditions:         Vec::new(),
+            failure_conditions: Vec::new(),
+        },
+        quest::Topology::default(),
+        condition::TimeElapsed { time: Duration::from_mins(1) },
+    ));
+}
+
+fn complete(app: &mut App) {
+    app.update();
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_mins(2));
+    app.update();
+    app.update();
+}
+
+#[test]
+fn test_unlock_spawn_set() {
+    let mut app = create_test_app();
+    app.world_mut().resource_mut::<spawn::Sets>().0 = store::WeightedList::from([
+        (spawn_set("arrivals", false), 1.0),
+        (spawn_set("departures", true), 1.0),
+    ]);
+    spawn_quest(
+        &mut app,
+        vec![store::QuestCompletionHook::UnlockSpawnSet { set: "departures".into() }],
+    );
+
+    app.update();
+    let locked = |app: &App| -> Vec<bool> {
+        app.world()
+            .resource::<spawn::Sets>()
+            .0
+            .items
+            .iter()
+            .map(|entry| entry.item.locked)
+            .collect()
+    };
+    assert_eq!(locked(&app), [false, true]);
+
+    complete(&mut app);
+    assert_eq!(locked(&app), [false, false]);
+}
+
+#[test]
+fn test_scale_traffic_rate() {
+    let mut app = create_test_app();
+    app.insert_resource(spawn::Trigger::Periodic(Duration::from_mins(3)));
+    spawn_quest(&mut app, vec![store::QuestCompletionHook::ScaleTrafficRate { factor: 1.5 }]);
+
+    complete(&mut app);
+    let spawn::Trigger::Periodic(period) = *app.world().resource::<spawn::Trigger>() else {
+        panic!("trigger mode should be preserved");
+    };
+    assert_eq!(period, Duration::from_mins(2));
+}
+
+#[test]
+fn test_scale_traffic_rate_overflow() {
+    let mut app = create_test_app();
+    app.insert_resource(spawn::Trigger::Periodic(Duration::from_mins(3)));
+    spawn_quest(&mut app, vec![store::QuestCompletionHook::ScaleTrafficRate { factor: 1e-38 }]);
+
+    complete(&mut app);
+    let spawn::Trigger::Periodic(period) = *app.world().resource::<spawn::Trigger>() else {
+        panic!("trigger mode should be preserved");
+    };
+    assert_eq!(period, Duration::from_mins(3));
+}
+
+#[test]
+fn test_scale_object_count() {
+    let mut app = create_test_app();
+    app.insert_resource(spawn::Trigger::ObjectCount { count: 5 });
+    spawn_quest(&mut app, vec![store::QuestCompletionHook::ScaleTrafficRate { factor: 1.5 }]);
+
+    complete(&mut app);
+    let spawn::Trigger::ObjectCount { count } = *app.world().resource::<spawn::Trigger>() else {
+        panic!("trigger mode should be preserved");
+    };
+    assert_eq!(count, 8);
+}
@@ -0,0 +1,158 @@
+//! Radio communication failures.
+//!
+//! Airborne objects occasionally lose radio communication.
+//! A [`Failed`] object squawks [`SQUAWK`] and no longer replies to instructions,
+//! which are reported as unanswered like calls on the wrong frequency.
+//! Following lost-communication rules, the object maintains its last clearance
+//! for [`Conf::lost_comm_delay`] and then resumes its route,
+//! skipping all standby points that would otherwise wait for a clearance.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use rand::Rng;
+
+use super::{SystemSets, drift, instr, message, object, route, session};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:radio");
+        app.init_resource::<session::Seed>();
+        app.add_systems(app::Update, failure_system.in_set(SystemSets::Aviate));
+        app.add_systems(
+            app::Update,
+            no_reply_system.before(instr::dispatch_system).in_set(SystemSets::Communicate),
+        );
+        app.add_systems(app::Update, lost_comm_system.in_set(SystemSets::Action));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Expected number of radio failures per hour an object spends airborne.
+    #[config(default = 0.02, min = 0.0, max = 10.0)]
+    pub failure_rate:    f32,
+    /// Duration for which an object maintains its last clearance before resuming its route.
+    #[config(default = Duration::from_mins(3), min = Duration::ZERO, max = Duration::from_mins(15))]
+    pub lost_comm_delay: Duration,
+}
+
+/// Transponder code squawked by objects with a radio failure.
+pub const SQUAWK: u16 = 7600;
+
+/// Duration for which an unanswered instruction is displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(30);
+
+/// Marks an object whose radio has failed.
+#[derive(Component, Default)]
+pub struct Failed {
+    /// Unpaused time elapsed since the failure.
+    pub elapsed: Duration,
+    /// Whether the object has resumed its route under lost-communication rules.
+    pub resumed: bool,
+}
+
+fn failure_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    object_query: Query<Entity, (With<object::Airborne>, Without<Failed>, Without<drift::Drifter>)>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let conf = conf.read();
+    let probability = f64::from(conf.failure_rate) * time.delta().as_secs_f64() / 3600.0;
+    if probability <= 0.0 {
+        return;
+    }
+    let rng = rng.get(session::STREAM_RADIO);
+
+    for object in object_query {
+        if !rng.random_bool(probability.min(1.0)) {
+            continue;
+        }
+
+        commands.entity(object).insert(Failed::default());
+        commands.queue(message::SendExpiring {
+            source:   object,
+            content:  format!("Squawking {SQUAWK}, radio failure"),
+            class:    message::Class::Urgent,
+            duration: Duration::from_secs(30),
+        });
+    }
+}
+
+/// Discards instructions transmitted to objects with a radio failure.
+fn no_reply_system(
+    time: Res<Time<time::Virtual>>,
+    mut instr_query: Query<
+        (Entity, &instr::Recipient, &mut message::Message),
+        With<instr::Instruction>,
+    >,
+    failed_query: Query<(), With<Failed>>,
+    mut commands: Commands,
+) {
+    for (entity, &instr::Recipient(recipient), mut message) in &mut instr_query {
+        if !failed_query.contains(recipient) {
+            continue;
+        }
+
+        message.class = message::Class::AnomalyInfo;
+        message.content.push_str(" (no reply)");
+        commands
+            .entity(entity)
+            .remove::<(
+                instr::Instruction,
+                instr::Recipient,
+                instr::TransmitDelay,
+                instr::PendingAck,
+                instr::DispatchAfter,
+            )>()
+            .insert(message::Expiry { expiry: time.elapsed() + MESSAGE_DURATION });
+    }
+}
+
+/// Resumes the route of objects that have maintained their last clearance for long enough.
+fn lost_comm_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    object_query: Query<(Entity, &mut Failed)>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (object, mut failed) in object_query {
+        if failed.resumed {
+            continue;
+        }
+        failed.elapsed += time.delta();
+        if failed.elapsed < conf.lost_comm_delay {
+            continue;
+        }
+
+        failed.resumed = true;
+        commands.entity(object).queue(route::RemoveAllStandby);
+    }
+}
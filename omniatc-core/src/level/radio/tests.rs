@@ -0,0 +1,103 @@
+use std::num::NonZero;
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position, Speed};
+
+use super::Failed;
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::{self, Object};
+use crate::level::route::{self, Route};
+use crate::level::test_util::{self, advance};
+use crate::level::{bird, message, phraseology, radio};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        radio::Plug::<()>::default(),
+    ));
+    app.update();
+    app
+}
+
+fn spawn_airborne(app: &mut App) -> Entity {
+    let altitude = Position::from_amsl_feet(3000.0);
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(altitude),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            object::Display { name: "ABC123".into() },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id()
+}
+
+#[test]
+fn test_failure_after_long_exposure() {
+    let mut app = base_app();
+    let object = spawn_airborne(&mut app);
+
+    // The failure probability saturates over a long enough exposure.
+    advance(&mut app, Duration::from_hours(100));
+
+    assert!(app.world().get::<Failed>(object).is_some());
+}
+
+#[test]
+fn test_failed_object_does_not_reply() {
+    let mut app = base_app();
+    let object = spawn_airborne(&mut app);
+    app.world_mut().entity_mut(object).insert(Failed::default());
+
+    let world = app.world_mut();
+    world.commands().send_instruction(object, instr::BirdCaution);
+    world.flush();
+    advance(&mut app, Duration::from_secs(10));
+
+    assert!(app.world().get::<bird::Cautioned>(object).is_none());
+    let world = app.world_mut();
+    let replies: Vec<_> = world
+        .query::<&message::Message>()
+        .iter(world)
+        .filter(|message| message.content.starts_with("ABC123,"))
+        .map(|message| (message.content.clone(), message.class))
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].0.ends_with("(no reply)"), "unexpected message {:?}", replies[0].0);
+    assert_eq!(replies[0].1, message::Class::AnomalyInfo);
+}
+
+#[test]
+fn test_route_resumed_after_lost_comm_delay() {
+    let mut app = base_app();
+    let object = spawn_airborne(&mut app);
+    let route: Route = [
+        route::StandbyNode { skip_id: None }.into(),
+        route::StandbyNode { skip_id: NonZero::new(1) }.into(),
+    ]
+    .into_iter()
+    .collect();
+    app.world_mut().entity_mut(object).insert((route, Failed::default()));
+
+    advance(&mut app, Duration::from_mins(1));
+    let route = app.world().get::<Route>(object).expect("route is retained");
+    assert!(matches!(route.current(), Some(route::Node::Standby(_))));
+
+    advance(&mut app, Duration::from_mins(3));
+    let route = app.world().get::<Route>(object).expect("route is retained");
+    assert!(route.iter().all(|node| !matches!(node, route::Node::Standby(_))));
+    assert!(app.world().get::<Failed>(object).is_some_and(|failed| failed.resumed));
+}
@@ -0,0 +1,237 @@
+//! Emergency vehicle response to runway incidents.
+//!
+//! When an object with a declared [`bird::Emergency`] lands,
+//! or an object enters a runway without clearance,
+//! the runway pair is closed for [`Conf::closure_duration`]
+//! and an emergency [`Vehicle`] is dispatched from the nearest apron.
+//! The vehicle follows the taxi planner route to the runway segment of the incident
+//! and stays at the scene until the runway reopens.
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::name::Name;
+use bevy::ecs::query::{Added, With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Command, Commands, Query, Res, SystemState};
+use bevy::ecs::world::World;
+use bevy::math::Vec2;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Position, Speed};
+
+use super::{SystemSets, bird, crossing, ground, message, object, route, runway};
+use crate::load::StoredEntity;
+use crate::try_log::WorldExt;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:rescue");
+        app.add_systems(
+            app::Update,
+            (emergency_landing_system, incursion_system).in_set(SystemSets::Statistics),
+        );
+        app.add_systems(app::Update, move_system.in_set(SystemSets::Aviate));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Duration for which the runway is closed after an incident.
+    #[config(default = Duration::from_mins(10), min = Duration::ZERO, max = Duration::from_hours(1))]
+    pub closure_duration: Duration,
+    /// Driving speed of emergency vehicles.
+    #[config(default = Speed::from_knots(30.0), min = Speed::from_knots(5.0), max = Speed::from_knots(80.0))]
+    pub vehicle_speed:    Speed<f32>,
+}
+
+/// Marks an object whose incident has already been responded to.
+#[derive(Component)]
+pub struct Attended;
+
+/// An emergency vehicle responding to a runway incident.
+#[derive(Component)]
+pub struct Vehicle {
+    /// Current position of the vehicle.
+    pub position:  Position<Vec2>,
+    /// Remaining points to drive through, ending at the incident position.
+    pub waypoints: VecDeque<Position<Vec2>>,
+    /// Virtual time at which the vehicle leaves the scene.
+    pub release:   Duration,
+}
+
+fn emergency_landing_system(
+    object_query: Query<
+        Entity,
+        (Added<object::OnGround>, With<bird::Emergency>, Without<Attended>),
+    >,
+    mut commands: Commands,
+) {
+    for object in object_query {
+        commands.queue(Dispatch { object });
+    }
+}
+
+fn incursion_system(
+    mut reader: MessageReader<crossing::IncursionMessage>,
+    attended_query: Query<(), With<Attended>>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        if !attended_query.contains(message.object) {
+            commands.queue(Dispatch { object: message.object });
+        }
+    }
+}
+
+fn move_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    vehicle_query: Query<(Entity, &mut Vehicle)>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+    let now = time.elapsed();
+    for (entity, mut vehicle) in vehicle_query {
+        if now >= vehicle.release {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let mut remaining = conf.vehicle_speed * time.delta();
+        while let Some(&next) = vehicle.waypoints.front() {
+            let distance = vehicle.position.distance_exact(next);
+            if distance > remaining {
+                let direction = next - vehicle.position;
+                vehicle.position += direction.normalize_to_magnitude(remaining);
+                break;
+            }
+            remaining -= distance;
+            vehicle.position = next;
+            vehicle.waypoints.pop_front();
+        }
+    }
+}
+
+/// Closes the runway at the position of an object and dispatches an emergency vehicle to it.
+///
+/// Does nothing if the object is not on a runway segment.
+pub struct Dispatch {
+    pub object: Entity,
+}
+
+impl Command for Dispatch {
+    fn apply(self, world: &mut World) {
+        let Some(&object::OnGround { segment, .. }) = world.log_get(self.object) else { return };
+        let Some(&ground::SegmentLabel::RunwayPair(runways)) = world.log_get(segment) else {
+            return;
+        };
+        let Some(&ground::SegmentOf(aerodrome)) = world.log_get(segment) else { return };
+        let Some(incident) = world.log_get::<object::Object>(self.object) else { return };
+        let incident = incident.position.horizontal();
+
+        let now = world.resource::<Time<time::Virtual>>().elapsed();
+        let mut state = SystemState::<ReadConfig<Conf>>::new(world);
+        let release = now + state.get(world).read().closure_duration;
+
+        world.entity_mut(self.object).insert(Attended);
+        for runway in runways {
+            world.spawn((
+                StoredEntity,
+                Name::new("Runway closure: emergency response"),
+                runway::Closure {
+                    runway,
+                    kind: store::ClosureKind::Closed,
+                    start: now,
+                    end: release,
+                },
+            ));
+        }
+
+        let Some(waypoints) = plan_route(world, aerodrome, segment, incident) else {
+            bevy::log::warn!(
+                "No route for emergency vehicles to runway incident of {:?}",
+                self.object
+            );
+            return;
+        };
+        let Some(&position) = waypoints.front() else { return };
+        world.spawn((
+            StoredEntity,
+            Name::new("Emergency vehicle"),
+            Vehicle { position, waypoints, release },
+        ));
+
+        message::SendExpiring {
+            source:   self.object,
+            content:  "Emergency vehicles responding".into(),
+            class:    message::Class::AnomalyInfo,
+            duration: Duration::from_secs(30),
+        }
+        .apply(world);
+    }
+}
+
+/// Plans the route from the apron nearest to `incident` to the incident on `runway_segment`.
+fn plan_route(
+    world: &World,
+    aerodrome: Entity,
+    runway_segment: Entity,
+    incident: Position<Vec2>,
+) -> Option<VecDeque<Position<Vec2>>> {
+    let endpoint_position =
+        |endpoint: Entity| world.log_get::<ground::Endpoint>(endpoint).map(|e| e.position);
+
+    let (_, apron) = world
+        .log_get::<ground::AerodromeSegments>(aerodrome)?
+        .segments()
+        .iter()
+        .filter(|&&segment| {
+            world.get::<ground::SegmentLabel>(segment).is_some_and(ground::SegmentLabel::is_apron)
+        })
+        .filter_map(|&segment| {
+            let apron = world.log_get::<ground::Segment>(segment)?;
+            let distance = endpoint_position(apron.alpha)?.distance_squared(incident).0;
+            Some((distance, (segment, apron)))
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))?;
+    let (apron_segment, &ground::Segment { alpha: station, beta: exit, .. }) = apron;
+
+    let label = world.log_get::<ground::SegmentLabel>(runway_segment)?;
+    let path = route::pathfind_through_subseq(
+        world,
+        apron_segment,
+        exit,
+        &[route::SubseqItem { label, direction: None }],
+        route::PathfindMode::Segment(runway_segment),
+        route::PathfindOptions { initial_speed: None, min_width: None },
+    )?;
+
+    // The path ends by traversing the runway segment,
+    // but the vehicle stops at the incident position on it.
+    let (_, approach) = path.endpoints.split_last()?;
+    let mut waypoints = VecDeque::with_capacity(approach.len() + 2);
+    waypoints.push_back(endpoint_position(station)?);
+    for &endpoint in approach {
+        waypoints.push_back(endpoint_position(endpoint)?);
+    }
+    waypoints.push_back(incident);
+    Some(waypoints)
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+use math::{Length, Position, Speed};
+
+use super::{Attended, Vehicle};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::{bird, crossing, ground, message, rescue, runway};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, rescue::Plug::<()>::default()));
+    app.add_message::<crossing::IncursionMessage>();
+    app.update();
+    app
+}
+
+struct Prepared {
+    runways:        [Entity; 2],
+    runway_segment: Entity,
+}
+
+/// ```text
+///        R (1, 2)
+///        |
+///        | runway
+///        |
+/// T ---- E (1, 0)
+/// |
+/// A (0, -0.5)
+/// ```
+fn prepare_ground(world: &mut World) -> Prepared {
+    let aerodrome = world.spawn_empty().id();
+    let runways = [world.spawn_empty().id(), world.spawn_empty().id()];
+
+    let mut commands = world.commands();
+    let [apron, taxiway, entry, runway_end] = [(0.0, -0.5), (0.0, 0.0), (1.0, 0.0), (1.0, 2.0)]
+        .map(|(x, y)| {
+            commands
+                .spawn_empty()
+                .queue(ground::SpawnEndpoint {
+                    position: Position::from_origin_nm(x, y),
+                    aerodrome,
+                })
+                .id()
+        });
+
+    let [_, _, runway_segment] = [
+        (apron, taxiway, ground::SegmentLabel::Apron { name: "1".into() }),
+        (taxiway, entry, ground::SegmentLabel::Taxiway { name: "A".into() }),
+        (entry, runway_end, ground::SegmentLabel::RunwayPair(runways)),
+    ]
+    .map(|(alpha, beta, label)| {
+        commands
+            .spawn_empty()
+            .queue(ground::SpawnSegment {
+                segment: ground::Segment {
+                    alpha,
+                    beta,
+                    width: Length::from_meters(50.0),
+                    max_speed: Speed::from_knots(30.0),
+                    elevation: Position::SEA_LEVEL,
+                },
+                label,
+                aerodrome,
+                display_label: false,
+            })
+            .id()
+    });
+    world.flush();
+
+    Prepared { runways, runway_segment }
+}
+
+fn spawn_on_runway(world: &mut World, segment: Entity) -> Entity {
+    world
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(1.0, 1.0).with_altitude(Position::SEA_LEVEL),
+                ground_speed: Speed::ZERO,
+            },
+            object::OnGround {
+                segment,
+                direction: ground::SegmentDirection::AlphaToBeta,
+                target_speed: object::OnGroundTargetSpeed::Exact(Speed::ZERO),
+                crossing: object::RunwayCrossing::None,
+            },
+        ))
+        .id()
+}
+
+fn find_vehicle(app: &mut App) -> Option<&Vehicle> {
+    app.world_mut().query::<&Vehicle>().iter(app.world()).next()
+}
+
+fn closed_runways(app: &mut App) -> Vec<Entity> {
+    let now = app.world().resource::<Time<time::Virtual>>().elapsed();
+    app.world_mut()
+        .query::<&runway::Closure>()
+        .iter(app.world())
+        .filter(|closure| (closure.start..closure.end).contains(&now))
+        .map(|closure| closure.runway)
+        .collect()
+}
+
+#[test]
+fn test_emergency_landing_dispatches_vehicle() {
+    let mut app = base_app();
+    let prepared = prepare_ground(app.world_mut());
+    let object = spawn_on_runway(app.world_mut(), prepared.runway_segment);
+    app.world_mut().entity_mut(object).insert(bird::Emergency);
+    app.update();
+
+    assert!(app.world().get::<Attended>(object).is_some());
+    let mut closed = closed_runways(&mut app);
+    closed.sort();
+    let mut expected = prepared.runways.to_vec();
+    expected.sort();
+    assert_eq!(closed, expected, "both directions of the runway should be closed");
+
+    let vehicle = find_vehicle(&mut app).expect("vehicle should be dispatched");
+    assert!(
+        vehicle.position.distance_exact(Position::from_origin_nm(0.0, -0.5))
+            < Length::from_nm(0.01)
+    );
+    let expected_waypoints = [(0.0, -0.5), (0.0, 0.0), (1.0, 0.0), (1.0, 1.0)];
+    assert_eq!(vehicle.waypoints.len(), expected_waypoints.len());
+    for (&actual, (x, y)) in vehicle.waypoints.iter().zip(expected_waypoints) {
+        assert!(actual.distance_exact(Position::from_origin_nm(x, y)) < Length::from_nm(0.01));
+    }
+
+    // 2.5 nm at 30 kt takes 5 minutes.
+    advance(&mut app, Duration::from_mins(6));
+    let vehicle = find_vehicle(&mut app).expect("vehicle should stay at the scene");
+    assert!(vehicle.waypoints.is_empty());
+    assert!(
+        vehicle.position.distance_exact(Position::from_origin_nm(1.0, 1.0)) < Length::from_nm(0.01)
+    );
+
+    advance(&mut app, Duration::from_mins(5));
+    assert!(find_vehicle(&mut app).is_none(), "vehicle should leave when the runway reopens");
+    assert!(closed_runways(&mut app).is_empty());
+}
+
+#[test]
+fn test_incursion_dispatches_once() {
+    let mut app = base_app();
+    let prepared = prepare_ground(app.world_mut());
+    let object = spawn_on_runway(app.world_mut(), prepared.runway_segment);
+
+    for _ in 0..2 {
+        app.world_mut()
+            .write_message(crossing::IncursionMessage { object, runway: prepared.runways[0] });
+        app.update();
+    }
+
+    let vehicles = app.world_mut().query::<&Vehicle>().iter(app.world()).count();
+    assert_eq!(vehicles, 1);
+    assert_eq!(closed_runways(&mut app).len(), 2);
+}
+
+#[test]
+fn test_normal_landing_ignored() {
+    let mut app = base_app();
+    let prepared = prepare_ground(app.world_mut());
+    spawn_on_runway(app.world_mut(), prepared.runway_segment);
+    app.update();
+
+    assert!(find_vehicle(&mut app).is_none());
+    assert!(closed_runways(&mut app).is_empty());
+}
@@ -18,6 +18,8 @@ use crate::level::object::{self, GroundSpeedCalculator, Object, RefAltitudeType}
 use crate::level::{SystemSets, nav};
 use crate::{EntityMutTryLog, WorldTryLog};
 
+mod deice;
+pub use deice::*;
 mod landing;
 pub use landing::*;
 mod navigation;
@@ -30,6 +32,11 @@ pub use taxi::*;
 
 pub mod loader;
 
+#[cfg(test)]
+mod proptests;
+#[cfg(test)]
+mod tests;
+
 /// Horizontal distance before the point at which
 /// an object must start changing altitude at standard rate
 /// in order to reach the required configured altitude set in the future.
@@ -51,6 +58,7 @@ impl Plugin for Plug {
                 trigger::distance_system,
                 trigger::navaid_system,
                 trigger::taxi_target_resolution_system,
+                conditional_speed_system,
             )
                 .in_set(SystemSets::Action),
         );
@@ -124,6 +132,10 @@ impl Route {
         self.current.iter().chain(self.next_queue.iter())
     }
 
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Node> {
+        self.current.iter_mut().chain(self.next_queue.iter_mut())
+    }
+
     #[must_use]
     pub fn get(&self, index: usize) -> Option<&Node> {
         match index.checked_sub(1) {
@@ -212,6 +224,53 @@ impl EntityCommand for RemoveStandby {
     }
 }
 
+/// Removes all standby nodes from the route,
+/// so that the object continues along its route without further clearances.
+pub struct RemoveAllStandby;
+
+impl EntityCommand for RemoveAllStandby {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let Some(mut route) = entity.log_get_mut::<Route>() else { return };
+
+        route.next_queue.retain(|node| !matches!(node, Node::Standby(_)));
+        if matches!(route.current(), Some(Node::Standby(_))) {
+            route.shift();
+
+            let entity_id = entity.id();
+            entity.world_scope(|world| run_current_node(world, entity_id));
+        }
+    }
+}
+
+/// Skips all nodes before the first [`DirectWaypointNode`] towards the given waypoint.
+///
+/// Does nothing if the route does not fly towards the waypoint.
+pub struct SkipToWaypoint {
+    pub waypoint: Entity,
+}
+
+impl EntityCommand for SkipToWaypoint {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let Some(mut route) = entity.log_get_mut::<Route>() else { return };
+
+        let Some(index) = route.iter().position(
+            |node| matches!(node, Node::DirectWaypoint(node) if node.waypoint == self.waypoint),
+        ) else {
+            return;
+        };
+        if index == 0 {
+            return;
+        }
+
+        for _ in 0..index {
+            route.shift();
+        }
+
+        let entity_id = entity.id();
+        entity.world_scope(|world| run_current_node(world, entity_id));
+    }
+}
+
 /// Recompute the triggers for the route, used after the entire route got replaced.
 pub struct RunCurrentNode;
 
@@ -292,6 +351,15 @@ fn run_current_node(world: &mut World, entity: Entity) {
                     RunNodeResult::ReplaceWithNodes(new_nodes) => {
                         replace_route(world, entity, new_nodes);
                     }
+                    RunNodeResult::PrependNodes(new_nodes) => {
+                        let mut entity_ref = world.entity_mut(entity);
+                        let mut route = entity_ref
+                            .get_mut::<Route>()
+                            .expect("route should not be removed by run_current_node");
+                        for node in new_nodes.into_iter().rev() {
+                            route.prepend(node);
+                        }
+                    }
                 },
             }
         };
@@ -517,6 +585,8 @@ enum RunNodeResult {
     ReplaceWithPreset(Option<Entity>),
     /// The entire route should be aborted and replaced with the specified nodes.
     ReplaceWithNodes(Vec<Node>),
+    /// The specified nodes should be executed before retrying the current node.
+    PrependNodes(Vec<Node>),
 }
 
 /// The horizontal direction to navigate towards.
@@ -549,6 +619,7 @@ pub enum Node {
     VisualLanding(VisualLandingNode),
     Takeoff(TakeoffNode),
     Taxi(TaxiNode),
+    Deice(DeiceNode),
 }
 
 /// Stay in this node until explicitly completed by user command.
@@ -581,11 +652,17 @@ impl NodeKind for StandbyNode {
 
 #[derive(Component, Clone)]
 pub struct Preset {
-    pub id:    String,
-    pub title: String,
-    pub nodes: Vec<Node>,
+    pub id:       String,
+    pub title:    String,
+    pub nodes:    Vec<Node>,
+    /// Equipment required to follow this preset.
+    pub equipage: store::Equipage,
 }
 
+/// The [`ref_id`](store::RoutePreset::ref_id) of a [`Preset`], if it has one.
+#[derive(Component)]
+pub struct PresetRef(pub store::RoutePresetRef);
+
 #[derive(Component)]
 #[relationship(relationship_target = WaypointPresetList)]
 pub struct PresetFromWaypoint(pub Entity);
@@ -604,20 +681,41 @@ pub struct DestinationMatcher {
 }
 
 impl DestinationMatcher {
+    /// Whether the preset may be used by an object with destination `dest`.
     #[must_use]
     pub fn matches(&self, dest: &Destination) -> bool {
-        self.items.iter().any(|item| item.matches(dest))
+        self.items.iter().any(|item| item.target.matches(dest))
     }
+
+    /// Whether the preset should be assigned to a spawned object with destination `dest`,
+    /// where `is_active` tells whether a runway configuration entity is active.
+    #[must_use]
+    pub fn applies(&self, dest: &Destination, is_active: impl Fn(Entity) -> bool) -> bool {
+        self.items.iter().any(|item| {
+            item.target.matches(dest)
+                && (item.configurations.is_empty()
+                    || item.configurations.iter().any(|&configuration| is_active(configuration)))
+        })
+    }
+}
+
+pub struct DestinationMatcherItem {
+    pub target:         DestinationTarget,
+    /// [Runway configurations](super::runway::Configuration) under which
+    /// spawned objects are assigned the preset.
+    ///
+    /// If empty, the preset applies under any configuration.
+    pub configurations: Vec<Entity>,
 }
 
-pub enum DestinationMatcherItem {
+pub enum DestinationTarget {
     Arrival { aerodrome: Entity },
     AnyArrival,
     Departure { waypoint: Entity },
     AnyDeparture,
 }
 
-impl DestinationMatcherItem {
+impl DestinationTarget {
     #[must_use]
     pub fn matches(&self, dest: &Destination) -> bool {
         #[expect(clippy::match_same_arms, reason = "simple value")]
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::Command;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+use rand::Rng;
+
+use super::{Node, NodeKind, RunNodeResult, TaxiNode, TaxiStopMode, trigger};
+use crate::level::{deice, message, object, session};
+
+/// Hold on a de-icing pad for a randomized treatment time.
+///
+/// # Completion condition
+/// Completes when the treatment time has elapsed.
+/// The object is then [`deice::Deiced`] until its holdover time expires.
+/// Completes immediately if winter operations are not in effect
+/// or the object is not on a de-icing pad.
+///
+/// # Prerequisites
+/// The object must be on ground and stopped on a segment of a [`deice::Pad`].
+#[derive(Clone, Copy)]
+pub struct DeiceNode;
+
+impl NodeKind for DeiceNode {
+    fn run_as_current_node(&self, world: &mut World, entity: Entity) -> RunNodeResult {
+        let Some(params) = world.resource::<deice::WinterOps>().0 else {
+            return RunNodeResult::NodeDone;
+        };
+        let now = world.resource::<Time<time::Virtual>>().elapsed();
+
+        let mut object = world.entity_mut(entity);
+        if let Some(&deice::Treatment { until }) = object.get() {
+            if now < until {
+                object.insert(trigger::TimeDelay(until));
+                return RunNodeResult::PendingTrigger;
+            }
+
+            object
+                .remove::<deice::Treatment>()
+                .insert(deice::Deiced { holdover_expiry: now + params.holdover });
+            message::SendExpiring {
+                source:   entity,
+                content:  format!(
+                    "De-icing complete, holdover time {} minutes",
+                    params.holdover.as_secs() / 60
+                ),
+                class:    message::Class::VerboseInfo,
+                duration: Duration::from_secs(10),
+            }
+            .apply(world);
+            return RunNodeResult::NodeDone;
+        }
+
+        let on_pad = object
+            .get::<object::OnGround>()
+            .is_some_and(|ground| object.world().get::<deice::Pad>(ground.segment).is_some());
+        if !on_pad {
+            message::SendExpiring {
+                source:   entity,
+                content:  "Unable to de-ice, not on a de-icing pad".into(),
+                class:    message::Class::NeedAck,
+                duration: Duration::from_secs(10),
+            }
+            .apply(world);
+            return RunNodeResult::NodeDone;
+        }
+
+        let treatment = if params.min_treatment < params.max_treatment {
+            session::with_world_rng(world, |rng| {
+                rng.random_range(params.min_treatment..params.max_treatment)
+            })
+        } else {
+            params.min_treatment
+        };
+        let until = now + treatment;
+        world.entity_mut(entity).insert((deice::Treatment { until }, trigger::TimeDelay(until)));
+        message::SendExpiring {
+            source:   entity,
+            content:  "Commencing de-icing".into(),
+            class:    message::Class::VerboseInfo,
+            duration: Duration::from_secs(10),
+        }
+        .apply(world);
+        RunNodeResult::PendingTrigger
+    }
+}
+
+/// Returns the nodes to taxi to the nearest de-icing pad and get treated there,
+/// or `None` if the object does not require de-icing or no pad is available.
+#[must_use]
+pub fn deice_nodes(world: &World, entity: Entity) -> Option<[Node; 2]> {
+    if !deice::requires_deicing(world, entity) {
+        return None;
+    }
+
+    let pad = deice::nearest_pad(world, entity)?;
+    Some([
+        TaxiNode { label: pad, direction: None, stop: TaxiStopMode::Exhaust }.into(),
+        DeiceNode.into(),
+    ])
+}
+
+/// Whether the object is already planned to be de-iced, or is being de-iced.
+#[must_use]
+pub fn has_deice_node(route: &super::Route) -> bool {
+    route.iter().any(|node| matches!(node, Node::Deice(_)))
+}
@@ -3,15 +3,15 @@ use std::time::Duration;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::system::{Command, EntityCommand};
 use bevy::ecs::world::{EntityRef, EntityWorldMut, World};
-use bevy::math::{Dir2, Vec2};
+use bevy::math::{Dir2, Vec2, Vec3};
 use bevy::time::{self, Time};
-use math::{Angle, Heading, Length, Speed};
+use math::{Angle, Heading, Length, Position, Speed};
 
-use super::{HorizontalTarget, NodeKind, RunNodeResult, trigger};
+use super::{HorizontalTarget, Node, NodeKind, Route, RunNodeResult, run_current_node, trigger};
 use crate::level::object::{self, Object};
 use crate::level::runway::{self, Runway};
 use crate::level::waypoint::Waypoint;
-use crate::level::{ground, message, nav, navaid, taxi};
+use crate::level::{fog, goaround, ground, message, nav, navaid, taxi};
 use crate::{EntityMutTryLog, EntityTryLog, WorldTryLog, try_log};
 
 /// [Activation range](nav::TargetAlignment::activation_range) for `AlignRunway` nodes.
@@ -27,6 +27,169 @@ const ALIGN_RUNWAY_LOOKAHEAD: Duration = Duration::from_secs(10);
 
 const MAX_TRACK_DEVIATION: Angle = Angle::from_degrees(15.0);
 
+/// Minimum distance before the runway threshold to accept an approach clearance.
+const MIN_INTERCEPT_DISTANCE: Length<f32> = Length::from_nm(4.0);
+
+/// Maximum height above the glidepath to accept an approach clearance.
+const MAX_GLIDEPATH_EXCESS: Length<f32> = Length::from_feet(500.0);
+
+/// Maximum angle between the track and the final approach course
+/// to accept an approach clearance.
+const MAX_INTERCEPT_ANGLE: Angle = Angle::from_degrees(45.0);
+
+/// Reason for declining an approach clearance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum UnableApproach {
+    /// The route does not contain an [`AlignRunwayNode`].
+    #[strum(to_string = "no approach in route")]
+    NoApproach,
+    /// The object is too close to the runway threshold to stabilize the approach.
+    #[strum(to_string = "too close")]
+    TooClose,
+    /// The object is too high above the glidepath.
+    #[strum(to_string = "too high")]
+    TooHigh,
+    /// The track intercepts the final approach course at a steep angle.
+    #[strum(to_string = "intercept angle too large")]
+    InterceptAngle,
+    /// The object has no ILS equipment and the runway is not in sight.
+    #[strum(to_string = "not ILS equipped, clear for visual approach once the runway is in sight")]
+    NotIlsEquipped,
+}
+
+/// Checks whether an object can intercept the final approach course of a runway
+/// from its current position and track.
+///
+/// # Errors
+/// Returns the first envelope limit exceeded by the object.
+pub fn check_intercept(
+    object: &Object,
+    runway_position: Position<Vec3>,
+    runway: &Runway,
+) -> Result<(), UnableApproach> {
+    let Ok(runway_dir) = Dir2::new(runway.landing_length.0) else {
+        return Err(UnableApproach::NoApproach);
+    };
+
+    let threshold_dist = runway_position - object.position;
+    let final_dist = threshold_dist.horizontal().project_onto_dir(runway_dir);
+    if final_dist < MIN_INTERCEPT_DISTANCE {
+        return Err(UnableApproach::TooClose);
+    }
+
+    let height = -threshold_dist.vertical();
+    let glidepath_height = final_dist * runway.glide_descent.acute_signed_tan();
+    if height > glidepath_height + MAX_GLIDEPATH_EXCESS {
+        return Err(UnableApproach::TooHigh);
+    }
+
+    let track = object.ground_speed.horizontal();
+    if track != Speed::ZERO
+        && track.heading().closest_distance(runway.landing_length.heading()).abs()
+            > MAX_INTERCEPT_ANGLE
+    {
+        return Err(UnableApproach::InterceptAngle);
+    }
+
+    Ok(())
+}
+
+/// Clears the object for the first approach in its route,
+/// removing the standby nodes before its [`AlignRunwayNode`].
+///
+/// If the approach engages immediately,
+/// the object must be within the intercept envelope checked by [`check_intercept`];
+/// otherwise the pilot replies unable and the route is left unchanged.
+/// Approaches joined through the preceding nodes of a published procedure are not checked.
+/// Objects without [ILS equipment](object::Equipage) must have the runway in sight.
+pub struct ClearApproach;
+
+impl EntityCommand for ClearApproach {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let object_id = entity.id();
+        let Some(route) = entity.log_get::<Route>() else { return };
+
+        let align = route.iter().enumerate().find_map(|(index, node)| match node {
+            Node::AlignRunway(node) => Some((index, node.runway)),
+            _ => None,
+        });
+        // The approach engages immediately if only standby nodes precede it.
+        let immediate_runway = route.iter().find_map(|node| match node {
+            Node::Standby(_) => None,
+            Node::AlignRunway(node) => Some(Some(node.runway)),
+            _ => Some(None),
+        });
+
+        let result = match (align, immediate_runway) {
+            (None, _) => Err(UnableApproach::NoApproach),
+            (Some((index, runway)), Some(Some(_))) => {
+                let Some(object) = entity.log_get::<Object>() else { return };
+                check_ils_equipage(&entity, runway)
+                    .and_then(|()| check_runway_intercept(entity.world(), object, runway))
+                    .map(|()| index)
+            }
+            (Some((index, runway)), _) => check_ils_equipage(&entity, runway).map(|()| index),
+        };
+
+        let align_index = match result {
+            Ok(index) => index,
+            Err(reason) => {
+                entity.world_scope(|world| {
+                    message::SendExpiring {
+                        source:   object_id,
+                        content:  format!("Unable approach, {reason}"),
+                        class:    message::Class::AnomalyInfo,
+                        duration: Duration::from_secs(10),
+                    }
+                    .apply(world);
+                });
+                return;
+            }
+        };
+
+        let mut route = entity.get_mut::<Route>().expect("checked above");
+        let nodes: Vec<Node> = route
+            .iter()
+            .enumerate()
+            .filter(|&(index, node)| index >= align_index || !matches!(node, Node::Standby(_)))
+            .map(|(_, node)| node.clone())
+            .collect();
+        route.clear();
+        route.extend(nodes);
+
+        entity.world_scope(|world| run_current_node(world, object_id));
+    }
+}
+
+/// Objects without ILS equipment can only accept approaches with the runway in sight.
+fn check_ils_equipage(object: &EntityWorldMut, runway: Entity) -> Result<(), UnableApproach> {
+    if object::Equipage::has(object.get(), store::Equipage::ILS) {
+        return Ok(());
+    }
+
+    let world = object.world();
+    let in_sight = object.get::<navaid::ObjectUsageList>().is_some_and(|navaids| {
+        navaids.0.iter().any(|&navaid| {
+            world.entity(navaid).contains::<navaid::Visual>()
+                && world.get::<navaid::OwnerWaypoint>(navaid).is_some_and(|owner| owner.0 == runway)
+        })
+    });
+    if in_sight { Ok(()) } else { Err(UnableApproach::NotIlsEquipped) }
+}
+
+fn check_runway_intercept(
+    world: &World,
+    object: &Object,
+    runway: Entity,
+) -> Result<(), UnableApproach> {
+    let (Some(&Waypoint { position: runway_position, .. }), Some(runway)) =
+        (world.log_get::<Waypoint>(runway), world.log_get::<Runway>(runway))
+    else {
+        return Err(UnableApproach::NoApproach);
+    };
+    check_intercept(object, runway_position, runway)
+}
+
 fn align_runway(object: &mut EntityWorldMut, runway: Entity, expedite: bool) -> Result<(), ()> {
     let Some((glide_descent, localizer_waypoint)) = object.world_scope(|world| {
         Some((
@@ -125,7 +288,9 @@ impl NodeKind for AlignRunwayNode {
 /// ILS is no longer used after this node completes.
 ///
 /// # Completion condition
-/// Completes when visual contact is established with the runway.
+/// Completes when visual contact is established with the runway,
+/// or when a [CAT III](object::Equipage) object passes the ILS minima of an ILS runway.
+/// The ILS is only used by objects with [ILS equipment](object::Equipage).
 /// Switches to goaround preset if ILS is lost before visual contact is established,
 /// e.g. due to ILS interference or low visibility
 /// (no visual contact within minimum runway visual range).
@@ -186,13 +351,21 @@ impl NodeKind for ShortFinalNode {
         for &navaid in &navaids.0 {
             classify_navaid(self.runway, navaid, object.world(), &mut has_visual, &mut has_ils);
         }
+        let equipage = object.get::<object::Equipage>();
+        has_ils &= object::Equipage::has(equipage, store::Equipage::ILS);
+        let cat_iii =
+            object::Equipage::has(equipage, store::Equipage::ILS | store::Equipage::CAT_III);
 
         if has_visual {
             RunNodeResult::NodeDone
         } else if has_ils {
             object.insert(trigger::NavaidChange);
             RunNodeResult::PendingTrigger
+        } else if cat_iii && has_landing_aid(&object, self.runway) {
+            // CAT III equipment continues the approach past the ILS minima without visual contact.
+            RunNodeResult::NodeDone
         } else {
+            go_around(&mut object, goaround::Cause::Weather, "runway not in sight");
             RunNodeResult::ReplaceWithPreset(self.goaround_preset)
         }
     }
@@ -228,6 +401,9 @@ impl NodeKind for VisualLandingNode {
         let exception =
             match find_landing_state(&object.as_readonly(), &object.world().entity(self.runway)) {
                 Err(None) => return RunNodeResult::PendingTrigger,
+                Ok(()) if is_runway_occupied(&mut object, self.runway) => {
+                    LandingException::RunwayOccupied
+                }
                 Ok(()) => match set_landed(&mut object, self.runway) {
                     Ok(()) => return RunNodeResult::NodeDone,
                     Err(exception) => exception,
@@ -244,58 +420,76 @@ impl NodeKind for VisualLandingNode {
 
                 RunNodeResult::PendingTrigger
             }
+            LandingException::RunwayOccupied => {
+                go_around(&mut object, goaround::Cause::RunwayOccupied, "runway occupied");
+                RunNodeResult::ReplaceWithPreset(self.goaround_preset)
+            }
             LandingException::TooFast => {
-                object.world_scope(|world| {
-                    message::SendExpiring {
-                        source:   object_id,
-                        content:  String::from("Going around, too fast"),
-                        class:    message::Class::AnomalyInfo,
-                        duration: Duration::from_secs(10),
-                    }
-                    .apply(world);
-                });
+                go_around(&mut object, goaround::Cause::Unstable, "too fast");
                 RunNodeResult::ReplaceWithPreset(self.goaround_preset)
             }
             LandingException::TooHigh => {
-                object.world_scope(|world| {
-                    message::SendExpiring {
-                        source:   object_id,
-                        content:  String::from("Going around, too high"),
-                        class:    message::Class::AnomalyInfo,
-                        duration: Duration::from_secs(10),
-                    }
-                    .apply(world);
-                });
+                go_around(&mut object, goaround::Cause::Unstable, "too high");
                 RunNodeResult::ReplaceWithPreset(self.goaround_preset)
             }
             LandingException::NotAligned => {
-                object.world_scope(|world| {
-                    message::SendExpiring {
-                        source:   object_id,
-                        content:  String::from("Going around, beyond runway width"),
-                        class:    message::Class::AnomalyInfo,
-                        duration: Duration::from_secs(10),
-                    }
-                    .apply(world);
-                });
+                go_around(&mut object, goaround::Cause::Unstable, "beyond runway width");
                 RunNodeResult::ReplaceWithPreset(self.goaround_preset)
             }
             LandingException::TrackDeviate => {
-                object.world_scope(|world| {
-                    message::SendExpiring {
-                        source:   object_id,
-                        content:  String::from("Going around, track not parallel to runway"),
-                        class:    message::Class::AnomalyInfo,
-                        duration: Duration::from_secs(10),
-                    }
-                    .apply(world);
-                });
+                go_around(&mut object, goaround::Cause::Unstable, "track not parallel to runway");
                 RunNodeResult::ReplaceWithPreset(self.goaround_preset)
             }
         }
     }
 }
 
+/// Reports a go-around of the object with the given cause and reason message.
+fn go_around(object: &mut EntityWorldMut, cause: goaround::Cause, reason: &str) {
+    let object_id = object.id();
+    object.world_scope(|world| {
+        message::SendExpiring {
+            source:   object_id,
+            content:  format!("Going around, {reason}"),
+            class:    message::Class::AnomalyInfo,
+            duration: Duration::from_secs(10),
+        }
+        .apply(world);
+    });
+    object.reborrow_scope(|entity| goaround::Record(cause).apply(entity));
+}
+
+/// Whether the runway is equipped with an ILS.
+fn has_landing_aid(object: &EntityWorldMut, runway: Entity) -> bool {
+    let world = object.world();
+    world.get::<navaid::ListAtWaypoint>(runway).is_some_and(|navaids| {
+        navaids
+            .navaids()
+            .iter()
+            .any(|&navaid| world.entity(navaid).contains::<navaid::LandingAid>())
+    })
+}
+
+/// Whether another object is on the ground on any segment of the runway,
+/// or within the protection zone of the runway during low visibility procedures.
+fn is_runway_occupied(object: &mut EntityWorldMut, runway_entity: Entity) -> bool {
+    if object.world().entity(runway_entity).contains::<fog::ProtectionZoneOccupied>() {
+        return true;
+    }
+
+    let object_id = object.id();
+    object.world_scope(|world| {
+        let Some(ground::RunwaySegments(segments)) = world.get(runway_entity) else {
+            return false;
+        };
+        let segments = segments.clone();
+        world
+            .query::<(Entity, &object::OnGround)>()
+            .iter(world)
+            .any(|(other, ground)| other != object_id && segments.contains(&ground.segment))
+    })
+}
+
 fn set_landed(object: &mut EntityWorldMut, runway_entity: Entity) -> Result<(), LandingException> {
     let &Object { position: object_pos, ground_speed } =
         object.get().expect("checked in find_landing_state");
@@ -342,6 +536,7 @@ fn set_landed(object: &mut EntityWorldMut, runway_entity: Entity) -> Result<(),
 #[derive(Debug)]
 enum LandingException {
     Approaching { remaining_time: Duration },
+    RunwayOccupied,
     TooFast,
     TooHigh,
     NotAligned,
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::num::NonZero;
 use std::{iter, mem};
@@ -6,6 +7,7 @@ use bevy::ecs::entity::Entity;
 use bevy::ecs::name::Name;
 use bevy::ecs::world::World;
 use either::Either;
+use math::Length;
 
 use crate::level::aerodrome::loader::AerodromeMap;
 use crate::level::ground;
@@ -13,6 +15,9 @@ use crate::level::route::{self, TaxiStopMode};
 use crate::level::waypoint::loader::WaypointMap;
 use crate::load::{self, StoredEntity};
 
+/// Completion distance of the direct legs expanded from [`store::RouteNode::FollowAirway`].
+const AIRWAY_FIX_DISTANCE: Length<f32> = Length::from_nm(1.0);
+
 #[derive(Default)]
 pub struct RoutePresetMap(HashMap<store::RoutePresetRef, Entity>);
 
@@ -28,8 +33,13 @@ impl RoutePresetMap {
 
 /// Spawns route presets declared in a store into the world.
 ///
+/// [Includes](store::PresetInclude) are expanded before the presets are converted.
+/// Presets with unresolvable references or cyclic includes
+/// are skipped and reported as [issues](load::Issues),
+/// along with any presets that reference a skipped preset.
+///
 /// # Errors
-/// If the stored route presets contain invalid references.
+/// If the stored route presets contain unrecoverable errors.
 pub fn spawn_presets(
     world: &mut World,
     aerodromes: &AerodromeMap,
@@ -42,7 +52,7 @@ pub fn spawn_presets(
         .iter()
         .map(|preset| world.spawn((StoredEntity, Name::new(format!("Preset: {}", preset.id)))).id())
         .collect();
-    let route_preset_map = RoutePresetMap(
+    let mut route_preset_map = RoutePresetMap(
         presets
             .iter()
             .zip(&route_preset_entities)
@@ -50,34 +60,163 @@ pub fn spawn_presets(
             .collect(),
     );
 
-    for (preset, entity) in presets.iter().zip(route_preset_entities) {
-        let mut entity_ref = world.entity_mut(entity);
-        entity_ref.insert((
-            route::Preset {
-                id:    preset.id.clone(),
-                title: preset.title.clone(),
-                nodes: convert_route(
+    let presets_by_ref: HashMap<_, _> =
+        presets.iter().filter_map(|preset| Some((preset.ref_id.as_ref()?, preset))).collect();
+
+    // A skipped preset invalidates every preset referencing it,
+    // so failures are collected until a fixed point is reached before anything is spawned.
+    let mut errors: Vec<Option<load::Error>> = presets.iter().map(|_| None).collect();
+    loop {
+        let mut changed = false;
+        for (preset, error) in iter::zip(presets, &mut errors) {
+            if error.is_some() {
+                continue;
+            }
+            let mut scratch_standby_id = *next_standby_id;
+            let result = expand_includes(&presets_by_ref, preset).and_then(|expanded| {
+                convert_preset(
                     aerodromes,
                     waypoints,
                     &route_preset_map,
-                    next_standby_id,
-                    &preset.nodes,
+                    &mut scratch_standby_id,
+                    &expanded,
                 )
-                .collect::<Result<_, load::Error>>()?,
-            },
-            resolve_destination_matcher(aerodromes, waypoints, &preset.destinations)?,
-        ));
-        match &preset.trigger {
-            store::RoutePresetTrigger::Waypoint(waypoint) => {
-                let waypoint = waypoints.resolve_ref(aerodromes, waypoint)?;
-                entity_ref.insert(route::PresetFromWaypoint(waypoint));
+            });
+            if let Err(err) = result {
+                if let Some(ref_id) = &preset.ref_id {
+                    route_preset_map.0.remove(ref_id);
+                }
+                *error = Some(err);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    for ((preset, entity), error) in presets.iter().zip(route_preset_entities).zip(errors) {
+        let result = match error {
+            Some(err) => Err(err),
+            None => expand_includes(&presets_by_ref, preset).and_then(|expanded| {
+                convert_preset(aerodromes, waypoints, &route_preset_map, next_standby_id, &expanded)
+            }),
+        };
+        match load::recover(world, || format!("route preset {}", preset.id), result)? {
+            Some(bundle) => {
+                let mut entity = world.entity_mut(entity);
+                entity.insert(bundle);
+                if let Some(ref_id) = &preset.ref_id {
+                    entity.insert(route::PresetRef(ref_id.clone()));
+                }
             }
+            None => world.entity_mut(entity).despawn(),
         }
     }
 
     Ok(route_preset_map)
 }
 
+fn convert_preset(
+    aerodromes: &AerodromeMap,
+    waypoints: &WaypointMap,
+    route_preset_map: &RoutePresetMap,
+    next_standby_id: &mut NonZero<u32>,
+    preset: &store::RoutePreset,
+) -> Result<(route::Preset, route::DestinationMatcher, route::PresetFromWaypoint), load::Error> {
+    let nodes =
+        convert_route(aerodromes, waypoints, route_preset_map, next_standby_id, &preset.nodes)
+            .collect::<Result<_, load::Error>>()?;
+    let destinations = resolve_destination_matcher(aerodromes, waypoints, &preset.destinations)?;
+    let trigger = match &preset.trigger {
+        store::RoutePresetTrigger::Waypoint(waypoint) => {
+            route::PresetFromWaypoint(waypoints.resolve_ref(aerodromes, waypoint)?)
+        }
+    };
+
+    Ok((
+        route::Preset {
+            id: preset.id.clone(),
+            title: preset.title.clone(),
+            nodes,
+            equipage: preset.equipage,
+        },
+        destinations,
+        trigger,
+    ))
+}
+
+/// Returns `preset` with the nodes of its [include](store::RoutePreset::include) chain appended.
+///
+/// # Errors
+/// If an included preset does not exist or the include chain is cyclic.
+pub fn expand_includes<'a>(
+    presets_by_ref: &HashMap<&store::RoutePresetRef, &store::RoutePreset>,
+    preset: &'a store::RoutePreset,
+) -> Result<Cow<'a, store::RoutePreset>, load::Error> {
+    let Some(include) = &preset.include else { return Ok(Cow::Borrowed(preset)) };
+
+    let mut visiting: Vec<_> = preset.ref_id.iter().collect();
+    let mut expanded = preset.clone();
+    expanded.nodes.extend(included_nodes(presets_by_ref, include, &mut visiting)?);
+    expanded.include = None;
+    Ok(Cow::Owned(expanded))
+}
+
+fn included_nodes<'a>(
+    presets_by_ref: &HashMap<&store::RoutePresetRef, &'a store::RoutePreset>,
+    include: &'a store::PresetInclude,
+    visiting: &mut Vec<&'a store::RoutePresetRef>,
+) -> Result<Vec<store::RouteNode>, load::Error> {
+    if visiting.contains(&&include.preset) {
+        return Err(load::Error::RoutePresetCycle(include.preset.0.clone()));
+    }
+    let &target = presets_by_ref
+        .get(&include.preset)
+        .ok_or_else(|| load::Error::UnresolvedRoutePreset(include.preset.0.clone()))?;
+
+    visiting.push(&include.preset);
+    let mut nodes = target.nodes.clone();
+    if let Some(nested) = &target.include {
+        nodes.extend(included_nodes(presets_by_ref, nested, visiting)?);
+    }
+    visiting.pop();
+
+    if let Some(runway) = &include.runway {
+        for node in &mut nodes {
+            override_runway(node, runway);
+        }
+    }
+    if let Some(altitude) = include.altitude
+        && let Some(store::RouteNode::DirectWaypoint { altitude: node_altitude, .. }) =
+            nodes.iter_mut().find(|node| matches!(node, store::RouteNode::DirectWaypoint { .. }))
+    {
+        *node_altitude = Some(altitude);
+    }
+    Ok(nodes)
+}
+
+fn override_runway(node: &mut store::RouteNode, runway: &store::RunwayRef) {
+    match node {
+        store::RouteNode::RunwayLanding { runway: node_runway, .. }
+        | store::RouteNode::RunwayTakeoff { runway: node_runway, .. }
+        | store::RouteNode::RunwayLineup { runway: node_runway }
+        | store::RouteNode::DirectWaypoint {
+            waypoint:
+                store::WaypointRef::RunwayThreshold(node_runway)
+                | store::WaypointRef::LocalizerStart(node_runway),
+            ..
+        } => *node_runway = runway.clone(),
+        store::RouteNode::DirectWaypoint { .. }
+        | store::RouteNode::FollowAirway { .. }
+        | store::RouteNode::SetAirSpeed { .. }
+        | store::RouteNode::StartPitchToAltitude { .. }
+        | store::RouteNode::Taxi { .. }
+        | store::RouteNode::HoldShort { .. }
+        | store::RouteNode::WaitForClearance => {}
+    }
+}
+
 /// Converts a list of stored route nodes into runtime route nodes.
 ///
 /// # Errors
@@ -104,6 +243,19 @@ pub fn convert_route<'a>(
                     proximity,
                     altitude,
                 }),
+                store::RouteNode::FollowAirway { ref airway, ref from, ref to } => waypoints
+                    .resolve_airway(airway, from, to)?
+                    .into_iter()
+                    .map(|waypoint| {
+                        route::DirectWaypointNode {
+                            waypoint,
+                            distance: AIRWAY_FIX_DISTANCE,
+                            proximity: store::WaypointProximity::FlyBy,
+                            altitude: None,
+                        }
+                        .into()
+                    })
+                    .collect(),
                 store::RouteNode::SetAirSpeed { goal, error } => {
                     node_vec(route::SetAirspeedNode { speed: goal, error })
                 }
@@ -185,25 +337,32 @@ fn resolve_destination_matcher(
     let items = dests
         .iter()
         .map(|dest| {
-            Ok(match dest {
+            let (target, configurations) = match dest {
                 store::PresetDestination::Arrival(dest) => {
-                    if let Some(aerodrome) = &dest.aerodrome {
+                    let target = if let Some(aerodrome) = &dest.aerodrome {
                         let aerodrome = aerodromes.resolve(aerodrome)?.aerodrome_entity;
-                        route::DestinationMatcherItem::Arrival { aerodrome }
+                        route::DestinationTarget::Arrival { aerodrome }
                     } else {
-                        route::DestinationMatcherItem::AnyArrival
-                    }
+                        route::DestinationTarget::AnyArrival
+                    };
+                    (target, &dest.configurations)
                 }
                 store::PresetDestination::Departure(dest) => {
-                    if let Some(waypoint) = &dest.waypoint {
+                    let target = if let Some(waypoint) = &dest.waypoint {
                         let waypoint = waypoints.resolve(waypoint)?;
-                        route::DestinationMatcherItem::Departure { waypoint }
+                        route::DestinationTarget::Departure { waypoint }
                     } else {
-                        route::DestinationMatcherItem::AnyDeparture
-                    }
+                        route::DestinationTarget::AnyDeparture
+                    };
+                    (target, &dest.configurations)
                 }
-            })
+            };
+            let configurations = configurations
+                .iter()
+                .map(|configuration| aerodromes.resolve_configuration(configuration))
+                .collect::<Result<_, _>>()?;
+            Ok(route::DestinationMatcherItem { target, configurations })
         })
-        .collect::<Result<Vec<_>, _>>()?;
+        .collect::<Result<Vec<_>, load::Error>>()?;
     Ok(route::DestinationMatcher { items })
 }
@@ -1,14 +1,21 @@
+use std::time::Duration;
+
+use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
-use bevy::ecs::system::SystemState;
-use bevy::ecs::world::World;
+use bevy::ecs::system::{Command, Commands, EntityCommand, Query, SystemState};
+use bevy::ecs::world::{EntityWorldMut, World};
 use bevy::math::Vec2;
 use math::{Between, Length, Position, Speed};
 use store::WaypointProximity;
 
-use super::{DesiredAltitude, HorizontalTarget, NodeKind, Route, RunNodeResult, trigger};
-use crate::level::nav;
+use super::{
+    DesiredAltitude, HorizontalTarget, Node, NodeKind, Route, RunNodeResult, run_current_node,
+    trigger,
+};
 use crate::level::object::{self, Object};
 use crate::level::waypoint::Waypoint;
+use crate::level::{message, nav, pilot};
+use crate::{EntityMutTryLog, WorldTryLog};
 
 /// Head towards a waypoint.
 ///
@@ -90,6 +97,124 @@ impl NodeKind for DirectWaypointNode {
     }
 }
 
+/// An altitude restriction at a waypoint of the route assigned by ATC.
+///
+/// The restriction is monitored until the object crosses the waypoint.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct CrossingRestriction {
+    /// The restricted waypoint.
+    pub waypoint: Entity,
+    /// The altitude at which the waypoint must be crossed.
+    pub altitude: Position<f32>,
+}
+
+/// Restricts the altitude of the first [`DirectWaypointNode`] towards the given waypoint.
+///
+/// The pilot replies unable if the route does not fly towards the waypoint.
+pub struct SetCrossingAltitude {
+    pub waypoint: Entity,
+    pub altitude: Position<f32>,
+}
+
+impl EntityCommand for SetCrossingAltitude {
+    fn apply(self, mut entity: EntityWorldMut) {
+        let object_id = entity.id();
+        let Some(mut route) = entity.log_get_mut::<Route>() else { return };
+
+        let node = route.iter_mut().find_map(|node| match node {
+            Node::DirectWaypoint(node) if node.waypoint == self.waypoint => Some(node),
+            _ => None,
+        });
+        let Some(node) = node else {
+            entity.world_scope(|world| {
+                let waypoint_name =
+                    world.log_get::<Waypoint>(self.waypoint).map_or("unknown", |w| w.name.as_str());
+                message::SendExpiring {
+                    source:   object_id,
+                    content:  format!("Unable, {waypoint_name} is not in the route"),
+                    class:    message::Class::AnomalyInfo,
+                    duration: Duration::from_secs(10),
+                }
+                .apply(world);
+            });
+            return;
+        };
+        node.altitude = Some(self.altitude);
+
+        entity.insert(CrossingRestriction { waypoint: self.waypoint, altitude: self.altitude });
+        entity.world_scope(|world| run_current_node(world, object_id));
+    }
+}
+
+/// Distance from a waypoint at which a [`SpeedCondition::Waypoint`] is met.
+const WAYPOINT_PASSAGE_DISTANCE: Length<f32> = Length::from_nm(1.0);
+
+/// A speed change deferred until a condition is met,
+/// e.g. the "then 180" part of "250 until 10 DME, then 180".
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ConditionalSpeed {
+    /// Indicated airspeed to set when the condition is met.
+    pub speed:     Speed<f32>,
+    /// The condition for the speed change.
+    pub condition: SpeedCondition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpeedCondition {
+    /// The object is within `distance` of a waypoint, e.g. "10 DME".
+    Distance { waypoint: Entity, distance: Length<f32> },
+    /// The object passes a waypoint.
+    Waypoint(Entity),
+}
+
+impl SpeedCondition {
+    /// The waypoint that the condition is relative to.
+    #[must_use]
+    pub fn waypoint(&self) -> Entity {
+        match *self {
+            Self::Distance { waypoint, .. } | Self::Waypoint(waypoint) => waypoint,
+        }
+    }
+
+    /// The distance from [`waypoint`](Self::waypoint) within which the condition is met.
+    #[must_use]
+    pub fn distance(&self) -> Length<f32> {
+        match *self {
+            Self::Distance { distance, .. } => distance,
+            Self::Waypoint(_) => WAYPOINT_PASSAGE_DISTANCE,
+        }
+    }
+}
+
+pub(super) fn conditional_speed_system(
+    object_query: Query<(
+        Entity,
+        &Object,
+        &ConditionalSpeed,
+        &mut nav::VelocityTarget,
+        Option<&mut pilot::Clearance>,
+    )>,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+) {
+    for (entity, object, conditional, mut target, clearance) in object_query {
+        let Ok(waypoint) = waypoint_query.get(conditional.condition.waypoint()) else {
+            continue;
+        };
+        if object.position.horizontal_distance_exact(waypoint.position)
+            > conditional.condition.distance()
+        {
+            continue;
+        }
+
+        target.horiz_speed = conditional.speed;
+        if let Some(mut clearance) = clearance {
+            clearance.speed = Some(conditional.speed);
+        }
+        commands.entity(entity).remove::<ConditionalSpeed>();
+    }
+}
+
 /// Increase/reduce the speed to the desired value.
 ///
 /// When the object is not yet airborne, this would control the expected airspeed
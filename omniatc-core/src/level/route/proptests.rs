@@ -0,0 +1,280 @@
+//! Randomized executions of route node sequences.
+
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::EntityCommand;
+use bevy::math::bounding::Aabb2d;
+use bevy::math::{Quat, Vec2};
+use bevy::time::{self, Time};
+use math::{Heading, ISA_SEA_LEVEL_PRESSURE, ISA_SEA_LEVEL_TEMPERATURE, Length, Position, Speed};
+use proptest::prelude::*;
+use store::{WaypointProximity, YawTarget};
+
+use super::{DirectWaypointNode, Node, ReplaceNodes, Route, SetAirspeedNode, StartSetAltitudeNode};
+use crate::level::object::{self, Object};
+use crate::level::test_util::NAV_LIMITS;
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{nav, navaid, plane, taxi, test_util, weather};
+
+const INITIAL_ALTITUDE: Position<f32> = Position::from_amsl_feet(5000.);
+const INITIAL_SPEED: Speed<f32> = Speed::from_knots(220.);
+
+/// Duration of each simulated frame.
+const FRAME: Duration = Duration::from_millis(500);
+/// Upper bound of the time spent on each step, used to detect routes that never complete.
+const STEP_TIMEOUT: Duration = Duration::from_mins(10);
+
+/// A route node to be generated, before the referenced waypoints are spawned.
+#[derive(Debug, Clone)]
+enum Step {
+    Waypoint {
+        /// Bearing from the previous waypoint, or from the initial position for the first one.
+        bearing:  f32,
+        /// Distance from the previous waypoint in nautical miles.
+        distance: f32,
+        fly_over: bool,
+        altitude: Option<f32>,
+    },
+    Speed {
+        knots:  f32,
+        settle: bool,
+    },
+    Altitude {
+        feet:     f32,
+        settle:   bool,
+        expedite: bool,
+    },
+}
+
+fn step_strategy() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        3 => (
+            0f32..360.,
+            6f32..15.,
+            any::<bool>(),
+            proptest::option::of(3000f32..10000.),
+        )
+            .prop_map(|(bearing, distance, fly_over, altitude)| Step::Waypoint {
+                bearing,
+                distance,
+                fly_over,
+                altitude,
+            }),
+        1 => (160f32..280., any::<bool>()).prop_map(|(knots, settle)| Step::Speed { knots, settle }),
+        1 => (3000f32..10000., any::<bool>(), any::<bool>())
+            .prop_map(|(feet, settle, expedite)| Step::Altitude { feet, settle, expedite }),
+    ]
+}
+
+/// Identifies a node in the route independent of its mutable state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKey {
+    Waypoint(Entity),
+    Speed,
+    Altitude,
+    Other,
+}
+
+impl NodeKey {
+    fn of(node: &Node) -> Self {
+        match node {
+            Node::DirectWaypoint(node) => Self::Waypoint(node.waypoint),
+            Node::SetAirSpeed(_) => Self::Speed,
+            Node::StartSetAltitude(_) => Self::Altitude,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Straight and level at [`INITIAL_ALTITUDE`] and [`INITIAL_SPEED`] from the origin,
+/// heading north in calm air.
+fn base_world() -> (App, Entity) {
+    let mut app = test_util::app();
+    app.add_plugins((
+        object::Plug::<()>::default(),
+        weather::Plug::<()>::default(),
+        plane::Plug,
+        nav::Plug,
+        super::Plug,
+    ));
+
+    app.add_message::<navaid::UsageChangeMessage>();
+    app.add_message::<taxi::TargetResolutionMessage>();
+
+    app.world_mut().commands().spawn_empty().queue(weather::SpawnCommand {
+        bundle: weather::Comps {
+            weather:       weather::Weather::default(),
+            effect_region: weather::EffectRegion(Aabb2d {
+                min: Vec2::splat(-1000.0),
+                max: Vec2::splat(1000.0),
+            }),
+        },
+    });
+
+    let velocity = (INITIAL_SPEED * Heading::NORTH).horizontally();
+    let object = app
+        .world_mut()
+        .commands()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(INITIAL_ALTITUDE),
+                ground_speed: velocity,
+            },
+            object::Airborne {
+                pressure_alt:  INITIAL_ALTITUDE,
+                pressure:      ISA_SEA_LEVEL_PRESSURE,
+                oat:           ISA_SEA_LEVEL_TEMPERATURE,
+                airspeed:      velocity,
+                true_airspeed: velocity,
+            },
+            object::Rotation(Quat::IDENTITY),
+        ))
+        .queue(plane::SpawnCommand { control: None, limits: nav::Limits(NAV_LIMITS) })
+        .insert(nav::VelocityTarget {
+            yaw:         YawTarget::Heading(Heading::NORTH),
+            horiz_speed: INITIAL_SPEED,
+            vert_rate:   Speed::ZERO,
+            expedite:    false,
+        })
+        .id();
+
+    app.world_mut().flush();
+    app.update();
+    (app, object)
+}
+
+/// Spawns the waypoints referenced by `steps` and converts them into route nodes.
+fn build_nodes(app: &mut App, steps: &[Step]) -> Vec<Node> {
+    let mut last_position = Position::<Vec2>::ORIGIN;
+
+    steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| match *step {
+            Step::Waypoint { bearing, distance, fly_over, altitude } => {
+                let position =
+                    last_position + Length::from_nm(distance) * Heading::from_degrees(bearing);
+                last_position = position;
+
+                let waypoint = app
+                    .world_mut()
+                    .spawn(Waypoint {
+                        name:         format!("WP{index}"),
+                        position:     position.with_altitude(Position::from_amsl_feet(0.)),
+                        display_type: waypoint::DisplayType::Waypoint,
+                        hidden:       false,
+                    })
+                    .id();
+
+                DirectWaypointNode {
+                    waypoint,
+                    // Fly-over distance is measured in 3D against the waypoint at sea level.
+                    distance: Length::from_nm(2.),
+                    proximity: if fly_over {
+                        WaypointProximity::FlyOver
+                    } else {
+                        WaypointProximity::FlyBy
+                    },
+                    altitude: altitude.map(Position::from_amsl_feet),
+                }
+                .into()
+            }
+            Step::Speed { knots, settle } => SetAirspeedNode {
+                speed: Speed::from_knots(knots),
+                error: settle.then_some(Speed::from_knots(5.)),
+            }
+            .into(),
+            Step::Altitude { feet, settle, expedite } => StartSetAltitudeNode {
+                altitude: Position::from_amsl_feet(feet),
+                error: settle.then_some(Length::from_feet(100.)),
+                expedite,
+            }
+            .into(),
+        })
+        .collect()
+}
+
+fn remaining_keys(app: &App, object: Entity) -> Vec<NodeKey> {
+    app.world()
+        .get::<Route>(object)
+        .map_or_else(Vec::new, |route| route.iter().map(NodeKey::of).collect())
+}
+
+/// Checks that the object state is physically valid and within its navigation limits.
+fn check_limits(app: &App, object: Entity) -> Result<(), TestCaseError> {
+    let world = app.world();
+    let &Object { position, ground_speed } = world.get::<Object>(object).expect("object exists");
+    prop_assert!(position.get().is_finite(), "position {position:?} is not finite");
+    prop_assert!(ground_speed.0.is_finite(), "ground speed {ground_speed:?} is not finite");
+
+    let airborne = world.get::<object::Airborne>(object).expect("object remains airborne");
+    prop_assert!(airborne.airspeed.0.is_finite(), "airspeed {:?} is not finite", airborne.airspeed);
+
+    let control = world.get::<plane::Control>(object).expect("plane has control");
+    prop_assert!(
+        control.yaw_speed.abs() <= NAV_LIMITS.max_yaw_speed * 1.001,
+        "yaw speed {:?} exceeds limit",
+        control.yaw_speed,
+    );
+
+    let vert_rate = airborne.airspeed.vertical();
+    let vert_tolerance = Speed::from_fpm(1.);
+    prop_assert!(
+        vert_rate <= NAV_LIMITS.exp_climb.vert_rate + vert_tolerance
+            && vert_rate >= NAV_LIMITS.exp_descent.vert_rate - vert_tolerance,
+        "vertical rate {:?} exceeds limits",
+        vert_rate,
+    );
+
+    let horiz_speed = airborne.airspeed.horizontal().magnitude_exact();
+    prop_assert!(
+        horiz_speed >= NAV_LIMITS.min_horiz_speed - Speed::from_knots(1.),
+        "airspeed {horiz_speed:?} below minimum",
+    );
+
+    Ok(())
+}
+
+fn execute_route(steps: &[Step]) -> Result<(), TestCaseError> {
+    let (mut app, object) = base_world();
+
+    let nodes = build_nodes(&mut app, steps);
+    let expected: Vec<_> = nodes.iter().map(NodeKey::of).collect();
+    ReplaceNodes(nodes).apply(app.world_mut().entity_mut(object));
+
+    let timeout = STEP_TIMEOUT * u32::try_from(steps.len()).expect("steps are short");
+    let mut elapsed = Duration::ZERO;
+
+    loop {
+        let remaining = remaining_keys(&app, object);
+        prop_assert!(
+            expected.ends_with(&remaining),
+            "remaining nodes {remaining:?} are not a suffix of {expected:?}",
+        );
+        if remaining.is_empty() {
+            return Ok(());
+        }
+
+        prop_assert!(
+            elapsed < timeout,
+            "route did not complete in {timeout:?}, remaining nodes {remaining:?}",
+        );
+
+        app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(FRAME);
+        app.update();
+        elapsed += FRAME;
+
+        check_limits(&app, object)?;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn route_nodes_complete_in_order(steps in proptest::collection::vec(step_strategy(), 1..6)) {
+        execute_route(&steps)?;
+    }
+}
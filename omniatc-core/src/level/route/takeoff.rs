@@ -8,10 +8,10 @@ use math::Position;
 use store::{ClimbProfile, NavLimits, TaxiLimits};
 
 use crate::level::object::{GroundSpeedCalculator, Object};
-use crate::level::route::{NodeKind, RunNodeResult, TaxiNode, TaxiStopMode, trigger};
-use crate::level::runway::Runway;
+use crate::level::route::{NodeKind, RunNodeResult, TaxiNode, TaxiStopMode, deice_nodes, trigger};
+use crate::level::runway::{self, Runway};
 use crate::level::waypoint::Waypoint;
-use crate::level::{ground, message, nav, object, taxi};
+use crate::level::{deice, fog, ground, message, nav, object, taxi};
 use crate::{EntityTryLog, WorldTryLog};
 
 /// Accelerate to takeoff speed and set the object to airborne.
@@ -57,7 +57,9 @@ fn run_takeoff_node(world: &mut World, entity: Entity, node: TakeoffNode) -> Opt
 
     let &Object { position: object_pos, ground_speed: current_ground_speed } = object.log_get()?;
 
-    let &object::OnGround { segment: segment_id, direction, .. } = object.log_get()?;
+    let &object::OnGround { segment: segment_id, direction, ref target_speed, .. } =
+        object.log_get()?;
+    let rolling = matches!(target_speed, object::OnGroundTargetSpeed::TakeoffRoll);
     let segment_entity = world.entity(segment_id);
     let Some(runways) = segment_entity.get::<ground::SegmentOfRunway>() else {
         bevy::log::error!(
@@ -67,6 +69,15 @@ fn run_takeoff_node(world: &mut World, entity: Entity, node: TakeoffNode) -> Opt
         return None;
     };
     let runway_entity = runways.by_direction(direction);
+    let runway_pair = runways.0;
+
+    if !rolling && let Some(result) = request_deicing(world, entity, runway_pair, direction) {
+        return Some(result);
+    }
+    if !rolling && let Some(result) = space_lvp_departure(world, entity, runway_entity) {
+        return Some(result);
+    }
+
     let runway_touchdown_position = world.log_get::<Waypoint>(runway_entity)?.position.horizontal();
     let runway = world.log_get::<Runway>(runway_entity)?;
     let runway_dir = runway.landing_length;
@@ -109,7 +120,7 @@ fn run_takeoff_node(world: &mut World, entity: Entity, node: TakeoffNode) -> Opt
     let available_dist = tora_end.distance_exact(object_pos.horizontal());
 
     if available_dist < required_dist {
-        let runway_label = ground::SegmentLabel::RunwayPair(runways.0);
+        let runway_label = ground::SegmentLabel::RunwayPair(runway_pair);
 
         world.spawn(message::Message {
             source:  entity,
@@ -144,3 +155,65 @@ fn run_takeoff_node(world: &mut World, entity: Entity, node: TakeoffNode) -> Opt
 
     Some(RunNodeResult::PendingTrigger)
 }
+
+/// Holds the object on the runway until the departure spacing of
+/// [low visibility procedures](fog::Lvp) at the aerodrome has elapsed.
+///
+/// Records the departure if the object may start its takeoff roll.
+fn space_lvp_departure(world: &mut World, entity: Entity, runway: Entity) -> Option<RunNodeResult> {
+    let &runway::RunwayOf(aerodrome) = world.log_get(runway)?;
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    let mut lvp = world.get_mut::<fog::Lvp>(aerodrome)?;
+
+    let Some(wait_until) = lvp.departure_wait(now) else {
+        lvp.last_departure = Some(now);
+        return None;
+    };
+
+    world.spawn(message::Message {
+        source:  entity,
+        created: now,
+        content: format!(
+            "Holding in position for low visibility departure spacing, {:.0} seconds",
+            wait_until.saturating_sub(now).as_secs_f32().ceil()
+        ),
+        class:   message::Class::VerboseInfo,
+    });
+    world.entity_mut(entity).insert(trigger::TimeDelay(wait_until));
+    Some(RunNodeResult::PendingTrigger)
+}
+
+/// Prepends nodes to taxi through a de-icing pad and line up again
+/// if the object requires de-icing before takeoff.
+fn request_deicing(
+    world: &mut World,
+    entity: Entity,
+    runway_pair: [Entity; 2],
+    direction: ground::SegmentDirection,
+) -> Option<RunNodeResult> {
+    let deice_nodes = deice_nodes(world, entity)?;
+
+    let expired = world.entity(entity).contains::<deice::Deiced>();
+    world.spawn(message::Message {
+        source:  entity,
+        created: world.resource::<Time<time::Virtual>>().elapsed(),
+        content: if expired {
+            "Holdover time expired, request de-icing again".into()
+        } else {
+            "Request de-icing before takeoff".into()
+        },
+        class:   message::Class::AnomalyInfo,
+    });
+
+    Some(RunNodeResult::PrependNodes(
+        deice_nodes
+            .into_iter()
+            .chain([TaxiNode {
+                label:     ground::SegmentLabel::RunwayPair(runway_pair),
+                direction: Some(direction),
+                stop:      TaxiStopMode::LineUp,
+            }
+            .into()])
+            .collect(),
+    ))
+}
@@ -67,12 +67,35 @@ impl TaxiStopMode {
             Self::Exhaust => format!("Taxi to {segment_name}"),
         }
     }
+
+    /// The verb instructing this stop mode, followed by the segment name.
+    #[must_use]
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::HoldShort => "hold short of",
+            Self::LineUp => "line up on",
+            Self::Exhaust => "taxi to",
+        }
+    }
 }
 
 impl NodeKind for TaxiNode {
     fn run_as_current_node(&self, world: &mut World, entity: Entity) -> RunNodeResult {
         let mut object = world.entity_mut(entity);
 
+        // Instructions to taxi onto a runway imply the clearance to enter it.
+        if let ground::SegmentLabel::RunwayPair(runway) = self.label
+            && self.stop != TaxiStopMode::HoldShort
+            && let Some(mut ground) = object.get_mut::<object::OnGround>()
+            && (ground.crossing == object::RunwayCrossing::None
+                || ground
+                    .crossing
+                    .holding_runway()
+                    .is_some_and(|holding| runway.contains(&holding)))
+        {
+            ground.crossing = object::RunwayCrossing::Cleared { runway: runway[0] };
+        }
+
         if let Some(mut target) = object.get_mut::<taxi::Target>() {
             if let Some(taxi::TargetResolution::Inoperable) = target.resolution {
                 message::SendExpiring {
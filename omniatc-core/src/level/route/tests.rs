@@ -0,0 +1,272 @@
+use bevy::app::{self, App};
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+use math::{Angle, Heading, Length, Position, Speed};
+use store::{WaypointProximity, YawTarget};
+
+use super::{
+    ConditionalSpeed, CrossingRestriction, DirectWaypointNode, Node, Route, SetCrossingAltitude,
+    SpeedCondition, UnableApproach, check_intercept, conditional_speed_system, loader,
+};
+use crate::level::object::Object;
+use crate::level::runway::Runway;
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{nav, pilot};
+use crate::load;
+
+const ELEVATION: Position<f32> = Position::from_amsl_feet(0.0);
+
+/// A north-facing runway with its threshold at the origin.
+fn runway() -> Runway {
+    Runway {
+        width:          Length::from_meters(50.0),
+        display_start:  Position::ORIGIN.with_altitude(ELEVATION),
+        display_end:    Position::from_origin_nm(0.0, 2.0).with_altitude(ELEVATION),
+        glide_descent:  Angle::from_degrees(3.0),
+        landing_length: Length::from_nm(2.0).with_heading(Heading::NORTH),
+    }
+}
+
+fn object(south_nm: f32, altitude_ft: f32, track: Heading) -> Object {
+    Object {
+        position:     Position::from_origin_nm(0.0, -south_nm)
+            .with_altitude(Position::from_amsl_feet(altitude_ft)),
+        ground_speed: (Speed::from_knots(180.0) * track).horizontally(),
+    }
+}
+
+fn check(object: &Object) -> Result<(), UnableApproach> {
+    check_intercept(object, Position::ORIGIN.with_altitude(ELEVATION), &runway())
+}
+
+#[test]
+fn test_intercept_within_envelope() {
+    assert_eq!(check(&object(10.0, 3000.0, Heading::from_degrees(30.0))), Ok(()));
+}
+
+#[test]
+fn test_intercept_too_close() {
+    assert_eq!(check(&object(2.0, 600.0, Heading::NORTH)), Err(UnableApproach::TooClose));
+    // Beyond the threshold is also too close.
+    assert_eq!(check(&object(-1.0, 600.0, Heading::NORTH)), Err(UnableApproach::TooClose));
+}
+
+#[test]
+fn test_intercept_too_high() {
+    // The glidepath is about 1900 ft at 6 nm.
+    assert_eq!(check(&object(6.0, 2300.0, Heading::NORTH)), Ok(()));
+    assert_eq!(check(&object(6.0, 5000.0, Heading::NORTH)), Err(UnableApproach::TooHigh));
+}
+
+#[test]
+fn test_intercept_angle_too_large() {
+    assert_eq!(check(&object(10.0, 3000.0, Heading::EAST)), Err(UnableApproach::InterceptAngle));
+}
+
+#[test]
+fn test_set_crossing_altitude() {
+    let mut world = World::new();
+    world.init_resource::<Time<time::Virtual>>();
+    let waypoint = world
+        .spawn(Waypoint {
+            name:         "DWIND".into(),
+            position:     Position::from_origin_nm(0.0, 10.0).with_altitude(ELEVATION),
+            display_type: waypoint::DisplayType::Waypoint,
+            hidden:       false,
+        })
+        .id();
+    let mut route = Route::default();
+    route.push(
+        DirectWaypointNode {
+            waypoint,
+            distance: Length::from_nm(1.0),
+            proximity: WaypointProximity::FlyOver,
+            altitude: None,
+        }
+        .into(),
+    );
+    let object = world.spawn((object(0.0, 6000.0, Heading::NORTH), route)).id();
+
+    let altitude = Position::from_amsl_feet(4000.0);
+    SetCrossingAltitude { waypoint, altitude }.apply(world.entity_mut(object));
+
+    let route = world.get::<Route>(object).expect("route is retained");
+    let Some(Node::DirectWaypoint(node)) = route.current() else {
+        panic!("route should still fly towards the waypoint");
+    };
+    assert_eq!(node.altitude, Some(altitude));
+    let restriction = world.get::<CrossingRestriction>(object).expect("restriction is monitored");
+    assert_eq!(restriction.waypoint, waypoint);
+}
+
+#[test]
+fn test_conditional_speed_within_distance() {
+    let mut app = App::new();
+    app.add_systems(app::Update, conditional_speed_system);
+    app.init_resource::<Time<time::Virtual>>();
+
+    let waypoint = app
+        .world_mut()
+        .spawn(Waypoint {
+            name:         "MAIN07".into(),
+            position:     Position::ORIGIN.with_altitude(ELEVATION),
+            display_type: waypoint::DisplayType::Waypoint,
+            hidden:       false,
+        })
+        .id();
+    let object = app
+        .world_mut()
+        .spawn((
+            object(15.0, 3000.0, Heading::NORTH),
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::NORTH),
+                horiz_speed: Speed::from_knots(250.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+            pilot::Clearance { speed: Some(Speed::from_knots(250.0)), ..Default::default() },
+            ConditionalSpeed {
+                speed:     Speed::from_knots(180.0),
+                condition: SpeedCondition::Distance { waypoint, distance: Length::from_nm(10.0) },
+            },
+        ))
+        .id();
+
+    app.update();
+    let target = app.world().get::<nav::VelocityTarget>(object).expect("spawned");
+    assert_eq!(target.horiz_speed, Speed::from_knots(250.0));
+
+    app.world_mut().get_mut::<Object>(object).expect("spawned").position =
+        Position::from_origin_nm(0.0, -9.0).with_altitude(Position::from_amsl_feet(3000.0));
+    app.update();
+
+    let target = app.world().get::<nav::VelocityTarget>(object).expect("spawned");
+    assert_eq!(target.horiz_speed, Speed::from_knots(180.0));
+    let clearance = app.world().get::<pilot::Clearance>(object).expect("spawned");
+    assert_eq!(clearance.speed, Some(Speed::from_knots(180.0)));
+    assert!(app.world().get::<ConditionalSpeed>(object).is_none());
+}
+
+fn preset(
+    ref_id: &str,
+    nodes: Vec<store::RouteNode>,
+    include: Option<store::PresetInclude>,
+) -> store::RoutePreset {
+    store::RoutePreset {
+        trigger: store::RoutePresetTrigger::Waypoint(store::WaypointRef::Named("A".into())),
+        id: ref_id.into(),
+        ref_id: Some(ref_id.into()),
+        title: ref_id.into(),
+        nodes,
+        destinations: Vec::new(),
+        equipage: store::Equipage::empty(),
+        include,
+    }
+}
+
+fn direct(name: &str) -> store::RouteNode {
+    store::RouteNode::DirectWaypoint {
+        waypoint:  store::WaypointRef::Named(name.into()),
+        distance:  Length::from_nm(1.),
+        proximity: WaypointProximity::FlyBy,
+        altitude:  None,
+    }
+}
+
+fn landing(runway_name: &str) -> store::RouteNode {
+    store::RouteNode::RunwayLanding {
+        runway:          store::RunwayRef {
+            aerodrome:   "MAIN".into(),
+            runway_name: runway_name.into(),
+        },
+        goaround_preset: None,
+        current_phase:   store::LandingPhase::Align,
+    }
+}
+
+#[test]
+fn test_expand_nested_includes_with_overrides() {
+    let final_ = preset("FINAL", vec![direct("C"), landing("18L")], None);
+    let middle = preset("MIDDLE", vec![direct("B")], Some(store::PresetInclude::new("FINAL")));
+    let arrival = preset(
+        "ARRIVAL",
+        vec![direct("A")],
+        Some(store::PresetInclude {
+            preset:   "MIDDLE".into(),
+            runway:   Some(store::RunwayRef {
+                aerodrome:   "MAIN".into(),
+                runway_name: "18R".into(),
+            }),
+            altitude: Some(Position::from_amsl_feet(4000.)),
+        }),
+    );
+    let presets = [final_, middle, arrival];
+    let by_ref = presets.iter().map(|p| (p.ref_id.as_ref().unwrap(), p)).collect();
+
+    let expanded = loader::expand_includes(&by_ref, &presets[2]).expect("expand");
+    assert!(expanded.include.is_none());
+    let [
+        store::RouteNode::DirectWaypoint {
+            waypoint: store::WaypointRef::Named(a),
+            altitude: None,
+            ..
+        },
+        store::RouteNode::DirectWaypoint {
+            waypoint: store::WaypointRef::Named(b),
+            altitude: Some(altitude),
+            ..
+        },
+        store::RouteNode::DirectWaypoint {
+            waypoint: store::WaypointRef::Named(c),
+            altitude: None,
+            ..
+        },
+        store::RouteNode::RunwayLanding { runway, .. },
+    ] = &expanded.nodes[..]
+    else {
+        panic!("unexpected expanded nodes");
+    };
+    assert_eq!([&a.0, &b.0, &c.0], ["A", "B", "C"]);
+    assert_eq!(*altitude, Position::from_amsl_feet(4000.));
+    assert_eq!(runway.runway_name, "18R");
+}
+
+#[test]
+fn test_expand_include_errors() {
+    let a = preset("A", vec![direct("A")], Some(store::PresetInclude::new("B")));
+    let b = preset("B", vec![direct("B")], Some(store::PresetInclude::new("A")));
+    let dangling = preset("C", vec![direct("C")], Some(store::PresetInclude::new("MISSING")));
+    let presets = [a, b, dangling];
+    let by_ref = presets.iter().map(|p| (p.ref_id.as_ref().unwrap(), p)).collect();
+
+    assert!(matches!(
+        loader::expand_includes(&by_ref, &presets[0]),
+        Err(load::Error::RoutePresetCycle(id)) if id == "A"
+    ));
+    assert!(matches!(
+        loader::expand_includes(&by_ref, &presets[2]),
+        Err(load::Error::UnresolvedRoutePreset(id)) if id == "MISSING"
+    ));
+}
+
+#[test]
+fn test_destination_matcher_applies_in_configuration() {
+    use super::{DestinationMatcher, DestinationMatcherItem, DestinationTarget};
+    use crate::level::dest::Destination;
+
+    let mut world = World::new();
+    let aerodrome = world.spawn_empty().id();
+    let configuration = world.spawn_empty().id();
+    let matcher = DestinationMatcher {
+        items: vec![DestinationMatcherItem {
+            target:         DestinationTarget::Arrival { aerodrome },
+            configurations: vec![configuration],
+        }],
+    };
+    let dest = Destination::Landing { aerodrome };
+
+    assert!(matcher.matches(&dest));
+    assert!(matcher.applies(&dest, |entity| entity == configuration));
+    assert!(!matcher.applies(&dest, |_| false));
+}
@@ -13,7 +13,7 @@ use super::{HorizontalTarget, NextNode, RunCurrentNode};
 use crate::QueryTryLog;
 use crate::level::object::Object;
 use crate::level::waypoint::Waypoint;
-use crate::level::{nav, navaid, taxi};
+use crate::level::{nav, navaid, pilot, taxi};
 
 #[derive(Component)]
 pub(super) struct FlyOver {
@@ -60,7 +60,7 @@ pub(super) enum FlyByCompletionCondition {
 pub(super) fn fly_by_system(
     time: Res<Time<time::Virtual>>,
     waypoint_query: Query<&Waypoint>,
-    object_query: Query<(Entity, &Object, &nav::Limits, &FlyBy)>,
+    object_query: Query<(Entity, &Object, &nav::Limits, &FlyBy, Option<&pilot::Response>)>,
     mut commands: Commands,
 ) {
     if time.is_paused() {
@@ -73,6 +73,7 @@ pub(super) fn fly_by_system(
             &Object { position: current_pos, ground_speed: speed },
             nav_limits,
             trigger,
+            response,
         )| {
             let Some(&Waypoint { position: current_target, .. }) =
                 waypoint_query.log_get(trigger.waypoint)
@@ -104,7 +105,8 @@ pub(super) fn fly_by_system(
                         .arc_to_radius(nav_limits.max_yaw_speed);
                     let turn_distance = turn_radius
                         * (current_heading.closest_distance(next_heading).abs() / 2.)
-                            .acute_signed_tan();
+                            .acute_signed_tan()
+                        * response.map_or(1.0, |response| response.turn_anticipation);
 
                     if current_pos.horizontal().distance_cmp(current_target) <= turn_distance {
                         commands.entity(object_entity).queue(NextNode);
@@ -1,20 +1,27 @@
+use std::time::Duration;
+
 use bevy::app::{self, App, Plugin};
+use bevy::ecs::change_detection::DetectChangesMut;
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::message::Message;
-use bevy::ecs::query::Without;
+use bevy::ecs::query::{Has, Without};
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use bevy::ecs::system::{EntityCommand, Query};
+use bevy::ecs::system::{Commands, EntityCommand, Query, Res};
 use bevy::ecs::world::EntityWorldMut;
 use bevy::math::{Vec2, Vec3};
+use bevy::time::{self, Time};
 use math::{Angle, Length, Position};
 use smallvec::SmallVec;
 
 use super::navaid::Navaid;
 use super::waypoint::{self, Waypoint};
-use super::{SystemSets, navaid};
+use super::{SystemSets, message, navaid};
 use crate::QueryTryLog;
 
+#[cfg(test)]
+mod tests;
+
 pub struct Plug;
 
 impl Plugin for Plug {
@@ -24,6 +31,10 @@ impl Plugin for Plug {
             app::Update,
             maintain_localizer_waypoint_system.in_set(SystemSets::PrepareEnviron),
         );
+        app.add_systems(
+            app::Update,
+            (closure_system, configuration_system).chain().in_set(SystemSets::PrepareEnviron),
+        );
     }
 }
 
@@ -86,7 +97,112 @@ pub struct Condition {
     /// A multiplier to the base braking rate of an object.
     ///
     /// This value may decrease to a value between 0 and 1 when it is wet.
+    /// Maintained by the [`surface`](super::surface) module from `surface`.
     pub friction_factor: f32,
+    /// Current surface condition of the runway.
+    pub surface:         store::SurfaceCondition,
+}
+
+/// Operations currently accepted by a runway.
+#[derive(Debug, Component, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Availability {
+    /// The runway accepts all operations.
+    #[default]
+    Open,
+    /// The runway is used exclusively for departures.
+    DeparturesOnly,
+    /// The runway is closed to all operations.
+    Closed,
+}
+
+impl Availability {
+    #[must_use]
+    pub fn accepts_arrivals(self) -> bool { self == Self::Open }
+}
+
+impl From<store::ClosureKind> for Availability {
+    fn from(kind: store::ClosureKind) -> Self {
+        match kind {
+            store::ClosureKind::Closed => Self::Closed,
+            store::ClosureKind::DeparturesOnly => Self::DeparturesOnly,
+        }
+    }
+}
+
+/// A scheduled period during which a runway does not accept arrivals.
+#[derive(Component)]
+pub struct Closure {
+    pub runway: Entity,
+    pub kind:   store::ClosureKind,
+    /// Virtual time at which the closure starts.
+    pub start:  Duration,
+    /// Virtual time at which the closure ends.
+    pub end:    Duration,
+}
+
+/// A named set of runways used together at an aerodrome.
+#[derive(Component)]
+pub struct Configuration {
+    pub name:    String,
+    /// Runway entities in use under this configuration.
+    pub runways: Vec<Entity>,
+}
+
+/// Component on a [`Configuration`] entity referencing the aerodrome it belongs to.
+#[derive(Component)]
+#[relationship(relationship_target = AerodromeConfigurations)]
+pub struct ConfigurationOf(pub Entity);
+
+/// Runway configurations of an aerodrome in descending order of preference.
+///
+/// Component on aerodrome entities.
+#[derive(Component)]
+#[relationship_target(relationship = ConfigurationOf, linked_spawn)]
+pub struct AerodromeConfigurations(Vec<Entity>);
+
+impl AerodromeConfigurations {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + use<'_> { self.0.iter().copied() }
+}
+
+/// Marks the active [`Configuration`] of an aerodrome.
+///
+/// The first configuration of an aerodrome without closed runways is active.
+#[derive(Component)]
+pub struct ActiveConfiguration;
+
+/// Duration for which runway availability changes are displayed.
+const AVAILABILITY_MESSAGE_DURATION: Duration = Duration::from_secs(30);
+
+fn closure_system(
+    time: Res<Time<time::Virtual>>,
+    closure_query: Query<&Closure>,
+    runway_query: Query<(Entity, &mut Availability)>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+    for (runway, mut availability) in runway_query {
+        let scheduled = closure_query
+            .iter()
+            .filter(|closure| {
+                closure.runway == runway && (closure.start..closure.end).contains(&now)
+            })
+            .map(|closure| Availability::from(closure.kind))
+            .max_by_key(|&availability| availability == Availability::Closed)
+            .unwrap_or_default();
+        if availability.set_if_neq(scheduled) {
+            let content = match scheduled {
+                Availability::Open => "Runway open",
+                Availability::DeparturesOnly => "Runway in use for departures only",
+                Availability::Closed => "Runway closed",
+            };
+            commands.queue(message::SendExpiring {
+                source:   runway,
+                content:  content.into(),
+                class:    message::Class::AnomalyInfo,
+                duration: AVAILABILITY_MESSAGE_DURATION,
+            });
+        }
+    }
 }
 
 pub struct SpawnCommand {
@@ -103,7 +219,12 @@ impl EntityCommand for SpawnCommand {
             waypoint::SpawnCommand { waypoint: self.waypoint }.apply(world.entity_mut(entity_id));
         });
 
-        entity.insert((self.runway, Condition { friction_factor: 1. }, RunwayOf(self.aerodrome)));
+        entity.insert((
+            self.runway,
+            Condition { friction_factor: 1., surface: store::SurfaceCondition::Dry },
+            Availability::Open,
+            RunwayOf(self.aerodrome),
+        ));
         entity.world_scope(|world| world.write_message(SpawnMessage(entity_id)));
     }
 }
@@ -162,3 +283,49 @@ fn maintain_localizer_waypoint_system(
 pub struct GroundSegmentList {
     pub segments: SmallVec<[Entity; 8]>,
 }
+
+fn configuration_system(
+    aerodrome_query: Query<&AerodromeConfigurations>,
+    configuration_query: Query<(&Configuration, &ConfigurationOf, Has<ActiveConfiguration>)>,
+    availability_query: Query<&Availability>,
+    mut commands: Commands,
+) {
+    for configurations in aerodrome_query {
+        let usable = |entity: Entity| {
+            configuration_query.get(entity).is_ok_and(|(configuration, ..)| {
+                configuration.runways.iter().all(|&runway| {
+                    availability_query.get(runway).is_ok_and(|&a| a != Availability::Closed)
+                })
+            })
+        };
+        let selected = configurations.iter().find(|&entity| usable(entity));
+        let had_active = configurations
+            .iter()
+            .any(|entity| configuration_query.get(entity).is_ok_and(|(.., active)| active));
+
+        for entity in configurations.iter() {
+            let Ok((configuration, &ConfigurationOf(aerodrome), active)) =
+                configuration_query.get(entity)
+            else {
+                continue;
+            };
+            let should_be_active = selected == Some(entity);
+            if active == should_be_active {
+                continue;
+            }
+            if should_be_active {
+                commands.entity(entity).insert(ActiveConfiguration);
+                if had_active {
+                    commands.queue(message::SendExpiring {
+                        source:   aerodrome,
+                        content:  format!("Runway configuration changed to {}", configuration.name),
+                        class:    message::Class::AnomalyInfo,
+                        duration: AVAILABILITY_MESSAGE_DURATION,
+                    });
+                }
+            } else {
+                commands.entity(entity).remove::<ActiveConfiguration>();
+            }
+        }
+    }
+}
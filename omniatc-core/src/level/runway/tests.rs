@@ -0,0 +1,46 @@
+use bevy::app::{self, App};
+use bevy::ecs::entity::Entity;
+use bevy::time::{self, Time};
+
+use super::{
+    ActiveConfiguration, Availability, Configuration, ConfigurationOf, configuration_system,
+};
+use crate::level::message::Message;
+
+fn spawn_configuration(
+    app: &mut App,
+    aerodrome: Entity,
+    name: &str,
+    runways: Vec<Entity>,
+) -> Entity {
+    app.world_mut()
+        .spawn((Configuration { name: name.into(), runways }, ConfigurationOf(aerodrome)))
+        .id()
+}
+
+#[test]
+fn test_first_usable_configuration_is_active() {
+    let mut app = App::new();
+    app.init_resource::<Time<time::Virtual>>();
+    app.add_systems(app::Update, configuration_system);
+
+    let aerodrome = app.world_mut().spawn_empty().id();
+    let north = app.world_mut().spawn(Availability::Open).id();
+    let south = app.world_mut().spawn(Availability::Open).id();
+    let north_flow = spawn_configuration(&mut app, aerodrome, "North", vec![north]);
+    let south_flow = spawn_configuration(&mut app, aerodrome, "South", vec![south]);
+
+    app.update();
+    assert!(app.world().get::<ActiveConfiguration>(north_flow).is_some());
+    assert!(app.world().get::<ActiveConfiguration>(south_flow).is_none());
+    assert_eq!(app.world_mut().query::<&Message>().iter(app.world()).len(), 0);
+
+    *app.world_mut().get_mut::<Availability>(north).expect("runway") = Availability::Closed;
+    app.update();
+    assert!(app.world().get::<ActiveConfiguration>(north_flow).is_none());
+    assert!(app.world().get::<ActiveConfiguration>(south_flow).is_some());
+
+    let messages: Vec<_> =
+        app.world_mut().query::<&Message>().iter(app.world()).map(|m| m.content.clone()).collect();
+    assert_eq!(messages, ["Runway configuration changed to South"]);
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
 use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::Entity;
 use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::{IntoScheduleConfigs, SystemSet};
 use store::Score;
@@ -39,4 +41,28 @@ pub struct Stats {
     pub num_conflicts:       u32,
     /// Total duration-pair time of all detected conflicts.
     pub total_conflict_time: Duration,
+
+    /// Number of requests from objects approved by the user.
+    pub num_pilot_requests_approved: u32,
+    /// Number of requests from objects denied by the user or left unanswered.
+    pub num_pilot_requests_denied:   u32,
+
+    /// Number of go-arounds for each cause.
+    pub go_arounds: store::GoAroundStats,
+
+    /// Statistics for each aerodrome, keyed by the aerodrome entity.
+    ///
+    /// Aerodromes without any completed objects may be absent.
+    pub aerodromes: HashMap<Entity, AerodromeStats>,
+}
+
+/// Statistics for objects completed at a single aerodrome.
+#[derive(Default, Clone, Copy)]
+pub struct AerodromeStats {
+    /// Number of objects completed with runway arrival at this aerodrome.
+    pub num_runway_arrivals: u32,
+    /// Number of objects completed with apron arrival at this aerodrome.
+    pub num_apron_arrivals:  u32,
+    /// Number of objects departing from this aerodrome that completed their departure.
+    pub num_departures:      u32,
 }
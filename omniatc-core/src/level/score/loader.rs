@@ -1,14 +1,41 @@
 use bevy::ecs::world::World;
 
+use crate::level::aerodrome::loader::AerodromeMap;
 use crate::level::score;
+use crate::load;
+
+/// Loads the statistics of a saved game.
+///
+/// # Errors
+/// If the per-aerodrome statistics reference an unknown aerodrome.
+pub fn spawn(world: &mut World, stats: &store::Stats, aerodromes: &AerodromeMap) -> load::Result {
+    let aerodrome_stats = stats
+        .aerodromes
+        .iter()
+        .map(|entry| {
+            let aerodrome = aerodromes.resolve(&entry.aerodrome)?;
+            Ok((
+                aerodrome.aerodrome_entity,
+                score::AerodromeStats {
+                    num_runway_arrivals: entry.num_runway_arrivals,
+                    num_apron_arrivals:  entry.num_apron_arrivals,
+                    num_departures:      entry.num_departures,
+                },
+            ))
+        })
+        .collect::<load::HashMapResult<_, _>>()?;
 
-pub fn spawn(world: &mut World, stats: &store::Stats) {
     *world.resource_mut::<score::Stats>() = score::Stats {
-        total:               stats.score,
-        num_runway_arrivals: stats.num_runway_arrivals,
-        num_apron_arrivals:  stats.num_apron_arrivals,
-        num_departures:      stats.num_departures,
-        num_conflicts:       stats.num_conflicts,
-        total_conflict_time: stats.total_conflict_time,
+        total:                       stats.score,
+        num_runway_arrivals:         stats.num_runway_arrivals,
+        num_apron_arrivals:          stats.num_apron_arrivals,
+        num_departures:              stats.num_departures,
+        num_conflicts:               stats.num_conflicts,
+        total_conflict_time:         stats.total_conflict_time,
+        num_pilot_requests_approved: stats.num_pilot_requests_approved,
+        num_pilot_requests_denied:   stats.num_pilot_requests_denied,
+        go_arounds:                  stats.go_arounds,
+        aerodromes:                  aerodrome_stats,
     };
+    Ok(())
 }
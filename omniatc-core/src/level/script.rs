@@ -0,0 +1,80 @@
+//! Scenario logic scripts.
+//!
+//! A level may reference script assets by ID in [`store::Level::scripts`].
+//! The sources are resolved from the [`Library`],
+//! which is populated from the assets bundled in the loaded file
+//! and may be extended by the host with shared scripts before loading.
+//!
+//! Scripts are run by an embedded Lua 5.4 runtime when the `scripting` feature is enabled.
+//! Without the feature, referenced scripts are still resolved but never run.
+//!
+//! # Lua API
+//! A script is executed once when the level is loaded,
+//! and then its global `on_tick(state)` function, if defined, is called every tick.
+//! Only the `table`, `string` and `math` standard libraries are available.
+//!
+//! `state` is a read-only snapshot of the world:
+//! - `state.time`: elapsed level time in seconds.
+//! - `state.score`: the current score.
+//! - `state.objects`: an array of objects, each with the fields
+//!   `name`, `x_nm`, `y_nm`, `altitude_ft`, `ground_speed_kt` and `airborne`,
+//!   as well as `id` for objects with a scenario-assigned ID.
+//!
+//! Actions are enqueued through the global `omniatc` table
+//! and applied after `on_tick` returns:
+//! - `omniatc.message(text, class)` shows a message from the script.
+//!   `class` is one of `"info"` (default), `"anomaly"` or `"urgent"`.
+//! - `omniatc.spawn()` spawns one more object from the spawn sets of the level,
+//!   in addition to the spawn trigger.
+//! - `omniatc.set_wind(from_deg, speed_kt)` sets the sea level wind of all weather regions.
+//!
+//! A script that raises an error or exceeds its per-tick instruction budget
+//! is marked as [`Failed`] and no longer run.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "scripting")]
+use bevy::app;
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::resource::Resource;
+#[cfg(feature = "scripting")]
+use bevy::ecs::schedule::IntoScheduleConfigs;
+
+#[cfg(feature = "scripting")]
+use super::SystemSets;
+
+pub mod loader;
+#[cfg(feature = "scripting")]
+mod lua;
+
+#[cfg(all(test, feature = "scripting"))]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Library>();
+        #[cfg(feature = "scripting")]
+        app.add_systems(app::Update, lua::run_system.in_set(SystemSets::QuestCompletion));
+    }
+}
+
+/// Script assets available to levels, keyed by asset ID.
+#[derive(Resource, Default)]
+pub struct Library(pub HashMap<store::ScriptRef, store::ScriptAsset>);
+
+/// A script run by the current level.
+///
+/// The entity is also a [`message::Sender`](super::message::Sender)
+/// for messages sent by the script.
+#[derive(Component)]
+pub struct Script {
+    /// Asset ID of the script.
+    pub id: store::ScriptRef,
+}
+
+/// Marks a script that has stopped running due to an error.
+#[derive(Component)]
+pub struct Failed(pub String);
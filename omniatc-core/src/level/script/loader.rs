@@ -0,0 +1,62 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+
+use super::{Library, Script};
+use crate::level::message;
+use crate::load::{self, StoredEntity};
+
+/// Registers the bundled script assets and spawns the scripts referenced by the level.
+///
+/// Scripts that are not in the [`Library`] or fail to initialize
+/// are skipped and reported as [issues](load::Issues).
+///
+/// # Errors
+/// Currently never fails.
+pub fn spawn(
+    world: &mut World,
+    assets: &[store::ScriptAsset],
+    scripts: &[store::ScriptRef],
+) -> load::Result {
+    let mut library = world.resource_mut::<Library>();
+    for asset in assets {
+        library.0.insert(asset.id.clone(), asset.clone());
+    }
+
+    for id in scripts {
+        let asset = world
+            .resource::<Library>()
+            .0
+            .get(id)
+            .cloned()
+            .ok_or_else(|| load::Error::UnresolvedScript(id.0.clone()));
+        let Some(asset) = load::recover(world, || format!("script {}", id.0), asset)? else {
+            continue;
+        };
+
+        #[cfg(feature = "scripting")]
+        let runtime = super::lua::Runtime::new(&asset)
+            .map_err(|err| load::Error::Script { id: id.0.clone(), message: err.to_string() });
+        #[cfg(feature = "scripting")]
+        let Some(runtime) = load::recover(world, || format!("script {}", id.0), runtime)? else {
+            continue;
+        };
+        #[cfg(not(feature = "scripting"))]
+        bevy::log::warn!(
+            "Scripting is not enabled in this build, script {:?} is ignored",
+            asset.id.0
+        );
+
+        let bundle = (
+            Script { id: id.clone() },
+            message::Sender { display: id.0.clone() },
+            Name::new(format!("Script: {}", id.0)),
+            StoredEntity,
+        );
+        #[cfg(feature = "scripting")]
+        world.spawn((bundle, runtime));
+        #[cfg(not(feature = "scripting"))]
+        world.spawn(bundle);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, Without};
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::time::{self, Time};
+use math::{Heading, Speed};
+
+use super::{Failed, Script};
+use crate::level::{message, object, score, spawn, weather};
+
+/// Duration for which messages sent by scripts are displayed.
+const MESSAGE_DURATION: Duration = Duration::from_mins(1);
+/// Maximum memory usage of a script runtime.
+const MEMORY_LIMIT: usize = 16 << 20;
+/// Number of instructions between two budget checks.
+const HOOK_INTERVAL: u32 = 1000;
+/// Maximum number of budget checks in a single call, i.e. 1M instructions.
+const MAX_HOOK_COUNT: u32 = 1000;
+
+/// Lua runtime of a [`Script`].
+#[derive(Component)]
+pub(super) struct Runtime {
+    lua:        Mutex<mlua::Lua>,
+    /// Number of budget checks during the current call.
+    hook_count: Arc<AtomicU32>,
+}
+
+/// An action enqueued by a script during a call.
+enum Action {
+    Message { content: String, class: message::Class },
+    Spawn,
+    SetWind(Speed<bevy::math::Vec2>),
+}
+
+#[derive(Default)]
+struct Actions(Vec<Action>);
+
+impl Runtime {
+    /// Creates a runtime and executes the top-level chunk of the script.
+    pub(super) fn new(asset: &store::ScriptAsset) -> mlua::Result<Self> {
+        let store::ScriptLanguage::Lua = asset.language;
+
+        let lua = mlua::Lua::new_with(
+            mlua::StdLib::TABLE | mlua::StdLib::STRING | mlua::StdLib::MATH,
+            mlua::LuaOptions::new(),
+        )?;
+        lua.set_memory_limit(MEMORY_LIMIT)?;
+        lua.set_app_data(Actions::default());
+
+        let hook_count = Arc::new(AtomicU32::new(0));
+        lua.set_hook(mlua::HookTriggers::new().every_nth_instruction(HOOK_INTERVAL), {
+            let hook_count = Arc::clone(&hook_count);
+            move |_, _| {
+                if hook_count.fetch_add(1, Ordering::Relaxed) >= MAX_HOOK_COUNT {
+                    return Err(mlua::Error::runtime("instruction budget exceeded"));
+                }
+                Ok(())
+            }
+        });
+
+        register_api(&lua)?;
+
+        hook_count.store(0, Ordering::Relaxed);
+        lua.load(&asset.source).set_name(&asset.id.0).exec()?;
+
+        Ok(Self { lua: Mutex::new(lua), hook_count })
+    }
+}
+
+fn register_api(lua: &mlua::Lua) -> mlua::Result<()> {
+    fn push(lua: &mlua::Lua, action: Action) {
+        lua.app_data_mut::<Actions>().expect("initialized in Runtime::new").0.push(action);
+    }
+
+    let api = lua.create_table()?;
+    api.set(
+        "message",
+        lua.create_function(|lua, (content, class): (String, Option<String>)| {
+            let class = match class.as_deref() {
+                None | Some("info") => message::Class::VerboseInfo,
+                Some("anomaly") => message::Class::AnomalyInfo,
+                Some("urgent") => message::Class::Urgent,
+                Some(class) => {
+                    return Err(mlua::Error::runtime(format!("unknown message class {class:?}")));
+                }
+            };
+            push(lua, Action::Message { content, class });
+            Ok(())
+        })?,
+    )?;
+    api.set(
+        "spawn",
+        lua.create_function(|lua, ()| {
+            push(lua, Action::Spawn);
+            Ok(())
+        })?,
+    )?;
+    api.set(
+        "set_wind",
+        lua.create_function(|lua, (from_deg, speed_kt): (f32, f32)| {
+            // Wind direction is reported as the direction the wind blows from.
+            let towards = Heading::from_degrees(from_deg).opposite();
+            let velocity = Speed::new(towards.into_dir2() * Speed::from_knots(speed_kt).0);
+            push(lua, Action::SetWind(velocity));
+            Ok(())
+        })?,
+    )?;
+    lua.globals().set("omniatc", api)?;
+    Ok(())
+}
+
+type ObjectData = (
+    &'static object::Display,
+    Option<&'static object::ScenarioId>,
+    &'static object::Object,
+    Has<object::Airborne>,
+);
+
+pub(super) fn run_system(
+    time: Res<Time<time::Virtual>>,
+    stats: Res<score::Stats>,
+    object_query: Query<ObjectData>,
+    script_query: Query<(Entity, &Script, &Runtime), Without<Failed>>,
+    mut weather_query: Query<&mut weather::Weather>,
+    mut spawn_requests: ResMut<spawn::Requested>,
+    mut commands: Commands,
+) {
+    for (entity, script, runtime) in &script_query {
+        let lua = runtime.lua.lock().expect("poisoned");
+        runtime.hook_count.store(0, Ordering::Relaxed);
+
+        let result = tick(&lua, &time, &stats, &object_query);
+        let actions = lua.app_data_mut::<Actions>().map(|mut actions| actions.0.split_off(0));
+
+        if let Err(err) = result {
+            bevy::log::error!("Script {:?} failed: {err}", script.id.0);
+            commands.queue(message::SendExpiring {
+                source:   entity,
+                content:  format!("Script failed: {err}"),
+                class:    message::Class::AnomalyInfo,
+                duration: MESSAGE_DURATION,
+            });
+            commands.entity(entity).insert(Failed(err.to_string()));
+            continue;
+        }
+
+        for action in actions.into_iter().flatten() {
+            match action {
+                Action::Message { content, class } => {
+                    commands.queue(message::SendExpiring {
+                        source: entity,
+                        content,
+                        class,
+                        duration: MESSAGE_DURATION,
+                    });
+                }
+                Action::Spawn => spawn_requests.0 += 1,
+                Action::SetWind(velocity) => {
+                    for mut weather in &mut weather_query {
+                        weather.sea_wind = velocity;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn tick(
+    lua: &mlua::Lua,
+    time: &Time<time::Virtual>,
+    score_stats: &score::Stats,
+    object_query: &Query<ObjectData>,
+) -> mlua::Result<()> {
+    let Some(on_tick) = lua.globals().get::<_, Option<mlua::Function>>("on_tick")? else {
+        return Ok(());
+    };
+
+    let state = lua.create_table()?;
+    state.set("time", time.elapsed_secs_f64())?;
+    state.set("score", score_stats.total.0)?;
+
+    let objects = lua.create_table()?;
+    for (display, id, object, airborne) in object_query {
+        let entry = lua.create_table()?;
+        let [x_nm, y_nm] = object.position.horizontal().get().to_array();
+        entry.set("name", display.name.as_str())?;
+        if let Some(object::ScenarioId(id)) = id {
+            entry.set("id", id.0.as_str())?;
+        }
+        entry.set("x_nm", x_nm)?;
+        entry.set("y_nm", y_nm)?;
+        entry.set("altitude_ft", object.position.altitude().amsl().into_feet())?;
+        entry.set(
+            "ground_speed_kt",
+            object.ground_speed.horizontal().magnitude_exact().into_knots(),
+        )?;
+        entry.set("airborne", airborne)?;
+        objects.push(entry)?;
+    }
+    state.set("objects", objects)?;
+
+    on_tick.call::<_, ()>(state)
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::time::{self, Time};
+use math::Speed;
+
+use super::{Failed, Script, loader};
+use crate::level::{SystemSets, message, score, script, spawn, test_util, weather};
+use crate::load;
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, score::Plug, script::Plug));
+    app.init_resource::<spawn::Requested>();
+    app.update();
+    app
+}
+
+fn asset(source: &str) -> store::ScriptAsset {
+    store::ScriptAsset {
+        id:       store::ScriptRef("test".into()),
+        language: store::ScriptLanguage::Lua,
+        source:   source.into(),
+    }
+}
+
+fn load_script(app: &mut App, source: &str) -> load::Result<Entity> {
+    let asset = asset(source);
+    let id = asset.id.clone();
+    loader::spawn(app.world_mut(), &[asset], &[id])?;
+    let entity = app
+        .world_mut()
+        .query_filtered::<Entity, With<Script>>()
+        .single(app.world())
+        .expect("exactly one script");
+    Ok(entity)
+}
+
+fn advance(app: &mut App) {
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(1));
+    app.update();
+}
+
+#[test]
+fn message_and_spawn_actions() {
+    let mut app = base_app();
+    let script = load_script(
+        &mut app,
+        r#"
+            function on_tick(state)
+                if state.time >= 1 and not done then
+                    omniatc.message("Hello from " .. #state.objects .. " objects", "anomaly")
+                    omniatc.spawn()
+                    done = true
+                end
+            end
+        "#,
+    )
+    .expect("load script");
+
+    advance(&mut app);
+
+    let world = app.world_mut();
+    let messages: Vec<_> = world
+        .query::<&message::Message>()
+        .iter(world)
+        .map(|message| (message.source, message.content.clone(), message.class))
+        .collect();
+    assert_eq!(messages, [(script, "Hello from 0 objects".into(), message::Class::AnomalyInfo)]);
+    assert_eq!(world.resource::<spawn::Requested>().0, 1);
+}
+
+#[test]
+fn set_wind_action() {
+    let mut app = base_app();
+    let weather = app.world_mut().spawn(weather::Weather::default()).id();
+    load_script(&mut app, "function on_tick() omniatc.set_wind(270, 20) end").expect("load script");
+
+    advance(&mut app);
+
+    // Wind from the west blows towards the east.
+    let wind = app.world().get::<weather::Weather>(weather).expect("weather entity").sea_wind;
+    Speed::from_knots_vec2(20.0, 0.0)
+        .assert_near(wind, Speed::from_knots(0.01))
+        .expect("wind velocity");
+}
+
+#[test]
+fn instruction_budget_fails_script() {
+    let mut app = base_app();
+    let script =
+        load_script(&mut app, "function on_tick() while true do end end").expect("load script");
+
+    advance(&mut app);
+
+    assert!(app.world().get::<Failed>(script).is_some());
+}
+
+#[test]
+fn unresolved_script() {
+    let mut app = base_app();
+    loader::spawn(app.world_mut(), &[], &[store::ScriptRef("missing".into())])
+        .expect("missing scripts are skipped");
+
+    let issues = &app.world().resource::<load::Issues>().0;
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(&issues[0].error, load::Error::UnresolvedScript(id) if id == "missing"));
+}
@@ -0,0 +1,55 @@
+//! Named airspace sectors displayed as boundary outlines.
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::Message;
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::EntityWorldMut;
+use bevy::math::Vec2;
+use math::Position;
+
+pub mod loader;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) { app.add_message::<SpawnMessage>(); }
+}
+
+/// A named volume of airspace delimited by a boundary polygon and an altitude range.
+#[derive(Component)]
+pub struct Sector {
+    /// Display name of the sector.
+    pub name:         String,
+    /// Vertices of the boundary polygon in order.
+    ///
+    /// The last vertex is implicitly connected to the first one.
+    pub boundary:     Vec<Position<Vec2>>,
+    /// Lowest altitude of the sector.
+    pub min_altitude: Option<Position<f32>>,
+    /// Highest altitude of the sector.
+    pub max_altitude: Option<Position<f32>>,
+}
+
+impl Sector {
+    /// Returns the edges of the boundary polygon, including the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = [Position<Vec2>; 2]> + '_ {
+        self.boundary.iter().zip(self.boundary.iter().cycle().skip(1)).map(|(&a, &b)| [a, b])
+    }
+}
+
+pub struct SpawnCommand {
+    pub sector: Sector,
+}
+
+impl EntityCommand for SpawnCommand {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert(self.sector);
+        let entity_id = entity.id();
+        entity.world_scope(|world| world.write_message(SpawnMessage(entity_id)));
+    }
+}
+
+#[derive(Message)]
+pub struct SpawnMessage(pub Entity);
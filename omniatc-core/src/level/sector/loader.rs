@@ -0,0 +1,23 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::system::EntityCommand;
+use bevy::ecs::world::World;
+
+use crate::level::sector::{self, Sector};
+use crate::load::StoredEntity;
+
+/// Spawns the airspace sectors declared in a store.
+pub fn spawn(world: &mut World, sectors: &[store::Sector]) {
+    for sector in sectors {
+        let sector_entity =
+            world.spawn((StoredEntity, Name::new(format!("Sector: {}", sector.name)))).id();
+        sector::SpawnCommand {
+            sector: Sector {
+                name:         sector.name.clone(),
+                boundary:     sector.boundary.clone(),
+                min_altitude: sector.min_altitude,
+                max_altitude: sector.max_altitude,
+            },
+        }
+        .apply(world.entity_mut(sector_entity));
+    }
+}
@@ -0,0 +1,358 @@
+//! Recording of played sessions for offline verification.
+//!
+//! A session starts when a file is loaded.
+//! The simulation of a session is reproducible from the loaded file,
+//! the [`Seed`] of all random number generators,
+//! the virtual time delta of each frame
+//! and the actions taken by the player.
+//! The [`Log`] records the latter two,
+//! which are exported as a [`store::SessionResult`] with [`build_result`]
+//! and re-simulated with [`replay::run`].
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Added, Without};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Local, Query, Res, ResMut, SystemParam};
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use super::{SystemSets, conflict, instr, object, score};
+use crate::util;
+
+pub mod bookmark;
+mod convert;
+pub use convert::{NameParams, record_state, resolve, restore_state};
+pub mod replay;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Seed>();
+        app.init_resource::<WorldRng>();
+        app.init_resource::<Log>();
+        app.add_systems(
+            app::Update,
+            (
+                record_frame_system.in_set(SystemSets::PrepareEnviron),
+                record_instructions_system
+                    .in_set(SystemSets::Communicate)
+                    .before(instr::dispatch_system),
+            ),
+        );
+        app.add_plugins(bookmark::Plug);
+    }
+}
+
+/// Stream of [`SessionRng`] for object spawning.
+pub const STREAM_SPAWN: u64 = 1;
+/// Stream of [`SessionRng`] for timetable spawning.
+pub const STREAM_TIMETABLE: u64 = 2;
+/// Stream of [`SessionRng`] for pilot request generation.
+pub const STREAM_PILOT_REQUEST: u64 = 3;
+/// Stream of [`with_world_rng`].
+const STREAM_WORLD: u64 = 4;
+/// Stream of [`SessionRng`] for bird activity.
+pub const STREAM_BIRD_ACTIVITY: u64 = 5;
+/// Stream of [`SessionRng`] for bird strikes.
+pub const STREAM_BIRD_STRIKE: u64 = 6;
+/// Stream of [`SessionRng`] for VFR wandering.
+pub const STREAM_VFR_WANDER: u64 = 7;
+/// Stream of [`SessionRng`] for VFR instruction compliance.
+pub const STREAM_VFR_COMPLIANCE: u64 = 8;
+/// Stream of [`SessionRng`] for pilot response characteristics.
+pub const STREAM_PILOT: u64 = 9;
+/// Stream of [`SessionRng`] for pilot errors.
+pub const STREAM_PILOT_ERROR: u64 = 10;
+/// Stream of [`SessionRng`] for altitude requests in turbulence.
+pub const STREAM_TURBULENCE: u64 = 11;
+/// Stream of [`SessionRng`] for transponder failures.
+pub const STREAM_TRANSPONDER: u64 = 12;
+/// Stream of [`SessionRng`] for radio failures.
+pub const STREAM_RADIO: u64 = 13;
+
+/// Seed of all random number generators in the current session.
+#[derive(Resource, Default)]
+pub struct Seed {
+    value:      u64,
+    /// Incremented every time the seed is set,
+    /// so that generators are reseeded even if the same value is set again.
+    generation: u64,
+}
+
+impl Seed {
+    #[must_use]
+    pub fn value(&self) -> u64 { self.value }
+
+    /// Sets the seed for the session, reseeding all generators.
+    pub fn set(&mut self, value: u64) {
+        self.value = value;
+        self.generation += 1;
+    }
+
+    fn rng(&self, stream: u64) -> SmallRng {
+        SmallRng::seed_from_u64(self.value ^ stream.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+    }
+}
+
+/// A random number generator derived from the session [`Seed`].
+#[derive(SystemParam)]
+pub struct SessionRng<'w, 's> {
+    seed: Res<'w, Seed>,
+    rng:  Local<'s, Option<(u64, SmallRng)>>,
+}
+
+impl SessionRng<'_, '_> {
+    /// Returns the generator for the given stream,
+    /// reseeded if the session seed has been set since the last call.
+    pub fn get(&mut self, stream: u64) -> &mut SmallRng {
+        let generation = self.seed.generation;
+        if self.rng.as_ref().is_none_or(|&(seeded, _)| seeded != generation) {
+            *self.rng = Some((generation, self.seed.rng(stream)));
+        }
+        let (_, rng) = self.rng.as_mut().expect("just inserted");
+        rng
+    }
+}
+
+#[derive(Resource, Default)]
+struct WorldRng(Option<(u64, SmallRng)>);
+
+/// Runs `f` with the session generator for code with exclusive world access.
+pub fn with_world_rng<R>(world: &mut World, f: impl FnOnce(&mut SmallRng) -> R) -> R {
+    world.init_resource::<Seed>();
+    world.init_resource::<WorldRng>();
+
+    let seed = world.resource::<Seed>();
+    let generation = seed.generation;
+    let fresh = seed.rng(STREAM_WORLD);
+
+    let mut world_rng = world.resource_mut::<WorldRng>();
+    if world_rng.0.as_ref().is_none_or(|&(seeded, _)| seeded != generation) {
+        world_rng.0 = Some((generation, fresh));
+    }
+    let (_, rng) = world_rng.0.as_mut().expect("just inserted");
+    f(rng)
+}
+
+/// Marks an instruction sent by the simulation instead of the player,
+/// which is reproduced by re-simulation and hence not recorded.
+#[derive(Component)]
+pub struct Unrecorded;
+
+/// The recorded state of the current session.
+#[derive(Resource, Default)]
+pub struct Log {
+    /// ID of the loaded file.
+    pub map_id:     String,
+    /// Hex-encoded [content hash](content_hash) of the loaded file.
+    pub map_hash:   String,
+    /// Elapsed virtual time when the file was loaded.
+    pub start_time: Duration,
+    /// Virtual time deltas of the frames simulated so far.
+    pub frames:     Vec<store::FrameRun>,
+    /// Number of frames simulated so far.
+    pub num_frames: u64,
+    /// Actions taken by the player so far.
+    pub actions:    Vec<store::SessionAction>,
+    /// Notable events that occurred so far, in chronological order.
+    pub bookmarks:  Vec<store::Bookmark>,
+}
+
+impl Log {
+    fn push_frame(&mut self, delta: Duration) {
+        self.num_frames += 1;
+        if let Some(run) = self.frames.last_mut()
+            && run.delta == delta
+            && run.count < u32::MAX
+        {
+            run.count += 1;
+        } else {
+            self.frames.push(store::FrameRun { delta, count: 1 });
+        }
+    }
+}
+
+/// Starts a new session for a file that has just been loaded.
+pub(crate) fn start(world: &mut World, file: &store::File) {
+    let start_time =
+        world.get_resource::<Time<time::Virtual>>().map_or(Duration::ZERO, Time::elapsed);
+    world.init_resource::<Log>();
+    world.get_resource_or_init::<Seed>().set(rand::rng().random());
+    *world.resource_mut::<Log>() = Log {
+        map_id: file.meta.id.clone(),
+        map_hash: content_hash(file),
+        start_time,
+        ..Default::default()
+    };
+}
+
+fn record_frame_system(mut log: ResMut<Log>, time: Res<Time<time::Virtual>>) {
+    log.push_frame(time.delta());
+}
+
+fn record_instructions_system(
+    mut log: ResMut<Log>,
+    instr_query: Query<
+        (&instr::Instruction, &instr::Recipient),
+        (Added<instr::Instruction>, Without<Unrecorded>),
+    >,
+    display_query: Query<&object::Display>,
+    names: NameParams,
+) {
+    let frame = log.num_frames.saturating_sub(1);
+    for (instruction, &instr::Recipient(object)) in &instr_query {
+        let Ok(object::Display { name }) = display_query.get(object) else {
+            bevy::log::warn!("Cannot record instruction to unnamed object {object:?}");
+            continue;
+        };
+        let Some(record) = names.record(instruction) else {
+            bevy::log::warn!("Cannot record instruction to {name}");
+            continue;
+        };
+        log.actions.push(store::SessionAction {
+            frame,
+            object: name.clone(),
+            kind: store::SessionActionKind::Instruction(record),
+        });
+    }
+}
+
+/// Records the response of the player to a pilot request.
+pub(crate) fn record_response(world: &mut World, object: Entity, approve: bool) {
+    let Some(display) = world.get::<object::Display>(object) else { return };
+    let name = display.name.clone();
+    let Some(mut log) = world.get_resource_mut::<Log>() else { return };
+    let frame = log.num_frames;
+    log.actions.push(store::SessionAction {
+        frame,
+        object: name,
+        kind: store::SessionActionKind::RespondRequest { approve },
+    });
+}
+
+/// Exports the current session as a sealed result.
+pub fn build_result(world: &mut World) -> store::SessionResult {
+    let event_digest = event_digest(world);
+    let world_hash = util::world_hash(world);
+    let score = world.resource::<score::Stats>().total;
+    let seed = world.resource::<Seed>().value;
+    let log = world.resource::<Log>();
+    let mut result = store::SessionResult {
+        map_id: log.map_id.clone(),
+        map_hash: log.map_hash.clone(),
+        seed,
+        start_time: log.start_time,
+        score,
+        event_digest,
+        world_hash,
+        frames: log.frames.clone(),
+        actions: log.actions.clone(),
+        bookmarks: log.bookmarks.clone(),
+        checksum: String::new(),
+    };
+    seal(&mut result);
+    result
+}
+
+/// Computes the hex-encoded digest of the scored events in the current session.
+///
+/// The digest covers the separation conflicts and the completion statistics,
+/// so that two sessions with the same score but different outcomes are distinguished.
+pub fn event_digest(world: &mut World) -> String {
+    let mut events: Vec<_> = world
+        .query::<&conflict::Event>()
+        .iter(world)
+        .map(|event| (event.time, event.names.clone()))
+        .collect();
+    events.sort();
+
+    let mut hasher = blake3::Hasher::new_derive_key("omniatc session events");
+    for (time, [first, second]) in events {
+        hasher.update(&time.as_nanos().to_le_bytes());
+        for name in [first, second] {
+            hasher.update(&(name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+        }
+    }
+
+    let stats = world.resource::<score::Stats>();
+    for value in [
+        stats.num_runway_arrivals,
+        stats.num_apron_arrivals,
+        stats.num_departures,
+        stats.num_conflicts,
+        stats.num_pilot_requests_approved,
+        stats.num_pilot_requests_denied,
+    ] {
+        hasher.update(&value.to_le_bytes());
+    }
+    hasher.update(&stats.total.0.to_le_bytes());
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Computes the hex-encoded content hash of a file.
+///
+/// Map keys are sorted before hashing,
+/// so the hash does not depend on the iteration order of hash maps in the file.
+#[must_use]
+pub fn content_hash(file: &store::File) -> String {
+    let mut hasher = blake3::Hasher::new_derive_key("omniatc file content");
+    match ciborium::Value::serialized(file) {
+        Ok(mut value) => {
+            canonicalize(&mut value);
+            if let Err(err) = ciborium::into_writer(&value, &mut hasher) {
+                bevy::log::error!("Cannot hash file content: {err}");
+            }
+        }
+        Err(err) => bevy::log::error!("Cannot hash file content: {err}"),
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+fn canonicalize(value: &mut ciborium::Value) {
+    match value {
+        ciborium::Value::Array(items) => items.iter_mut().for_each(canonicalize),
+        ciborium::Value::Map(entries) => {
+            for (key, value) in entries.iter_mut() {
+                canonicalize(key);
+                canonicalize(value);
+            }
+            entries.sort_by_cached_key(|(key, _)| {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(key, &mut bytes).expect("writing to Vec is infallible");
+                bytes
+            });
+        }
+        ciborium::Value::Tag(_, inner) => canonicalize(inner),
+        _ => {}
+    }
+}
+
+/// Sets the checksum of a result to the hash of its other fields.
+pub fn seal(result: &mut store::SessionResult) { result.checksum = checksum(result); }
+
+/// Checks whether the checksum of a result matches its other fields.
+#[must_use]
+pub fn verify_checksum(result: &store::SessionResult) -> bool {
+    result.checksum == checksum(result)
+}
+
+fn checksum(result: &store::SessionResult) -> String {
+    let unsealed = store::SessionResult { checksum: String::new(), ..result.clone() };
+    let mut hasher = blake3::Hasher::new_derive_key("omniatc session result");
+    if let Err(err) = ciborium::into_writer(&unsealed, &mut hasher) {
+        bevy::log::error!("Cannot checksum session result: {err}");
+    }
+    hasher.finalize().to_hex().to_string()
+}
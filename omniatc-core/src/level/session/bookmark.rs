@@ -0,0 +1,112 @@
+//! Bookmarks of notable events for incident review.
+//!
+//! Separation conflicts, go-arounds, runway incursions and declared emergencies
+//! are recorded in [`Log::bookmarks`] along with the frame they occurred in,
+//! so that the debrief and re-simulations can jump to the [`WINDOW`] around each incident.
+
+use std::ops::Range;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::{Added, Or};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, Res, ResMut, SystemParam};
+use bevy::time::{self, Time};
+
+use super::Log;
+use crate::level::{SystemSets, bird, conflict, crossing, goaround, object, radio};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            app::Update,
+            (conflict_system, go_around_system, incursion_system, emergency_system)
+                .chain()
+                .in_set(SystemSets::Statistics),
+        );
+    }
+}
+
+/// Duration of the review window surrounding each bookmark.
+pub const WINDOW: Duration = Duration::from_secs(30);
+
+/// Returns the range of virtual time to review for a bookmark,
+/// centered at the event unless it occurred within half a window from the start.
+#[must_use]
+pub fn window(bookmark: &store::Bookmark) -> Range<Duration> {
+    let start = bookmark.time.saturating_sub(WINDOW / 2);
+    start..start + WINDOW
+}
+
+#[derive(SystemParam)]
+struct Recorder<'w, 's> {
+    log:          ResMut<'w, Log>,
+    time:         Res<'w, Time<time::Virtual>>,
+    object_query: Query<'w, 's, (&'static object::Object, &'static object::Display)>,
+}
+
+impl Recorder<'_, '_> {
+    fn push(&mut self, bookmark: store::Bookmark) { self.log.bookmarks.push(bookmark); }
+
+    /// Records an event involving a single object at its current position.
+    fn record_object(&mut self, kind: store::BookmarkKind, object: Entity) {
+        let Ok((&object::Object { position, .. }, display)) = self.object_query.get(object) else {
+            bevy::log::warn!("Cannot bookmark {kind:?} of unnamed object {object:?}");
+            return;
+        };
+        let bookmark = store::Bookmark {
+            frame: self.frame(),
+            time: self.time.elapsed(),
+            kind,
+            objects: vec![display.name.clone()],
+            position: position.horizontal(),
+        };
+        self.push(bookmark);
+    }
+
+    fn frame(&self) -> u64 { self.log.num_frames.saturating_sub(1) }
+}
+
+fn conflict_system(
+    mut recorder: Recorder,
+    event_query: Query<&conflict::Event, Added<conflict::Event>>,
+) {
+    for event in event_query {
+        let bookmark = store::Bookmark {
+            frame:    recorder.frame(),
+            time:     event.time,
+            kind:     store::BookmarkKind::Conflict,
+            objects:  event.names.to_vec(),
+            position: event.position.horizontal(),
+        };
+        recorder.push(bookmark);
+    }
+}
+
+fn go_around_system(mut recorder: Recorder, mut reader: MessageReader<goaround::GoAroundMessage>) {
+    for message in reader.read() {
+        recorder.record_object(store::BookmarkKind::GoAround, message.object);
+    }
+}
+
+fn incursion_system(mut recorder: Recorder, mut reader: MessageReader<crossing::IncursionMessage>) {
+    for message in reader.read() {
+        recorder.record_object(store::BookmarkKind::Incursion, message.object);
+    }
+}
+
+fn emergency_system(
+    mut recorder: Recorder,
+    object_query: Query<Entity, Or<(Added<bird::Emergency>, Added<radio::Failed>)>>,
+) {
+    for object in object_query {
+        recorder.record_object(store::BookmarkKind::Emergency, object);
+    }
+}
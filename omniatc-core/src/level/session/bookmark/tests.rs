@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Position, Speed};
+
+use super::{Plug, WINDOW, window};
+use crate::level::object::{self, Object};
+use crate::level::session::Log;
+use crate::level::{bird, crossing, goaround, test_util};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins(Plug);
+    app.add_message::<goaround::GoAroundMessage>();
+    app.add_message::<crossing::IncursionMessage>();
+    app.init_resource::<Log>();
+    app.world_mut().resource_mut::<Log>().num_frames = 5;
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App, name: &str) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(1.0, 2.0)
+                    .with_altitude(Position::from_amsl_feet(3000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            object::Display { name: name.into() },
+        ))
+        .id()
+}
+
+fn bookmark(time: Duration) -> store::Bookmark {
+    store::Bookmark {
+        frame: 0,
+        time,
+        kind: store::BookmarkKind::GoAround,
+        objects: Vec::new(),
+        position: Position::ORIGIN,
+    }
+}
+
+#[test]
+fn test_window_surrounds_event() {
+    let range = window(&bookmark(Duration::from_mins(2)));
+    assert_eq!(range, Duration::from_secs(105)..Duration::from_secs(135));
+
+    let range = window(&bookmark(Duration::from_secs(5)));
+    assert_eq!(range, Duration::ZERO..WINDOW, "window should not start before the session");
+}
+
+#[test]
+fn test_records_object_events() {
+    let mut app = base_app();
+    let first = spawn_object(&mut app, "ABC123");
+    let second = spawn_object(&mut app, "DEF456");
+
+    app.world_mut().write_message(goaround::GoAroundMessage {
+        object: first,
+        cause:  goaround::Cause::Unstable,
+    });
+    app.world_mut().write_message(crossing::IncursionMessage { object: second, runway: first });
+    app.update();
+    app.world_mut().entity_mut(second).insert(bird::Emergency);
+    app.update();
+
+    let bookmarks = &app.world().resource::<Log>().bookmarks;
+    let kinds: Vec<_> = bookmarks.iter().map(|bookmark| bookmark.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            store::BookmarkKind::GoAround,
+            store::BookmarkKind::Incursion,
+            store::BookmarkKind::Emergency
+        ]
+    );
+    assert_eq!(bookmarks[0].objects, ["ABC123"]);
+    assert_eq!(bookmarks[0].frame, 4);
+    assert_eq!(bookmarks[2].objects, ["DEF456"]);
+    assert_eq!(bookmarks[2].position, Position::from_origin_nm(1.0, 2.0));
+}
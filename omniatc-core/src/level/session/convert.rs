@@ -0,0 +1,524 @@
+//! Conversion between instructions and their recorded form.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::relationship::RelationshipTarget;
+use bevy::ecs::system::{EntityCommand, Query, SystemParam, SystemState};
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+
+use super::Unrecorded;
+use crate::level::aerodrome::Aerodrome;
+use crate::level::instr::{self, Instruction};
+use crate::level::route::{self, TaxiStopMode};
+use crate::level::waypoint::Waypoint;
+use crate::level::{aerodrome, frequency, ground, nav, pilot, runway};
+use crate::load;
+
+/// Queries resolving entity references in instructions and objects into names.
+#[derive(SystemParam)]
+pub struct NameParams<'w, 's> {
+    waypoint_query: Query<
+        'w,
+        's,
+        (
+            &'static Waypoint,
+            Option<&'static runway::RunwayOf>,
+            Option<&'static runway::LocalizerWaypoint>,
+        ),
+    >,
+    aerodrome_query: Query<'w, 's, &'static Aerodrome>,
+}
+
+impl NameParams<'_, '_> {
+    /// Converts an instruction into its recorded form.
+    ///
+    /// Returns `None` if a referenced entity cannot be named.
+    #[must_use]
+    pub fn record(&self, instruction: &Instruction) -> Option<store::InstructionRecord> {
+        Some(match instruction {
+            Instruction::SetHeading(instr) => {
+                store::InstructionRecord::SetHeading { target: instr.target }
+            }
+            Instruction::SetWaypoint(instr) => {
+                store::InstructionRecord::SetWaypoint { waypoint: self.waypoint(instr.waypoint)? }
+            }
+            Instruction::SetSpeed(instr) => {
+                store::InstructionRecord::SetSpeed { target: instr.target }
+            }
+            Instruction::SetAltitude(instr) => store::InstructionRecord::SetAltitude {
+                altitude: instr.target.altitude,
+                expedite: instr.target.expedite,
+            },
+            Instruction::AirborneVector(instr) => store::InstructionRecord::AirborneVector {
+                direction: match &instr.directional {
+                    None => None,
+                    Some(instr::AirborneVectorDirectional::SetHeading(heading)) => {
+                        Some(store::AirborneDirection::Heading(heading.target))
+                    }
+                    Some(instr::AirborneVectorDirectional::SetWaypoint(waypoint)) => {
+                        Some(store::AirborneDirection::Waypoint(self.waypoint(waypoint.waypoint)?))
+                    }
+                },
+                speed:     instr.speed.as_ref().map(|speed| speed.target),
+                altitude:  instr
+                    .altitude
+                    .as_ref()
+                    .map(|altitude| (altitude.target.altitude, altitude.target.expedite)),
+            },
+            Instruction::ClearRoute(_) => store::InstructionRecord::ClearRoute,
+            Instruction::RemoveStandby(instr) => {
+                store::InstructionRecord::RemoveStandby { skip_id: instr.skip_id }
+            }
+            Instruction::SelectRoute(instr) => {
+                store::InstructionRecord::SelectRoute { preset: instr.preset.id.clone() }
+            }
+            Instruction::AppendSegment(instr) => store::InstructionRecord::AppendSegment {
+                clear_existing: instr.clear_existing,
+                segment:        match &instr.segment {
+                    ground::SegmentLabel::Taxiway { name } => {
+                        store::SegmentRecord::Taxiway(name.clone())
+                    }
+                    ground::SegmentLabel::Apron { name } => {
+                        store::SegmentRecord::Apron(name.clone())
+                    }
+                    &ground::SegmentLabel::RunwayPair([runway, _]) => {
+                        store::SegmentRecord::Runway(self.runway(runway)?)
+                    }
+                },
+                stop_mode:      match instr.stop_mode {
+                    TaxiStopMode::HoldShort => store::TaxiStopModeRecord::HoldShort,
+                    TaxiStopMode::LineUp => store::TaxiStopModeRecord::LineUp,
+                    TaxiStopMode::Exhaust => store::TaxiStopModeRecord::Exhaust,
+                },
+            },
+            Instruction::SkipToWaypoint(instr) => store::InstructionRecord::SkipToWaypoint {
+                waypoint: self.waypoint(instr.waypoint)?,
+            },
+            Instruction::Divert(instr) => {
+                store::InstructionRecord::Divert { aerodrome: self.aerodrome(instr.aerodrome)? }
+            }
+            Instruction::GrantFlightFollowing(_) => store::InstructionRecord::GrantFlightFollowing,
+            Instruction::BreakupFormation(_) => store::InstructionRecord::BreakupFormation,
+            Instruction::BirdCaution(_) => store::InstructionRecord::BirdCaution,
+            Instruction::ContactFrequency(instr) => store::InstructionRecord::ContactFrequency {
+                frequency: match instr.frequency {
+                    frequency::Frequency::Ground => store::FrequencyRecord::Ground,
+                    frequency::Frequency::Tower => store::FrequencyRecord::Tower,
+                },
+            },
+            Instruction::ClearApproach(_) => store::InstructionRecord::ClearApproach,
+            Instruction::SetSpeedUntil(instr) => store::InstructionRecord::SetSpeedUntil {
+                initial:   instr.initial.target,
+                condition: self.speed_condition(instr.condition)?,
+                then:      instr.then,
+            },
+            Instruction::CrossAltitude(instr) => store::InstructionRecord::CrossAltitude {
+                waypoint: self.waypoint(instr.waypoint)?,
+                altitude: instr.altitude,
+            },
+            Instruction::VectorThenResume(instr) => store::InstructionRecord::VectorThenResume {
+                heading:   instr.heading.target,
+                condition: self.resume_condition(instr.condition)?,
+                waypoint:  match instr.waypoint {
+                    Some(waypoint) => Some(self.waypoint(waypoint)?),
+                    None => None,
+                },
+            },
+            Instruction::CrossRunway(instr) => {
+                store::InstructionRecord::CrossRunway { runway: self.runway(instr.runway)? }
+            }
+            Instruction::CycleTransponder(_) => store::InstructionRecord::CycleTransponder,
+        })
+    }
+
+    fn speed_condition(
+        &self,
+        condition: route::SpeedCondition,
+    ) -> Option<store::SpeedConditionRecord> {
+        Some(match condition {
+            route::SpeedCondition::Distance { waypoint, distance } => {
+                store::SpeedConditionRecord::Distance {
+                    waypoint: self.waypoint(waypoint)?,
+                    distance,
+                }
+            }
+            route::SpeedCondition::Waypoint(waypoint) => {
+                store::SpeedConditionRecord::Waypoint { waypoint: self.waypoint(waypoint)? }
+            }
+        })
+    }
+
+    fn resume_condition(
+        &self,
+        condition: nav::ResumeCondition,
+    ) -> Option<store::ResumeConditionRecord> {
+        Some(match condition {
+            nav::ResumeCondition::Distance(distance) => {
+                store::ResumeConditionRecord::Distance(distance)
+            }
+            nav::ResumeCondition::Abeam(fix) => {
+                store::ResumeConditionRecord::Abeam(self.waypoint(fix)?)
+            }
+            nav::ResumeCondition::Time(duration) => store::ResumeConditionRecord::Time(duration),
+        })
+    }
+
+    /// Names a waypoint entity.
+    #[must_use]
+    pub fn waypoint(&self, entity: Entity) -> Option<store::WaypointRef> {
+        let (waypoint, runway_of, localizer) = self.waypoint_query.get(entity).ok()?;
+        Some(if let Some(localizer) = localizer {
+            store::WaypointRef::LocalizerStart(self.runway(localizer.runway_ref)?)
+        } else if runway_of.is_some() {
+            store::WaypointRef::RunwayThreshold(self.runway(entity)?)
+        } else {
+            store::WaypointRef::Named(store::NamedWaypointRef(waypoint.name.clone()))
+        })
+    }
+
+    /// Names a runway entity.
+    #[must_use]
+    pub fn runway(&self, entity: Entity) -> Option<store::RunwayRef> {
+        let (waypoint, runway_of, _) = self.waypoint_query.get(entity).ok()?;
+        Some(store::RunwayRef {
+            aerodrome:   self.aerodrome(runway_of?.0)?,
+            runway_name: waypoint.name.clone(),
+        })
+    }
+
+    /// Names an aerodrome entity.
+    #[must_use]
+    pub fn aerodrome(&self, entity: Entity) -> Option<store::AerodromeRef> {
+        let aerodrome = self.aerodrome_query.get(entity).ok()?;
+        Some(store::AerodromeRef(aerodrome.code.clone()))
+    }
+}
+
+/// Resolves a recorded instruction against the loaded level.
+#[expect(clippy::too_many_lines, reason = "one arm per instruction record variant")]
+pub fn resolve(
+    world: &mut World,
+    record: &store::InstructionRecord,
+) -> Result<Instruction, load::Error> {
+    let context = world.resource::<load::SpawnContext>();
+    let aerodromes = context.aerodromes.clone();
+    let waypoints = context.waypoints.clone();
+    let resolve_waypoint =
+        |waypoint: &store::WaypointRef| waypoints.resolve_ref(&aerodromes, waypoint);
+    let set_altitude = |altitude, expedite| instr::SetAltitude {
+        target: nav::TargetAltitude { altitude, expedite },
+    };
+
+    Ok(match record {
+        &store::InstructionRecord::SetHeading { target } => instr::SetHeading { target }.into(),
+        store::InstructionRecord::SetWaypoint { waypoint } => {
+            instr::SetWaypoint { waypoint: resolve_waypoint(waypoint)? }.into()
+        }
+        &store::InstructionRecord::SetSpeed { target } => instr::SetSpeed { target }.into(),
+        &store::InstructionRecord::SetAltitude { altitude, expedite } => {
+            set_altitude(altitude, expedite).into()
+        }
+        store::InstructionRecord::AirborneVector { direction, speed, altitude } => {
+            instr::AirborneVector {
+                directional: match direction {
+                    None => None,
+                    Some(store::AirborneDirection::Heading(target)) => {
+                        Some(instr::AirborneVectorDirectional::SetHeading(instr::SetHeading {
+                            target: *target,
+                        }))
+                    }
+                    Some(store::AirborneDirection::Waypoint(waypoint)) => {
+                        Some(instr::AirborneVectorDirectional::SetWaypoint(instr::SetWaypoint {
+                            waypoint: resolve_waypoint(waypoint)?,
+                        }))
+                    }
+                },
+                speed:       speed.map(|target| instr::SetSpeed { target }),
+                altitude:    altitude.map(|(altitude, expedite)| set_altitude(altitude, expedite)),
+            }
+            .into()
+        }
+        store::InstructionRecord::ClearRoute => instr::ClearRoute.into(),
+        &store::InstructionRecord::RemoveStandby { skip_id } => {
+            instr::RemoveStandby { skip_id }.into()
+        }
+        store::InstructionRecord::SelectRoute { preset } => {
+            let preset = world
+                .query::<&route::Preset>()
+                .iter(world)
+                .find(|candidate| &candidate.id == preset)
+                .ok_or_else(|| load::Error::UnresolvedRoutePreset(preset.clone()))?;
+            instr::SelectRoute { preset: preset.clone() }.into()
+        }
+        store::InstructionRecord::AppendSegment { clear_existing, segment, stop_mode } => {
+            instr::AppendSegment {
+                clear_existing: *clear_existing,
+                segment:        resolve_segment(segment, &aerodromes)?,
+                stop_mode:      match stop_mode {
+                    store::TaxiStopModeRecord::HoldShort => TaxiStopMode::HoldShort,
+                    store::TaxiStopModeRecord::LineUp => TaxiStopMode::LineUp,
+                    store::TaxiStopModeRecord::Exhaust => TaxiStopMode::Exhaust,
+                },
+            }
+            .into()
+        }
+        store::InstructionRecord::SkipToWaypoint { waypoint } => {
+            instr::SkipToWaypoint { waypoint: resolve_waypoint(waypoint)? }.into()
+        }
+        store::InstructionRecord::Divert { aerodrome } => {
+            instr::Divert { aerodrome: aerodromes.resolve(aerodrome)?.aerodrome_entity }.into()
+        }
+        store::InstructionRecord::GrantFlightFollowing => instr::GrantFlightFollowing.into(),
+        store::InstructionRecord::BreakupFormation => instr::BreakupFormation.into(),
+        store::InstructionRecord::BirdCaution => instr::BirdCaution.into(),
+        store::InstructionRecord::ContactFrequency { frequency } => instr::ContactFrequency {
+            frequency: match frequency {
+                store::FrequencyRecord::Ground => frequency::Frequency::Ground,
+                store::FrequencyRecord::Tower => frequency::Frequency::Tower,
+            },
+        }
+        .into(),
+        store::InstructionRecord::ClearApproach => instr::ClearApproach.into(),
+        store::InstructionRecord::SetSpeedUntil { initial, condition, then } => {
+            instr::SetSpeedUntil {
+                initial:   instr::SetSpeed { target: *initial },
+                condition: resolve_speed_condition(condition, resolve_waypoint)?,
+                then:      *then,
+            }
+            .into()
+        }
+        store::InstructionRecord::CrossAltitude { waypoint, altitude } => {
+            instr::CrossAltitude { waypoint: resolve_waypoint(waypoint)?, altitude: *altitude }
+                .into()
+        }
+        store::InstructionRecord::VectorThenResume { heading, condition, waypoint } => {
+            instr::VectorThenResume {
+                heading:   instr::SetHeading { target: *heading },
+                condition: resolve_resume_condition(condition, resolve_waypoint)?,
+                waypoint:  waypoint.as_ref().map(resolve_waypoint).transpose()?,
+            }
+            .into()
+        }
+        store::InstructionRecord::CrossRunway { runway } => {
+            instr::CrossRunway { runway: aerodromes.resolve_runway_ref(runway)?.runway.runway }
+                .into()
+        }
+        store::InstructionRecord::CycleTransponder => instr::CycleTransponder.into(),
+    })
+}
+
+fn resolve_speed_condition(
+    record: &store::SpeedConditionRecord,
+    resolve_waypoint: impl Fn(&store::WaypointRef) -> Result<Entity, load::Error>,
+) -> Result<route::SpeedCondition, load::Error> {
+    Ok(match record {
+        store::SpeedConditionRecord::Distance { waypoint, distance } => {
+            route::SpeedCondition::Distance {
+                waypoint: resolve_waypoint(waypoint)?,
+                distance: *distance,
+            }
+        }
+        store::SpeedConditionRecord::Waypoint { waypoint } => {
+            route::SpeedCondition::Waypoint(resolve_waypoint(waypoint)?)
+        }
+    })
+}
+
+fn resolve_resume_condition(
+    record: &store::ResumeConditionRecord,
+    resolve_waypoint: impl Fn(&store::WaypointRef) -> Result<Entity, load::Error>,
+) -> Result<nav::ResumeCondition, load::Error> {
+    Ok(match record {
+        &store::ResumeConditionRecord::Distance(distance) => {
+            nav::ResumeCondition::Distance(distance)
+        }
+        store::ResumeConditionRecord::Abeam(fix) => {
+            nav::ResumeCondition::Abeam(resolve_waypoint(fix)?)
+        }
+        &store::ResumeConditionRecord::Time(duration) => nav::ResumeCondition::Time(duration),
+    })
+}
+
+fn resolve_segment(
+    record: &store::SegmentRecord,
+    aerodromes: &aerodrome::loader::AerodromeMap,
+) -> Result<ground::SegmentLabel, load::Error> {
+    Ok(match record {
+        store::SegmentRecord::Taxiway(name) => ground::SegmentLabel::Taxiway { name: name.clone() },
+        store::SegmentRecord::Apron(name) => ground::SegmentLabel::Apron { name: name.clone() },
+        store::SegmentRecord::Runway(runway) => {
+            let runway = aerodromes.resolve_runway_ref(runway)?;
+            ground::SegmentLabel::RunwayPair([runway.runway.runway, runway.paired])
+        }
+    })
+}
+
+/// Records the instructions of `object` that have not been fully executed.
+///
+/// Instructions referencing entities that cannot be named are omitted.
+#[must_use]
+pub fn record_state(world: &mut World, object: Entity) -> store::InstructionState {
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    let mut params_state = SystemState::<NameParams>::new(world);
+    let params = params_state.get(world);
+
+    let mut pending = Vec::new();
+
+    if let Some(queue) = world.get::<pilot::ReactionQueue>(object) {
+        for (due, instruction) in queue.iter_due() {
+            let Some(instruction) = params.record(instruction) else { continue };
+            pending.push(store::PendingInstruction {
+                instruction,
+                stage: store::PendingStage::Reacting { remaining: due.saturating_sub(now) },
+            });
+        }
+    }
+
+    let transmitting: Vec<(Entity, store::InstructionRecord)> = world
+        .get::<instr::PendingList>(object)
+        .into_iter()
+        .flat_map(RelationshipTarget::iter)
+        .filter_map(|instr_entity| {
+            let instruction = world.get::<Instruction>(instr_entity)?;
+            Some((instr_entity, params.record(instruction)?))
+        })
+        .collect();
+    let indices: HashMap<Entity, usize> = transmitting
+        .iter()
+        .enumerate()
+        .map(|(offset, &(instr_entity, _))| (instr_entity, pending.len() + offset))
+        .collect();
+    for (instr_entity, instruction) in transmitting {
+        let instr_ref = world.entity(instr_entity);
+        let remaining = instr_ref
+            .get::<instr::TransmitDelay>()
+            .map_or(Duration::ZERO, |delay| delay.expiry.saturating_sub(now));
+        let after = instr_ref
+            .get::<instr::DispatchAfter>()
+            .into_iter()
+            .flat_map(|deps| deps.dependency.iter())
+            .filter_map(|dep| indices.get(dep).copied())
+            .collect();
+        pending.push(store::PendingInstruction {
+            instruction,
+            stage: store::PendingStage::Transmitting {
+                remaining,
+                after,
+                pending_ack: instr_ref.contains::<instr::PendingAck>(),
+            },
+        });
+    }
+
+    let conditional_speed = world.get::<route::ConditionalSpeed>(object).and_then(|speed| {
+        Some(store::ConditionalSpeedRecord {
+            speed:     speed.speed,
+            condition: params.speed_condition(speed.condition)?,
+        })
+    });
+    let scheduled_resume = world.get::<nav::ScheduledResume>(object).and_then(|resume| {
+        Some(store::ScheduledResumeRecord {
+            condition: params.resume_condition(resume.condition)?,
+            waypoint:  match resume.waypoint {
+                Some(waypoint) => Some(params.waypoint(waypoint)?),
+                None => None,
+            },
+            flown:     resume.flown,
+            elapsed:   resume.elapsed,
+        })
+    });
+
+    store::InstructionState { pending, conditional_speed, scheduled_resume }
+}
+
+/// Restores the instructions of `object` recorded by [`record_state`].
+///
+/// # Errors
+/// If a recorded instruction cannot be resolved against the loaded level,
+/// in which case no instruction is restored.
+pub fn restore_state(
+    world: &mut World,
+    object: Entity,
+    state: &store::InstructionState,
+) -> Result<(), load::Error> {
+    let pending = state
+        .pending
+        .iter()
+        .map(|pending| Ok((resolve(world, &pending.instruction)?, &pending.stage)))
+        .collect::<Result<Vec<_>, load::Error>>()?;
+
+    let context = world.resource::<load::SpawnContext>();
+    let aerodromes = context.aerodromes.clone();
+    let waypoints = context.waypoints.clone();
+    let resolve_waypoint =
+        |waypoint: &store::WaypointRef| waypoints.resolve_ref(&aerodromes, waypoint);
+    let conditional_speed = state
+        .conditional_speed
+        .as_ref()
+        .map(|record| {
+            Ok::<_, load::Error>(route::ConditionalSpeed {
+                speed:     record.speed,
+                condition: resolve_speed_condition(&record.condition, resolve_waypoint)?,
+            })
+        })
+        .transpose()?;
+    let scheduled_resume = state
+        .scheduled_resume
+        .as_ref()
+        .map(|record| {
+            Ok::<_, load::Error>(nav::ScheduledResume {
+                condition: resolve_resume_condition(&record.condition, resolve_waypoint)?,
+                waypoint:  record.waypoint.as_ref().map(resolve_waypoint).transpose()?,
+                flown:     record.flown,
+                elapsed:   record.elapsed,
+            })
+        })
+        .transpose()?;
+
+    let mut object_ref = world.entity_mut(object);
+    if let Some(speed) = conditional_speed {
+        object_ref.insert(speed);
+    }
+    if let Some(resume) = scheduled_resume {
+        object_ref.insert(resume);
+    }
+
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    let mut spawned = Vec::with_capacity(pending.len());
+    for (instruction, stage) in pending {
+        match *stage {
+            store::PendingStage::Reacting { remaining } => {
+                pilot::acknowledge_with_delay(world.entity_mut(object), instruction, remaining);
+                spawned.push(None);
+            }
+            store::PendingStage::Transmitting { remaining, pending_ack, .. } => {
+                let instr_entity = world.spawn_empty().id();
+                instr::SpawnCommand { object, body: instruction }
+                    .apply(world.entity_mut(instr_entity));
+                let mut instr_ref = world.entity_mut(instr_entity);
+                // The instruction was recorded when it was originally sent,
+                // and is restored again when the session is replayed from the same file.
+                instr_ref.insert((instr::TransmitDelay { expiry: now + remaining }, Unrecorded));
+                if pending_ack {
+                    instr_ref.insert(instr::PendingAck);
+                }
+                spawned.push(Some(instr_entity));
+            }
+        }
+    }
+
+    for (pending, &instr_entity) in state.pending.iter().zip(&spawned) {
+        if let (store::PendingStage::Transmitting { after, .. }, Some(instr_entity)) =
+            (&pending.stage, instr_entity)
+            && !after.is_empty()
+        {
+            let dependency =
+                after.iter().filter_map(|&index| spawned.get(index).copied().flatten()).collect();
+            world.entity_mut(instr_entity).insert(instr::DispatchAfter { dependency });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Headless re-simulation of recorded sessions.
+//!
+//! Re-simulation uses the default configuration of all simulation modules,
+//! so sessions played with modified simulation settings do not reproduce the same score.
+
+use std::mem;
+use std::time::Duration;
+
+use bevy::app::{App, TaskPoolPlugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::Command;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time, TimePlugin, TimeUpdateStrategy};
+use store::Score;
+
+use super::{Log, Seed, convert, event_digest};
+use crate::level::instr::CommandsExt;
+use crate::level::{self, object, pilot_request, score, track};
+use crate::{load, util};
+
+/// Outcome of re-simulating a session.
+pub struct Report {
+    /// Score at the end of the re-simulation.
+    pub score:           Score,
+    /// Event digest at the end of the re-simulation.
+    pub event_digest:    String,
+    /// [World hash](util::world_hash) at the end of the re-simulation.
+    pub world_hash:      String,
+    /// Descriptions of recorded actions that could not be reproduced.
+    pub skipped_actions: Vec<String>,
+    /// Tracks flown during the re-simulation.
+    pub tracks:          track::Log,
+    /// Notable events that occurred during the re-simulation.
+    pub bookmarks:       Vec<store::Bookmark>,
+}
+
+impl Report {
+    /// Whether the re-simulation reproduced the claimed result.
+    #[must_use]
+    pub fn matches(&self, result: &store::SessionResult) -> bool {
+        self.skipped_actions.is_empty()
+            && self.score == result.score
+            && self.event_digest == result.event_digest
+            && self.world_hash == result.world_hash
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("The file does not match the session: expected hash {expected}, got {actual}")]
+    MapMismatch { expected: String, actual: String },
+    #[error("Cannot load file: {0}")]
+    Load(load::Error),
+}
+
+#[derive(Resource, Default)]
+struct LoadFailure(Option<load::Error>);
+
+/// Re-simulates a recorded session on the file it was played on.
+pub fn run(file: store::File, result: &store::SessionResult) -> Result<Report, Error> {
+    let actual = super::content_hash(&file);
+    if actual != result.map_hash {
+        return Err(Error::MapMismatch { expected: result.map_hash.clone(), actual });
+    }
+
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TimePlugin,
+        level::Plug::<()>::default(),
+        load::Plug,
+        util::Plug,
+    ));
+    app.init_resource::<LoadFailure>();
+    app.finish();
+    app.cleanup();
+    app.world_mut().resource_mut::<Time<time::Virtual>>().set_max_delta(Duration::MAX);
+
+    // The first update only initializes the clock.
+    advance(&mut app, Duration::ZERO);
+    if !result.start_time.is_zero() {
+        advance(&mut app, result.start_time);
+    }
+
+    let world = app.world_mut();
+    load::Command {
+        source:   load::Source::Parsed(Box::new(file)),
+        on_error: Box::new(|world, err| world.resource_mut::<LoadFailure>().0 = Some(err)),
+    }
+    .apply(world);
+    if let Some(err) = world.resource_mut::<LoadFailure>().0.take() {
+        return Err(Error::Load(err));
+    }
+    world.resource_mut::<Seed>().set(result.seed);
+
+    let mut skipped_actions = Vec::new();
+    let mut actions = result.actions.iter().peekable();
+    let deltas = result
+        .frames
+        .iter()
+        .flat_map(|run| std::iter::repeat_n(run.delta, usize::try_from(run.count).unwrap_or(0)));
+    for (frame, delta) in (0..).zip(deltas) {
+        while let Some(action) = actions.next_if(|action| action.frame <= frame) {
+            if let Err(err) = apply_action(app.world_mut(), action) {
+                skipped_actions.push(format!("frame {}, {}: {err}", action.frame, action.object));
+            }
+        }
+        advance(&mut app, delta);
+    }
+    skipped_actions.extend(
+        actions
+            .map(|action| format!("frame {}, {}: after last frame", action.frame, action.object)),
+    );
+
+    let world = app.world_mut();
+    Ok(Report {
+        score: world.resource::<score::Stats>().total,
+        event_digest: event_digest(world),
+        world_hash: util::world_hash(world),
+        skipped_actions,
+        tracks: world.remove_resource::<track::Log>().unwrap_or_default(),
+        bookmarks: mem::take(&mut world.resource_mut::<Log>().bookmarks),
+    })
+}
+
+fn advance(app: &mut App, delta: Duration) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+    app.update();
+}
+
+fn apply_action(world: &mut World, action: &store::SessionAction) -> Result<(), String> {
+    let object = world
+        .query::<(Entity, &object::Display)>()
+        .iter(world)
+        .find(|(_, display)| display.name == action.object)
+        .map(|(entity, _)| entity)
+        .ok_or("no such object")?;
+
+    match &action.kind {
+        store::SessionActionKind::Instruction(record) => {
+            let instruction = convert::resolve(world, record).map_err(|err| err.to_string())?;
+            world.commands().send_instruction(object, instruction);
+            world.flush();
+        }
+        &store::SessionActionKind::RespondRequest { approve } => {
+            let request = world
+                .query::<(Entity, &pilot_request::Requester)>()
+                .iter(world)
+                .find(|&(_, &pilot_request::Requester(requester))| requester == object)
+                .map(|(entity, _)| entity)
+                .ok_or("no pending request")?;
+            pilot_request::Respond { request, approve }.apply(world);
+        }
+    }
+    Ok(())
+}
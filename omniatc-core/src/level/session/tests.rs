@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::{RunSystemOnce, SystemState};
+use bevy::time::{self, Time};
+use math::{Heading, Position, Speed};
+use rand::Rng;
+use store::YawTarget;
+
+use super::{
+    Log, Seed, SessionRng, canonicalize, record_state, restore_state, seal, verify_checksum,
+};
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::Object;
+use crate::level::{message, nav, phraseology, pilot, test_util};
+use crate::load;
+
+fn result() -> store::SessionResult {
+    store::SessionResult {
+        map_id:       "test".into(),
+        map_hash:     "abc".into(),
+        seed:         42,
+        start_time:   Duration::ZERO,
+        score:        store::Score(100),
+        event_digest: "def".into(),
+        world_hash:   "123".into(),
+        frames:       vec![store::FrameRun { delta: Duration::from_millis(16), count: 60 }],
+        actions:      vec![store::SessionAction {
+            frame:  10,
+            object: "ABC123".into(),
+            kind:   store::SessionActionKind::RespondRequest { approve: true },
+        }],
+        bookmarks:    Vec::new(),
+        checksum:     String::new(),
+    }
+}
+
+#[test]
+fn test_checksum_detects_modification() {
+    let mut result = result();
+    assert!(!verify_checksum(&result), "unsealed result should not verify");
+
+    seal(&mut result);
+    assert!(verify_checksum(&result));
+
+    result.score = store::Score(1000);
+    assert!(!verify_checksum(&result), "modified score should not verify");
+}
+
+#[test]
+fn test_canonicalize_sorts_map_keys() {
+    let entry = |key: &str, value: i32| {
+        (ciborium::Value::Text(key.into()), ciborium::Value::Integer(value.into()))
+    };
+    let mut forward =
+        ciborium::Value::Array(vec![ciborium::Value::Map(vec![entry("a", 1), entry("b", 2)])]);
+    let mut backward =
+        ciborium::Value::Array(vec![ciborium::Value::Map(vec![entry("b", 2), entry("a", 1)])]);
+    canonicalize(&mut forward);
+    canonicalize(&mut backward);
+    assert_eq!(forward, backward);
+}
+
+#[test]
+fn test_push_frame_merges_equal_deltas() {
+    let mut log = Log::default();
+    for delta in [16, 16, 16, 33, 16] {
+        log.push_frame(Duration::from_millis(delta));
+    }
+
+    assert_eq!(log.num_frames, 5);
+    assert_eq!(
+        log.frames,
+        [
+            store::FrameRun { delta: Duration::from_millis(16), count: 3 },
+            store::FrameRun { delta: Duration::from_millis(33), count: 1 },
+            store::FrameRun { delta: Duration::from_millis(16), count: 1 },
+        ]
+    );
+}
+
+#[test]
+fn test_session_rng_reseeds_on_set() {
+    let mut app = App::new();
+    app.init_resource::<Seed>();
+    let mut state = SystemState::<SessionRng>::new(app.world_mut());
+
+    let mut draw = |app: &mut App| -> [u64; 4] {
+        let mut rng = state.get_mut(app.world_mut());
+        let values = std::array::from_fn(|_| rng.get(1).random());
+        state.apply(app.world_mut());
+        values
+    };
+
+    app.world_mut().resource_mut::<Seed>().set(7);
+    let first = draw(&mut app);
+    let continued = draw(&mut app);
+    assert_ne!(first, continued, "generator should continue without reseeding");
+
+    app.world_mut().resource_mut::<Seed>().set(7);
+    assert_eq!(draw(&mut app), first, "setting the same seed should restart the sequence");
+
+    let other_stream = app
+        .world_mut()
+        .run_system_once(|mut rng: SessionRng| -> [u64; 4] {
+            std::array::from_fn(|_| rng.get(2).random())
+        })
+        .unwrap();
+    assert_ne!(other_stream, first, "streams should be independent");
+}
+
+fn instruction_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        pilot::Plug::<()>::default(),
+    ));
+    app.init_resource::<load::SpawnContext>();
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(5000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            message::Sender { display: "ABC123".into() },
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::NORTH),
+                horiz_speed: Speed::from_knots(250.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+            pilot::Response { reaction_delay: Duration::from_secs(5), ..pilot::Response::PRECISE },
+        ))
+        .id()
+}
+
+#[test]
+fn test_instruction_state_round_trip() {
+    let mut app = instruction_app();
+    let object = spawn_object(&mut app);
+
+    let world = app.world_mut();
+    world.commands().send_instruction(object, instr::SetSpeed { target: Speed::from_knots(210.0) });
+    world.flush();
+    app.update();
+
+    let world = app.world_mut();
+    let heading = world
+        .commands()
+        .send_instruction(object, instr::SetHeading { target: YawTarget::Heading(Heading::EAST) })
+        .id();
+    let altitude = world
+        .commands()
+        .send_instruction(
+            object,
+            instr::SetAltitude {
+                target: nav::TargetAltitude {
+                    altitude: Position::from_amsl_feet(3000.0),
+                    expedite: false,
+                },
+            },
+        )
+        .id();
+    world.flush();
+    world
+        .entity_mut(altitude)
+        .insert(instr::DispatchAfter { dependency: [heading].into_iter().collect() });
+
+    let state = record_state(app.world_mut(), object);
+    assert!(matches!(
+        state.pending.as_slice(),
+        [
+            store::PendingInstruction {
+                instruction: store::InstructionRecord::SetSpeed { .. },
+                stage:       store::PendingStage::Reacting { remaining },
+            },
+            store::PendingInstruction {
+                instruction: store::InstructionRecord::SetHeading { .. },
+                stage:       store::PendingStage::Transmitting { .. },
+            },
+            store::PendingInstruction {
+                instruction: store::InstructionRecord::SetAltitude { .. },
+                stage:       store::PendingStage::Transmitting { after, .. },
+            },
+        ] if *remaining == Duration::from_secs(5) && after == &[1]
+    ));
+
+    let restored = spawn_object(&mut app);
+    restore_state(app.world_mut(), restored, &state).unwrap();
+    let restored_state = record_state(app.world_mut(), restored);
+    assert_eq!(restored_state.pending.len(), 3);
+    assert!(matches!(
+        &restored_state.pending[2].stage,
+        store::PendingStage::Transmitting { after, .. } if after == &[1]
+    ));
+
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(5));
+    app.update();
+    let target = app.world().get::<nav::VelocityTarget>(restored).expect("object has target");
+    assert_eq!(target.horiz_speed, Speed::from_knots(210.0));
+}
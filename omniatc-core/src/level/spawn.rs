@@ -1,28 +1,44 @@
+use std::collections::HashSet;
 use std::time::Duration;
 
 use bevy::app::{self, App, Plugin};
 use bevy::ecs::entity::Entity;
 use bevy::ecs::name::Name;
-use bevy::ecs::query::With;
+use bevy::ecs::query::{With, Without};
 use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use bevy::ecs::system::{Commands, Local, ParamSet, Query, Res, SystemParam};
+use bevy::ecs::system::{
+    Commands, EntityCommands, Local, ParamSet, Query, Res, ResMut, SystemParam,
+};
 use bevy::math::Vec3;
 use bevy::time::Time;
 use math::{Accel, AngularSpeed, Heading, Length, Position, Speed};
-use rand::SeedableRng;
-use rand::rngs::SmallRng;
 use rand::seq::IteratorRandom;
 use store::{Score, WeightedList, YawTarget};
 
 use crate::QueryTryLog;
 use crate::level::aerodrome::loader::APRON_FORWARD_HEADING_DIRECTION;
-use crate::level::dest::Destination;
+use crate::level::dest::{self, Destination};
 use crate::level::waypoint::Waypoint;
-use crate::level::{SystemSets, aerodrome, ground, nav, object, plane, route, wake};
+use crate::level::{
+    SystemSets, aerodrome, drift, facility, formation, fuel, ground, message, nav, object, plane,
+    route, runway, session, vfr, wake,
+};
 use crate::load::StoredEntity;
 
 pub mod loader;
+pub mod timetable;
+
+#[cfg(test)]
+mod tests;
+
+/// Distance from the runway at which arrivals spawn in tower-only mode.
+const FINAL_SPAWN_DISTANCE: Length<f32> = Length::from_nm(6.0);
+/// Maximum speed of arrivals spawned on final in tower-only mode.
+const FINAL_SPAWN_SPEED: Speed<f32> = Speed::from_knots(160.0);
+/// Maximum number of names generated for a spawn
+/// in search of one that is not similar to the name of an existing object.
+const NAME_ATTEMPTS: usize = 16;
 
 pub struct Plug;
 
@@ -30,18 +46,38 @@ impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.init_resource::<Sets>();
         app.init_resource::<Trigger>();
+        app.init_resource::<Requested>();
+        app.init_resource::<ExcludedNames>();
+        app.init_resource::<session::Seed>();
         app.add_systems(app::Update, spawn_system.in_set(SystemSets::Spawn));
+        app.add_plugins(timetable::Plug);
     }
 }
 
 #[derive(Default, Resource)]
 pub struct Sets(pub store::WeightedList<Set>);
 
+/// Names that are never generated for spawned objects.
+#[derive(Default, Resource)]
+pub struct ExcludedNames(pub HashSet<String>);
+
 pub struct Set {
-    pub gen_name: WeightedList<store::NameGenerator>,
-    pub types:    WeightedList<Entity>,
-    pub route:    WeightedList<Route>,
-    pub position: WeightedList<Location>,
+    pub gen_name:  WeightedList<store::NameGenerator>,
+    pub types:     WeightedList<Entity>,
+    pub route:     WeightedList<Route>,
+    pub position:  WeightedList<Location>,
+    pub endurance: Option<store::SpawnEndurance>,
+    pub vfr:       Option<VfrRules>,
+    pub formation: Option<store::SpawnFormation>,
+    pub id:        Option<store::SpawnSetRef>,
+    /// Whether spawning from this set is disabled until unlocked by a quest.
+    pub locked:    bool,
+}
+
+/// Visual flight rules for objects spawned in a set.
+pub struct VfrRules {
+    pub reporting_points: Vec<Entity>,
+    pub legs:             u32,
 }
 
 pub struct Route {
@@ -69,12 +105,52 @@ pub struct LocationRunwayTaxiway {
     pub direction:       ground::SegmentDirection,
 }
 
-fn spawn_system(mut params: ParamSet<(TriggerParams, Spawner)>, mut rng: Local<Option<SmallRng>>) {
-    let rng = rng.get_or_insert_with(|| SmallRng::from_rng(&mut rand::rng()));
-    if params.p0().need_more() {
+fn spawn_system(
+    mut params: ParamSet<(TriggerParams, Spawner)>,
+    mut requested: ResMut<Requested>,
+    mut rng: session::SessionRng,
+) {
+    let rng = rng.get(session::STREAM_SPAWN);
+    let triggered = params.p0().need_more();
+    if triggered || requested.0 > 0 {
         let result = params.p1().spawn_once(rng);
         if result.is_some() {
-            params.p0().on_successful_spawn();
+            if triggered {
+                params.p0().on_successful_spawn();
+            } else {
+                requested.0 -= 1;
+            }
+        }
+    }
+}
+
+/// Inserts the components for the per-set rules of a newly spawned object.
+fn insert_set_rules(object: &mut EntityCommands, set: &Set, rng: &mut impl rand::Rng) {
+    if let Some(endurance) = &set.endurance {
+        let remaining = if endurance.min < endurance.max {
+            rng.random_range(endurance.min..=endurance.max)
+        } else {
+            endurance.min
+        };
+        object.insert(fuel::Endurance { remaining });
+    }
+
+    if let Some(vfr) = &set.vfr {
+        object.insert(vfr::Vfr {
+            reporting_points: vfr.reporting_points.clone(),
+            remaining_legs:   vfr.legs,
+        });
+        object.queue(route::PrependStandby);
+    }
+
+    if let Some(formation) = &set.formation {
+        let size = if formation.min < formation.max {
+            rng.random_range(formation.min..=formation.max)
+        } else {
+            formation.min
+        };
+        if size > 1 {
+            object.insert(formation::Formation { size });
         }
     }
 }
@@ -84,9 +160,14 @@ struct TriggerParams<'w, 's> {
     time:         Res<'w, Time>,
     last_spawned: Local<'s, Option<Duration>>,
     mode:         Res<'w, Trigger>,
-    object_query: Query<'w, 's, (), With<object::Object>>,
+    object_query: Query<'w, 's, (), (With<object::Object>, Without<drift::Drifter>)>,
 }
 
+/// Number of additional spawns requested by scenario scripts,
+/// performed one per tick regardless of the [`Trigger`].
+#[derive(Resource, Default)]
+pub struct Requested(pub u32);
+
 /// Determines when new objects should be spawned.
 #[derive(Resource, Default)]
 pub enum Trigger {
@@ -96,13 +177,15 @@ pub enum Trigger {
     ObjectCount {
         count: usize,
     },
+    /// Spawn objects according to the [`timetable::Timetable`].
+    Timetable,
 }
 
 impl TriggerParams<'_, '_> {
     /// Whether a new object needs to be spawned.
     fn need_more(&self) -> bool {
         match *self.mode {
-            Trigger::Disabled => false,
+            Trigger::Disabled | Trigger::Timetable => false,
             Trigger::Periodic(period) => match *self.last_spawned {
                 None => true,
                 Some(last) => self.time.elapsed().checked_sub(last).is_some_and(|v| v >= period),
@@ -120,61 +203,145 @@ impl TriggerParams<'_, '_> {
 
 #[derive(SystemParam)]
 struct Spawner<'w, 's> {
-    sets:              Res<'w, Sets>,
-    commands:          Commands<'w, 's>,
-    object_type_query: Query<'w, 's, &'static object::Type>,
-    endpoint_query:    Query<'w, 's, &'static ground::Endpoint>,
-    aerodrome_query:   Query<'w, 's, &'static aerodrome::Aerodrome>,
-    segment_query:     Query<'w, 's, (&'static ground::Segment, &'static ground::SegmentOf)>,
-    waypoint_query:    Query<'w, 's, &'static Waypoint>,
-    preset_query:      Query<'w, 's, &'static route::Preset>,
+    sets:                       Res<'w, Sets>,
+    commands:                   Commands<'w, 's>,
+    object_type_query:          Query<'w, 's, (&'static object::Type, &'static object::TypeRef)>,
+    endpoint_query:             Query<'w, 's, &'static ground::Endpoint>,
+    aerodrome_query:            Query<'w, 's, &'static aerodrome::Aerodrome>,
+    segment_query: Query<'w, 's, (&'static ground::Segment, &'static ground::SegmentOf)>,
+    waypoint_query:             Query<'w, 's, &'static Waypoint>,
+    preset_query:               Query<'w, 's, &'static route::Preset>,
+    family_query: Query<
+        'w,
+        's,
+        (&'static route::DestinationMatcher, Option<&'static route::PresetFromWaypoint>),
+    >,
+    waypoint_presets_query:     Query<'w, 's, &'static route::WaypointPresetList>,
+    active_configuration_query: Query<'w, 's, (), With<runway::ActiveConfiguration>>,
+    runway_query:               Query<'w, 's, (&'static Waypoint, &'static runway::Runway)>,
+    runway_segments_query:      Query<'w, 's, &'static ground::RunwaySegments>,
+    label_query:                Query<'w, 's, &'static ground::SegmentLabel>,
+    facility_mode:              Res<'w, facility::Mode>,
+    excluded_names:             Res<'w, ExcludedNames>,
+    display_query:              Query<'w, 's, &'static object::Display>,
 }
 
 impl Spawner<'_, '_> {
     /// Attempts to spawn a single object.
     /// Returns `Some(())` if an object was spawned, or `None` if spawning failed.
     fn spawn_once(&mut self, rng: &mut impl rand::Rng) -> Option<()> {
-        let Some(set) = self.sets.0.sample(rng) else {
-            bevy::log::warn_once!("Unable to spawn objects due to empty spawn sets");
+        let sets = Res::clone(&self.sets).into_inner();
+        let Some(set) = sets.0.sample_where(rng, |set| !set.locked) else {
+            bevy::log::warn_once!("Unable to spawn objects due to empty or locked spawn sets");
             return None;
         };
         let Some(gen_name) = set.gen_name.sample(rng) else {
             bevy::log::warn_once!("Unable to spawn objects due to empty name generator");
             return None;
         };
-        let name = gen_name.generate(rng);
+        let Some(name) = self.generate_name(gen_name, rng) else {
+            bevy::log::warn_once!("Unable to generate a name that is not excluded or in use");
+            return None;
+        };
+        let telephony = gen_name.telephony(&name);
 
         let Some(&object_type_id) = set.types.sample(rng) else {
             bevy::log::warn_once!("Unable to spawn objects due to empty object types");
             return None;
         };
-        let object_type = self.object_type_query.log_get(object_type_id)?;
 
         let Some(location) = set.position.sample(rng) else {
             bevy::log::warn_once!("Unable to spawn objects due to empty spawn locations");
             return None;
         };
 
-        let resolved_location = self.resolve_location(location, object_type, rng)?;
         let Some(route) = set.route.sample(rng) else {
             bevy::log::warn_once!("Unable to spawn objects due to empty routes");
             return None;
         };
 
-        let preset = self.preset_query.log_get(route.preset)?;
+        let mut object = self.spawn_object(name, object_type_id, location, route, rng)?;
+        if let Some(telephony) = telephony {
+            object.insert(message::Telephony(telephony));
+        }
+        insert_set_rules(&mut object, set, rng);
+
+        Some(())
+    }
+
+    /// Generates a name that is neither excluded nor used by an existing object.
+    ///
+    /// Names [similar](is_similar_name) to those of existing objects are avoided if possible.
+    fn generate_name(
+        &self,
+        gen_name: &store::NameGenerator,
+        rng: &mut impl rand::Rng,
+    ) -> Option<String> {
+        let mut fallback = None;
+        for _ in 0..NAME_ATTEMPTS {
+            let name = gen_name.generate(rng);
+            if self.excluded_names.0.contains(&name) {
+                continue;
+            }
+
+            let mut existing = self.display_query.iter().map(|display| display.name.as_str());
+            if existing.clone().all(|existing| !is_similar_name(existing, &name)) {
+                return Some(name);
+            }
+            if fallback.is_none() && existing.all(|existing| existing != name) {
+                fallback = Some(name);
+            }
+        }
+        fallback
+    }
+
+    /// Spawns an object with the specified parameters.
+    /// Returns `None` if the location cannot be resolved.
+    fn spawn_object(
+        &mut self,
+        name: String,
+        object_type_id: Entity,
+        location: &Location,
+        route: &Route,
+        rng: &mut impl rand::Rng,
+    ) -> Option<EntityCommands<'_>> {
+        let (object_type, type_ref) = self.object_type_query.log_get(object_type_id)?;
+        let mut resolved_location = self.resolve_location(location, object_type, rng)?;
+        let preset = self.preset_query.log_get(self.select_preset(route))?;
+        let skipped_nodes = match resolved_location.spawn_type {
+            SpawnType::Airborne if self.facility_mode.is_tower_only() => {
+                self.establish_on_final(&mut resolved_location, &preset.nodes)
+            }
+            SpawnType::Airborne if self.facility_mode.is_ground_only() => {
+                self.land_at_exit(&mut resolved_location, &preset.nodes)
+            }
+            _ => 0,
+        };
+        let destination = match (&resolved_location.spawn_type, &route.destination) {
+            (
+                &SpawnType::Landed { aerodrome, .. },
+                Destination::Landing { .. } | Destination::VacateAnyRunway,
+            ) => Destination::Parking { aerodrome },
+            (_, destination) => destination.clone(),
+        };
 
         let mut object = self.commands.spawn((StoredEntity, Name::new(format!("Plane: {name}"))));
         object.queue(object::SpawnCommand {
             position:         resolved_location.position,
             ground_speed:     (resolved_location.speed * resolved_location.heading).horizontally(),
             display:          object::Display { name },
-            destination:      route.destination.clone(),
+            destination:      destination.clone(),
             completion_score: Some(route.score),
         });
 
         match object_type {
-            object::Type::Plane { taxi, nav } => {
-                object.insert(taxi.clone());
+            &object::Type::Plane { ref taxi, ref nav, equipage, category } => {
+                object.insert((
+                    type_ref.clone(),
+                    taxi.clone(),
+                    object::Equipage(equipage),
+                    object::Category(category),
+                ));
                 object.queue(plane::SpawnCommand {
                     limits:  nav.clone(),
                     control: Some(plane::Control {
@@ -200,7 +367,18 @@ impl Spawner<'_, '_> {
                             expedite:    false,
                         });
                     }
-                    SpawnType::Ground { segment, direction } => {
+                    SpawnType::Ground { segment, direction, aerodrome } => {
+                        object.queue(object::SetOnGroundCommand {
+                            segment,
+                            direction,
+                            heading: Some(resolved_location.heading),
+                        });
+                        object.insert(dest::Origin { aerodrome });
+                        if !matches!(destination, Destination::Departure { .. }) {
+                            object.insert(dest::PendingTakeoff);
+                        }
+                    }
+                    SpawnType::Landed { segment, direction, .. } => {
                         object.queue(object::SetOnGroundCommand {
                             segment,
                             direction,
@@ -212,9 +390,37 @@ impl Spawner<'_, '_> {
         }
 
         object.insert(route::Id(Some(preset.id.clone())));
-        object.queue(route::ReplaceNodes(preset.nodes.clone()));
+        object.queue(route::ReplaceNodes(preset.nodes[skipped_nodes..].to_vec()));
 
-        Some(())
+        Some(object)
+    }
+
+    /// Selects the preset of the same family as `route.preset`
+    /// that applies under the active runway configurations.
+    ///
+    /// Presets of the same family start from the same waypoint.
+    /// Falls back to `route.preset` if no preset in the family applies.
+    fn select_preset(&self, route: &Route) -> Entity {
+        let is_active = |configuration| self.active_configuration_query.contains(configuration);
+        let applies = |preset| {
+            self.family_query
+                .get(preset)
+                .is_ok_and(|(matcher, _)| matcher.applies(&route.destination, is_active))
+        };
+        if applies(route.preset) {
+            return route.preset;
+        }
+
+        let Ok((_, Some(&route::PresetFromWaypoint(waypoint)))) =
+            self.family_query.get(route.preset)
+        else {
+            return route.preset;
+        };
+        self.waypoint_presets_query
+            .get(waypoint)
+            .ok()
+            .and_then(|family| family.iter().find(|&preset| applies(preset)))
+            .unwrap_or(route.preset)
     }
 
     fn resolve_location(
@@ -251,6 +457,7 @@ impl Spawner<'_, '_> {
                     spawn_type: SpawnType::Ground {
                         segment:   apron_entity,
                         direction: APRON_FORWARD_HEADING_DIRECTION,
+                        aerodrome: aerodrome_entity,
                     },
                 })
             }
@@ -285,6 +492,7 @@ impl Spawner<'_, '_> {
                     spawn_type: SpawnType::Ground {
                         segment:   chosen.taxiway_segment,
                         direction: chosen.direction,
+                        aerodrome: aerodrome_entity,
                     },
                 })
             }
@@ -297,6 +505,106 @@ impl Spawner<'_, '_> {
         }
     }
 
+    /// Moves an airborne spawn onto the final approach of the runway its route lands on.
+    ///
+    /// Returns the number of route nodes before the final approach,
+    /// which are skipped by the spawned object.
+    fn establish_on_final(&self, location: &mut ResolvedLocation, nodes: &[route::Node]) -> usize {
+        let Some((index, runway)) = nodes.iter().enumerate().find_map(|(index, node)| match node {
+            route::Node::AlignRunway(node) => Some((index, node.runway)),
+            _ => None,
+        }) else {
+            return 0;
+        };
+        let Some((waypoint, runway)) = self.runway_query.log_get(runway) else { return 0 };
+
+        location.position = waypoint.position
+            + runway
+                .landing_length
+                .normalize_to_magnitude(-FINAL_SPAWN_DISTANCE)
+                .projected_from_elevation_angle(runway.glide_descent);
+        location.heading = runway.landing_length.heading();
+        location.speed = location.speed.min(FINAL_SPAWN_SPEED);
+        index
+    }
+
+    /// Moves an airborne spawn onto the exit taxiway of the runway its route lands on,
+    /// as if the object has just landed and vacated the runway.
+    ///
+    /// Returns the number of route nodes up to touchdown,
+    /// which are skipped by the spawned object.
+    fn land_at_exit(&self, location: &mut ResolvedLocation, nodes: &[route::Node]) -> usize {
+        let Some(runway) = nodes.iter().find_map(|node| match node {
+            route::Node::AlignRunway(node) => Some(node.runway),
+            _ => None,
+        }) else {
+            return 0;
+        };
+        let Some(touchdown_index) = nodes.iter().rposition(|node| {
+            matches!(
+                node,
+                route::Node::AlignRunway(_)
+                    | route::Node::ShortFinal(_)
+                    | route::Node::VisualLanding(_)
+            )
+        }) else {
+            return 0;
+        };
+        let Some(exit) = self.find_runway_exit(runway) else { return 0 };
+
+        *location = exit;
+        touchdown_index + 1
+    }
+
+    /// Finds the taxiway connected to a runway closest to its far end,
+    /// facing away from the runway.
+    fn find_runway_exit(&self, runway_entity: Entity) -> Option<ResolvedLocation> {
+        let (waypoint, runway) = self.runway_query.log_get(runway_entity)?;
+        let runway_end = waypoint.position.horizontal() + runway.landing_length;
+
+        let (exit_entity, runway_endpoint, _) = self
+            .runway_segments_query
+            .log_get(runway_entity)?
+            .0
+            .iter()
+            .filter_map(|&segment| self.segment_query.log_get(segment))
+            .flat_map(|(segment, _)| [segment.alpha, segment.beta])
+            .filter_map(|endpoint| Some((endpoint, self.endpoint_query.log_get(endpoint)?)))
+            .flat_map(|(endpoint_entity, endpoint)| {
+                endpoint
+                    .adjacency
+                    .iter()
+                    .map(move |&adjacent| (adjacent, endpoint_entity, endpoint.position))
+            })
+            .filter(|&(adjacent, ..)| {
+                self.label_query.get(adjacent).is_ok_and(ground::SegmentLabel::is_taxiway)
+            })
+            .min_by_key(|&(.., position)| position.distance_cmp(runway_end))?;
+
+        let (segment, &ground::SegmentOf(aerodrome_entity)) =
+            self.segment_query.log_get(exit_entity)?;
+        let aerodrome_elevation = self.aerodrome_query.log_get(aerodrome_entity)?.elevation;
+        let direction = if segment.alpha == runway_endpoint {
+            ground::SegmentDirection::AlphaToBeta
+        } else {
+            ground::SegmentDirection::BetaToAlpha
+        };
+        let (endpoint_behind, endpoint_ahead) = segment.by_direction(direction);
+        let endpoint_behind_position = self.endpoint_query.log_get(endpoint_behind)?.position;
+        let endpoint_ahead_position = self.endpoint_query.log_get(endpoint_ahead)?.position;
+
+        Some(ResolvedLocation {
+            position:   endpoint_ahead_position.with_altitude(aerodrome_elevation),
+            speed:      Speed::ZERO,
+            heading:    (endpoint_ahead_position - endpoint_behind_position).heading(),
+            spawn_type: SpawnType::Landed {
+                segment: exit_entity,
+                direction,
+                aerodrome: aerodrome_entity,
+            },
+        })
+    }
+
     /// Checks if the apron is available for spawning.
     ///
     /// An apron is available if it is currently marked as occupied by a virtual object.
@@ -325,5 +633,32 @@ struct ResolvedLocation {
 
 enum SpawnType {
     Airborne,
-    Ground { segment: Entity, direction: ground::SegmentDirection },
+    Ground {
+        segment:   Entity,
+        direction: ground::SegmentDirection,
+        aerodrome: Entity,
+    },
+    /// An arrival that has already landed, spawned on a runway exit in ground-only mode.
+    Landed {
+        segment:   Entity,
+        direction: ground::SegmentDirection,
+        aerodrome: Entity,
+    },
+}
+
+/// Whether two names are easily confused on the radio,
+/// i.e. they are identical, differ in a single character,
+/// or differ by swapping two characters, e.g. `DLH123` and `DLH132`.
+#[must_use]
+pub fn is_similar_name(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diffs = a.chars().zip(b.chars()).filter(|(x, y)| x != y);
+    match (diffs.next(), diffs.next(), diffs.next()) {
+        (None, _, _) | (Some(_), None, _) => true,
+        (Some((a1, b1)), Some((a2, b2)), None) => a1 == b2 && a2 == b1,
+        _ => false,
+    }
 }
@@ -1,10 +1,17 @@
 use std::collections::HashSet;
 
+use bevy::ecs::entity::EntityHashMap;
 use bevy::ecs::world::World;
+use bevy::time::{self, Time};
 
 use crate::level::{aerodrome, ground, object, route, spawn, waypoint};
 use crate::load;
 
+/// Replaces the names excluded from generation for spawned objects.
+pub fn excluded_names(world: &mut World, names: &[String]) {
+    world.resource_mut::<spawn::ExcludedNames>().0 = names.iter().cloned().collect();
+}
+
 /// Spawns stored spawn sets into the world.
 ///
 /// # Errors
@@ -19,27 +26,54 @@ pub fn spawn_sets(
 ) -> load::Result<()> {
     world.resource_mut::<spawn::Sets>().0 = spawn_sets.try_map_ref(|set| {
         Ok(spawn::Set {
-            gen_name: set.gen_name.clone(),
-            types:    set.types.try_map_ref(|ty| object_types.resolve(ty))?,
-            route:    set.route.try_map_ref(|route| {
-                Ok(spawn::Route {
-                    preset:      route_presets.resolve(&route.preset)?,
-                    destination: object::loader::resolve_destination(
-                        aerodromes,
-                        waypoints,
-                        &route.destination,
-                    )?,
-                    score:       route.score,
-                })
-            })?,
-            position: set
+            gen_name:  set.gen_name.clone(),
+            types:     set.types.try_map_ref(|ty| object_types.resolve(ty))?,
+            route:     set
+                .route
+                .try_map_ref(|route| resolve_route(aerodromes, waypoints, route_presets, route))?,
+            position:  set
                 .position
                 .try_map_ref(|position| resolve_position(aerodromes, waypoints, position))?,
+            endurance: set.endurance.clone(),
+            vfr:       set
+                .vfr
+                .as_ref()
+                .map(|vfr| {
+                    Ok::<_, load::Error>(spawn::VfrRules {
+                        reporting_points: vfr
+                            .reporting_points
+                            .iter()
+                            .map(|point| waypoints.resolve(point))
+                            .collect::<load::Result<_>>()?,
+                        legs:             vfr.legs,
+                    })
+                })
+                .transpose()?,
+            formation: set.formation.clone(),
+            id:        set.id.clone(),
+            locked:    set.locked,
         })
     })?;
     Ok(())
 }
 
+fn resolve_route(
+    aerodromes: &aerodrome::loader::AerodromeMap,
+    waypoints: &waypoint::loader::WaypointMap,
+    route_presets: &route::loader::RoutePresetMap,
+    route: &store::SpawnRoute,
+) -> load::Result<spawn::Route> {
+    Ok(spawn::Route {
+        preset:      route_presets.resolve(&route.preset)?,
+        destination: object::loader::resolve_destination(
+            aerodromes,
+            waypoints,
+            &route.destination,
+        )?,
+        score:       route.score,
+    })
+}
+
 fn resolve_position(
     aerodromes: &aerodrome::loader::AerodromeMap,
     waypoints: &waypoint::loader::WaypointMap,
@@ -127,12 +161,58 @@ fn resolve_position(
     }
 }
 
-pub fn spawn_trigger(world: &mut World, trigger: &store::SpawnTrigger) {
+/// Configures the spawn trigger from a store.
+///
+/// # Errors
+/// If the stored timetable contains invalid data.
+pub fn spawn_trigger(
+    world: &mut World,
+    object_types: &object::loader::ObjectTypeMap,
+    aerodromes: &aerodrome::loader::AerodromeMap,
+    waypoints: &waypoint::loader::WaypointMap,
+    route_presets: &route::loader::RoutePresetMap,
+    trigger: &store::SpawnTrigger,
+) -> load::Result<()> {
     *world.resource_mut::<spawn::Trigger>() = match *trigger {
         store::SpawnTrigger::Disabled => spawn::Trigger::Disabled,
         store::SpawnTrigger::Periodic { duration } => spawn::Trigger::Periodic(duration),
         store::SpawnTrigger::ObjectCount { count } => {
             spawn::Trigger::ObjectCount { count: count.try_into().expect("usize >= u32") }
         }
+        store::SpawnTrigger::Timetable(ref timetable) => {
+            let elapsed = world.resource::<Time<time::Virtual>>().elapsed();
+            let mut flights = timetable
+                .flights
+                .iter()
+                .map(|flight| {
+                    Ok(spawn::timetable::Flight {
+                        callsign:    flight.callsign.clone(),
+                        telephony:   flight.telephony.clone(),
+                        object_type: object_types.resolve(&flight.object_type)?,
+                        origin:      flight.origin.clone(),
+                        destination: flight.destination.clone(),
+                        scheduled:   elapsed + flight.scheduled,
+                        location:    resolve_position(aerodromes, waypoints, &flight.position)?,
+                        route:       resolve_route(
+                            aerodromes,
+                            waypoints,
+                            route_presets,
+                            &flight.route,
+                        )?,
+                    })
+                })
+                .collect::<load::VecResult<_>>()?;
+            flights.sort_by_key(|flight| flight.scheduled);
+
+            *world.resource_mut::<spawn::timetable::Timetable>() = spawn::timetable::Timetable {
+                flights,
+                next: 0,
+                lead_time: timetable.lead_time,
+                on_time_tolerance: timetable.on_time_tolerance,
+                active: EntityHashMap::default(),
+            };
+            spawn::Trigger::Timetable
+        }
     };
+    Ok(())
 }
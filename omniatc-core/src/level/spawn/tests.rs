@@ -0,0 +1,40 @@
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use super::is_similar_name;
+
+#[test]
+fn test_similar_names() {
+    assert!(is_similar_name("DLH123", "DLH123"));
+    assert!(is_similar_name("DLH123", "DLH132"), "swapped digits are similar");
+    assert!(is_similar_name("DLH123", "DLH124"), "single substitution is similar");
+    assert!(is_similar_name("DLH123", "DLB123"));
+    assert!(!is_similar_name("DLH123", "DLH456"));
+    assert!(!is_similar_name("DLH123", "DLH1234"));
+    assert!(!is_similar_name("DLH123", "DLH293"));
+}
+
+#[test]
+fn test_registration_telephony() {
+    let gen_name =
+        store::NameGenerator::Registration { region: store::RegistrationRegion::UnitedKingdom };
+    assert_eq!(gen_name.telephony("G-ABCD").as_deref(), Some("Golf Alfa Bravo Charlie Delta"));
+
+    let gen_name =
+        store::NameGenerator::Registration { region: store::RegistrationRegion::UnitedStates };
+    assert_eq!(gen_name.telephony("N193AB").as_deref(), Some("November One Niner Tree Alfa Bravo"));
+}
+
+#[test]
+fn test_registration_prefixes() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for region in [
+        store::RegistrationRegion::France,
+        store::RegistrationRegion::Netherlands,
+        store::RegistrationRegion::Switzerland,
+        store::RegistrationRegion::China,
+    ] {
+        let name = store::NameGenerator::Registration { region }.generate(&mut rng);
+        assert!(name.starts_with(region.prefix()), "{name} should start with {}", region.prefix());
+    }
+}
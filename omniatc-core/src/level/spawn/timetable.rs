@@ -0,0 +1,142 @@
+//! Spawning objects from a timetable of scheduled flights.
+//!
+//! Each flight is spawned [`Timetable::lead_time`] before its scheduled time.
+//! Its punctuality is evaluated when it completes its destination.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::{Entity, EntityHashMap};
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Res, ResMut};
+use bevy::time::{self, Time};
+
+use super::{Location, Route, Spawner, Trigger};
+use crate::level::{SystemSets, dest, message, object, session};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Timetable>();
+        app.init_resource::<Stats>();
+        app.init_resource::<session::Seed>();
+        app.add_systems(app::Update, spawn_system.in_set(SystemSets::Spawn));
+        app.add_systems(app::Update, stats_system.in_set(SystemSets::Statistics));
+    }
+}
+
+/// Scheduled flights to spawn when [`Trigger::Timetable`] is active.
+#[derive(Resource, Default)]
+pub struct Timetable {
+    /// All flights in the timetable, sorted by scheduled time.
+    pub flights:           Vec<Flight>,
+    /// Index of the next flight to spawn in `flights`.
+    pub next:              usize,
+    /// Time before the scheduled time at which each flight is spawned.
+    pub lead_time:         Duration,
+    /// Maximum delay after the scheduled time for a flight to be considered on time.
+    pub on_time_tolerance: Duration,
+    /// Spawned flights that have not completed yet,
+    /// mapped to their scheduled completion time.
+    pub active:            EntityHashMap<Duration>,
+}
+
+/// A flight in the [`Timetable`].
+pub struct Flight {
+    pub callsign:    String,
+    pub telephony:   Option<String>,
+    pub object_type: Entity,
+    pub origin:      String,
+    pub destination: String,
+    /// Virtual time at which the flight is scheduled to complete its destination.
+    pub scheduled:   Duration,
+    pub location:    Location,
+    pub route:       Route,
+}
+
+/// On-time performance of completed timetable flights.
+#[derive(Resource, Default)]
+pub struct Stats {
+    /// Number of flights completed within the tolerance after the scheduled time.
+    pub on_time:     u32,
+    /// Number of flights completed later than the tolerance.
+    pub delayed:     u32,
+    /// Sum of delays of all completed flights.
+    pub total_delay: Duration,
+}
+
+impl Stats {
+    /// Ratio of on-time flights among all completed flights, or `None` if none completed.
+    #[must_use]
+    pub fn on_time_ratio(&self) -> Option<f32> {
+        let total = self.on_time + self.delayed;
+        #[expect(clippy::cast_precision_loss, reason = "flight counts are small")]
+        (total > 0).then(|| self.on_time as f32 / total as f32)
+    }
+}
+
+fn spawn_system(
+    time: Res<Time<time::Virtual>>,
+    trigger: Res<Trigger>,
+    mut timetable: ResMut<Timetable>,
+    mut spawner: Spawner,
+    mut rng: session::SessionRng,
+) {
+    if !matches!(*trigger, Trigger::Timetable) {
+        return;
+    }
+    let rng = rng.get(session::STREAM_TIMETABLE);
+    let timetable = &mut *timetable;
+
+    while let Some(flight) = timetable.flights.get(timetable.next) {
+        if flight.scheduled.saturating_sub(timetable.lead_time) > time.elapsed() {
+            break;
+        }
+
+        let Some(mut object) = spawner.spawn_object(
+            flight.callsign.clone(),
+            flight.object_type,
+            &flight.location,
+            &flight.route,
+            rng,
+        ) else {
+            // Retry in the next frame; the flight gets delayed as a result.
+            break;
+        };
+        if let Some(telephony) = &flight.telephony {
+            object.insert(message::Telephony(telephony.clone()));
+        }
+        timetable.active.insert(object.id(), flight.scheduled);
+        timetable.next += 1;
+    }
+}
+
+fn stats_system(
+    time: Res<Time<time::Virtual>>,
+    mut timetable: ResMut<Timetable>,
+    mut stats: ResMut<Stats>,
+    mut completed_reader: MessageReader<dest::CompletedMessage>,
+    mut despawn_reader: MessageReader<object::DespawnMessage>,
+) {
+    for &dest::CompletedMessage(entity) in completed_reader.read() {
+        let Some(scheduled) = timetable.active.remove(&entity) else { continue };
+
+        let delay = time.elapsed().saturating_sub(scheduled);
+        if delay <= timetable.on_time_tolerance {
+            stats.on_time += 1;
+        } else {
+            stats.delayed += 1;
+        }
+        stats.total_delay += delay;
+    }
+
+    for &object::DespawnMessage(entity) in despawn_reader.read() {
+        timetable.active.remove(&entity);
+    }
+}
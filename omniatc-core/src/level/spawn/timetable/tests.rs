@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+
+use super::{Stats, Timetable};
+use crate::level::spawn::timetable;
+use crate::level::test_util::{self, advance};
+use crate::level::{dest, facility, object, spawn};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((object::Plug::<()>::default(), timetable::Plug));
+    app.add_message::<dest::CompletedMessage>();
+    app.init_resource::<spawn::Sets>();
+    app.init_resource::<spawn::ExcludedNames>();
+    app.init_resource::<spawn::Trigger>();
+    app.init_resource::<facility::Mode>();
+    app.update();
+    app
+}
+
+fn track(app: &mut App, scheduled: Duration) -> Entity {
+    let entity = app.world_mut().spawn_empty().id();
+    let mut timetable = app.world_mut().resource_mut::<Timetable>();
+    timetable.on_time_tolerance = Duration::from_mins(5);
+    timetable.active.insert(entity, scheduled);
+    entity
+}
+
+#[test]
+fn test_completion_punctuality() {
+    let mut app = base_app();
+    let early = track(&mut app, Duration::from_mins(10));
+    let late = track(&mut app, Duration::from_mins(10));
+
+    advance(&mut app, Duration::from_mins(12));
+    app.world_mut().write_message(dest::CompletedMessage(early));
+    app.update();
+
+    advance(&mut app, Duration::from_mins(8));
+    app.world_mut().write_message(dest::CompletedMessage(late));
+    app.update();
+
+    let stats = app.world().resource::<Stats>();
+    assert_eq!((stats.on_time, stats.delayed), (1, 1));
+    assert_eq!(stats.total_delay, Duration::from_mins(12));
+    assert!(app.world().resource::<Timetable>().active.is_empty());
+}
+
+#[test]
+fn test_despawn_without_completion() {
+    let mut app = base_app();
+    let entity = track(&mut app, Duration::from_mins(10));
+
+    app.world_mut().write_message(object::DespawnMessage(entity));
+    app.update();
+
+    let stats = app.world().resource::<Stats>();
+    assert_eq!((stats.on_time, stats.delayed), (0, 0));
+    assert!(app.world().resource::<Timetable>().active.is_empty());
+}
@@ -0,0 +1,159 @@
+//! Runway surface conditions and their effect on braking.
+//!
+//! Each runway has a [`runway::Condition`] with a [`store::SurfaceCondition`].
+//! Scheduled [`Rain`] showers wet the dry runways they cover,
+//! which dry up again some time after the rain stops.
+//! A braking action report is sent whenever the surface condition of a runway changes.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::math::Vec2;
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Length, Position};
+use store::SurfaceCondition;
+
+use super::SystemSets;
+use crate::level::message;
+use crate::level::runway::{self, Runway};
+use crate::level::waypoint::Waypoint;
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:surface");
+        app.add_systems(
+            app::Update,
+            (rain_system, friction_system).chain().in_set(SystemSets::PrepareEnviron),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Multiplier on the base braking rate of objects on a wet runway.
+    #[config(default = 0.7, min = 0.0, max = 1.0)]
+    pub wet_friction:          f32,
+    /// Multiplier on the base braking rate of objects on a contaminated runway.
+    #[config(default = 0.4, min = 0.0, max = 1.0)]
+    pub contaminated_friction: f32,
+    /// Time for a wet runway to dry up after the rain stops.
+    #[config(default = Duration::from_mins(20))]
+    pub drying_duration:       Duration,
+}
+
+/// A rain shower over a circular area during a period of virtual time.
+#[derive(Component)]
+pub struct Rain {
+    pub center: Position<Vec2>,
+    pub radius: Length<f32>,
+    /// Virtual time at which the shower starts.
+    pub start:  Duration,
+    /// Virtual time at which the shower ends.
+    pub end:    Duration,
+}
+
+impl Rain {
+    /// Whether the shower is raining over `position` at virtual time `now`.
+    #[must_use]
+    pub fn covers(&self, position: Position<Vec2>, now: Duration) -> bool {
+        (self.start..self.end).contains(&now) && self.center.distance_cmp(position) < self.radius
+    }
+}
+
+/// Virtual time at which rain was last observed on a runway.
+///
+/// Component on runway entities.
+#[derive(Component)]
+pub struct LastRain(pub Duration);
+
+/// Describes the braking action associated with a surface condition.
+#[must_use]
+pub fn braking_action(surface: SurfaceCondition) -> &'static str {
+    match surface {
+        SurfaceCondition::Dry => "good",
+        SurfaceCondition::Wet => "medium",
+        SurfaceCondition::Contaminated => "poor",
+    }
+}
+
+fn rain_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    rain_query: Query<&Rain>,
+    runway_query: Query<(Entity, &Waypoint, &mut runway::Condition, Option<&LastRain>)>,
+    mut commands: Commands,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let conf = conf.read();
+    let now = time.elapsed();
+
+    for (runway_entity, waypoint, mut condition, last_rain) in runway_query {
+        let raining =
+            rain_query.iter().any(|rain| rain.covers(waypoint.position.horizontal(), now));
+
+        let new_surface = if raining {
+            commands.entity(runway_entity).insert(LastRain(now));
+            match condition.surface {
+                SurfaceCondition::Dry => SurfaceCondition::Wet,
+                surface => surface,
+            }
+        } else {
+            match (condition.surface, last_rain) {
+                (SurfaceCondition::Wet, Some(&LastRain(last)))
+                    if now.saturating_sub(last) >= conf.drying_duration =>
+                {
+                    commands.entity(runway_entity).remove::<LastRain>();
+                    SurfaceCondition::Dry
+                }
+                (surface, _) => surface,
+            }
+        };
+
+        if new_surface != condition.surface {
+            condition.surface = new_surface;
+            commands.queue(message::SendExpiring {
+                source:   runway_entity,
+                content:  format!("Braking action {} reported", braking_action(new_surface)),
+                class:    message::Class::AnomalyInfo,
+                duration: Duration::from_mins(1),
+            });
+        }
+    }
+}
+
+fn friction_system(
+    conf: ReadConfig<Conf>,
+    runway_query: Query<&mut runway::Condition, With<Runway>>,
+) {
+    let conf = conf.read();
+
+    for mut condition in runway_query {
+        condition.friction_factor = match condition.surface {
+            SurfaceCondition::Dry => 1.0,
+            SurfaceCondition::Wet => conf.wet_friction,
+            SurfaceCondition::Contaminated => conf.contaminated_friction,
+        };
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+
+use crate::level::surface;
+use crate::load::StoredEntity;
+
+/// Spawns the rain showers declared in a store into the world.
+///
+/// Shower times are relative to the current virtual time.
+pub fn spawn_rain_showers(world: &mut World, showers: &[store::RainShower]) {
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    for shower in showers {
+        world.spawn((
+            StoredEntity,
+            Name::new("Rain shower"),
+            surface::Rain {
+                center: shower.center,
+                radius: shower.radius,
+                start:  now.saturating_add(shower.start),
+                end:    now.saturating_add(shower.start).saturating_add(shower.duration),
+            },
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Angle, Heading, Length, Position};
+use store::SurfaceCondition;
+
+use super::Rain;
+use crate::level::runway::{self, Runway};
+use crate::level::test_util::{self, advance};
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{message, surface};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, surface::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+fn spawn_runway(app: &mut App, surface: SurfaceCondition) -> Entity {
+    let position = Position::ORIGIN.with_altitude(Position::SEA_LEVEL);
+    app.world_mut()
+        .spawn((
+            Waypoint {
+                name: "36".into(),
+                display_type: waypoint::DisplayType::Runway,
+                position,
+                hidden: false,
+            },
+            Runway {
+                landing_length: Length::from_meters(3000.0).with_heading(Heading::NORTH),
+                display_start:  position,
+                display_end:    position,
+                width:          Length::from_meters(60.0),
+                glide_descent:  Angle::from_degrees(3.0),
+            },
+            runway::Condition { friction_factor: 1.0, surface },
+            message::Sender { display: "MAIN/36".into() },
+        ))
+        .id()
+}
+
+fn surface_of(app: &App, runway: Entity) -> SurfaceCondition {
+    app.world().get::<runway::Condition>(runway).expect("runway should exist").surface
+}
+
+#[test]
+fn test_rain_wets_runway_then_dries() {
+    let mut app = base_app();
+    let runway = spawn_runway(&mut app, SurfaceCondition::Dry);
+    app.world_mut().spawn(Rain {
+        center: Position::ORIGIN,
+        radius: Length::from_nm(5.0),
+        start:  Duration::from_mins(1),
+        end:    Duration::from_mins(2),
+    });
+
+    advance(&mut app, Duration::from_secs(30));
+    assert_eq!(surface_of(&app, runway), SurfaceCondition::Dry);
+
+    advance(&mut app, Duration::from_mins(1));
+    assert_eq!(surface_of(&app, runway), SurfaceCondition::Wet);
+    let condition = app.world().get::<runway::Condition>(runway).expect("runway should exist");
+    assert!(condition.friction_factor < 1.0, "wet runway should have reduced friction");
+
+    let world = app.world_mut();
+    assert!(
+        world
+            .query::<&message::Message>()
+            .iter(world)
+            .any(|message| message.source == runway && message.content.contains("medium")),
+        "wetting should report medium braking action"
+    );
+
+    // The rain ends at 2 minutes and the runway dries 20 minutes afterwards.
+    advance(&mut app, Duration::from_mins(10));
+    assert_eq!(surface_of(&app, runway), SurfaceCondition::Wet);
+    advance(&mut app, Duration::from_mins(15));
+    assert_eq!(surface_of(&app, runway), SurfaceCondition::Dry);
+}
+
+#[test]
+fn test_rain_does_not_improve_contaminated_runway() {
+    let mut app = base_app();
+    let runway = spawn_runway(&mut app, SurfaceCondition::Contaminated);
+    app.world_mut().spawn(Rain {
+        center: Position::ORIGIN,
+        radius: Length::from_nm(5.0),
+        start:  Duration::ZERO,
+        end:    Duration::from_mins(1),
+    });
+
+    advance(&mut app, Duration::from_secs(30));
+    advance(&mut app, Duration::from_hours(1));
+    assert_eq!(surface_of(&app, runway), SurfaceCondition::Contaminated);
+}
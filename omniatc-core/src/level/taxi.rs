@@ -16,6 +16,8 @@
 //! and it is the responsibility of `target_path_system` to reduce the target speed
 //! when approaching an intersection or holding short.
 
+use std::borrow::Cow;
+use std::marker::PhantomData;
 use std::ops;
 
 use bevy::app::{self, App, Plugin};
@@ -27,15 +29,19 @@ use bevy::ecs::schedule::IntoScheduleConfigs;
 use bevy::ecs::system::{Commands, Query, Res, SystemParam};
 use bevy::math::{Dir2, Vec2};
 use bevy::time::{self, Time};
-use math::{Angle, CanSqrt, Heading, Length, Position, Speed, point_line_closest};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Accel, Angle, CanSqrt, Heading, Length, Position, Speed, Squared, point_line_closest};
 use ordered_float::OrderedFloat;
 use wordvec::WordVec;
 
 use super::object::Object;
 use super::{SystemSets, ground, object};
-use crate::level::message;
+use crate::level::{crossing, message, runway};
 use crate::{QueryTryLog, try_log, try_log_return};
 
+#[cfg(test)]
+mod tests;
+
 /// An object is considered stationary when slower than this speed.
 ///
 /// This value is intended for comparison.
@@ -68,16 +74,32 @@ const MISS_TURN_OVERSHOOT_TOLERANCE: Length<f32> = Length::from_meters(15.0);
 /// Extra deceleration distance in case braking is less effective.
 const DECEL_BUFFER: f32 = 1.2;
 
-pub struct Plug;
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
 
-impl Plugin for Plug {
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
     fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:taxi");
         app.add_systems(app::Update, maintain_dir_system.in_set(SystemSets::Aviate));
         app.add_systems(app::Update, target_path_system.in_set(SystemSets::Navigate));
         app.add_message::<TargetResolutionMessage>();
     }
 }
 
+#[derive(Config)]
+pub struct Conf {
+    /// Minimum gap maintained behind the preceding object on the same segment,
+    /// in addition to the half-lengths of both objects.
+    #[config(default = Length::from_meters(15.0), min = Length::ZERO, max = Length::from_meters(200.0))]
+    pub following_distance: Length<f32>,
+}
+
 #[derive(Component, Clone)]
 pub struct Limits(pub store::TaxiLimits);
 
@@ -86,6 +108,20 @@ impl ops::Deref for Limits {
     fn deref(&self) -> &Self::Target { &self.0 }
 }
 
+impl Limits {
+    /// Braking is less effective on a runway with a degraded surface.
+    fn with_friction(&self, friction_factor: f32) -> Cow<'_, Self> {
+        if friction_factor < 1.0 {
+            Cow::Owned(Limits(store::TaxiLimits {
+                base_braking: self.base_braking * friction_factor,
+                ..self.0.clone()
+            }))
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+}
+
 #[derive(QueryData)]
 #[query_data(mutable)]
 struct MaintainDirObjectQuery {
@@ -95,13 +131,52 @@ struct MaintainDirObjectQuery {
     taxi_status: &'static mut object::TaxiStatus,
     limits:      &'static Limits,
     off_road:    Option<&'static HasOffRoad>,
+    waiting_for: Option<&'static WaitingFor>,
+}
+
+/// The object that this object is stopped behind,
+/// unable to move until the other object moves away.
+///
+/// Updated by the taxi plugin during the Aviate phase.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitingFor(pub Entity);
+
+/// Snapshot of a ground object for following distance control.
+struct Traffic {
+    object:      Entity,
+    segment:     Entity,
+    position:    Position<Vec2>,
+    velocity:    Speed<Vec2>,
+    half_length: Length<f32>,
+}
+
+impl Traffic {
+    fn snapshot(object_query: &Query<MaintainDirObjectQuery>) -> Vec<Self> {
+        object_query
+            .iter()
+            .filter(|object| object.off_road.is_none())
+            .map(|object| Self::new(object.object_id, object.object, object.ground, object.limits))
+            .collect()
+    }
+
+    fn new(object_id: Entity, object: &Object, ground: &object::OnGround, limits: &Limits) -> Self {
+        Traffic {
+            object:      object_id,
+            segment:     ground.segment,
+            position:    object.position.horizontal(),
+            velocity:    object.ground_speed.horizontal(),
+            half_length: limits.half_length,
+        }
+    }
 }
 
 fn maintain_dir_system(
     time: Res<Time<time::Virtual>>,
-    object_query: Query<MaintainDirObjectQuery>,
-    segment_query: Query<&ground::Segment>,
+    conf: ReadConfig<Conf>,
+    mut object_query: Query<MaintainDirObjectQuery>,
+    segment_query: Query<(&ground::Segment, Option<&ground::SegmentOfRunway>)>,
     endpoint_query: Query<&ground::Endpoint>,
+    runway_condition_query: Query<&runway::Condition>,
     off_road_params: OffRoadSystemParams<'_, '_>,
     mut commands: Commands,
 ) {
@@ -109,7 +184,10 @@ fn maintain_dir_system(
         return;
     }
 
-    for mut object in object_query {
+    let conf = conf.read();
+    let traffic = Traffic::snapshot(&object_query);
+
+    for mut object in &mut object_query {
         if let Some(off_road) = object.off_road {
             if let Some((segment_id, segment_dir)) = find_closest_segment(
                 &object.object,
@@ -127,7 +205,10 @@ fn maintain_dir_system(
                 commands.entity(off_road.0).despawn();
             }
         } else {
-            let Some(segment) = segment_query.log_get(object.ground.segment) else { continue };
+            let Some((segment, segment_runways)) = segment_query.log_get(object.ground.segment)
+            else {
+                continue;
+            };
             let (other_endpoint_entity, target_endpoint_entity) = match object.ground.direction {
                 ground::SegmentDirection::AlphaToBeta => (segment.alpha, segment.beta),
                 ground::SegmentDirection::BetaToAlpha => (segment.beta, segment.alpha),
@@ -139,14 +220,32 @@ fn maintain_dir_system(
                 continue;
             };
 
+            let friction_factor = segment_runways
+                .and_then(|runways| {
+                    runway_condition_query.get(runways.by_direction(object.ground.direction)).ok()
+                })
+                .map_or(1.0, |condition| condition.friction_factor);
+            let limits = object.limits.with_friction(friction_factor);
+
+            let following = following_speed_limit(
+                &Traffic::new(object.object_id, &object.object, &object.ground, object.limits),
+                (target_endpoint.position - other_endpoint.position).heading().into_dir2(),
+                &traffic,
+                limits.base_braking,
+                conf.following_distance,
+            );
+            update_waiting_for(&mut commands, &object, following);
+            let following_limit = following.map(|(_, limit)| limit);
+
             let result = maintain_dir_for_object(
                 &time,
                 &mut object.object,
                 &object.ground,
                 &mut object.taxi_status,
-                object.limits,
+                &limits,
                 [other_endpoint, target_endpoint].map(|e| e.position),
                 segment,
+                following_limit,
             );
             match result {
                 MaintainDirResult::Ok => {}
@@ -183,6 +282,63 @@ fn maintain_dir_system(
     }
 }
 
+/// Returns the maximum speed at which `follower` can still stop behind
+/// the nearest object ahead of it on the same segment,
+/// keeping `following_distance` between them.
+///
+/// `travel_dir` is the direction from the start endpoint to the target endpoint of the segment.
+/// Returns the preceding object and the speed limit,
+/// or `None` if there is no object ahead on the segment.
+fn following_speed_limit(
+    follower: &Traffic,
+    travel_dir: Dir2,
+    traffic: &[Traffic],
+    braking: Accel<f32>,
+    following_distance: Length<f32>,
+) -> Option<(Entity, Speed<f32>)> {
+    let (distance, leader) = traffic
+        .iter()
+        .filter(|other| other.segment == follower.segment && other.object != follower.object)
+        .map(|other| ((other.position - follower.position).project_onto_dir(travel_dir), other))
+        .filter(|(distance, _)| distance.is_positive())
+        .min_by_key(|&(distance, _)| OrderedFloat(distance.0))?;
+
+    let gap = (distance - follower.half_length - leader.half_length - following_distance)
+        .max(Length::ZERO);
+    let leader_speed = leader.velocity.project_onto_dir(travel_dir).max(Speed::ZERO);
+    // v^2 = u^2 + 2 * braking * gap, where u is the speed of the leader.
+    let speed_squared =
+        Squared::<Speed<f32>>::new(leader_speed.squared().0 + braking.0 * gap.0 * 2.0);
+    Some((leader.object, speed_squared.sqrt_or_zero()))
+}
+
+/// Marks the object as [waiting for](WaitingFor) the preceding object
+/// if it is stopped by the following distance while intending to move.
+fn update_waiting_for(
+    commands: &mut Commands,
+    object: &MaintainDirObjectQueryItem,
+    following: Option<(Entity, Speed<f32>)>,
+) {
+    let intends_to_move = match object.ground.target_speed {
+        object::OnGroundTargetSpeed::Exact(speed) => speed.abs() >= NEGLIGIBLE_SPEED,
+        object::OnGroundTargetSpeed::TakeoffRoll => true,
+    };
+    let waiting_for = following
+        .filter(|&(_, limit)| {
+            intends_to_move
+                && limit < NEGLIGIBLE_SPEED
+                && object.object.ground_speed.magnitude_cmp() < NEGLIGIBLE_SPEED
+        })
+        .map(|(leader, _)| WaitingFor(leader));
+
+    if object.waiting_for.copied() != waiting_for {
+        match waiting_for {
+            Some(waiting_for) => commands.entity(object.object_id).insert(waiting_for),
+            None => commands.entity(object.object_id).remove::<WaitingFor>(),
+        };
+    }
+}
+
 #[derive(Debug)]
 enum TurnTowards {
     /// Turn towards the target endpoint.
@@ -206,6 +362,7 @@ enum TurnTowards {
 /// - Respect reversal of the target speed, i.e. if the target speed is negative,
 ///   all speeds and headings interpreted regarding the object should be negated.
 ///   (This process is only relevant for initial reading and final writing)
+/// - Do not exceed `following_limit` when taxiing behind other objects on the same segment.
 fn maintain_dir_for_object(
     time: &Time<time::Virtual>,
     object: &mut Object,
@@ -214,6 +371,7 @@ fn maintain_dir_for_object(
     limits: &Limits,
     [start_endpoint, target_endpoint]: [Position<Vec2>; 2],
     segment: &ground::Segment,
+    following_limit: Option<Speed<f32>>,
 ) -> MaintainDirResult {
     let reversed = match ground.target_speed {
         object::OnGroundTargetSpeed::Exact(speed) => speed.is_negative(),
@@ -272,8 +430,10 @@ fn maintain_dir_for_object(
         * (1.0 - turn_towards_target_dir.cos())
         * limits.turn_rate.duration_per_radian();
 
-    // Direct heading from object to target endpoint.
-    let direct_heading = (target_endpoint - object.position.horizontal()).heading();
+    // Direct heading from object to target endpoint,
+    // or the segment heading if the object is exactly at the endpoint.
+    let direct_heading =
+        (target_endpoint - object.position.horizontal()).try_heading().unwrap_or(target_heading);
 
     let turn_towards = if object_to_line_ortho.magnitude_cmp() < NEGLIGIBLE_DEVIATION_LENGTH {
         // Do not overcorrect if the deviation from the centerline is negligible.
@@ -305,7 +465,9 @@ fn maintain_dir_for_object(
 
     let desired_heading = match turn_towards {
         TurnTowards::TargetEndpoint => target_heading,
-        TurnTowards::StartEndpoint => (start_endpoint - object.position.horizontal()).heading(),
+        TurnTowards::StartEndpoint => {
+            (start_endpoint - object.position.horizontal()).try_heading().unwrap_or(target_heading)
+        }
         TurnTowards::Centerline => {
             // If we are very close to the centerline,
             // we only want to turn to a point on the centerline
@@ -347,13 +509,14 @@ fn maintain_dir_for_object(
         false
     };
 
+    let follow = |speed: Speed<f32>| following_limit.map_or(speed, |limit| speed.min(limit));
     let new_speed = match ground.target_speed {
         _ if should_brake => {
-            limited_taxi_speed(reversed, MIN_POSITIVE_SPEED, current_speed, limits, time)
+            limited_taxi_speed(reversed, follow(MIN_POSITIVE_SPEED), current_speed, limits, time)
         }
 
         object::OnGroundTargetSpeed::Exact(target_speed) => {
-            limited_taxi_speed(reversed, target_speed.abs(), current_speed, limits, time)
+            limited_taxi_speed(reversed, follow(target_speed.abs()), current_speed, limits, time)
         }
         object::OnGroundTargetSpeed::TakeoffRoll => {
             let speed_change = limits.accel * time.delta();
@@ -365,9 +528,6 @@ fn maintain_dir_for_object(
     object.ground_speed = desired_velocity.horizontally();
     taxi_status.heading = if reversed { new_heading.opposite() } else { new_heading };
 
-    // TODO check for other objects on the segment.
-    // Control speed such that the braking distance is shorter than the separation between objects.
-
     MaintainDirResult::Ok
 }
 
@@ -512,7 +672,10 @@ pub enum TargetResolution {
 struct TargetPathParams<'w, 's> {
     segment_query:      Query<'w, 's, &'static ground::Segment>,
     endpoint_query:     Query<'w, 's, &'static ground::Endpoint>,
+    runway_of_query:    Query<'w, 's, &'static ground::SegmentOfRunway>,
     resolve_msg_writer: MessageWriter<'w, TargetResolutionMessage>,
+    hold_short_writer:  MessageWriter<'w, crossing::HoldShortMessage>,
+    incursion_writer:   MessageWriter<'w, crossing::IncursionMessage>,
 }
 
 /// An event sent when the target resolution of an object changes.
@@ -575,7 +738,7 @@ impl TargetPathParams<'_, '_> {
                 None // no resolution from the taxi plugin
             }
             TargetAction::Taxi { ref options } => {
-                self.action_taxi(object, limits, ground, taxi_status, options)
+                self.action_taxi(object_id, object, limits, ground, taxi_status, options)
             }
             TargetAction::Hold { kind } => {
                 self.action_hold_short(object, limits, ground, taxi_status, kind)
@@ -592,9 +755,13 @@ impl TargetPathParams<'_, '_> {
     /// Attempt to turn to one of the options,
     /// or hold before the end of the current segment if all are currently unavailable.
     ///
+    /// Holds short instead if the next endpoint enters a runway
+    /// that the object is not cleared to enter.
+    ///
     /// `segment_options` must be a slice of segment entities.
     fn action_taxi(
-        &self,
+        &mut self,
+        object_id: Entity,
         object: &Object,
         limits: &Limits,
         ground: &mut object::OnGround,
@@ -609,6 +776,24 @@ impl TargetPathParams<'_, '_> {
         let current_segment = self.segment_query.log_get(ground.segment)?;
 
         let intersection_endpoint = current_segment.by_direction(ground.direction).1;
+        let entered_runway = self.runway_entered_at(ground.segment, intersection_endpoint);
+        if let Some(runway) = entered_runway
+            && !ground.crossing.permits(runway)
+        {
+            if !ground.crossing.holding_runway().is_some_and(|holding| runway.contains(&holding)) {
+                ground.crossing = object::RunwayCrossing::Holding { runway: runway[0] };
+                self.hold_short_writer
+                    .write(crossing::HoldShortMessage { object: object_id, runway: runway[0] });
+            }
+            self.hold_before_endpoint(
+                object,
+                limits,
+                ground,
+                self.endpoint_query.log_get(intersection_endpoint)?,
+            );
+            return None;
+        }
+
         for (option_index, &target_segment) in segment_options.iter().enumerate() {
             match self.turn_to_segment(
                 object,
@@ -624,7 +809,10 @@ impl TargetPathParams<'_, '_> {
                     // no need to fall through to the next target yet.
                     return None;
                 }
-                TurnResult::Completed => return Some(TargetResolution::Completed(option_index)),
+                TurnResult::Completed => {
+                    self.update_crossing(object_id, ground, entered_runway);
+                    return Some(TargetResolution::Completed(option_index));
+                }
             }
         }
 
@@ -642,6 +830,49 @@ impl TargetPathParams<'_, '_> {
         }
     }
 
+    /// Returns the runway pair entered when passing through `endpoint` from `from_segment`.
+    ///
+    /// Objects already on a runway are not considered to enter another runway,
+    /// so that landing and departing objects do not hold at runway intersections.
+    fn runway_entered_at(&self, from_segment: Entity, endpoint: Entity) -> Option<[Entity; 2]> {
+        if self.runway_of_query.contains(from_segment) {
+            return None;
+        }
+
+        let endpoint = self.endpoint_query.log_get(endpoint)?;
+        endpoint
+            .adjacency
+            .iter()
+            .find_map(|&segment| self.runway_of_query.get(segment).ok())
+            .map(|runway| runway.0)
+    }
+
+    /// Updates the crossing state after the object has turned onto a new segment.
+    ///
+    /// `entered_runway` is the runway pair adjacent to the endpoint just passed through,
+    /// if it was entered from the previous segment.
+    fn update_crossing(
+        &mut self,
+        object_id: Entity,
+        ground: &mut object::OnGround,
+        entered_runway: Option<[Entity; 2]>,
+    ) {
+        if let Some(runway) = entered_runway {
+            if !ground.crossing.permits(runway) {
+                self.incursion_writer
+                    .write(crossing::IncursionMessage { object: object_id, runway: runway[0] });
+            }
+            ground.crossing = object::RunwayCrossing::Crossing { runway: runway[0] };
+        } else if !self.runway_of_query.contains(ground.segment)
+            && matches!(
+                ground.crossing,
+                object::RunwayCrossing::Holding { .. } | object::RunwayCrossing::Crossing { .. }
+            )
+        {
+            ground.crossing = object::RunwayCrossing::None;
+        }
+    }
+
     /// Hold before the end of the current segment.
     fn action_hold_short(
         &self,
@@ -0,0 +1,57 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::world::World;
+use bevy::math::{Dir2, Vec2};
+use math::{Accel, Length, Position, Speed};
+
+use super::{Traffic, following_speed_limit};
+
+const BRAKING: Accel<f32> = Accel::from_knots_per_sec(3.0);
+const FOLLOWING_DISTANCE: Length<f32> = Length::from_meters(15.0);
+
+fn traffic(object: Entity, segment: Entity, meters: f32, knots: f32) -> Traffic {
+    Traffic {
+        object,
+        segment,
+        position: Position::new(Vec2::new(Length::from_meters(meters).0, 0.0)),
+        velocity: Speed::new(Vec2::new(Speed::from_knots(knots).0, 0.0)),
+        half_length: Length::from_meters(20.0),
+    }
+}
+
+#[test]
+fn test_following_limit_ignores_traffic_behind_and_elsewhere() {
+    let mut world = World::new();
+    let [follower, behind, elsewhere, segment, other_segment] =
+        [(); 5].map(|()| world.spawn_empty().id());
+
+    let all = [
+        traffic(follower, segment, 0.0, 10.0),
+        traffic(behind, segment, -100.0, 0.0),
+        traffic(elsewhere, other_segment, 100.0, 0.0),
+    ];
+    assert_eq!(following_speed_limit(&all[0], Dir2::X, &all, BRAKING, FOLLOWING_DISTANCE), None);
+}
+
+#[test]
+fn test_following_limit_stops_within_buffer() {
+    let mut world = World::new();
+    let [follower, leader, segment] = [(); 3].map(|()| world.spawn_empty().id());
+
+    // 50m apart, within the sum of half-lengths and the buffer.
+    let stopped = [traffic(follower, segment, 0.0, 10.0), traffic(leader, segment, 50.0, 0.0)];
+    let limit = following_speed_limit(&stopped[0], Dir2::X, &stopped, BRAKING, FOLLOWING_DISTANCE);
+    assert_eq!(limit, Some((leader, Speed::ZERO)));
+
+    // A moving leader can be followed at its own speed even without a gap.
+    let moving = [traffic(follower, segment, 0.0, 10.0), traffic(leader, segment, 50.0, 8.0)];
+    let (_, limit) =
+        following_speed_limit(&moving[0], Dir2::X, &moving, BRAKING, FOLLOWING_DISTANCE)
+            .expect("leader is ahead");
+    assert!((limit.into_knots() - 8.0).abs() < 0.01, "{limit:?}");
+
+    // The limit increases with the gap.
+    let far = [traffic(follower, segment, 0.0, 10.0), traffic(leader, segment, 200.0, 0.0)];
+    let (_, limit) = following_speed_limit(&far[0], Dir2::X, &far, BRAKING, FOLLOWING_DISTANCE)
+        .expect("leader is ahead");
+    assert!(limit > Speed::ZERO);
+}
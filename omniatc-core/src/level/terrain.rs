@@ -0,0 +1,43 @@
+//! Terrain altitude of the level.
+//!
+//! The [`Heightmap`] is loaded from the level environment
+//! and queried for the terrain cross-section along a route.
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::resource::Resource;
+use bevy::math::Vec2;
+use math::Position;
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) { app.init_resource::<Heightmap>(); }
+}
+
+/// Terrain altitude of the level.
+///
+/// Data points are stored as the raw AMSL altitude
+/// so that the heatmap can be interpolated directly.
+#[derive(Resource)]
+pub struct Heightmap(pub store::HeatMap2<f32>);
+
+impl Default for Heightmap {
+    fn default() -> Self {
+        Self(store::HeatMap2 {
+            aligned: store::AlignedHeatMap2::constant(Position::SEA_LEVEL.get()),
+            sparse:  store::SparseHeatMap2 { functions: Vec::new() },
+        })
+    }
+}
+
+impl Heightmap {
+    /// Returns the terrain altitude at a horizontal position.
+    #[must_use]
+    pub fn altitude_at(&self, position: Position<Vec2>) -> Position<f32> {
+        Position::new(self.0.resolve(position))
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::ecs::world::World;
+use math::Position;
+
+use crate::level::terrain;
+
+/// Loads the terrain heightmap from a store.
+pub fn spawn(world: &mut World, heightmap: &store::HeatMap2<Position<f32>>) {
+    let aligned = &heightmap.aligned;
+    world.insert_resource(terrain::Heightmap(store::HeatMap2 {
+        aligned: store::AlignedHeatMap2 {
+            initial_corner:  aligned.initial_corner,
+            end_corner:      aligned.end_corner,
+            major_direction: aligned.major_direction,
+            major_length:    aligned.major_length,
+            data:            aligned.data.map_linear(|altitude| altitude.get()),
+        },
+        sparse:  store::SparseHeatMap2 {
+            functions: heightmap
+                .sparse
+                .functions
+                .iter()
+                .map(|function| store::SparseFunction2 {
+                    shape:               function.shape.clone(),
+                    value:               function.value.get(),
+                    emergency_exception: function.emergency_exception,
+                })
+                .collect(),
+        },
+    }));
+}
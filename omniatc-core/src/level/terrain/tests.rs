@@ -0,0 +1,91 @@
+use bevy::ecs::world::World;
+use math::{Angle, Length, Position};
+
+use super::{Heightmap, loader};
+
+fn heightmap() -> Heightmap {
+    let mut world = World::new();
+    loader::spawn(
+        &mut world,
+        &store::HeatMap2 {
+            aligned: store::AlignedHeatMap2 {
+                initial_corner:  Position::from_origin_nm(0.0, 0.0),
+                end_corner:      Position::from_origin_nm(10.0, 10.0),
+                major_direction: store::AxisDirection::X,
+                major_length:    2,
+                data:            store::HeatMapData::Dense(vec![
+                    Position::from_amsl_feet(0.0),
+                    Position::from_amsl_feet(1000.0),
+                    Position::from_amsl_feet(0.0),
+                    Position::from_amsl_feet(1000.0),
+                ]),
+            },
+            sparse:  store::SparseHeatMap2 {
+                functions: vec![store::SparseFunction2 {
+                    shape:               store::Shape2d::Ellipse {
+                        center:       Position::from_origin_nm(2.0, 5.0),
+                        major_radius: Length::from_nm(1.0),
+                        minor_radius: Length::from_nm(0.5),
+                        major_dir:    Angle::ZERO,
+                    },
+                    value:               Position::from_amsl_feet(3000.0),
+                    emergency_exception: false,
+                }],
+            },
+        },
+    );
+    world.remove_resource::<Heightmap>().expect("inserted by loader")
+}
+
+#[test]
+fn test_aligned_interpolation() {
+    heightmap()
+        .altitude_at(Position::from_origin_nm(5.0, 8.0))
+        .assert_near(Position::from_amsl_feet(500.0), Length::from_feet(1.0))
+        .expect("bilinear interpolation between corners");
+}
+
+#[test]
+fn test_sparse_function_takes_maximum() {
+    let heightmap = heightmap();
+    heightmap
+        .altitude_at(Position::from_origin_nm(2.8, 5.0))
+        .assert_near(Position::from_amsl_feet(3000.0), Length::from_feet(1.0))
+        .expect("within the ellipse");
+    heightmap
+        .altitude_at(Position::from_origin_nm(2.0, 5.8))
+        .assert_near(Position::from_amsl_feet(200.0), Length::from_feet(1.0))
+        .expect("outside the minor radius of the ellipse");
+}
+
+#[test]
+fn test_quantized_matches_dense() {
+    let feet: Vec<f32> = (0..12u8).map(|i| f32::from(i) * 250.0).collect();
+    let aligned = |data| store::AlignedHeatMap2 {
+        initial_corner: Position::from_origin_nm(0.0, 0.0),
+        end_corner: Position::from_origin_nm(6.0, 4.0),
+        major_direction: store::AxisDirection::X,
+        major_length: 4,
+        data,
+    };
+
+    let dense = aligned(store::HeatMapData::Dense(feet.clone()));
+    let quantized = aligned(store::HeatMapData::quantize(&feet, 5));
+    assert!(quantized.validate());
+
+    let bytes = {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&quantized, &mut bytes).expect("serialize into memory");
+        bytes
+    };
+    let decoded: store::AlignedHeatMap2<f32> =
+        ciborium::from_reader(&bytes[..]).expect("deserialize from memory");
+
+    for (x, y) in [(0.0, 0.0), (1.3, 2.7), (5.9, 0.4), (-3.0, 2.0), (7.0, 9.0)] {
+        let position = Position::from_origin_nm(x, y);
+        let expected = dense.resolve(position);
+        for actual in [quantized.resolve(position), decoded.resolve(position)] {
+            assert!((actual - expected).abs() < 0.1, "{actual} != {expected} at ({x}, {y})");
+        }
+    }
+}
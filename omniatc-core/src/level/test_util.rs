@@ -0,0 +1,63 @@
+//! Shared fixtures for simulation tests.
+
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::time::{self, Time};
+use math::{Accel, AccelRate, AngularAccel, AngularSpeed, Length, Speed};
+use store::NavLimits;
+
+use super::SystemSets;
+
+/// Creates an app with ordered [`SystemSets`] and a manually advanced virtual clock.
+pub(crate) fn app() -> App {
+    let mut app = App::new();
+    SystemSets::configure_ordering(&mut app);
+    app.init_resource::<Time<time::Virtual>>();
+    app
+}
+
+/// Advances the virtual clock by `dt` and runs one update.
+pub(crate) fn advance(app: &mut App, dt: Duration) {
+    app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(dt);
+    app.update();
+}
+
+/// Navigation limits of a typical airliner.
+pub(crate) const NAV_LIMITS: NavLimits = NavLimits {
+    min_horiz_speed:   Speed::from_knots(120.),
+    max_yaw_speed:     AngularSpeed::from_degrees_per_sec(3.),
+    max_vert_accel:    Accel::from_fpm_per_sec(200.),
+    exp_climb:         store::ClimbProfile {
+        vert_rate: Speed::from_fpm(3000.),
+        accel:     Accel::from_knots_per_sec(0.2),
+        decel:     Accel::from_knots_per_sec(-1.8),
+    },
+    std_climb:         store::ClimbProfile {
+        vert_rate: Speed::from_fpm(1500.),
+        accel:     Accel::from_knots_per_sec(0.6),
+        decel:     Accel::from_knots_per_sec(-1.4),
+    },
+    level:             store::ClimbProfile {
+        vert_rate: Speed::from_fpm(0.),
+        accel:     Accel::from_knots_per_sec(1.),
+        decel:     Accel::from_knots_per_sec(-1.),
+    },
+    std_descent:       store::ClimbProfile {
+        vert_rate: Speed::from_fpm(-1500.),
+        accel:     Accel::from_knots_per_sec(1.4),
+        decel:     Accel::from_knots_per_sec(-0.6),
+    },
+    exp_descent:       store::ClimbProfile {
+        vert_rate: Speed::from_fpm(-3000.),
+        accel:     Accel::from_knots_per_sec(1.8),
+        decel:     Accel::from_knots_per_sec(-0.2),
+    },
+    weight:            1e5,
+    accel_change_rate: AccelRate::from_knots_per_sec2(0.3),
+    drag_coef:         3. / 500. / 500.,
+    max_yaw_accel:     AngularAccel::from_degrees_per_sec2(1.),
+    takeoff_speed:     Speed::from_knots(150.),
+    short_final_dist:  Length::from_nm(4.),
+    short_final_speed: Speed::from_knots(150.),
+};
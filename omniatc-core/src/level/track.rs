@@ -0,0 +1,103 @@
+//! History of flown tracks, exportable to geographic formats.
+//!
+//! The position of every object is sampled at a fixed virtual time interval
+//! for the whole session, including objects that have since been despawned.
+//! Tracks are projected to geodetic coordinates with [`GeoPoint::from_map`]
+//! around the [`store::Level::geo_origin`] of the loaded map.
+
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::entity::{Entity, EntityHashMap};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Query, Res, ResMut};
+use bevy::math::Vec3;
+use bevy::time::{self, Time};
+use math::{GeoPoint, Position};
+
+use super::{SystemSets, object};
+
+pub mod export;
+pub mod loader;
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug;
+
+impl Plugin for Plug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Log>();
+        app.add_systems(app::Update, record_system.in_set(SystemSets::Statistics));
+    }
+}
+
+/// Virtual time between two samples of a track.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Tracks flown in the current session.
+#[derive(Resource, Default)]
+pub struct Log {
+    /// Geodetic position of the map origin, if known.
+    pub origin:  Option<GeoPoint>,
+    /// All recorded tracks, in the order the objects were first seen.
+    pub tracks:  Vec<Track>,
+    /// Virtual time at which the map was loaded.
+    start:       Duration,
+    /// Index in `tracks` of each object seen so far.
+    index:       EntityHashMap<usize>,
+    /// Virtual time at which the next sample is taken.
+    next_sample: Duration,
+}
+
+/// The sampled path of a single object.
+pub struct Track {
+    /// Display name of the object.
+    pub name:   String,
+    /// Samples in chronological order.
+    pub points: Vec<TrackPoint>,
+}
+
+/// A sampled position of an object.
+#[derive(Clone, Copy)]
+pub struct TrackPoint {
+    /// Virtual time elapsed since the map was loaded.
+    pub time:     Duration,
+    /// Position relative to the map origin at mean sea level.
+    pub position: Position<Vec3>,
+}
+
+impl Log {
+    /// Clears the log for a newly loaded map.
+    pub fn reset(&mut self, origin: Option<GeoPoint>, now: Duration) {
+        *self = Self { origin, start: now, next_sample: now, ..Default::default() };
+    }
+
+    /// Appends a sample of an object.
+    pub fn push(&mut self, entity: Entity, name: &str, point: TrackPoint) {
+        let tracks = &mut self.tracks;
+        let &mut index = self.index.entry(entity).or_insert_with(|| {
+            tracks.push(Track { name: name.to_owned(), points: Vec::new() });
+            tracks.len() - 1
+        });
+        tracks[index].points.push(point);
+    }
+}
+
+fn record_system(
+    time: Res<Time<time::Virtual>>,
+    mut log: ResMut<Log>,
+    object_query: Query<(Entity, &object::Display, &object::Object)>,
+) {
+    let now = time.elapsed();
+    if now < log.next_sample {
+        return;
+    }
+    log.next_sample = now + SAMPLE_INTERVAL;
+
+    let elapsed = now.saturating_sub(log.start);
+    for (entity, display, object) in &object_query {
+        log.push(entity, &display.name, TrackPoint { time: elapsed, position: object.position });
+    }
+}
@@ -0,0 +1,113 @@
+//! Serialization of track logs to KML and `GeoJSON`.
+
+use std::fmt::Write;
+
+use math::GeoPoint;
+
+use super::{Log, Track, TrackPoint};
+
+/// A geographic file format for exported tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter, strum::Display)]
+pub enum Format {
+    /// Keyhole Markup Language, as used by Google Earth.
+    #[strum(to_string = "KML")]
+    Kml,
+    /// `GeoJSON` feature collection.
+    #[strum(to_string = "GeoJSON")]
+    GeoJson,
+}
+
+impl Format {
+    /// File extension of the format, without the leading dot.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Kml => "kml",
+            Self::GeoJson => "geojson",
+        }
+    }
+
+    /// Serializes the tracks of `log` with the map origin at `origin`.
+    #[must_use]
+    pub fn write(self, log: &Log, origin: GeoPoint) -> String {
+        match self {
+            Self::Kml => to_kml(log, origin),
+            Self::GeoJson => to_geojson(log, origin),
+        }
+    }
+}
+
+/// Returns the longitude, latitude and altitude in meters of a sampled position.
+fn coordinates(point: &TrackPoint, origin: GeoPoint) -> (f64, f64, f32) {
+    let geo = GeoPoint::from_map(point.position.horizontal(), origin);
+    (geo.lon, geo.lat, point.position.altitude().amsl().into_meters())
+}
+
+/// Tracks with at least two points, since a single point cannot form a line.
+fn lines(log: &Log) -> impl Iterator<Item = &Track> {
+    log.tracks.iter().filter(|track| track.points.len() >= 2)
+}
+
+/// Serializes tracks as a KML document with one placemark per object.
+#[must_use]
+pub fn to_kml(log: &Log, origin: GeoPoint) -> String {
+    let mut output = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<kml \
+         xmlns=\"http://www.opengis.net/kml/2.2\">\n<Document>\n",
+    );
+    for track in lines(log) {
+        output.push_str("<Placemark>\n<name>");
+        escape_xml(&mut output, &track.name);
+        output.push_str(
+            "</name>\n<LineString>\n<altitudeMode>absolute</altitudeMode>\n<coordinates>\n",
+        );
+        for point in &track.points {
+            let (lon, lat, alt) = coordinates(point, origin);
+            writeln!(output, "{lon:.6},{lat:.6},{alt:.0}").expect("write to string");
+        }
+        output.push_str("</coordinates>\n</LineString>\n</Placemark>\n");
+    }
+    output.push_str("</Document>\n</kml>\n");
+    output
+}
+
+fn escape_xml(output: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            _ => output.push(ch),
+        }
+    }
+}
+
+/// Serializes tracks as a `GeoJSON` feature collection with one line string per object.
+///
+/// Each feature has the properties `name`, `start_time` and `end_time`,
+/// the latter two being seconds since the map was loaded.
+#[must_use]
+pub fn to_geojson(log: &Log, origin: GeoPoint) -> String {
+    let features: Vec<_> = lines(log)
+        .map(|track| {
+            let coordinates: Vec<_> = track
+                .points
+                .iter()
+                .map(|point| {
+                    let (lon, lat, alt) = coordinates(point, origin);
+                    serde_json::json!([lon, lat, alt])
+                })
+                .collect();
+            let start = track.points.first().map(|point| point.time.as_secs_f64());
+            let end = track.points.last().map(|point| point.time.as_secs_f64());
+            serde_json::json!({
+                "type": "Feature",
+                "properties": { "name": track.name, "start_time": start, "end_time": end },
+                "geometry": { "type": "LineString", "coordinates": coordinates },
+            })
+        })
+        .collect();
+    serde_json::json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
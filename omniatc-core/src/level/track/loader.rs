@@ -0,0 +1,11 @@
+use bevy::ecs::world::World;
+use bevy::time::{self, Time};
+use math::GeoPoint;
+
+use crate::level::track;
+
+/// Starts recording tracks for a newly loaded map.
+pub fn spawn(world: &mut World, geo_origin: Option<GeoPoint>) {
+    let now = world.resource::<Time<time::Virtual>>().elapsed();
+    world.resource_mut::<track::Log>().reset(geo_origin, now);
+}
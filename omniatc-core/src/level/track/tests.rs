@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use bevy::math::Vec3;
+use bevy::time::{self, Time};
+use math::{GeoPoint, Position};
+
+use super::{Log, Plug, SAMPLE_INTERVAL, TrackPoint, export};
+use crate::level::{object, test_util};
+
+const ORIGIN: GeoPoint = GeoPoint { lat: 0., lon: 0. };
+
+fn object(x_nm: f32, y_nm: f32) -> object::Object {
+    object::Object {
+        position:     Position::from_origin_nm(x_nm, y_nm)
+            .with_altitude(Position::from_amsl_feet(3000.)),
+        ground_speed: math::Speed::ZERO,
+    }
+}
+
+#[test]
+fn samples_at_interval() {
+    let mut app = test_util::app();
+    app.add_plugins(Plug);
+    let entity =
+        app.world_mut().spawn((object::Display { name: "ABC123".into() }, object(0., 0.))).id();
+
+    for _ in 0..3 {
+        app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(4));
+        app.update();
+    }
+    app.world_mut().get_mut::<object::Object>(entity).expect("object").position =
+        object(1., 0.).position;
+    for _ in 0..2 {
+        app.world_mut().resource_mut::<Time<time::Virtual>>().advance_by(Duration::from_secs(4));
+        app.update();
+    }
+
+    let log = app.world().resource::<Log>();
+    let [track] = log.tracks.as_slice() else { panic!("expected one track") };
+    assert_eq!(track.name, "ABC123");
+    let times: Vec<_> = track.points.iter().map(|point| point.time).collect();
+    // The next sample is taken at the first update at least `SAMPLE_INTERVAL` after the previous one.
+    assert_eq!(SAMPLE_INTERVAL, Duration::from_secs(10));
+    assert_eq!(times, [Duration::from_secs(4), Duration::from_secs(16)]);
+    assert!((track.points[1].position.horizontal().get().x - 1.).abs() < 1e-6);
+}
+
+fn sample_log() -> Log {
+    let mut log = Log::default();
+    let entity = bevy::ecs::entity::Entity::from_raw_u32(1).expect("valid entity index");
+    for (secs, y_nm) in [(0, 0.), (10, 6.)] {
+        log.push(
+            entity,
+            "A&B",
+            TrackPoint { time: Duration::from_secs(secs), position: object(0., y_nm).position },
+        );
+    }
+    log
+}
+
+#[test]
+fn geojson_line_strings() {
+    let json: serde_json::Value =
+        serde_json::from_str(&export::to_geojson(&sample_log(), ORIGIN)).expect("valid json");
+
+    let feature = &json["features"][0];
+    assert_eq!(feature["properties"]["name"], "A&B");
+    assert_eq!(feature["properties"]["end_time"], 10.);
+    let end = &feature["geometry"]["coordinates"][1];
+    // 6 nm north is a tenth of a degree of latitude.
+    assert!((end[1].as_f64().expect("latitude") - 0.1).abs() < 1e-6);
+    assert!((end[2].as_f64().expect("altitude") - 914.4).abs() < 0.1);
+}
+
+#[test]
+fn kml_escapes_names() {
+    let kml = export::to_kml(&sample_log(), ORIGIN);
+    assert!(kml.contains("<name>A&amp;B</name>"));
+    assert!(kml.contains("0.000000,0.100000,914"));
+}
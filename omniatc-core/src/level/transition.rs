@@ -0,0 +1,220 @@
+//! Transition altitude and transition level.
+//!
+//! Altitudes below the [transition level](Transition::level) are referenced to QNH
+//! and expressed in feet above mean sea level.
+//! Altitudes at or above the transition level are referenced to standard pressure
+//! and expressed as flight levels, i.e. pressure altitude in hundreds of feet.
+//!
+//! The transition level is the lowest flight level
+//! at least [`Conf::min_layer`] above the transition altitude,
+//! so it rises when the QNH drops.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::ResMut;
+use bevy::ecs::world::World;
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{ISA_SEA_LEVEL_PRESSURE, ISA_SEA_LEVEL_TEMPERATURE, Length, Position, Pressure, Temp};
+
+use super::{SystemSets, weather};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+/// Flight levels are multiples of this pressure altitude.
+const FLIGHT_LEVEL_UNIT: Length<f32> = Length::from_feet(100.0);
+
+/// Transition levels are multiples of this pressure altitude.
+const TRANSITION_LEVEL_STEP: Length<f32> = Length::from_feet(1000.0);
+
+/// Tolerance below a flight level within which an altitude is still considered at that level.
+const LEVEL_TOLERANCE: Length<f32> = Length::from_feet(50.0);
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:transition");
+        app.init_resource::<Transition>();
+        app.add_systems(app::Update, update_system.in_set(SystemSets::PrepareEnviron));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Minimum height of the transition layer between the transition altitude
+    /// and the transition level.
+    #[config(default = Length::from_feet(1000.0), min = Length::ZERO, max = Length::from_feet(5000.0))]
+    pub min_layer: Length<f32>,
+}
+
+/// The transition altitude and the current transition level.
+#[derive(Resource)]
+pub struct Transition {
+    /// Transition altitude, referenced to QNH.
+    pub altitude: Position<f32>,
+    /// Transition level as a pressure altitude.
+    pub level:    Position<f32>,
+    /// Current QNH.
+    pub qnh:      Pressure,
+    /// Current sea level temperature.
+    pub sea_temp: Temp,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self::new(
+            Position::from_amsl_feet(18000.0),
+            ISA_SEA_LEVEL_PRESSURE,
+            ISA_SEA_LEVEL_TEMPERATURE,
+            Length::from_feet(1000.0),
+        )
+    }
+}
+
+impl Transition {
+    /// Derives the transition level from the transition altitude and the QNH.
+    #[must_use]
+    pub fn new(
+        altitude: Position<f32>,
+        qnh: Pressure,
+        sea_temp: Temp,
+        min_layer: Length<f32>,
+    ) -> Self {
+        let mut this = Self { altitude, level: altitude, qnh, sea_temp };
+        this.level = this.derive_level(min_layer);
+        this
+    }
+
+    fn derive_level(&self, min_layer: Length<f32>) -> Position<f32> {
+        let lowest = self.pressure_altitude(self.altitude + min_layer);
+        let steps = ((lowest - Position::SEA_LEVEL) / TRANSITION_LEVEL_STEP).ceil();
+        Position::SEA_LEVEL + TRANSITION_LEVEL_STEP * steps
+    }
+
+    /// Converts a true altitude to the pressure altitude indicated under standard pressure.
+    #[must_use]
+    pub fn pressure_altitude(&self, altitude: Position<f32>) -> Position<f32> {
+        math::compute_barometric(altitude, self.qnh, self.sea_temp).pressure_altitude
+    }
+
+    /// Converts a pressure altitude to the true altitude at which it is indicated.
+    #[must_use]
+    pub fn true_altitude(&self, pressure_altitude: Position<f32>) -> Position<f32> {
+        // The pressure altitude is almost a constant offset from the true altitude,
+        // so a few fixed point iterations converge quickly.
+        let mut altitude = pressure_altitude;
+        for _ in 0..3 {
+            altitude = altitude - (self.pressure_altitude(altitude) - pressure_altitude);
+        }
+        altitude
+    }
+
+    /// Whether the altitude is expressed as a flight level.
+    #[must_use]
+    pub fn is_flight_level(&self, altitude: Position<f32>) -> bool {
+        self.pressure_altitude(altitude) >= self.level - LEVEL_TOLERANCE
+    }
+
+    /// Expresses a true altitude in the reference used at that altitude.
+    #[must_use]
+    pub fn reading(&self, altitude: Position<f32>) -> Reading {
+        if self.is_flight_level(altitude) {
+            let level =
+                (self.pressure_altitude(altitude) - Position::SEA_LEVEL) / FLIGHT_LEVEL_UNIT;
+            Reading::FlightLevel(level.round())
+        } else {
+            Reading::Altitude(altitude.amsl().into_feet())
+        }
+    }
+
+    /// Converts a flight level to the true altitude at which it is flown.
+    #[must_use]
+    pub fn flight_level_altitude(&self, level: f32) -> Position<f32> {
+        self.true_altitude(Position::SEA_LEVEL + FLIGHT_LEVEL_UNIT * level)
+    }
+
+    /// Converts a reading back to the true altitude it expresses.
+    #[must_use]
+    pub fn altitude(&self, reading: Reading) -> Position<f32> {
+        match reading {
+            Reading::Altitude(feet) => Position::from_amsl_feet(feet),
+            Reading::FlightLevel(level) => self.flight_level_altitude(level),
+        }
+    }
+}
+
+/// An altitude as displayed to the player.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reading {
+    /// Feet above mean sea level, referenced to QNH.
+    Altitude(f32),
+    /// Flight level, in hundreds of feet of pressure altitude.
+    FlightLevel(f32),
+}
+
+impl Reading {
+    /// Formats the reading in hundreds of feet for compact displays, e.g. `050` or `F350`.
+    #[must_use]
+    pub fn hundreds(self) -> String {
+        match self {
+            Self::Altitude(feet) => format!("{:03.0}", feet / 100.0),
+            Self::FlightLevel(level) => format!("F{level:03.0}"),
+        }
+    }
+}
+
+/// Formats as `FL350` or `5000 ft`, or `5000 feet` with the alternate flag.
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Altitude(feet) if f.alternate() => write!(f, "{feet:.0} feet"),
+            Self::Altitude(feet) => write!(f, "{feet:.0} ft"),
+            Self::FlightLevel(level) => write!(f, "FL{level:03.0}"),
+        }
+    }
+}
+
+/// Expresses `altitude` with the [`Transition`] of the world,
+/// or in feet if the world has no transition.
+#[must_use]
+pub fn reading(world: &World, altitude: Position<f32>) -> Reading {
+    world.get_resource::<Transition>().map_or_else(
+        || Reading::Altitude(altitude.amsl().into_feet()),
+        |transition| transition.reading(altitude),
+    )
+}
+
+fn update_system(
+    conf: ReadConfig<Conf>,
+    locator: weather::Locator,
+    mut transition: ResMut<Transition>,
+) {
+    let conf = conf.read();
+
+    let weather = locator.get(Position::ORIGIN);
+    let updated = Transition::new(
+        transition.altitude,
+        weather.sea_pressure,
+        weather.sea_temp,
+        conf.min_layer,
+    );
+    if updated.level != transition.level
+        || updated.qnh != transition.qnh
+        || updated.sea_temp != transition.sea_temp
+    {
+        *transition = updated;
+    }
+}
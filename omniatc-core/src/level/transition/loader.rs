@@ -0,0 +1,17 @@
+use bevy::ecs::world::World;
+use math::{ISA_SEA_LEVEL_PRESSURE, ISA_SEA_LEVEL_TEMPERATURE, Length, Position};
+
+use crate::level::transition::Transition;
+
+/// Configures the transition altitude from a store.
+///
+/// The transition level is derived from standard pressure
+/// until the weather is sampled in the next update.
+pub fn spawn(world: &mut World, altitude: Position<f32>) {
+    world.insert_resource(Transition::new(
+        altitude,
+        ISA_SEA_LEVEL_PRESSURE,
+        ISA_SEA_LEVEL_TEMPERATURE,
+        Length::from_feet(1000.0),
+    ));
+}
@@ -0,0 +1,47 @@
+use math::{ISA_SEA_LEVEL_TEMPERATURE, Length, Position, Pressure};
+
+use super::{Reading, Transition};
+
+fn transition(qnh_hpa: f32) -> Transition {
+    Transition::new(
+        Position::from_amsl_feet(5000.0),
+        Pressure::from_hpa(qnh_hpa),
+        ISA_SEA_LEVEL_TEMPERATURE,
+        Length::from_feet(1000.0),
+    )
+}
+
+fn assert_level(qnh_hpa: f32, feet: f32) {
+    let level = transition(qnh_hpa).level.amsl().into_feet();
+    assert!((level - feet).abs() < 1.0, "level {level} at QNH {qnh_hpa} should be {feet}");
+}
+
+#[test]
+fn test_transition_level_rises_with_low_qnh() {
+    assert_level(1013.25, 6000.0);
+    assert_level(1030.0, 6000.0);
+    assert_level(990.0, 7000.0);
+}
+
+#[test]
+fn test_reading_switches_at_transition_level() {
+    let transition = transition(990.0);
+
+    assert_eq!(transition.reading(Position::from_amsl_feet(5000.0)), Reading::Altitude(5000.0));
+    let fl70 = transition.flight_level_altitude(70.0);
+    assert!(fl70 < Position::from_amsl_feet(7000.0));
+    assert_eq!(transition.reading(fl70), Reading::FlightLevel(70.0));
+    assert_eq!(
+        transition.reading(transition.flight_level_altitude(350.0)),
+        Reading::FlightLevel(350.0)
+    );
+}
+
+#[test]
+fn test_reading_format() {
+    assert_eq!(Reading::Altitude(5000.0).to_string(), "5000 ft");
+    assert_eq!(format!("{:#}", Reading::Altitude(5000.0)), "5000 feet");
+    assert_eq!(Reading::FlightLevel(70.0).to_string(), "FL070");
+    assert_eq!(Reading::Altitude(5000.0).hundreds(), "050");
+    assert_eq!(Reading::FlightLevel(350.0).hundreds(), "F350");
+}
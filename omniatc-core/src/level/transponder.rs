@@ -0,0 +1,123 @@
+//! Transponder failures.
+//!
+//! Airborne objects occasionally suffer a transponder failure,
+//! after which they are only visible as [`Failed`] primary targets
+//! without altitude or callsign readouts.
+//! The controller may instruct the pilot to recycle the transponder,
+//! which restores it after a delay.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use rand::Rng;
+
+use super::SystemSets;
+use crate::level::{drift, message, object, session};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:transponder");
+        app.init_resource::<session::Seed>();
+        app.add_systems(app::Update, failure_system.in_set(SystemSets::Aviate));
+        app.add_systems(app::Update, recycle_system.in_set(SystemSets::Communicate));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Expected number of transponder failures per hour an object spends airborne.
+    #[config(default = 0.05, min = 0.0, max = 10.0)]
+    pub failure_rate:  f32,
+    /// Duration after the recycle instruction until the transponder is restored.
+    #[config(default = Duration::from_secs(30), min = Duration::ZERO, max = Duration::from_mins(5))]
+    pub recycle_delay: Duration,
+}
+
+/// Marks an object whose transponder has failed.
+///
+/// The object is displayed as a primary target without altitude or callsign.
+#[derive(Component)]
+pub struct Failed;
+
+/// Marks an object recycling its failed transponder.
+#[derive(Component, Default)]
+pub struct Recycling {
+    /// Unpaused time elapsed since the transponder was switched off.
+    pub elapsed: Duration,
+}
+
+fn failure_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    object_query: Query<Entity, (With<object::Airborne>, Without<Failed>, Without<drift::Drifter>)>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    if time.is_paused() {
+        return;
+    }
+    let conf = conf.read();
+    let probability = f64::from(conf.failure_rate) * time.delta().as_secs_f64() / 3600.0;
+    if probability <= 0.0 {
+        return;
+    }
+    let rng = rng.get(session::STREAM_TRANSPONDER);
+
+    for object in object_query {
+        if !rng.random_bool(probability.min(1.0)) {
+            continue;
+        }
+
+        commands.entity(object).insert(Failed);
+        commands.queue(message::SendExpiring {
+            source:   object,
+            content:  "Transponder failure, primary target only".into(),
+            class:    message::Class::AnomalyInfo,
+            duration: Duration::from_secs(20),
+        });
+    }
+}
+
+fn recycle_system(
+    time: Res<Time<time::Virtual>>,
+    conf: ReadConfig<Conf>,
+    object_query: Query<(Entity, &mut Recycling)>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (object, mut recycling) in object_query {
+        recycling.elapsed += time.delta();
+        if recycling.elapsed < conf.recycle_delay {
+            continue;
+        }
+
+        commands.entity(object).remove::<(Failed, Recycling)>();
+        commands.queue(message::SendExpiring {
+            source:   object,
+            content:  "Transponder restored".into(),
+            class:    message::Class::AnomalyInfo,
+            duration: Duration::from_secs(10),
+        });
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position, Speed};
+
+use super::{Failed, Recycling};
+use crate::level::instr::{self, Kind as _};
+use crate::level::object::{self, Object};
+use crate::level::test_util::{self, advance};
+use crate::level::{message, transponder};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((message::Plug, transponder::Plug::<()>::default()));
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App) -> Entity {
+    let altitude = Position::from_amsl_feet(5000.0);
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::ORIGIN.with_altitude(altitude),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id()
+}
+
+fn cycle(app: &mut App, object: Entity) {
+    instr::CycleTransponder.process(&mut app.world_mut().commands().entity(object));
+    app.world_mut().flush();
+}
+
+#[test]
+fn test_failure_after_long_exposure() {
+    let mut app = base_app();
+    let object = spawn_object(&mut app);
+
+    // The failure probability saturates over a long enough exposure.
+    advance(&mut app, Duration::from_hours(100));
+
+    assert!(app.world().get::<Failed>(object).is_some());
+}
+
+#[test]
+fn test_recycle_restores_after_delay() {
+    let mut app = base_app();
+    let object = spawn_object(&mut app);
+    app.world_mut().entity_mut(object).insert(Failed);
+
+    cycle(&mut app, object);
+    assert!(app.world().get::<Recycling>(object).is_some());
+
+    advance(&mut app, Duration::from_secs(10));
+    assert!(app.world().get::<Failed>(object).is_some(), "transponder restored too early");
+
+    advance(&mut app, Duration::from_secs(25));
+    assert!(app.world().get::<Failed>(object).is_none());
+    assert!(app.world().get::<Recycling>(object).is_none());
+}
+
+#[test]
+fn test_recycle_without_failure_is_noop() {
+    let mut app = base_app();
+    let object = spawn_object(&mut app);
+
+    cycle(&mut app, object);
+
+    assert!(app.world().get::<Recycling>(object).is_none());
+}
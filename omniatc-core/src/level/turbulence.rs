@@ -0,0 +1,264 @@
+//! Turbulent air in environment volumes.
+//!
+//! Airborne objects entering a turbulence [`Area`] of at least moderate intensity
+//! report the encounter with a PIREP and reduce their speed
+//! by the factor configured for the intensity.
+//! While in the area, they refuse instructions to increase speed
+//! and occasionally request an altitude change to leave the area.
+//! The speed before the encounter is resumed after leaving the area.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Command, Commands, Query, Res};
+use bevy::ecs::world::EntityWorldMut;
+use bevy::math::{Vec2, Vec3};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::{Length, Position, Speed};
+use rand::Rng;
+pub use store::TurbulenceIntensity as Intensity;
+
+use super::SystemSets;
+use crate::QueryTryLog;
+use crate::level::object::{self, Object};
+use crate::level::{message, nav, pilot, pilot_request, session};
+
+pub mod loader;
+#[cfg(test)]
+mod tests;
+
+/// Duration for which PIREPs and refusals are displayed.
+const MESSAGE_DURATION: Duration = Duration::from_secs(20);
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:turbulence");
+        app.init_resource::<session::Seed>();
+        app.add_systems(app::Update, request_system.in_set(SystemSets::Communicate));
+        app.add_systems(app::Update, encounter_system.in_set(SystemSets::Action));
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Factor applied to the speed of objects entering moderate turbulence.
+    #[config(default = 0.9, min = 0.5, max = 1.0)]
+    pub moderate_speed_factor: f32,
+    /// Factor applied to the speed of objects entering severe turbulence.
+    #[config(default = 0.8, min = 0.5, max = 1.0)]
+    pub severe_speed_factor:   f32,
+    /// Mean time between altitude change requests of an object in turbulence.
+    #[config(default = Duration::from_mins(2))]
+    pub request_interval:      Duration,
+    /// Margin beyond the boundary of the area to request when leaving it vertically.
+    #[config(default = Length::from_feet(1000.0), min = Length::ZERO, max = Length::from_feet(5000.0))]
+    pub request_margin:        Length<f32>,
+}
+
+/// A cylindrical volume of turbulent air.
+#[derive(Component)]
+pub struct Area {
+    pub center:    Position<Vec2>,
+    pub radius:    Length<f32>,
+    pub bottom:    Position<f32>,
+    pub top:       Position<f32>,
+    pub intensity: Intensity,
+}
+
+impl Area {
+    /// Whether the position is within the area.
+    #[must_use]
+    pub fn contains(&self, position: Position<Vec3>) -> bool {
+        (self.bottom..=self.top).contains(&position.altitude())
+            && self.center.distance_cmp(position.horizontal()) < self.radius
+    }
+}
+
+/// An object currently in turbulence of at least moderate intensity.
+#[derive(Component)]
+pub struct Encounter {
+    /// The most intense area containing the object.
+    pub area:         Entity,
+    pub intensity:    Intensity,
+    /// The object does not fly faster than this speed while in the area.
+    pub max_speed:    Speed<f32>,
+    /// The target speed before entering the area, resumed after leaving it.
+    pub resume_speed: Speed<f32>,
+}
+
+/// Lowercase description of the intensity for messages.
+#[must_use]
+pub fn describe(intensity: Intensity) -> &'static str {
+    match intensity {
+        Intensity::Light => "light",
+        Intensity::Moderate => "moderate",
+        Intensity::Severe => "severe",
+    }
+}
+
+fn encounter_system(
+    conf: ReadConfig<Conf>,
+    area_query: Query<(Entity, &Area)>,
+    object_query: Query<
+        (Entity, &Object, &mut nav::VelocityTarget, Option<&Encounter>),
+        With<object::Airborne>,
+    >,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    for (object_entity, object, mut target, encounter) in object_query {
+        let area = area_query
+            .iter()
+            .filter(|(_, area)| area.intensity >= Intensity::Moderate)
+            .filter(|(_, area)| area.contains(object.position))
+            .max_by_key(|(_, area)| area.intensity);
+
+        let max_speed = match (area, encounter) {
+            (None, None) => continue,
+            (None, Some(encounter)) => {
+                // Do not speed up if the controller has reduced speed further in the meantime.
+                if target.horiz_speed >= encounter.max_speed {
+                    target.horiz_speed = encounter.resume_speed;
+                }
+                commands.entity(object_entity).remove::<Encounter>();
+                commands.queue(message::SendExpiring {
+                    source:   object_entity,
+                    content:  "Clear of turbulence".into(),
+                    class:    message::Class::VerboseInfo,
+                    duration: MESSAGE_DURATION,
+                });
+                continue;
+            }
+            (Some((_, area)), Some(encounter)) if encounter.intensity >= area.intensity => {
+                encounter.max_speed
+            }
+            (Some((area_entity, area)), encounter) => {
+                let resume_speed = encounter.map_or(target.horiz_speed, |e| e.resume_speed);
+                let factor = match area.intensity {
+                    Intensity::Light => 1.0,
+                    Intensity::Moderate => conf.moderate_speed_factor,
+                    Intensity::Severe => conf.severe_speed_factor,
+                };
+                let max_speed = resume_speed * factor;
+                commands.entity(object_entity).insert(Encounter {
+                    area: area_entity,
+                    intensity: area.intensity,
+                    max_speed,
+                    resume_speed,
+                });
+                commands.queue(message::SendExpiring {
+                    source:   object_entity,
+                    content:  format!(
+                        "PIREP: {} turbulence at {:.0} feet, reducing speed to {:.0} knots",
+                        describe(area.intensity),
+                        object.position.altitude().amsl().into_feet(),
+                        max_speed.into_knots(),
+                    ),
+                    class:    message::Class::AnomalyInfo,
+                    duration: MESSAGE_DURATION,
+                });
+                max_speed
+            }
+        };
+
+        if target.horiz_speed > max_speed {
+            target.horiz_speed = max_speed;
+        }
+    }
+}
+
+fn request_system(
+    conf: ReadConfig<Conf>,
+    request_conf: ReadConfig<pilot_request::Conf>,
+    time: Res<Time<time::Virtual>>,
+    mut rng: session::SessionRng,
+    object_query: Query<(Entity, &Object, &Encounter), Without<pilot_request::RequestList>>,
+    area_query: Query<&Area>,
+    mut commands: Commands,
+) {
+    let conf = conf.read();
+
+    if object_query.is_empty() || time.delta().is_zero() || conf.request_interval.is_zero() {
+        return;
+    }
+    let probability = time.delta().as_secs_f64() / conf.request_interval.as_secs_f64();
+    let rng = rng.get(session::STREAM_TURBULENCE);
+
+    for (object_entity, object, encounter) in object_query {
+        if !rng.random_bool(probability.clamp(0.0, 1.0)) {
+            continue;
+        }
+        let Some(area) = area_query.log_get(encounter.area) else { continue };
+
+        let altitude = object.position.altitude();
+        let (body, verb, requested) = if area.top - altitude <= altitude - area.bottom {
+            let requested = area.top + conf.request_margin;
+            (pilot_request::Body::Climb { altitude: requested }, "climb", requested)
+        } else {
+            let requested = area.bottom - conf.request_margin;
+            (pilot_request::Body::Descend { altitude: requested }, "descent", requested)
+        };
+
+        commands.spawn((
+            message::Message {
+                source:  object_entity,
+                created: time.elapsed(),
+                content: format!(
+                    "Requesting {verb} to {:.0} feet due to {} turbulence",
+                    requested.amsl().into_feet(),
+                    describe(encounter.intensity),
+                ),
+                class:   message::Class::NeedAck,
+            },
+            pilot_request::Requester(object_entity),
+            pilot_request::Request {
+                body,
+                deadline: time.elapsed() + request_conf.read().response_timeout,
+            },
+        ));
+    }
+}
+
+/// Refuses an instruction to increase speed if the object is in turbulence.
+///
+/// Returns whether the instruction is refused.
+pub fn refuse_speed_up(entity: &mut EntityWorldMut, target: Speed<f32>) -> bool {
+    let Some(&Encounter { intensity, .. }) = entity.get() else { return false };
+    let Some(current) = entity.get::<nav::VelocityTarget>().map(|t| t.horiz_speed) else {
+        return false;
+    };
+    if target <= current {
+        return false;
+    }
+
+    if let Some(mut clearance) = entity.get_mut::<pilot::Clearance>() {
+        clearance.speed = None;
+    }
+    let source = entity.id();
+    entity.world_scope(|world| {
+        message::SendExpiring {
+            source,
+            content: format!("Unable to increase speed, {} turbulence", describe(intensity)),
+            class: message::Class::AnomalyInfo,
+            duration: MESSAGE_DURATION,
+        }
+        .apply(world);
+    });
+    true
+}
@@ -0,0 +1,22 @@
+use bevy::ecs::name::Name;
+use bevy::ecs::world::World;
+
+use crate::level::turbulence;
+use crate::load::StoredEntity;
+
+/// Spawns the turbulence areas declared in a store into the world.
+pub fn spawn_areas(world: &mut World, areas: &[store::TurbulenceArea]) {
+    for area in areas {
+        world.spawn((
+            StoredEntity,
+            Name::new("Turbulence area"),
+            turbulence::Area {
+                center:    area.center,
+                radius:    area.radius,
+                bottom:    area.bottom,
+                top:       area.top,
+                intensity: area.intensity,
+            },
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{Heading, ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Length, Position, Speed};
+use store::YawTarget;
+
+use super::{Area, Encounter, Intensity, refuse_speed_up};
+use crate::level::object::{self, Object};
+use crate::level::{message, nav, pilot_request, test_util, turbulence};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        pilot_request::Plug::<()>::default(),
+        turbulence::Plug::<()>::default(),
+    ));
+    app.world_mut().spawn(Area {
+        center:    Position::from_origin_nm(0.0, 0.0),
+        radius:    Length::from_nm(5.0),
+        bottom:    Position::from_amsl_feet(10000.0),
+        top:       Position::from_amsl_feet(14000.0),
+        intensity: Intensity::Moderate,
+    });
+    app.update();
+    app
+}
+
+fn spawn_object(app: &mut App, x_nm: f32) -> Entity {
+    let altitude = Position::from_amsl_feet(12000.0);
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(x_nm, 0.0).with_altitude(altitude),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  altitude,
+            },
+            nav::VelocityTarget {
+                yaw:         YawTarget::Heading(Heading::NORTH),
+                horiz_speed: Speed::from_knots(250.0),
+                vert_rate:   Speed::ZERO,
+                expedite:    false,
+            },
+            message::Sender { display: "ABC123".into() },
+        ))
+        .id()
+}
+
+fn target_speed(app: &App, object: Entity) -> f32 {
+    app.world().get::<nav::VelocityTarget>(object).expect("spawned").horiz_speed.into_knots()
+}
+
+#[test]
+fn test_encounter_reduces_and_resumes_speed() {
+    let mut app = base_app();
+    let object = spawn_object(&mut app, 0.0);
+    let outside = spawn_object(&mut app, 10.0);
+
+    app.update();
+    assert!((target_speed(&app, object) - 225.0).abs() < 0.01);
+    assert!(app.world().entity(object).contains::<Encounter>());
+    assert!((target_speed(&app, outside) - 250.0).abs() < 0.01);
+    assert!(!app.world().entity(outside).contains::<Encounter>());
+
+    app.world_mut().get_mut::<Object>(object).expect("spawned").position =
+        Position::from_origin_nm(10.0, 0.0).with_altitude(Position::from_amsl_feet(12000.0));
+    app.update();
+    assert!((target_speed(&app, object) - 250.0).abs() < 0.01);
+    assert!(!app.world().entity(object).contains::<Encounter>());
+}
+
+#[test]
+fn test_refuse_speed_up_in_turbulence() {
+    let mut app = base_app();
+    let object = spawn_object(&mut app, 0.0);
+    let outside = spawn_object(&mut app, 10.0);
+    app.update();
+
+    let world = app.world_mut();
+    assert!(refuse_speed_up(&mut world.entity_mut(object), Speed::from_knots(240.0)));
+    assert!(!refuse_speed_up(&mut world.entity_mut(object), Speed::from_knots(210.0)));
+    assert!(!refuse_speed_up(&mut world.entity_mut(outside), Speed::from_knots(300.0)));
+}
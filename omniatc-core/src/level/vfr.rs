@@ -0,0 +1,182 @@
+//! Objects flying under visual flight rules.
+//!
+//! A [`Vfr`] object does not follow a route initially.
+//! It wanders between visual reporting points at low altitude
+//! until it has flown the configured number of legs,
+//! after which it continues on its route.
+//!
+//! VFR objects are not obliged to follow instructions
+//! unless the controller has granted [`FlightFollowing`].
+//! An instruction accepted by a VFR object terminates its wandering.
+
+use std::marker::PhantomData;
+
+use bevy::app::{self, App, Plugin};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, With, Without};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::{Commands, Local, Query, Res};
+use bevy::time::{self, Time};
+use bevy_mod_config::{AppExt, Config, ConfigFieldFor, Manager, ReadConfig};
+use math::Length;
+use rand::Rng;
+use rand::seq::IteratorRandom;
+
+use super::SystemSets;
+use crate::level::instr::{self, Instruction};
+use crate::level::object::{self, Object};
+use crate::level::waypoint::Waypoint;
+use crate::level::{message, nav, route, session};
+
+#[cfg(test)]
+mod tests;
+
+pub struct Plug<M>(PhantomData<M>);
+
+impl<M> Default for Plug<M> {
+    fn default() -> Self { Self(PhantomData) }
+}
+
+impl<M: Manager + Default> Plugin for Plug<M>
+where
+    Conf: ConfigFieldFor<M>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_config::<M, Conf>("core:vfr");
+        app.init_resource::<session::Seed>();
+        app.add_systems(app::Update, wander_system.in_set(SystemSets::Action));
+        app.add_systems(
+            app::Update,
+            compliance_system.in_set(SystemSets::Communicate).before(instr::dispatch_system),
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct Conf {
+    /// Probability that a VFR object without flight following
+    /// complies with an instruction.
+    #[config(default = 0.5, min = 0.0, max = 1.0)]
+    pub compliance_probability: f32,
+    /// A reporting point is considered reached within this horizontal distance.
+    #[config(default = Length::from_nm(1.0), min = Length::ZERO, max = Length::from_nm(5.0))]
+    pub arrival_distance:       Length<f32>,
+}
+
+/// Marks an object flying under visual flight rules.
+#[derive(Component)]
+pub struct Vfr {
+    /// Waypoints between which the object wanders.
+    pub reporting_points: Vec<Entity>,
+    /// Number of legs to fly between reporting points before continuing on the route.
+    ///
+    /// Zero if the object no longer wanders.
+    pub remaining_legs:   u32,
+}
+
+/// Marks a VFR object that has been granted flight following,
+/// after which it complies with all instructions.
+#[derive(Component)]
+pub struct FlightFollowing;
+
+/// Marks an instruction that a VFR object has decided to comply with.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+struct ComplianceChecked;
+
+fn wander_system(
+    conf: ReadConfig<Conf>,
+    object_query: Query<
+        (Entity, &Object, &mut Vfr, Option<&nav::TargetWaypoint>),
+        With<object::Airborne>,
+    >,
+    waypoint_query: Query<&Waypoint>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    let conf = conf.read();
+    let rng = rng.get(session::STREAM_VFR_WANDER);
+
+    for (object_entity, object, mut vfr, target) in object_query {
+        if vfr.remaining_legs == 0 {
+            continue;
+        }
+
+        let current_point = target
+            .map(|target| target.waypoint_entity)
+            .filter(|point| vfr.reporting_points.contains(point));
+        if let Some(current_point) = current_point {
+            let Ok(waypoint) = waypoint_query.get(current_point) else { continue };
+            if object.position.horizontal_distance_cmp(waypoint.position) > conf.arrival_distance {
+                continue;
+            }
+
+            vfr.remaining_legs -= 1;
+            if vfr.remaining_legs == 0 {
+                commands
+                    .entity(object_entity)
+                    .remove::<nav::TargetWaypoint>()
+                    .queue(route::RemoveStandby { skip_id: None });
+                continue;
+            }
+        }
+
+        let next_point = vfr
+            .reporting_points
+            .iter()
+            .copied()
+            .filter(|&point| Some(point) != current_point)
+            .choose(rng);
+        if let Some(waypoint_entity) = next_point {
+            commands.entity(object_entity).insert(nav::TargetWaypoint { waypoint_entity });
+        }
+    }
+}
+
+fn compliance_system(
+    conf: ReadConfig<Conf>,
+    instr_conf: ReadConfig<instr::Conf>,
+    time: Res<Time<time::Virtual>>,
+    instr_query: Query<
+        (Entity, &Instruction, &instr::Recipient, &instr::TransmitDelay),
+        (Without<instr::PendingAck>, Without<ComplianceChecked>),
+    >,
+    mut object_query: Query<(&mut Vfr, Has<FlightFollowing>)>,
+    mut commands: Commands,
+    mut rng: session::SessionRng,
+) {
+    let conf = conf.read();
+    let instr_conf = instr_conf.read();
+    let rng = rng.get(session::STREAM_VFR_COMPLIANCE);
+
+    for (instr_entity, instr, &instr::Recipient(object_entity), delay) in instr_query {
+        if time.elapsed() < delay.expiry {
+            continue;
+        }
+        let Ok((mut vfr, has_flight_following)) = object_query.get_mut(object_entity) else {
+            continue;
+        };
+
+        let is_grant = matches!(instr, Instruction::GrantFlightFollowing(_));
+        if is_grant || has_flight_following || rng.random_bool(conf.compliance_probability.into()) {
+            if !is_grant {
+                vfr.remaining_legs = 0;
+            }
+            commands.entity(instr_entity).insert(ComplianceChecked);
+        } else {
+            commands
+                .entity(instr_entity)
+                .remove::<(Instruction, instr::Recipient, instr::TransmitDelay, instr::DispatchAfter)>()
+                .insert(message::Expiry {
+                    expiry: time.elapsed() + instr_conf.message_duration_after_dispatch,
+                });
+            commands.spawn(message::Message {
+                source:  object_entity,
+                created: time.elapsed(),
+                content: "Unable, VFR".into(),
+                class:   message::Class::VerboseInfo,
+            });
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::app::App;
+use bevy::ecs::entity::Entity;
+use math::{ISA_TROPOPAUSE_PRESSURE, ISA_TROPOPAUSE_TEMPERATURE, Position, Speed};
+
+use super::{FlightFollowing, Vfr};
+use crate::level::instr::{self, CommandsExt};
+use crate::level::object::{self, Object};
+use crate::level::waypoint::{self, Waypoint};
+use crate::level::{message, nav, phraseology, test_util, vfr};
+
+fn base_app() -> App {
+    let mut app = test_util::app();
+    app.add_plugins((
+        message::Plug,
+        instr::Plug::<()>::default(),
+        phraseology::Plug::<()>::default(),
+        vfr::Plug::<()>::default(),
+    ));
+    app.update();
+    app
+}
+
+fn spawn_point(app: &mut App, name: &str, x: f32) -> Entity {
+    app.world_mut()
+        .spawn(Waypoint {
+            name:         name.into(),
+            display_type: waypoint::DisplayType::Waypoint,
+            position:     Position::from_origin_nm(x, 0.0).with_altitude(Position::SEA_LEVEL),
+            hidden:       false,
+        })
+        .id()
+}
+
+fn spawn_vfr(app: &mut App, reporting_points: Vec<Entity>, remaining_legs: u32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Object {
+                position:     Position::from_origin_nm(0.0, 0.0)
+                    .with_altitude(Position::from_amsl_feet(3000.0)),
+                ground_speed: Speed::ZERO,
+            },
+            object::Airborne {
+                airspeed:      Speed::ZERO,
+                true_airspeed: Speed::ZERO,
+                oat:           ISA_TROPOPAUSE_TEMPERATURE,
+                pressure:      ISA_TROPOPAUSE_PRESSURE,
+                pressure_alt:  Position::from_amsl_feet(3000.0),
+            },
+            message::Sender { display: "N123A".into() },
+            Vfr { reporting_points, remaining_legs },
+        ))
+        .id()
+}
+
+fn send_instruction(app: &mut App, object: Entity, instruction: impl Into<instr::Instruction>) {
+    let world = app.world_mut();
+    world.commands().send_instruction(object, instruction);
+    world.flush();
+}
+
+#[test]
+fn test_wander_to_next_point() {
+    let mut app = base_app();
+    let here = spawn_point(&mut app, "HERE", 0.0);
+    let there = spawn_point(&mut app, "THERE", 10.0);
+    let object = spawn_vfr(&mut app, vec![here, there], 2);
+    app.world_mut().entity_mut(object).insert(nav::TargetWaypoint { waypoint_entity: here });
+
+    app.update();
+
+    let target = app.world().get::<nav::TargetWaypoint>(object).expect("target should be set");
+    assert_eq!(target.waypoint_entity, there);
+    assert_eq!(app.world().get::<Vfr>(object).map(|vfr| vfr.remaining_legs), Some(1));
+}
+
+#[test]
+fn test_grant_flight_following() {
+    let mut app = base_app();
+    let object = spawn_vfr(&mut app, Vec::new(), 3);
+
+    send_instruction(&mut app, object, instr::GrantFlightFollowing);
+    app.update();
+
+    assert!(app.world().get::<FlightFollowing>(object).is_some());
+    assert_eq!(
+        app.world().get::<Vfr>(object).map(|vfr| vfr.remaining_legs),
+        Some(3),
+        "granting flight following should not interrupt wandering"
+    );
+}
+
+#[test]
+fn test_flight_following_complies() {
+    let mut app = base_app();
+    let object = spawn_vfr(&mut app, Vec::new(), 3);
+    app.world_mut().entity_mut(object).insert(FlightFollowing);
+
+    send_instruction(&mut app, object, instr::ClearRoute);
+    app.update();
+
+    assert_eq!(
+        app.world().get::<Vfr>(object).map(|vfr| vfr.remaining_legs),
+        Some(0),
+        "an accepted instruction should terminate wandering"
+    );
+}
@@ -8,14 +8,15 @@ use bevy::ecs::world::World;
 use math::{Angle, Length, Position};
 
 use crate::level::aerodrome::loader::AerodromeMap;
+use crate::level::airway;
 use crate::level::navaid::{self, Navaid};
 use crate::level::waypoint::{self, Waypoint};
 use crate::load::{self, StoredEntity};
 
 /// Spawns named waypoints declared in a store into the world.
 pub fn spawn(world: &mut World, waypoints: &[store::Waypoint]) -> WaypointMap {
-    WaypointMap(
-        waypoints
+    WaypointMap {
+        waypoints: waypoints
             .iter()
             .map(|waypoint| {
                 let waypoint_entity = world
@@ -42,7 +43,8 @@ pub fn spawn(world: &mut World, waypoints: &[store::Waypoint]) -> WaypointMap {
                 (waypoint.name.clone(), waypoint_entity)
             })
             .collect::<HashMap<_, _>>(),
-    )
+        airways:   HashMap::new(),
+    }
 }
 
 fn choose_waypoint_display_type(navaids: &[store::Navaid]) -> waypoint::DisplayType {
@@ -75,7 +77,11 @@ fn spawn_waypoint_navaid(b: &mut RelatedSpawner<'_, impl Relationship>, navaid:
 }
 
 #[derive(Debug, Default)]
-pub struct WaypointMap(HashMap<String, Entity>);
+pub struct WaypointMap {
+    waypoints: HashMap<String, Entity>,
+    /// Ordered waypoint entities of each airway.
+    airways:   HashMap<String, Vec<Entity>>,
+}
 
 impl WaypointMap {
     /// Resolve a named waypoint reference.
@@ -87,7 +93,10 @@ impl WaypointMap {
     }
 
     fn resolve_str(&self, name: &str) -> Result<Entity, load::Error> {
-        self.0.get(name).copied().ok_or_else(|| load::Error::UnresolvedWaypoint(name.to_string()))
+        self.waypoints
+            .get(name)
+            .copied()
+            .ok_or_else(|| load::Error::UnresolvedWaypoint(name.to_string()))
     }
 
     /// Resolve a waypoint reference, including runway virtual waypoints.
@@ -109,4 +118,32 @@ impl WaypointMap {
             }
         }
     }
+
+    pub(crate) fn insert_airway(&mut self, airway: String, waypoints: Vec<Entity>) {
+        self.airways.insert(airway, waypoints);
+    }
+
+    /// Resolve the waypoints passed when following an airway from `from` to `to`,
+    /// including both ends.
+    ///
+    /// # Errors
+    /// If the airway or either waypoint does not exist,
+    /// or if either waypoint is not on the airway.
+    pub fn resolve_airway(
+        &self,
+        airway: &store::AirwayRef,
+        from: &store::NamedWaypointRef,
+        to: &store::NamedWaypointRef,
+    ) -> Result<Vec<Entity>, load::Error> {
+        let chain = self
+            .airways
+            .get(&airway.0)
+            .ok_or_else(|| load::Error::UnresolvedAirway(airway.0.clone()))?;
+        let from_entity = self.resolve(from)?;
+        let to_entity = self.resolve(to)?;
+        airway::legs(chain, from_entity, to_entity).ok_or_else(|| {
+            let off_airway = if chain.contains(&from_entity) { to } else { from };
+            load::Error::NotOnAirway { airway: airway.0.clone(), waypoint: off_airway.0.clone() }
+        })
+    }
 }
@@ -1,25 +1,47 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
 use std::num::NonZero;
 use std::sync::Arc;
+use std::time::Duration;
 
-use bevy::app::{App, Plugin};
+use bevy::app::{self, App, Plugin};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::With;
 use bevy::ecs::resource::Resource;
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::schedule::common_conditions::{not, resource_exists};
 use bevy::ecs::system::Command as BevyCommand;
 use bevy::ecs::world::World;
+use bevy::platform::time::Instant;
+use bevy::tasks::{self, AsyncComputeTaskPool, Task};
 use math::sweep;
+use strum::{EnumCount, IntoEnumIterator};
 
-use crate::level::{aerodrome, object, quest, route, score, spawn, waypoint, weather};
+use crate::level;
+use crate::level::{
+    aerodrome, airway, bird, clock, deice, drift, facility, fog, hold, icing, object,
+    pilot_request, quest, route, score, script, sector, session, spawn, surface, terrain, track,
+    transition, turbulence, waypoint, weather,
+};
 
 pub struct Plug;
 
 impl Plugin for Plug {
     fn build(&self, app: &mut App) {
         app.init_resource::<CameraAdvice>();
+        app.init_resource::<DisplaySettingsAdvice>();
+        app.init_resource::<LoadedMeta>();
+        app.init_resource::<LoadedFile>();
         app.init_resource::<SpawnContext>();
+        app.init_resource::<object::loader::ObjectMap>();
+        app.init_resource::<Issues>();
+        app.configure_sets(
+            app::Update,
+            level::AllSystemSets.run_if(not(resource_exists::<Loading>)),
+        );
+        app.add_systems(app::PreUpdate, drive_staged_system);
     }
 }
 
@@ -31,14 +53,80 @@ pub struct StoredEntity;
 #[derive(Resource, Default)]
 pub struct CameraAdvice(pub Option<store::Camera>);
 
+/// Recommended display settings of the last loaded level, to be consumed by the client.
+#[derive(Resource, Default)]
+pub struct DisplaySettingsAdvice(pub Option<BTreeMap<String, String>>);
+
+/// Metadata of the last loaded level.
+#[derive(Resource, Default)]
+pub struct LoadedMeta(pub Option<store::Meta>);
+
+/// The last loaded file, as it was before loading.
+#[derive(Resource, Default)]
+pub struct LoadedFile(pub Option<Arc<store::File>>);
+
+/// Elements of the last loaded level that were skipped due to recoverable errors.
+#[derive(Resource, Default)]
+pub struct Issues(pub Vec<Issue>);
+
+/// An element skipped during loading.
+pub struct Issue {
+    /// Human-readable description of the skipped element.
+    pub element: String,
+    pub error:   Error,
+}
+
+impl Issues {
+    /// Records `result` as an issue if it is a [recoverable](Error::is_recoverable) error.
+    ///
+    /// Returns `Ok(None)` if the element should be skipped.
+    ///
+    /// # Errors
+    /// If `result` is an unrecoverable error.
+    pub fn recover<T>(
+        &mut self,
+        element: impl FnOnce() -> String,
+        result: Result<T>,
+    ) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.is_recoverable() => {
+                let element = element();
+                bevy::log::warn!("Skipped {element} during load: {error}");
+                self.0.push(Issue { element, error });
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Records `result` into the [`Issues`] of `world`. See [`Issues::recover`].
+///
+/// # Errors
+/// If `result` is an unrecoverable error.
+pub fn recover<T>(
+    world: &mut World,
+    element: impl FnOnce() -> String,
+    result: Result<T>,
+) -> Result<Option<T>> {
+    world.get_resource_or_init::<Issues>().recover(element, result)
+}
+
 #[cfg(test)]
 mod tests;
 
+mod validate;
+pub use validate::validate;
+
 pub enum Source {
     Raw(Cow<'static, [u8]>),
     Parsed(Box<store::File>),
 }
 
+/// Loads a level synchronously within a single frame.
+///
+/// Any level being loaded by a [`StagedCommand`] is cancelled.
 pub struct Command {
     pub source:   Source,
     pub on_error: Box<dyn FnOnce(&mut World, Error) + Send>,
@@ -46,73 +134,385 @@ pub struct Command {
 
 impl BevyCommand for Command {
     fn apply(self, world: &mut World) {
-        if let Err(err) = do_load(world, &self.source) {
+        world.remove_resource::<Loading>();
+        if let Err(err) = do_load(world, self.source) {
             (self.on_error)(world, err);
         }
     }
 }
 
-fn do_load(world: &mut World, source: &Source) -> Result<(), Error> {
-    let file_owned: store::File;
+fn do_load(world: &mut World, source: Source) -> Result<(), Error> {
     let file = match source {
         Source::Raw(bytes) => {
-            file_owned = store::File::from_osav(bytes.as_ref()).map_err(Error::Deserialize)?;
-            &file_owned
+            Box::new(store::File::from_osav(bytes.as_ref()).map_err(Error::Deserialize)?)
         }
         Source::Parsed(file) => file,
     };
 
-    world
-        .query_filtered::<Entity, With<StoredEntity>>()
-        .iter(world)
-        .collect::<Vec<_>>()
-        .into_iter()
-        .for_each(|entity| world.entity_mut(entity).despawn());
-
-    let mut next_standby_id = const { NonZero::new(1).unwrap() };
-
-    weather::loader::spawn(world, &file.level.environment.weather);
-    let object_types = object::loader::spawn_types(world, &file.level.object_types);
-    let aerodromes = aerodrome::loader::spawn(world, &file.level.aerodromes)?;
-    let waypoints = waypoint::loader::spawn(world, &file.level.waypoints);
-    let route_presets = route::loader::spawn_presets(
-        world,
-        &aerodromes,
-        &waypoints,
-        &mut next_standby_id,
-        &file.level.route_presets,
-    )?;
-    spawn::loader::spawn_sets(
-        world,
-        &object_types,
-        &aerodromes,
-        &waypoints,
-        &route_presets,
-        &file.level.spawn_sets,
-    )?;
-    spawn::loader::spawn_trigger(world, &file.level.spawn_trigger);
-    score::loader::spawn(world, &file.stats);
-    for object in &file.objects {
-        object::loader::spawn(
-            world,
-            &aerodromes,
-            &waypoints,
-            &route_presets,
-            &mut next_standby_id,
-            object,
-        )?;
+    let mut loader = Loader::new(file);
+    while loader.step(world, usize::MAX)? == StepResult::Pending {}
+    Ok(())
+}
+
+/// Loads a level across multiple frames.
+///
+/// The file is decoded on the async compute task pool,
+/// then the level is spawned in [stages](Stage) within a time budget per frame.
+/// The [`Loading`] resource exists until loading completes or fails,
+/// during which the level systems do not run.
+pub struct StagedCommand {
+    pub source:   Source,
+    pub on_error: Box<dyn FnOnce(&mut World, Error) + Send + Sync>,
+}
+
+impl BevyCommand for StagedCommand {
+    fn apply(self, world: &mut World) {
+        let state = match self.source {
+            Source::Raw(bytes) => {
+                LoadingState::Decode(AsyncComputeTaskPool::get().spawn(async move {
+                    store::File::from_osav(bytes.as_ref()).map_err(Error::Deserialize)
+                }))
+            }
+            Source::Parsed(file) => LoadingState::Spawn(Box::new(Loader::new(file))),
+        };
+        world.insert_resource(Loading { state, on_error: self.on_error });
+    }
+}
+
+/// Maximum time spent on spawning a staged level per frame.
+const FRAME_BUDGET: Duration = Duration::from_millis(8);
+
+/// Number of objects spawned per step in [`Stage::Objects`].
+const OBJECTS_PER_STEP: usize = 16;
+
+/// A level being loaded by a [`StagedCommand`].
+#[derive(Resource)]
+pub struct Loading {
+    state:    LoadingState,
+    on_error: Box<dyn FnOnce(&mut World, Error) + Send + Sync>,
+}
+
+enum LoadingState {
+    Decode(Task<Result<store::File, Error>>),
+    Spawn(Box<Loader>),
+}
+
+impl Loading {
+    /// Returns the current progress of loading.
+    #[must_use]
+    pub fn progress(&self) -> Progress {
+        match &self.state {
+            LoadingState::Decode(_) => Progress { stage: Stage::Decode, fraction: 0. },
+            LoadingState::Spawn(loader) => loader.progress(),
+        }
+    }
+}
+
+/// Progress of a [`Loading`] level.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    /// The stage being executed.
+    pub stage:    Stage,
+    /// Estimated fraction of the whole loading completed, within `[0, 1]`.
+    pub fraction: f32,
+}
+
+/// Stages of loading a level, executed in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, strum::EnumIter, strum::EnumCount)]
+pub enum Stage {
+    /// Decoding the file.
+    Decode,
+    /// Despawning entities of the previous level.
+    Clear,
+    /// Spawning terrain, weather and other environmental features.
+    Environment,
+    /// Spawning aerodromes and resolving their ground networks.
+    Aerodromes,
+    /// Spawning waypoints and holding patterns.
+    Waypoints,
+    /// Spawning route presets.
+    Routes,
+    /// Spawning spawn sets, pilot requests, scripts and score statistics.
+    Spawns,
+    /// Spawning objects.
+    Objects,
+    /// Spawning quests.
+    Quests,
+    /// Starting the session.
+    Finish,
+}
+
+impl Stage {
+    /// Human-readable description of the stage.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Decode => "Decoding file",
+            Self::Clear => "Unloading previous level",
+            Self::Environment => "Loading environment",
+            Self::Aerodromes => "Resolving aerodrome ground networks",
+            Self::Waypoints => "Loading waypoints",
+            Self::Routes => "Loading routes",
+            Self::Spawns => "Loading spawn sets",
+            Self::Objects => "Spawning objects",
+            Self::Quests => "Loading quests",
+            Self::Finish => "Starting level",
+        }
+    }
+
+    fn next(self) -> Option<Self> { Self::iter().find(|&stage| stage > self) }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum StepResult {
+    Pending,
+    Done,
+}
+
+/// Spawns a decoded level step by step.
+struct Loader {
+    file:            Box<store::File>,
+    stage:           Stage,
+    next_standby_id: NonZero<u32>,
+    object_types:    object::loader::ObjectTypeMap,
+    aerodromes:      aerodrome::loader::AerodromeMap,
+    waypoints:       waypoint::loader::WaypointMap,
+    route_presets:   route::loader::RoutePresetMap,
+    objects:         object::loader::ObjectMap,
+    /// Number of objects in `file.objects` already spawned.
+    spawned_objects: usize,
+    /// Spawned planes with in-flight instructions, as indices into `file.objects`.
+    restored_planes: Vec<(usize, Entity)>,
+}
+
+impl Loader {
+    fn new(file: Box<store::File>) -> Self {
+        Self {
+            file,
+            stage: Stage::Clear,
+            next_standby_id: const { NonZero::new(1).unwrap() },
+            object_types: object::loader::ObjectTypeMap::default(),
+            aerodromes: aerodrome::loader::AerodromeMap::default(),
+            waypoints: waypoint::loader::WaypointMap::default(),
+            route_presets: route::loader::RoutePresetMap::default(),
+            objects: object::loader::ObjectMap::default(),
+            spawned_objects: 0,
+            restored_planes: Vec::new(),
+        }
+    }
+
+    fn progress(&self) -> Progress {
+        let within_stage = if self.stage == Stage::Objects && !self.file.objects.is_empty() {
+            #[expect(clippy::cast_precision_loss, reason = "object counts are small")]
+            let ratio = self.spawned_objects as f32 / self.file.objects.len() as f32;
+            ratio
+        } else {
+            0.
+        };
+        #[expect(clippy::cast_precision_loss, reason = "stage counts are small")]
+        let fraction = (self.stage as usize as f32 + within_stage) / Stage::COUNT as f32;
+        Progress { stage: self.stage, fraction }
+    }
+
+    /// Executes the current stage, spawning at most `max_objects` objects in [`Stage::Objects`].
+    fn step(&mut self, world: &mut World, max_objects: usize) -> Result<StepResult, Error> {
+        let file = &*self.file;
+
+        match self.stage {
+            Stage::Decode => {}
+            Stage::Clear => {
+                // Validate before clearing so that an invalid file does not unload the current level.
+                validate(file)?;
+                world.get_resource_or_init::<Issues>().0.clear();
+                world
+                    .query_filtered::<Entity, With<StoredEntity>>()
+                    .iter(world)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .for_each(|entity| world.entity_mut(entity).despawn());
+            }
+            Stage::Environment => {
+                spawn_environment(world, file);
+                self.object_types = object::loader::spawn_types(world, &file.level.object_types);
+            }
+            Stage::Aerodromes => {
+                self.aerodromes = aerodrome::loader::spawn(world, &file.level.aerodromes)?;
+            }
+            Stage::Waypoints => {
+                self.waypoints = waypoint::loader::spawn(world, &file.level.waypoints);
+                airway::loader::spawn(world, &mut self.waypoints, &file.level.airways)?;
+                sector::loader::spawn(world, &file.level.sectors);
+                hold::loader::spawn(
+                    world,
+                    &file.level.aerodromes,
+                    &self.aerodromes,
+                    &self.waypoints,
+                )?;
+            }
+            Stage::Routes => {
+                self.route_presets = route::loader::spawn_presets(
+                    world,
+                    &self.aerodromes,
+                    &self.waypoints,
+                    &mut self.next_standby_id,
+                    &file.level.route_presets,
+                )?;
+            }
+            Stage::Spawns => {
+                spawn::loader::spawn_sets(
+                    world,
+                    &self.object_types,
+                    &self.aerodromes,
+                    &self.waypoints,
+                    &self.route_presets,
+                    &file.level.spawn_sets,
+                )?;
+                spawn::loader::excluded_names(world, &file.level.excluded_names);
+                spawn::loader::spawn_trigger(
+                    world,
+                    &self.object_types,
+                    &self.aerodromes,
+                    &self.waypoints,
+                    &self.route_presets,
+                    &file.level.spawn_trigger,
+                )?;
+                pilot_request::loader::spawn(world, &file.level.pilot_requests);
+                script::loader::spawn(world, &file.scripts, &file.level.scripts)?;
+                score::loader::spawn(world, &file.stats, &self.aerodromes)?;
+            }
+            Stage::Objects => {
+                self.spawn_objects(world, max_objects)?;
+                if self.spawned_objects < self.file.objects.len() {
+                    return Ok(StepResult::Pending);
+                }
+            }
+            Stage::Quests => {
+                quest::loader::spawn(
+                    world,
+                    &file.quests,
+                    &self.aerodromes,
+                    &self.waypoints,
+                    &self.objects,
+                )?;
+            }
+            Stage::Finish => {
+                self.finish(world)?;
+                return Ok(StepResult::Done);
+            }
+        }
+
+        self.stage = self.stage.next().unwrap_or(Stage::Finish);
+        Ok(StepResult::Pending)
+    }
+
+    /// Spawns at most `max_objects` of the objects not spawned yet.
+    fn spawn_objects(&mut self, world: &mut World, max_objects: usize) -> Result<()> {
+        let file = &*self.file;
+        for (index, object) in
+            file.objects.iter().enumerate().skip(self.spawned_objects).take(max_objects)
+        {
+            let result = object::loader::spawn(
+                world,
+                &self.aerodromes,
+                &self.waypoints,
+                &self.route_presets,
+                &mut self.next_standby_id,
+                object,
+            );
+            let name = || format!("object {}", object::loader::name(object));
+            if let Some(entity) = recover(world, name, result)? {
+                self.objects.insert(object, entity);
+                if let store::Object::Plane(plane) = object
+                    && !plane.aircraft.instructions.is_empty()
+                {
+                    self.restored_planes.push((index, entity));
+                }
+            }
+            self.spawned_objects += 1;
+        }
+        Ok(())
     }
-    quest::loader::spawn(world, &file.quests, &aerodromes)?;
-
-    world.resource_mut::<CameraAdvice>().0 = Some(file.ui.camera.clone());
-    *world.resource_mut::<SpawnContext>() = SpawnContext {
-        aerodromes: Arc::new(aerodromes),
-        waypoints: Arc::new(waypoints),
-        route_presets: Arc::new(route_presets),
-        next_standby_id,
+
+    fn finish(&mut self, world: &mut World) -> Result<()> {
+        let file = &*self.file;
+        let mut camera = file.ui.camera.clone();
+        facility::advise_camera(file.meta.mode, &mut camera);
+        world.resource_mut::<CameraAdvice>().0 = Some(camera);
+        world.insert_resource(facility::Mode(file.meta.mode));
+        world.resource_mut::<DisplaySettingsAdvice>().0 = Some(file.ui.display_settings.clone());
+        world.resource_mut::<LoadedMeta>().0 = Some(file.meta.clone());
+        world.resource_mut::<LoadedFile>().0 = Some(Arc::new(file.clone()));
+        session::start(world, file);
+        track::loader::spawn(world, file.level.geo_origin);
+        *world.resource_mut::<SpawnContext>() = SpawnContext {
+            aerodromes:      Arc::new(mem::take(&mut self.aerodromes)),
+            waypoints:       Arc::new(mem::take(&mut self.waypoints)),
+            route_presets:   Arc::new(mem::take(&mut self.route_presets)),
+            next_standby_id: self.next_standby_id,
+        };
+        world.insert_resource(mem::take(&mut self.objects));
+
+        // Instructions may reference any object, so they are restored after all objects exist.
+        // Objects skipped in `Stage::Objects` have no entity and are not restored.
+        for &(index, entity) in &self.restored_planes {
+            let store::Object::Plane(plane) = &file.objects[index] else { continue };
+            let name = &plane.aircraft.name;
+            let result = session::restore_state(world, entity, &plane.aircraft.instructions);
+            recover(world, || format!("instructions of {name}"), result)?;
+        }
+        Ok(())
+    }
+}
+
+fn spawn_environment(world: &mut World, file: &store::File) {
+    let env = &file.level.environment;
+    terrain::loader::spawn(world, &env.heightmap);
+    weather::loader::spawn(world, &env.weather);
+    drift::loader::spawn_thermals(world, &env.thermals);
+    bird::loader::spawn_sources(world, &env.bird_activity);
+    surface::loader::spawn_rain_showers(world, &env.rain_showers);
+    turbulence::loader::spawn_areas(world, &env.turbulence);
+    icing::loader::spawn_bands(world, &env.icing);
+    fog::loader::spawn_banks(world, &env.fog);
+    deice::loader::spawn(world, env.winter_ops.as_ref());
+    transition::loader::spawn(world, env.transition_altitude);
+    clock::loader::spawn(world, &file.clock);
+}
+
+/// Advances the [`Loading`] level within the frame budget.
+fn drive_staged_system(world: &mut World) {
+    let Some(mut loading) = world.remove_resource::<Loading>() else { return };
+
+    let result = match &mut loading.state {
+        LoadingState::Decode(task) => match tasks::block_on(tasks::poll_once(task)) {
+            None => Ok(StepResult::Pending),
+            Some(Ok(file)) => {
+                loading.state = LoadingState::Spawn(Box::new(Loader::new(Box::new(file))));
+                Ok(StepResult::Pending)
+            }
+            Some(Err(err)) => Err(err),
+        },
+        LoadingState::Spawn(loader) => {
+            let start = Instant::now();
+            loop {
+                match loader.step(world, OBJECTS_PER_STEP) {
+                    Ok(StepResult::Pending) if start.elapsed() < FRAME_BUDGET => {}
+                    result => break result,
+                }
+            }
+        }
     };
 
-    Ok(())
+    match result {
+        Ok(StepResult::Pending) => {
+            // A new level may have been requested while this one was loading.
+            if !world.contains_resource::<Loading>() {
+                world.insert_resource(loading);
+            }
+        }
+        Ok(StepResult::Done) => {}
+        Err(err) => (loading.on_error)(world, err),
+    }
 }
 
 /// Stores the level loading state such as spawned entity maps.
@@ -149,8 +549,14 @@ pub enum Error {
     UnresolvedAerodrome(String),
     #[error("No runway called {runway:?} in aerodrome {aerodrome:?}")]
     UnresolvedRunway { aerodrome: String, runway: String },
+    #[error("No runway configuration called {configuration:?} in aerodrome {aerodrome:?}")]
+    UnresolvedRunwayConfiguration { aerodrome: String, configuration: String },
     #[error("No waypoint called {0:?}")]
     UnresolvedWaypoint(String),
+    #[error("No airway called {0:?}")]
+    UnresolvedAirway(String),
+    #[error("Waypoint {waypoint:?} is not on airway {airway:?}")]
+    NotOnAirway { airway: String, waypoint: String },
     #[error("No {variant} called {value:?} in aerodrome {aerodrome:?}")]
     UnresolvedSegment { variant: &'static str, value: String, aerodrome: String },
     #[error(
@@ -164,10 +570,14 @@ pub enum Error {
     },
     #[error("No route preset called {0:?}")]
     UnresolvedRoutePreset(String),
+    #[error("Route preset {0:?} includes itself")]
+    RoutePresetCycle(String),
     #[error("No object type called {0:?}")]
     UnresolvedObjectType(String),
     #[error("Non-finite value encountered at {0}")]
     NonFiniteFloat(&'static str),
+    #[error("Invalid {field} in {element}: {reason}")]
+    InvalidValue { element: String, field: &'static str, reason: &'static str },
     #[error(
         "The backward direction of apron {0} does not intersect with any taxiways within 100nm"
     )]
@@ -176,6 +586,43 @@ pub enum Error {
     GroundSweep(sweep::Error),
     #[error("No quest with ID {0:?}")]
     UnresolvedQuest(String),
+    #[error("No object called {0:?}")]
+    UnresolvedObject(String),
+    #[error("No script called {0:?}")]
+    UnresolvedScript(String),
+    #[error("Script {id:?} failed to initialize: {message}")]
+    Script { id: String, message: String },
+}
+
+impl Error {
+    /// Whether the error only affects a single element of the level,
+    /// which can be skipped without corrupting the rest of the level.
+    #[must_use]
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::UnresolvedAerodrome(_)
+            | Self::UnresolvedRunway { .. }
+            | Self::UnresolvedRunwayConfiguration { .. }
+            | Self::UnresolvedWaypoint(_)
+            | Self::UnresolvedAirway(_)
+            | Self::NotOnAirway { .. }
+            | Self::UnresolvedSegment { .. }
+            | Self::NotOnSegment { .. }
+            | Self::UnresolvedRoutePreset(_)
+            | Self::RoutePresetCycle(_)
+            | Self::UnresolvedObjectType(_)
+            | Self::UnreachableApron(_)
+            | Self::UnresolvedObject(_)
+            | Self::UnresolvedScript(_)
+            | Self::Script { .. } => true,
+            Self::Deserialize(_)
+            | Self::TooManyAerodromes
+            | Self::NonFiniteFloat(_)
+            | Self::InvalidValue { .. }
+            | Self::GroundSweep(_)
+            | Self::UnresolvedQuest(_) => false,
+        }
+    }
 }
 
 pub type Result<T = (), E = Error> = std::result::Result<T, E>;
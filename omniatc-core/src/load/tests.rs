@@ -1,2 +1,33 @@
 #[test]
 fn spawn_ground_segments() {}
+
+#[test]
+fn stages_advance_to_finish() {
+    use super::Stage;
+
+    let mut stages = vec![Stage::Decode];
+    while let Some(next) = stages.last().and_then(|&stage| stage.next()) {
+        stages.push(next);
+    }
+    assert_eq!(stages.first(), Some(&Stage::Decode));
+    assert_eq!(stages.last(), Some(&Stage::Finish));
+    assert!(stages.is_sorted());
+}
+
+#[test]
+fn recover_skips_only_recoverable_errors() {
+    use super::{Error, Issues};
+
+    let mut issues = Issues::default();
+    assert_eq!(issues.recover(|| "a".into(), Ok(1)).ok(), Some(Some(1)));
+
+    let skipped =
+        issues.recover(|| "b".into(), Err::<(), _>(Error::UnresolvedWaypoint("X".into())));
+    assert!(matches!(skipped, Ok(None)));
+
+    let failed = issues.recover(|| "c".into(), Err::<(), _>(Error::TooManyAerodromes));
+    assert!(matches!(failed, Err(Error::TooManyAerodromes)));
+
+    assert_eq!(issues.0.len(), 1);
+    assert_eq!(issues.0[0].element, "b");
+}
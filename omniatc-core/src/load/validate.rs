@@ -0,0 +1,453 @@
+//! Validation of untrusted level files before any entity is spawned.
+//!
+//! Systems assume that physical quantities loaded from the file are finite
+//! and within the ranges documented in the store schema.
+//! Values outside these ranges are rejected here
+//! instead of causing panics or NaN propagation during simulation.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use bevy::math::Vec2;
+use math::{
+    Accel, AccelRate, Angle, AngularAccel, AngularSpeed, Heading, Length, Position, Pressure,
+    Speed, Temp,
+};
+
+use super::{Error, Result};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum horizontal distance of any position from the level origin.
+const MAX_DISTANCE: Length<f32> = Length::from_nm(10000.);
+const MIN_ALTITUDE: Position<f32> = Position::from_amsl_feet(-2000.);
+const MAX_ALTITUDE: Position<f32> = Position::from_amsl_feet(200_000.);
+const MAX_SPEED: Speed<f32> = Speed::from_knots(2000.);
+const MAX_ACCEL: Accel<f32> = Accel::from_knots_per_sec(100.);
+const MAX_ACCEL_RATE: AccelRate<f32> = AccelRate::from_knots_per_sec2(100.);
+const MAX_YAW_SPEED: AngularSpeed = AngularSpeed::from_degrees_per_sec(360.);
+const MAX_YAW_ACCEL: AngularAccel = AngularAccel::from_degrees_per_sec2(360.);
+const PRESSURE_RANGE: RangeInclusive<Pressure> =
+    Pressure::from_pascals(50_000.)..=Pressure::from_pascals(150_000.);
+const TEMP_RANGE: RangeInclusive<Temp> = Temp::from_kelvins(150.)..=Temp::from_kelvins(400.);
+/// Range of [`store::Weather::wind_scaling_per_nm`].
+const WIND_SCALING_RANGE: RangeInclusive<f32> = 0.01..=10.;
+
+/// Checks that all physical quantities in `file` are within their valid ranges.
+///
+/// # Errors
+/// [`Error::InvalidValue`] for the first invalid value encountered.
+pub fn validate(file: &store::File) -> Result {
+    let level = &file.level;
+
+    environment(&level.environment)?;
+
+    for (name, object_type) in &level.object_types {
+        let element = format!("object type {}", name.0);
+        taxi_limits(&element, &object_type.taxi_limits)?;
+        match &object_type.class {
+            store::ObjectClassSpec::Plane { nav_limits: limits } => {
+                nav_limits(&element, limits)?;
+            }
+        }
+    }
+
+    for aerodrome in &level.aerodromes {
+        self::aerodrome(aerodrome)?;
+    }
+
+    for waypoint in &level.waypoints {
+        let element = format!("waypoint {}", waypoint.name);
+        position(&element, "position", waypoint.position)?;
+        if let Some(elevation) = waypoint.elevation {
+            altitude(&element, "elevation", elevation)?;
+        }
+    }
+
+    for sector in &level.sectors {
+        let element = format!("sector {}", sector.name);
+        for &vertex in &sector.boundary {
+            position(&element, "boundary", vertex)?;
+        }
+    }
+
+    spawn_sets(&level.spawn_sets)?;
+    weights("pilot requests", &level.pilot_requests.kinds)?;
+    if let Some(interval) = level.pilot_requests.mean_interval {
+        positive_duration("pilot requests", "mean_interval", interval)?;
+    }
+
+    for quest in &file.quests.quests {
+        let element = format!("quest {}", quest.id.0);
+        for hook in &quest.completion_hooks {
+            completion_hook(&element, hook)?;
+        }
+    }
+
+    for object in &file.objects {
+        self::object(object)?;
+    }
+
+    Ok(())
+}
+
+fn completion_hook(element: &str, hook: &store::QuestCompletionHook) -> Result {
+    match *hook {
+        store::QuestCompletionHook::ScaleTrafficRate { factor } => ensure(
+            element,
+            "factor",
+            factor.is_finite() && factor > 0.,
+            "must be finite and positive",
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn environment(env: &store::Environment) -> Result {
+    for (index, weather) in env.weather.iter().enumerate() {
+        let element = format!("weather region #{index}");
+        position(&element, "start", weather.start)?;
+        position(&element, "end", weather.end)?;
+        in_range(&element, "sea_pressure", weather.sea_pressure, PRESSURE_RANGE)?;
+        in_range(&element, "sea_temp", weather.sea_temp, TEMP_RANGE)?;
+        in_range(
+            &element,
+            "sea_wind",
+            weather.sea_wind.magnitude_exact(),
+            Speed::ZERO..=MAX_SPEED,
+        )?;
+        in_range(&element, "wind_scaling_per_nm", weather.wind_scaling_per_nm, WIND_SCALING_RANGE)?;
+        ensure(
+            &element,
+            "wind_rotation_per_nm",
+            weather.wind_rotation_per_nm.is_finite(),
+            "must be finite",
+        )?;
+    }
+
+    for (index, thermal) in env.thermals.iter().enumerate() {
+        let element = format!("thermal #{index}");
+        circle(&element, thermal.center, thermal.radius)?;
+        altitude(&element, "top", thermal.top)?;
+        speed(&element, "lift", thermal.lift)?;
+    }
+
+    for (index, source) in env.bird_activity.iter().enumerate() {
+        let element = format!("bird activity source #{index}");
+        circle(&element, source.center, source.radius)?;
+        altitude(&element, "top", source.top)?;
+        positive_duration(&element, "mean_interval", source.mean_interval)?;
+    }
+
+    for (index, shower) in env.rain_showers.iter().enumerate() {
+        circle(&format!("rain shower #{index}"), shower.center, shower.radius)?;
+    }
+
+    for (index, area) in env.turbulence.iter().enumerate() {
+        let element = format!("turbulence area #{index}");
+        circle(&element, area.center, area.radius)?;
+        altitude(&element, "bottom", area.bottom)?;
+        altitude(&element, "top", area.top)?;
+    }
+
+    for (index, band) in env.icing.iter().enumerate() {
+        let element = format!("icing band #{index}");
+        circle(&element, band.center, band.radius)?;
+        altitude(&element, "bottom", band.bottom)?;
+        altitude(&element, "top", band.top)?;
+    }
+
+    for (index, bank) in env.fog.iter().enumerate() {
+        let element = format!("fog bank #{index}");
+        circle(&element, bank.center, bank.radius)?;
+        non_negative_length(&element, "visibility", bank.visibility)?;
+    }
+
+    altitude("environment", "transition_altitude", env.transition_altitude)
+}
+
+fn aerodrome(aerodrome: &store::Aerodrome) -> Result {
+    let element = format!("aerodrome {}", aerodrome.code);
+    altitude(&element, "elevation", aerodrome.elevation)?;
+
+    let network = &aerodrome.ground_network;
+    speed(&element, "taxi_speed", network.taxi_speed)?;
+    speed(&element, "apron_speed", network.apron_speed)?;
+
+    for pair in &aerodrome.runways {
+        runway_pair(&element, pair)?;
+    }
+
+    for taxiway in &network.taxiways {
+        let element = format!("taxiway {} in {element}", taxiway.name);
+        positive_length(&element, "width", taxiway.width)?;
+        ensure(&element, "endpoints", taxiway.endpoints.len() >= 2, "needs at least 2 endpoints")?;
+        for &endpoint in &taxiway.endpoints {
+            position(&element, "endpoints", endpoint)?;
+        }
+        ensure(
+            &element,
+            "endpoints",
+            taxiway.endpoints.windows(2).all(|pair| pair[0] != pair[1]),
+            "consecutive endpoints must be distinct",
+        )?;
+    }
+
+    for apron in &network.aprons {
+        let element = format!("apron {} in {element}", apron.name);
+        position(&element, "position", apron.position)?;
+        positive_length(&element, "width", apron.width)?;
+    }
+
+    Ok(())
+}
+
+fn runway_pair(aerodrome: &str, pair: &store::RunwayPair) -> Result {
+    let element = format!("runway {}/{} in {aerodrome}", pair.forward.name, pair.backward.name);
+    position(&element, "forward_start", pair.forward_start)?;
+    position(&element, "backward_start", pair.backward_start)?;
+    positive_length(&element, "width", pair.width)?;
+
+    let length = pair.forward_start.distance_exact(pair.backward_start);
+    ensure(&element, "length", length.is_positive(), "runway must have a positive length")?;
+
+    for runway in [&pair.forward, &pair.backward] {
+        in_range(
+            &element,
+            "touchdown_displacement",
+            runway.touchdown_displacement,
+            Length::ZERO..=length,
+        )?;
+        non_negative_length(&element, "stopway", runway.stopway)?;
+        non_negative_length(&element, "max_visual_distance", runway.max_visual_distance)?;
+        ensure(
+            &element,
+            "glide_angle",
+            runway.glide_angle.is_positive() && runway.glide_angle < Angle::RIGHT,
+            "must be between 0 and 90 degrees",
+        )?;
+
+        if let Some(ils) = &runway.ils {
+            for (field, angle) in [
+                ("half_width", ils.half_width),
+                ("min_pitch", ils.min_pitch),
+                ("max_pitch", ils.max_pitch),
+            ] {
+                ensure(&element, field, angle.is_finite(), "must be finite")?;
+            }
+            for (field, value) in [
+                ("horizontal_range", ils.horizontal_range),
+                ("vertical_range", ils.vertical_range),
+                ("visual_range", ils.visual_range),
+                ("decision_height", ils.decision_height),
+            ] {
+                non_negative_length(&element, field, value)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn spawn_sets(sets: &store::WeightedList<store::SpawnSet>) -> Result {
+    weights("spawn sets", sets)?;
+    for (index, entry) in sets.items.iter().enumerate() {
+        let set = &entry.item;
+        let element = format!("spawn set #{index}");
+        weights(&element, &set.route)?;
+        weights(&element, &set.gen_name)?;
+        weights(&element, &set.types)?;
+        weights(&element, &set.position)?;
+
+        for entry in &set.position.items {
+            if let store::SpawnPosition::Airborne {
+                altitude: alt, speed: spd, heading: hdg, ..
+            } = entry.item
+            {
+                altitude(&element, "altitude", alt)?;
+                speed(&element, "speed", spd)?;
+                heading(&element, "heading", hdg)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn object(object: &store::Object) -> Result {
+    match object {
+        store::Object::Plane(plane) => {
+            let aircraft = &plane.aircraft;
+            let element = format!("object {}", aircraft.name);
+            position(&element, "position", aircraft.position)?;
+            altitude(&element, "altitude", aircraft.altitude)?;
+            in_range(&element, "ground_speed", aircraft.ground_speed, -MAX_SPEED..=MAX_SPEED)?;
+            heading(&element, "ground_dir", aircraft.ground_dir)?;
+            in_range(&element, "vert_rate", aircraft.vert_rate, -MAX_SPEED..=MAX_SPEED)?;
+
+            heading(&element, "control.heading", plane.control.heading)?;
+            in_range(
+                &element,
+                "control.yaw_speed",
+                plane.control.yaw_speed,
+                -MAX_YAW_SPEED..=MAX_YAW_SPEED,
+            )?;
+            in_range(
+                &element,
+                "control.horiz_accel",
+                plane.control.horiz_accel,
+                -MAX_ACCEL..=MAX_ACCEL,
+            )?;
+
+            taxi_limits(&element, &plane.taxi_limits)?;
+            nav_limits(&element, &plane.nav_limits)?;
+        }
+        store::Object::Drifter(drifter) => {
+            let element = format!("object {}", drifter.name);
+            position(&element, "position", drifter.position)?;
+            altitude(&element, "altitude", drifter.altitude)?;
+            heading(&element, "heading", drifter.heading)?;
+            match drifter.kind {
+                store::DrifterKind::Balloon { vert_rate, ceiling } => {
+                    in_range(&element, "vert_rate", vert_rate, -MAX_SPEED..=MAX_SPEED)?;
+                    altitude(&element, "ceiling", ceiling)?;
+                }
+                store::DrifterKind::Glider { airspeed, sink_rate } => {
+                    speed(&element, "airspeed", airspeed)?;
+                    speed(&element, "sink_rate", sink_rate)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn taxi_limits(element: &str, limits: &store::TaxiLimits) -> Result {
+    in_range(element, "taxi_limits.accel", limits.accel, Accel::ZERO..=MAX_ACCEL)?;
+    in_range(element, "taxi_limits.base_braking", limits.base_braking, Accel::ZERO..=MAX_ACCEL)?;
+    speed(element, "taxi_limits.max_speed", limits.max_speed)?;
+    in_range(element, "taxi_limits.min_speed", limits.min_speed, -MAX_SPEED..=Speed::ZERO)?;
+    ensure(
+        element,
+        "taxi_limits.turn_rate",
+        limits.turn_rate.is_positive() && limits.turn_rate <= MAX_YAW_SPEED,
+        "must be positive",
+    )?;
+    positive_length(element, "taxi_limits.width", limits.width)?;
+    positive_length(element, "taxi_limits.half_length", limits.half_length)
+}
+
+fn nav_limits(element: &str, limits: &store::NavLimits) -> Result {
+    speed(element, "nav_limits.min_horiz_speed", limits.min_horiz_speed)?;
+    in_range(
+        element,
+        "nav_limits.max_yaw_speed",
+        limits.max_yaw_speed,
+        AngularSpeed::ZERO..=MAX_YAW_SPEED,
+    )?;
+    in_range(
+        element,
+        "nav_limits.max_yaw_accel",
+        limits.max_yaw_accel,
+        AngularAccel::ZERO..=MAX_YAW_ACCEL,
+    )?;
+    in_range(element, "nav_limits.max_vert_accel", limits.max_vert_accel, Accel::ZERO..=MAX_ACCEL)?;
+    in_range(
+        element,
+        "nav_limits.accel_change_rate",
+        limits.accel_change_rate,
+        AccelRate::ZERO..=MAX_ACCEL_RATE,
+    )?;
+    ensure(
+        element,
+        "nav_limits.weight",
+        limits.weight.is_finite() && limits.weight > 0.,
+        "must be positive",
+    )?;
+    ensure(
+        element,
+        "nav_limits.drag_coef",
+        (0. ..=1.).contains(&limits.drag_coef),
+        "must be between 0 and 1",
+    )?;
+    speed(element, "nav_limits.takeoff_speed", limits.takeoff_speed)?;
+    non_negative_length(element, "nav_limits.short_final_dist", limits.short_final_dist)?;
+    speed(element, "nav_limits.short_final_speed", limits.short_final_speed)?;
+
+    let profiles = [
+        ("nav_limits.exp_climb", &limits.exp_climb),
+        ("nav_limits.std_climb", &limits.std_climb),
+        ("nav_limits.level", &limits.level),
+        ("nav_limits.std_descent", &limits.std_descent),
+        ("nav_limits.exp_descent", &limits.exp_descent),
+    ];
+    for (field, profile) in profiles {
+        in_range(element, field, profile.vert_rate, -MAX_SPEED..=MAX_SPEED)?;
+        in_range(element, field, profile.accel, Accel::ZERO..=MAX_ACCEL)?;
+        in_range(element, field, profile.decel, -MAX_ACCEL..=Accel::ZERO)?;
+    }
+    ensure(
+        element,
+        "nav_limits",
+        profiles.windows(2).all(|pair| pair[0].1.vert_rate >= pair[1].1.vert_rate)
+            && limits.std_climb.vert_rate >= Speed::ZERO
+            && limits.std_descent.vert_rate <= Speed::ZERO,
+        "climb profiles must be ordered from expedited climb to expedited descent",
+    )
+}
+
+fn weights<T>(element: &str, list: &store::WeightedList<T>) -> Result {
+    ensure(
+        element,
+        "weight",
+        list.items.iter().all(|entry| entry.weight.is_finite() && entry.weight >= 0.),
+        "must be finite and non-negative",
+    )
+}
+
+fn circle(element: &str, center: Position<Vec2>, radius: Length<f32>) -> Result {
+    position(element, "center", center)?;
+    non_negative_length(element, "radius", radius)
+}
+
+fn position(element: &str, field: &'static str, value: Position<Vec2>) -> Result {
+    in_range(element, field, value.distance_exact(Position::ORIGIN), Length::ZERO..=MAX_DISTANCE)
+}
+
+fn altitude(element: &str, field: &'static str, value: Position<f32>) -> Result {
+    in_range(element, field, value, MIN_ALTITUDE..=MAX_ALTITUDE)
+}
+
+fn heading(element: &str, field: &'static str, value: Heading) -> Result {
+    ensure(element, field, value.radians().is_finite(), "must be finite")
+}
+
+fn speed(element: &str, field: &'static str, value: Speed<f32>) -> Result {
+    in_range(element, field, value, Speed::ZERO..=MAX_SPEED)
+}
+
+fn positive_length(element: &str, field: &'static str, value: Length<f32>) -> Result {
+    ensure(element, field, value.is_positive() && value <= MAX_DISTANCE, "must be positive")
+}
+
+fn non_negative_length(element: &str, field: &'static str, value: Length<f32>) -> Result {
+    in_range(element, field, value, Length::ZERO..=MAX_DISTANCE)
+}
+
+fn positive_duration(element: &str, field: &'static str, value: Duration) -> Result {
+    ensure(element, field, !value.is_zero(), "must be positive")
+}
+
+fn in_range<T: PartialOrd>(
+    element: &str,
+    field: &'static str,
+    value: T,
+    range: RangeInclusive<T>,
+) -> Result {
+    // NaN is never contained in any range.
+    ensure(element, field, range.contains(&value), "out of range")
+}
+
+fn ensure(element: &str, field: &'static str, valid: bool, reason: &'static str) -> Result {
+    if valid { Ok(()) } else { Err(Error::InvalidValue { element: element.into(), field, reason }) }
+}
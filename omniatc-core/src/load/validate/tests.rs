@@ -0,0 +1,109 @@
+use math::{Accel, Angle, AngularSpeed, Length, Position, Speed};
+
+use super::{completion_hook, runway_pair, taxi_limits, weights};
+use crate::load::Error;
+
+fn runway(name: &str) -> store::Runway {
+    store::Runway {
+        name:                   name.into(),
+        touchdown_displacement: Length::from_meters(300.),
+        stopway:                Length::ZERO,
+        glide_angle:            Angle::from_degrees(3.),
+        max_visual_distance:    Length::from_nm(5.),
+        ils:                    None,
+    }
+}
+
+fn pair() -> store::RunwayPair {
+    store::RunwayPair {
+        width:          Length::from_meters(60.),
+        forward_start:  Position::from_origin_nm(0., 1.),
+        forward:        runway("18"),
+        backward_start: Position::from_origin_nm(0., -1.),
+        backward:       runway("36"),
+        surface:        store::SurfaceCondition::default(),
+    }
+}
+
+fn taxi() -> store::TaxiLimits {
+    store::TaxiLimits {
+        accel:        Accel::from_knots_per_sec(3.),
+        base_braking: Accel::from_knots_per_sec(5.),
+        max_speed:    Speed::from_knots(30.),
+        min_speed:    Speed::from_knots(-4.),
+        turn_rate:    AngularSpeed::from_degrees_per_sec(15.),
+        width:        Length::from_meters(60.),
+        half_length:  Length::from_meters(40.),
+    }
+}
+
+fn invalid_field(result: super::Result) -> &'static str {
+    match result {
+        Err(Error::InvalidValue { field, .. }) => field,
+        Err(err) => panic!("unexpected error {err}"),
+        Ok(()) => panic!("invalid value was accepted"),
+    }
+}
+
+#[test]
+fn valid_runway_accepted() { runway_pair("MAIN", &pair()).unwrap(); }
+
+#[test]
+fn zero_length_runway_rejected() {
+    let mut pair = pair();
+    pair.backward_start = pair.forward_start;
+    assert_eq!(invalid_field(runway_pair("MAIN", &pair)), "length");
+}
+
+#[test]
+fn displacement_beyond_runway_rejected() {
+    let mut pair = pair();
+    pair.backward.touchdown_displacement = Length::from_nm(3.);
+    assert_eq!(invalid_field(runway_pair("MAIN", &pair)), "touchdown_displacement");
+}
+
+#[test]
+fn nan_position_rejected() {
+    let mut pair = pair();
+    pair.forward_start = Position::from_origin_nm(f32::NAN, 0.);
+    assert_eq!(invalid_field(runway_pair("MAIN", &pair)), "forward_start");
+}
+
+#[test]
+fn degenerate_taxi_limits_rejected() {
+    taxi_limits("A359", &taxi()).unwrap();
+
+    let mut limits = taxi();
+    limits.turn_rate = AngularSpeed::ZERO;
+    assert_eq!(invalid_field(taxi_limits("A359", &limits)), "taxi_limits.turn_rate");
+
+    let mut limits = taxi();
+    limits.base_braking = Accel::from_knots_per_sec(-5.);
+    assert_eq!(invalid_field(taxi_limits("A359", &limits)), "taxi_limits.base_braking");
+
+    let mut limits = taxi();
+    limits.max_speed = Speed::from_knots(f32::INFINITY);
+    assert_eq!(invalid_field(taxi_limits("A359", &limits)), "taxi_limits.max_speed");
+}
+
+#[test]
+fn non_finite_weights_rejected() {
+    weights("spawn sets", &store::WeightedList::from([((), 1.), ((), 0.)])).unwrap();
+    assert_eq!(
+        invalid_field(weights("spawn sets", &store::WeightedList::from([((), f32::NAN)]))),
+        "weight"
+    );
+    assert_eq!(
+        invalid_field(weights("spawn sets", &store::WeightedList::from([((), -1.)]))),
+        "weight"
+    );
+}
+
+#[test]
+fn invalid_traffic_rate_factor_rejected() {
+    let hook = |factor| store::QuestCompletionHook::ScaleTrafficRate { factor };
+    completion_hook("quest Q", &hook(1.5)).unwrap();
+    for factor in [0., -1., f32::NAN, f32::INFINITY] {
+        assert_eq!(invalid_field(completion_hook("quest Q", &hook(factor))), "factor");
+    }
+}
@@ -20,7 +20,9 @@ mod query;
 pub use query::{MapQuery, QueryWith};
 mod schedule;
 pub use schedule::{EnumScheduleConfig, configure_ordered_system_sets};
+mod world_hash;
 use serde::{Deserialize, Serialize};
+pub use world_hash::world_hash;
 
 pub struct Plug;
 
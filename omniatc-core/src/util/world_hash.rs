@@ -0,0 +1,59 @@
+use bevy::ecs::world::World;
+use bevy::math::Vec3;
+
+use crate::level::object::{self, Object};
+use crate::level::route::Route;
+
+#[cfg(test)]
+mod tests;
+
+/// Computes the hex-encoded hash of the simulation state in `world`.
+///
+/// The hash covers the name, position and velocity of each object
+/// and the number of nodes remaining in its route.
+/// Objects are hashed in the order of their projected state,
+/// so the hash does not depend on entity allocation or query iteration order.
+#[must_use]
+pub fn world_hash(world: &mut World) -> String {
+    let mut records: Vec<Vec<u8>> = world
+        .query::<(&Object, Option<&object::Display>, Option<&object::Airborne>, Option<&Route>)>()
+        .iter(world)
+        .map(|(object, display, airborne, route)| {
+            let mut record = Vec::new();
+
+            let name = display.map_or("", |display| display.name.as_str());
+            record.extend_from_slice(&(name.len() as u64).to_le_bytes());
+            record.extend_from_slice(name.as_bytes());
+
+            write_vec3(&mut record, object.position.get());
+            write_vec3(&mut record, object.ground_speed.0);
+            match airborne {
+                Some(airborne) => {
+                    record.push(1);
+                    write_vec3(&mut record, airborne.airspeed.0);
+                }
+                None => record.push(0),
+            }
+
+            let remaining_nodes = route.map_or(0, |route| route.iter().count());
+            record.extend_from_slice(&(remaining_nodes as u64).to_le_bytes());
+
+            record
+        })
+        .collect();
+    records.sort_unstable();
+
+    let mut hasher = blake3::Hasher::new_derive_key("omniatc world state");
+    hasher.update(&(records.len() as u64).to_le_bytes());
+    for record in records {
+        hasher.update(&(record.len() as u64).to_le_bytes());
+        hasher.update(&record);
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+fn write_vec3(record: &mut Vec<u8>, vec: Vec3) {
+    for component in vec.to_array() {
+        record.extend_from_slice(&component.to_bits().to_le_bytes());
+    }
+}
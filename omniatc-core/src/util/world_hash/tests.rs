@@ -0,0 +1,57 @@
+use bevy::ecs::world::World;
+use math::{Heading, Position, Speed};
+
+use super::world_hash;
+use crate::level::object::{self, Object};
+use crate::level::route::{Route, StandbyNode};
+
+fn object(name: &str, x_nm: f32) -> (Object, object::Display) {
+    (
+        Object {
+            position:     Position::from_origin_nm(x_nm, 0.)
+                .with_altitude(Position::from_amsl_feet(3000.)),
+            ground_speed: (Speed::from_knots(200.) * Heading::NORTH).horizontally(),
+        },
+        object::Display { name: name.into() },
+    )
+}
+
+#[test]
+fn independent_of_spawn_order() {
+    let mut forward = World::new();
+    forward.spawn(object("ABC123", 1.));
+    forward.spawn(object("DEF456", 2.));
+
+    let mut backward = World::new();
+    let unrelated = backward.spawn_empty().id();
+    backward.spawn(object("DEF456", 2.));
+    backward.despawn(unrelated);
+    backward.spawn(object("ABC123", 1.));
+
+    assert_eq!(world_hash(&mut forward), world_hash(&mut backward));
+}
+
+#[test]
+fn changes_with_position() {
+    let mut world = World::new();
+    let entity = world.spawn(object("ABC123", 1.)).id();
+    let before = world_hash(&mut world);
+
+    world.get_mut::<Object>(entity).unwrap().position =
+        Position::from_origin_nm(1.001, 0.).with_altitude(Position::from_amsl_feet(3000.));
+    assert_ne!(before, world_hash(&mut world));
+}
+
+#[test]
+fn changes_with_route_progress() {
+    let mut route = Route::default();
+    route.push(StandbyNode { skip_id: None }.into());
+    route.push(StandbyNode { skip_id: None }.into());
+
+    let mut world = World::new();
+    let entity = world.spawn((object("ABC123", 1.), route)).id();
+    let before = world_hash(&mut world);
+
+    world.get_mut::<Route>(entity).unwrap().shift();
+    assert_ne!(before, world_hash(&mut world));
+}
@@ -0,0 +1,454 @@
+//! Import recorded ADS-B traces as a timetable replay over an existing map.
+//!
+//! Each trace point has the fields
+//! `callsign`, `time` (UNIX seconds), `lat`, `lon` (degrees) and `altitude` (feet AMSL),
+//! optionally followed by `type`, `origin` and `destination`.
+//! Traces are read either from a CSV file with a header line naming these columns,
+//! or from a JSON array of objects with the same keys.
+//!
+//! Geographic coordinates are projected onto the map with [`GeoPoint::to_map`],
+//! and the origin is recorded in [`store::Level::geo_origin`] of the output.
+//! Each flight is spawned at the point where its interpolated trace
+//! first enters the replay radius within the replay window,
+//! or at its interpolated position at the start of the window if it is already inside.
+//! Flights whose trace starts on the ground near the aerodrome are spawned as departures.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use bevy_math::Vec2;
+use math::{GeoPoint, Heading, Length, Position, Speed};
+use serde::Deserialize;
+use store::Score;
+
+use crate::airlines;
+
+#[cfg(test)]
+mod tests;
+
+/// Traces starting below this height above the aerodrome within [`GROUND_RADIUS`]
+/// are considered departures.
+const GROUND_HEIGHT: Length<f32> = Length::from_feet(500.);
+/// Distance from the map origin within which a low trace point is considered on the aerodrome.
+const GROUND_RADIUS: Length<f32> = Length::from_nm(5.);
+/// Distance from the exit waypoint at which a departure is completed.
+const EXIT_PROXIMITY: Length<f32> = Length::from_nm(1.);
+
+/// A recorded position of a flight.
+#[derive(Clone, Deserialize)]
+pub struct TracePoint {
+    pub callsign:    String,
+    /// UNIX timestamp in seconds.
+    pub time:        f64,
+    pub lat:         f64,
+    pub lon:         f64,
+    /// Barometric altitude in feet.
+    pub altitude:    f32,
+    #[serde(default, rename = "type")]
+    pub object_type: Option<String>,
+    #[serde(default)]
+    pub origin:      Option<String>,
+    #[serde(default)]
+    pub destination: Option<String>,
+}
+
+/// Parses trace points from CSV.
+///
+/// The first non-comment line is a header naming the columns.
+/// The columns `callsign`, `time`, `lat`, `lon` and `altitude` are required,
+/// and the columns `type`, `origin` and `destination` are optional.
+/// Unknown columns are ignored.
+///
+/// # Errors
+/// If the header lacks a required column or a row is malformed.
+pub fn parse_csv(csv: &str) -> Result<Vec<TracePoint>> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (_, header) = lines.next().context("missing header line")?;
+    let header: Vec<_> = header.split(',').map(str::trim).collect();
+    let column = |name: &str| header.iter().position(|&column| column == name);
+    let required = |name: &str| column(name).with_context(|| format!("missing column {name}"));
+
+    let callsign = required("callsign")?;
+    let time = required("time")?;
+    let lat = required("lat")?;
+    let lon = required("lon")?;
+    let altitude = required("altitude")?;
+    let object_type = column("type");
+    let origin = column("origin");
+    let destination = column("destination");
+
+    lines
+        .map(|(line_number, line)| {
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            let field = |index: usize| {
+                fields
+                    .get(index)
+                    .copied()
+                    .with_context(|| format!("line {line_number}: missing field"))
+            };
+            let optional = |index: Option<usize>| {
+                index
+                    .and_then(|index| fields.get(index))
+                    .filter(|field| !field.is_empty())
+                    .map(|&field| String::from(field))
+            };
+            let number = |index: usize, name: &str| -> Result<f64> {
+                field(index)?.parse().with_context(|| format!("line {line_number}: invalid {name}"))
+            };
+
+            Ok(TracePoint {
+                callsign:    field(callsign)?.into(),
+                time:        number(time, "time")?,
+                lat:         number(lat, "lat")?,
+                lon:         number(lon, "lon")?,
+                #[expect(clippy::cast_possible_truncation, reason = "altitude precision is low")]
+                altitude:    number(altitude, "altitude")? as f32,
+                object_type: optional(object_type),
+                origin:      optional(origin),
+                destination: optional(destination),
+            })
+        })
+        .collect()
+}
+
+/// Parses trace points from a JSON array.
+///
+/// # Errors
+/// If the JSON is malformed.
+pub fn parse_json(json: &str) -> Result<Vec<TracePoint>> {
+    serde_json::from_str(json).context("parse trace json")
+}
+
+/// Parameters for converting traces into a replay.
+pub struct Options {
+    /// Geodetic position of the map origin.
+    pub origin:       GeoPoint,
+    /// Code of the aerodrome in the base map that traffic arrives at or departs from.
+    pub aerodrome:    String,
+    /// Flights are spawned when they enter this distance from the map origin.
+    pub radius:       Length<f32>,
+    /// UNIX timestamp of the start of the replay, or the earliest trace point if `None`.
+    pub start:        Option<f64>,
+    /// Length of the replay window.
+    pub duration:     Duration,
+    /// Object type for flights whose type is unknown or absent in the base map.
+    pub default_type: Option<String>,
+    /// Lead time of the generated timetable.
+    pub lead_time:    Duration,
+}
+
+/// A trace point projected onto the map.
+#[derive(Clone, Copy)]
+pub struct Sample {
+    /// Time since the start of the replay in seconds.
+    pub time:     f32,
+    pub position: Position<Vec2>,
+    pub altitude: Position<f32>,
+}
+
+/// Interpolated kinematic state of a flight.
+pub struct State {
+    pub time:     f32,
+    pub position: Position<Vec2>,
+    pub altitude: Position<f32>,
+    pub speed:    Speed<f32>,
+    pub heading:  Heading,
+}
+
+/// Finds the first state of a trace within `radius` of the map origin
+/// between the times `0` and `end`.
+///
+/// `samples` must be sorted by time.
+/// Speed and heading are derived from the trace segment containing the state.
+#[must_use]
+pub fn entry_state(samples: &[Sample], radius: Length<f32>, end: f32) -> Option<State> {
+    samples.windows(2).find_map(|pair| {
+        let &[from, to] = pair else { return None };
+        let dt = to.time - from.time;
+        if dt <= 0. || to.time < 0. || from.time > end {
+            return None;
+        }
+
+        // Clip the segment to the replay window.
+        let ratio_start = (-from.time / dt).max(0.);
+        let ratio_end = ((end - from.time) / dt).min(1.);
+
+        let start = from.position.get().lerp(to.position.get(), ratio_start);
+        let delta = to.position.get() - from.position.get();
+        let ratio = if start.length() <= radius.0 {
+            ratio_start
+        } else {
+            // Solve |from + delta * ratio| = radius for the smaller root.
+            let origin = from.position.get();
+            let a = delta.length_squared();
+            let b = 2. * origin.dot(delta);
+            let c = origin.length_squared() - radius.0 * radius.0;
+            let discriminant = b * b - 4. * a * c;
+            if a <= 0. || discriminant < 0. {
+                return None;
+            }
+            let root = (-b - discriminant.sqrt()) / (2. * a);
+            if !(ratio_start..=ratio_end).contains(&root) {
+                return None;
+            }
+            root
+        };
+
+        Some(State {
+            time:     from.time + dt * ratio,
+            position: Position::new(from.position.get() + delta * ratio),
+            altitude: from.altitude.lerp(to.altitude, ratio),
+            speed:    Speed::new(delta.length() / dt),
+            heading:  Heading::from_vec2(delta),
+        })
+    })
+}
+
+/// Converts traces into a replay of the base map.
+///
+/// Existing objects and quests of the base map are removed,
+/// and a hidden waypoint is added at the entry point of each arrival.
+///
+/// # Errors
+/// If the aerodrome or suitable route presets are not found in the base map.
+pub fn import(
+    mut map: store::File,
+    points: Vec<TracePoint>,
+    options: &Options,
+) -> Result<store::File> {
+    let Some(aerodrome) = map.level.aerodromes.iter().find(|ad| ad.code == options.aerodrome)
+    else {
+        bail!("aerodrome {} is not in the base map", options.aerodrome);
+    };
+    let elevation = aerodrome.elevation;
+
+    let start = options
+        .start
+        .or_else(|| points.iter().map(|point| point.time).min_by(f64::total_cmp))
+        .context("no trace points")?;
+    let end = options.duration.as_secs_f32();
+
+    let default_type = match &options.default_type {
+        Some(object_type) => store::ObjectTypeRef(object_type.clone()),
+        None => map
+            .level
+            .object_types
+            .keys()
+            .min_by(|a, b| a.0.cmp(&b.0))
+            .context("base map has no object types")?
+            .clone(),
+    };
+
+    let mut traces = BTreeMap::<String, Vec<TracePoint>>::new();
+    for point in points {
+        traces.entry(point.callsign.clone()).or_default().push(point);
+    }
+
+    let mut flights = Vec::new();
+    let mut waypoints = Vec::new();
+    for (callsign, mut trace) in traces {
+        trace.sort_by(|a, b| a.time.total_cmp(&b.time));
+        #[expect(clippy::cast_possible_truncation, reason = "replay windows are short")]
+        let samples: Vec<_> = trace
+            .iter()
+            .map(|point| Sample {
+                time:     (point.time - start) as f32,
+                position: GeoPoint { lat: point.lat, lon: point.lon }.to_map(options.origin),
+                altitude: Position::from_amsl_feet(point.altitude),
+            })
+            .collect();
+
+        let first = &trace[0];
+        let object_type = first
+            .object_type
+            .as_ref()
+            .map(|object_type| store::ObjectTypeRef(object_type.clone()))
+            .filter(|object_type| map.level.object_types.contains_key(object_type))
+            .unwrap_or_else(|| default_type.clone());
+
+        let departs = first.origin.as_ref().map_or_else(
+            || {
+                let first = samples[0];
+                first.altitude.distance_cmp(elevation) < GROUND_HEIGHT
+                    && first.position.get().length() < GROUND_RADIUS.0
+            },
+            |origin| *origin == options.aerodrome,
+        );
+
+        let (spawn_time, position, route) = if departs {
+            let spawn_time = samples[0].time;
+            if !(0. ..=end).contains(&spawn_time) {
+                continue;
+            }
+            let preset = best_departure(&map.level, &samples)
+                .with_context(|| format!("no departure route for {callsign}"))?;
+            (
+                spawn_time,
+                store::SpawnPosition::Aprons {
+                    aerodrome: options.aerodrome.as_str().into(),
+                    aprons:    None,
+                },
+                preset,
+            )
+        } else {
+            let Some(entry) = entry_state(&samples, options.radius, end) else { continue };
+            let preset = best_arrival(&map.level, &options.aerodrome, &entry, &samples)
+                .with_context(|| format!("no arrival route for {callsign}"))?;
+
+            let waypoint = format!("{callsign} ENTRY");
+            waypoints.push(store::Waypoint {
+                name:      waypoint.clone(),
+                position:  entry.position,
+                elevation: None,
+                navaids:   Vec::new(),
+                visual:    None,
+                hidden:    true,
+            });
+            let position = store::SpawnPosition::Airborne {
+                waypoint: waypoint.as_str().into(),
+                altitude: entry.altitude,
+                speed:    entry.speed,
+                heading:  entry.heading,
+            };
+            (entry.time, position, preset)
+        };
+
+        flights.push(store::TimetableFlight {
+            telephony: airlines::telephony(&callsign),
+            callsign,
+            object_type,
+            origin: first.origin.clone().unwrap_or_default(),
+            destination: first.destination.clone().unwrap_or_default(),
+            scheduled: Duration::from_secs_f32(spawn_time.max(0.)) + options.lead_time,
+            position,
+            route,
+        });
+    }
+
+    map.level.waypoints.extend(waypoints);
+    map.level.geo_origin = Some(options.origin);
+    map.level.spawn_trigger = store::SpawnTrigger::Timetable(store::Timetable {
+        flights,
+        lead_time: options.lead_time,
+        on_time_tolerance: Duration::from_mins(5),
+    });
+    #[expect(clippy::cast_possible_truncation, reason = "UNIX time is positive and within range")]
+    #[expect(clippy::cast_sign_loss, reason = "UNIX time is positive and within range")]
+    let time_of_day = Duration::from_secs(start as u64 % Duration::from_hours(24).as_secs());
+    map.clock.time_of_day = time_of_day;
+    map.quests = store::QuestTree::default();
+    map.objects = [].into();
+    map.meta.id = format!("{}.replay", map.meta.id);
+    map.meta.title = format!("{} (replay)", map.meta.title);
+    map.meta.description = format!("Recorded traffic at {} replayed", options.aerodrome);
+    map.meta.tags.insert("type".into(), "scenario".into());
+    map.meta.next_scenario = None;
+    Ok(map)
+}
+
+/// Positions of the named waypoints visited by a preset, in order.
+fn preset_waypoints(level: &store::Level, preset: &store::RoutePreset) -> Vec<Vec2> {
+    preset
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            store::RouteNode::DirectWaypoint {
+                waypoint: store::WaypointRef::Named(name), ..
+            } => level.waypoints.iter().find(|waypoint| waypoint.name == name.0),
+            _ => None,
+        })
+        .map(|waypoint| waypoint.position.get())
+        .collect()
+}
+
+/// Mean distance of the waypoints from the trace polyline.
+fn mean_deviation(waypoints: &[Vec2], samples: &[Sample]) -> f32 {
+    let deviation = |point: Vec2| {
+        samples
+            .windows(2)
+            .map(|pair| {
+                let (from, to) = (pair[0].position.get(), pair[1].position.get());
+                let delta = to - from;
+                let ratio = if delta == Vec2::ZERO {
+                    0.
+                } else {
+                    ((point - from).dot(delta) / delta.length_squared()).clamp(0., 1.)
+                };
+                point.distance(from + delta * ratio)
+            })
+            .fold(f32::INFINITY, f32::min)
+    };
+    #[expect(clippy::cast_precision_loss, reason = "routes are short")]
+    let count = waypoints.len() as f32;
+    waypoints.iter().map(|&point| deviation(point)).sum::<f32>() / count
+}
+
+/// Selects the arrival preset whose route starts near the entry point and follows the trace.
+fn best_arrival(
+    level: &store::Level,
+    aerodrome: &str,
+    entry: &State,
+    samples: &[Sample],
+) -> Option<store::SpawnRoute> {
+    level
+        .route_presets
+        .iter()
+        .filter(|preset| {
+            preset.destinations.iter().any(|dest| match dest {
+                store::PresetDestination::Arrival(arrival) => {
+                    arrival.aerodrome.as_ref().is_none_or(|ad| ad.0 == aerodrome)
+                }
+                store::PresetDestination::Departure(_) => false,
+            })
+        })
+        .filter_map(|preset| {
+            let ref_id = preset.ref_id.as_ref()?;
+            let waypoints = preset_waypoints(level, preset);
+            let &first = waypoints.first()?;
+            let cost = first.distance(entry.position.get()) + mean_deviation(&waypoints, samples);
+            Some((ref_id, cost))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(ref_id, _)| store::SpawnRoute {
+            preset:      ref_id.clone(),
+            destination: store::Destination::Landing { aerodrome: aerodrome.into() },
+            score:       Score(10),
+        })
+}
+
+/// Selects the departure preset whose route follows the trace most closely.
+fn best_departure(level: &store::Level, samples: &[Sample]) -> Option<store::SpawnRoute> {
+    level
+        .route_presets
+        .iter()
+        .filter_map(|preset| {
+            let exit = preset.destinations.iter().find_map(|dest| match dest {
+                store::PresetDestination::Departure(departure) => departure.waypoint.as_ref(),
+                store::PresetDestination::Arrival(_) => None,
+            })?;
+            let ref_id = preset.ref_id.as_ref()?;
+            let waypoints = preset_waypoints(level, preset);
+            if waypoints.is_empty() {
+                return None;
+            }
+            // Prefer complete routes over their suffixes starting at later waypoints.
+            #[expect(clippy::cast_precision_loss, reason = "routes are short")]
+            let cost = mean_deviation(&waypoints, samples) - waypoints.len() as f32 * 1e-3;
+            Some((ref_id, exit, cost))
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        .map(|(ref_id, exit, _)| store::SpawnRoute {
+            preset:      ref_id.clone(),
+            destination: store::Destination::Departure {
+                min_altitude:       None,
+                waypoint_proximity: Some((store::WaypointRef::Named(exit.clone()), EXIT_PROXIMITY)),
+            },
+            score:       Score(10),
+        })
+}
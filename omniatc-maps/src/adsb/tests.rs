@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use bevy_math::Vec2;
+use math::{GeoPoint, Length, Position};
+
+use super::{Options, Sample, entry_state, import, parse_csv};
+use crate::demo;
+
+fn options() -> Options {
+    Options {
+        origin:       GeoPoint { lat: 0., lon: 0. },
+        aerodrome:    "MAIN".into(),
+        radius:       Length::from_nm(40.),
+        start:        Some(1000.),
+        duration:     Duration::from_hours(1),
+        default_type: None,
+        lead_time:    Duration::from_mins(15),
+    }
+}
+
+#[test]
+fn parse_csv_columns() {
+    let points = parse_csv(
+        "
+            # exported traces
+            lat,lon,time,callsign,altitude,type,ignored
+            1.5,-0.5,1000,CPA421,12000,A359,x
+            1.0,-0.25,1060,CPA421,11000,,x
+        ",
+    )
+    .expect("parse csv");
+
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0].callsign, "CPA421");
+    assert!((points[0].lon + 0.5).abs() < 1e-9);
+    assert_eq!(points[0].object_type.as_deref(), Some("A359"));
+    assert_eq!(points[1].object_type, None);
+    assert!(points[0].origin.is_none());
+}
+
+#[test]
+fn parse_csv_missing_column() {
+    assert!(parse_csv("callsign,time,lat,lon\nCPA421,1000,1,1\n").is_err());
+}
+
+#[test]
+fn entry_interpolates_radius_crossing() {
+    let samples = [
+        Sample {
+            time:     0.,
+            position: Position::from_origin_nm(0., 60.),
+            altitude: Position::from_amsl_feet(12000.),
+        },
+        Sample {
+            time:     100.,
+            position: Position::from_origin_nm(0., 20.),
+            altitude: Position::from_amsl_feet(8000.),
+        },
+    ];
+    let entry = entry_state(&samples, Length::from_nm(40.), 3600.).expect("enters radius");
+
+    assert!((entry.time - 50.).abs() < 1e-3);
+    entry
+        .position
+        .assert_near(Position::from_origin_nm(0., 40.), Length::from_nm(1e-3))
+        .expect("entry position");
+    assert!((entry.altitude.amsl().into_feet() - 10000.).abs() < 1.);
+    assert!((entry.speed.into_knots() - 1440.).abs() < 1.);
+    assert!((entry.heading.degrees() - 180.).abs() < 1e-3);
+}
+
+#[test]
+fn entry_inside_radius_at_start() {
+    let samples = [
+        Sample {
+            time:     -100.,
+            position: Position::from_origin_nm(10., 0.),
+            altitude: Position::from_amsl_feet(5000.),
+        },
+        Sample {
+            time:     100.,
+            position: Position::from_origin_nm(-10., 0.),
+            altitude: Position::from_amsl_feet(5000.),
+        },
+    ];
+    let entry = entry_state(&samples, Length::from_nm(40.), 3600.).expect("inside at start");
+
+    assert!(entry.time.abs() < 1e-3);
+    assert!(entry.position.get().abs_diff_eq(Vec2::ZERO, 1e-3));
+}
+
+#[test]
+fn import_arrival_into_demo() {
+    // One degree of latitude is 60 nm at the equator.
+    let points = parse_csv(
+        "
+            callsign,time,lat,lon,altitude,destination
+            CPA421,1000,-1.0,0.0,12000,MAIN
+            CPA421,1600,-0.5,0.0,8000,MAIN
+            CPA421,2200,0.0,0.0,0,MAIN
+            LATE1,9000,-1.0,0.0,12000,MAIN
+            LATE1,9600,-0.5,0.0,8000,MAIN
+        ",
+    )
+    .expect("parse csv");
+
+    let file = import(demo::file(), points, &options()).expect("import traces");
+
+    let store::SpawnTrigger::Timetable(timetable) = &file.level.spawn_trigger else {
+        panic!("spawn trigger should be a timetable");
+    };
+    let [flight] = timetable.flights.as_slice() else {
+        panic!("only CPA421 enters within the replay window");
+    };
+    assert_eq!(flight.callsign, "CPA421");
+    assert!(
+        flight.scheduled.abs_diff(Duration::from_secs(400 + 15 * 60)) < Duration::from_millis(10)
+    );
+    let store::SpawnPosition::Airborne { waypoint, .. } = &flight.position else {
+        panic!("arrivals should spawn airborne");
+    };
+    let entry = file
+        .level
+        .waypoints
+        .iter()
+        .find(|candidate| candidate.name == waypoint.0)
+        .expect("entry waypoint is added");
+    assert!(entry.hidden);
+    assert!((entry.position.get().length() - 40.).abs() < 1e-2);
+    assert!(file.objects.is_empty());
+}
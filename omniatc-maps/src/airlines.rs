@@ -0,0 +1,61 @@
+/// An entry in the airline database.
+pub struct Airline {
+    /// ICAO designator used as the callsign prefix.
+    pub icao:      &'static str,
+    /// Telephony designator used in radio communication.
+    pub telephony: &'static str,
+    /// Full name of the airline.
+    pub name:      &'static str,
+}
+
+/// Known airlines, sorted by ICAO designator.
+pub const AIRLINES: &[Airline] = &[
+    Airline { icao: "AAL", telephony: "American", name: "American Airlines" },
+    Airline { icao: "AFR", telephony: "Airfrans", name: "Air France" },
+    Airline { icao: "ANA", telephony: "All Nippon", name: "All Nippon Airways" },
+    Airline { icao: "BAW", telephony: "Speedbird", name: "British Airways" },
+    Airline { icao: "CPA", telephony: "Cathay", name: "Cathay Pacific" },
+    Airline { icao: "DAL", telephony: "Delta", name: "Delta Air Lines" },
+    Airline { icao: "DLH", telephony: "Lufthansa", name: "Lufthansa" },
+    Airline { icao: "EIN", telephony: "Shamrock", name: "Aer Lingus" },
+    Airline { icao: "FIN", telephony: "Finnair", name: "Finnair" },
+    Airline { icao: "JAL", telephony: "Japan Air", name: "Japan Airlines" },
+    Airline { icao: "KLM", telephony: "KLM", name: "KLM Royal Dutch Airlines" },
+    Airline { icao: "QFA", telephony: "Qantas", name: "Qantas" },
+    Airline { icao: "RYR", telephony: "Ryanair", name: "Ryanair" },
+    Airline { icao: "SIA", telephony: "Singapore", name: "Singapore Airlines" },
+    Airline { icao: "SWR", telephony: "Swiss", name: "Swiss International Air Lines" },
+    Airline { icao: "UAE", telephony: "Emirates", name: "Emirates" },
+    Airline { icao: "UAL", telephony: "United", name: "United Airlines" },
+];
+
+/// Finds an airline by its ICAO designator.
+#[must_use]
+pub fn find(icao: &str) -> Option<&'static Airline> {
+    AIRLINES.binary_search_by_key(&icao, |airline| airline.icao).ok().map(|index| &AIRLINES[index])
+}
+
+/// Returns the radio telephony callsign for an airline callsign, e.g. `Speedbird 27` for `BAW27`.
+///
+/// Returns `None` if the callsign does not start with a known ICAO designator.
+#[must_use]
+pub fn telephony(callsign: &str) -> Option<String> {
+    let (icao, flight_number) = callsign.split_at_checked(3)?;
+    let airline = find(icao)?;
+    Some(format!("{} {flight_number}", airline.telephony))
+}
+
+/// Creates a name generator for an airline in the database.
+///
+/// # Panics
+/// If `icao` is not in the database.
+#[must_use]
+pub fn name_generator(icao: &str, digits: u16) -> store::NameGenerator {
+    let airline = find(icao).unwrap_or_else(|| panic!("unknown airline {icao}"));
+    store::NameGenerator::Airline {
+        prefix: airline.icao.into(),
+        digits,
+        trailing_letter: None,
+        telephony: Some(airline.telephony.into()),
+    }
+}
@@ -5,14 +5,16 @@ use crate::demo;
 pub fn file() -> store::File {
     let mut map = demo::file();
     map.meta = store::Meta {
-        id:          "omniatc.blank".into(),
-        title:       "Blank".into(),
-        description: "Blank map for tests".into(),
-        authors:     ["omniatc".into()].into(),
-        tags:        [("region", "fictional"), ("source", "builtin"), ("type", "scenario")]
+        id:            "omniatc.blank".into(),
+        title:         "Blank".into(),
+        description:   "Blank map for tests".into(),
+        authors:       ["omniatc".into()].into(),
+        tags:          [("region", "fictional"), ("source", "builtin"), ("type", "scenario")]
             .into_iter()
             .map(|(k, v)| (String::from(k), String::from(v)))
             .collect(),
+        next_scenario: None,
+        mode:          store::FacilityMode::Full,
     };
     map.level.spawn_sets = [].into();
     map.level.spawn_trigger = store::SpawnTrigger::Disabled;
@@ -1,13 +1,14 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use bevy_math::Vec2;
 use math::{
     Accel, Angle, AngularSpeed, Heading, ISA_SEA_LEVEL_PRESSURE, ISA_SEA_LEVEL_TEMPERATURE, Length,
-    Position, Speed,
+    Position, Speed, TurnDirection,
 };
 use store::{Score, WaypointProximity, WeightedList};
 
-use crate::common_types;
+use crate::{airlines, common_types};
 
 #[must_use]
 pub fn route_retry_18r() -> Vec<store::RouteNode> {
@@ -47,7 +48,7 @@ pub fn route_retry_18r() -> Vec<store::RouteNode> {
 }
 
 #[must_use]
-pub fn route_dwind_18l() -> Vec<store::RouteNode> {
+pub fn route_dwind_arrival() -> Vec<store::RouteNode> {
     [
         store::RouteNode::DirectWaypoint {
             waypoint:  store::WaypointRef::Named("DWIND".into()),
@@ -69,88 +70,34 @@ pub fn route_dwind_18l() -> Vec<store::RouteNode> {
             proximity: WaypointProximity::FlyBy,
             altitude:  None,
         },
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("APPNE".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  None,
-        },
-        store::RouteNode::SetAirSpeed { goal: Speed::from_knots(180.), error: None },
-        store::RouteNode::RunwayLanding {
-            runway:          store::RunwayRef {
-                aerodrome:   "MAIN".into(),
-                runway_name: "18L".into(),
-            },
-            goaround_preset: Some("RETRY.RETRY18R".into()),
-            current_phase:   store::LandingPhase::Align,
-        },
     ]
-    .into_iter()
-    .chain(route_taxi_runway_east_to_tango())
-    .collect()
+    .into()
 }
 
 #[must_use]
-pub fn route_dwind_18r() -> Vec<store::RouteNode> {
+pub fn route_polar_arrival() -> Vec<store::RouteNode> {
     [
         store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("DWIND".into()),
+            waypoint:  store::WaypointRef::Named("POLAR".into()),
             distance:  Length::from_nm(1.),
             proximity: WaypointProximity::FlyBy,
             altitude:  None,
         },
         store::RouteNode::SetAirSpeed { goal: Speed::from_knots(250.), error: None },
         store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("LONG".into()),
+            waypoint:  store::WaypointRef::Named("SHORT".into()),
             distance:  Length::from_nm(1.),
             proximity: WaypointProximity::FlyBy,
             altitude:  Some(Position::from_amsl_feet(4000.)),
         },
         store::RouteNode::SetAirSpeed { goal: Speed::from_knots(200.), error: None },
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("SHORT".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  None,
-        },
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("APPNW".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  None,
-        },
-        store::RouteNode::SetAirSpeed { goal: Speed::from_knots(180.), error: None },
-        store::RouteNode::RunwayLanding {
-            runway:          store::RunwayRef {
-                aerodrome:   "MAIN".into(),
-                runway_name: "18R".into(),
-            },
-            goaround_preset: Some("RETRY.RETRY18R".into()),
-            current_phase:   store::LandingPhase::Align,
-        },
     ]
-    .into_iter()
-    .chain(route_taxi_runway_west_to_tango())
-    .collect()
+    .into()
 }
 
 #[must_use]
-pub fn route_polar_18l() -> Vec<store::RouteNode> {
+pub fn route_final_18l() -> Vec<store::RouteNode> {
     [
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("POLAR".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  None,
-        },
-        store::RouteNode::SetAirSpeed { goal: Speed::from_knots(250.), error: None },
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("SHORT".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  Some(Position::from_amsl_feet(4000.)),
-        },
-        store::RouteNode::SetAirSpeed { goal: Speed::from_knots(200.), error: None },
         store::RouteNode::DirectWaypoint {
             waypoint:  store::WaypointRef::Named("APPNE".into()),
             distance:  Length::from_nm(1.),
@@ -173,22 +120,8 @@ pub fn route_polar_18l() -> Vec<store::RouteNode> {
 }
 
 #[must_use]
-pub fn route_polar_18r() -> Vec<store::RouteNode> {
+pub fn route_final_18r() -> Vec<store::RouteNode> {
     [
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("POLAR".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  None,
-        },
-        store::RouteNode::SetAirSpeed { goal: Speed::from_knots(250.), error: None },
-        store::RouteNode::DirectWaypoint {
-            waypoint:  store::WaypointRef::Named("SHORT".into()),
-            distance:  Length::from_nm(1.),
-            proximity: WaypointProximity::FlyBy,
-            altitude:  Some(Position::from_amsl_feet(4000.)),
-        },
-        store::RouteNode::SetAirSpeed { goal: Speed::from_knots(200.), error: None },
         store::RouteNode::DirectWaypoint {
             waypoint:  store::WaypointRef::Named("APPNW".into()),
             distance:  Length::from_nm(1.),
@@ -210,6 +143,39 @@ pub fn route_polar_18r() -> Vec<store::RouteNode> {
     .collect()
 }
 
+#[must_use]
+pub fn route_dwind_18l() -> Vec<store::RouteNode> {
+    route_dwind_arrival().into_iter().chain(route_final_18l()).collect()
+}
+
+#[must_use]
+pub fn route_polar_18l() -> Vec<store::RouteNode> {
+    route_polar_arrival().into_iter().chain(route_final_18l()).collect()
+}
+
+/// Route presets of an arrival route that continues with the preset `include`.
+fn arrival_presets(
+    id: &str,
+    title: &str,
+    nodes: Vec<store::RouteNode>,
+    include: &str,
+    equipage: store::Equipage,
+) -> Vec<store::RoutePreset> {
+    store::route_presets_at_waypoints(
+        id,
+        title,
+        nodes,
+        store::PresetDestination::arrival("MAIN"),
+        equipage,
+    )
+    .into_iter()
+    .map(|preset| store::RoutePreset {
+        include: Some(store::PresetInclude::new(include)),
+        ..preset
+    })
+    .collect()
+}
+
 #[must_use]
 pub fn route_taxi_runway_east_to_tango() -> Vec<store::RouteNode> {
     [
@@ -353,15 +319,16 @@ fn rapid_exit_taxiways() -> impl Iterator<Item = store::Taxiway> {
             let taxiway_endpoint = runway_endpoint
                 + FIRST_TAXIWAY_OFFSET * (runway_to_taxiway + angle * angle_dir) / angle.cos();
             store::Taxiway {
-                name:      format!("{prefix}{}", index + 1),
-                endpoints: [
+                name:        format!("{prefix}{}", index + 1),
+                endpoints:   [
                     runway_endpoint,
                     taxiway_endpoint,
                     taxiway_endpoint
                         + (SECOND_TAXIWAY_OFFSET - FIRST_TAXIWAY_OFFSET) * runway_to_taxiway,
                 ]
                 .into(),
-                width:     TAXIWAY_WIDTH,
+                width:       TAXIWAY_WIDTH,
+                deicing_pad: false,
             }
         })
     })
@@ -370,16 +337,16 @@ fn rapid_exit_taxiways() -> impl Iterator<Item = store::Taxiway> {
 #[must_use]
 pub fn level() -> store::Level {
     store::Level {
-        environment:   store::Environment {
-            heightmap:  store::HeatMap2 {
+        environment:    store::Environment {
+            heightmap:           store::HeatMap2 {
                 aligned: store::AlignedHeatMap2::constant(Position::from_amsl_feet(0.)),
                 sparse:  store::SparseHeatMap2 { functions: [].into() },
             },
-            visibility: store::HeatMap2 {
+            visibility:          store::HeatMap2 {
                 aligned: store::AlignedHeatMap2::constant(Length::from_nm(1000.)),
                 sparse:  store::SparseHeatMap2 { functions: [].into() },
             },
-            weather:    [store::Weather {
+            weather:             [store::Weather {
                 start:                Position::from_origin_nm(-1000., -1000.),
                 end:                  Position::from_origin_nm(1000., 1000.),
                 sea_pressure:         ISA_SEA_LEVEL_PRESSURE,
@@ -390,8 +357,55 @@ pub fn level() -> store::Level {
                 wind_rotation_per_nm: Angle::from_degrees(5.0),
             }]
             .into(),
+            thermals:            [store::Thermal {
+                center: Position::from_origin_nm(-15., 10.),
+                radius: Length::from_nm(0.5),
+                lift:   Speed::from_fpm(500.),
+                top:    Position::from_amsl_feet(6000.),
+            }]
+            .into(),
+            bird_activity:       [store::BirdActivitySource {
+                center:        Position::from_origin_nm(0., 3.),
+                radius:        Length::from_nm(1.5),
+                top:           Position::from_amsl_feet(2000.),
+                mean_interval: Duration::from_mins(10),
+                duration:      Duration::from_mins(3),
+            }]
+            .into(),
+            rain_showers:        [store::RainShower {
+                center:   Position::from_origin_nm(0., 0.),
+                radius:   Length::from_nm(10.),
+                start:    Duration::from_mins(15),
+                duration: Duration::from_mins(10),
+            }]
+            .into(),
+            turbulence:          [store::TurbulenceArea {
+                center:    Position::from_origin_nm(6., -24.),
+                radius:    Length::from_nm(5.),
+                bottom:    Position::from_amsl_feet(10000.),
+                top:       Position::from_amsl_feet(14000.),
+                intensity: store::TurbulenceIntensity::Moderate,
+            }]
+            .into(),
+            icing:               [store::IcingBand {
+                center: Position::from_origin_nm(8., 24.),
+                radius: Length::from_nm(8.),
+                bottom: Position::from_amsl_feet(8500.),
+                top:    Position::from_amsl_feet(11000.),
+            }]
+            .into(),
+            fog:                 [store::FogBank {
+                center:     Position::from_origin_nm(0., 0.),
+                radius:     Length::from_nm(6.),
+                visibility: Length::from_meters(150.),
+                start:      Duration::from_mins(30),
+                duration:   Duration::from_mins(15),
+            }]
+            .into(),
+            winter_ops:          None,
+            transition_altitude: Position::from_amsl_feet(18000.),
         },
-        object_types:  [(
+        object_types:   [(
             "A359",
             store::ObjectType {
                 full_name:   "Airbus A350-900".into(),
@@ -399,64 +413,70 @@ pub fn level() -> store::Level {
                 class:       store::ObjectClassSpec::Plane {
                     nav_limits: common_types::a359_nav_limits(),
                 },
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
             },
         )]
         .into_iter()
         .map(|(k, v)| (store::ObjectTypeRef(k.into()), v))
         .collect(),
-        aerodromes:    [store::Aerodrome {
+        aerodromes:     [store::Aerodrome {
             code:           "MAIN".into(),
             full_name:      "Main Airport".into(),
             elevation:      MAIN_AERODROME_ELEVATION,
             ground_network: store::GroundNetwork {
                 taxiways:    [
                     store::Taxiway {
-                        name:      "A".into(),
-                        endpoints: [
+                        name:        "A".into(),
+                        endpoints:   [
                             TOP_LEFT_ORIGIN
                                 + Length::from_components(FIRST_TAXIWAY_OFFSET, Length::ZERO),
                             BOTTOM_LEFT_ORIGIN
                                 + Length::from_components(FIRST_TAXIWAY_OFFSET, Length::ZERO),
                         ]
                         .into(),
-                        width:     TAXIWAY_WIDTH,
+                        width:       TAXIWAY_WIDTH,
+                        deicing_pad: false,
                     },
                     store::Taxiway {
-                        name:      "B".into(),
-                        endpoints: [
+                        name:        "B".into(),
+                        endpoints:   [
                             TOP_RIGHT_ORIGIN
                                 + Length::from_components(-FIRST_TAXIWAY_OFFSET, Length::ZERO),
                             BOTTOM_RIGHT_ORIGIN
                                 + Length::from_components(-FIRST_TAXIWAY_OFFSET, Length::ZERO),
                         ]
                         .into(),
-                        width:     TAXIWAY_WIDTH,
+                        width:       TAXIWAY_WIDTH,
+                        deicing_pad: false,
                     },
                     store::Taxiway {
-                        name:      "J".into(),
-                        endpoints: [
+                        name:        "J".into(),
+                        endpoints:   [
                             TOP_LEFT_ORIGIN
                                 + Length::from_components(SECOND_TAXIWAY_OFFSET, Length::ZERO),
                             BOTTOM_LEFT_ORIGIN
                                 + Length::from_components(SECOND_TAXIWAY_OFFSET, Length::ZERO),
                         ]
                         .into(),
-                        width:     TAXIWAY_WIDTH,
+                        width:       TAXIWAY_WIDTH,
+                        deicing_pad: false,
                     },
                     store::Taxiway {
-                        name:      "K".into(),
-                        endpoints: [
+                        name:        "K".into(),
+                        endpoints:   [
                             TOP_RIGHT_ORIGIN
                                 + Length::from_components(-SECOND_TAXIWAY_OFFSET, Length::ZERO),
                             BOTTOM_RIGHT_ORIGIN
                                 + Length::from_components(-SECOND_TAXIWAY_OFFSET, Length::ZERO),
                         ]
                         .into(),
-                        width:     TAXIWAY_WIDTH,
+                        width:       TAXIWAY_WIDTH,
+                        deicing_pad: false,
                     },
                     store::Taxiway {
-                        name:      "T".into(),
-                        endpoints: [
+                        name:        "T".into(),
+                        endpoints:   [
                             TOP_LEFT_ORIGIN
                                 + Length::from_components(
                                     FIRST_TAXIWAY_OFFSET,
@@ -469,11 +489,12 @@ pub fn level() -> store::Level {
                                 ),
                         ]
                         .into(),
-                        width:     TAXIWAY_WIDTH,
+                        width:       TAXIWAY_WIDTH,
+                        deicing_pad: false,
                     },
                     store::Taxiway {
-                        name:      "U".into(),
-                        endpoints: [
+                        name:        "U".into(),
+                        endpoints:   [
                             BOTTOM_LEFT_ORIGIN
                                 + Length::from_components(
                                     FIRST_TAXIWAY_OFFSET,
@@ -486,7 +507,8 @@ pub fn level() -> store::Level {
                                 ),
                         ]
                         .into(),
-                        width:     TAXIWAY_WIDTH,
+                        width:       TAXIWAY_WIDTH,
+                        deicing_pad: false,
                     },
                 ]
                 .into_iter()
@@ -569,6 +591,7 @@ pub fn level() -> store::Level {
                             decision_height:  Length::from_feet(100.),
                         }),
                     },
+                    surface:        store::SurfaceCondition::Dry,
                 },
                 store::RunwayPair {
                     width:          RUNWAY_WIDTH,
@@ -606,12 +629,25 @@ pub fn level() -> store::Level {
                             decision_height:  Length::from_feet(100.),
                         }),
                     },
+                    surface:        store::SurfaceCondition::Dry,
                 },
             ]
             .into(),
+            holds:          [store::HoldingPattern {
+                fix:             store::WaypointRef::Named(store::NamedWaypointRef("POLAR".into())),
+                inbound_heading: Heading::SOUTH,
+                direction:       TurnDirection::Clockwise,
+                leg_time:        Duration::from_mins(1),
+                min_altitude:    Position::from_amsl_feet(6000.),
+                level_spacing:   Length::from_feet(1000.),
+                levels:          4,
+            }]
+            .into(),
+            closures:       Vec::new(),
+            configurations: Vec::new(),
         }]
         .into(),
-        waypoints:     [
+        waypoints:      [
             store::Waypoint {
                 name:      "EXITS".into(),
                 position:  Position::from_origin_nm(15., 1.),
@@ -728,30 +764,82 @@ pub fn level() -> store::Level {
             },
         ]
         .into(),
-        route_presets: [
+        airways:        [store::Airway {
+            name:         "A1".into(),
+            waypoints:    ["OCEAN", "EXITS", "POLAR"].map(Into::into).into(),
+            min_altitude: Some(Position::from_amsl_feet(5000.)),
+            max_altitude: Some(Position::from_amsl_feet(24000.)),
+        }]
+        .into(),
+        sectors:        [
+            store::Sector {
+                name:         "APP".into(),
+                boundary:     [
+                    Position::from_origin_nm(-15., -15.),
+                    Position::from_origin_nm(15., -15.),
+                    Position::from_origin_nm(15., 30.),
+                    Position::from_origin_nm(-15., 30.),
+                ]
+                .into(),
+                min_altitude: None,
+                max_altitude: Some(Position::from_amsl_feet(10000.)),
+            },
+            store::Sector {
+                name:         "CTR".into(),
+                boundary:     [
+                    Position::from_origin_nm(-40., -40.),
+                    Position::from_origin_nm(40., -40.),
+                    Position::from_origin_nm(40., 50.),
+                    Position::from_origin_nm(-40., 50.),
+                ]
+                .into(),
+                min_altitude: Some(Position::from_amsl_feet(10000.)),
+                max_altitude: Some(Position::from_amsl_feet(24000.)),
+            },
+        ]
+        .into(),
+        route_presets:  [
             store::route_presets_at_waypoints(
-                "DWIND18L",
-                "DWIND 18L",
-                route_dwind_18l(),
+                "FINAL18L",
+                "Final 18L",
+                route_final_18l(),
                 store::PresetDestination::arrival("MAIN"),
+                store::Equipage::empty(),
             ),
             store::route_presets_at_waypoints(
+                "FINAL18R",
+                "Final 18R",
+                route_final_18r(),
+                store::PresetDestination::arrival("MAIN"),
+                store::Equipage::empty(),
+            ),
+            arrival_presets(
+                "DWIND18L",
+                "DWIND 18L",
+                route_dwind_arrival(),
+                "FINAL18L APPNE",
+                store::Equipage::empty(),
+            ),
+            arrival_presets(
                 "DWIND18R",
                 "DWIND 18R",
-                route_dwind_18r(),
-                store::PresetDestination::arrival("MAIN"),
+                route_dwind_arrival(),
+                "FINAL18R APPNW",
+                store::Equipage::empty(),
             ),
-            store::route_presets_at_waypoints(
+            arrival_presets(
                 "POLAR18L",
                 "POLAR 18L",
-                route_polar_18l(),
-                store::PresetDestination::arrival("MAIN"),
+                route_polar_arrival(),
+                "FINAL18L APPNE",
+                store::Equipage::RNAV,
             ),
-            store::route_presets_at_waypoints(
+            arrival_presets(
                 "POLAR18R",
                 "POLAR 18R",
-                route_polar_18r(),
-                store::PresetDestination::arrival("MAIN"),
+                route_polar_arrival(),
+                "FINAL18R APPNW",
+                store::Equipage::RNAV,
             ),
             [store::RoutePreset {
                 trigger:      store::RoutePresetTrigger::Waypoint(store::WaypointRef::Named(
@@ -762,6 +850,8 @@ pub fn level() -> store::Level {
                 title:        "Missed approach 18R".into(),
                 nodes:        route_retry_18r(),
                 destinations: [store::PresetDestination::arrival("MAIN")].into(),
+                equipage:     store::Equipage::empty(),
+                include:      None,
             }]
             .into(),
             store::route_presets_at_waypoints(
@@ -769,49 +859,116 @@ pub fn level() -> store::Level {
                 "EXITS 18R",
                 route_sid_exits_18r(),
                 store::PresetDestination::departure("EXITS"),
+                store::Equipage::RNAV,
             ),
         ]
         .into_iter()
         .flatten()
         .collect(),
-        spawn_sets:    [(
-            store::SpawnSet {
-                route:    WeightedList::singleton(store::SpawnRoute {
-                    preset:      store::RoutePresetRef("DWIND18L DWIND".into()),
-                    destination: store::Destination::Landing { aerodrome: "MAIN".into() },
-                    score:       Score(10),
-                }),
-                gen_name: [
-                    (
-                        store::NameGenerator::Airline {
-                            prefix:          "RND".into(),
-                            digits:          3,
-                            trailing_letter: None,
-                        },
-                        1.0,
-                    ),
-                    (
-                        store::NameGenerator::Airline {
-                            prefix:          "FRT".into(),
-                            digits:          3,
-                            trailing_letter: Some("XYZ".into()),
-                        },
-                        1.0,
-                    ),
-                ]
-                .into(),
-                types:    [(store::ObjectTypeRef("A359".into()), 1.0)].into(),
-                position: WeightedList::singleton(store::SpawnPosition::Airborne {
-                    waypoint: "OCEAN".into(),
-                    altitude: Position::from_amsl_feet(12000.0),
-                    speed:    Speed::from_knots(280.0),
-                    heading:  Heading::from_degrees(300.0),
-                }),
-            },
-            1.0,
-        )]
+        spawn_sets:     [
+            (
+                store::SpawnSet {
+                    route:     WeightedList::singleton(store::SpawnRoute {
+                        preset:      store::RoutePresetRef("DWIND18L DWIND".into()),
+                        destination: store::Destination::Landing { aerodrome: "MAIN".into() },
+                        score:       Score(10),
+                    }),
+                    gen_name:  [
+                        (
+                            store::NameGenerator::Airline {
+                                prefix:          "RND".into(),
+                                digits:          3,
+                                trailing_letter: None,
+                                telephony:       Some("Random".into()),
+                            },
+                            1.0,
+                        ),
+                        (
+                            store::NameGenerator::Airline {
+                                prefix:          "FRT".into(),
+                                digits:          3,
+                                trailing_letter: Some("XYZ".into()),
+                                telephony:       Some("Freighter".into()),
+                            },
+                            1.0,
+                        ),
+                        (airlines::name_generator("BAW", 2), 0.5),
+                        (airlines::name_generator("CPA", 3), 0.5),
+                    ]
+                    .into(),
+                    types:     [(store::ObjectTypeRef("A359".into()), 1.0)].into(),
+                    position:  WeightedList::singleton(store::SpawnPosition::Airborne {
+                        waypoint: "OCEAN".into(),
+                        altitude: Position::from_amsl_feet(12000.0),
+                        speed:    Speed::from_knots(280.0),
+                        heading:  Heading::from_degrees(300.0),
+                    }),
+                    endurance: Some(store::SpawnEndurance {
+                        min: Duration::from_mins(25),
+                        max: Duration::from_mins(90),
+                    }),
+                    vfr:       None,
+                    formation: None,
+                    id:        None,
+                    locked:    false,
+                },
+                1.0,
+            ),
+            (
+                store::SpawnSet {
+                    route:     WeightedList::singleton(store::SpawnRoute {
+                        preset:      store::RoutePresetRef("DWIND18L DWIND".into()),
+                        destination: store::Destination::Landing { aerodrome: "MAIN".into() },
+                        score:       Score(5),
+                    }),
+                    gen_name:  [
+                        (
+                            store::NameGenerator::Registration {
+                                region: store::RegistrationRegion::UnitedStates,
+                            },
+                            2.0,
+                        ),
+                        (
+                            store::NameGenerator::Registration {
+                                region: store::RegistrationRegion::UnitedKingdom,
+                            },
+                            1.0,
+                        ),
+                    ]
+                    .into(),
+                    types:     [(store::ObjectTypeRef("A359".into()), 1.0)].into(),
+                    position:  WeightedList::singleton(store::SpawnPosition::Airborne {
+                        waypoint: "SHADE".into(),
+                        altitude: Position::from_amsl_feet(3500.0),
+                        speed:    Speed::from_knots(180.0),
+                        heading:  Heading::from_degrees(0.0),
+                    }),
+                    endurance: None,
+                    vfr:       Some(store::SpawnVfr {
+                        reporting_points: vec!["CLIFF".into(), "SHADE".into(), "RETRY".into()],
+                        legs:             3,
+                    }),
+                    formation: None,
+                    id:        None,
+                    locked:    false,
+                },
+                0.2,
+            ),
+        ]
         .into(),
-        spawn_trigger: store::SpawnTrigger::Periodic { duration: Duration::from_mins(1) },
+        spawn_trigger:  store::SpawnTrigger::Periodic { duration: Duration::from_mins(1) },
+        excluded_names: Vec::new(),
+        pilot_requests: store::PilotRequests {
+            mean_interval: Some(Duration::from_mins(3)),
+            kinds:         [
+                (store::PilotRequestKind::Climb { delta: Length::from_feet(2000.) }, 1.0),
+                (store::PilotRequestKind::Direct, 2.0),
+                (store::PilotRequestKind::Deviation { max_angle: Angle::from_degrees(30.) }, 1.0),
+            ]
+            .into(),
+        },
+        scripts:        Vec::new(),
+        geo_origin:     None,
     }
 }
 
@@ -820,31 +977,57 @@ pub fn level() -> store::Level {
 pub fn file() -> store::File {
     store::File {
         meta:  store::Meta {
-            id:          "omniatc.demo".into(),
-            title:       "Demo".into(),
-            description: "Map for testing and demonstrating game features".into(),
-            authors:     ["omniatc".into()].into(),
-            tags:        [("region", "fictional"), ("source", "builtin"), ("type", "scenario")]
+            id:            "omniatc.demo".into(),
+            title:         "Demo".into(),
+            description:   "Map for testing and demonstrating game features".into(),
+            authors:       ["omniatc".into()].into(),
+            tags:          [("region", "fictional"), ("source", "builtin"), ("type", "scenario")]
                 .into_iter()
                 .map(|(k, v)| (String::from(k), String::from(v)))
                 .collect(),
+            next_scenario: None,
+            mode:          store::FacilityMode::Full,
         },
         level: level(),
         ui:    store::Ui {
-            camera: store::Camera::TwoDimension(store::Camera2d {
+            camera:           store::Camera::TwoDimension(store::Camera2d {
                 center:       Position::from_origin_nm(0., 0.),
                 up:           Heading::NORTH,
                 scale_axis:   store::AxisDirection::X,
                 scale_length: Length::from_nm(100.),
             }),
+            display_settings: BTreeMap::new(),
         },
 
         stats:   store::Stats::default(),
         quests:  store::QuestTree::default(),
         objects: [
+            store::Object::Drifter(store::Drifter {
+                name:     "GLIDER".into(),
+                id:       None,
+                kind:     store::DrifterKind::Glider {
+                    airspeed:  Speed::from_knots(50.),
+                    sink_rate: Speed::from_fpm(150.),
+                },
+                position: Position::from_origin_nm(-14., 8.),
+                altitude: Position::from_amsl_feet(4000.),
+                heading:  Heading::NORTH,
+            }),
+            store::Object::Drifter(store::Drifter {
+                name:     "BALLOON".into(),
+                id:       None,
+                kind:     store::DrifterKind::Balloon {
+                    vert_rate: Speed::from_fpm(200.),
+                    ceiling:   Position::from_amsl_feet(5000.),
+                },
+                position: Position::from_origin_nm(-20., 15.),
+                altitude: Position::from_amsl_feet(2000.),
+                heading:  Heading::NORTH,
+            }),
             store::Object::Plane(store::Plane {
                 aircraft:    store::BaseAircraft {
                     name:             "ABC123".into(),
+                    id:               None,
                     dest:             store::Destination::Landing { aerodrome: "MAIN".into() },
                     completion_score: Score(10),
                     position:         Position::from_origin_nm(2., -14.),
@@ -852,6 +1035,11 @@ pub fn file() -> store::File {
                     ground_speed:     Speed::from_knots(280.),
                     ground_dir:       Heading::from_degrees(250.),
                     vert_rate:        Speed::ZERO,
+                    endurance:        None,
+                    vfr:              None,
+                    formation_size:   None,
+                    notes:            store::Notes::default(),
+                    instructions:     store::InstructionState::default(),
                 },
                 control:     store::PlaneControl {
                     heading:     Heading::from_degrees(80.),
@@ -861,6 +1049,8 @@ pub fn file() -> store::File {
                 object_type: store::ObjectTypeRef("A359".into()),
                 taxi_limits: common_types::a359_taxi_limits(),
                 nav_limits:  common_types::a359_nav_limits(),
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
                 nav_target:  store::NavTarget::Airborne(Box::new(store::AirborneNavTarget {
                     yaw:              store::YawTarget::Heading(Heading::from_degrees(80.)),
                     horiz_ias:        None,
@@ -879,6 +1069,7 @@ pub fn file() -> store::File {
             store::Object::Plane(store::Plane {
                 aircraft:    store::BaseAircraft {
                     name:             "DEF789".into(),
+                    id:               None,
                     dest:             store::Destination::Landing { aerodrome: "MAIN".into() },
                     completion_score: Score(10),
                     position:         Position::from_origin_nm(2., -18.),
@@ -886,6 +1077,11 @@ pub fn file() -> store::File {
                     ground_speed:     Speed::from_knots(280.),
                     ground_dir:       Heading::from_degrees(250.),
                     vert_rate:        Speed::ZERO,
+                    endurance:        None,
+                    vfr:              None,
+                    formation_size:   None,
+                    notes:            store::Notes::default(),
+                    instructions:     store::InstructionState::default(),
                 },
                 control:     store::PlaneControl {
                     heading:     Heading::from_degrees(80.),
@@ -895,6 +1091,8 @@ pub fn file() -> store::File {
                 object_type: store::ObjectTypeRef("A359".into()),
                 taxi_limits: common_types::a359_taxi_limits(),
                 nav_limits:  common_types::a359_nav_limits(),
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
                 nav_target:  store::NavTarget::Airborne(Box::new(store::AirborneNavTarget {
                     yaw:              store::YawTarget::Heading(Heading::from_degrees(80.)),
                     horiz_ias:        None,
@@ -913,6 +1111,7 @@ pub fn file() -> store::File {
             store::Object::Plane(store::Plane {
                 aircraft:    store::BaseAircraft {
                     name:             "ARC512".into(),
+                    id:               None,
                     dest:             store::Destination::Landing { aerodrome: "MAIN".into() },
                     completion_score: Score(10),
                     position:         Position::from_origin_nm(8., 28.),
@@ -920,6 +1119,11 @@ pub fn file() -> store::File {
                     ground_speed:     Speed::from_knots(220.),
                     ground_dir:       Heading::from_degrees(250.),
                     vert_rate:        Speed::ZERO,
+                    endurance:        None,
+                    vfr:              None,
+                    formation_size:   None,
+                    notes:            store::Notes::default(),
+                    instructions:     store::InstructionState::default(),
                 },
                 control:     store::PlaneControl {
                     heading:     Heading::from_degrees(200.),
@@ -929,6 +1133,8 @@ pub fn file() -> store::File {
                 object_type: store::ObjectTypeRef("A359".into()),
                 taxi_limits: common_types::a359_taxi_limits(),
                 nav_limits:  common_types::a359_nav_limits(),
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
                 nav_target:  store::NavTarget::Airborne(Box::new(store::AirborneNavTarget {
                     yaw:              store::YawTarget::Heading(Heading::from_degrees(80.)),
                     horiz_ias:        None,
@@ -947,6 +1153,7 @@ pub fn file() -> store::File {
             store::Object::Plane(store::Plane {
                 aircraft:    store::BaseAircraft {
                     name:             "ADE127".into(),
+                    id:               None,
                     dest:             store::Destination::Departure {
                         min_altitude:       Some(Position::from_amsl_feet(18000.)),
                         waypoint_proximity: Some((
@@ -960,6 +1167,11 @@ pub fn file() -> store::File {
                     ground_speed:     Speed::from_knots(250.),
                     ground_dir:       Heading::EAST,
                     vert_rate:        Speed::ZERO,
+                    endurance:        None,
+                    vfr:              None,
+                    formation_size:   None,
+                    notes:            store::Notes::default(),
+                    instructions:     store::InstructionState::default(),
                 },
                 control:     store::PlaneControl {
                     heading:     Heading::EAST,
@@ -969,6 +1181,8 @@ pub fn file() -> store::File {
                 object_type: store::ObjectTypeRef("A359".into()),
                 taxi_limits: common_types::a359_taxi_limits(),
                 nav_limits:  common_types::a359_nav_limits(),
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
                 nav_target:  store::NavTarget::Airborne(Box::new(store::AirborneNavTarget {
                     yaw:              store::YawTarget::Heading(Heading::NORTH),
                     horiz_ias:        None,
@@ -989,6 +1203,7 @@ pub fn file() -> store::File {
             store::Object::Plane(store::Plane {
                 aircraft:    store::BaseAircraft {
                     name:             "LND456".into(),
+                    id:               None,
                     dest:             store::Destination::Parking { aerodrome: "MAIN".into() },
                     completion_score: Score(5),
                     position:         Position::from_origin_nm(1., 0.),
@@ -996,6 +1211,11 @@ pub fn file() -> store::File {
                     ground_speed:     Speed::from_knots(140.),
                     ground_dir:       Heading::SOUTH,
                     vert_rate:        Speed::ZERO,
+                    endurance:        None,
+                    vfr:              None,
+                    formation_size:   None,
+                    notes:            store::Notes::default(),
+                    instructions:     store::InstructionState::default(),
                 },
                 control:     store::PlaneControl {
                     heading:     Heading::SOUTH,
@@ -1005,6 +1225,8 @@ pub fn file() -> store::File {
                 object_type: store::ObjectTypeRef("A359".into()),
                 taxi_limits: common_types::a359_taxi_limits(),
                 nav_limits:  common_types::a359_nav_limits(),
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
                 nav_target:  store::NavTarget::Ground(store::GroundNavTarget {
                     segment: store::SegmentRef {
                         aerodrome: "MAIN".into(),
@@ -1016,6 +1238,7 @@ pub fn file() -> store::File {
             store::Object::Plane(store::Plane {
                 aircraft:    store::BaseAircraft {
                     name:             "DEP256".into(),
+                    id:               None,
                     dest:             store::Destination::Departure {
                         min_altitude:       Some(Position::from_amsl_feet(18000.)),
                         waypoint_proximity: Some((
@@ -1033,6 +1256,11 @@ pub fn file() -> store::File {
                     ground_speed:     Speed::ZERO,
                     ground_dir:       Heading::WEST,
                     vert_rate:        Speed::ZERO,
+                    endurance:        None,
+                    vfr:              None,
+                    formation_size:   None,
+                    notes:            store::Notes::default(),
+                    instructions:     store::InstructionState::default(),
                 },
                 control:     store::PlaneControl {
                     heading:     Heading::WEST,
@@ -1042,6 +1270,8 @@ pub fn file() -> store::File {
                 object_type: store::ObjectTypeRef("A359".into()),
                 taxi_limits: common_types::a359_taxi_limits(),
                 nav_limits:  common_types::a359_nav_limits(),
+                equipage:    store::Equipage::all(),
+                category:    store::Category::Jet,
                 nav_target:  store::NavTarget::Ground(store::GroundNavTarget {
                     segment: store::SegmentRef {
                         aerodrome: "MAIN".into(),
@@ -1055,5 +1285,14 @@ pub fn file() -> store::File {
             }),
         ]
         .into(),
+        clock:   store::Clock {
+            time_of_day: Duration::from_hours(17),
+            day_night:   Some(store::DayNightCycle {
+                sunrise:  Duration::from_hours(6),
+                sunset:   Duration::from_hours(18),
+                twilight: Duration::from_mins(30),
+            }),
+        },
+        scripts: Vec::new(),
     }
 }
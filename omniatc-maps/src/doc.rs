@@ -0,0 +1,530 @@
+//! Human-readable reference of the map format, generated from the JSON schema.
+//!
+//! Every definition in the schema of [`store::File`] becomes a section
+//! describing its fields or variants with the doc comments of the store types.
+//! Quantities such as [`math::Length`] are documented by their unit,
+//! which is taken from the description of the quantity schema (e.g. "Length in nm").
+//! Rustdoc links to other store types in doc comments become links to their sections.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use serde_json::{Map, Value};
+
+#[cfg(test)]
+mod tests;
+
+/// Output format of the reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// Generates the reference of the map format.
+#[must_use]
+pub fn generate(format: Format) -> String {
+    let schema = schemars::schema_for!(store::File);
+    let reference = Reference::from_schema(schema.as_value());
+    match format {
+        Format::Markdown => reference.to_markdown(),
+        Format::Html => reference.to_html(),
+    }
+}
+
+/// The documented definitions of a schema.
+pub struct Reference {
+    /// Name of the root type.
+    pub title:    String,
+    /// Root type first, followed by the other definitions in alphabetical order.
+    pub sections: Vec<Section>,
+}
+
+/// Documentation of a single type.
+pub struct Section {
+    pub name:        String,
+    pub description: String,
+    pub body:        Body,
+}
+
+pub enum Body {
+    /// A JSON object with named fields.
+    Fields(Vec<Field>),
+    /// One of several variants.
+    Variants(Vec<Variant>),
+    /// A value of another type, e.g. a string or a quantity.
+    Value { ty: String, unit: Option<String> },
+}
+
+pub struct Field {
+    pub name:        String,
+    /// Type of the field as inline Markdown, linking to other sections.
+    pub ty:          String,
+    pub unit:        Option<String>,
+    pub required:    bool,
+    pub description: String,
+    /// Examples and the default value of the field, serialized as JSON.
+    pub examples:    Vec<String>,
+    pub default:     Option<String>,
+}
+
+pub struct Variant {
+    /// The string value or the object key of the variant.
+    pub name:        String,
+    /// Type of the variant content as inline Markdown, if it is not a unit variant.
+    pub ty:          Option<String>,
+    pub description: String,
+}
+
+impl Reference {
+    /// Extracts the documentation from a JSON schema with definitions under `$defs`.
+    #[must_use]
+    pub fn from_schema(schema: &Value) -> Self {
+        let empty = Map::new();
+        let defs = schema.get("$defs").and_then(Value::as_object).unwrap_or(&empty);
+        let ctx = Context { defs };
+
+        let title = schema.get("title").and_then(Value::as_str).unwrap_or("File").to_owned();
+        let mut sections = vec![ctx.section(&title, schema)];
+        sections.extend(defs.iter().map(|(name, def)| ctx.section(name, def)));
+
+        Self { title, sections }
+    }
+
+    /// Renders the reference as a Markdown document.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {} format reference\n\n", self.title);
+        for section in &self.sections {
+            section.write_markdown(&mut out);
+        }
+        out
+    }
+
+    /// Renders the reference as a standalone HTML document.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let title = escape_html(&format!("{} format reference", self.title));
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta \
+             charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\\
+             n<h1>{title}</h1>\n"
+        );
+        for section in &self.sections {
+            section.write_html(&mut out);
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:60em;margin:auto;padding:\
+                     1em}table{border-collapse:collapse}td,th{border:1px solid \
+                     #ccc;padding:.3em;vertical-align:top}code{background:#eee}";
+
+impl Section {
+    fn write_markdown(&self, out: &mut String) {
+        _ = write!(out, "<a id=\"{}\"></a>\n\n## {}\n\n", anchor(&self.name), self.name);
+        if !self.description.is_empty() {
+            _ = write!(out, "{}\n\n", self.description);
+        }
+
+        match &self.body {
+            Body::Fields(fields) if fields.is_empty() => {}
+            Body::Fields(fields) => {
+                out.push_str("| Field | Type | Unit | Required | Description |\n");
+                out.push_str("| --- | --- | --- | --- | --- |\n");
+                for field in fields {
+                    _ = writeln!(
+                        out,
+                        "| `{}` | {} | {} | {} | {} |",
+                        field.name,
+                        field.ty,
+                        field.unit.as_deref().unwrap_or(""),
+                        if field.required { "yes" } else { "no" },
+                        table_cell(&field.details()),
+                    );
+                }
+                out.push('\n');
+            }
+            Body::Variants(variants) => {
+                out.push_str("| Variant | Content | Description |\n");
+                out.push_str("| --- | --- | --- |\n");
+                for variant in variants {
+                    _ = writeln!(
+                        out,
+                        "| `{}` | {} | {} |",
+                        variant.name,
+                        variant.ty.as_deref().unwrap_or(""),
+                        table_cell(&variant.description),
+                    );
+                }
+                out.push('\n');
+            }
+            Body::Value { ty, unit } => {
+                _ = write!(out, "Type: {ty}");
+                if let Some(unit) = unit {
+                    _ = write!(out, " (unit: {unit})");
+                }
+                out.push_str("\n\n");
+            }
+        }
+    }
+
+    fn write_html(&self, out: &mut String) {
+        _ = writeln!(out, "<h2 id=\"{}\">{}</h2>", anchor(&self.name), escape_html(&self.name));
+        out.push_str(&block_html(&self.description));
+
+        match &self.body {
+            Body::Fields(fields) if fields.is_empty() => {}
+            Body::Fields(fields) => {
+                out.push_str(
+                    "<table>\n<tr><th>Field</th><th>Type</th><th>Unit</th><th>Required</\
+                     th><th>Description</th></tr>\n",
+                );
+                for field in fields {
+                    _ = writeln!(
+                        out,
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</\
+                         td></tr>",
+                        escape_html(&field.name),
+                        inline_html(&field.ty),
+                        escape_html(field.unit.as_deref().unwrap_or("")),
+                        if field.required { "yes" } else { "no" },
+                        block_html(&field.details()),
+                    );
+                }
+                out.push_str("</table>\n");
+            }
+            Body::Variants(variants) => {
+                out.push_str(
+                    "<table>\n<tr><th>Variant</th><th>Content</th><th>Description</th></tr>\n",
+                );
+                for variant in variants {
+                    _ = writeln!(
+                        out,
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>",
+                        escape_html(&variant.name),
+                        inline_html(variant.ty.as_deref().unwrap_or("")),
+                        block_html(&variant.description),
+                    );
+                }
+                out.push_str("</table>\n");
+            }
+            Body::Value { ty, unit } => {
+                _ = write!(out, "<p>Type: {}", inline_html(ty));
+                if let Some(unit) = unit {
+                    _ = write!(out, " (unit: {})", escape_html(unit));
+                }
+                out.push_str("</p>\n");
+            }
+        }
+    }
+}
+
+impl Field {
+    /// The description of the field followed by its default value and examples.
+    fn details(&self) -> String {
+        let mut paragraphs = Vec::new();
+        if !self.description.is_empty() {
+            paragraphs.push(self.description.clone());
+        }
+        if let Some(default) = &self.default {
+            paragraphs.push(format!("Default: `{default}`"));
+        }
+        for example in &self.examples {
+            paragraphs.push(format!("Example: `{example}`"));
+        }
+        paragraphs.join("\n\n")
+    }
+}
+
+struct Context<'a> {
+    defs: &'a Map<String, Value>,
+}
+
+impl Context<'_> {
+    fn section(&self, name: &str, schema: &Value) -> Section {
+        let mut description = self.description(schema);
+        for example in examples(schema) {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            _ = write!(description, "Example: `{example}`");
+        }
+
+        let body = if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            let required: BTreeSet<&str> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            Body::Fields(
+                properties
+                    .iter()
+                    .map(|(field, prop)| self.field(field, prop, required.contains(field.as_str())))
+                    .collect(),
+            )
+        } else if let Some(variants) = variants(schema) {
+            Body::Variants(variants.iter().map(|variant| self.variant(variant)).collect())
+        } else {
+            Body::Value { ty: type_name(schema), unit: self.unit(schema) }
+        };
+
+        Section { name: name.to_owned(), description, body }
+    }
+
+    fn field(&self, name: &str, schema: &Value, required: bool) -> Field {
+        Field {
+            name: name.to_owned(),
+            ty: type_name(schema),
+            unit: self.unit(schema),
+            required,
+            description: self.description(schema),
+            examples: examples(schema),
+            default: schema.get("default").map(Value::to_string),
+        }
+    }
+
+    fn variant(&self, schema: &Value) -> Variant {
+        let description = self.description(schema);
+
+        if let Some(value) = schema.get("const").and_then(Value::as_str) {
+            return Variant { name: value.to_owned(), ty: None, description };
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            let names: Vec<_> = values.iter().filter_map(Value::as_str).collect();
+            return Variant { name: names.join("`, `"), ty: None, description };
+        }
+        // externally tagged variants are objects with a single property
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object)
+            && let [(tag, content)] = properties.iter().collect::<Vec<_>>()[..]
+        {
+            return Variant { name: tag.clone(), ty: Some(type_name(content)), description };
+        }
+        Variant { name: "(untagged)".into(), ty: Some(type_name(schema)), description }
+    }
+
+    /// Returns the description of a schema with rustdoc links resolved.
+    fn description(&self, schema: &Value) -> String {
+        schema.get("description").and_then(Value::as_str).map_or_else(String::new, |text| {
+            rewrite_links(text, |target| self.defs.contains_key(target))
+        })
+    }
+
+    /// Returns the unit of a quantity schema, following references and optional values.
+    fn unit(&self, schema: &Value) -> Option<String> {
+        if let Some(name) = ref_name(schema) {
+            return self.defs.get(name).and_then(|def| self.unit(def));
+        }
+        if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+            let mut units = options.iter().filter(|option| !is_null(option));
+            let unit = self.unit(units.next()?)?;
+            return units.next().is_none().then_some(unit);
+        }
+        if let Some(items) = schema.get("items")
+            && let Some(unit) = self.unit(items)
+        {
+            return Some(unit);
+        }
+
+        let is_number = matches!(
+            schema.get("type").and_then(Value::as_str),
+            Some("number" | "integer" | "array")
+        );
+        let description = schema.get("description").and_then(Value::as_str)?;
+        let (_, unit) = description.rsplit_once(" in ")?;
+        (is_number && !unit.contains(char::is_whitespace)).then(|| unit.to_owned())
+    }
+}
+
+/// Returns the type of a schema as inline Markdown.
+fn type_name(schema: &Value) -> String {
+    if let Some(name) = ref_name(schema) {
+        return format!("[{name}](#{})", anchor(name));
+    }
+    if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+        let non_null: Vec<_> = options.iter().filter(|option| !is_null(option)).collect();
+        let names: Vec<_> = non_null.iter().map(|option| type_name(option)).collect();
+        let names = names.join(" or ");
+        return if non_null.len() < options.len() { format!("{names} or null") } else { names };
+    }
+    if variants(schema).is_some() {
+        return "one of the variants below".into();
+    }
+
+    match schema.get("type") {
+        Some(Value::String(ty)) if ty == "array" => {
+            let items = schema.get("items").map_or_else(|| "any".into(), type_name);
+            match (schema.get("minItems"), schema.get("maxItems")) {
+                (Some(min), Some(max)) if min == max => format!("array of {min} {items}"),
+                _ => format!("array of {items}"),
+            }
+        }
+        Some(Value::String(ty)) if ty == "object" => match schema.get("additionalProperties") {
+            Some(value @ Value::Object(_)) => {
+                format!("map from string to {}", type_name(value))
+            }
+            _ => "object".into(),
+        },
+        Some(Value::String(ty)) => match schema.get("format").and_then(Value::as_str) {
+            Some(format) => format!("{ty} ({format})"),
+            None => ty.clone(),
+        },
+        Some(Value::Array(types)) => {
+            let types: Vec<_> = types.iter().filter_map(Value::as_str).collect();
+            types.join(" or ")
+        }
+        _ => "any".into(),
+    }
+}
+
+fn examples(schema: &Value) -> Vec<String> {
+    schema
+        .get("examples")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(Value::to_string)
+        .collect()
+}
+
+fn variants(schema: &Value) -> Option<&Vec<Value>> { schema.get("oneOf").and_then(Value::as_array) }
+
+fn ref_name(schema: &Value) -> Option<&str> {
+    schema.get("$ref").and_then(Value::as_str)?.strip_prefix("#/$defs/")
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+        || schema.get("const").is_some_and(Value::is_null)
+}
+
+/// Returns the HTML anchor of the section of a type.
+#[must_use]
+pub fn anchor(name: &str) -> String {
+    name.chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch.to_ascii_lowercase() } else { '-' })
+        .collect()
+}
+
+/// Rewrites rustdoc intra-doc links in `text` into Markdown links to sections.
+///
+/// A link `` [`Name`] `` or `` [text](path::Name) `` links to the section of `Name`
+/// if `is_section(Name)`, otherwise it is replaced by its link text.
+/// Links to fields such as `` [`Name::field`] `` link to the section of `Name`.
+#[must_use]
+pub fn rewrite_links(text: &str, is_section: impl Fn(&str) -> bool) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find(']') else {
+            rest = &rest[start..];
+            break;
+        };
+        let label = &after[..end];
+        let mut tail = &after[end + 1..];
+
+        let mut target = label.trim_matches('`');
+        if let Some(paren) = tail.strip_prefix('(')
+            && let Some(close) = paren.find(')')
+        {
+            target = &paren[..close];
+            tail = &paren[close + 1..];
+        }
+
+        match link_section(target, &is_section) {
+            Some(section) => _ = write!(out, "[{label}](#{})", anchor(section)),
+            None => out.push_str(label),
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Finds the section linked by a rustdoc path, e.g. `crate::File::objects` links to `File`.
+fn link_section<'a>(target: &'a str, is_section: &impl Fn(&str) -> bool) -> Option<&'a str> {
+    let segments: Vec<_> = target.split("::").collect();
+    segments.iter().rev().take(2).copied().find(|segment| is_section(segment))
+}
+
+/// Escapes a Markdown table cell, which cannot contain line breaks.
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace("\n\n", "<br><br>").replace('\n', " ")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Converts Markdown paragraphs and fenced code blocks to HTML.
+fn block_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    for block in text.split("\n\n") {
+        if block.trim().is_empty() {
+            continue;
+        }
+        for (index, part) in block.split("```").enumerate() {
+            if index > 0 {
+                in_code = !in_code;
+            }
+            if in_code {
+                // drop the language tag after the opening fence
+                let code = part.split_once('\n').map_or("", |(_, code)| code);
+                _ = writeln!(out, "<pre><code>{}</code></pre>", escape_html(code.trim_end()));
+            } else if !part.trim().is_empty() {
+                _ = writeln!(out, "<p>{}</p>", inline_html(part.trim()));
+            }
+        }
+    }
+    out
+}
+
+/// Converts inline code spans and links in Markdown to HTML.
+fn inline_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    loop {
+        let code = rest.find('`');
+        let link = rest.find('[');
+        match (code, link) {
+            (Some(code), link) if link.is_none_or(|link| code < link) => {
+                out.push_str(&escape_html(&rest[..code]));
+                let after = &rest[code + 1..];
+                let Some(end) = after.find('`') else {
+                    out.push_str(&escape_html(&rest[code..]));
+                    break;
+                };
+                _ = write!(out, "<code>{}</code>", escape_html(&after[..end]));
+                rest = &after[end + 1..];
+            }
+            (_, Some(link)) => {
+                out.push_str(&escape_html(&rest[..link]));
+                let after = &rest[link + 1..];
+                let parsed = after.find("](").and_then(|label_end| {
+                    let href_end = after[label_end..].find(')')? + label_end;
+                    Some((&after[..label_end], &after[label_end + 2..href_end], href_end))
+                });
+                let Some((label, href, href_end)) = parsed else {
+                    out.push('[');
+                    rest = after;
+                    continue;
+                };
+                _ = write!(out, "<a href=\"{}\">{}</a>", escape_html(href), inline_html(label));
+                rest = &after[href_end + 1..];
+            }
+            _ => {
+                out.push_str(&escape_html(rest));
+                break;
+            }
+        }
+    }
+    out
+}
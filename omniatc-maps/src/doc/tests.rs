@@ -0,0 +1,90 @@
+use serde_json::json;
+
+use super::{Body, Format, Reference, anchor, generate, rewrite_links};
+
+fn schema() -> serde_json::Value {
+    json!({
+        "title": "File",
+        "description": "Root of the file.",
+        "type": "object",
+        "properties": {
+            "elevation": {
+                "description": "Elevation of the [`Aerodrome`].",
+                "anyOf": [{ "$ref": "#/$defs/Length::f32" }, { "type": "null" }],
+            },
+            "kind": { "$ref": "#/$defs/Kind", "default": "A" },
+        },
+        "required": ["kind"],
+        "$defs": {
+            "Length::f32": { "description": "Length in nm", "type": "number" },
+            "Kind": {
+                "description": "A kind.",
+                "oneOf": [
+                    { "description": "The A kind.", "type": "string", "const": "A" },
+                    {
+                        "type": "object",
+                        "properties": { "B": { "$ref": "#/$defs/Length::f32" } },
+                        "required": ["B"],
+                    },
+                ],
+            },
+        },
+    })
+}
+
+#[test]
+fn fields_resolve_types_and_units() {
+    let reference = Reference::from_schema(&schema());
+    assert_eq!(reference.sections[0].name, "File");
+
+    let Body::Fields(fields) = &reference.sections[0].body else { panic!("root has fields") };
+    let elevation = fields.iter().find(|field| field.name == "elevation").expect("elevation");
+    assert_eq!(elevation.ty, "[Length::f32](#length--f32) or null");
+    assert_eq!(elevation.unit.as_deref(), Some("nm"));
+    assert!(!elevation.required);
+    assert_eq!(elevation.description, "Elevation of the `Aerodrome`.");
+
+    let kind = fields.iter().find(|field| field.name == "kind").expect("kind");
+    assert!(kind.required);
+    assert_eq!(kind.unit, None);
+    assert_eq!(kind.default.as_deref(), Some("\"A\""));
+}
+
+#[test]
+fn variants_list_tags() {
+    let reference = Reference::from_schema(&schema());
+    let kind = reference.sections.iter().find(|section| section.name == "Kind").expect("Kind");
+    let Body::Variants(variants) = &kind.body else { panic!("Kind has variants") };
+    assert_eq!(variants[0].name, "A");
+    assert_eq!(variants[0].ty, None);
+    assert_eq!(variants[1].name, "B");
+    assert_eq!(variants[1].ty.as_deref(), Some("[Length::f32](#length--f32)"));
+}
+
+#[test]
+fn links_resolve_to_sections() {
+    let is_section = |name: &str| name == "File";
+    assert_eq!(rewrite_links("See [`File`].", is_section), "See [`File`](#file).");
+    assert_eq!(
+        rewrite_links("See [`objects`](crate::File::objects).", is_section),
+        "See [`objects`](#file)."
+    );
+    assert_eq!(rewrite_links("See [`Other`].", is_section), "See `Other`.");
+    assert_eq!(rewrite_links("Unclosed [bracket", is_section), "Unclosed [bracket");
+    assert_eq!(anchor("Position_Length::f32"), "position-length--f32");
+}
+
+#[test]
+fn html_has_section_anchors() {
+    let html = generate(Format::Html);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h2 id=\"aerodrome\">Aerodrome</h2>"));
+}
+
+#[test]
+fn store_reference_documents_units() {
+    let markdown = generate(Format::Markdown);
+    assert!(markdown.starts_with("# File format reference"));
+    assert!(markdown.contains("## Aerodrome\n"));
+    assert!(markdown.contains("| nm |"));
+}
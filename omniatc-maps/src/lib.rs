@@ -5,16 +5,31 @@ use std::path::Path;
 use std::{fmt, fs, io};
 
 use anyhow::{Context, Result};
+use math::GeoPoint;
+use omniatc::level::{ground, session, track};
 
+pub mod adsb;
+pub mod airlines;
 pub mod common_types;
+pub mod doc;
 
 pub mod blank;
 pub mod demo;
+pub mod timetable;
 pub mod tutorial;
 
+#[cfg(test)]
+mod tests;
+
 pub fn builtins()
 -> impl Iterator<Item = (impl AsRef<str> + Into<String> + fmt::Display, store::File)> {
-    [("blank", blank::file()), ("tutorial", tutorial::file()), ("demo", demo::file())].into_iter()
+    [
+        ("blank", blank::file()),
+        ("tutorial", tutorial::file()),
+        ("demo", demo::file()),
+        ("timetable", timetable::file()),
+    ]
+    .into_iter()
 }
 
 pub fn build_assets(maps_dir: &Path) -> Result<()> {
@@ -47,6 +62,11 @@ pub fn json_schema(output: &Path, gzip: bool) -> Result<()> {
     Ok(())
 }
 
+pub fn doc(output: &Path, format: doc::Format) -> Result<()> {
+    fs::write(output, doc::generate(format)).context("write reference")?;
+    Ok(())
+}
+
 pub fn from_json(input: &Path, output: &Path) -> Result<()> {
     let file: store::File =
         serde_json::from_reader(BufReader::new(fs::File::open(input).context("open input")?))
@@ -67,3 +87,132 @@ pub fn to_json(input: &Path, output: &Path) -> Result<()> {
     .context("write json")?;
     Ok(())
 }
+
+/// Reads a map file in OSAV or JSON format, determined by the file extension.
+fn read_map(path: &Path) -> Result<store::File> {
+    let reader = BufReader::new(fs::File::open(path).context("open map")?);
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_reader(reader).context("parse json")
+    } else {
+        store::File::from_osav(reader).context("parse osav")
+    }
+}
+
+pub fn import_adsb(
+    base: &Path,
+    traces: &Path,
+    output: &Path,
+    options: &adsb::Options,
+) -> Result<()> {
+    let map = read_map(base).context("read base map")?;
+    let contents = fs::read_to_string(traces).context("read traces")?;
+    let points = if traces.extension().is_some_and(|ext| ext == "json") {
+        adsb::parse_json(&contents)?
+    } else {
+        adsb::parse_csv(&contents)?
+    };
+
+    let file = adsb::import(map, points, options)?;
+    let store::SpawnTrigger::Timetable(timetable) = &file.level.spawn_trigger else {
+        unreachable!("import always generates a timetable")
+    };
+    println!("Imported {} flights", timetable.flights.len());
+    fs::write(output, file.to_osav().context("serialize osav")?).context("write osav")?;
+    Ok(())
+}
+
+pub fn verify_run(map: &Path, result: &Path) -> Result<()> {
+    let result: store::SessionResult =
+        serde_json::from_reader(BufReader::new(fs::File::open(result).context("open result")?))
+            .context("parse result")?;
+    anyhow::ensure!(session::verify_checksum(&result), "result checksum does not match");
+
+    let file = read_map(map)?;
+    anyhow::ensure!(
+        file.meta.id == result.map_id,
+        "result is for map {:?}, got {:?}",
+        result.map_id,
+        file.meta.id
+    );
+
+    let report = session::replay::run(file, &result).context("re-simulate session")?;
+    println!("Claimed score:   {}", result.score.0);
+    println!("Simulated score: {}", report.score.0);
+    println!("Claimed events:   {}", result.event_digest);
+    println!("Simulated events: {}", report.event_digest);
+    println!("Claimed world hash:   {}", result.world_hash);
+    println!("Simulated world hash: {}", report.world_hash);
+    for action in &report.skipped_actions {
+        println!("Skipped action: {action}");
+    }
+    for bookmark in &report.bookmarks {
+        let window = session::bookmark::window(bookmark);
+        println!(
+            "Incident: {:?} of {} at frame {} (review {:.0}s to {:.0}s)",
+            bookmark.kind,
+            bookmark.objects.join(", "),
+            bookmark.frame,
+            window.start.as_secs_f32(),
+            window.end.as_secs_f32(),
+        );
+    }
+    anyhow::ensure!(report.matches(&result), "re-simulation does not reproduce the claimed result");
+    println!("Verified");
+    Ok(())
+}
+
+pub fn export_tracks(
+    map: &Path,
+    result: &Path,
+    output: &Path,
+    origin: Option<GeoPoint>,
+) -> Result<()> {
+    let result: store::SessionResult =
+        serde_json::from_reader(BufReader::new(fs::File::open(result).context("open result")?))
+            .context("parse result")?;
+    let file = read_map(map)?;
+    let origin = origin.or(file.level.geo_origin).context(
+        "the map has no geographic origin, specify one with --origin-lat and --origin-lon",
+    )?;
+    let format = if output.extension().is_some_and(|ext| ext == "kml") {
+        track::export::Format::Kml
+    } else {
+        track::export::Format::GeoJson
+    };
+
+    let report = session::replay::run(file, &result).context("re-simulate session")?;
+    for action in &report.skipped_actions {
+        println!("Skipped action: {action}");
+    }
+    fs::write(output, format.write(&report.tracks, origin)).context("write tracks")?;
+    println!("Exported {} tracks as {format}", report.tracks.tracks.len());
+    Ok(())
+}
+
+pub fn ground_graph(map: &Path, output: &Path, origin: Option<GeoPoint>) -> Result<()> {
+    let file = read_map(map)?;
+    let origin = origin.or(file.level.geo_origin);
+    let format = if output.extension().is_some_and(|ext| ext == "dot" || ext == "gv") {
+        ground::export::Format::Dot
+    } else {
+        ground::export::Format::GeoJson
+    };
+
+    let graph = ground::export::from_file(file).context("load map")?;
+    for aerodrome in &graph.aerodromes {
+        let dead_ends = aerodrome.degrees().into_iter().filter(|&degree| degree <= 1).count();
+        println!(
+            "{}: {} endpoints, {} segments, {} connected components, {dead_ends} dead ends",
+            aerodrome.code,
+            aerodrome.endpoints.len(),
+            aerodrome.segments.len(),
+            aerodrome.component_count(),
+        );
+    }
+    if format == ground::export::Format::GeoJson && origin.is_none() {
+        println!("The map has no geographic origin, writing map coordinates in meters");
+    }
+    fs::write(output, format.write(&graph, origin)).context("write graph")?;
+    println!("Exported ground graph as {format}");
+    Ok(())
+}
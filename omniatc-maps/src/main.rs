@@ -1,6 +1,8 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Result;
+use math::{GeoPoint, Length};
 
 #[derive(clap::Parser)]
 struct Options {
@@ -19,6 +21,15 @@ enum Command {
         #[clap(long, default_value_t = true)]
         gzip:   std::primitive::bool,
     },
+    /// Output a human-readable reference of the map format.
+    Doc {
+        /// Output reference file.
+        #[clap(default_value = "map-format.md")]
+        output: PathBuf,
+        /// Format of the reference.
+        #[clap(long, value_enum, default_value_t = omniatc_maps::doc::Format::Markdown)]
+        format: omniatc_maps::doc::Format,
+    },
     /// Convert a JSON file to an OSAV file.
     FromJson {
         /// Input JSON file
@@ -39,13 +50,123 @@ enum Command {
         #[clap(default_value = "assets/maps")]
         maps_dir: PathBuf,
     },
+    /// Verify a session result by re-simulating it on the played map.
+    VerifyRun {
+        /// Map file the session was played on, in OSAV or JSON format.
+        map:    PathBuf,
+        /// Session result JSON file exported from the debrief screen.
+        result: PathBuf,
+    },
+    /// Convert recorded ADS-B traces into a timetable replay over a base map.
+    ImportAdsb {
+        /// Base map containing the aerodrome, in OSAV or JSON format.
+        base:          PathBuf,
+        /// Trace file in CSV or JSON format.
+        traces:        PathBuf,
+        /// Output OSAV file.
+        output:        PathBuf,
+        /// Latitude of the base map origin in degrees.
+        #[clap(long, allow_negative_numbers = true)]
+        origin_lat:    f64,
+        /// Longitude of the base map origin in degrees.
+        #[clap(long, allow_negative_numbers = true)]
+        origin_lon:    f64,
+        /// Code of the aerodrome in the base map that traffic arrives at or departs from.
+        #[clap(long)]
+        aerodrome:     String,
+        /// Distance from the map origin at which arrivals are spawned, in nautical miles.
+        #[clap(long, default_value_t = 40.)]
+        radius_nm:     f32,
+        /// UNIX timestamp of the start of the replay. Defaults to the earliest trace point.
+        #[clap(long)]
+        start:         Option<f64>,
+        /// Length of the replay window in minutes.
+        #[clap(long, default_value_t = 180)]
+        duration_mins: u64,
+        /// Object type for flights with an unknown type.
+        #[clap(long)]
+        default_type:  Option<String>,
+        /// Lead time of the generated timetable in minutes.
+        #[clap(long, default_value_t = 15)]
+        lead_mins:     u64,
+    },
+    /// Export the tracks flown in a session for map viewers by re-simulating it.
+    ExportTracks {
+        /// Map file the session was played on, in OSAV or JSON format.
+        map:        PathBuf,
+        /// Session result JSON file exported from the debrief screen.
+        result:     PathBuf,
+        /// Output file, written as KML if the extension is `.kml` or as JSON features otherwise.
+        output:     PathBuf,
+        /// Latitude of the map origin in degrees, overriding the origin in the map.
+        #[clap(long, allow_negative_numbers = true, requires = "origin_lon")]
+        origin_lat: Option<f64>,
+        /// Longitude of the map origin in degrees, overriding the origin in the map.
+        #[clap(long, allow_negative_numbers = true, requires = "origin_lat")]
+        origin_lon: Option<f64>,
+    },
+    /// Export the resolved ground network of each aerodrome for inspection.
+    GroundGraph {
+        /// Map file in OSAV or JSON format.
+        map:        PathBuf,
+        /// Output file, written as Graphviz DOT if the extension is `.dot` or `.gv`
+        /// or as JSON features otherwise.
+        output:     PathBuf,
+        /// Latitude of the map origin in degrees, overriding the origin in the map.
+        #[clap(long, allow_negative_numbers = true, requires = "origin_lon")]
+        origin_lat: Option<f64>,
+        /// Longitude of the map origin in degrees, overriding the origin in the map.
+        #[clap(long, allow_negative_numbers = true, requires = "origin_lat")]
+        origin_lon: Option<f64>,
+    },
 }
 
 fn main() -> Result<()> {
     match <Options as clap::Parser>::parse().command {
         Command::JsonSchema { output, gzip } => omniatc_maps::json_schema(&output, gzip),
+        Command::Doc { output, format } => omniatc_maps::doc(&output, format),
         Command::FromJson { input, output } => omniatc_maps::from_json(&input, &output),
         Command::ToJson { input, output } => omniatc_maps::to_json(&input, &output),
         Command::BuildAssets { maps_dir: output_dir } => omniatc_maps::build_assets(&output_dir),
+        Command::VerifyRun { map, result } => omniatc_maps::verify_run(&map, &result),
+        Command::ExportTracks { map, result, output, origin_lat, origin_lon } => {
+            omniatc_maps::export_tracks(
+                &map,
+                &result,
+                &output,
+                origin_lat.zip(origin_lon).map(|(lat, lon)| GeoPoint { lat, lon }),
+            )
+        }
+        Command::GroundGraph { map, output, origin_lat, origin_lon } => omniatc_maps::ground_graph(
+            &map,
+            &output,
+            origin_lat.zip(origin_lon).map(|(lat, lon)| GeoPoint { lat, lon }),
+        ),
+        Command::ImportAdsb {
+            base,
+            traces,
+            output,
+            origin_lat,
+            origin_lon,
+            aerodrome,
+            radius_nm,
+            start,
+            duration_mins,
+            default_type,
+            lead_mins,
+        } => omniatc_maps::import_adsb(
+            &base,
+            &traces,
+            &output,
+            &omniatc_maps::adsb::Options {
+                origin: GeoPoint { lat: origin_lat, lon: origin_lon },
+                aerodrome,
+                radius: Length::from_nm(radius_nm),
+                start,
+                duration: Duration::from_mins(duration_mins),
+                default_type,
+                lead_time: Duration::from_mins(lead_mins),
+            },
+        ),
     }
 }
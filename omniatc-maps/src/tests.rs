@@ -0,0 +1,293 @@
+use std::time::Duration;
+
+use bevy::app::{App, TaskPoolPlugin};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::With;
+use bevy::ecs::system::Command;
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use math::{Heading, Length, Speed};
+use omniatc::level::instr::{self, CommandsExt};
+use omniatc::level::{object, pilot_request, plane, route, session};
+use omniatc::{level, load, util};
+
+#[test]
+fn builtins_pass_validation() {
+    for (name, file) in super::builtins() {
+        if let Err(err) = omniatc::load::validate(&file) {
+            panic!("builtin map {name} is invalid: {err}");
+        }
+    }
+}
+
+#[test]
+fn builtin_ground_networks_are_connected() {
+    for (name, file) in super::builtins() {
+        let graph = match omniatc::level::ground::export::from_file(file) {
+            Ok(graph) => graph,
+            Err(err) => panic!("cannot load builtin map {name}: {err}"),
+        };
+        for aerodrome in &graph.aerodromes {
+            assert!(
+                aerodrome.component_count() <= 1,
+                "ground network of {} in {name} has {} disconnected parts",
+                aerodrome.code,
+                aerodrome.component_count(),
+            );
+        }
+    }
+}
+
+fn load_app(file: store::File) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TimePlugin,
+        level::Plug::<()>::default(),
+        load::Plug,
+        util::Plug,
+    ));
+    app.finish();
+    app.cleanup();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+    // Startup systems run before the level is loaded, as in the client.
+    app.update();
+
+    load::Command {
+        source:   load::Source::Parsed(Box::new(file)),
+        on_error: Box::new(|_, err| panic!("cannot load level: {err}")),
+    }
+    .apply(app.world_mut());
+    app
+}
+
+#[test]
+fn skipped_route_preset_skips_dependent_presets() {
+    let mut file = crate::blank::file();
+    let retry = file
+        .level
+        .route_presets
+        .iter_mut()
+        .find(|preset| preset.id == "RETRY18R")
+        .expect("demo map has a missed approach preset");
+    retry.trigger =
+        store::RoutePresetTrigger::Waypoint(store::WaypointRef::Named("NOWHERE".into()));
+
+    let mut app = load_app(file);
+    let world = app.world_mut();
+
+    let issues = &world.resource::<load::Issues>().0;
+    assert!(issues.iter().any(|issue| issue.element == "route preset RETRY18R"));
+    assert!(issues.len() > 1, "presets with a goaround to RETRY18R must also be skipped");
+
+    let mut presets = world.query::<&route::Preset>();
+    let goarounds: Vec<_> = presets
+        .iter(world)
+        .flat_map(|preset| &preset.nodes)
+        .filter_map(|node| match node {
+            route::Node::AlignRunway(node) => node.goaround_preset,
+            _ => None,
+        })
+        .collect();
+    for goaround in goarounds {
+        assert!(world.get_entity(goaround).is_ok(), "preset references a despawned goaround");
+    }
+}
+
+/// Returns the blank map with the objects of the demo map.
+fn blank_with_demo_objects() -> store::File {
+    let mut file = crate::blank::file();
+    file.objects = crate::demo::file().objects;
+    file
+}
+
+fn first_plane(file: &mut store::File) -> &mut store::Plane {
+    file.objects
+        .iter_mut()
+        .find_map(|object| match object {
+            store::Object::Plane(plane) => Some(plane),
+            store::Object::Drifter(_) => None,
+        })
+        .expect("demo map has planes")
+}
+
+fn named_plane<'a>(file: &'a mut store::File, name: &str) -> &'a mut store::Plane {
+    file.objects
+        .iter_mut()
+        .find_map(|object| match object {
+            store::Object::Plane(plane) if plane.aircraft.name == name => Some(plane),
+            _ => None,
+        })
+        .expect("plane is saved")
+}
+
+fn advance(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+#[test]
+fn unresolvable_plane_is_skipped() {
+    let mut file = blank_with_demo_objects();
+    let plane_count =
+        file.objects.iter().filter(|object| matches!(object, store::Object::Plane(_))).count();
+    let plane = first_plane(&mut file);
+    let name = plane.aircraft.name.clone();
+    plane.route.nodes.push(store::RouteNode::DirectWaypoint {
+        waypoint:  store::WaypointRef::Named("NOWHERE".into()),
+        distance:  Length::from_nm(1.),
+        proximity: store::WaypointProximity::FlyBy,
+        altitude:  None,
+    });
+
+    let mut app = load_app(file);
+    let world = app.world_mut();
+
+    let issues = &world.resource::<load::Issues>().0;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].element, format!("object {name}"));
+
+    let mut planes = world.query_filtered::<(), With<plane::Control>>();
+    assert_eq!(planes.iter(world).count(), plane_count - 1);
+}
+
+#[test]
+fn restored_instructions_are_not_recorded() {
+    let mut file = blank_with_demo_objects();
+    first_plane(&mut file).aircraft.instructions.pending.push(store::PendingInstruction {
+        instruction: store::InstructionRecord::SetHeading {
+            target: store::YawTarget::Heading(Heading::EAST),
+        },
+        stage:       store::PendingStage::Transmitting {
+            remaining:   Duration::from_secs(2),
+            after:       Vec::new(),
+            pending_ack: false,
+        },
+    });
+
+    let mut app = load_app(file.clone());
+    advance(&mut app, 200);
+    let result = session::build_result(app.world_mut());
+    assert!(result.actions.is_empty(), "restored instructions must not be recorded as actions");
+
+    let report = session::replay::run(file, &result).expect("replay session");
+    assert!(report.matches(&result));
+}
+
+#[test]
+fn recorded_session_replays_to_same_result() {
+    let mut file = blank_with_demo_objects();
+    file.level.pilot_requests.mean_interval = Some(Duration::from_secs(10));
+    let name = first_plane(&mut file).aircraft.name.clone();
+
+    let mut app = load_app(file.clone());
+    advance(&mut app, 20);
+
+    let world = app.world_mut();
+    let object = world
+        .query::<(Entity, &object::Display)>()
+        .iter(world)
+        .find(|(_, display)| display.name == name)
+        .map(|(entity, _)| entity)
+        .expect("plane is spawned");
+    world.commands().send_instruction(object, instr::SetSpeed { target: Speed::from_knots(220.) });
+    world.flush();
+
+    let mut request = None;
+    for _ in 0..2000 {
+        app.update();
+        let world = app.world_mut();
+        request =
+            world.query_filtered::<Entity, With<pilot_request::Requester>>().iter(world).next();
+        if request.is_some() {
+            break;
+        }
+    }
+    let request = request.expect("a pilot request is initiated");
+    pilot_request::Respond { request, approve: true }.apply(app.world_mut());
+    advance(&mut app, 200);
+
+    let result = session::build_result(app.world_mut());
+    assert!(session::verify_checksum(&result));
+    assert_eq!(result.actions.len(), 2, "instruction and response are recorded");
+
+    let report = session::replay::run(file, &result).expect("replay session");
+    assert!(report.skipped_actions.is_empty(), "skipped actions: {:?}", report.skipped_actions);
+    assert!(report.matches(&result));
+}
+
+#[test]
+fn saved_objects_reload_with_pending_instructions() {
+    let mut file = blank_with_demo_objects();
+    let name = first_plane(&mut file).aircraft.name.clone();
+
+    let mut app = load_app(file.clone());
+    advance(&mut app, 20);
+
+    let world = app.world_mut();
+    let object = world
+        .query::<(Entity, &object::Display)>()
+        .iter(world)
+        .find(|(_, display)| display.name == name)
+        .map(|(entity, _)| entity)
+        .expect("plane is spawned");
+    world.commands().send_instruction(object, instr::SetSpeed { target: Speed::from_knots(220.) });
+    world.flush();
+
+    let saved = object::saver::save(world);
+    assert_eq!(saved.len(), file.objects.len(), "all demo objects are saved");
+
+    file.objects = saved;
+    let saved_plane = named_plane(&mut file, &name).clone();
+    assert!(matches!(
+        saved_plane.aircraft.instructions.pending.as_slice(),
+        [store::PendingInstruction { instruction: store::InstructionRecord::SetSpeed { .. }, .. }]
+    ));
+
+    let mut reloaded = load_app(file.clone());
+    let world = reloaded.world_mut();
+    assert!(world.resource::<load::Issues>().0.is_empty(), "saved objects load without issues");
+
+    let resaved = object::saver::save(world);
+    assert_eq!(resaved.len(), file.objects.len());
+    file.objects = resaved;
+    let resaved_plane = named_plane(&mut file, &name);
+    assert_eq!(resaved_plane.aircraft.position, saved_plane.aircraft.position);
+    assert_eq!(resaved_plane.aircraft.altitude, saved_plane.aircraft.altitude);
+    assert_eq!(resaved_plane.route.nodes.len(), saved_plane.route.nodes.len());
+    assert_eq!(
+        resaved_plane.aircraft.instructions.pending.len(),
+        saved_plane.aircraft.instructions.pending.len()
+    );
+}
+
+#[test]
+fn saved_notes_reload_with_remaining_time() {
+    let mut file = blank_with_demo_objects();
+    let plane = first_plane(&mut file);
+    let name = plane.aircraft.name.clone();
+    plane.aircraft.notes = store::Notes {
+        scratchpad: "expect ILS 18L".into(),
+        reminders:  vec![store::Reminder {
+            text:      "handover".into(),
+            remaining: Duration::from_mins(10),
+        }],
+    };
+
+    let mut app = load_app(file.clone());
+    advance(&mut app, 20);
+    file.objects = object::saver::save(app.world_mut());
+    let notes = named_plane(&mut file, &name).aircraft.notes.clone();
+    assert_eq!(notes.scratchpad, "expect ILS 18L");
+    assert!(matches!(
+        notes.reminders.as_slice(),
+        [store::Reminder { text, remaining }]
+            if text == "handover" && *remaining == Duration::from_mins(10) - Duration::from_secs(1)
+    ));
+
+    let mut reloaded = load_app(file.clone());
+    file.objects = object::saver::save(reloaded.world_mut());
+    let reloaded_notes = &named_plane(&mut file, &name).aircraft.notes;
+    assert_eq!(reloaded_notes.scratchpad, notes.scratchpad);
+    assert_eq!(reloaded_notes.reminders[0].remaining, notes.reminders[0].remaining);
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use math::{Heading, Position, Speed};
+use store::Score;
+
+use crate::{airlines, demo};
+
+const DAY: Duration = Duration::from_hours(24);
+
+/// A row of a timetable CSV file.
+pub struct Row<'a> {
+    pub callsign:    &'a str,
+    pub object_type: &'a str,
+    pub origin:      &'a str,
+    pub destination: &'a str,
+}
+
+/// Parses a timetable from CSV.
+///
+/// Each line has the columns `callsign,type,origin,destination,HH:MM`.
+/// Empty lines, lines starting with `#` and a header line starting with `callsign` are ignored.
+/// The scheduled time is a time of day,
+/// which is converted to the time since `start_time_of_day`,
+/// wrapping to the next day if necessary.
+///
+/// `resolve` determines the spawn position and route of a flight from its row.
+///
+/// # Errors
+/// If the CSV is malformed or `resolve` returns an error.
+pub fn parse_csv(
+    csv: &str,
+    start_time_of_day: Duration,
+    mut resolve: impl FnMut(&Row) -> Result<(store::SpawnPosition, store::SpawnRoute)>,
+) -> Result<Vec<store::TimetableFlight>> {
+    let mut flights = Vec::new();
+
+    for (line_index, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("callsign,") {
+            continue;
+        }
+
+        let flight = parse_line(line, start_time_of_day, &mut resolve)
+            .with_context(|| format!("line {}", line_index + 1))?;
+        flights.push(flight);
+    }
+
+    Ok(flights)
+}
+
+fn parse_line(
+    line: &str,
+    start_time_of_day: Duration,
+    resolve: &mut impl FnMut(&Row) -> Result<(store::SpawnPosition, store::SpawnRoute)>,
+) -> Result<store::TimetableFlight> {
+    let columns: Vec<_> = line.split(',').map(str::trim).collect();
+    let &[callsign, object_type, origin, destination, time] = columns.as_slice() else {
+        bail!("expected 5 columns, got {}", columns.len());
+    };
+
+    let time_of_day = parse_time_of_day(time)?;
+    let day = DAY.as_secs();
+    let scheduled = Duration::from_secs(
+        (time_of_day.as_secs() + day - start_time_of_day.as_secs() % day) % day,
+    );
+
+    let row = Row { callsign, object_type, origin, destination };
+    let (position, route) = resolve(&row)?;
+
+    Ok(store::TimetableFlight {
+        callsign: callsign.into(),
+        telephony: airlines::telephony(callsign),
+        object_type: store::ObjectTypeRef(object_type.into()),
+        origin: origin.into(),
+        destination: destination.into(),
+        scheduled,
+        position,
+        route,
+    })
+}
+
+fn parse_time_of_day(time: &str) -> Result<Duration> {
+    let (hours, minutes) = time.split_once(':').context("time should be in HH:MM format")?;
+    let hours: u64 = hours.parse().context("invalid hours")?;
+    let minutes: u64 = minutes.parse().context("invalid minutes")?;
+    if hours >= 24 || minutes >= 60 {
+        bail!("time {time} is out of range");
+    }
+    Ok(Duration::from_hours(hours) + Duration::from_mins(minutes))
+}
+
+/// The demo map with traffic spawned from a timetable instead of random spawn sets.
+///
+/// # Panics
+/// If the builtin timetable is invalid.
+#[must_use]
+pub fn file() -> store::File {
+    let mut map = demo::file();
+    map.meta = store::Meta {
+        id:            "omniatc.timetable".into(),
+        title:         "Timetable".into(),
+        description:   "Demo map with scheduled arrivals".into(),
+        authors:       ["omniatc".into()].into(),
+        tags:          [("region", "fictional"), ("source", "builtin"), ("type", "scenario")]
+            .into_iter()
+            .map(|(k, v)| (String::from(k), String::from(v)))
+            .collect(),
+        next_scenario: None,
+        mode:          store::FacilityMode::Full,
+    };
+
+    let flights =
+        parse_csv(include_str!("timetable/demo.csv"), map.clock.time_of_day, resolve_demo)
+            .expect("builtin timetable should be valid");
+    map.level.spawn_trigger = store::SpawnTrigger::Timetable(store::Timetable {
+        flights,
+        lead_time: Duration::from_mins(15),
+        on_time_tolerance: Duration::from_mins(5),
+    });
+    map.quests = store::QuestTree::default();
+    map.objects = [].into();
+    map
+}
+
+/// Arrivals from the south enter at OCEAN, and the others enter at POLAR.
+fn resolve_demo(row: &Row) -> Result<(store::SpawnPosition, store::SpawnRoute)> {
+    if row.destination != "MAIN" {
+        bail!("unsupported destination {}", row.destination);
+    }
+
+    let (waypoint, preset, altitude, heading) = match row.origin {
+        "VHHH" | "RJTT" => ("OCEAN", "DWIND18L DWIND", 12000., 300.),
+        _ => ("POLAR", "POLAR18L POLAR", 10000., 200.),
+    };
+
+    Ok((
+        store::SpawnPosition::Airborne {
+            waypoint: waypoint.into(),
+            altitude: Position::from_amsl_feet(altitude),
+            speed:    Speed::from_knots(280.),
+            heading:  Heading::from_degrees(heading),
+        },
+        store::SpawnRoute {
+            preset:      store::RoutePresetRef(preset.into()),
+            destination: store::Destination::Landing { aerodrome: "MAIN".into() },
+            score:       Score(10),
+        },
+    ))
+}
@@ -1,54 +1,66 @@
+use std::collections::BTreeMap;
+
 use math::{Accel, AngularSpeed, Heading, Length, Position, Speed};
 use store::Score;
 
 use crate::{common_types, demo};
 
+#[cfg(test)]
+mod tests;
+
 fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest>> {
     [
         store::Quest {
-            id:               "tutorial/drag".into(),
-            title:            "Tutorial: Camera (1/3)".into(),
-            description:      "Right-click the radar view and drag to move the camera.".into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     [].into(),
-            conditions:       [store::UiQuestCompletionCondition::CameraDrag.into()].into(),
-            ui_highlight:     [store::HighlightableUiElement::RadarView].into(),
-            completion_hooks: [].into(),
+            id:                 "tutorial/drag".into(),
+            title:              "Tutorial: Camera (1/3)".into(),
+            description:        "Right-click the radar view and drag to move the camera.".into(),
+            class:              store::QuestClass::Tutorial,
+            dependencies:       [].into(),
+            conditions:         [store::UiQuestCompletionCondition::CameraDrag.into()].into(),
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [store::HighlightableUiElement::RadarView].into(),
+            completion_hooks:   [].into(),
         },
         store::Quest {
-            id:               "tutorial/zoom".into(),
-            title:            "Tutorial: Camera (2/3)".into(),
-            description:      "Scroll on the radar view up and down to zoom in and out.".into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     ["tutorial/drag".into()].into(),
-            conditions:       [store::UiQuestCompletionCondition::CameraZoom.into()].into(),
-            ui_highlight:     [
+            id:                 "tutorial/zoom".into(),
+            title:              "Tutorial: Camera (2/3)".into(),
+            description:        "Scroll on the radar view up and down to zoom in and out.".into(),
+            class:              store::QuestClass::Tutorial,
+            dependencies:       ["tutorial/drag".into()].into(),
+            conditions:         [store::UiQuestCompletionCondition::CameraZoom.into()].into(),
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [
                 store::HighlightableUiElement::RadarView,
                 store::HighlightableUiElement::SetCameraZoom,
             ]
             .into(),
-            completion_hooks: [].into(),
+            completion_hooks:   [].into(),
         },
         store::Quest {
-            id:               "tutorial/rotate".into(),
-            title:            "Tutorial: Camera (3/3)".into(),
-            description:      concat!(
+            id:                 "tutorial/rotate".into(),
+            title:              "Tutorial: Camera (3/3)".into(),
+            description:        concat!(
                 "Scroll on the radar view left and right, ",
                 "or use the slider in the Level menu to rotate.",
             )
             .into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     ["tutorial/zoom".into()].into(),
-            conditions:       [store::UiQuestCompletionCondition::CameraRotate.into()].into(),
-            ui_highlight:     [
+            class:              store::QuestClass::Tutorial,
+            dependencies:       ["tutorial/zoom".into()].into(),
+            conditions:         [store::UiQuestCompletionCondition::CameraRotate.into()].into(),
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [
                 store::HighlightableUiElement::RadarView,
                 store::HighlightableUiElement::SetCameraRotation,
             ]
             .into(),
-            completion_hooks: [store::QuestCompletionHook::SpawnObject {
+            completion_hooks:   [store::QuestCompletionHook::SpawnObject {
                 object: Box::new(store::Object::Plane(store::Plane {
                     aircraft:    store::BaseAircraft {
                         name:             "ABC123".into(),
+                        id:               None,
                         dest:             store::Destination::Landing { aerodrome: "MAIN".into() },
                         completion_score: Score(10),
                         position:         Position::from_origin_nm(-5.0, -5.0),
@@ -56,6 +68,11 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
                         ground_speed:     Speed::from_knots(289.0),
                         ground_dir:       Heading::EAST,
                         vert_rate:        Speed::ZERO,
+                        endurance:        None,
+                        vfr:              None,
+                        formation_size:   None,
+                        notes:            store::Notes::default(),
+                        instructions:     store::InstructionState::default(),
                     },
                     control:     store::PlaneControl {
                         heading:     Heading::EAST,
@@ -65,6 +82,8 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
                     object_type: store::ObjectTypeRef("A359".into()),
                     taxi_limits: common_types::a359_taxi_limits(),
                     nav_limits:  common_types::a359_nav_limits(),
+                    equipage:    store::Equipage::all(),
+                    category:    store::Category::Jet,
                     nav_target:  store::NavTarget::Airborne(Box::new(store::AirborneNavTarget {
                         yaw:              store::YawTarget::Heading(Heading::EAST),
                         horiz_ias:        Some(Speed::from_knots(280.0)),
@@ -81,23 +100,29 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
             .into(),
         },
         store::Quest {
-            id:               "tutorial/focus".into(),
-            title:            "Tutorial: Aircraft control (1/5)".into(),
-            description:      concat!(
+            id:                 "tutorial/focus".into(),
+            title:              "Tutorial: Aircraft control (1/5)".into(),
+            description:        concat!(
                 "An aircraft has just entered our airspace! ",
                 "Click on it in the radar view or in the Vehicles table to view details.",
             )
             .into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     ["tutorial/rotate".into()].into(),
-            conditions:       [store::UiQuestCompletionCondition::ObjectSelect.into()].into(),
-            ui_highlight:     [store::HighlightableUiElement::ObjectSelect].into(),
-            completion_hooks: [].into(),
+            class:              store::QuestClass::Tutorial,
+            dependencies:       ["tutorial/rotate".into()].into(),
+            conditions:         [store::UiQuestCompletionCondition::ObjectSelect.into()].into(),
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [
+                store::HighlightableUiElement::ObjectSelect,
+                store::HighlightableUiElement::Aircraft("ABC123".into()),
+            ]
+            .into(),
+            completion_hooks:   [].into(),
         },
         store::Quest {
-            id:               "tutorial/altitude".into(),
-            title:            "Tutorial: Aircraft control (2/5)".into(),
-            description:      concat!(
+            id:                 "tutorial/altitude".into(),
+            title:              "Tutorial: Aircraft control (2/5)".into(),
+            description:        concat!(
                 "The aircraft is currently at 8000 feet. ",
                 "Let's prepare it for landing by descending to 6000 feet.\n",
                 r#"Drag the altitude slider and click "Send" "#,
@@ -109,9 +134,39 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
                 "or hold Shift-Space to fast-forward.",
             )
             .into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     ["tutorial/focus".into()].into(),
-            conditions:       [store::ObjectControlQuestCompletionCondition::ReachAltitude(
+            class:              store::QuestClass::Tutorial,
+            dependencies:       ["tutorial/focus".into()].into(),
+            conditions:         [store::ObjectControlQuestCompletionCondition::ReachAltitude(
+                store::Range {
+                    min: Position::from_amsl_feet(5950.0),
+                    max: Position::from_amsl_feet(6050.0),
+                },
+            )
+            .into()]
+            .into(),
+            failure_conditions: [store::QuestFailureCondition::ExitAltitude(store::Range {
+                min: Position::from_amsl_feet(5000.0),
+                max: Position::from_amsl_feet(8500.0),
+            })]
+            .into(),
+            on_fail:            ["tutorial/altitude-bust".into()].into(),
+            ui_highlight:       [store::HighlightableUiElement::SetAltitude].into(),
+            completion_hooks:   [].into(),
+        },
+        store::Quest {
+            id:                 "tutorial/altitude-bust".into(),
+            title:              "Tutorial: Aircraft control (2/5)".into(),
+            description:        concat!(
+                "Oops, the aircraft has left the altitude we wanted! ",
+                "Other traffic may be flying just above or below, ",
+                "so an altitude bust can quickly lead to a conflict.\n",
+                "Let's bring it back to 6000 feet ",
+                r#"by setting the altitude slider and clicking "Send"."#,
+            )
+            .into(),
+            class:              store::QuestClass::Tutorial,
+            dependencies:       [].into(),
+            conditions:         [store::ObjectControlQuestCompletionCondition::ReachAltitude(
                 store::Range {
                     min: Position::from_amsl_feet(5950.0),
                     max: Position::from_amsl_feet(6050.0),
@@ -119,32 +174,36 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
             )
             .into()]
             .into(),
-            ui_highlight:     [store::HighlightableUiElement::SetAltitude].into(),
-            completion_hooks: [].into(),
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [store::HighlightableUiElement::SetAltitude].into(),
+            completion_hooks:   [].into(),
         },
         store::Quest {
-            id:               "tutorial/speed".into(),
-            title:            "Tutorial: Aircraft control (3/5)".into(),
-            description:      concat!(
+            id:                 "tutorial/speed".into(),
+            title:              "Tutorial: Aircraft control (3/5)".into(),
+            description:        concat!(
                 "The plane is too fast to land right now. Let's slow it down to 230 knots.\n",
                 r#"Drag the speed slider on the right, or press ","/"." to adjust the speed. "#,
                 r#"Remember to click "Send" or press Enter to send the instruction!"#,
             )
             .into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     ["tutorial/focus".into()].into(),
-            conditions:       [store::ObjectControlQuestCompletionCondition::ReachSpeed(
+            class:              store::QuestClass::Tutorial,
+            dependencies:       ["tutorial/focus".into()].into(),
+            conditions:         [store::ObjectControlQuestCompletionCondition::ReachSpeed(
                 store::Range { min: Speed::from_knots(225.0), max: Speed::from_knots(235.0) },
             )
             .into()]
             .into(),
-            ui_highlight:     [store::HighlightableUiElement::SetSpeed].into(),
-            completion_hooks: [].into(),
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [store::HighlightableUiElement::SetSpeed].into(),
+            completion_hooks:   [].into(),
         },
         store::Quest {
-            id:               "tutorial/heading".into(),
-            title:            "Tutorial: Aircraft control (4/5)".into(),
-            description:      concat!(
+            id:                 "tutorial/heading".into(),
+            title:              "Tutorial: Aircraft control (4/5)".into(),
+            description:        concat!(
                 "The plane is currently above the aerodrome east of the runways. ",
                 "The current wind direction is from the southeast, ",
                 "so arrivals will land from the north of the runway. ",
@@ -158,9 +217,9 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
                 "so I recommend giving a heading of 005\u{b0} to compensate for it."
             )
             .into(),
-            class:            store::QuestClass::Tutorial,
-            dependencies:     ["tutorial/altitude".into(), "tutorial/speed".into()].into(),
-            conditions:       [store::ObjectControlQuestCompletionCondition::ReachHeading(
+            class:              store::QuestClass::Tutorial,
+            dependencies:       ["tutorial/altitude".into(), "tutorial/speed".into()].into(),
+            conditions:         [store::ObjectControlQuestCompletionCondition::ReachHeading(
                 store::Range {
                     min: Heading::from_degrees(355.0),
                     max: Heading::from_degrees(15.0),
@@ -168,8 +227,10 @@ fn quests(waypoints: Vec<store::NamedWaypointRef>) -> impl Into<Vec<store::Quest
             )
             .into()]
             .into(),
-            ui_highlight:     [store::HighlightableUiElement::SetHeading].into(),
-            completion_hooks: waypoints
+            failure_conditions: [].into(),
+            on_fail:            [].into(),
+            ui_highlight:       [store::HighlightableUiElement::SetHeading].into(),
+            completion_hooks:   waypoints
                 .into_iter()
                 .map(|waypoint| store::QuestCompletionHook::RevealWaypoint { waypoint })
                 .collect(),
@@ -190,11 +251,11 @@ pub fn file() -> store::File {
 
     store::File {
         meta:    store::Meta {
-            id:          "omniatc.tutorial".into(),
-            title:       "Tutorial".into(),
-            description: "Tutorial map".into(),
-            authors:     ["omniatc".into()].into(),
-            tags:        [
+            id:            "omniatc.tutorial".into(),
+            title:         "Tutorial".into(),
+            description:   "Tutorial map".into(),
+            authors:       ["omniatc".into()].into(),
+            tags:          [
                 ("region", "fictional"),
                 ("source", "builtin"),
                 ("type", "scenario"),
@@ -203,18 +264,23 @@ pub fn file() -> store::File {
             .into_iter()
             .map(|(k, v)| (String::from(k), String::from(v)))
             .collect(),
+            next_scenario: Some("omniatc.demo".into()),
+            mode:          store::FacilityMode::Full,
         },
         level:   demo_level,
         ui:      store::Ui {
-            camera: store::Camera::TwoDimension(store::Camera2d {
+            camera:           store::Camera::TwoDimension(store::Camera2d {
                 center:       Position::from_origin_nm(0.0, 0.0),
                 up:           Heading::NORTH,
                 scale_axis:   store::AxisDirection::X,
                 scale_length: Length::from_nm(100.0),
             }),
+            display_settings: BTreeMap::new(),
         },
         stats:   store::Stats::default(),
         quests:  store::QuestTree { quests: quests(waypoints).into() },
         objects: [].into(),
+        clock:   store::Clock::default(),
+        scripts: Vec::new(),
     }
 }
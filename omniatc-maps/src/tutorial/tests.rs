@@ -0,0 +1,341 @@
+//! Golden trace regression tests for scripted flights on the tutorial map.
+//!
+//! Each test runs the level headlessly and records the trajectory of a single aircraft,
+//! which is compared against `traces/<name>.baseline.csv` within per-field tolerances.
+//! A missing baseline is generated from the current run,
+//! so deleting a baseline file regenerates it after an intentional behavior change.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+use bevy::app::{App, TaskPoolPlugin};
+use bevy::ecs::query::Has;
+use bevy::ecs::system::Command;
+use bevy::time::{TimePlugin, TimeUpdateStrategy};
+use math::{Accel, AngularSpeed, Heading, Length, Position, Speed};
+use omniatc::level::object::{self, Object};
+use omniatc::{level, load, util};
+use store::Score;
+
+use crate::common_types;
+use crate::demo::MAIN_AERODROME_ELEVATION;
+
+/// Duration of each simulated frame.
+const FRAME: Duration = Duration::from_millis(50);
+/// Number of frames between two recorded samples.
+const FRAMES_PER_SAMPLE: u32 = 20;
+/// Samples after which the recording is aborted if the stop condition is never met.
+const MAX_SAMPLES: usize = 900;
+
+const CALLSIGN: &str = "TRC001";
+
+/// A recorded state of the traced aircraft.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    /// Seconds since the level was loaded.
+    time:         f32,
+    /// Horizontal position in nautical miles.
+    x:            f32,
+    y:            f32,
+    /// Altitude in feet.
+    altitude:     f32,
+    /// Horizontal ground speed in knots.
+    ground_speed: f32,
+    /// Vertical rate in feet per minute.
+    vert_rate:    f32,
+    on_ground:    bool,
+}
+
+impl Sample {
+    const HEADER: &str = "time,x,y,altitude,ground_speed,vert_rate,on_ground";
+
+    fn to_csv(self) -> String {
+        format!(
+            "{:.1},{:.4},{:.4},{:.1},{:.2},{:.1},{}",
+            self.time,
+            self.x,
+            self.y,
+            self.altitude,
+            self.ground_speed,
+            self.vert_rate,
+            u8::from(self.on_ground),
+        )
+    }
+
+    fn from_csv(line: &str) -> Self {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [time, x, y, altitude, ground_speed, vert_rate, on_ground] = fields[..] else {
+            panic!("malformed trace line {line:?}");
+        };
+        let parse = |field: &str| -> f32 {
+            field.parse().unwrap_or_else(|err| panic!("malformed field {field:?}: {err}"))
+        };
+        Self {
+            time:         parse(time),
+            x:            parse(x),
+            y:            parse(y),
+            altitude:     parse(altitude),
+            ground_speed: parse(ground_speed),
+            vert_rate:    parse(vert_rate),
+            on_ground:    on_ground == "1",
+        }
+    }
+}
+
+/// Maximum deviation of each field from the baseline.
+struct Tolerance {
+    position:     Length<f32>,
+    altitude:     Length<f32>,
+    ground_speed: Speed<f32>,
+    vert_rate:    Speed<f32>,
+}
+
+const TOLERANCE: Tolerance = Tolerance {
+    position:     Length::from_meters(30.),
+    altitude:     Length::from_feet(20.),
+    ground_speed: Speed::from_knots(2.),
+    vert_rate:    Speed::from_fpm(100.),
+};
+
+impl Tolerance {
+    fn check(&self, expected: &Sample, actual: &Sample) -> Result<(), String> {
+        let position_error = Length::from_nm((expected.x - actual.x).hypot(expected.y - actual.y));
+        let checks = [
+            ("position (nm)", position_error.into_nm(), self.position.into_nm()),
+            ("altitude (ft)", expected.altitude - actual.altitude, self.altitude.into_feet()),
+            (
+                "ground speed (kt)",
+                expected.ground_speed - actual.ground_speed,
+                self.ground_speed.into_knots(),
+            ),
+            (
+                "vertical rate (fpm)",
+                expected.vert_rate - actual.vert_rate,
+                self.vert_rate.into_fpm(),
+            ),
+        ];
+
+        for (name, error, tolerance) in checks {
+            if error.abs() > tolerance {
+                return Err(format!("{name} deviates by {error} at t={}s", actual.time));
+            }
+        }
+        if expected.on_ground != actual.on_ground {
+            return Err(format!("ground contact differs at t={}s", actual.time));
+        }
+        Ok(())
+    }
+}
+
+/// Builds a headless app with the tutorial level and `plane` as its only object.
+fn tutorial_app(plane: store::Plane) -> App {
+    let mut file = super::file();
+    file.objects = [store::Object::Plane(plane)].into();
+
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TimePlugin,
+        level::Plug::<()>::default(),
+        load::Plug,
+        util::Plug,
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+
+    load::Command {
+        source:   load::Source::Parsed(Box::new(file)),
+        on_error: Box::new(|_, err| panic!("cannot load tutorial level: {err}")),
+    }
+    .apply(app.world_mut());
+    app
+}
+
+fn sample(app: &mut App, time: Duration) -> Option<Sample> {
+    let world = app.world_mut();
+    let mut query =
+        world
+            .query::<(&object::Display, &Object, Option<&object::Airborne>, Has<object::OnGround>)>(
+            );
+    let (_, &Object { position, ground_speed }, airborne, on_ground) =
+        query.iter(world).find(|(display, ..)| display.name == CALLSIGN)?;
+
+    Some(Sample {
+        time: time.as_secs_f32(),
+        x: position.horizontal().get().x,
+        y: position.horizontal().get().y,
+        altitude: (position.altitude() - Position::SEA_LEVEL).into_feet(),
+        ground_speed: ground_speed.horizontal().magnitude_exact().into_knots(),
+        vert_rate: airborne.map_or(0., |airborne| airborne.airspeed.vertical().into_fpm()),
+        on_ground,
+    })
+}
+
+/// Records samples of the traced aircraft until `stop` returns true or the aircraft despawns.
+fn record(mut app: App, mut stop: impl FnMut(&Sample) -> bool) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    let mut elapsed = Duration::ZERO;
+
+    while let Some(sample) = sample(&mut app, elapsed) {
+        samples.push(sample);
+        if stop(&sample) {
+            break;
+        }
+        assert!(samples.len() < MAX_SAMPLES, "trace did not reach the stop condition");
+
+        for _ in 0..FRAMES_PER_SAMPLE {
+            app.update();
+        }
+        elapsed += FRAME * FRAMES_PER_SAMPLE;
+    }
+
+    samples
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tutorial/traces")
+        .join(format!("{name}.baseline.csv"))
+}
+
+/// Compares `samples` against the stored baseline, generating it if it does not exist.
+fn assert_golden(name: &str, samples: &[Sample]) {
+    let path = baseline_path(name);
+
+    let expected = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let mut contents = String::from(Sample::HEADER);
+            for sample in samples {
+                writeln!(contents).expect("write to string");
+                contents.push_str(&sample.to_csv());
+            }
+            contents.push('\n');
+
+            fs::create_dir_all(path.parent().expect("baseline path has parent"))
+                .expect("create trace directory");
+            fs::write(&path, contents).expect("write trace baseline");
+            println!("Generated new trace baseline at {}", path.display());
+            return;
+        }
+        Err(err) => panic!("cannot read trace baseline {}: {err}", path.display()),
+    };
+
+    let expected: Vec<_> = expected.lines().skip(1).map(Sample::from_csv).collect();
+
+    for (expected, actual) in expected.iter().zip(samples) {
+        // Round the actual sample to the stored precision before comparing.
+        let actual = Sample::from_csv(&actual.to_csv());
+        if let Err(err) = TOLERANCE.check(expected, &actual) {
+            panic!("{name} trace diverges from baseline: {err}");
+        }
+    }
+    assert_eq!(
+        expected.len(),
+        samples.len(),
+        "{name} trace has a different duration from baseline"
+    );
+}
+
+fn plane(
+    position: Position<bevy::math::Vec2>,
+    altitude: Position<f32>,
+    ground_speed: Speed<f32>,
+    dest: store::Destination,
+    nav_target: store::NavTarget,
+    nodes: Vec<store::RouteNode>,
+) -> store::Plane {
+    store::Plane {
+        aircraft: store::BaseAircraft {
+            name: CALLSIGN.into(),
+            id: None,
+            dest,
+            completion_score: Score(0),
+            position,
+            altitude,
+            ground_speed,
+            ground_dir: Heading::SOUTH,
+            vert_rate: Speed::ZERO,
+            endurance: None,
+            vfr: None,
+            formation_size: None,
+            notes: store::Notes::default(),
+            instructions: store::InstructionState::default(),
+        },
+        control: store::PlaneControl {
+            heading:     Heading::SOUTH,
+            yaw_speed:   AngularSpeed::ZERO,
+            horiz_accel: Accel::ZERO,
+        },
+        object_type: store::ObjectTypeRef("A359".into()),
+        taxi_limits: common_types::a359_taxi_limits(),
+        nav_limits: common_types::a359_nav_limits(),
+        equipage: store::Equipage::all(),
+        category: store::Category::Jet,
+        nav_target,
+        route: store::Route { id: None, nodes },
+    }
+}
+
+fn runway_18r() -> store::RunwayRef {
+    store::RunwayRef { aerodrome: "MAIN".into(), runway_name: "18R".into() }
+}
+
+/// An aircraft established on the 18R localizer at 8 nm flies the approach, flares and rolls out.
+#[test]
+fn landing_18r() {
+    let app = tutorial_app(plane(
+        Position::from_origin_nm(0., 8.),
+        Position::from_amsl_feet(2800.),
+        Speed::from_knots(170.),
+        store::Destination::Parking { aerodrome: "MAIN".into() },
+        store::NavTarget::Airborne(Box::new(store::AirborneNavTarget {
+            yaw:              store::YawTarget::Heading(Heading::SOUTH),
+            horiz_ias:        Some(Speed::from_knots(170.)),
+            vert_rate:        Speed::ZERO,
+            expedite:         false,
+            target_altitude:  None,
+            target_glide:     None,
+            target_waypoint:  None,
+            target_alignment: None,
+        })),
+        vec![store::RouteNode::RunwayLanding {
+            runway:          runway_18r(),
+            goaround_preset: None,
+            current_phase:   store::LandingPhase::Align,
+        }],
+    ));
+
+    let samples = record(app, |sample| sample.on_ground && sample.ground_speed < 40.);
+    assert!(samples.last().is_some_and(|sample| sample.on_ground), "aircraft did not land");
+    assert_golden("landing_18r", &samples);
+}
+
+/// An aircraft lined up on 18R rolls, rotates and climbs to its initial altitude.
+#[test]
+fn takeoff_18r() {
+    let app = tutorial_app(plane(
+        Position::ORIGIN,
+        MAIN_AERODROME_ELEVATION,
+        Speed::ZERO,
+        store::Destination::Departure {
+            min_altitude:       Some(Position::from_amsl_feet(18000.)),
+            waypoint_proximity: None,
+        },
+        store::NavTarget::Ground(store::GroundNavTarget {
+            segment: store::SegmentRef {
+                aerodrome: "MAIN".into(),
+                label:     store::SegmentLabel::Runway("18R".into()),
+            },
+        }),
+        vec![store::RouteNode::RunwayTakeoff {
+            runway:          runway_18r(),
+            target_altitude: Position::from_amsl_feet(4000.),
+        }],
+    ));
+
+    let samples = record(app, |sample| sample.altitude >= 3000.);
+    assert!(samples.last().is_some_and(|sample| !sample.on_ground), "aircraft did not take off");
+    assert_golden("takeoff_18r", &samples);
+}
@@ -0,0 +1,93 @@
+//! Enforcement of the constraints declared in config field metadata.
+//!
+//! Metadata entries such as `#[config(min = ..., max = ..., validate = ...)]`
+//! only restrict the editor widgets by themselves.
+//! [`Constrained::constrain`] applies the same constraints to values from other sources,
+//! such as deserialized config profiles,
+//! so that consumers can rely on the declared range without clamping it again.
+
+use std::time::Duration;
+
+use bevy_mod_config::ConfigField;
+use bevy_mod_config::impls::NumericMetadata;
+
+use crate::{
+    Angle, AngleMetadata, Length, LengthMetadata, Position, PositionMetadata, Speed, SpeedMetadata,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A config field type whose metadata declares constraints on its value.
+pub trait Constrained: ConfigField + Sized {
+    /// Restricts `value` to the constraints declared in `metadata`.
+    ///
+    /// Values outside the declared range are clamped to the nearest bound.
+    /// Values that cannot be ordered (NaN) or are rejected by the validation function
+    /// are replaced by the default value.
+    #[must_use]
+    fn constrain(value: Self, metadata: &Self::Metadata) -> Self;
+}
+
+fn clamp<T: PartialOrd>(value: T, min: T, max: T, default: T) -> T {
+    if value.partial_cmp(&min).is_none() || value.partial_cmp(&max).is_none() {
+        default
+    } else if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+fn validate<T: Copy>(value: T, validate: Option<fn(T) -> bool>, default: T) -> T {
+    match validate {
+        Some(validate) if !validate(value) => default,
+        _ => value,
+    }
+}
+
+macro_rules! impl_constrained_numeric {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Constrained for $ty {
+                fn constrain(value: Self, metadata: &NumericMetadata<Self>) -> Self {
+                    clamp(value, metadata.min, metadata.max, metadata.default)
+                }
+            }
+        )*
+    };
+}
+
+impl_constrained_numeric!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, Duration,
+);
+
+impl Constrained for Length<f32> {
+    fn constrain(value: Self, metadata: &LengthMetadata) -> Self {
+        let value = clamp(value, metadata.min, metadata.max, metadata.default);
+        validate(value, metadata.validate, metadata.default)
+    }
+}
+
+impl Constrained for Speed<f32> {
+    fn constrain(value: Self, metadata: &SpeedMetadata) -> Self {
+        let value = clamp(value, metadata.min, metadata.max, metadata.default);
+        validate(value, metadata.validate, metadata.default)
+    }
+}
+
+impl Constrained for Angle {
+    fn constrain(value: Self, metadata: &AngleMetadata) -> Self {
+        let value = clamp(value, metadata.min, metadata.max, metadata.default);
+        validate(value, metadata.validate, metadata.default)
+    }
+}
+
+impl Constrained for Position<f32> {
+    fn constrain(value: Self, metadata: &PositionMetadata) -> Self {
+        let value = clamp(value, metadata.min, metadata.max, metadata.default);
+        validate(value, metadata.validate, metadata.default)
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use bevy_mod_config::impls::NumericMetadata;
+
+use super::Constrained;
+use crate::{Angle, AngleMetadata, Length, LengthMetadata};
+
+fn length_metadata() -> LengthMetadata {
+    LengthMetadata {
+        default: Length::from_nm(1.0),
+        min: Length::ZERO,
+        max: Length::from_nm(5.0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn length_clamped_to_range() {
+    let metadata = length_metadata();
+    assert_eq!(Length::constrain(Length::from_nm(-2.0), &metadata), Length::ZERO);
+    assert_eq!(Length::constrain(Length::from_nm(7.0), &metadata), Length::from_nm(5.0));
+    assert_eq!(Length::constrain(Length::from_nm(3.0), &metadata), Length::from_nm(3.0));
+    assert_eq!(Length::constrain(Length::from_nm(f32::INFINITY), &metadata), Length::from_nm(5.0));
+}
+
+#[test]
+fn nan_replaced_by_default() {
+    assert_eq!(
+        Length::constrain(Length::from_nm(f32::NAN), &length_metadata()),
+        Length::from_nm(1.0)
+    );
+
+    let metadata = NumericMetadata { default: 0.5, min: 0.0, max: 1.0, ..Default::default() };
+    assert!((f32::constrain(f32::NAN, &metadata) - 0.5).abs() < f32::EPSILON);
+}
+
+#[test]
+fn rejected_value_replaced_by_default() {
+    let metadata = AngleMetadata {
+        default:  Angle::from_degrees(10.0),
+        min:      Angle::ZERO,
+        max:      Angle::RIGHT,
+        validate: Some(|angle| angle > Angle::ZERO),
+    };
+    assert_eq!(Angle::constrain(Angle::ZERO, &metadata), Angle::from_degrees(10.0));
+    assert_eq!(Angle::constrain(Angle::from_degrees(30.0), &metadata), Angle::from_degrees(30.0));
+}
+
+#[test]
+fn numeric_clamped_to_range() {
+    let metadata = NumericMetadata {
+        default: Duration::from_secs(1),
+        min: Duration::from_millis(100),
+        max: Duration::from_secs(10),
+        ..Default::default()
+    };
+    assert_eq!(Duration::constrain(Duration::ZERO, &metadata), Duration::from_millis(100));
+    assert_eq!(Duration::constrain(Duration::from_mins(1), &metadata), Duration::from_secs(10));
+
+    let metadata = NumericMetadata { default: 3, min: 1, max: 5, ..Default::default() };
+    assert_eq!(u32::constrain(0, &metadata), 1);
+}
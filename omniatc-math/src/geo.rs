@@ -0,0 +1,42 @@
+//! Conversion between map coordinates and geodetic coordinates.
+
+use bevy_math::Vec2;
+
+use crate::Position;
+
+/// Nautical miles per degree of latitude.
+const NM_PER_DEGREE: f64 = 60.;
+
+/// A geodetic position in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GeoPoint {
+    /// Latitude in degrees, positive towards the north.
+    pub lat: f64,
+    /// Longitude in degrees, positive towards the east.
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Projects this point onto a map whose origin is at `origin`.
+    ///
+    /// Uses an equirectangular projection around the latitude of `origin`,
+    /// which is accurate within the extent of a typical terminal area.
+    #[must_use]
+    pub fn to_map(self, origin: GeoPoint) -> Position<Vec2> {
+        let x = (self.lon - origin.lon) * NM_PER_DEGREE * origin.lat.to_radians().cos();
+        let y = (self.lat - origin.lat) * NM_PER_DEGREE;
+        #[expect(clippy::cast_possible_truncation, reason = "map coordinates are f32")]
+        Position::from_origin_nm(x as f32, y as f32)
+    }
+
+    /// Inverse of [`to_map`](Self::to_map).
+    #[must_use]
+    pub fn from_map(position: Position<Vec2>, origin: GeoPoint) -> Self {
+        let Vec2 { x, y } = position.get();
+        Self {
+            lat: origin.lat + f64::from(y) / NM_PER_DEGREE,
+            lon: origin.lon + f64::from(x) / NM_PER_DEGREE / origin.lat.to_radians().cos(),
+        }
+    }
+}
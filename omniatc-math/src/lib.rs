@@ -18,6 +18,10 @@ pub use control::*;
 mod physics;
 pub use physics::*;
 
+mod geo;
+pub use geo::*;
+
+pub mod config;
 pub mod sweep;
 
 #[cfg(test)]
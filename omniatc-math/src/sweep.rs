@@ -271,15 +271,6 @@ where
     }
 
     fn yield_start(&mut self) -> Poll {
-        /// Equal to `1f32.to_degrees().cos().powi(2)`.
-        /// If the square of the dot product between two unit vectors exceeds this constant,
-        /// the angle between them is less than 1 degree, which we consider as parallel.
-        const COS_EPSILON_SQ: f32 = 0.9996954;
-
-        fn almost_parallel(v1: Vec2, v2: Vec2) -> bool {
-            v1.dot(v2).powi(2) / v1.length_squared() / v2.length_squared() > COS_EPSILON_SQ
-        }
-
         let &entry =
             slice_take_first(&mut self.starts).expect("yield_start called when starts is empty");
         let epsilon = NotNan::new(self.sweeper.epsilon.0).expect("epsilon must not be nan");
@@ -288,32 +279,13 @@ where
         for active in self.active_need.values().chain(
             entry_need_intersect.then(|| self.active_need_not.values()).into_iter().flatten(),
         ) {
-            let mut intersect = None;
-            if almost_parallel(active.dot_ortho_dir(epsilon), entry.dot_ortho_dir(epsilon)) {
-                // We assume the segments cannot overlap, so just check for the endpoint equality
-                for active_v in [active.start_dot_ortho(epsilon), active.end_dot_ortho(epsilon)] {
-                    for entry_v in [entry.start_dot_ortho(epsilon), entry.end_dot_ortho(epsilon)] {
-                        if active_v.distance_squared(entry_v) < epsilon.into_inner() {
-                            intersect = Some(active_v);
-                        }
-                    }
-                }
-            } else {
-                let (entry_t, active_t) = line_intersect(
-                    entry.start_dot_ortho(epsilon),
-                    entry.dot_ortho_dir(epsilon),
-                    active.start_dot_ortho(epsilon),
-                    active.dot_ortho_dir(epsilon),
-                );
-                let allowed_range = (-epsilon.into_inner())..=(1. + epsilon.into_inner());
-                if allowed_range.contains(&entry_t) && allowed_range.contains(&active_t) {
-                    intersect = Some(
-                        entry.start_dot_ortho(epsilon) + entry.dot_ortho_dir(epsilon) * entry_t,
-                    );
-                }
-            }
+            let points = segment_intersections(
+                [active.start_dot_ortho(epsilon), active.end_dot_ortho(epsilon)],
+                [entry.start_dot_ortho(epsilon), entry.end_dot_ortho(epsilon)],
+                epsilon.into_inner(),
+            );
 
-            if let Some(Vec2 { x: intersect_dot, y: intersect_ortho }) = intersect {
+            for Vec2 { x: intersect_dot, y: intersect_ortho } in points {
                 let intersect_position = Position::ORIGIN
                     + Length::new(
                         *self.sweeper.sweep_dir * intersect_dot
@@ -376,13 +348,6 @@ impl StartEntry {
     }
     fn end_dot(self, epsilon: NotNan<f32>) -> NotNan<f32> { self.end_dot_plus_epsilon - epsilon }
 
-    fn dot_ortho_dir(self, epsilon: NotNan<f32>) -> Vec2 {
-        Vec2::new(
-            (self.end_dot(epsilon) - self.start_dot(epsilon)).into_inner(),
-            (self.end_ortho - self.start_ortho).into_inner(),
-        )
-    }
-
     fn start_dot_ortho(self, epsilon: NotNan<f32>) -> Vec2 {
         Vec2::new(self.start_dot(epsilon).into_inner(), self.start_ortho.into_inner())
     }
@@ -407,7 +372,7 @@ pub struct LineIntersection {
 }
 
 impl PartialEq for LineIntersection {
-    fn eq(&self, other: &Self) -> bool { self.dot == other.dot && self.lines == other.lines }
+    fn eq(&self, other: &Self) -> bool { self.cmp(other).is_eq() }
 }
 
 impl Eq for LineIntersection {}
@@ -418,7 +383,12 @@ impl PartialOrd for LineIntersection {
 
 impl Ord for LineIntersection {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.dot.cmp(&other.dot).then_with(|| self.lines.cmp(&other.lines))
+        // `ortho` breaks ties between multiple intersections of the same collinear pair
+        // orthogonal to the sweep direction, keeping the output order deterministic.
+        self.dot
+            .cmp(&other.dot)
+            .then_with(|| self.lines.cmp(&other.lines))
+            .then_with(|| self.ortho.cmp(&other.ortho))
     }
 }
 
@@ -449,3 +419,46 @@ fn slice_take_first<'a, T>(slice: &mut &'a [T]) -> Option<&'a T> {
         _ => None,
     }
 }
+
+/// Returns the points at which the segments `a` and `b` intersect within `epsilon`.
+///
+/// Endpoints within `epsilon` from the other segment are reported as intersections,
+/// which handles segments sharing an endpoint, T-junctions
+/// and near-parallel segments touching at their ends.
+/// Collinear overlapping segments intersect at both ends of the overlap.
+/// Otherwise, segments intersect only if they properly cross each other,
+/// determined by the orientation of each segment's endpoints relative to the other,
+/// so that near-parallel crossings are neither missed nor reported spuriously.
+fn segment_intersections(a: [Vec2; 2], b: [Vec2; 2], epsilon: f32) -> Vec<Vec2> {
+    let mut points = Vec::new();
+    let touching = a
+        .into_iter()
+        .filter(|&point| point_segment_distance(point, b) < epsilon)
+        .chain(b.into_iter().filter(|&point| point_segment_distance(point, a) < epsilon));
+    for point in touching {
+        if points.iter().all(|&existing: &Vec2| existing.distance(point) >= epsilon) {
+            points.push(point);
+        }
+    }
+
+    if points.is_empty() && properly_crosses(a, b) && properly_crosses(b, a) {
+        let (a_t, _) = line_intersect(a[0], a[1] - a[0], b[0], b[1] - b[0]);
+        points.push(a[0] + (a[1] - a[0]) * a_t.clamp(0., 1.));
+    }
+
+    points
+}
+
+/// Whether the endpoints of `other` lie strictly on opposite sides of the line through `line`.
+fn properly_crosses(line: [Vec2; 2], other: [Vec2; 2]) -> bool {
+    let dir = line[1] - line[0];
+    let [side0, side1] = other.map(|point| dir.perp_dot(point - line[0]));
+    (side0 > 0. && side1 < 0.) || (side0 < 0. && side1 > 0.)
+}
+
+fn point_segment_distance(point: Vec2, [start, end]: [Vec2; 2]) -> f32 {
+    let dir = end - start;
+    let length_sq = dir.length_squared();
+    let t = if length_sq > 0. { ((point - start).dot(dir) / length_sq).clamp(0., 1.) } else { 0. };
+    point.distance(start + dir * t)
+}
@@ -327,3 +327,96 @@ fn assert_groups(
         panic!("Unexpected group {group:?}, expected one of {expect_groups:?}");
     }
 }
+
+fn sweep_lines(lines: &[[Position<Vec2>; 2]], sweep_dir: Dir2) -> Vec<LineIntersection> {
+    LineSweeper::new(
+        |LineIndex(index)| Line {
+            alpha:          lines[index][0],
+            beta:           lines[index][1],
+            need_intersect: true,
+        },
+        lines.len(),
+        Length::new(0.0001),
+        sweep_dir,
+    )
+    .unwrap()
+    .intersections()
+    .collect()
+}
+
+fn assert_positions(intersects: &[LineIntersection], expect: &[Position<Vec2>]) {
+    assert_eq!(intersects.len(), expect.len(), "{intersects:?} != {expect:?}");
+    for &expect in expect {
+        assert!(
+            intersects
+                .iter()
+                .any(|actual| actual.position.distance_cmp(expect) < Length::new(0.0001)),
+            "missing intersection at {expect:?} in {intersects:?}"
+        );
+    }
+}
+
+#[test]
+fn sweep_collinear_chain() {
+    let points = [0., 1., 2., 3.].map(|x| Position::from_origin_nm(x, x * 0.5));
+    let lines = [[points[0], points[1]], [points[1], points[2]], [points[2], points[3]]];
+
+    for sweep_dir in [Dir2::EAST, Dir2::NORTH, Dir2::new(Vec2::new(2., 1.)).unwrap()] {
+        let intersects = sweep_lines(&lines, sweep_dir);
+        assert_positions(&intersects, &points[1..3]);
+    }
+}
+
+#[test]
+fn sweep_collinear_overlap() {
+    let lines = [
+        [Position::from_origin_nm(0., 0.), Position::from_origin_nm(0., 2.)],
+        [Position::from_origin_nm(0., 1.), Position::from_origin_nm(0., 3.)],
+    ];
+
+    for sweep_dir in [Dir2::EAST, Dir2::NORTH, Dir2::new(Vec2::new(1., 1.)).unwrap()] {
+        let intersects = sweep_lines(&lines, sweep_dir);
+        assert_positions(
+            &intersects,
+            &[Position::from_origin_nm(0., 1.), Position::from_origin_nm(0., 2.)],
+        );
+        assert!(intersects.iter().all(|intersect| intersect.lines == [LineIndex(0), LineIndex(1)]));
+    }
+}
+
+#[test]
+fn sweep_near_parallel_crossing() {
+    // The lines cross at (0, 0) with an angle of about 0.1 degrees.
+    let lines = [
+        [Position::from_origin_nm(-1., -0.001), Position::from_origin_nm(1., 0.001)],
+        [Position::from_origin_nm(-1., 0.001), Position::from_origin_nm(1., -0.001)],
+    ];
+    let intersects = sweep_lines(&lines, Dir2::EAST);
+    assert_positions(&intersects, &[Position::from_origin_nm(0., 0.)]);
+}
+
+#[test]
+fn sweep_near_parallel_disjoint() {
+    let lines = [
+        [Position::from_origin_nm(-1., 0.), Position::from_origin_nm(1., 0.)],
+        [Position::from_origin_nm(-1., 0.001), Position::from_origin_nm(1., 0.002)],
+    ];
+    let intersects = sweep_lines(&lines, Dir2::EAST);
+    assert_positions(&intersects, &[]);
+}
+
+#[test]
+fn sweep_collinear_order_deterministic() {
+    // Both overlap endpoints have the same dot product along the sweep direction.
+    let lines = [
+        [Position::from_origin_nm(0., 0.), Position::from_origin_nm(0., 2.)],
+        [Position::from_origin_nm(0., 1.), Position::from_origin_nm(0., 3.)],
+    ];
+    let positions = |intersects: Vec<LineIntersection>| -> Vec<_> {
+        intersects.into_iter().map(|intersect| intersect.position.get().to_array()).collect()
+    };
+    let expect = positions(sweep_lines(&lines, Dir2::EAST));
+    for _ in 0..8 {
+        assert_eq!(positions(sweep_lines(&lines, Dir2::EAST)), expect);
+    }
+}
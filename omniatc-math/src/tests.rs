@@ -1,4 +1,4 @@
-use crate::range_steps;
+use crate::{GeoPoint, Length, Position, range_steps};
 
 #[test]
 fn range_steps_exact() {
@@ -19,3 +19,19 @@ fn range_steps_singleton() {
 fn range_steps_empty() {
     assert_eq!(range_steps(0.3, 0.2, 0.5).collect::<Vec<_>>(), Vec::<f32>::new());
 }
+
+#[test]
+fn geo_point_map_roundtrip() {
+    let origin = GeoPoint { lat: 22.3, lon: 113.9 };
+    let point = GeoPoint { lat: 22.8, lon: 113.4 };
+
+    let position = point.to_map(origin);
+    // Half a degree of latitude north, and slightly less than 30 nm west due to convergence.
+    position
+        .assert_near(Position::from_origin_nm(-27.756, 30.), Length::from_nm(0.01))
+        .expect("projected position");
+
+    let roundtrip = GeoPoint::from_map(position, origin);
+    assert!((roundtrip.lat - point.lat).abs() < 1e-5);
+    assert!((roundtrip.lon - point.lon).abs() < 1e-5);
+}
@@ -13,9 +13,9 @@ use crate::Sign;
 mod display;
 pub use display::{LengthUnit, SpeedUnit, UnitEnum};
 mod heading;
-pub use heading::{Heading, TurnDirection};
+pub use heading::{Heading, ShortestTurn, TurnDirection};
 mod position;
-pub use position::Position;
+pub use position::{Position, PositionMetadata};
 mod temp;
 pub use temp::{Temp, TempDelta};
 mod squared;
@@ -790,6 +790,10 @@ where
     #[must_use]
     pub fn heading(self) -> Heading { Heading::from_vec2(self.0) }
 
+    /// Returns the direction of the receiver, or `None` if the receiver is zero.
+    #[must_use]
+    pub fn try_heading(self) -> Option<Heading> { Heading::try_from_vec2(self.0) }
+
     /// Normalizes the receiver to a unit direction.
     ///
     /// # Errors
@@ -1452,7 +1456,13 @@ pub struct QuantityMetadataWithUnit<T, U> {
     pub unit:      U,
     pub min:       T,
     pub max:       T,
+    /// Step size of the value in the editor.
     pub precision: Option<T>,
+    /// Additional constraint on the value.
+    ///
+    /// Edits rejected by this function are discarded,
+    /// and rejected values are replaced by the default value when loaded.
+    pub validate:  Option<fn(T) -> bool>,
 }
 
 pub type LengthMetadata = QuantityMetadataWithUnit<Length<f32>, LengthUnit>;
@@ -1464,6 +1474,7 @@ impl Default for LengthMetadata {
             min:       Length::from_nm(0.0),
             max:       Length::from_nm(100.0),
             precision: None,
+            validate:  None,
         }
     }
 }
@@ -1485,6 +1496,7 @@ impl Default for QuantityMetadataWithUnit<Speed<f32>, SpeedUnit> {
             min:       Speed::from_knots(0.0),
             max:       Speed::from_knots(500.0),
             precision: Some(Speed::from_knots(1.0)),
+            validate:  None,
         }
     }
 }
@@ -1499,13 +1511,22 @@ bevy_mod_config::impl_scalar_config_field!(
 
 #[derive(Clone)]
 pub struct AngleMetadata {
-    pub default: Angle,
-    pub min:     Angle,
-    pub max:     Angle,
+    pub default:  Angle,
+    pub min:      Angle,
+    pub max:      Angle,
+    /// Additional constraint on the value, as in [`QuantityMetadataWithUnit::validate`].
+    pub validate: Option<fn(Angle) -> bool>,
 }
 
 impl Default for AngleMetadata {
-    fn default() -> Self { Self { default: Angle::ZERO, min: Angle::ZERO, max: Angle::RIGHT } }
+    fn default() -> Self {
+        Self {
+            default:  Angle::ZERO,
+            min:      Angle::ZERO,
+            max:      Angle::RIGHT,
+            validate: None,
+        }
+    }
 }
 
 #[cfg(feature = "egui")]
@@ -49,13 +49,15 @@ where
     let quantity_to_float = unit.quantity_to_float();
     let mut edited = quantity_to_float(*value);
     let resp = ui.horizontal(|ui| {
-        let mut slider_resp = ui.add(
-            egui::Slider::new(
-                &mut edited,
-                quantity_to_float(metadata.min)..=quantity_to_float(metadata.max),
-            )
-            .suffix(unit.to_str()),
-        );
+        let mut slider = egui::Slider::new(
+            &mut edited,
+            quantity_to_float(metadata.min)..=quantity_to_float(metadata.max),
+        )
+        .suffix(unit.to_str());
+        if let Some(precision) = metadata.precision {
+            slider = slider.step_by(quantity_to_float(precision).into());
+        }
+        let mut slider_resp = ui.add(slider);
 
         let unit_changed = egui::ComboBox::from_id_salt(id_salt)
             .selected_text(unit.to_str())
@@ -76,7 +78,10 @@ where
     if resp.inner.changed() {
         // do not perform unnecessary updates,
         // otherwise we will be accumulating numerical errors in a loop.
-        *value = unit.float_to_quantity()(edited);
+        let edited = unit.float_to_quantity()(edited);
+        if metadata.validate.is_none_or(|validate| validate(edited)) {
+            *value = edited;
+        }
     }
 
     resp.inner
@@ -105,7 +110,10 @@ impl Editable<DefaultStyle> for Angle {
         if resp.changed() {
             // do not perform unnecessary updates,
             // otherwise we will be accumulating numerical errors in a loop.
-            *value = Angle::from_degrees(edited);
+            let edited = Angle::from_degrees(edited);
+            if metadata.validate.is_none_or(|validate| validate(edited)) {
+                *value = edited;
+            }
         }
 
         resp
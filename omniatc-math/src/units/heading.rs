@@ -40,9 +40,17 @@ impl Heading {
     /// Returns the heading of the vector.
     ///
     /// Returns a NaN heading if and only if the argument is zero or contains NaN components.
+    /// Use [`try_from_vec2`](Self::try_from_vec2) if the vector may be zero.
     #[must_use]
     pub fn from_vec2(vec: Vec2) -> Self { Self(Angle::new(vec.x.atan2(vec.y))) }
 
+    /// Returns the heading of the vector,
+    /// or `None` if the vector is zero or not finite and hence has no direction.
+    #[must_use]
+    pub fn try_from_vec2(vec: Vec2) -> Option<Self> {
+        if vec == Vec2::ZERO || !vec.is_finite() { None } else { Some(Self::from_vec2(vec)) }
+    }
+
     /// Converts the heading into a direction vector.
     #[must_use]
     pub fn into_dir2(self) -> Dir2 {
@@ -163,10 +171,45 @@ impl Heading {
         self.distance(other, self.closer_direction_to(other))
     }
 
+    /// Returns the shorter turn from `self` towards `other`,
+    /// distinguishing the degenerate cases where no turn or a reversal is required.
+    ///
+    /// Headings within floating point error of each other or of the opposite heading
+    /// are considered equal or opposite respectively.
+    #[must_use]
+    pub fn shortest_turn(self, other: Heading) -> ShortestTurn {
+        let clockwise = self.distance(other, TurnDirection::Clockwise);
+        if clockwise < AMBIGUITY_EPSILON || clockwise > Angle::FULL - AMBIGUITY_EPSILON {
+            ShortestTurn::Aligned
+        } else if (clockwise - Angle::STRAIGHT).abs() < AMBIGUITY_EPSILON {
+            ShortestTurn::Reversal
+        } else if clockwise < Angle::STRAIGHT {
+            ShortestTurn::Turn { direction: TurnDirection::Clockwise, angle: clockwise }
+        } else {
+            ShortestTurn::Turn {
+                direction: TurnDirection::CounterClockwise,
+                angle:     Angle::FULL - clockwise,
+            }
+        }
+    }
+
+    /// Returns the signed angle closest to zero such that
+    /// adding it to `self` approximately returns `other`,
+    /// turning in the direction of `tie` if the headings are opposite.
+    #[must_use]
+    pub fn closest_distance_or(self, other: Heading, tie: TurnDirection) -> Angle {
+        match self.shortest_turn(other) {
+            ShortestTurn::Aligned => Angle::ZERO,
+            ShortestTurn::Turn { direction, angle } => angle * direction,
+            ShortestTurn::Reversal => Angle::STRAIGHT * tie,
+        }
+    }
+
     /// Returns the closer direction to turn towards `other`.
     ///
     /// This assumes zero current angular velocity.
-    /// The result is unspecified if `a` and `b` are exactly opposite or equal.
+    /// The result is unspecified if `a` and `b` are exactly opposite or equal;
+    /// use [`shortest_turn`](Self::shortest_turn) to handle these cases explicitly.
     #[must_use]
     pub fn closer_direction_to(self, other: Heading) -> TurnDirection {
         if self.distance(other, TurnDirection::Clockwise) < Angle::STRAIGHT {
@@ -203,10 +246,13 @@ impl Heading {
 
     /// Turns towards the desired heading, but does not exceed the maximum turn angle.
     ///
+    /// If `desired` is opposite to `self`, the turn is clockwise.
     /// `max_turn` must be non-negative.
     #[must_use]
     pub fn restricted_turn(self, desired: Heading, max_turn: Angle) -> Self {
-        self + self.closest_distance(desired).clamp(-max_turn, max_turn)
+        self + self
+            .closest_distance_or(desired, TurnDirection::Clockwise)
+            .clamp(-max_turn, max_turn)
     }
 
     /// Returns the midpoint of the non-reflex angle between the receiver and `other`.
@@ -290,6 +336,22 @@ impl ops::SubAssign<Angle> for Heading {
     fn sub_assign(&mut self, angle: Angle) { *self = *self - angle; }
 }
 
+/// Angles below this threshold are considered floating point error
+/// when classifying a [`ShortestTurn`].
+const AMBIGUITY_EPSILON: Angle = Angle::from_radians(1e-5);
+
+/// The shorter turn between two headings, returned by [`Heading::shortest_turn`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortestTurn {
+    /// The headings are equal, so no turn is required.
+    Aligned,
+    /// Turn in `direction` by `angle`, which is positive and less than a half turn.
+    Turn { direction: TurnDirection, angle: Angle },
+    /// The headings are opposite, so both directions require a half turn.
+    /// The caller must choose a direction, e.g. from the current yaw speed.
+    Reversal,
+}
+
 /// The direction for yaw change.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -1,6 +1,6 @@
 use bevy_math::Vec2;
 
-use super::{Heading, TurnDirection};
+use super::{Heading, ShortestTurn, TurnDirection};
 use crate::units::Angle;
 
 const EPSILON: Angle = Angle::from_radians(1e-4);
@@ -166,3 +166,58 @@ fn heading_closest_midpoint() {
         .assert_approx(Heading::from_degrees(0.0), EPSILON)
         .expect("backward crossing");
 }
+
+#[test]
+fn heading_try_from_vec2() {
+    assert!(Heading::try_from_vec2(Vec2::ZERO).is_none(), "zero vector has no heading");
+    assert!(Heading::try_from_vec2(Vec2::new(f32::NAN, 1.)).is_none(), "NaN vector has no heading");
+    Heading::try_from_vec2(Vec2::new(0., -1.))
+        .expect("nonzero vector has heading")
+        .assert_approx(Heading::SOUTH, EPSILON)
+        .expect("(0, -1) is southward");
+}
+
+#[test]
+fn heading_shortest_turn() {
+    assert_eq!(
+        Heading::from_degrees(30.).shortest_turn(Heading::from_degrees(30.)),
+        ShortestTurn::Aligned
+    );
+    assert_eq!(Heading::NORTH.shortest_turn(Heading::SOUTH), ShortestTurn::Reversal);
+    assert_eq!(
+        Heading::from_degrees(37.).shortest_turn(Heading::from_degrees(37.).opposite()),
+        ShortestTurn::Reversal
+    );
+
+    let ShortestTurn::Turn { direction, angle } =
+        Heading::from_degrees(350.).shortest_turn(Heading::from_degrees(10.))
+    else {
+        panic!("expected a turn");
+    };
+    assert_eq!(direction, TurnDirection::Clockwise);
+    angle.assert_approx(Angle::from_degrees(20.), EPSILON).expect("turn angle");
+
+    let ShortestTurn::Turn { direction, angle } =
+        Heading::from_degrees(10.).shortest_turn(Heading::from_degrees(200.))
+    else {
+        panic!("expected a turn");
+    };
+    assert_eq!(direction, TurnDirection::CounterClockwise);
+    angle.assert_approx(Angle::from_degrees(170.), EPSILON).expect("turn angle");
+}
+
+#[test]
+fn heading_closest_distance_or() {
+    Heading::NORTH
+        .closest_distance_or(Heading::SOUTH, TurnDirection::CounterClockwise)
+        .assert_approx(-Angle::STRAIGHT, EPSILON)
+        .expect("reversal follows tie direction");
+    Heading::NORTH
+        .closest_distance_or(Heading::SOUTH, TurnDirection::Clockwise)
+        .assert_approx(Angle::STRAIGHT, EPSILON)
+        .expect("reversal follows tie direction");
+    Heading::NORTH
+        .closest_distance_or(Heading::from_degrees(-90.), TurnDirection::Clockwise)
+        .assert_approx(-Angle::RIGHT, EPSILON)
+        .expect("unambiguous turn ignores tie direction");
+}
@@ -230,19 +230,22 @@ impl_scalar_config_field!(
 #[derive(Clone)]
 pub struct PositionMetadata {
     /// The default value.
-    pub default: Position<f32>,
-    pub min:     Position<f32>,
-    pub max:     Position<f32>,
-    pub unit:    LengthUnit,
+    pub default:  Position<f32>,
+    pub min:      Position<f32>,
+    pub max:      Position<f32>,
+    pub unit:     LengthUnit,
+    /// Additional constraint on the value, as in [`crate::QuantityMetadataWithUnit::validate`].
+    pub validate: Option<fn(Position<f32>) -> bool>,
 }
 
 impl Default for PositionMetadata {
     fn default() -> Self {
         Self {
-            default: Position::SEA_LEVEL,
-            min:     Position::SEA_LEVEL,
-            max:     Position::from_amsl_feet(50000.),
-            unit:    LengthUnit::Feet,
+            default:  Position::SEA_LEVEL,
+            min:      Position::SEA_LEVEL,
+            max:      Position::from_amsl_feet(50000.),
+            unit:     LengthUnit::Feet,
+            validate: None,
         }
     }
 }
@@ -273,7 +276,10 @@ const _: () = {
                 )
                 .suffix(metadata.unit.to_str()),
             );
-            value.set_amsl(metadata.unit.float_to_quantity()(edited));
+            let edited = Position::SEA_LEVEL + metadata.unit.float_to_quantity()(edited);
+            if resp.changed() && metadata.validate.is_none_or(|validate| validate(edited)) {
+                *value = edited;
+            }
             resp
         }
     }
@@ -0,0 +1,239 @@
+//! Protocol between the headless simulation server and external agents.
+//!
+//! Messages are exchanged as JSON text frames over WebSocket.
+//! The server periodically sends a [`ServerMessage::Snapshot`] of the observable state,
+//! and agents submit [`ClientMessage::Instruct`] to control objects.
+//!
+//! Quantities use plain numbers with the unit in the field name
+//! so that agents do not need to depend on the simulation math types.
+//! Objects, waypoints and aerodromes are referenced by their display names.
+
+#![forbid(missing_docs)]
+
+use serde::{Deserialize, Serialize};
+
+/// A message sent from an agent to the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Sends an instruction to an object.
+    Instruct {
+        /// Opaque value echoed in the corresponding [`ServerMessage::Ack`] or
+        /// [`ServerMessage::Error`].
+        #[serde(default)]
+        request_id:  u64,
+        /// Display name of the instructed object.
+        object:      String,
+        /// The instruction to send.
+        instruction: Instruction,
+    },
+}
+
+/// A message sent from the server to agents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// The current observable state of the simulation.
+    Snapshot(Snapshot),
+    /// An instruction was accepted and queued for transmission.
+    Ack {
+        /// The `request_id` of the accepted request.
+        request_id: u64,
+    },
+    /// A request could not be processed.
+    Error {
+        /// The `request_id` of the rejected request, if the request could be parsed.
+        request_id: Option<u64>,
+        /// Human-readable description of the error.
+        message:    String,
+    },
+}
+
+/// Observable state of the simulation at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Elapsed simulation time in seconds.
+    pub time_secs: f64,
+    /// All objects in the level.
+    pub objects:   Vec<Object>,
+    /// All active separation conflicts.
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Observable state of an object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Object {
+    /// Display name of the object.
+    pub name:              String,
+    /// Horizontal position east and north of the level origin, in nautical miles.
+    pub position_nm:       [f32; 2],
+    /// Altitude above mean sea level, in feet.
+    pub altitude_ft:       f32,
+    /// Horizontal ground speed, in knots.
+    pub ground_speed_kt:   f32,
+    /// Direction of the horizontal ground velocity, in degrees clockwise from north.
+    pub track_deg:         f32,
+    /// Vertical speed, in feet per minute.
+    pub vertical_rate_fpm: f32,
+    /// Whether the object is airborne.
+    pub airborne:          bool,
+    /// Names of the waypoints remaining in the route, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub route:             Vec<String>,
+}
+
+/// An active separation conflict between two objects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conflict {
+    /// Display names of the two objects.
+    pub objects:       [String; 2],
+    /// Total time the pair has violated separation minima, in seconds.
+    pub duration_secs: f32,
+    /// Current horizontal distance between the objects, in nautical miles.
+    pub horizontal_nm: f32,
+    /// Current vertical distance between the objects, in feet.
+    pub vertical_ft:   f32,
+}
+
+/// An instruction to an object, mirroring the simulation instruction set.
+///
+/// Ground movement instructions are not exposed yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Instruction {
+    /// Fly a heading, in degrees clockwise from north.
+    SetHeading {
+        /// The assigned heading.
+        heading_deg: f32,
+    },
+    /// Proceed direct to a waypoint.
+    SetWaypoint {
+        /// Name of the waypoint.
+        waypoint: String,
+    },
+    /// Maintain an indicated airspeed, in knots.
+    SetSpeed {
+        /// The assigned speed.
+        speed_kt: f32,
+    },
+    /// Climb or descend to an altitude, in feet above mean sea level.
+    SetAltitude {
+        /// The assigned altitude.
+        altitude_ft: f32,
+        /// Whether to expedite the altitude change.
+        #[serde(default)]
+        expedite:    bool,
+    },
+    /// Remove all nodes from the route.
+    ClearRoute,
+    /// Continue after a standby node in the route.
+    RemoveStandby,
+    /// Replace the route with a route preset.
+    SelectRoute {
+        /// ID of the route preset.
+        preset: String,
+    },
+    /// Skip the route to a waypoint already in the route.
+    SkipToWaypoint {
+        /// Name of the waypoint.
+        waypoint: String,
+    },
+    /// Divert to another aerodrome.
+    Divert {
+        /// Code of the aerodrome.
+        aerodrome: String,
+    },
+    /// Grant flight following to a VFR object.
+    GrantFlightFollowing,
+    /// Break up a formation.
+    BreakupFormation,
+    /// Caution the object about bird activity.
+    BirdCaution,
+    /// Transfer the object to another controller frequency.
+    ContactFrequency {
+        /// The frequency to contact.
+        frequency: Frequency,
+    },
+    /// Clear the object for the approach in its route.
+    ///
+    /// The pilot replies unable if the object is outside the intercept envelope.
+    ClearApproach,
+    /// Restrict the altitude at which the object crosses a waypoint already in its route.
+    CrossAltitude {
+        /// Name of the waypoint.
+        waypoint:    String,
+        /// The altitude at which the waypoint must be crossed.
+        altitude_ft: f32,
+    },
+    /// Set the airspeed until a condition is met, then change to another airspeed.
+    SetSpeedUntil {
+        /// The airspeed to maintain until the condition is met.
+        speed_kt:      f32,
+        /// Name of the waypoint that the condition refers to.
+        waypoint:      String,
+        /// Distance from the waypoint at which the speed changes,
+        /// or `None` to change speed when passing the waypoint.
+        #[serde(default)]
+        distance_nm:   Option<f32>,
+        /// The airspeed to set when the condition is met.
+        then_speed_kt: f32,
+    },
+    /// Fly a heading temporarily, then resume the route when a condition is met.
+    VectorThenResume {
+        /// The assigned heading.
+        heading_deg: f32,
+        /// Name of the waypoint in the route to proceed direct to when resuming,
+        /// or `None` to continue after the vector.
+        #[serde(default)]
+        waypoint:    Option<String>,
+        /// The condition for resuming the route.
+        resume:      ResumeCondition,
+    },
+}
+
+/// Condition of [`Instruction::VectorThenResume`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResumeCondition {
+    /// Resume after flying a ground distance, in nautical miles.
+    DistanceNm(f32),
+    /// Resume when the named waypoint is abeam the object.
+    Abeam(String),
+    /// Resume after a duration, in seconds.
+    TimeSecs(f32),
+}
+
+/// A controller frequency at the aerodrome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Frequency {
+    /// The ground frequency.
+    Ground,
+    /// The tower frequency.
+    Tower,
+}
+
+impl ClientMessage {
+    /// Parses a message from a JSON text frame.
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> { serde_json::from_str(text) }
+
+    /// Encodes the message as a JSON text frame.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("message is always serializable")
+    }
+}
+
+impl ServerMessage {
+    /// Parses a message from a JSON text frame.
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> { serde_json::from_str(text) }
+
+    /// Encodes the message as a JSON text frame.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("message is always serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,44 @@
+use crate::{ClientMessage, Instruction, ServerMessage, Snapshot};
+
+#[test]
+fn parse_instruct() {
+    let message = ClientMessage::from_json(
+        r#"{"type":"instruct","request_id":3,"object":"ABC123",
+            "instruction":{"kind":"set_altitude","altitude_ft":5000}}"#,
+    )
+    .expect("valid message");
+    assert_eq!(
+        message,
+        ClientMessage::Instruct {
+            request_id:  3,
+            object:      "ABC123".into(),
+            instruction: Instruction::SetAltitude { altitude_ft: 5000.0, expedite: false },
+        }
+    );
+}
+
+#[test]
+fn parse_unit_instruction() {
+    let message = ClientMessage::from_json(
+        r#"{"type":"instruct","object":"ABC123","instruction":{"kind":"clear_route"}}"#,
+    )
+    .expect("valid message");
+    assert_eq!(
+        message,
+        ClientMessage::Instruct {
+            request_id:  0,
+            object:      "ABC123".into(),
+            instruction: Instruction::ClearRoute,
+        }
+    );
+}
+
+#[test]
+fn snapshot_round_trip() {
+    let message = ServerMessage::Snapshot(Snapshot {
+        time_secs: 12.5,
+        objects:   Vec::new(),
+        conflicts: Vec::new(),
+    });
+    assert_eq!(ServerMessage::from_json(&message.to_json()).expect("round trip"), message);
+}
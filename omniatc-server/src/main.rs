@@ -0,0 +1,83 @@
+//! Headless simulation server for external agents.
+//!
+//! Loads a level file, runs the simulation without rendering,
+//! and exposes it over WebSocket using the [`proto`] JSON protocol.
+//! Every connected agent receives periodic snapshots and may submit instructions.
+
+use std::borrow::Cow;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use bevy::app::{self, App, AppExit, ScheduleRunnerPlugin, TaskPoolPlugin};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::ecs::system::Command;
+use bevy::log::LogPlugin;
+use bevy::time::TimePlugin;
+use omniatc::{level, load, util};
+
+mod net;
+mod snapshot;
+
+#[derive(clap::Parser)]
+#[clap(version, about)]
+struct Options {
+    /// Path to the level file to simulate.
+    level: PathBuf,
+
+    /// Address to accept WebSocket connections on.
+    #[clap(long, default_value = "127.0.0.1:7878")]
+    listen: SocketAddr,
+
+    /// Number of simulation ticks per second.
+    #[clap(long, default_value_t = 20.0)]
+    tick_rate: f64,
+
+    /// Interval between two snapshots sent to agents, in seconds.
+    #[clap(long, default_value_t = 1.0)]
+    snapshot_interval: f64,
+}
+
+fn main() -> anyhow::Result<()> {
+    let options = <Options as clap::Parser>::parse();
+
+    let bytes = fs::read(&options.level)
+        .with_context(|| format!("read level file {}", options.level.display()))?;
+    let tick_interval = Duration::try_from_secs_f64(1.0 / options.tick_rate)
+        .with_context(|| format!("invalid tick rate {}", options.tick_rate))?;
+    let snapshot_interval = Duration::try_from_secs_f64(options.snapshot_interval)
+        .with_context(|| format!("invalid snapshot interval {}", options.snapshot_interval))?;
+    let hub = net::listen(options.listen).context("listen for agents")?;
+
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TimePlugin,
+        LogPlugin::default(),
+        ScheduleRunnerPlugin::run_loop(tick_interval),
+        level::Plug::<()>::default(),
+        load::Plug,
+        util::Plug,
+    ));
+    app.insert_resource(hub);
+    app.insert_resource(snapshot::Interval::new(snapshot_interval));
+    app.add_systems(app::Update, net::receive_system.before(level::AllSystemSets));
+    app.add_systems(app::Update, snapshot::broadcast_system.after(level::AllSystemSets));
+
+    load::Command {
+        source:   load::Source::Raw(Cow::Owned(bytes)),
+        on_error: Box::new(|world, err| {
+            bevy::log::error!("Cannot load level: {err}");
+            world.write_message(AppExit::error());
+        }),
+    }
+    .apply(app.world_mut());
+
+    bevy::log::info!("Accepting agents on ws://{}", options.listen);
+    match app.run() {
+        AppExit::Success => Ok(()),
+        AppExit::Error(code) => anyhow::bail!("server exited with code {code}"),
+    }
+}
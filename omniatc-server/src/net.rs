@@ -0,0 +1,280 @@
+//! WebSocket transport between agents and the simulation.
+//!
+//! Each connection is served by a dedicated thread
+//! that forwards parsed requests to the simulation through [`Hub`]
+//! and writes queued outgoing frames back to the socket.
+
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{io, thread};
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::world::World;
+use math::{Heading, Length, Position, Speed};
+use omniatc::level::instr::CommandsExt;
+use omniatc::level::{object, session};
+use proto::{ClientMessage, ServerMessage};
+use tungstenite::{Message, WebSocket};
+
+/// Duration a connection thread waits for incoming frames before flushing outgoing frames.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Maximum duration to wait for each read of the WebSocket upgrade request.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Channels between the simulation and all connection threads.
+#[derive(Resource)]
+pub struct Hub {
+    requests: Mutex<Receiver<Request>>,
+    clients:  Arc<Mutex<Vec<Sender<String>>>>,
+}
+
+impl Hub {
+    /// Sends a message to all connected agents, dropping disconnected ones.
+    pub fn broadcast(&self, message: &ServerMessage) {
+        let text = message.to_json();
+        let mut clients = self.clients.lock().expect("poisoned");
+        clients.retain(|client| client.send(text.clone()).is_ok());
+    }
+}
+
+/// A parsed request from an agent.
+struct Request {
+    message: ClientMessage,
+    /// Sends frames back to the requesting agent only.
+    reply:   Sender<String>,
+}
+
+/// Starts accepting agent connections on `addr` in a background thread.
+pub fn listen(addr: SocketAddr) -> io::Result<Hub> {
+    let listener = TcpListener::bind(addr)?;
+    let (request_send, request_recv) = mpsc::channel();
+    let clients = Arc::new(Mutex::new(Vec::new()));
+
+    thread::spawn({
+        let clients = Arc::clone(&clients);
+        move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        bevy::log::warn!("Cannot accept agent connection: {err}");
+                        continue;
+                    }
+                };
+
+                let (frame_send, frame_recv) = mpsc::channel();
+                clients.lock().expect("poisoned").push(frame_send.clone());
+                let request_send = request_send.clone();
+                thread::spawn(move || {
+                    if let Err(err) = serve(stream, &request_send, &frame_send, &frame_recv) {
+                        bevy::log::warn!("Agent connection closed: {err}");
+                    }
+                });
+            }
+        }
+    });
+
+    Ok(Hub { requests: Mutex::new(request_recv), clients })
+}
+
+fn serve(
+    stream: TcpStream,
+    requests: &Sender<Request>,
+    frame_send: &Sender<String>,
+    frames: &Receiver<String>,
+) -> tungstenite::Result<()> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut socket = tungstenite::accept(stream).map_err(|err| match err {
+        tungstenite::HandshakeError::Failure(err) => err,
+        tungstenite::HandshakeError::Interrupted(_) => {
+            tungstenite::Error::Io(io::ErrorKind::TimedOut.into())
+        }
+    })?;
+    socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => match ClientMessage::from_json(&text) {
+                Ok(message) => {
+                    let request = Request { message, reply: frame_send.clone() };
+                    if requests.send(request).is_err() {
+                        return Ok(());
+                    }
+                }
+                Err(err) => {
+                    let error =
+                        ServerMessage::Error { request_id: None, message: err.to_string() };
+                    socket.send(Message::text(error.to_json()))?;
+                }
+            },
+            Ok(Message::Close(_)) | Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(tungstenite::Error::Io(err))
+                if !matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) =>
+            {
+                return Err(err.into());
+            }
+            // Other frames are handled by tungstenite, and timeouts just poll outgoing frames.
+            Ok(_) | Err(tungstenite::Error::Io(_)) => {}
+            Err(err) => return Err(err),
+        }
+
+        if !flush(&mut socket, frames)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Writes all queued frames to the socket.
+///
+/// Returns `false` if the simulation has shut down.
+fn flush(
+    socket: &mut WebSocket<TcpStream>,
+    frames: &Receiver<String>,
+) -> tungstenite::Result<bool> {
+    loop {
+        match frames.try_recv() {
+            Ok(frame) => socket.send(Message::text(frame))?,
+            Err(TryRecvError::Empty) => return Ok(true),
+            Err(TryRecvError::Disconnected) => return Ok(false),
+        }
+    }
+}
+
+/// Applies all pending agent requests to the simulation.
+pub fn receive_system(world: &mut World) {
+    let requests: Vec<Request> = {
+        let hub = world.resource::<Hub>();
+        let receiver = hub.requests.lock().expect("poisoned");
+        receiver.try_iter().collect()
+    };
+
+    for Request { message, reply } in requests {
+        let response = match message {
+            ClientMessage::Instruct { request_id, object, instruction } => {
+                match instruct(world, &object, &instruction) {
+                    Ok(()) => ServerMessage::Ack { request_id },
+                    Err(message) => ServerMessage::Error { request_id: Some(request_id), message },
+                }
+            }
+        };
+        // The agent may have disconnected in the meantime.
+        _ = reply.send(response.to_json());
+    }
+}
+
+fn instruct(
+    world: &mut World,
+    object: &str,
+    instruction: &proto::Instruction,
+) -> Result<(), String> {
+    let entity = world
+        .query::<(Entity, &object::Display)>()
+        .iter(world)
+        .find(|(_, display)| display.name == object)
+        .map(|(entity, _)| entity)
+        .ok_or_else(|| format!("No object called {object:?}"))?;
+
+    let record = to_record(instruction)?;
+    let instruction = session::resolve(world, &record).map_err(|err| err.to_string())?;
+    world.commands().send_instruction(entity, instruction);
+    world.flush();
+    Ok(())
+}
+
+/// Converts a protocol instruction into its recorded form for name resolution.
+///
+/// # Errors
+/// If the instruction contains out-of-range values.
+fn to_record(instruction: &proto::Instruction) -> Result<store::InstructionRecord, String> {
+    let waypoint = |name: &str| store::WaypointRef::Named(store::NamedWaypointRef(name.to_owned()));
+
+    Ok(match instruction {
+        &proto::Instruction::SetHeading { heading_deg } => store::InstructionRecord::SetHeading {
+            target: store::YawTarget::Heading(Heading::from_degrees(heading_deg)),
+        },
+        proto::Instruction::SetWaypoint { waypoint: name } => {
+            store::InstructionRecord::SetWaypoint { waypoint: waypoint(name) }
+        }
+        &proto::Instruction::SetSpeed { speed_kt } => {
+            store::InstructionRecord::SetSpeed { target: Speed::from_knots(speed_kt) }
+        }
+        &proto::Instruction::SetAltitude { altitude_ft, expedite } => {
+            store::InstructionRecord::SetAltitude {
+                altitude: Position::from_amsl_feet(altitude_ft),
+                expedite,
+            }
+        }
+        proto::Instruction::ClearRoute => store::InstructionRecord::ClearRoute,
+        proto::Instruction::RemoveStandby => {
+            store::InstructionRecord::RemoveStandby { skip_id: None }
+        }
+        proto::Instruction::SelectRoute { preset } => {
+            store::InstructionRecord::SelectRoute { preset: preset.clone() }
+        }
+        proto::Instruction::SkipToWaypoint { waypoint: name } => {
+            store::InstructionRecord::SkipToWaypoint { waypoint: waypoint(name) }
+        }
+        proto::Instruction::Divert { aerodrome } => {
+            store::InstructionRecord::Divert { aerodrome: store::AerodromeRef(aerodrome.clone()) }
+        }
+        proto::Instruction::GrantFlightFollowing => store::InstructionRecord::GrantFlightFollowing,
+        proto::Instruction::BreakupFormation => store::InstructionRecord::BreakupFormation,
+        proto::Instruction::BirdCaution => store::InstructionRecord::BirdCaution,
+        proto::Instruction::ContactFrequency { frequency } => {
+            store::InstructionRecord::ContactFrequency {
+                frequency: match frequency {
+                    proto::Frequency::Ground => store::FrequencyRecord::Ground,
+                    proto::Frequency::Tower => store::FrequencyRecord::Tower,
+                },
+            }
+        }
+        proto::Instruction::ClearApproach => store::InstructionRecord::ClearApproach,
+        proto::Instruction::SetSpeedUntil {
+            speed_kt,
+            waypoint: name,
+            distance_nm,
+            then_speed_kt,
+        } => {
+            let waypoint = waypoint(name);
+            store::InstructionRecord::SetSpeedUntil {
+                initial:   Speed::from_knots(*speed_kt),
+                condition: match distance_nm {
+                    Some(distance) => store::SpeedConditionRecord::Distance {
+                        waypoint,
+                        distance: Length::from_nm(*distance),
+                    },
+                    None => store::SpeedConditionRecord::Waypoint { waypoint },
+                },
+                then:      Speed::from_knots(*then_speed_kt),
+            }
+        }
+        proto::Instruction::CrossAltitude { waypoint: name, altitude_ft } => {
+            store::InstructionRecord::CrossAltitude {
+                waypoint: waypoint(name),
+                altitude: Position::from_amsl_feet(*altitude_ft),
+            }
+        }
+        proto::Instruction::VectorThenResume { heading_deg, waypoint: name, resume } => {
+            store::InstructionRecord::VectorThenResume {
+                heading:   store::YawTarget::Heading(Heading::from_degrees(*heading_deg)),
+                condition: match resume {
+                    &proto::ResumeCondition::DistanceNm(distance) => {
+                        store::ResumeConditionRecord::Distance(Length::from_nm(distance))
+                    }
+                    proto::ResumeCondition::Abeam(fix) => {
+                        store::ResumeConditionRecord::Abeam(waypoint(fix))
+                    }
+                    &proto::ResumeCondition::TimeSecs(secs) => store::ResumeConditionRecord::Time(
+                        Duration::try_from_secs_f32(secs.max(0.0))
+                            .map_err(|_| format!("Invalid resume time {secs}"))?,
+                    ),
+                },
+                waypoint:  name.as_deref().map(waypoint),
+            }
+        }
+    })
+}
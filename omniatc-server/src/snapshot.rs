@@ -0,0 +1,100 @@
+//! Periodic snapshots of the observable simulation state.
+
+use std::time::Duration;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{Has, QueryData};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Query, Res, ResMut};
+use bevy::time::{self, Time};
+use math::Heading;
+use omniatc::level::route::{self, Route};
+use omniatc::level::waypoint::Waypoint;
+use omniatc::level::{conflict, object};
+
+use crate::net::Hub;
+
+/// Interval between two snapshots.
+#[derive(Resource)]
+pub struct Interval {
+    period: Duration,
+    /// Virtual time at which the next snapshot is due.
+    next:   Duration,
+}
+
+impl Interval {
+    pub fn new(period: Duration) -> Self { Self { period, next: Duration::ZERO } }
+}
+
+#[derive(QueryData)]
+pub struct ObjectQuery {
+    entity:   Entity,
+    display:  &'static object::Display,
+    object:   &'static object::Object,
+    airborne: Has<object::Airborne>,
+    route:    Option<&'static Route>,
+}
+
+pub fn broadcast_system(
+    hub: Res<Hub>,
+    time: Res<Time<time::Virtual>>,
+    mut interval: ResMut<Interval>,
+    object_query: Query<ObjectQuery>,
+    pair_query: Query<&conflict::PairState>,
+    waypoint_query: Query<&Waypoint>,
+) {
+    if time.elapsed() < interval.next {
+        return;
+    }
+    interval.next = time.elapsed() + interval.period;
+
+    let objects = object_query
+        .iter()
+        .map(|data| {
+            let ground_speed = data.object.ground_speed;
+            proto::Object {
+                name:              data.display.name.clone(),
+                position_nm:       data.object.position.horizontal().get().to_array(),
+                altitude_ft:       data.object.position.altitude().amsl().into_feet(),
+                ground_speed_kt:   ground_speed.horizontal().magnitude_exact().into_knots(),
+                track_deg:         Heading::from_vec2(ground_speed.horizontal().0).degrees(),
+                vertical_rate_fpm: ground_speed.vertical().into_fpm(),
+                airborne:          data.airborne,
+                route:             data
+                    .route
+                    .into_iter()
+                    .flat_map(Route::iter)
+                    .filter_map(|node| match node {
+                        route::Node::DirectWaypoint(node) => Some(node.waypoint),
+                        route::Node::AlignRunway(node) => Some(node.runway),
+                        _ => None,
+                    })
+                    .filter_map(|waypoint| waypoint_query.get(waypoint).ok())
+                    .map(|waypoint| waypoint.name.clone())
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let conflicts = pair_query
+        .iter()
+        .filter(|pair| pair.is_active)
+        .filter_map(|pair| {
+            let a = object_query.get(pair.entity_a).ok()?;
+            let b = object_query.get(pair.entity_b).ok()?;
+            let distance = a.object.position - b.object.position;
+            Some(proto::Conflict {
+                objects:       [a.display.name.clone(), b.display.name.clone()],
+                duration_secs: pair.cumul.as_secs_f32(),
+                horizontal_nm: distance.horizontal().magnitude_exact().into_nm(),
+                vertical_ft:   distance.vertical().abs().into_feet(),
+            })
+        })
+        .collect();
+
+    hub.broadcast(&proto::ServerMessage::Snapshot(proto::Snapshot {
+        time_secs: time.elapsed_secs_f64(),
+        objects,
+        conflicts,
+    }));
+}
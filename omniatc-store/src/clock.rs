@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Scenario clock state.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Clock {
+    /// Time of day, measured from midnight, when the file is loaded.
+    pub time_of_day: Duration,
+    /// The day/night cycle of the scenario.
+    ///
+    /// If `None`, it is always daytime.
+    pub day_night:   Option<DayNightCycle>,
+}
+
+impl Default for Clock {
+    fn default() -> Self { Self { time_of_day: Duration::from_hours(12), day_night: None } }
+}
+
+/// Times of sunrise and sunset in a day/night cycle.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DayNightCycle {
+    /// Time of day, measured from midnight, when it becomes fully bright.
+    pub sunrise:  Duration,
+    /// Time of day, measured from midnight, when it starts getting dark.
+    pub sunset:   Duration,
+    /// Duration of the transition between day and night,
+    /// before sunrise and after sunset.
+    pub twilight: Duration,
+}
@@ -79,6 +79,38 @@ pub enum Shape2d {
     },
 }
 
+impl Shape2d {
+    /// Checks whether a point is within the shape.
+    ///
+    /// `major_dir` of an ellipse is measured counterclockwise from the X axis.
+    #[must_use]
+    pub fn contains(&self, point: Position<Vec2>) -> bool {
+        match *self {
+            Shape2d::Ellipse { center, major_radius, minor_radius, major_dir } => {
+                let offset = (point - center).0;
+                let major_axis = Vec2::from_angle(major_dir.into_radians());
+                let major = offset.dot(major_axis) / major_radius.0;
+                let minor = offset.perp_dot(major_axis) / minor_radius.0;
+                major * major + minor * minor <= 1.0
+            }
+            Shape2d::Polygon { ref points } => {
+                let point = point.get();
+                let mut inside = false;
+                for (start, end) in points.iter().zip(points.iter().cycle().skip(1)) {
+                    let (start, end) = (start.get(), end.get());
+                    if (start.y > point.y) != (end.y > point.y)
+                        && point.x
+                            < start.x + (point.y - start.y) / (end.y - start.y) * (end.x - start.x)
+                    {
+                        inside = !inside;
+                    }
+                }
+                inside
+            }
+        }
+    }
+}
+
 /// A generic range.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -2,12 +2,12 @@ use std::collections::HashMap;
 
 use bevy_math::Vec2;
 use derive_more::From;
-use math::{Angle, Heading, Length, Position};
+use math::{Angle, GeoPoint, Heading, Length, Position};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    AerodromeRef, NamedWaypointRef, ObjectType, ObjectTypeRef, RouteNode, RoutePresetRef,
-    WaypointRef, WeightedList,
+    AerodromeRef, AirwayRef, Equipage, NamedWaypointRef, ObjectType, ObjectTypeRef, RouteNode,
+    RoutePresetRef, RunwayConfigurationRef, RunwayRef, ScriptRef, WaypointRef, WeightedList,
 };
 
 mod env;
@@ -19,24 +19,48 @@ pub use aerodrome::*;
 mod spawn;
 pub use spawn::*;
 
+mod pilot_request;
+pub use pilot_request::*;
+
 /// Contents of a map.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Level {
     /// Environmental features of the map.
-    pub environment:   Environment,
+    pub environment:    Environment,
     /// Types of objects that may exist in the level.
-    pub object_types:  HashMap<ObjectTypeRef, ObjectType>,
+    pub object_types:   HashMap<ObjectTypeRef, ObjectType>,
     /// Aerodromes in the map.
-    pub aerodromes:    Vec<Aerodrome>,
+    pub aerodromes:     Vec<Aerodrome>,
     /// Waypoints in the airspace.
-    pub waypoints:     Vec<Waypoint>,
+    pub waypoints:      Vec<Waypoint>,
+    /// Airways connecting waypoints in the airspace.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub airways:        Vec<Airway>,
+    /// Airspace sectors displayed as boundary outlines.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sectors:        Vec<Sector>,
     /// Route presets that aircraft may be assigned to.
-    pub route_presets: Vec<RoutePreset>,
+    pub route_presets:  Vec<RoutePreset>,
     /// Spawnpoints for new objects.
-    pub spawn_sets:    WeightedList<SpawnSet>,
+    pub spawn_sets:     WeightedList<SpawnSet>,
     /// Determines when new objects may spawn.
-    pub spawn_trigger: SpawnTrigger,
+    pub spawn_trigger:  SpawnTrigger,
+    /// Names that must not be generated for spawned objects,
+    /// e.g. callsigns of real flights operating in the modelled airspace.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_names: Vec<String>,
+    /// Determines how often objects initiate requests to the controller.
+    #[serde(default)]
+    pub pilot_requests: PilotRequests,
+    /// Scripts run every tick to express scenario logic beyond the declarative triggers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts:        Vec<ScriptRef>,
+    /// Geodetic position of the map origin, if the map models a real location.
+    ///
+    /// Used for exporting flown tracks to geographic formats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_origin:     Option<GeoPoint>,
 }
 
 /// A waypoint in the airspace.
@@ -59,6 +83,36 @@ pub struct Waypoint {
     pub hidden:    bool,
 }
 
+/// A published route through an ordered chain of waypoints.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Airway {
+    /// Identifier of the airway, also used as its display name.
+    pub name:         AirwayRef,
+    /// Waypoints along the airway in order.
+    pub waypoints:    Vec<NamedWaypointRef>,
+    /// Lowest usable altitude on the airway.
+    pub min_altitude: Option<Position<f32>>,
+    /// Highest usable altitude on the airway.
+    pub max_altitude: Option<Position<f32>>,
+}
+
+/// A named volume of airspace delimited by a boundary polygon and an altitude range.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Sector {
+    /// Display name of the sector.
+    pub name:         String,
+    /// Vertices of the boundary polygon in order.
+    ///
+    /// The last vertex is implicitly connected to the first one.
+    pub boundary:     Vec<Position<Vec2>>,
+    /// Lowest altitude of the sector.
+    pub min_altitude: Option<Position<f32>>,
+    /// Highest altitude of the sector.
+    pub max_altitude: Option<Position<f32>>,
+}
+
 /// A navigation aid provided at a waypoint.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -142,6 +196,38 @@ pub struct RoutePreset {
     ///
     /// An object matched by any of the destinations can use this preset.
     pub destinations: Vec<PresetDestination>,
+    /// Equipment required to fly this preset, e.g. [`Equipage::RNAV`] for RNAV procedures.
+    #[serde(default)]
+    pub equipage:     Equipage,
+    /// Another preset whose nodes are appended after `nodes`.
+    ///
+    /// Includes are expanded recursively when the level is loaded.
+    /// A preset must not directly or indirectly include itself.
+    #[serde(default)]
+    pub include:      Option<PresetInclude>,
+}
+
+/// Includes the nodes of another route preset, optionally with parameter overrides.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PresetInclude {
+    /// The included preset.
+    pub preset:   RoutePresetRef,
+    /// If `Some`, replaces the runway of all runway nodes and runway waypoints
+    /// in the included nodes.
+    #[serde(default)]
+    pub runway:   Option<RunwayRef>,
+    /// If `Some`, replaces the altitude constraint of
+    /// the first [`DirectWaypoint`](RouteNode::DirectWaypoint) node in the included nodes.
+    #[serde(default)]
+    pub altitude: Option<Position<f32>>,
+}
+
+impl PresetInclude {
+    /// Includes `preset` without overrides.
+    pub fn new(preset: impl Into<RoutePresetRef>) -> Self {
+        Self { preset: preset.into(), runway: None, altitude: None }
+    }
 }
 
 /// Matches object destinations that can use this preset.
@@ -157,12 +243,31 @@ pub enum PresetDestination {
 impl PresetDestination {
     /// Creates an arrival destination.
     pub fn arrival(aerodrome: impl Into<AerodromeRef>) -> Self {
-        Self::Arrival(PresetDestinationArrival { aerodrome: Some(aerodrome.into()) })
+        Self::Arrival(PresetDestinationArrival {
+            aerodrome:      Some(aerodrome.into()),
+            configurations: Vec::new(),
+        })
     }
 
     /// Creates a departure destination.
     pub fn departure(waypoint: impl Into<NamedWaypointRef>) -> Self {
-        Self::Departure(PresetDestinationDeparture { waypoint: Some(waypoint.into()) })
+        Self::Departure(PresetDestinationDeparture {
+            waypoint:       Some(waypoint.into()),
+            configurations: Vec::new(),
+        })
+    }
+
+    /// Restricts the destination to the given runway configurations.
+    #[must_use]
+    pub fn in_configurations(
+        mut self,
+        configurations: impl IntoIterator<Item = RunwayConfigurationRef>,
+    ) -> Self {
+        match &mut self {
+            Self::Arrival(dest) => dest.configurations.extend(configurations),
+            Self::Departure(dest) => dest.configurations.extend(configurations),
+        }
+        self
     }
 }
 
@@ -174,7 +279,13 @@ pub struct PresetDestinationArrival {
     /// An aerodrome that the object may land on.
     ///
     /// If `None`, matches all arrivals.
-    pub aerodrome: Option<AerodromeRef>,
+    pub aerodrome:      Option<AerodromeRef>,
+    /// Runway configurations under which spawned arrivals are assigned this preset.
+    ///
+    /// If empty, the preset is applicable under any configuration.
+    /// Presets remain available for manual selection regardless of the active configuration.
+    #[serde(default)]
+    pub configurations: Vec<RunwayConfigurationRef>,
 }
 
 /// Matches objects that need to depart and reach the specified waypoint,
@@ -185,16 +296,24 @@ pub struct PresetDestinationDeparture {
     /// A waypoint that the object may hand off at.
     ///
     /// If `None`, matches all departures.
-    pub waypoint: Option<NamedWaypointRef>,
+    pub waypoint:       Option<NamedWaypointRef>,
+    /// Runway configurations under which spawned departures are assigned this preset.
+    ///
+    /// If empty, the preset is applicable under any configuration.
+    /// Presets remain available for manual selection regardless of the active configuration.
+    #[serde(default)]
+    pub configurations: Vec<RunwayConfigurationRef>,
 }
 
-/// Generates [`RoutePreset`] starting at each waypoint on the way.
+/// Generates [`RoutePreset`] starting at each waypoint on the way,
+/// each requiring `equipage`.
 #[must_use]
 pub fn route_presets_at_waypoints(
     id: &str,
     title: &str,
     nodes: Vec<RouteNode>,
     destination: impl Into<PresetDestination>,
+    equipage: Equipage,
 ) -> Vec<RoutePreset> {
     let destination: PresetDestination = destination.into();
 
@@ -211,12 +330,14 @@ pub fn route_presets_at_waypoints(
                 return None;
             };
             Some(RoutePreset {
-                trigger:      RoutePresetTrigger::Waypoint(waypoint.clone()),
-                id:           id.to_owned(),
-                ref_id:       Some(RoutePresetRef(format!("{id} {}", &waypoint_name.0))),
-                title:        title.to_owned(),
-                nodes:        nodes[start_index..].to_vec(),
+                trigger: RoutePresetTrigger::Waypoint(waypoint.clone()),
+                id: id.to_owned(),
+                ref_id: Some(RoutePresetRef(format!("{id} {}", &waypoint_name.0))),
+                title: title.to_owned(),
+                nodes: nodes[start_index..].to_vec(),
                 destinations: [destination.clone()].into(),
+                equipage,
+                include: None,
             })
         })
         .collect()
@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use bevy_math::Vec2;
-use math::{Angle, Heading, Length, Position, Speed};
+use math::{Angle, Heading, Length, Position, Speed, TurnDirection};
 use serde::{Deserialize, Serialize};
 
+use crate::WaypointRef;
+
 /// An aerodrome, consisting of multiple runways and ground structures.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -16,6 +20,77 @@ pub struct Aerodrome {
     pub ground_network: GroundNetwork,
     /// Runways for the aerodrome.
     pub runways:        Vec<RunwayPair>,
+    /// Published holding patterns for arrivals when no runway accepts arrivals.
+    #[serde(default)]
+    pub holds:          Vec<HoldingPattern>,
+    /// Scheduled periods during which runways do not accept arrivals.
+    #[serde(default)]
+    pub closures:       Vec<RunwayClosure>,
+    /// Runway configurations of the aerodrome in descending order of preference.
+    ///
+    /// The first configuration whose runways are not closed is active.
+    #[serde(default)]
+    pub configurations: Vec<RunwayConfiguration>,
+}
+
+/// A named set of runways used together, e.g. for a traffic flow direction.
+///
+/// Route presets may be restricted to specific configurations
+/// through [`PresetDestinationArrival::configurations`](crate::PresetDestinationArrival::configurations)
+/// and [`PresetDestinationDeparture::configurations`](crate::PresetDestinationDeparture::configurations).
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RunwayConfiguration {
+    /// Name of the configuration, unique within the aerodrome.
+    pub name:    String,
+    /// Names of the runway directions in use under this configuration.
+    pub runways: Vec<String>,
+}
+
+/// A published holding pattern over a fix.
+///
+/// Arrivals are stacked at consecutive levels starting from `min_altitude`.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HoldingPattern {
+    /// The holding fix.
+    pub fix:             WaypointRef,
+    /// Track of the inbound leg towards the fix.
+    pub inbound_heading: Heading,
+    /// Direction of the turns in the pattern.
+    pub direction:       TurnDirection,
+    /// Time to fly the outbound leg after turning outbound.
+    pub leg_time:        Duration,
+    /// Altitude of the lowest level in the stack.
+    pub min_altitude:    Position<f32>,
+    /// Vertical separation between consecutive levels.
+    pub level_spacing:   Length<f32>,
+    /// Number of levels in the stack.
+    pub levels:          u32,
+}
+
+/// A scheduled period during which a runway does not accept arrivals.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RunwayClosure {
+    /// Name of the affected runway direction.
+    pub runway:   String,
+    /// Time since the start of the level when the closure starts.
+    pub start:    Duration,
+    /// Duration of the closure.
+    pub duration: Duration,
+    /// Operations still allowed on the runway during the closure.
+    pub kind:     ClosureKind,
+}
+
+/// Operations allowed on a runway during a [`RunwayClosure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ClosureKind {
+    /// The runway is closed to all operations.
+    Closed,
+    /// The runway is used exclusively for a bank of departures.
+    DeparturesOnly,
 }
 
 /// Ground paths of an aerodrome, such as taxiways and aprons.
@@ -44,15 +119,18 @@ pub struct Taxiway {
     ///
     /// If multiple taxiways in the same aerodrome have the same name,
     /// they are considered the same taxiway.
-    pub name:      String,
+    pub name:        String,
     /// Points of the taxiway.
     ///
     /// Must have at least two points.
     /// A taxiway may be composed of more than two points if it is curved,
     /// in which case every two adjacent points are connected by a straight segment.
-    pub endpoints: Vec<Position<Vec2>>,
+    pub endpoints:   Vec<Position<Vec2>>,
     /// Width of the taxiway.
-    pub width:     Length<f32>,
+    pub width:       Length<f32>,
+    /// Whether this taxiway is a de-icing pad used during winter operations.
+    #[serde(default)]
+    pub deicing_pad: bool,
 }
 
 /// An apron, representing a parking area for aircraft.
@@ -90,6 +168,22 @@ pub struct RunwayPair {
     pub backward_start: Position<Vec2>,
     /// Other details of the backward runway.
     pub backward:       Runway,
+    /// Initial surface condition of the runway.
+    #[serde(default)]
+    pub surface:        SurfaceCondition,
+}
+
+/// Surface condition of a runway, affecting the braking performance of objects on it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SurfaceCondition {
+    /// Normal braking performance.
+    #[default]
+    Dry,
+    /// Reduced braking performance due to water on the surface.
+    Wet,
+    /// Severely reduced braking performance due to snow, ice or standing water.
+    Contaminated,
 }
 
 /// One direction of a runway.
@@ -1,3 +1,7 @@
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+
 use bevy_math::{Vec2, VectorSpace};
 use math::{Angle, Length, Position, Pressure, Speed, Temp};
 use serde::{Deserialize, Serialize};
@@ -20,8 +24,47 @@ pub struct Environment {
 
     /// Weather at different areas.
     pub weather: Vec<Weather>,
+
+    /// Rising air columns used by gliders.
+    #[serde(default)]
+    pub thermals: Vec<Thermal>,
+
+    /// Areas where transient bird activity may occur.
+    #[serde(default)]
+    pub bird_activity: Vec<BirdActivitySource>,
+
+    /// Scheduled rain showers that wet the runways they cover.
+    #[serde(default)]
+    pub rain_showers: Vec<RainShower>,
+
+    /// Volumes of turbulent air.
+    #[serde(default)]
+    pub turbulence: Vec<TurbulenceArea>,
+
+    /// Altitude bands where holding objects accumulate ice.
+    #[serde(default)]
+    pub icing: Vec<IcingBand>,
+
+    /// Scheduled fog banks that reduce the runway visual range of the runways they cover.
+    #[serde(default)]
+    pub fog: Vec<FogBank>,
+
+    /// Winter operations requiring departures to be de-iced before takeoff.
+    ///
+    /// Winter operations are disabled if `None`.
+    #[serde(default)]
+    pub winter_ops: Option<WinterOps>,
+
+    /// Transition altitude.
+    ///
+    /// Altitudes are expressed as flight levels from the transition level upwards,
+    /// which is derived from the transition altitude and the QNH.
+    #[serde(default = "default_transition_altitude")]
+    pub transition_altitude: Position<f32>,
 }
 
+fn default_transition_altitude() -> Position<f32> { Position::from_amsl_feet(18000.0) }
+
 /// A 2D heatmap representing a function `Vec2 -> Datum` within a rectangle.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -39,6 +82,26 @@ pub struct HeatMap2<Datum> {
     pub sparse:  SparseHeatMap2<Datum>,
 }
 
+impl<Datum> HeatMap2<Datum> {
+    /// Resolve the function value at a given position,
+    /// taking the maximum of the aligned heatmap and all sparse functions containing it.
+    #[must_use]
+    pub fn resolve(&self, position: Position<Vec2>) -> Datum
+    where
+        Datum: VectorSpace<Scalar = f32> + PartialOrd,
+    {
+        self.sparse
+            .functions
+            .iter()
+            .filter(|function| function.shape.contains(position))
+            .map(|function| function.value)
+            .fold(
+                self.aligned.resolve(position),
+                |max, value| if value > max { value } else { max },
+            )
+    }
+}
+
 /// A 2D heatmap represented as a matrix of values of type `Datum`.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -62,7 +125,7 @@ pub struct AlignedHeatMap2<Datum> {
     /// A point within the AABB from `initial_corner` to `end_corner`
     /// is interpolated using the bilinear interpolation of the four closest points.
     /// A point outside the range is interpolated using the closest one or two points.
-    pub data:            Vec<Datum>,
+    pub data:            HeatMapData<Datum>,
 }
 
 impl<Datum> AlignedHeatMap2<Datum> {
@@ -73,11 +136,13 @@ impl<Datum> AlignedHeatMap2<Datum> {
             end_corner:      Position::new(Vec2::new(0., 0.)),
             major_direction: AxisDirection::X,
             major_length:    1,
-            data:            vec![value],
+            data:            HeatMapData::Dense(vec![value]),
         }
     }
 
-    fn minor_length(&self) -> usize { self.data.len() / usize::from(self.major_length) }
+    /// Number of major groups in the heatmap.
+    #[must_use]
+    pub fn minor_length(&self) -> usize { self.data.len() / usize::from(self.major_length) }
 
     fn minor_length_u16(&self) -> u16 {
         u16::try_from(self.minor_length()).expect("checked during validation")
@@ -91,6 +156,7 @@ impl<Datum> AlignedHeatMap2<Datum> {
     pub fn validate(&self) -> bool {
         self.major_length > 0
             && !self.data.is_empty()
+            && self.data.validate()
             && self.data.len().is_multiple_of(usize::from(self.major_length))
             && u16::try_from(self.minor_length()).is_ok()
             && (self.major_length == 1
@@ -101,11 +167,52 @@ impl<Datum> AlignedHeatMap2<Datum> {
                     != self.minor_direction().of_position(self.end_corner))
     }
 
-    fn get_datum(&self, major_index: usize, minor_index: usize) -> Datum
+    /// Returns the data point at the given indices.
+    ///
+    /// # Panics
+    /// Panics if the indices are out of bounds.
+    #[must_use]
+    pub fn get(&self, major_index: usize, minor_index: usize) -> Datum
     where
-        Datum: Copy,
+        Datum: VectorSpace<Scalar = f32>,
     {
-        self.data[major_index + minor_index * usize::from(self.major_length)]
+        self.data.get(major_index + minor_index * usize::from(self.major_length))
+    }
+
+    /// Samples the heatmap at fractional indices using bilinear interpolation.
+    ///
+    /// Indices are clamped to the grid,
+    /// so sampling outside the grid returns the value of the closest edge.
+    #[must_use]
+    pub fn sample(&self, major_index: f32, minor_index: f32) -> Datum
+    where
+        Datum: VectorSpace<Scalar = f32>,
+    {
+        let (major_floor, major_ceil, major_fract) =
+            Self::split_index(major_index, usize::from(self.major_length));
+        let (minor_floor, minor_ceil, minor_fract) =
+            Self::split_index(minor_index, self.minor_length());
+
+        let v00 = self.get(major_floor, minor_floor);
+        let v10 = self.get(major_ceil, minor_floor);
+        let v01 = self.get(major_floor, minor_ceil);
+        let v11 = self.get(major_ceil, minor_ceil);
+
+        v00.lerp(v10, major_fract).lerp(v01.lerp(v11, major_fract), minor_fract)
+    }
+
+    /// Splits a fractional index into the two neighboring indices within `0..length`
+    /// and the interpolation ratio between them.
+    fn split_index(index: f32, length: usize) -> (usize, usize, f32) {
+        #[expect(clippy::cast_precision_loss, reason = "grid dimensions fit in u16")]
+        let index = index.clamp(0., (length - 1) as f32);
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "index is clamped to be positive"
+        )]
+        let floor = index.trunc() as usize;
+        (floor, (floor + 1).min(length - 1), index.fract())
     }
 
     /// Resolve the function value at a given position.
@@ -123,81 +230,230 @@ impl<Datum> AlignedHeatMap2<Datum> {
             self.minor_direction().of_position(self.end_corner),
         );
 
-        match (major, minor) {
-            (0.0..=1.0, 0.0..=1.0) => {
-                let major_index = major * f32::from(self.major_length - 1);
-                #[expect(
-                    clippy::cast_possible_truncation,
-                    clippy::cast_sign_loss,
-                    reason = "major_index is positive"
-                )]
-                let major_index_floor = major_index.trunc() as usize;
-                let major_index_fract = major_index.fract();
-                let major_index_floor_plus_one =
-                    (major_index_floor + 1).min(usize::from(self.major_length - 1));
-
-                let minor_index = minor * f32::from(self.minor_length_u16() - 1);
-                #[expect(
-                    clippy::cast_possible_truncation,
-                    clippy::cast_sign_loss,
-                    reason = "major_index is positive"
-                )]
-                let minor_index_floor = minor_index.trunc() as usize;
-                let minor_index_fract = minor_index.fract();
-                let minor_index_floor_plus_one =
-                    (minor_index_floor + 1).min(self.minor_length() - 1);
-
-                let v00 = self.get_datum(major_index_floor, minor_index_floor);
-                let v10 = self.get_datum(major_index_floor_plus_one, minor_index_floor);
-                let v01 = self.get_datum(major_index_floor, minor_index_floor_plus_one);
-                let v11 = self.get_datum(major_index_floor_plus_one, minor_index_floor_plus_one);
-
-                v00 * (1. - major_index_fract) * (1. - minor_index_fract)
-                    + v10 * major_index_fract * (1. - minor_index_fract)
-                    + v01 * (1. - major_index_fract) * minor_index_fract
-                    + v11 * major_index_fract * minor_index_fract
+        // A degenerate axis of a single data point yields a non-finite ratio.
+        let major = if major.is_finite() { major } else { 0. };
+        let minor = if minor.is_finite() { minor } else { 0. };
+
+        self.sample(
+            major * f32::from(self.major_length - 1),
+            minor * f32::from(self.minor_length_u16() - 1),
+        )
+    }
+}
+
+/// Storage of the data points of an [`AlignedHeatMap2`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum HeatMapData<Datum> {
+    /// Every data point is stored as is.
+    Dense(Vec<Datum>),
+    /// Data points are quantized into 16-bit levels
+    /// and stored in separately compressed chunks.
+    ///
+    /// This is preferred for large imported terrains,
+    /// where dense storage would inflate both the file size and the memory usage.
+    Quantized(QuantizedGrid<Datum>),
+}
+
+impl<Datum> HeatMapData<Datum> {
+    /// Number of data points.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Dense(data) => data.len(),
+            Self::Quantized(grid) => grid.len as usize,
+        }
+    }
+
+    /// Whether there are no data points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    fn validate(&self) -> bool {
+        match self {
+            Self::Dense(_) => true,
+            Self::Quantized(grid) => {
+                grid.chunk_len > 0
+                    && grid.chunks.len() == grid.len.div_ceil(grid.chunk_len) as usize
             }
-            (0.0..=1.0, _) => {
-                let major_index = major * f32::from(self.major_length - 1);
-                #[expect(
-                    clippy::cast_possible_truncation,
-                    clippy::cast_sign_loss,
-                    reason = "major_index is positive"
-                )]
-                let major_index_floor = major_index.trunc() as usize;
-                let major_index_fract = major_index.fract();
-                let major_index_floor_plus_one =
-                    (major_index_floor + 1).min(usize::from(self.major_length - 1));
-
-                let minor_index = if minor < 0.0 { 0 } else { self.minor_length() - 1 };
-
-                let v0 = self.get_datum(major_index_floor, minor_index);
-                let v1 = self.get_datum(major_index_floor_plus_one, minor_index);
-                v0.lerp(v1, major_index_fract)
+        }
+    }
+
+    /// Returns the data point at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Datum
+    where
+        Datum: VectorSpace<Scalar = f32>,
+    {
+        match self {
+            Self::Dense(data) => data[index],
+            Self::Quantized(grid) => grid.get(index),
+        }
+    }
+
+    /// Converts each data point with a linear function `f`.
+    ///
+    /// Quantized levels are preserved by only converting the bounds,
+    /// so `f` must be linear for the result to be meaningful.
+    #[must_use]
+    pub fn map_linear<U>(&self, mut f: impl FnMut(&Datum) -> U) -> HeatMapData<U> {
+        match self {
+            Self::Dense(data) => HeatMapData::Dense(data.iter().map(f).collect()),
+            Self::Quantized(grid) => HeatMapData::Quantized(QuantizedGrid {
+                min:       f(&grid.min),
+                max:       f(&grid.max),
+                len:       grid.len,
+                chunk_len: grid.chunk_len,
+                chunks:    grid.chunks.clone(),
+            }),
+        }
+    }
+}
+
+impl HeatMapData<f32> {
+    /// Quantizes `data` into chunks of `chunk_len` data points.
+    ///
+    /// # Panics
+    /// Panics if `chunk_len` is zero or `data` has more than `u32::MAX` points.
+    #[must_use]
+    pub fn quantize(data: &[f32], chunk_len: u32) -> Self {
+        assert!(chunk_len > 0, "chunk length must be positive");
+        let len = u32::try_from(data.len()).expect("too many data points");
+
+        let min = data.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = data.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let (min, max) = if min <= max { (min, max) } else { (0., 0.) };
+
+        let chunks = data
+            .chunks(chunk_len as usize)
+            .map(|chunk| {
+                let levels: Vec<u16> = chunk
+                    .iter()
+                    .map(|&value| {
+                        let ratio = if max > min { (value - min) / (max - min) } else { 0. };
+                        #[expect(
+                            clippy::cast_possible_truncation,
+                            clippy::cast_sign_loss,
+                            reason = "ratio is within [0, 1]"
+                        )]
+                        let level = (ratio * f32::from(u16::MAX)).round() as u16;
+                        level
+                    })
+                    .collect();
+                QuantizedChunk::compress(&levels)
+            })
+            .collect();
+
+        Self::Quantized(QuantizedGrid { min, max, len, chunk_len, chunks })
+    }
+}
+
+/// Data points quantized into 16-bit levels between `min` and `max`.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct QuantizedGrid<Datum> {
+    /// The value represented by level 0.
+    pub min:       Datum,
+    /// The value represented by level `u16::MAX`.
+    pub max:       Datum,
+    /// Total number of data points.
+    pub len:       u32,
+    /// Number of data points in each chunk except the last one.
+    pub chunk_len: u32,
+    /// Chunks of consecutive data points.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<Vec<u8>>"))]
+    pub chunks:    Vec<QuantizedChunk>,
+}
+
+impl<Datum> QuantizedGrid<Datum> {
+    fn get(&self, index: usize) -> Datum
+    where
+        Datum: VectorSpace<Scalar = f32>,
+    {
+        let chunk_len = self.chunk_len as usize;
+        let level = self.chunks[index / chunk_len].levels().get(index % chunk_len).copied();
+        self.min.lerp(self.max, f32::from(level.unwrap_or(0)) / f32::from(u16::MAX))
+    }
+}
+
+/// A zstd-compressed chunk of little-endian 16-bit levels,
+/// decompressed lazily upon the first access.
+#[derive(Clone)]
+pub struct QuantizedChunk {
+    compressed: Vec<u8>,
+    levels:     OnceLock<Vec<u16>>,
+}
+
+impl QuantizedChunk {
+    fn compress(levels: &[u16]) -> Self {
+        let bytes: Vec<u8> = levels.iter().flat_map(|level| level.to_le_bytes()).collect();
+        let compressed = zstd::encode_all(&bytes[..], crate::ZSTD_COMPRESSION_LEVEL)
+            .expect("compression into memory buffer should not fail");
+        Self { compressed, levels: OnceLock::from(levels.to_vec()) }
+    }
+
+    /// Returns the decompressed levels.
+    ///
+    /// A corrupted chunk decompresses to no levels,
+    /// which are read as the minimum value.
+    fn levels(&self) -> &[u16] {
+        self.levels.get_or_init(|| {
+            zstd::decode_all(&self.compressed[..]).map_or_else(
+                |_| Vec::new(),
+                |bytes| {
+                    bytes
+                        .chunks_exact(2)
+                        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                        .collect()
+                },
+            )
+        })
+    }
+}
+
+impl Serialize for QuantizedChunk {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.compressed)
+    }
+}
+
+impl<'de> Deserialize<'de> for QuantizedChunk {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("compressed chunk bytes")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+                Ok(bytes.to_vec())
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+                Ok(bytes)
             }
-            (_, 0.0..=1.0) => {
-                let minor_index = minor * f32::from(self.minor_length_u16() - 1);
-                #[expect(
-                    clippy::cast_possible_truncation,
-                    clippy::cast_sign_loss,
-                    reason = "major_index is positive"
-                )]
-                let minor_index_floor = minor_index.trunc() as usize;
-                let minor_index_fract = minor_index.fract();
-                let minor_index_floor_plus_one =
-                    (minor_index_floor + 1).min(self.minor_length() - 1);
-
-                let major_index = if major < 0.0 { 0 } else { self.major_length - 1 };
-
-                let v0 = self.get_datum(major_index.into(), minor_index_floor);
-                let v1 = self.get_datum(major_index.into(), minor_index_floor_plus_one);
-                v0.lerp(v1, minor_index_fract)
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Vec<u8>, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(bytes)
             }
-            (..0.0, ..0.0) => self.get_datum(0, 0),
-            (..0.0, _) => self.get_datum(0, self.minor_length() - 1),
-            (_, ..0.0) => self.get_datum((self.major_length - 1).into(), 0),
-            _ => self.get_datum((self.major_length - 1).into(), self.minor_length() - 1),
         }
+
+        let compressed = deserializer.deserialize_byte_buf(Visitor)?;
+        Ok(Self { compressed, levels: OnceLock::new() })
     }
 }
 
@@ -221,6 +477,130 @@ pub struct SparseFunction2<Datum> {
     pub emergency_exception: bool,
 }
 
+/// A cylindrical column of rising air.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Thermal {
+    /// Horizontal center of the thermal.
+    pub center: Position<Vec2>,
+    /// Horizontal radius of the thermal.
+    pub radius: Length<f32>,
+    /// Vertical speed of the rising air.
+    pub lift:   Speed<f32>,
+    /// Altitude of the top of the thermal.
+    pub top:    Position<f32>,
+}
+
+/// A cylindrical area, typically near a runway, where bird flocks occasionally appear.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BirdActivitySource {
+    /// Horizontal center of the area.
+    pub center:        Position<Vec2>,
+    /// Horizontal radius of the area.
+    pub radius:        Length<f32>,
+    /// Objects above this altitude are not affected by the activity.
+    pub top:           Position<f32>,
+    /// Mean time between the end of an activity and the start of the next one.
+    pub mean_interval: Duration,
+    /// Duration of each activity.
+    pub duration:      Duration,
+}
+
+/// A rain shower over a circular area during a period of the level.
+///
+/// Dry runways within the area become wet while the shower is active.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RainShower {
+    /// Horizontal center of the area.
+    pub center:   Position<Vec2>,
+    /// Horizontal radius of the area.
+    pub radius:   Length<f32>,
+    /// Time since the start of the level when the shower starts.
+    pub start:    Duration,
+    /// Duration of the shower.
+    pub duration: Duration,
+}
+
+/// A cylindrical volume of turbulent air.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TurbulenceArea {
+    /// Horizontal center of the area.
+    pub center:    Position<Vec2>,
+    /// Horizontal radius of the area.
+    pub radius:    Length<f32>,
+    /// Altitude of the bottom of the area.
+    pub bottom:    Position<f32>,
+    /// Altitude of the top of the area.
+    pub top:       Position<f32>,
+    /// Intensity of the turbulence within the area.
+    pub intensity: TurbulenceIntensity,
+}
+
+/// Intensity of turbulence as reported by pilots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TurbulenceIntensity {
+    /// Slight erratic changes in altitude or attitude, without operational impact.
+    Light,
+    /// Objects reduce speed and request to leave the area.
+    Moderate,
+    /// Objects reduce speed further and request to leave the area.
+    Severe,
+}
+
+/// A cylindrical volume of icing conditions.
+///
+/// Objects holding within the band accumulate ice,
+/// degrading their climb performance until they descend out of it.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct IcingBand {
+    /// Horizontal center of the area.
+    pub center: Position<Vec2>,
+    /// Horizontal radius of the area.
+    pub radius: Length<f32>,
+    /// Altitude of the bottom of the band.
+    pub bottom: Position<f32>,
+    /// Altitude of the top of the band.
+    pub top:    Position<f32>,
+}
+
+/// A fog bank over a circular area during a period of time.
+///
+/// Aerodromes enter low visibility procedures while the runway visual range
+/// of any of their runways is below the ILS minima.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FogBank {
+    /// Horizontal center of the area.
+    pub center:     Position<Vec2>,
+    /// Horizontal radius of the area.
+    pub radius:     Length<f32>,
+    /// Runway visual range within the area.
+    pub visibility: Length<f32>,
+    /// Time since the start of the level when the fog forms.
+    pub start:      Duration,
+    /// Duration of the fog.
+    pub duration:   Duration,
+}
+
+/// Parameters for de-icing departures during winter operations.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WinterOps {
+    /// Minimum time to treat an aircraft on a de-icing pad.
+    pub min_treatment: Duration,
+    /// Maximum time to treat an aircraft on a de-icing pad.
+    pub max_treatment: Duration,
+    /// Time after treatment within which the aircraft must take off.
+    ///
+    /// The aircraft must be de-iced again if the holdover time expires.
+    pub holdover:      Duration,
+}
+
 /// Weather in a rectangular region.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use math::{Angle, Length};
+use serde::{Deserialize, Serialize};
+
+use crate::WeightedList;
+
+/// Determines how often objects initiate requests to the controller.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PilotRequests {
+    /// Mean time interval between two requests in the whole level.
+    ///
+    /// Requests are generated as a Poisson process with this mean interval.
+    /// If `None`, objects never initiate requests.
+    pub mean_interval: Option<Duration>,
+    /// Types of requests that may be initiated.
+    pub kinds:         WeightedList<PilotRequestKind>,
+}
+
+/// A type of request initiated by an object.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PilotRequestKind {
+    /// Request a higher altitude than the current target altitude,
+    /// e.g. to avoid turbulence.
+    Climb {
+        /// Altitude increment requested over the current target altitude.
+        delta: Length<f32>,
+    },
+    /// Request to proceed direct to a waypoint further ahead in the current route,
+    /// skipping the waypoints in between.
+    Direct,
+    /// Request a heading deviation from the current course, e.g. to avoid weather.
+    Deviation {
+        /// Maximum heading change requested in either direction.
+        max_angle: Angle,
+    },
+}
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     AerodromeRef, Destination, NamedWaypointRef, ObjectTypeRef, RoutePresetRef, RunwayRef, Score,
-    WeightedList,
+    SpawnSetRef, WeightedList,
 };
 
 /// A setup for spawning objects.
@@ -14,13 +14,68 @@ use crate::{
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SpawnSet {
     /// Reference to the route preset that spawned objects will follow.
-    pub route:    WeightedList<SpawnRoute>,
+    pub route:     WeightedList<SpawnRoute>,
     /// Rules for generating names for spawned objects.
-    pub gen_name: WeightedList<NameGenerator>,
+    pub gen_name:  WeightedList<NameGenerator>,
     /// The types of objects that may be spawned in this set.
-    pub types:    WeightedList<ObjectTypeRef>,
+    pub types:     WeightedList<ObjectTypeRef>,
     /// Position at which objects in this set will be spawned.
-    pub position: WeightedList<SpawnPosition>,
+    pub position:  WeightedList<SpawnPosition>,
+    /// Initial flight time before fuel exhaustion of spawned objects.
+    ///
+    /// If `None`, fuel consumption is not simulated for objects in this set.
+    #[serde(default)]
+    pub endurance: Option<SpawnEndurance>,
+    /// If specified, objects in this set fly under visual flight rules.
+    #[serde(default)]
+    pub vfr:       Option<SpawnVfr>,
+    /// If specified, objects in this set are spawned as formations of multiple aircraft.
+    #[serde(default)]
+    pub formation: Option<SpawnFormation>,
+    /// Identifies the set for [`QuestCompletionHook::UnlockSpawnSet`](crate::QuestCompletionHook::UnlockSpawnSet).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id:        Option<SpawnSetRef>,
+    /// If true, no objects are spawned from this set until it is unlocked by a quest.
+    #[serde(default)]
+    pub locked:    bool,
+}
+
+/// Range of initial endurance for spawned objects.
+///
+/// The initial endurance is uniformly distributed between `min` and `max`.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpawnEndurance {
+    /// Minimum initial endurance.
+    pub min: Duration,
+    /// Maximum initial endurance.
+    pub max: Duration,
+}
+
+/// Range of formation sizes for spawned objects.
+///
+/// The formation size is uniformly distributed between `min` and `max`, inclusive.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpawnFormation {
+    /// Minimum number of aircraft in a formation.
+    pub min: u32,
+    /// Maximum number of aircraft in a formation.
+    pub max: u32,
+}
+
+/// Visual flight rules for spawned objects.
+///
+/// VFR objects wander between visual reporting points
+/// before continuing on the spawn route,
+/// and only comply with instructions optionally until granted flight following.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpawnVfr {
+    /// Visual reporting points between which spawned objects wander.
+    pub reporting_points: Vec<NamedWaypointRef>,
+    /// Number of legs to fly between reporting points before continuing on the route.
+    pub legs:             u32,
 }
 
 /// The route and destination for a spawned object.
@@ -52,6 +107,15 @@ pub enum NameGenerator {
         /// If an empty string is specified, a random alphabet will be chosen.
         /// If set to `None`, no letter will be appended.
         trailing_letter: Option<String>,
+        /// Telephony designator of the airline used in radio communication,
+        /// e.g. `Speedbird` for the prefix `BAW`.
+        #[serde(default)]
+        telephony:       Option<String>,
+    },
+    /// A general aviation registration mark from a region.
+    Registration {
+        /// Region whose registration format is used.
+        region: RegistrationRegion,
     },
     /// A custom name generated from a sequence of elements.
     Elements {
@@ -60,6 +124,90 @@ pub enum NameGenerator {
     },
 }
 
+/// Regional formats of [`NameGenerator::Registration`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum RegistrationRegion {
+    /// `N` followed by 3 digits and up to 2 letters, e.g. `N123AB`.
+    UnitedStates,
+    /// `G-` followed by 4 letters, e.g. `G-ABCD`.
+    UnitedKingdom,
+    /// `D-` followed by 4 letters, e.g. `D-EFGH`.
+    Germany,
+    /// `C-F` followed by 3 letters, e.g. `C-FABC`.
+    Canada,
+    /// `VH-` followed by 3 letters, e.g. `VH-ABC`.
+    Australia,
+    /// `JA` followed by 4 digits, e.g. `JA1234`.
+    Japan,
+    /// `F-` followed by 4 letters, e.g. `F-GABC`.
+    France,
+    /// `PH-` followed by 3 letters, e.g. `PH-ABC`.
+    Netherlands,
+    /// `HB-` followed by 3 letters, e.g. `HB-ABC`.
+    Switzerland,
+    /// `B-` followed by 4 digits, e.g. `B-1234`.
+    China,
+}
+
+impl RegistrationRegion {
+    /// The nationality prefix of registrations in this region.
+    #[must_use]
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::UnitedStates => "N",
+            Self::UnitedKingdom => "G-",
+            Self::Germany => "D-",
+            Self::Canada => "C-F",
+            Self::Australia => "VH-",
+            Self::Japan => "JA",
+            Self::France => "F-",
+            Self::Netherlands => "PH-",
+            Self::Switzerland => "HB-",
+            Self::China => "B-",
+        }
+    }
+
+    fn generate_suffix(self, rng: &mut impl rand::Rng, output: &mut String) {
+        // Letters that are easily confused with digits are not used in registrations.
+        fn random_letter(rng: &mut impl rand::Rng) -> char {
+            loop {
+                let letter = rng.random_range('A'..='Z');
+                if letter != 'I' && letter != 'O' {
+                    return letter;
+                }
+            }
+        }
+
+        match self {
+            Self::UnitedStates => {
+                output.push(rng.random_range('1'..='9'));
+                for _ in 0..2 {
+                    output.push(rng.random_range('0'..='9'));
+                }
+                for _ in 0..rng.random_range(0..=2) {
+                    output.push(random_letter(rng));
+                }
+            }
+            Self::UnitedKingdom | Self::Germany | Self::France => {
+                for _ in 0..4 {
+                    output.push(random_letter(rng));
+                }
+            }
+            Self::Canada | Self::Australia | Self::Netherlands | Self::Switzerland => {
+                for _ in 0..3 {
+                    output.push(random_letter(rng));
+                }
+            }
+            Self::Japan | Self::China => {
+                for _ in 0..4 {
+                    output.push(rng.random_range('0'..='9'));
+                }
+            }
+        }
+    }
+}
+
 /// An element of [`NameGenerator::Elements`].
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -78,7 +226,7 @@ impl NameGenerator {
     /// Generates a name according to the rules of this generator.
     pub fn generate(&self, rng: &mut impl rand::Rng) -> String {
         match *self {
-            NameGenerator::Airline { ref prefix, digits, ref trailing_letter } => {
+            NameGenerator::Airline { ref prefix, digits, ref trailing_letter, .. } => {
                 let mut output = String::with_capacity(
                     prefix.len() + usize::from(digits) + usize::from(trailing_letter.is_some()),
                 );
@@ -93,6 +241,11 @@ impl NameGenerator {
                 }
                 output
             }
+            NameGenerator::Registration { region } => {
+                let mut output = String::from(region.prefix());
+                region.generate_suffix(rng, &mut output);
+                output
+            }
             NameGenerator::Elements { ref elements } => {
                 let mut output = String::new();
                 for element in elements {
@@ -118,6 +271,48 @@ impl NameGenerator {
             }
         }
     }
+
+    /// Returns the radio telephony callsign for a name generated by this generator,
+    /// e.g. `Speedbird 123` for `BAW123`,
+    /// or `Golf Alfa Bravo Charlie Delta` for the registration `G-ABCD`.
+    ///
+    /// Returns `None` if the name is spoken as-is.
+    #[must_use]
+    pub fn telephony(&self, name: &str) -> Option<String> {
+        match self {
+            NameGenerator::Airline { prefix, telephony: Some(telephony), .. } => {
+                let flight_number = name.strip_prefix(prefix.as_str())?;
+                Some(format!("{telephony} {flight_number}"))
+            }
+            NameGenerator::Registration { .. } => Some(spell_phonetic(name)),
+            _ => None,
+        }
+    }
+}
+
+/// Spells out the letters and digits of `name` in the ICAO radiotelephony alphabet,
+/// e.g. `November One Two Tree Alfa Bravo` for `N123AB`.
+///
+/// Other characters such as the hyphen in registrations are not spoken.
+#[must_use]
+pub fn spell_phonetic(name: &str) -> String {
+    const LETTERS: [&str; 26] = [
+        "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India",
+        "Juliett", "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo",
+        "Sierra", "Tango", "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+    ];
+    const DIGITS: [&str; 10] =
+        ["Zero", "One", "Two", "Tree", "Four", "Fife", "Six", "Seven", "Eight", "Niner"];
+
+    let words = name.chars().filter_map(|ch| {
+        let ch = ch.to_ascii_uppercase();
+        if ch.is_ascii_uppercase() {
+            LETTERS.get(usize::from(ch as u8 - b'A')).copied()
+        } else {
+            ch.to_digit(10).and_then(|digit| DIGITS.get(digit as usize).copied())
+        }
+    });
+    words.collect::<Vec<_>>().join(" ")
 }
 
 /// Position at which objects in a spawn set will be spawned.
@@ -170,4 +365,45 @@ pub enum SpawnTrigger {
         /// Number of active objects to maintain.
         count: u32,
     },
+    /// New objects spawn according to a timetable of scheduled flights.
+    Timetable(Timetable),
+}
+
+/// A list of scheduled flights.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Timetable {
+    /// Scheduled flights, in any order.
+    pub flights:           Vec<TimetableFlight>,
+    /// Time before the scheduled time at which each flight is spawned.
+    ///
+    /// This should approximate the typical time
+    /// from spawning until an object completes its destination.
+    pub lead_time:         Duration,
+    /// Maximum delay after the scheduled time for a flight to be considered on time.
+    pub on_time_tolerance: Duration,
+}
+
+/// A flight in a [`Timetable`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimetableFlight {
+    /// Callsign of the spawned object.
+    pub callsign:    String,
+    /// Radio telephony callsign of the spawned object, e.g. `Speedbird 27` for `BAW27`.
+    #[serde(default)]
+    pub telephony:   Option<String>,
+    /// Type of the spawned object.
+    pub object_type: ObjectTypeRef,
+    /// Code of the origin aerodrome, for display only.
+    pub origin:      String,
+    /// Code of the destination aerodrome, for display only.
+    pub destination: String,
+    /// Time since the start of the level
+    /// at which the flight is scheduled to complete its destination.
+    pub scheduled:   Duration,
+    /// Position at which the object is spawned.
+    pub position:    SpawnPosition,
+    /// Route and destination of the object.
+    pub route:       SpawnRoute,
 }
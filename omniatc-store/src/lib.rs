@@ -9,6 +9,9 @@ use serde::{Deserialize, Serialize};
 mod score;
 pub use score::Score;
 
+mod clock;
+pub use clock::*;
+
 mod meta;
 pub use meta::*;
 
@@ -36,6 +39,12 @@ pub use quest::*;
 mod route;
 pub use route::*;
 
+mod script;
+pub use script::*;
+
+mod session;
+pub use session::*;
+
 mod ui;
 pub use ui::*;
 
@@ -63,9 +72,15 @@ pub struct File {
     /// Existing objects in the level.
     #[serde(default)]
     pub objects: Vec<Object>,
+    /// Scenario clock state.
+    #[serde(default)]
+    pub clock:   Clock,
+    /// Script assets that may be referenced by [`Level::scripts`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<ScriptAsset>,
 }
 
-const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+pub(crate) const ZSTD_COMPRESSION_LEVEL: i32 = 3;
 
 impl File {
     /// Serializes the file to a .osav format.
@@ -15,18 +15,52 @@ pub struct Meta {
     /// to allow user updates.
     ///
     /// For local save files, this ID is simply the filename.
-    pub id:          String,
+    pub id:            String,
     /// Title of the map.
     ///
     /// For published maps, this should be the airport/airspace name.
     ///
     /// For local save files, this is renameable but defaults to
     /// inheriting the title of the map/scenario it was created from.
-    pub title:       String,
+    pub title:         String,
     /// A human-readable description of the map.
-    pub description: String,
+    pub description:   String,
     /// Authors of the map. Only for display.
-    pub authors:     Vec<String>,
+    pub authors:       Vec<String>,
     /// Tags for categorizing and searching maps.
-    pub tags:        HashMap<String, String>,
+    pub tags:          HashMap<String, String>,
+    /// ID of the scenario unlocked and offered to continue with
+    /// after this scenario is completed successfully.
+    #[serde(default)]
+    pub next_scenario: Option<String>,
+    /// Controller duties the player is responsible for.
+    #[serde(default)]
+    pub mode:          FacilityMode,
+}
+
+/// The ATC facility simulated by a map,
+/// which determines the controller duties available to the player.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum FacilityMode {
+    /// The player controls all phases of flight.
+    #[default]
+    Full,
+    /// The player only acts as the aerodrome tower.
+    ///
+    /// Arrivals spawn established on final approach,
+    /// and only clearances, runway crossings and taxi instructions are available.
+    TowerOnly,
+    /// The player only acts as the approach controller.
+    ///
+    /// Arrivals are scored when handed off established on final approach,
+    /// after which landing, rollout and taxi are automated by the tower,
+    /// along with the clearances of objects on the ground.
+    ApproachOnly,
+    /// The player only manages surface movement.
+    ///
+    /// Arrivals spawn on an exit taxiway of their landing runway and taxi to parking,
+    /// departures spawn at aprons,
+    /// and clearances of airborne objects are automated.
+    GroundOnly,
 }
@@ -5,15 +5,20 @@ use math::{Accel, Angle, AngularSpeed, Heading, Length, Position, Speed, TurnDir
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    AerodromeRef, NavLimits, ObjectTypeRef, Route, Score, SegmentRef, TaxiLimits, WaypointRef,
+    AerodromeRef, Category, Equipage, InstructionRecord, NamedWaypointRef, NavLimits, ObjectRef,
+    ObjectTypeRef, ResumeConditionRecord, Route, Score, SegmentRef, SpeedConditionRecord,
+    TaxiLimits, WaypointRef,
 };
 
 /// An object in the world.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[expect(clippy::large_enum_variant, reason = "most objects are planes")]
 pub enum Object {
     /// A [`Plane`].
     Plane(Plane),
+    /// An unpowered [`Drifter`].
+    Drifter(Drifter),
 }
 
 /// A plane, characterized by its ability to fly, takeoff and land,
@@ -31,18 +36,71 @@ pub struct Plane {
     pub taxi_limits: TaxiLimits,
     /// Physical and performance limits of the aircraft affecting airborne navigation.
     pub nav_limits:  NavLimits,
+    /// Navigation and approach equipment of the plane.
+    #[serde(default = "Equipage::all")]
+    pub equipage:    Equipage,
+    /// Category of the plane, determining its icon on the radar view.
+    #[serde(default)]
+    pub category:    Category,
     /// Higher-level control mode.
     pub nav_target:  NavTarget,
     /// Planned route.
     pub route:       Route,
 }
 
+/// An unpowered object whose trajectory is dominated by the wind field.
+///
+/// Drifters cannot comply with instructions.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Drifter {
+    /// Name of the object, used for visual display.
+    pub name:     String,
+    /// Stable identifier for referencing the object from quests and scripts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id:       Option<ObjectRef>,
+    /// Type of the drifter.
+    pub kind:     DrifterKind,
+    /// Current position.
+    pub position: Position<Vec2>,
+    /// Current altitude.
+    pub altitude: Position<f32>,
+    /// Current heading of the object.
+    ///
+    /// Only meaningful for gliders.
+    pub heading:  Heading,
+}
+
+/// Type of a [`Drifter`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DrifterKind {
+    /// A balloon that moves along with the wind.
+    Balloon {
+        /// Rate of altitude change until the ceiling is reached.
+        vert_rate: Speed<f32>,
+        /// Altitude at which the balloon levels off.
+        ceiling:   Position<f32>,
+    },
+    /// A glider that circles in thermals and glides between them.
+    Glider {
+        /// Constant forward airspeed of the glider.
+        airspeed:  Speed<f32>,
+        /// Rate of altitude loss in still air. Always positive.
+        sink_rate: Speed<f32>,
+    },
+}
+
 /// Common attributes of an aircraft.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BaseAircraft {
     /// Name of the aircraft, used for visual display.
     pub name:             String,
+    /// Stable identifier for referencing the aircraft from quests and scripts,
+    /// independent of its display name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id:               Option<ObjectRef>,
     /// The completion condition for the object.
     pub dest:             Destination,
     /// Score awarded upon completion of control of the object.
@@ -59,6 +117,144 @@ pub struct BaseAircraft {
     pub ground_dir:       Heading,
     /// Current change in altitude.
     pub vert_rate:        Speed<f32>,
+    /// Remaining flight time before fuel exhaustion.
+    ///
+    /// If `None`, fuel consumption is not simulated for this object.
+    #[serde(default)]
+    pub endurance:        Option<Duration>,
+    /// Visual flight rules state of the object.
+    ///
+    /// If `None`, the object flies under instrument flight rules.
+    #[serde(default)]
+    pub vfr:              Option<VfrState>,
+    /// Number of aircraft in the formation represented by this object.
+    ///
+    /// If `None`, the object is a single aircraft.
+    #[serde(default)]
+    pub formation_size:   Option<u32>,
+    /// Controller notes attached to the aircraft.
+    #[serde(default)]
+    pub notes:            Notes,
+    /// Instructions and clearances that have not been fully executed.
+    #[serde(default)]
+    pub instructions:     InstructionState,
+}
+
+/// State of an object flying under visual flight rules.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VfrState {
+    /// Visual reporting points between which the object wanders.
+    pub reporting_points: Vec<NamedWaypointRef>,
+    /// Number of legs remaining before the object continues on its route.
+    pub remaining_legs:   u32,
+    /// Whether the controller has granted flight following to the object.
+    pub flight_following: bool,
+}
+
+/// Free-text notes and reminders attached to an object by the controller.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Notes {
+    /// Free-text scratchpad.
+    #[serde(default)]
+    pub scratchpad: String,
+    /// Pending reminders, in no particular order.
+    #[serde(default)]
+    pub reminders:  Vec<Reminder>,
+}
+
+/// A reminder that surfaces as a message when due.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Reminder {
+    /// Text of the reminder.
+    pub text:      String,
+    /// Simulated time remaining until the reminder is due.
+    pub remaining: Duration,
+}
+
+/// Instructions of an object that are still in flight.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InstructionState {
+    /// Instructions sent to the object but not executed yet,
+    /// in the order they were sent.
+    #[serde(default)]
+    pub pending:           Vec<PendingInstruction>,
+    /// Speed change deferred by a [`InstructionRecord::SetSpeedUntil`] instruction.
+    #[serde(default)]
+    pub conditional_speed: Option<ConditionalSpeedRecord>,
+    /// Route resumption scheduled by a [`InstructionRecord::VectorThenResume`] instruction.
+    #[serde(default)]
+    pub scheduled_resume:  Option<ScheduledResumeRecord>,
+}
+
+impl InstructionState {
+    /// Whether no instruction is in flight.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+            && self.conditional_speed.is_none()
+            && self.scheduled_resume.is_none()
+    }
+}
+
+/// An instruction sent to an object but not executed yet.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PendingInstruction {
+    /// The pending instruction.
+    pub instruction: InstructionRecord,
+    /// How far the instruction has progressed.
+    pub stage:       PendingStage,
+}
+
+/// Progress of a [`PendingInstruction`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PendingStage {
+    /// The instruction is being transmitted and has not been acknowledged.
+    Transmitting {
+        /// Simulated time remaining until the transmission completes.
+        remaining:   Duration,
+        /// Indices of other transmitting instructions in [`InstructionState::pending`]
+        /// that must be dispatched before this one.
+        #[serde(default)]
+        after:       Vec<usize>,
+        /// Whether the instruction waits for an explicit acknowledgement before dispatch.
+        #[serde(default)]
+        pending_ack: bool,
+    },
+    /// The instruction has been acknowledged and awaits the pilot reaction.
+    Reacting {
+        /// Simulated time remaining until the pilot acts on the instruction.
+        remaining: Duration,
+    },
+}
+
+/// A speed change deferred until a condition is met.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConditionalSpeedRecord {
+    /// Indicated airspeed to set when the condition is met.
+    pub speed:     Speed<f32>,
+    /// The condition for the speed change.
+    pub condition: SpeedConditionRecord,
+}
+
+/// A route resumption scheduled after a vector.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScheduledResumeRecord {
+    /// The condition for resuming the route.
+    pub condition: ResumeConditionRecord,
+    /// The waypoint in the route to proceed direct to when resuming.
+    pub waypoint:  Option<WaypointRef>,
+    /// Ground distance flown since the vector was issued.
+    pub flown:     Length<f32>,
+    /// Simulated time elapsed since the vector was issued.
+    pub elapsed:   Duration,
 }
 
 /// Condition for the completion of control of an object.
@@ -12,6 +12,55 @@ pub struct ObjectType {
     pub taxi_limits: TaxiLimits,
     /// Class-specific specifications of the object type.
     pub class:       ObjectClassSpec,
+    /// Navigation and approach equipment of the object type.
+    ///
+    /// Defaults to full equipage if unspecified.
+    #[serde(default = "Equipage::all")]
+    pub equipage:    Equipage,
+    /// Category of the object type, determining its icon on the radar view.
+    #[serde(default)]
+    pub category:    Category,
+}
+
+/// Broad category of an object type, used for display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Category {
+    /// Jet-powered fixed-wing aircraft.
+    #[default]
+    Jet,
+    /// Propeller-driven fixed-wing aircraft.
+    Prop,
+    /// Rotorcraft.
+    Helicopter,
+    /// Ground vehicle.
+    Vehicle,
+}
+
+bitflags::bitflags! {
+    /// Navigation and approach equipment of an object.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct Equipage: u8 {
+        /// Area navigation, required to fly RNAV procedures.
+        const RNAV = 1 << 0;
+        /// ILS receiver, required to fly ILS approaches without visual contact.
+        const ILS = 1 << 1;
+        /// CAT III autoland, allowing ILS landings without visual contact with the runway.
+        const CAT_III = 1 << 2;
+        /// Approval for reduced vertical separation minima.
+        const RVSM = 1 << 3;
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Equipage {
+    fn schema_name() -> std::borrow::Cow<'static, str> { "Equipage".into() }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        // Serialized as flag names separated by `|`, e.g. `"RNAV | ILS"`.
+        String::json_schema(generator)
+    }
 }
 
 /// Class-specific specifications of an object type.
@@ -2,24 +2,119 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use crate::Score;
+use crate::{AerodromeRef, Score};
 
 /// Game statistics.
 #[derive(Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Stats {
     /// Current score.
-    pub score:               Score,
+    pub score:                       Score,
     /// Total number of objects with runway arrival destination completed.
     ///
     /// Does not include apron arrivals.
-    pub num_runway_arrivals: u32,
+    pub num_runway_arrivals:         u32,
     /// Total number of objects with apron arrival destination completed.
-    pub num_apron_arrivals:  u32,
+    pub num_apron_arrivals:          u32,
     /// Total number of departures completed.
-    pub num_departures:      u32,
+    pub num_departures:              u32,
     /// Number of conflicting pairs that have been detected.
-    pub num_conflicts:       u32,
+    pub num_conflicts:               u32,
     /// Total duration-pair time of all detected conflicts.
-    pub total_conflict_time: Duration,
+    pub total_conflict_time:         Duration,
+    /// Number of requests from objects approved by the controller.
+    #[serde(default)]
+    pub num_pilot_requests_approved: u32,
+    /// Number of requests from objects denied or left unanswered by the controller.
+    #[serde(default)]
+    pub num_pilot_requests_denied:   u32,
+    /// Number of go-arounds for each cause.
+    #[serde(default)]
+    pub go_arounds:                  GoAroundStats,
+    /// Statistics for each aerodrome in the level.
+    ///
+    /// Aerodromes without any completed objects may be omitted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aerodromes:                  Vec<AerodromeStats>,
+}
+
+/// Reason for an object to abandon its landing approach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum GoAroundCause {
+    /// The controller vectored the object off its final approach.
+    PlayerIssued,
+    /// The approach was not stable enough to land,
+    /// e.g. too fast, too high or misaligned.
+    Unstable,
+    /// Another object was on the runway at touchdown.
+    RunwayOccupied,
+    /// Visual contact with the runway was not established,
+    /// e.g. due to low visibility.
+    Weather,
+    /// The object was struck by birds on approach.
+    BirdStrike,
+}
+
+/// Number of go-arounds for each [cause](GoAroundCause).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GoAroundStats {
+    /// Go-arounds initiated by the controller.
+    pub player_issued:   u32,
+    /// Go-arounds due to unstable approaches.
+    pub unstable:        u32,
+    /// Go-arounds due to an occupied runway.
+    pub runway_occupied: u32,
+    /// Go-arounds due to lack of visual contact.
+    pub weather:         u32,
+    /// Go-arounds due to bird strikes.
+    pub bird_strike:     u32,
+}
+
+impl GoAroundStats {
+    /// Returns the number of go-arounds with the given cause.
+    #[must_use]
+    pub fn count(&self, cause: GoAroundCause) -> u32 {
+        match cause {
+            GoAroundCause::PlayerIssued => self.player_issued,
+            GoAroundCause::Unstable => self.unstable,
+            GoAroundCause::RunwayOccupied => self.runway_occupied,
+            GoAroundCause::Weather => self.weather,
+            GoAroundCause::BirdStrike => self.bird_strike,
+        }
+    }
+
+    /// Returns a mutable reference to the counter of the given cause.
+    pub fn count_mut(&mut self, cause: GoAroundCause) -> &mut u32 {
+        match cause {
+            GoAroundCause::PlayerIssued => &mut self.player_issued,
+            GoAroundCause::Unstable => &mut self.unstable,
+            GoAroundCause::RunwayOccupied => &mut self.runway_occupied,
+            GoAroundCause::Weather => &mut self.weather,
+            GoAroundCause::BirdStrike => &mut self.bird_strike,
+        }
+    }
+
+    /// Returns the total number of go-arounds of all causes.
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.player_issued + self.unstable + self.runway_occupied + self.weather + self.bird_strike
+    }
+}
+
+/// Game statistics for a single aerodrome.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AerodromeStats {
+    /// The aerodrome these statistics apply to.
+    pub aerodrome:           AerodromeRef,
+    /// Number of objects that completed a runway arrival at this aerodrome.
+    ///
+    /// Does not include apron arrivals.
+    pub num_runway_arrivals: u32,
+    /// Number of objects that completed an apron arrival at this aerodrome.
+    pub num_apron_arrivals:  u32,
+    /// Number of objects departing from this aerodrome that completed their departure.
+    pub num_departures:      u32,
 }
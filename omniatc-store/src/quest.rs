@@ -3,7 +3,10 @@ use std::time::Duration;
 use math::{Heading, Position, Speed};
 use serde::{Deserialize, Serialize};
 
-use crate::{NamedWaypointRef, Object, QuestRef, Range, Score, SegmentRef};
+use crate::{
+    AerodromeRef, GoAroundCause, NamedWaypointRef, Object, ObjectRef, QuestRef, Range, RunwayRef,
+    Score, SegmentRef, SpawnSetRef,
+};
 
 /// All quests.
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -21,26 +24,40 @@ pub struct QuestTree {
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Quest {
     /// Unique identifier for the quest.
-    pub id:               QuestRef,
+    pub id:                 QuestRef,
     /// Human-readable title of the quest.
-    pub title:            String,
+    pub title:              String,
     /// Description of the quest.
-    pub description:      String,
+    pub description:        String,
     /// Type of the quest.
-    pub class:            QuestClass,
+    pub class:              QuestClass,
     /// List of quests that must be completed before this quest is displayed.
-    pub dependencies:     Vec<QuestRef>,
+    pub dependencies:       Vec<QuestRef>,
     /// Conditions for completing the quest.
     ///
     /// If a condition has been completed, it is removed from the quest.
     /// If `conditions` is empty, the quest is considered completed.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub conditions:       Vec<QuestCompletionCondition>,
+    pub conditions:         Vec<QuestCompletionCondition>,
+    /// Conditions for failing the quest.
+    ///
+    /// If any failure condition is fulfilled while the quest is active,
+    /// the quest is deactivated and the quests in `on_fail` are activated.
+    /// The quest is retried with all its conditions restored
+    /// after all quests in `on_fail` are completed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failure_conditions: Vec<QuestFailureCondition>,
+    /// Quests to activate when the quest fails, typically remedial tutorials.
+    ///
+    /// A quest referenced here is only activated when a quest referencing it has failed,
+    /// in addition to the requirement of completing its own dependencies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_fail:            Vec<QuestRef>,
     /// UI elements to highlight when the quest is focused.
-    pub ui_highlight:     Vec<HighlightableUiElement>,
+    pub ui_highlight:       Vec<HighlightableUiElement>,
     /// Actions to perform when the quest is completed.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub completion_hooks: Vec<QuestCompletionHook>,
+    pub completion_hooks:   Vec<QuestCompletionHook>,
 }
 
 /// Classifies the quest type.
@@ -98,6 +115,23 @@ pub enum QuestCompletionCondition {
     Statistic(StatisticQuestCompletionCondition),
 }
 
+/// A condition that fails an active quest.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum QuestFailureCondition {
+    /// Fails when any airborne object leaves the altitude range,
+    /// e.g. when the player busts an assigned altitude.
+    ExitAltitude(Range<Position<f32>>),
+    /// Fails when a new conflict occurs while the quest is active.
+    Conflict,
+    /// Fails when an object goes around while the quest is active.
+    ///
+    /// Only go-arounds with the given cause are considered if specified.
+    GoAround(Option<GoAroundCause>),
+    /// Fails when the quest remains active for longer than the given duration.
+    TimeLimit(Duration),
+}
+
 /// Simple tutorial actions for UI interaction.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -151,6 +185,24 @@ pub enum ObjectControlQuestCompletionCondition {
     // Generic
     /// Instructing an object to follow a route.
     FollowRoute,
+
+    // Specific objects
+    /// Instructing a specific object to land on a specific runway.
+    ///
+    /// Completes when the object touches down on the runway in the referenced direction.
+    ObjectLand {
+        /// The object to land.
+        object: ObjectRef,
+        /// The runway to land on.
+        runway: RunwayRef,
+    },
+    /// Instructing a specific object to taxi to a specific taxiway, apron or runway.
+    ObjectTaxiSegment {
+        /// The object to taxi.
+        object:  ObjectRef,
+        /// The segment to reach.
+        segment: SegmentRef,
+    },
 }
 
 /// Statistical achievement conditions.
@@ -163,6 +215,27 @@ pub enum StatisticQuestCompletionCondition {
     MinParking(u32),
     /// Complete a number of departures.
     MinDeparture(u32),
+    /// Complete a number of landings at a specific aerodrome.
+    AerodromeMinLanding {
+        /// The aerodrome to count landings at.
+        aerodrome: AerodromeRef,
+        /// Minimum number of landings.
+        landings:  u32,
+    },
+    /// Complete a number of apron arrivals at a specific aerodrome.
+    AerodromeMinParking {
+        /// The aerodrome to count apron arrivals at.
+        aerodrome: AerodromeRef,
+        /// Minimum number of apron arrivals.
+        parkings:  u32,
+    },
+    /// Complete a number of departures from a specific aerodrome.
+    AerodromeMinDeparture {
+        /// The aerodrome to count departures from.
+        aerodrome:  AerodromeRef,
+        /// Minimum number of departures.
+        departures: u32,
+    },
     /// Achieve at least the given score.
     MinScore(Score),
     /// Completes immediately if the number of conflicts is below or equal to the given number.
@@ -170,6 +243,15 @@ pub enum StatisticQuestCompletionCondition {
     ///
     /// Typically used as a dependent quest after another statistic quest.
     MaxConflicts(u32),
+    /// Completes immediately if the number of go-arounds is below or equal to the given number.
+    /// Never completes if the number of go-arounds exceeds this value.
+    MaxGoArounds {
+        /// Only count go-arounds with this cause, or all go-arounds if `None`.
+        #[serde(default)]
+        cause:      Option<GoAroundCause>,
+        /// Maximum number of go-arounds.
+        go_arounds: u32,
+    },
     /// Minimum play time.
     TimeElapsed(Duration),
 }
@@ -192,6 +274,19 @@ pub enum HighlightableUiElement {
     SetSpeed,
     /// UI for setting heading.
     SetHeading,
+    /// A specific aircraft on the radar view, identified by its name.
+    ///
+    /// The aircraft need not exist when the quest is loaded,
+    /// e.g. it may be spawned by the completion hook of a preceding quest.
+    ///
+    /// Only one aircraft highlight can be used in the same quest.
+    /// Behavior is unspecified if multiple aircraft highlights are used.
+    Aircraft(String),
+    /// A specific waypoint on the radar view.
+    ///
+    /// Only one waypoint highlight can be used in the same quest.
+    /// Behavior is unspecified if multiple waypoint highlights are used.
+    Waypoint(NamedWaypointRef),
 }
 
 /// An action to perform when a quest is completed.
@@ -208,4 +303,26 @@ pub enum QuestCompletionHook {
         /// The waypoint to reveal.
         waypoint: NamedWaypointRef,
     },
+    /// Allows objects to spawn from a [locked](crate::SpawnSet::locked) spawn set.
+    UnlockSpawnSet {
+        /// The spawn set to unlock.
+        set: SpawnSetRef,
+    },
+    /// Multiplies the rate at which new objects spawn.
+    ///
+    /// A [periodic](crate::SpawnTrigger::Periodic) trigger spawns `factor` times as often,
+    /// and an [object count](crate::SpawnTrigger::ObjectCount) trigger maintains
+    /// `factor` times as many objects, rounded to the nearest integer.
+    /// Timetables and disabled triggers are unaffected.
+    ScaleTrafficRate {
+        /// The multiplier to apply. Must be positive.
+        factor: f32,
+    },
+    /// Ends all ongoing closures of a runway.
+    ///
+    /// Closures scheduled to start in the future are unaffected.
+    OpenRunway {
+        /// The runway to open.
+        runway: RunwayRef,
+    },
 }
@@ -64,6 +64,15 @@ impl From<&str> for NamedWaypointRef {
     fn from(value: &str) -> Self { Self(value.into()) }
 }
 
+/// References an airway by name.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AirwayRef(pub String);
+
+impl From<&str> for AirwayRef {
+    fn from(value: &str) -> Self { Self(value.into()) }
+}
+
 /// References an aerodrome by name.
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -83,6 +92,16 @@ pub struct RunwayRef {
     pub runway_name: String,
 }
 
+/// References a [runway configuration](crate::RunwayConfiguration).
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RunwayConfigurationRef {
+    /// Code of the aerodrome for the configuration.
+    pub aerodrome:     AerodromeRef,
+    /// Name of the configuration.
+    pub configuration: String,
+}
+
 macro_rules! newtype_str {
     ($(#[$meta:meta])* $name:ident) => {
         $(#[$meta])*
@@ -116,3 +135,14 @@ newtype_str! {
     /// References a quest by ID.
     QuestRef
 }
+
+newtype_str! {
+    /// References a spawn set by its `id`.
+    SpawnSetRef
+}
+
+newtype_str! {
+    /// References an object declared in [`File::objects`](crate::File::objects)
+    /// by its scenario-assigned `id`, or by its name if no object has such an `id`.
+    ObjectRef
+}
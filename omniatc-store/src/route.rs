@@ -1,7 +1,7 @@
 use math::{Length, Position, Speed};
 use serde::{Deserialize, Serialize};
 
-use crate::{RoutePresetRef, RunwayRef, SegmentRef, WaypointRef};
+use crate::{AirwayRef, NamedWaypointRef, RoutePresetRef, RunwayRef, SegmentRef, WaypointRef};
 
 /// A sequence of highest-level actions to execute,
 /// describing the route to follow.
@@ -33,6 +33,19 @@ pub enum RouteNode {
         /// approximately reaching this altitude by the time the specified waypoint is reached.
         altitude:  Option<Position<f32>>,
     },
+    /// Fly along an airway between two of its waypoints.
+    ///
+    /// Expanded into [`DirectWaypoint`](RouteNode::DirectWaypoint) legs
+    /// to each waypoint of the airway from `from` to `to` inclusive,
+    /// in either direction of the airway.
+    FollowAirway {
+        /// Airway to follow.
+        airway: AirwayRef,
+        /// Waypoint on the airway at which the object joins it.
+        from:   NamedWaypointRef,
+        /// Waypoint on the airway at which the object leaves it.
+        to:     NamedWaypointRef,
+    },
     /// Adjust throttle until the airspeed is reached.
     SetAirSpeed {
         /// Target airspeed.
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// References a script asset by ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScriptRef(pub String);
+
+/// A script asset bundled with the file.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScriptAsset {
+    /// ID used to reference this script from [`Level::scripts`](crate::Level::scripts).
+    pub id:       ScriptRef,
+    /// Language of the script source.
+    pub language: ScriptLanguage,
+    /// Source code of the script.
+    pub source:   String,
+}
+
+/// Language of a script asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ScriptLanguage {
+    /// Lua 5.4.
+    Lua,
+}
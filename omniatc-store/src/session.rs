@@ -0,0 +1,291 @@
+use std::num::NonZero;
+use std::time::Duration;
+
+use bevy_math::Vec2;
+use math::{Length, Position, Speed};
+use serde::{Deserialize, Serialize};
+
+use crate::{AerodromeRef, RunwayRef, Score, WaypointRef, YawTarget};
+
+/// Result of a played session, exported for submission to leaderboards.
+///
+/// A result is verified offline by loading the file identified by `map_hash`,
+/// re-simulating the recorded frames with the recorded seed and player actions,
+/// and comparing the resulting score, event digest and world hash with the claimed values.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionResult {
+    /// ID of the played file, as in [`Meta::id`](crate::Meta::id).
+    pub map_id:       String,
+    /// Hex-encoded content hash of the played file.
+    pub map_hash:     String,
+    /// Seed of the random number generators used in the simulation.
+    pub seed:         u64,
+    /// Elapsed virtual time when the file was loaded.
+    pub start_time:   Duration,
+    /// Score claimed at the end of the session.
+    pub score:        Score,
+    /// Hex-encoded digest of the scored events in the session.
+    pub event_digest: String,
+    /// Hex-encoded hash of the simulation state at the end of the session.
+    pub world_hash:   String,
+    /// Virtual time deltas of all simulated frames since the file was loaded.
+    pub frames:       Vec<FrameRun>,
+    /// Actions taken by the player, in the order they were taken.
+    pub actions:      Vec<SessionAction>,
+    /// Notable events that occurred in the session, in chronological order.
+    #[serde(default)]
+    pub bookmarks:    Vec<Bookmark>,
+    /// Hex-encoded checksum over all other fields.
+    ///
+    /// The checksum is unkeyed and only detects accidental corruption of the result;
+    /// the claimed score is only trusted after re-simulation.
+    #[serde(default)]
+    pub checksum:     String,
+}
+
+/// Consecutive frames with the same virtual time delta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameRun {
+    /// Virtual time elapsed in each frame.
+    pub delta: Duration,
+    /// Number of consecutive frames.
+    pub count: u32,
+}
+
+/// An action taken by the player.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionAction {
+    /// Index of the frame before which the action takes effect.
+    pub frame:  u64,
+    /// Name of the object the action is directed to.
+    pub object: String,
+    /// The action taken.
+    pub kind:   SessionActionKind,
+}
+
+/// Type of a player action.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SessionActionKind {
+    /// Send an instruction to the object.
+    Instruction(InstructionRecord),
+    /// Respond to the pending request of the object.
+    RespondRequest {
+        /// Whether the request is approved.
+        approve: bool,
+    },
+}
+
+/// A notable event in a session, recorded for incident review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    /// Index of the frame during which the event occurred.
+    pub frame:    u64,
+    /// Elapsed virtual time when the event occurred.
+    pub time:     Duration,
+    /// Type of the event.
+    pub kind:     BookmarkKind,
+    /// Names of the objects involved in the event.
+    pub objects:  Vec<String>,
+    /// Horizontal position of the event.
+    pub position: Position<Vec2>,
+}
+
+/// Type of a [`Bookmark`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookmarkKind {
+    /// Two objects started violating separation minima.
+    Conflict,
+    /// An object abandoned its landing approach.
+    GoAround,
+    /// An object entered a runway without clearance.
+    Incursion,
+    /// An object declared an emergency.
+    Emergency,
+}
+
+/// An instruction sent to an object, with entity references replaced by names.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum InstructionRecord {
+    /// Fly towards a yaw target.
+    SetHeading {
+        /// The yaw target.
+        target: YawTarget,
+    },
+    /// Fly direct to a waypoint.
+    SetWaypoint {
+        /// The target waypoint.
+        waypoint: WaypointRef,
+    },
+    /// Change the target airspeed.
+    SetSpeed {
+        /// The target airspeed.
+        target: Speed<f32>,
+    },
+    /// Change the target altitude.
+    SetAltitude {
+        /// The target altitude.
+        altitude: Position<f32>,
+        /// Whether to expedite the altitude change.
+        expedite: bool,
+    },
+    /// Combination of airborne vectors sent in a single transmission.
+    AirborneVector {
+        /// The directional component, if any.
+        direction: Option<AirborneDirection>,
+        /// The target airspeed, if any.
+        speed:     Option<Speed<f32>>,
+        /// The target altitude and whether to expedite, if any.
+        altitude:  Option<(Position<f32>, bool)>,
+    },
+    /// Cancel the current route.
+    ClearRoute,
+    /// Clear a standby node of the route.
+    RemoveStandby {
+        /// Skip ID of the standby node to clear, or `None` for the next one.
+        skip_id: Option<NonZero<u32>>,
+    },
+    /// Follow a route preset.
+    SelectRoute {
+        /// ID of the route preset.
+        preset: String,
+    },
+    /// Append a taxi segment to the route.
+    AppendSegment {
+        /// Whether the existing route is cancelled first.
+        clear_existing: bool,
+        /// The segment to taxi to.
+        segment:        SegmentRecord,
+        /// Where to stop on the segment.
+        stop_mode:      TaxiStopModeRecord,
+    },
+    /// Skip the route until a waypoint.
+    SkipToWaypoint {
+        /// The waypoint to skip to.
+        waypoint: WaypointRef,
+    },
+    /// Divert to another aerodrome.
+    Divert {
+        /// The aerodrome to divert to.
+        aerodrome: AerodromeRef,
+    },
+    /// Grant flight following to a VFR object.
+    GrantFlightFollowing,
+    /// Break up a formation.
+    BreakupFormation,
+    /// Warn about bird activity.
+    BirdCaution,
+    /// Transfer the object to another controller frequency.
+    ContactFrequency {
+        /// The frequency to contact.
+        frequency: FrequencyRecord,
+    },
+    /// Clear the object for the approach in its route.
+    ClearApproach,
+    /// Restrict the altitude at which the object crosses a waypoint of its route.
+    CrossAltitude {
+        /// The restricted waypoint.
+        waypoint: WaypointRef,
+        /// The altitude at which the waypoint must be crossed.
+        altitude: Position<f32>,
+    },
+    /// Set the airspeed until a condition is met, then change to another airspeed.
+    SetSpeedUntil {
+        /// The airspeed to maintain until the condition is met.
+        initial:   Speed<f32>,
+        /// The condition for the speed change.
+        condition: SpeedConditionRecord,
+        /// The airspeed to set when the condition is met.
+        then:      Speed<f32>,
+    },
+    /// Fly a heading temporarily, then resume the route when a condition is met.
+    VectorThenResume {
+        /// The heading to fly.
+        heading:   YawTarget,
+        /// The condition for resuming the route.
+        condition: ResumeConditionRecord,
+        /// The waypoint in the route to proceed direct to when resuming.
+        waypoint:  Option<WaypointRef>,
+    },
+    /// Clear a ground object to cross a runway.
+    CrossRunway {
+        /// The runway to cross.
+        runway: RunwayRef,
+    },
+    /// Recycle a failed transponder.
+    CycleTransponder,
+}
+
+/// Condition of [`InstructionRecord::SetSpeedUntil`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SpeedConditionRecord {
+    /// The object is within a distance of a waypoint.
+    Distance {
+        /// The reference waypoint.
+        waypoint: WaypointRef,
+        /// The distance from the waypoint.
+        distance: Length<f32>,
+    },
+    /// The object passes a waypoint.
+    Waypoint {
+        /// The waypoint to pass.
+        waypoint: WaypointRef,
+    },
+}
+
+/// Condition of [`InstructionRecord::VectorThenResume`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ResumeConditionRecord {
+    /// The object has flown a ground distance.
+    Distance(Length<f32>),
+    /// A waypoint is abeam the object.
+    Abeam(WaypointRef),
+    /// A duration has elapsed.
+    Time(Duration),
+}
+
+/// A controller frequency in [`InstructionRecord::ContactFrequency`].
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum FrequencyRecord {
+    /// The ground frequency.
+    Ground,
+    /// The tower frequency.
+    Tower,
+}
+
+/// Directional component of [`InstructionRecord::AirborneVector`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum AirborneDirection {
+    /// Fly towards a yaw target.
+    Heading(YawTarget),
+    /// Fly direct to a waypoint.
+    Waypoint(WaypointRef),
+}
+
+/// Ground segment referenced by [`InstructionRecord::AppendSegment`].
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SegmentRecord {
+    /// A taxiway by name.
+    Taxiway(String),
+    /// An apron by name.
+    Apron(String),
+    /// A runway, referenced by either direction.
+    Runway(RunwayRef),
+}
+
+/// Where an object stops when taxiing to a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TaxiStopModeRecord {
+    /// Hold before entering the segment.
+    HoldShort,
+    /// Hold immediately after entering the segment.
+    LineUp,
+    /// Hold right before leaving the segment.
+    Exhaust,
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use bevy_math::Vec2;
 use math::{Heading, Length, Position};
 use serde::{Deserialize, Serialize};
@@ -9,7 +11,15 @@ use crate::AxisDirection;
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Ui {
     /// The camera state.
-    pub camera: Camera,
+    pub camera:           Camera,
+    /// Recommended display settings for this map.
+    ///
+    /// These settings are applied on top of the player's own config profile
+    /// without modifying the profile itself.
+    /// Keys are config paths such as `2d:aerodrome.taxiway.label_size`,
+    /// and values are JSON-encoded config values.
+    #[serde(default)]
+    pub display_settings: BTreeMap<String, String>,
 }
 
 /// State of a camera.
@@ -46,24 +46,37 @@ impl<T> WeightedList<T> {
 
     /// Samples a random item from the list according to the weights.
     pub fn sample<'a>(&'a self, rng: &mut impl rand::Rng) -> Option<&'a T> {
-        match self.items[..] {
-            [] => None,
-            [ref item] => Some(&item.item),
-            ref items => {
-                let total_weight: f32 = items.iter().map(|entry| entry.weight).sum();
-                if total_weight <= 0.0 {
-                    return None;
-                }
-                let mut choice = rng.random_range(0.0..total_weight);
-                for entry in items {
-                    if choice < entry.weight {
-                        return Some(&entry.item);
-                    }
-                    choice -= entry.weight;
-                }
-                items.last().map(|entry| &entry.item)
+        self.sample_where(rng, |_| true)
+    }
+
+    /// Samples a random item among those matching `filter` according to the weights.
+    pub fn sample_where<'a>(
+        &'a self,
+        rng: &mut impl rand::Rng,
+        filter: impl Fn(&T) -> bool,
+    ) -> Option<&'a T> {
+        let matching = || self.items.iter().filter(|entry| filter(&entry.item));
+
+        let mut iter = matching();
+        let first = iter.next()?;
+        if iter.next().is_none() {
+            return Some(&first.item);
+        }
+
+        let total_weight: f32 = matching().map(|entry| entry.weight).sum();
+        if total_weight <= 0.0 || !total_weight.is_finite() {
+            return None;
+        }
+        let mut choice = rng.random_range(0.0..total_weight);
+        let mut last = first;
+        for entry in matching() {
+            if choice < entry.weight {
+                return Some(&entry.item);
             }
+            choice -= entry.weight;
+            last = entry;
         }
+        Some(&last.item)
     }
 }
 
@@ -71,7 +71,7 @@ fn load_plane_limits(world: &mut World) -> Result<(taxi::Limits, nav::Limits)> {
     let mut query = world.query::<&object::types::Type>();
     let object_type = query.iter(world).next().context("Expected at least one object type")?;
     match object_type {
-        object::types::Type::Plane { taxi, nav } => Ok((taxi.clone(), nav.clone())),
+        object::types::Type::Plane { taxi, nav, .. } => Ok((taxi.clone(), nav.clone())),
     }
 }
 
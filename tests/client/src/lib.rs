@@ -48,6 +48,7 @@ pub fn start_test(test_name: impl Into<String>, default_scenario: String) -> Res
         headless_test: true,
         open_level_id: None,
         default_scenario,
+        theme: "default".into(),
     };
 
     let mut app = omniatc_client::main_app(options);